@@ -22,6 +22,9 @@ fn get_decode_method(kind: &str) -> String {
     if kind.starts_with("Id") {
         return "id".to_string();
     }
+    if kind == "LiteralString" {
+        return "symbol".to_string();
+    }
 
     let mut kind = kind;
     if kind.starts_with("Literal") {
@@ -41,7 +44,7 @@ pub fn gen_operand_decode_errors(grammar: &Vec<structs::OperandKind>)
 
     { // Comments, attributes, uses.
         ret.push_str(RUSTFMT_SKIP_BANG);
-        ret.push_str("\n\nuse spirv;\nuse std::{error, fmt};\n\n");
+        ret.push_str("\n\nuse spirv;\nuse std::{error, fmt};\n#[cfg(not(feature = \"std\"))]\nuse prelude::*;\n\n");
     }
 
     let kinds: Vec<&str> = grammar.iter().filter(|element| {
@@ -197,13 +200,13 @@ fn gen_operand_param_parse_methods(grammar: &Vec<structs::OperandKind>)
             let cases: Vec<String> = pairs.into_iter().map(|(symbol, params)| {
                 let params: Vec<String> = params.iter().map(|element| {
                     format!("mr::Operand::{kind}(\
-                             try_decode!(self.decoder.{decode}()))",
+                             try_decode!(self, self.decoder.{decode}()))",
                             kind = get_mr_operand_kind(element),
                             decode = get_decode_method(element))
                 }).collect();
                 format!(
                     "{s:8}if {arg}.contains(spirv::{kind}::{bit}) {{\n\
-                         {s:12}params.append(&mut vec![{params}]);\n\
+                         {s:12}params.extend([{params}]);\n\
                      {s:8}}}",
                     s = "",
                     arg = lo_kind,
@@ -213,8 +216,8 @@ fn gen_operand_param_parse_methods(grammar: &Vec<structs::OperandKind>)
             }).collect();
             format!(
                 "{s:4}fn parse_{k}_arguments(&mut self, {k}: \
-                     spirv::{kind}) -> Result<Vec<mr::Operand>> {{\n\
-                     {s:8}let mut params = vec![];\n\
+                     spirv::{kind}) -> Result<mr::Operands> {{\n\
+                     {s:8}let mut params = mr::Operands::new();\n\
                      {cases}\n\
                      {s:8}Ok(params)\n\
                  {s:4}}}",
@@ -226,12 +229,12 @@ fn gen_operand_param_parse_methods(grammar: &Vec<structs::OperandKind>)
             let cases: Vec<String> = pairs.into_iter().map(|(symbol, params)| {
                 let params: Vec<String> = params.iter().map(|element| {
                     format!("mr::Operand::{kind}(\
-                             try_decode!(self.decoder.{decode}()))",
+                             try_decode!(self, self.decoder.{decode}()))",
                             kind = get_mr_operand_kind(element),
                             decode = get_decode_method(element))
                 }).collect();
                 format!(
-                    "{s:12}spirv::{kind}::{symbol} => vec![{params}],",
+                    "{s:12}spirv::{kind}::{symbol} => mr::Operands::from([{params}]),",
                     s = "",
                     kind = kind,
                     symbol = symbol,
@@ -239,10 +242,10 @@ fn gen_operand_param_parse_methods(grammar: &Vec<structs::OperandKind>)
             }).collect();
             format!(
                 "{s:4}fn parse_{k}_arguments(&mut self, {k}: spirv::{kind})\
-                     {s:1}-> Result<Vec<mr::Operand>> {{\n\
+                     {s:1}-> Result<mr::Operands> {{\n\
                      {s:8}Ok(match {k} {{\n\
                         {cases}\n\
-                        {s:12}_ => vec![]\n\
+                        {s:12}_ => mr::Operands::new()\n\
                      {s:8}}})\n\
                  {s:4}}}",
                 s = "",
@@ -265,9 +268,9 @@ pub fn gen_operand_parse_methods(grammar: &Vec<structs::OperandKind>) -> String
         further_parse_kinds.iter().map(|kind| {
             format!(
                 "{s:12}GOpKind::{kind} => {{\n\
-                 {s:16}let val = try_decode!(self.decoder.{decode}());\n\
-                 {s:16}let mut ops = vec![mr::Operand::{kind}(val)];\n\
-                 {s:16}ops.append(&mut self.parse_{k}_arguments(val)?);\n\
+                 {s:16}let val = try_decode!(self, self.decoder.{decode}());\n\
+                 {s:16}let mut ops = mr::Operands::from([mr::Operand::{kind}(val)]);\n\
+                 {s:16}ops.extend(self.parse_{k}_arguments(val)?);\n\
                  {s:16}ops\n\
                  {s:12}}}",
                 s = "",
@@ -286,10 +289,10 @@ pub fn gen_operand_parse_methods(grammar: &Vec<structs::OperandKind>) -> String
     ];
     let pair_cases: Vec<String> = pair_kinds.iter().map(|&(k0, k1)| {
         format!("{s:12}GOpKind::{kind} => {{\n\
-                 {s:16}vec![\
-                 mr::Operand::{k0}(try_decode!(self.decoder.{m0}())), \
-                 mr::Operand::{k1}(try_decode!(self.decoder.{m1}()))\
-                 ]\n{s:12}}}",
+                 {s:16}mr::Operands::from([\
+                 mr::Operand::{k0}(try_decode!(self, self.decoder.{m0}())), \
+                 mr::Operand::{k1}(try_decode!(self, self.decoder.{m1}()))\
+                 ])\n{s:12}}}",
                 s = "",
                 kind = format!("Pair{}{}", k0, k1),
                 k0 = get_mr_operand_kind(k0),
@@ -313,8 +316,8 @@ pub fn gen_operand_parse_methods(grammar: &Vec<structs::OperandKind>) -> String
             }
     }).map(|kind| {
         format!(
-            "{s:12}GOpKind::{gkind} => vec![mr::Operand::{mkind}\
-             (try_decode!(self.decoder.{decode}()))],",
+            "{s:12}GOpKind::{gkind} => mr::Operands::from([mr::Operand::{mkind}\
+             (try_decode!(self, self.decoder.{decode}()))]),",
              s = "",
              gkind = kind,
              mkind = get_mr_operand_kind(kind),
@@ -331,7 +334,7 @@ pub fn gen_operand_parse_methods(grammar: &Vec<structs::OperandKind>) -> String
     format!(
         "impl<'c, 'd> Parser<'c, 'd> {{\n\
          {s:4}fn parse_operand(&mut self, kind: GOpKind) \
-             -> Result<Vec<mr::Operand>> {{\n\
+             -> Result<mr::Operands> {{\n\
              {s:8}Ok(match kind {{\n\
                  {normal_cases}\n\
                  {pair_cases}\n\
@@ -102,14 +102,27 @@ fn main() {
     }
     let cl_grammar: structs::ExtInstSetGrammar = serde_json::from_str(&contents).unwrap();
 
+    // For NonSemantic.Shader.DebugInfo.100 extended instruction set.
+    {
+        let path = codegen_src_dir.join(
+            "external/extinst.nonsemantic.shader.debuginfo.100.grammar.json");
+        let filename = path.to_str().unwrap();
+        let mut file = fs::File::open(filename).unwrap();
+        contents.clear();
+        file.read_to_string(&mut contents).unwrap();
+    }
+    let debug_info_grammar: structs::ExtInstSetGrammar =
+        serde_json::from_str(&contents).unwrap();
+
     {
         // Path to the generated SPIR-V header file.
         let path = codegen_src_dir.join("../spirv/spirv.rs");
         let core = header::gen_spirv_header(&grammar);
         let gl = header::gen_glsl_std_450_opcodes(&gl_grammar);
         let cl = header::gen_opencl_std_opcodes(&cl_grammar);
+        let debug_info = header::gen_nonsemantic_shader_debuginfo_100(&debug_info_grammar);
 
-        write!(core + "\n" + &gl + "\n" + &cl, path);
+        write!(core + "\n" + &gl + "\n" + &cl + "\n" + &debug_info, path);
 
     }
 
@@ -224,4 +237,10 @@ fn main() {
         let c = table::gen_opencl_std_100_inst_table(&cl_grammar);
         write!(c, path);
     }
+
+    {
+        let path = codegen_src_dir.join("../rspirv/grammar/nonsemantic_shader_debuginfo_100.rs");
+        let c = table::gen_nonsemantic_shader_debuginfo_100_inst_table(&debug_info_grammar);
+        write!(c, path);
+    }
 }
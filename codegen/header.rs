@@ -25,6 +25,9 @@ https://www.khronos.org/registry/spir-v/specs/unified1/GLSL.std.450.html";
 static OPENCL_STD_SPEC_LINK: &'static str = "\
 https://www.khronos.org/registry/spir-v/specs/unified1/OpenCL.ExtendedInstructionSet.100.html";
 
+static NONSEMANTIC_SHADER_DEBUGINFO_100_SPEC_LINK: &'static str = "\
+https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html";
+
 /// Returns the markdown string containing a link to the spec for the given
 /// operand `kind`.
 fn get_spec_link(kind: &str) -> String {
@@ -40,7 +43,7 @@ fn get_spec_link(kind: &str) -> String {
                            symbol, symbol))
 }
 
-fn gen_bit_enum_operand_kind(grammar: &structs::OperandKind) -> String {
+fn gen_bit_enum_operand_kind(grammar: &structs::OperandKind, link: &str) -> String {
     let elements: Vec<String> = grammar.enumerants.iter().map(|enumerant| {
         // Special treatment for "NaN"
         let mut symbol = snake_casify(&enumerant.symbol);
@@ -53,13 +56,12 @@ fn gen_bit_enum_operand_kind(grammar: &structs::OperandKind) -> String {
     }).collect();
     format!("bitflags!{{\n    {doc}\n    pub struct {kind} : u32 \
              {{\n{enumerants}\n    }}\n}}\n",
-            doc = format!("/// SPIR-V operand kind: {}",
-                          get_spec_link(&grammar.kind)),
+            doc = format!("/// SPIR-V operand kind: {}", link),
             kind = grammar.kind,
             enumerants = elements.join("\n"))
 }
 
-fn gen_value_enum_operand_kind(grammar: &structs::OperandKind) -> String {
+fn gen_value_enum_operand_kind(grammar: &structs::OperandKind, link: &str) -> String {
     use std::collections::BTreeMap;
 
     // We can have more than one enumerants mapping to the same discriminator.
@@ -92,24 +94,197 @@ fn gen_value_enum_operand_kind(grammar: &structs::OperandKind) -> String {
     }
 
     format!("{doc}\n{attribute}\npub enum {kind} {{\n{enumerants}\n}}\n{aliases}",
-            doc = format!("/// SPIR-V operand kind: {}",
-                          get_spec_link(&grammar.kind)),
+            doc = format!("/// SPIR-V operand kind: {}", link),
             attribute = VAULE_ENUM_ATTRIBUTE,
             kind = grammar.kind,
             aliases = associated_consts,
             enumerants = enumerants.join("\n"))
 }
 
+/// Returns the code implementing `Display` and `FromStr` for the bit enum
+/// of the given operand kind, using the enumerant names in the grammar.
+fn gen_bit_enum_conversions(grammar: &structs::OperandKind) -> String {
+    let flag = |symbol: &str| {
+        let mut symbol = snake_casify(symbol).to_uppercase();
+        if &symbol == "NOT_NA_N" {
+            symbol = "NOT_NAN".to_string()
+        }
+        format!("{}::{}", grammar.kind, symbol)
+    };
+    let displays: Vec<String> = grammar.enumerants.iter().filter(|enumerant| {
+        enumerant.value.string != "0x0000"
+    }).map(|enumerant| {
+        format!("            (\"{}\", self.contains({})),",
+                enumerant.symbol, flag(&enumerant.symbol))
+    }).collect();
+    let parses: Vec<String> = grammar.enumerants.iter().map(|enumerant| {
+        format!("                \"{}\" => {},", enumerant.symbol, flag(&enumerant.symbol))
+    }).collect();
+    format!("impl fmt::Display for {kind} {{\n\
+             {s:4}fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{\n\
+             {s:8}write_flags(f, &[\n{displays}\n{s:8}])\n\
+             {s:4}}}\n\
+             }}\n\n\
+             impl str::FromStr for {kind} {{\n\
+             {s:4}type Err = ParseEnumError;\n\n\
+             {s:4}fn from_str(s: &str) -> Result<Self, Self::Err> {{\n\
+             {s:8}let mut flags = {kind}::empty();\n\
+             {s:8}for name in s.split('|') {{\n\
+             {s:12}flags |= match name.trim() {{\n{parses}\n\
+             {s:16}_ => return Err(ParseEnumError),\n\
+             {s:12}}};\n\
+             {s:8}}}\n\
+             {s:8}Ok(flags)\n\
+             {s:4}}}\n\
+             }}\n",
+            s = "",
+            kind = grammar.kind,
+            displays = displays.join("\n"),
+            parses = parses.join("\n"))
+}
+
+/// Returns the code implementing `Display` and `FromStr` for the value enum
+/// of the given operand kind, using the enumerant names in the grammar.
+fn gen_value_enum_conversions(grammar: &structs::OperandKind) -> String {
+    use std::collections::BTreeSet;
+
+    let mut seen_discriminator = BTreeSet::new();
+    let mut displays = vec![];
+    let mut parses = vec![];
+    for e in &grammar.enumerants {
+        // Aliases are associated constants with the symbol as their name.
+        let mut variant = e.symbol.clone();
+        if seen_discriminator.insert(e.value.number) {
+            if grammar.kind == "Dim" {
+                variant = format!("Dim{}", e.symbol);
+            }
+            displays.push(format!("            {}::{} => \"{}\",",
+                                  grammar.kind, variant, e.symbol));
+        }
+        parses.push(format!("            \"{}\" => Ok({}::{}),",
+                            e.symbol, grammar.kind, variant));
+    }
+    format!("impl fmt::Display for {kind} {{\n\
+             {s:4}fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{\n\
+             {s:8}f.write_str(match *self {{\n{displays}\n{s:8}}})\n\
+             {s:4}}}\n\
+             }}\n\n\
+             impl str::FromStr for {kind} {{\n\
+             {s:4}type Err = ParseEnumError;\n\n\
+             {s:4}fn from_str(s: &str) -> Result<Self, Self::Err> {{\n\
+             {s:8}match s {{\n{parses}\n\
+             {s:12}_ => Err(ParseEnumError),\n\
+             {s:8}}}\n\
+             {s:4}}}\n\
+             }}\n",
+            s = "",
+            kind = grammar.kind,
+            displays = displays.join("\n"),
+            parses = parses.join("\n"))
+}
+
+/// The requirements for using an opcode or enumerant, as given in the grammar.
+struct Requirements<'a> {
+    /// The name of the enum variant.
+    variant: String,
+    capabilities: &'a [String],
+    extensions: &'a [String],
+    /// The version in which it became core, "None" if never, or empty if
+    /// it is in all versions.
+    version: &'a str,
+}
+
+/// Returns the code defining methods on the enum `kind` reporting the
+/// capabilities, extensions, and SPIR-V version required by its variants.
+fn gen_requirements(kind: &str, what: &str, entries: &[Requirements]) -> String {
+    // Generates a match with the given arms, falling back to the default.
+    let gen_match = |arms: Vec<String>, default: &str| {
+        if arms.is_empty() {
+            format!("{s:8}{}", default, s = "")
+        } else if arms.len() == entries.len() {
+            format!("{s:8}match self {{\n{}\n{s:8}}}", arms.join("\n"), s = "")
+        } else {
+            format!("{s:8}match self {{\n{}\n{s:12}_ => {},\n{s:8}}}",
+                    arms.join("\n"), default, s = "")
+        }
+    };
+    let capabilities = entries.iter().filter(|e| !e.capabilities.is_empty()).map(|e| {
+        let caps: Vec<String> = e.capabilities.iter().map(|c| {
+            format!("Capability::{}", c)
+        }).collect();
+        format!("{s:12}{}::{} => &[{}],", kind, e.variant, caps.join(", "), s = "")
+    }).collect();
+    let extensions = entries.iter().filter(|e| !e.extensions.is_empty()).map(|e| {
+        let exts: Vec<String> = e.extensions.iter().map(|x| format!("\"{}\"", x)).collect();
+        format!("{s:12}{}::{} => &[{}],", kind, e.variant, exts.join(", "), s = "")
+    }).collect();
+    let versions = entries.iter().filter(|e| {
+        !e.version.is_empty() && e.version != "1.0"
+    }).map(|e| {
+        let version = if e.version == "None" {
+            "None".to_string()
+        } else {
+            format!("Some(({}))", e.version.replace(".", ", "))
+        };
+        format!("{s:12}{}::{} => {},", kind, e.variant, version, s = "")
+    }).collect();
+    format!("impl {kind} {{\n\
+             {s:4}/// Returns the capabilities enabling this {what}; any one of them is\n\
+             {s:4}/// sufficient. The {what} is always available if there are none.\n\
+             {s:4}pub fn required_capabilities(self) -> &'static [Capability] {{\n\
+             {capabilities}\n\
+             {s:4}}}\n\n\
+             {s:4}/// Returns the extensions enabling this {what} in SPIR-V versions before\n\
+             {s:4}/// `required_version`; any one of them is sufficient.\n\
+             {s:4}pub fn required_extensions(self) -> &'static [&'static str] {{\n\
+             {extensions}\n\
+             {s:4}}}\n\n\
+             {s:4}/// Returns the SPIR-V version, as (major, minor), since which this {what}\n\
+             {s:4}/// is available without extensions, or `None` if it is only available\n\
+             {s:4}/// through extensions.\n\
+             {s:4}pub fn required_version(self) -> Option<(u8, u8)> {{\n\
+             {versions}\n\
+             {s:4}}}\n\
+             }}\n",
+            s = "",
+            kind = kind,
+            what = what,
+            capabilities = gen_match(capabilities, "&[]"),
+            extensions = gen_match(extensions, "&[]"),
+            versions = gen_match(versions, "Some((1, 0))"))
+}
+
 /// Returns the code defining the enum for an operand kind by parsing
-/// the given SPIR-V `grammar`.
+/// the given SPIR-V `grammar`, together with its conversions from and to
+/// strings.
 fn gen_operand_kind(grammar: &structs::OperandKind) -> Option<String> {
-    if grammar.category == "BitEnum" {
-        Some(gen_bit_enum_operand_kind(grammar))
+    use std::collections::BTreeSet;
+
+    let link = get_spec_link(&grammar.kind);
+    let (definition, conversions) = if grammar.category == "BitEnum" {
+        (gen_bit_enum_operand_kind(grammar, &link), gen_bit_enum_conversions(grammar))
     } else if grammar.category == "ValueEnum" {
-        Some(gen_value_enum_operand_kind(grammar))
+        // Aliases share the requirements of the enumerant they alias.
+        let mut seen_discriminator = BTreeSet::new();
+        let entries: Vec<Requirements> = grammar.enumerants.iter().filter(|e| {
+            seen_discriminator.insert(e.value.number)
+        }).map(|e| Requirements {
+            variant: if grammar.kind == "Dim" {
+                format!("Dim{}", e.symbol)
+            } else {
+                e.symbol.clone()
+            },
+            capabilities: &e.capabilities,
+            extensions: &e.extensions,
+            version: &e.version,
+        }).collect();
+        (gen_value_enum_operand_kind(grammar, &link),
+         gen_value_enum_conversions(grammar) + "\n" +
+             &gen_requirements(&grammar.kind, "enumerant", &entries))
     } else {
-        None
-    }
+        return None;
+    };
+    Some(format!("{}\n\n{}", definition.trim_end(), conversions))
 }
 
 /// Returns the generated SPIR-V header.
@@ -150,6 +325,14 @@ pub fn gen_spirv_header(grammar: &structs::Grammar) -> String {
                               link = get_spec_link("instructions"),
                               attribute = VAULE_ENUM_ATTRIBUTE,
                               opcodes = opcodes.join("\n")));
+        let entries: Vec<Requirements> = grammar.instructions.iter().map(|inst| Requirements {
+            variant: inst.opname[2..].to_string(),
+            capabilities: &inst.capabilities,
+            extensions: &inst.extensions,
+            version: &inst.version,
+        }).collect();
+        ret.push('\n');
+        ret.push_str(&gen_requirements("Op", "instruction", &entries));
     }
 
     ret
@@ -196,3 +379,38 @@ pub fn gen_opencl_std_opcodes(grammar: &structs::ExtInstSetGrammar) -> String {
 
     ret
 }
+
+/// Returns the NonSemantic.Shader.DebugInfo.100 extended instruction opcodes
+/// and operand kinds.
+///
+/// All operands of these instructions are ids; the operand kinds are the
+/// values of the constants these ids refer to.
+pub fn gen_nonsemantic_shader_debuginfo_100(grammar: &structs::ExtInstSetGrammar) -> String {
+    let mut ret = String::new();
+
+    { // Opcodes.
+        let opcodes: Vec<String> = grammar.instructions.iter().map(|inst| {
+            format!("    {} = {},", inst.opname, inst.opcode)
+        }).collect();
+        ret.push_str(&format!("/// [NonSemantic.Shader.DebugInfo.100]({link}) extended instruction \
+                               opcode\n\
+                               {attribute}\n\
+                               pub enum DebugInfoOp {{\n{opcodes}\n}}\n",
+                              link = NONSEMANTIC_SHADER_DEBUGINFO_100_SPEC_LINK,
+                              attribute = VAULE_ENUM_ATTRIBUTE,
+                              opcodes = opcodes.join("\n")));
+    }
+    { // Operand kinds.
+        for kind in &grammar.operand_kinds {
+            let link = format!("[{}]({})", kind.kind, NONSEMANTIC_SHADER_DEBUGINFO_100_SPEC_LINK);
+            let (definition, conversions) = if kind.category == "BitEnum" {
+                (gen_bit_enum_operand_kind(kind, &link), gen_bit_enum_conversions(kind))
+            } else {
+                (gen_value_enum_operand_kind(kind, &link), gen_value_enum_conversions(kind))
+            };
+            ret.push_str(&format!("\n{}\n\n{}", definition.trim_end(), conversions));
+        }
+    }
+
+    ret
+}
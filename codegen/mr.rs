@@ -129,7 +129,8 @@ fn get_push_extras(params: &[structs::Operand],
                     container = container))
         } else {
             // TODO: Ouch! Bad smell. This has special case treatment yet
-            // still doesn't solve 64-bit selectors in OpSwitch.
+            // only handles 32-bit selectors in OpSwitch; 64-bit selectors
+            // go through the hand-written Builder::switch_u64.
             if param.kind == "PairLiteralIntegerIdRef" {
                 Some(format!(
                         "{s:8}for v in {name}.as_ref() {{\n\
@@ -211,11 +212,12 @@ pub fn gen_mr_operand_kinds(grammar: &Vec<structs::OperandKind>) -> String {
             "    LiteralFloat32(f32),",
             "    LiteralFloat64(f64),",
             "    LiteralExtInstInteger(u32),",
+            "    ExtInstOpcode(&'static grammar::ExtendedInstruction<'static>),",
             "    LiteralSpecConstantOpInteger(spirv::Op),"];
         let str_kinds: Vec<String> = kinds.iter().filter(|element| {
             element.ends_with("String")
         }).map(|element| {
-            format!("    {}(String),", element)
+            format!("    {}(Symbol),", element)
         }).collect();
         let enum_kinds: Vec<String> = kinds.iter().filter(|element| {
             !(element.starts_with("Id") ||
@@ -243,7 +245,7 @@ pub fn gen_mr_operand_kinds(grammar: &Vec<structs::OperandKind>) -> String {
         let mut kinds = kinds;
         kinds.append(&mut vec!["LiteralInt32", "LiteralInt64",
                                "LiteralFloat32", "LiteralFloat64"]);
-        let cases: Vec<String> =
+        let mut cases: Vec<String> =
             kinds.iter().map(|element| {
                 if element == &"Dim" {
                     // Skip the "Dim" prefix, which is only used in the API to
@@ -259,6 +261,14 @@ pub fn gen_mr_operand_kinds(grammar: &Vec<structs::OperandKind>) -> String {
                             kind = element)
                 }
             }).collect();
+        // Decoded extended instruction opcodes are shown by their opnames.
+        let ext_inst = cases.iter().position(|case| {
+            case.contains("Operand::LiteralExtInstInteger(")
+        }).unwrap();
+        cases.insert(ext_inst + 1,
+                     format!("{s:12}Operand::ExtInstOpcode(v) => \
+                              write!(f, \"{{}}\", v.opname),",
+                             s = ""));
         let impl_code = format!(
             "impl fmt::Display for Operand {{\n\
              {s:4}fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{\n\
@@ -294,8 +304,8 @@ pub fn gen_mr_builder_types(grammar: &structs::Grammar) -> String {
                  {s:4}pub fn {name}{generic}(&mut self{sep}{param}) -> spirv::Word {{\n\
                  {s:8}let id = self.id();\n\
                  {s:8}self.module.types_global_values.push(\
-                     mr::Instruction::new(spirv::Op::{opcode}, \
-                     None, Some(id), vec![{init}]));\n\
+                     mr::Instruction::with_operands(spirv::Op::{opcode}, \
+                     None, Some(id), mr::Operands::from([{init}])));\n\
                  {extras}{x}\
                  {s:8}id\n\
                  {s:4}}}",
@@ -322,8 +332,8 @@ pub fn gen_mr_builder_terminator(grammar: &structs::Grammar) -> String {
         let extras = get_push_extras(&inst.operands, kinds, "inst.operands").join(";\n");
         format!("{s:4}/// Appends an Op{opcode} instruction and ends the current basic block.\n\
                  {s:4}pub fn {name}{generic}(&mut self{x}{params}) -> BuildResult<()> {{\n\
-                 {s:8}let {m}inst = mr::Instruction::new(\
-                     spirv::Op::{opcode}, None, None, vec![{init}]);\n\
+                 {s:8}let {m}inst = mr::Instruction::with_operands(\
+                     spirv::Op::{opcode}, None, None, mr::Operands::from([{init}]));\n\
                  {extras}{y}\
                  {s:8}self.end_basic_block(inst)\n\
                  {s:4}}}",
@@ -362,9 +372,14 @@ pub fn gen_mr_builder_normal_insts(grammar: &structs::Grammar) -> String {
                      {s:12}Some(v) => v,\n\
                      {s:12}None => self.id(),\n\
                      {s:8}}};\n\
-                     {s:8}let {m}inst = mr::Instruction::new(\
-                         spirv::Op::{opcode}, Some(result_type), Some(_id), vec![{init}]);\n\
+                     {s:8}let {m}inst = mr::Instruction::with_operands(\
+                         spirv::Op::{opcode}, Some(result_type), Some(_id), mr::Operands::from([{init}]));\n\
                      {extras}{y}\
+                     {s:8}if result_id.is_none() {{\n\
+                     {s:12}if let Some(id) = self.fold(&inst) {{\n\
+                     {s:16}return Ok(id);\n\
+                     {s:12}}}\n\
+                     {s:8}}}\n\
                      {s:8}self.basic_block.as_mut().unwrap().instructions.push(inst);\n\
                      {s:8}Ok(_id)\n\
                      {s:4}}}",
@@ -384,8 +399,8 @@ pub fn gen_mr_builder_normal_insts(grammar: &structs::Grammar) -> String {
                      {s:8}if self.basic_block.is_none() {{\n\
                      {s:12}return Err(Error::DetachedInstruction);\n\
                      {s:8}}}\n\
-                     {s:8}let {m}inst = mr::Instruction::new(\
-                         spirv::Op::{opcode}, None, None, vec![{init}]);\n\
+                     {s:8}let {m}inst = mr::Instruction::with_operands(\
+                         spirv::Op::{opcode}, None, None, mr::Operands::from([{init}]));\n\
                      {extras}{y}\
                      {s:8}Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))\n\
                      {s:4}}}",
@@ -408,15 +423,16 @@ pub fn gen_mr_builder_constants(grammar: &structs::Grammar) -> String {
     let kinds = &grammar.operand_kinds;
     // Generate build methods for all constants.
     let elements: Vec<String> = grammar.instructions.iter().filter(|inst| {
-        inst.class == "Constant" && inst.opname != "OpConstant" && inst.opname != "OpSpecConstant"
+        inst.class == "Constant" && inst.opname != "OpConstant" && inst.opname != "OpSpecConstant" &&
+            inst.opname != "OpSpecConstantOp"
     }).map(|inst| {
         let (params, type_generics) = get_param_list(&inst.operands, false, kinds);
         let extras = get_push_extras(&inst.operands, kinds, "inst.operands").join(";\n");
         format!("{s:4}/// Appends an Op{opcode} instruction.\n\
                  {s:4}pub fn {name}{generic}(&mut self{x}{params}) -> spirv::Word {{\n\
                  {s:8}let id = self.id();\n\
-                 {s:8}let {m}inst = mr::Instruction::new(\
-                     spirv::Op::{opcode}, Some(result_type), Some(id), vec![{init}]);\n\
+                 {s:8}let {m}inst = mr::Instruction::with_operands(\
+                     spirv::Op::{opcode}, Some(result_type), Some(id), mr::Operands::from([{init}]));\n\
                  {extras}{y}\
                  {s:8}self.module.types_global_values.push(inst);\n\
                  {s:8}id\n\
@@ -445,9 +461,9 @@ pub fn gen_mr_builder_debug(grammar: &structs::Grammar) -> String {
         assert!(type_generics.is_empty());
         let extras = get_push_extras(&inst.operands, kinds, "inst.operands").join(";\n");
         format!("{s:4}/// Appends an Op{opcode} instruction.\n\
-                 {s:4}pub fn {name}<T: Into<String>>(&mut self{x}{params}) {{\n\
-                 {s:8}let {m}inst = mr::Instruction::new(\
-                     spirv::Op::{opcode}, None, None, vec![{init}]);\n\
+                 {s:4}pub fn {name}<T: Into<mr::Symbol>>(&mut self{x}{params}) {{\n\
+                 {s:8}let {m}inst = mr::Instruction::with_operands(\
+                     spirv::Op::{opcode}, None, None, mr::Operands::from([{init}]));\n\
                  {extras}{y}\
                  {s:8}self.module.debugs.push(inst);\n\
                  {s:4}}}",
@@ -474,8 +490,8 @@ pub fn gen_mr_builder_annotation(grammar: &structs::Grammar) -> String {
         let extras = get_push_extras(&inst.operands, kinds, "inst.operands").join(";\n");
         format!("{s:4}/// Appends an Op{opcode} instruction.\n\
                  {s:4}pub fn {name}{generic}(&mut self{x}{params}) {{\n\
-                 {s:8}let {m}inst = mr::Instruction::new(\
-                     spirv::Op::{opcode}, None, None, vec![{init}]);\n\
+                 {s:8}let {m}inst = mr::Instruction::with_operands(\
+                     spirv::Op::{opcode}, None, None, mr::Operands::from([{init}]));\n\
                  {extras}{y}\
                  {s:8}self.module.annotations.push(inst);\n\
                  {s:4}}}",
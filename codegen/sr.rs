@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use regex;
 use structs;
 use utils::*;
 
@@ -32,7 +31,7 @@ pub fn get_operand_type_sr_tokens(kind: &str) -> TokenStream {
     } else if kind == "LiteralString" {
         quote! { String }
     } else if kind == "PairLiteralIntegerIdRef" {
-        quote! { (u32, spirv::Word) }
+        quote! { (u64, spirv::Word) }
     } else if kind == "PairIdRefLiteralInteger" {
         quote! { (spirv::Word, u32) }
     } else if kind == "PairIdRefIdRef" {
@@ -55,9 +54,7 @@ pub fn get_operand_name_sr_tokens(param: &structs::Operand) -> TokenStream {
             quote! { #token }
         }
     } else {
-        let re = regex::Regex::new(r"\W").unwrap();
-        let name = snake_casify(&re.replace_all(&param.name.replace(" ", "_"), ""));
-        let token = Ident::new(&name, Span::call_site());
+        let token = Ident::new(&get_param_name(param), Span::call_site());
         quote! { #token }
     }
 }
@@ -103,10 +100,9 @@ pub fn gen_sr_decoration(grammar: &structs::Grammar) -> String {
 
 pub fn get_operand_type_ident(grammar: &structs::Operand) -> TokenStream {
     let ty = if grammar.kind == "IdRef" {
-        if grammar.name == "'Length'" {
-            quote! { ConstantToken }
-        } else {
-            quote! { TypeToken }
+        match grammar.name.as_str() {
+            "'Length'" | "'Rows'" | "'Columns'" | "'Use'" => quote! { ConstantToken },
+            _ => quote! { TypeToken },
         }
     } else {
         get_operand_type_sr_tokens(&grammar.kind)
@@ -275,51 +271,203 @@ pub fn gen_sr_type_creation(grammar: &structs::Grammar) -> String {
     tokens.to_string()
 }
 
+/// Returns true if the given operand of a terminator instruction refers to
+/// the label of a basic block.
+fn is_label_operand(operand: &structs::Operand) -> bool {
+    operand.kind == "IdRef" && (operand.name.ends_with("Label'") || operand.name == "'Default'")
+}
+
+/// Returns the corresponding Rust type used in structured representation
+/// for the given operand of an instruction inside a function.
+///
+/// Ids are resolved into the values they refer to so that instructions form
+/// a graph without looking up ids.
+fn get_instruction_operand_type_sr_tokens(operand: &structs::Operand) -> TokenStream {
+    if operand.kind.starts_with("Id") {
+        quote! { Value }
+    } else if operand.kind == "PairIdRefLiteralInteger" {
+        quote! { (Value, u32) }
+    } else if operand.kind == "PairIdRefIdRef" {
+        quote! { (Value, Value) }
+    } else {
+        get_operand_type_sr_tokens(&operand.kind)
+    }
+}
+
+/// Returns the corresponding Rust type used in structured representation
+/// for the given operand of a terminator instruction.
+///
+/// Branch targets are represented as basic block tokens so that the control
+/// flow graph can be navigated without looking up ids.
+fn get_terminator_operand_type_sr_tokens(operand: &structs::Operand) -> TokenStream {
+    if is_label_operand(operand) {
+        quote! { Token<BasicBlock> }
+    } else if operand.kind == "PairLiteralIntegerIdRef" {
+        quote! { (u64, Token<BasicBlock>) }
+    } else {
+        get_instruction_operand_type_sr_tokens(operand)
+    }
+}
+
+/// Returns the token stream reading the given operand with the
+/// `OperandReader` named `r` when lifting an instruction.
+fn get_operand_reader_sr_tokens(operand: &structs::Operand, terminator: bool) -> TokenStream {
+    let kind = operand.kind.as_str();
+    if terminator && is_label_operand(operand) {
+        return quote! { r.block()? };
+    }
+    match kind {
+        "PairLiteralIntegerIdRef" => return quote! { r.literal_block_pairs()? },
+        "PairIdRefLiteralInteger" => return quote! { r.value_literal_pairs()? },
+        "PairIdRefIdRef" => return quote! { r.value_pairs()? },
+        _ => {}
+    }
+    if kind.starts_with("Id") {
+        return match operand.quantifier.as_str() {
+            "" => quote! { r.value()? },
+            "?" => quote! { r.optional_value()? },
+            _ => quote! { r.values()? },
+        };
+    }
+    let extract = match kind {
+        "LiteralInteger" => quote! { literal },
+        "LiteralExtInstInteger" => quote! { ext_inst_integer },
+        "LiteralString" => quote! { string },
+        _ => {
+            let kind = Ident::new(kind, Span::call_site());
+            quote! { kind!(#kind) }
+        }
+    };
+    match operand.quantifier.as_str() {
+        "" => quote! { r.required(#extract)? },
+        "?" => quote! { r.optional(#extract)? },
+        _ => quote! { r.repeated(#extract)? },
+    }
+}
+
+/// Returns the token stream for the `lift` method of the enum `name`
+/// holding the given instructions.
+fn gen_sr_lift(name: &str, insts: &[&structs::Instruction], terminator: bool) -> TokenStream {
+    let name = Ident::new(name, Span::call_site());
+    let arms: Vec<_> = insts
+        .iter()
+        .map(|inst| {
+            let symbol = Ident::new(&inst.opname[2..], Span::call_site());
+            let fields: Vec<_> = inst.operands
+                .iter()
+                .filter(|operand| !operand.kind.starts_with("IdResult"))
+                .map(|operand| {
+                    let field_name = get_operand_name_sr_tokens(operand);
+                    let read = get_operand_reader_sr_tokens(operand, terminator);
+                    quote! { #field_name: #read }
+                })
+                .collect();
+            if fields.is_empty() {
+                quote! { spirv::Op::#symbol => #name::#symbol }
+            } else {
+                quote! { spirv::Op::#symbol => #name::#symbol { #( #fields ),* } }
+            }
+        })
+        .collect();
+    quote! {
+        impl #name {
+            /// Lifts the operands read by `r` into the variant for `opcode`.
+            ///
+            /// Returns `Ok(None)` if there is no variant for `opcode`.
+            pub(in sr) fn lift(
+                opcode: spirv::Op,
+                r: &mut OperandReader,
+            ) -> LiftResult<Option<#name>> {
+                Ok(Some(match opcode {
+                    #( #arms, )*
+                    _ => return Ok(None),
+                }))
+            }
+        }
+    }
+}
+
+/// Returns the token stream for the enumerant of the given instruction,
+/// using `get_type` to compose the types of its fields.
+fn gen_sr_instruction_enumerant<F>(inst: &structs::Instruction, get_type: F) -> TokenStream
+where
+    F: Fn(&structs::Operand) -> TokenStream,
+{
+    // Get the token for its enumerant
+    let name = Ident::new(&inst.opname[2..], Span::call_site());
+
+    // Compose the token stream for all parameters
+    let params: Vec<_> = inst.operands
+        .iter() // Loop over all parameters
+        .filter_map(|operand| {
+            if operand.kind.starts_with("IdResult") {
+                None
+            } else {
+                let field_name = get_operand_name_sr_tokens(operand);
+                let field_type = get_type(operand);
+                if operand.quantifier == "" {
+                    Some(quote! { #field_name : #field_type })
+                } else if operand.quantifier == "?" {
+                    Some(quote! { #field_name : Option<#field_type> })
+                } else {
+                    Some(quote! { #field_name : Vec<#field_type> })
+                }
+            }
+        }).collect();
+    let params = if params.is_empty() {
+        quote!{}
+    } else {
+        // Create a list parameter tokens separated by comma
+        quote! { {#( #params ),*} }
+    };
+
+    // Get token stream for this enumerant
+    quote! { #name #params }
+}
+
 pub fn gen_sr_instruction(grammar: &structs::Grammar) -> String {
-    // Compose the token stream for all instructions
     let insts: Vec<_> = grammar
         .instructions
         .iter() // Loop over all instructions
         .filter(|i| i.class != "Type") // Skip types
         .filter(|i| i.class != "Constant") // Skip constants
-        .map(|inst| {
-            // Get the token for its enumerant
-            let name = Ident::new(&inst.opname[2..], Span::call_site());
-
-            // Compose the token stream for all parameters
-            let params: Vec<_> = inst.operands
-                .iter() // Loop over all parameters
-                .filter_map(|operand| {
-                    if operand.kind.starts_with("IdResult") {
-                        None
-                    } else {
-                        let field_name = get_operand_name_sr_tokens(operand);
-                        let field_type = get_operand_type_sr_tokens(&operand.kind);
-                        if operand.quantifier == "" {
-                            Some(quote! { #field_name : #field_type })
-                        } else if operand.quantifier == "?" {
-                            Some(quote! { #field_name : Option<#field_type> })
-                        } else {
-                            Some(quote! { #field_name : Vec<#field_type> })
-                        }
-                    }
-                }).collect();
-            let params = if params.is_empty() {
-                quote!{}
-            } else {
-                // Create a list parameter tokens separated by comma
-                quote! { {#( #params ),*} }
-            };
+        .filter(|i| i.class != "Terminator") // Skip terminators
+        .collect();
+    let terminators: Vec<_> = grammar
+        .instructions
+        .iter()
+        .filter(|i| i.class == "Terminator")
+        .collect();
 
-            // Get token stream for this enumerant
-            quote! { #name #params }
-        }).collect();
+    // Compose the token stream for all instructions and terminators
+    let inst_enumerants: Vec<_> = insts
+        .iter()
+        .map(|inst| gen_sr_instruction_enumerant(inst, get_instruction_operand_type_sr_tokens))
+        .collect();
+    let terminator_enumerants: Vec<_> = terminators
+        .iter()
+        .map(|inst| gen_sr_instruction_enumerant(inst, get_terminator_operand_type_sr_tokens))
+        .collect();
+    let inst_lift = gen_sr_lift("Instruction", &insts, false);
+    let terminator_lift = gen_sr_lift("Terminator", &terminators, true);
 
     // Wrap it up with enum definition boilerplate
     let insts = quote! {
-        enum Instruction {
-            #( #insts ),*
+        /// SPIR-V instructions other than types, constants, and terminators.
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum Instruction {
+            #( #inst_enumerants ),*
         }
+
+        /// SPIR-V instructions ending a basic block.
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum Terminator {
+            #( #terminator_enumerants ),*
+        }
+
+        #inst_lift
+
+        #terminator_lift
     };
     insts.to_string()
 }
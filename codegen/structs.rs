@@ -36,6 +36,10 @@ pub struct Instruction {
     pub operands: Vec<Operand>,
     #[serde(default)]
     pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub version: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,6 +52,10 @@ pub struct Enumerant {
     pub parameters: Vec<Operand>,
     #[serde(default)]
     pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub version: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -79,6 +87,8 @@ pub struct ExtInstSetGrammar {
     pub version: u32,
     pub revision: u32,
     pub instructions: Vec<Instruction>,
+    #[serde(default)]
+    pub operand_kinds: Vec<OperandKind>,
 }
 
 /// The struct that represents either a number or a string.
@@ -81,6 +81,18 @@ pub fn gen_grammar_inst_table_operand_kinds(grammar: &structs::Grammar)
              pub enum OperandKind {{\n{}\n}}\n\n",
             elements.join("\n"));
         ret.push_str(&kind_enum);
+
+        let arms: Vec<String> =
+            grammar.operand_kinds.iter().map(|kind| {
+                format!("            \"{k}\" => Some(OperandKind::{k}),", k = kind.kind)
+            }).collect();
+        let from_name = format!(
+            "impl OperandKind {{\n\
+             \x20   /// Returns the operand kind with the given `name` in the SPIR-V grammar.\n\
+             \x20   pub fn from_name(name: &str) -> Option<OperandKind> {{\n\
+             \x20       match name {{\n{}\n            _ => None,\n        }}\n    }}\n}}\n\n",
+            arms.join("\n"));
+        ret.push_str(&from_name);
     }
 
     { // Instruction table.
@@ -105,3 +117,11 @@ pub fn gen_opencl_std_100_inst_table(grammar: &structs::ExtInstSetGrammar) -> St
     gen_instruction_table(
         &grammar.instructions, "OPENCL_STD_100_INSTRUCTION_TABLE", true)
 }
+
+/// Writes the generated instruction table for NonSemantic.Shader.DebugInfo.100
+/// extended instruction set from `grammar` to the file with the given `filename`.
+pub fn gen_nonsemantic_shader_debuginfo_100_inst_table(
+    grammar: &structs::ExtInstSetGrammar) -> String {
+    gen_instruction_table(
+        &grammar.instructions, "NONSEMANTIC_SHADER_DEBUGINFO_100_INSTRUCTION_TABLE", true)
+}
@@ -103,6 +103,11 @@ pub fn get_param_name(param: &structs::Operand) -> String {
         }
     } else {
         let re = regex::Regex::new(r"\W").unwrap();
-        snake_casify(&re.replace_all(&param.name.replace(" ", "_"), ""))
+        let name = snake_casify(&re.replace_all(&param.name.replace(" ", "_"), ""));
+        // Avoid clashing with Rust keywords, e.g., for 'Type' and 'Use'.
+        match name.as_str() {
+            "type" | "use" => name + "_",
+            _ => name,
+        }
     }
 }
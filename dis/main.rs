@@ -18,23 +18,36 @@ extern crate rspirv;
 use std::fs;
 use std::io::Read;
 
-use rspirv::binary::Disassemble;
+use rspirv::binary::{Disassemble, Parser};
 
 fn main() {
     let matches = clap::App::new("rspirv-dis")
         .version(env!("CARGO_PKG_VERSION"))
         .about("SPIR-V binary module disassembler from the rspirv project")
         .arg(clap::Arg::with_name("input").index(1).required(true))
+        .arg(clap::Arg::with_name("grammar")
+                 .long("grammar")
+                 .takes_value(true)
+                 .help("SPIR-V JSON grammar for instructions unknown to rspirv"))
         .get_matches();
 
     let input = matches.value_of("input").unwrap();
     let mut f = fs::File::open(input).expect("cannot open file");
-    let mut buffer = Vec::new();
 
-    f.read_to_end(&mut buffer).expect("cannot read file");
+    let grammar = match matches.value_of("grammar") {
+        Some(path) => {
+            let json = fs::read_to_string(path).expect("cannot read grammar");
+            rspirv::grammar::DynamicGrammar::from_json(&json).expect("cannot load grammar")
+        }
+        None => rspirv::grammar::DynamicGrammar::new(),
+    };
+    let mut bytes = vec![];
+    f.read_to_end(&mut bytes).expect("cannot read file");
 
-    match rspirv::mr::load_bytes(&buffer) {
-        Ok(module) => println!("{}", module.disassemble()),
+    let mut loader = rspirv::mr::Loader::new();
+    let result = Parser::new(&bytes, &mut loader).with_grammar(&grammar).parse();
+    match result {
+        Ok(()) => println!("{}", loader.module().disassemble()),
         Err(err) => println!("{}", err),
     }
 }
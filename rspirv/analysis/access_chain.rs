@@ -0,0 +1,290 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// One step of an [`AccessPath`](struct.AccessPath.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessStep {
+    /// A struct member, together with its OpMemberName if any.
+    Member(u32, Option<String>),
+    /// An array element or matrix column at a constant index.
+    Element(u32),
+    /// A vector component at a constant index.
+    Component(u32),
+    /// An element or component at the index held by the given id, together
+    /// with its OpName if any.
+    Dynamic(Word, Option<String>),
+}
+
+/// The symbolic path of the memory accessed by an access chain, e.g.,
+/// `lights[3].position.y`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessPath {
+    /// The id of the variable (or other pointer) the path starts at.
+    pub base: Word,
+    /// The OpName of the base, if any.
+    pub base_name: Option<String>,
+    /// The steps from the base to the accessed memory.
+    pub steps: Vec<AccessStep>,
+}
+
+impl fmt::Display for AccessStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AccessStep::Member(_, Some(ref name)) => write!(f, ".{}", name),
+            AccessStep::Member(index, None) => write!(f, ".{}", index),
+            AccessStep::Component(index) if index < 4 => {
+                write!(f, ".{}", ["x", "y", "z", "w"][index as usize])
+            }
+            AccessStep::Element(index) | AccessStep::Component(index) => write!(f, "[{}]", index),
+            AccessStep::Dynamic(_, Some(ref name)) => write!(f, "[{}]", name),
+            AccessStep::Dynamic(id, None) => write!(f, "[%{}]", id),
+        }
+    }
+}
+
+impl fmt::Display for AccessPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base_name {
+            Some(ref name) => f.write_str(name)?,
+            None => write!(f, "%{}", self.base)?,
+        }
+        for step in &self.steps {
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the indices of access chains against the types they index
+/// into, turning them into symbolic [`AccessPath`](struct.AccessPath.html)s.
+///
+/// Access chains based on other access chains are resolved from the
+/// innermost base, so the path always starts at a variable, function
+/// parameter, or other non-access chain pointer.
+#[derive(Debug)]
+pub struct AccessChainResolver<'a> {
+    defs: HashMap<Word, &'a mr::Instruction>,
+    names: HashMap<Word, &'a str>,
+    member_names: HashMap<(Word, u32), &'a str>,
+}
+
+impl<'a> AccessChainResolver<'a> {
+    /// Creates a resolver for the access chains in the given `module`.
+    pub fn new(module: &'a mr::Module) -> AccessChainResolver<'a> {
+        let defs = module
+            .types_global_values
+            .iter()
+            .chain(module.functions.iter().flat_map(|f| {
+                let body = f.basic_blocks.iter().flat_map(|bb| &bb.instructions);
+                f.parameters.iter().chain(body)
+            }))
+            .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+            .collect();
+        let mut names = HashMap::new();
+        let mut member_names = HashMap::new();
+        for inst in &module.debugs {
+            match (inst.class.opcode, &inst.operands[..]) {
+                (spirv::Op::Name,
+                 &[mr::Operand::IdRef(target), mr::Operand::LiteralString(ref name)]) => {
+                    names.insert(target, name.as_str());
+                }
+                (spirv::Op::MemberName,
+                 &[mr::Operand::IdRef(target),
+                   mr::Operand::LiteralInt32(member),
+                   mr::Operand::LiteralString(ref name)]) => {
+                    member_names.insert((target, member), name.as_str());
+                }
+                _ => {}
+            }
+        }
+        AccessChainResolver { defs, names, member_names }
+    }
+
+    /// Resolves the access chain instruction `inst` (OpAccessChain,
+    /// OpInBoundsAccessChain, OpPtrAccessChain, or
+    /// OpInBoundsPtrAccessChain).
+    ///
+    /// Returns `None` if `inst` is not an access chain, or if its indices
+    /// do not match the types they index into.
+    pub fn resolve(&self, inst: &mr::Instruction) -> Option<AccessPath> {
+        self.resolve_with_type(inst).map(|(path, _)| path)
+    }
+
+    /// Resolves the access chain with the given result `id`.
+    pub fn resolve_id(&self, id: Word) -> Option<AccessPath> {
+        self.resolve(self.defs.get(&id)?)
+    }
+
+    /// Resolves `inst` and also returns the type the resulting pointer
+    /// points to.
+    fn resolve_with_type(&self, inst: &mr::Instruction) -> Option<(AccessPath, Word)> {
+        let (base, indices) = match inst.operands.split_first() {
+            Some((&mr::Operand::IdRef(base), indices)) => (base, indices),
+            _ => return None,
+        };
+        let mut indices = indices.iter().map(|operand| match *operand {
+            mr::Operand::IdRef(id) => Some(id),
+            _ => None,
+        });
+        let (mut path, mut ty) = match self.defs.get(&base) {
+            Some(def) if is_access_chain(def.class.opcode) => self.resolve_with_type(def)?,
+            _ => {
+                let pointer = self.defs.get(&base)?.result_type?;
+                let path = AccessPath {
+                    base,
+                    base_name: self.names.get(&base).map(|name| name.to_string()),
+                    steps: vec![],
+                };
+                (path, self.operand_id(pointer, 1)?)
+            }
+        };
+        match inst.class.opcode {
+            spirv::Op::AccessChain | spirv::Op::InBoundsAccessChain => {}
+            spirv::Op::PtrAccessChain | spirv::Op::InBoundsPtrAccessChain => {
+                // The Element operand indexes the base pointer itself.
+                let element = indices.next()??;
+                path.steps.push(self.index_step(element, AccessStep::Element));
+            }
+            _ => return None,
+        }
+        for index in indices {
+            let index = index?;
+            let def = self.defs.get(&ty)?;
+            let (step, next) = match def.class.opcode {
+                spirv::Op::TypeStruct => {
+                    let member = self.constant(index)?;
+                    let name = self.member_names.get(&(ty, member)).map(|name| name.to_string());
+                    (AccessStep::Member(member, name), self.operand_id(ty, member as usize)?)
+                }
+                spirv::Op::TypeArray | spirv::Op::TypeRuntimeArray | spirv::Op::TypeMatrix => {
+                    (self.index_step(index, AccessStep::Element), self.operand_id(ty, 0)?)
+                }
+                spirv::Op::TypeVector => {
+                    (self.index_step(index, AccessStep::Component), self.operand_id(ty, 0)?)
+                }
+                _ => return None,
+            };
+            path.steps.push(step);
+            ty = next;
+        }
+        Some((path, ty))
+    }
+
+    /// Returns the step for indexing with the given id, using `constant`
+    /// to construct the step if the id is an integer constant.
+    fn index_step<F: Fn(u32) -> AccessStep>(&self, index: Word, constant: F) -> AccessStep {
+        match self.constant(index) {
+            Some(value) => constant(value),
+            None => AccessStep::Dynamic(index, self.names.get(&index).map(|n| n.to_string())),
+        }
+    }
+
+    /// Returns the value of `id` if it is a 32-bit OpConstant.
+    fn constant(&self, id: Word) -> Option<u32> {
+        let def = self.defs.get(&id)?;
+        match (def.class.opcode, &def.operands[..]) {
+            (spirv::Op::Constant, &[mr::Operand::LiteralInt32(value)]) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn operand_id(&self, id: Word, index: usize) -> Option<Word> {
+        match self.defs.get(&id)?.operands.get(index) {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+fn is_access_chain(opcode: spirv::Op) -> bool {
+    matches!(
+        opcode,
+        spirv::Op::AccessChain |
+            spirv::Op::InBoundsAccessChain |
+            spirv::Op::PtrAccessChain |
+            spirv::Op::InBoundsPtrAccessChain
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::{AccessChainResolver, AccessStep};
+    use mr;
+    use spirv;
+
+    #[test]
+    fn test_resolve_access_chains() {
+        use spirv::StorageClass::{Function, Uniform};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let vec3 = b.type_vector(float, 3);
+        let light = b.type_struct(vec![vec3, float]);
+        let eight = b.constant_u32(uint, 8);
+        let lights = b.type_array(light, eight);
+        let block = b.type_struct(vec![uint, lights]);
+        let zero = b.constant_u32(uint, 0);
+        let one = b.constant_u32(uint, 1);
+        let three = b.constant_u32(uint, 3);
+        let block_ptr = b.type_pointer(None, Uniform, block);
+        let light_ptr = b.type_pointer(None, Uniform, light);
+        let float_ptr = b.type_pointer(None, Uniform, float);
+        let uint_ptr = b.type_pointer(None, Function, uint);
+        let voidf = b.type_function(void, vec![]);
+        let ubo = b.variable(block_ptr, None, Uniform, None);
+        b.name(ubo, "scene");
+        b.member_name(block, 1, "lights");
+        b.member_name(light, 0, "position");
+
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(uint_ptr, None, Function, None);
+        let i = b.load(uint, None, var, None, vec![]).unwrap();
+        b.name(i, "i");
+        let y = b.access_chain(float_ptr, None, ubo, vec![one, three, zero, one]).unwrap();
+        let light_i = b.access_chain(light_ptr, None, ubo, vec![one, i]).unwrap();
+        let intensity = b.access_chain(float_ptr, None, light_i, vec![one]).unwrap();
+        let count = b.access_chain(uint_ptr, None, ubo, vec![zero]).unwrap();
+        let bad = b.access_chain(float_ptr, None, ubo, vec![zero, zero]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let module = b.module();
+        let resolver = AccessChainResolver::new(&module);
+        let path = |id| resolver.resolve_id(id).map(|p| p.to_string());
+        assert_eq!(Some("scene.lights[3].position.y".to_string()), path(y));
+        assert_eq!(Some("scene.lights[i]".to_string()), path(light_i));
+        assert_eq!(Some("scene.lights[i].1".to_string()), path(intensity));
+        assert_eq!(Some("scene.0".to_string()), path(count));
+        assert_eq!(None, path(bad));
+        assert_eq!(None, path(var));
+
+        let resolved = resolver.resolve_id(light_i).unwrap();
+        assert_eq!(ubo, resolved.base);
+        assert_eq!(
+            vec![AccessStep::Member(1, Some("lights".to_string())),
+                 AccessStep::Dynamic(i, Some("i".to_string()))],
+            resolved.steps
+        );
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+
+/// Returns the labels of the successors of the given basic `block`, in
+/// the order they appear in its terminator.
+///
+/// Blocks terminated by OpReturn, OpReturnValue, OpKill, and OpUnreachable
+/// have no successors. Merge and continue targets declared via
+/// OpSelectionMerge and OpLoopMerge are not successors.
+pub fn block_successors(block: &mr::BasicBlock) -> Vec<Word> {
+    let terminator = match block.instructions.last() {
+        Some(inst) => inst,
+        None => return vec![],
+    };
+    let targets: Vec<Word> = match terminator.class.opcode {
+        spirv::Op::Branch => terminator.operands.iter().take(1).filter_map(id_ref).collect(),
+        spirv::Op::BranchConditional => {
+            terminator.operands.iter().skip(1).take(2).filter_map(id_ref).collect()
+        }
+        // Default target, followed by (literal, label) pairs.
+        spirv::Op::Switch => terminator.operands.iter().skip(1).filter_map(id_ref).collect(),
+        _ => vec![],
+    };
+    let mut successors = Vec::with_capacity(targets.len());
+    for target in targets {
+        if !successors.contains(&target) {
+            successors.push(target);
+        }
+    }
+    successors
+}
+
+fn id_ref(operand: &mr::Operand) -> Option<Word> {
+    match *operand {
+        mr::Operand::IdRef(id) => Some(id),
+        _ => None,
+    }
+}
+
+/// The control flow graph of a SPIR-V function.
+///
+/// Basic blocks are referred to by their indices in the function. The first
+/// block is the entry block.
+#[derive(Clone, Debug)]
+pub struct ControlFlowGraph {
+    labels: Vec<Word>,
+    indices: HashMap<Word, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    unreachable: Vec<bool>,
+}
+
+impl ControlFlowGraph {
+    /// Builds the control flow graph of the given `function`.
+    ///
+    /// Branches to labels not defined in the function are ignored.
+    pub fn new(function: &mr::Function) -> ControlFlowGraph {
+        let labels: Vec<Word> = function
+            .basic_blocks
+            .iter()
+            .map(|bb| bb.label.as_ref().and_then(|l| l.result_id).unwrap_or(0))
+            .collect();
+        let indices: HashMap<Word, usize> =
+            labels.iter().enumerate().map(|(i, &l)| (l, i)).collect();
+
+        let mut successors = vec![vec![]; labels.len()];
+        let mut predecessors = vec![vec![]; labels.len()];
+        for (index, block) in function.basic_blocks.iter().enumerate() {
+            for label in block_successors(block) {
+                if let Some(&succ) = indices.get(&label) {
+                    successors[index].push(succ);
+                    predecessors[succ].push(index);
+                }
+            }
+        }
+        let unreachable = function
+            .basic_blocks
+            .iter()
+            .map(|bb| {
+                bb.instructions
+                    .last()
+                    .is_some_and(|inst| inst.class.opcode == spirv::Op::Unreachable)
+            })
+            .collect();
+
+        ControlFlowGraph {
+            labels,
+            indices,
+            successors,
+            predecessors,
+            unreachable,
+        }
+    }
+
+    /// Returns the number of basic blocks.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns true if the function has no basic blocks.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns the label of the basic block at the given `index`.
+    pub fn label(&self, index: usize) -> Word {
+        self.labels[index]
+    }
+
+    /// Returns the index of the basic block with the given `label`.
+    pub fn index(&self, label: Word) -> Option<usize> {
+        self.indices.get(&label).cloned()
+    }
+
+    /// Returns the indices of the successors of the given block.
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.successors[index]
+    }
+
+    /// Returns the indices of the predecessors of the given block.
+    pub fn predecessors(&self, index: usize) -> &[usize] {
+        &self.predecessors[index]
+    }
+
+    /// Returns true if the given block is terminated by OpUnreachable.
+    ///
+    /// Such blocks have no successors; executing them is undefined behavior.
+    pub fn ends_in_unreachable(&self, index: usize) -> bool {
+        self.unreachable[index]
+    }
+
+    /// Returns, for each block, whether it is reachable from the entry block.
+    pub fn reachable_blocks(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        let mut stack = vec![];
+        if !self.is_empty() {
+            reachable[0] = true;
+            stack.push(0);
+        }
+        while let Some(index) = stack.pop() {
+            for &succ in &self.successors[index] {
+                if !reachable[succ] {
+                    reachable[succ] = true;
+                    stack.push(succ);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Returns the blocks reachable from the entry block in reverse post
+    /// order.
+    pub fn reverse_post_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len());
+        if self.is_empty() {
+            return order;
+        }
+        let mut visited = vec![false; self.len()];
+        // (block, index of the next successor to visit)
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        while let Some(&mut (block, ref mut next)) = stack.last_mut() {
+            if let Some(&succ) = self.successors[block].get(*next) {
+                *next += 1;
+                if !visited[succ] {
+                    visited[succ] = true;
+                    stack.push((succ, 0));
+                }
+            } else {
+                order.push(block);
+                stack.pop();
+            }
+        }
+        order.reverse();
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::ControlFlowGraph;
+
+    /// Builds a function with blocks 10 to 14: 10 branches conditionally
+    /// to 11 and 12, both of which branch to 13; 14 is unreachable.
+    fn diamond(terminate_12: bool) -> mr::Function {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.selection_merge(13, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, 11, 12, vec![]).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.branch(13).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        if terminate_12 {
+            b.unreachable().unwrap();
+        } else {
+            b.branch(13).unwrap();
+        }
+        b.begin_basic_block(Some(13)).unwrap();
+        b.ret().unwrap();
+        b.begin_basic_block(Some(14)).unwrap();
+        b.branch(13).unwrap();
+        b.end_function().unwrap();
+        b.module().functions.pop().unwrap()
+    }
+
+    #[test]
+    fn test_cfg() {
+        let cfg = ControlFlowGraph::new(&diamond(false));
+        assert_eq!(5, cfg.len());
+        assert_eq!(12, cfg.label(2));
+        assert_eq!(Some(3), cfg.index(13));
+        assert_eq!(None, cfg.index(42));
+        assert_eq!(&[1, 2], cfg.successors(0));
+        assert_eq!(&[1, 2, 4], cfg.predecessors(3));
+        assert!(cfg.successors(3).is_empty());
+        assert_eq!(vec![true, true, true, true, false], cfg.reachable_blocks());
+        assert_eq!(vec![0, 2, 1, 3], cfg.reverse_post_order());
+    }
+
+    #[test]
+    fn test_cfg_unreachable_terminator() {
+        let cfg = ControlFlowGraph::new(&diamond(true));
+        assert!(cfg.ends_in_unreachable(2));
+        assert!(!cfg.ends_in_unreachable(1));
+        assert!(cfg.successors(2).is_empty());
+        assert_eq!(&[1, 4], cfg.predecessors(3));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use grammar::{OpClass, Opcode};
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Built-in inputs which have the same value for all invocations of a
+/// draw or dispatch.
+const UNIFORM_BUILTINS: &[spirv::BuiltIn] = &[
+    spirv::BuiltIn::NumWorkgroups,
+    spirv::BuiltIn::WorkgroupSize,
+    spirv::BuiltIn::WorkgroupId,
+    spirv::BuiltIn::SubgroupSize,
+    spirv::BuiltIn::NumSubgroups,
+    spirv::BuiltIn::BaseVertex,
+    spirv::BuiltIn::BaseInstance,
+    spirv::BuiltIn::DrawIndex,
+];
+
+/// The divergence analysis of a SPIR-V function.
+///
+/// A value is divergent if it may differ between invocations executing the
+/// function together. The analysis is a conservative approximation:
+///
+/// * Loads of input variables are divergent, except for loads of built-ins
+///   uniform across a draw or dispatch (e.g., `WorkgroupId`).
+/// * Function parameters and results of atomic instructions are divergent.
+/// * Loads from variables are divergent if the function stores a divergent
+///   value into the same variable.
+/// * Any other value is divergent if one of its operands is.
+///
+/// Divergence caused by control flow (e.g., OpPhi instructions merging
+/// uniform values from divergent branches) is not tracked.
+#[derive(Clone, Debug)]
+pub struct DivergenceAnalysis {
+    divergent: HashSet<Word>,
+}
+
+impl DivergenceAnalysis {
+    /// Computes the divergence analysis of the given `function` in
+    /// `module`.
+    pub fn new(module: &mr::Module, function: &mr::Function) -> DivergenceAnalysis {
+        let builtins: HashMap<Word, spirv::BuiltIn> = module.annotations
+            .iter()
+            .filter_map(|inst| match inst.operands[..] {
+                [mr::Operand::IdRef(target),
+                 mr::Operand::Decoration(spirv::Decoration::BuiltIn),
+                 mr::Operand::BuiltIn(builtin)] => Some((target, builtin)),
+                _ => None,
+            })
+            .collect();
+        let inputs: HashSet<Word> = module.types_global_values
+            .iter()
+            .filter(|inst| {
+                inst.class.opcode == spirv::Op::Variable &&
+                    inst.operands.first() == Some(&mr::Operand::StorageClass(
+                        spirv::StorageClass::Input,
+                    ))
+            })
+            .filter_map(|inst| inst.result_id)
+            .filter(|id| builtins.get(id).is_none_or(|b| !UNIFORM_BUILTINS.contains(b)))
+            .collect();
+
+        let instructions: Vec<&mr::Instruction> = function.basic_blocks
+            .iter()
+            .flat_map(|bb| bb.instructions.iter())
+            .collect();
+        // Pointers derived from variables, mapped to the variables.
+        let mut bases: HashMap<Word, Word> = HashMap::new();
+        for inst in &instructions {
+            let derived = matches!(
+                inst.class.opcode,
+                spirv::Op::AccessChain | spirv::Op::InBoundsAccessChain |
+                    spirv::Op::PtrAccessChain | spirv::Op::InBoundsPtrAccessChain |
+                    spirv::Op::CopyObject
+            );
+            if let (true, Some(id), Some(&mr::Operand::IdRef(pointer))) =
+                (derived, inst.result_id, inst.operands.first())
+            {
+                let base = *bases.get(&pointer).unwrap_or(&pointer);
+                bases.insert(id, base);
+            }
+        }
+        let base = |pointer: Word| *bases.get(&pointer).unwrap_or(&pointer);
+
+        let mut divergent: HashSet<Word> =
+            function.parameters.iter().filter_map(|inst| inst.result_id).collect();
+        let mut divergent_memory: HashSet<Word> = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for inst in &instructions {
+                let uses_divergent = inst.operands.iter().any(|operand| match *operand {
+                    mr::Operand::IdRef(id) => divergent.contains(&id),
+                    _ => false,
+                });
+                let opcode = inst.class.opcode;
+                if opcode == spirv::Op::Store {
+                    if let Some(&mr::Operand::IdRef(pointer)) = inst.operands.first() {
+                        if uses_divergent && divergent_memory.insert(base(pointer)) {
+                            changed = true;
+                        }
+                    }
+                    continue;
+                }
+                let id = match inst.result_id {
+                    Some(id) if !divergent.contains(&id) => id,
+                    _ => continue,
+                };
+                let is_divergent = uses_divergent || match opcode {
+                    spirv::Op::Load => match inst.operands.first() {
+                        Some(&mr::Operand::IdRef(pointer)) => {
+                            inputs.contains(&base(pointer)) ||
+                                divergent_memory.contains(&base(pointer))
+                        }
+                        _ => false,
+                    },
+                    _ => opcode.class() == OpClass::Atomic,
+                };
+                if is_divergent {
+                    divergent.insert(id);
+                    changed = true;
+                }
+            }
+        }
+        DivergenceAnalysis { divergent }
+    }
+
+    /// Returns true if the value with the given `id` may differ between
+    /// invocations.
+    pub fn is_divergent(&self, id: Word) -> bool {
+        self.divergent.contains(&id)
+    }
+
+    /// Returns true if the terminator of the given `block` is a conditional
+    /// branch or switch on a divergent value.
+    pub fn is_divergent_branch(&self, block: &mr::BasicBlock) -> bool {
+        match block.instructions.last() {
+            Some(inst) => match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::BranchConditional, Some(&mr::Operand::IdRef(condition))) |
+                (spirv::Op::Switch, Some(&mr::Operand::IdRef(condition))) => {
+                    self.is_divergent(condition)
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::DivergenceAnalysis;
+
+    #[test]
+    fn test_divergence() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let uvec3 = b.type_vector(uint, 3);
+        let boolean = b.type_bool();
+        let input = b.type_pointer(None, spirv::StorageClass::Input, uvec3);
+        let local = b.type_pointer(None, spirv::StorageClass::Function, uint);
+        let invocation = b.variable(input, None, spirv::StorageClass::Input, None);
+        let workgroup = b.variable(input, None, spirv::StorageClass::Input, None);
+        let one = b.constant_u32(uint, 1);
+        b.decorate(
+            invocation,
+            spirv::Decoration::BuiltIn,
+            vec![mr::Operand::BuiltIn(spirv::BuiltIn::LocalInvocationId)],
+        );
+        b.decorate(
+            workgroup,
+            spirv::Decoration::BuiltIn,
+            vec![mr::Operand::BuiltIn(spirv::BuiltIn::WorkgroupId)],
+        );
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(local, None, spirv::StorageClass::Function, None);
+        let lid = b.load(uvec3, None, invocation, None, vec![]).unwrap();
+        let wid = b.load(uvec3, None, workgroup, None, vec![]).unwrap();
+        let x = b.composite_extract(uint, None, lid, vec![0]).unwrap();
+        let y = b.composite_extract(uint, None, wid, vec![0]).unwrap();
+        let sum = b.iadd(uint, None, y, one).unwrap();
+        b.store(var, x, None, vec![]).unwrap();
+        let loaded = b.load(uint, None, var, None, vec![]).unwrap();
+        let cond = b.iequal(boolean, None, sum, loaded).unwrap();
+        b.selection_merge(20, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, 20, 20, vec![]).unwrap();
+        b.begin_basic_block(Some(20)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let m = b.module();
+
+        let divergence = DivergenceAnalysis::new(&m, &m.functions[0]);
+        assert!(divergence.is_divergent(lid));
+        assert!(divergence.is_divergent(x));
+        assert!(!divergence.is_divergent(wid));
+        assert!(!divergence.is_divergent(sum));
+        assert!(divergence.is_divergent(loaded));
+        assert!(divergence.is_divergent(cond));
+        assert!(divergence.is_divergent_branch(&m.functions[0].basic_blocks[0]));
+        assert!(!divergence.is_divergent_branch(&m.functions[0].basic_blocks[1]));
+    }
+}
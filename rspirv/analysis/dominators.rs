@@ -0,0 +1,225 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ControlFlowGraph;
+
+/// The dominator tree of a control flow graph.
+///
+/// Computed with the iterative algorithm by Cooper, Harvey, and Kennedy.
+/// Blocks unreachable from the entry block are not part of the tree: they
+/// have no immediate dominator and neither dominate nor are dominated by
+/// any other block. Since blocks terminated by OpUnreachable have no
+/// successors, they never dominate other blocks.
+#[derive(Clone, Debug)]
+pub struct DominatorTree {
+    idoms: Vec<Option<usize>>,
+}
+
+impl DominatorTree {
+    /// Computes the dominator tree of the given `cfg`.
+    pub fn new(cfg: &ControlFlowGraph) -> DominatorTree {
+        let order = cfg.reverse_post_order();
+        let mut rpo_number = vec![usize::MAX; cfg.len()];
+        for (number, &block) in order.iter().enumerate() {
+            rpo_number[block] = number;
+        }
+
+        let mut idoms: Vec<Option<usize>> = vec![None; cfg.len()];
+        if let Some(&entry) = order.first() {
+            idoms[entry] = Some(entry);
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in order.iter().skip(1) {
+                let mut new_idom = None;
+                for &pred in cfg.predecessors(block) {
+                    if idoms[pred].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(other) => intersect(&idoms, &rpo_number, pred, other),
+                    });
+                }
+                if new_idom.is_some() && idoms[block] != new_idom {
+                    idoms[block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        DominatorTree { idoms }
+    }
+
+    /// Returns true if the given block is reachable from the entry block.
+    pub fn is_reachable(&self, index: usize) -> bool {
+        self.idoms[index].is_some()
+    }
+
+    /// Returns the immediate dominator of the given block.
+    ///
+    /// The entry block and unreachable blocks have no immediate dominator.
+    pub fn immediate_dominator(&self, index: usize) -> Option<usize> {
+        self.idoms[index].filter(|&idom| idom != index)
+    }
+
+    /// Returns true if block `a` dominates block `b`.
+    ///
+    /// Every reachable block dominates itself.
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        if !self.is_reachable(a) || !self.is_reachable(b) {
+            return false;
+        }
+        let mut block = b;
+        loop {
+            if block == a {
+                return true;
+            }
+            match self.immediate_dominator(block) {
+                Some(idom) => block = idom,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl DominatorTree {
+    /// Returns the children of each block in the dominator tree.
+    pub fn children(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![vec![]; self.idoms.len()];
+        for index in 0..self.idoms.len() {
+            if let Some(idom) = self.immediate_dominator(index) {
+                children[idom].push(index);
+            }
+        }
+        children
+    }
+
+    /// Returns the dominance frontier of each block, i.e., the blocks where
+    /// the dominance of the block ends.
+    ///
+    /// The given `cfg` must be the one this tree was computed from.
+    pub fn dominance_frontiers(&self, cfg: &ControlFlowGraph) -> Vec<Vec<usize>> {
+        let mut frontiers: Vec<Vec<usize>> = vec![vec![]; self.idoms.len()];
+        for block in 0..cfg.len() {
+            let idom = match self.immediate_dominator(block) {
+                Some(idom) => idom,
+                None => continue,
+            };
+            let preds = cfg.predecessors(block);
+            if preds.len() < 2 {
+                continue;
+            }
+            for &pred in preds.iter().filter(|&&p| self.is_reachable(p)) {
+                let mut runner = pred;
+                while runner != idom {
+                    if !frontiers[runner].contains(&block) {
+                        frontiers[runner].push(block);
+                    }
+                    match self.immediate_dominator(runner) {
+                        Some(next) => runner = next,
+                        None => break,
+                    }
+                }
+            }
+        }
+        frontiers
+    }
+}
+
+fn intersect(idoms: &[Option<usize>], rpo_number: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while rpo_number[a] > rpo_number[b] {
+            a = idoms[a].unwrap();
+        }
+        while rpo_number[b] > rpo_number[a] {
+            b = idoms[b].unwrap();
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use analysis::{ControlFlowGraph, DominatorTree};
+
+    #[test]
+    fn test_dominators() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.selection_merge(13, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, 11, 12, vec![]).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.branch(13).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.unreachable().unwrap();
+        b.begin_basic_block(Some(13)).unwrap();
+        b.ret().unwrap();
+        b.begin_basic_block(Some(14)).unwrap();
+        b.branch(13).unwrap();
+        b.end_function().unwrap();
+        let function = b.module().functions.pop().unwrap();
+
+        let domtree = DominatorTree::new(&ControlFlowGraph::new(&function));
+        assert_eq!(None, domtree.immediate_dominator(0));
+        assert_eq!(Some(0), domtree.immediate_dominator(1));
+        assert_eq!(Some(0), domtree.immediate_dominator(2));
+        // Block 12 ends in OpUnreachable, so only block 11 reaches block 13.
+        assert_eq!(Some(1), domtree.immediate_dominator(3));
+        assert!(domtree.dominates(0, 3));
+        assert!(domtree.dominates(3, 3));
+        assert!(!domtree.dominates(2, 3));
+        assert!(!domtree.is_reachable(4));
+        assert!(!domtree.dominates(4, 3));
+        assert!(!domtree.dominates(0, 4));
+        assert_eq!(vec![vec![1, 2], vec![3], vec![], vec![], vec![]], domtree.children());
+    }
+
+    #[test]
+    fn test_dominance_frontiers() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        // 10 -> 11 (loop header) -> 12 -> {11, 13}
+        b.begin_basic_block(Some(10)).unwrap();
+        b.branch(11).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.loop_merge(13, 12, spirv::LoopControl::NONE, vec![]).unwrap();
+        b.branch(12).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.branch_conditional(cond, 11, 13, vec![]).unwrap();
+        b.begin_basic_block(Some(13)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let function = b.module().functions.pop().unwrap();
+
+        let cfg = ControlFlowGraph::new(&function);
+        let domtree = DominatorTree::new(&cfg);
+        assert_eq!(
+            vec![vec![], vec![1], vec![1], vec![]],
+            domtree.dominance_frontiers(&cfg)
+        );
+    }
+}
@@ -0,0 +1,533 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use super::reflect::{descriptor_kind, entry_point_resources};
+use super::DescriptorKind;
+use spirv::Word;
+use std::collections::HashMap;
+
+/// The interface of a module in a neutral schema, for pipelines built on
+/// other shader representations, e.g., naga and wgpu, or custom engines.
+///
+/// With the `serialize` feature, all types of the schema implement serde's
+/// `Serialize` and `Deserialize`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ShaderInterface {
+    /// The entry points, in the order of their OpEntryPoint instructions.
+    pub entry_points: Vec<EntryPointInterface>,
+    /// The specialization constants, in the order of their SpecIds.
+    pub spec_constants: Vec<SpecConstant>,
+}
+
+/// The interface of one entry point.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct EntryPointInterface {
+    /// The name of the entry point.
+    pub name: String,
+    /// The pipeline stage of the entry point.
+    pub stage: ShaderStage,
+    /// The workgroup size of compute-like stages, if known.
+    pub workgroup_size: Option<[u32; 3]>,
+    /// The resources statically used by the entry point.
+    pub bindings: Vec<ResourceBinding>,
+    /// The Input variables statically used by the entry point.
+    pub inputs: Vec<StageVariable>,
+    /// The Output variables statically used by the entry point.
+    pub outputs: Vec<StageVariable>,
+}
+
+/// The pipeline stage of an entry point.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ShaderStage {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+    Task,
+    Mesh,
+    /// Any of the ray tracing stages.
+    RayTracing,
+    /// OpenCL kernels.
+    Kernel,
+}
+
+impl From<spirv::ExecutionModel> for ShaderStage {
+    fn from(model: spirv::ExecutionModel) -> Self {
+        use spirv::ExecutionModel::*;
+        match model {
+            Vertex => ShaderStage::Vertex,
+            TessellationControl => ShaderStage::TessellationControl,
+            TessellationEvaluation => ShaderStage::TessellationEvaluation,
+            Geometry => ShaderStage::Geometry,
+            Fragment => ShaderStage::Fragment,
+            GLCompute => ShaderStage::Compute,
+            Kernel => ShaderStage::Kernel,
+            TaskEXT => ShaderStage::Task,
+            MeshEXT => ShaderStage::Mesh,
+            RayGenerationKHR | IntersectionKHR | AnyHitKHR | ClosestHitKHR | MissKHR |
+            CallableKHR => ShaderStage::RayTracing,
+        }
+    }
+}
+
+/// The kind of resource bound to a binding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ResourceKind {
+    UniformBuffer,
+    StorageBuffer,
+    Sampler,
+    SampledImage,
+    CombinedImageSampler,
+    StorageImage,
+    UniformTexelBuffer,
+    StorageTexelBuffer,
+    InputAttachment,
+}
+
+/// A resource bound to a (group, binding) pair, i.e., a descriptor set
+/// and binding in Vulkan terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ResourceBinding {
+    /// The DescriptorSet decoration, if any.
+    pub group: Option<u32>,
+    /// The Binding decoration, if any.
+    pub binding: Option<u32>,
+    /// The name of the variable, if any.
+    pub name: Option<String>,
+    /// The kind of the resource.
+    pub kind: ResourceKind,
+    /// The number of resources of an array binding, 0 if runtime-sized,
+    /// or 1 if the binding is not an array.
+    pub count: u32,
+}
+
+/// The kind of the components of a value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ScalarKind {
+    Bool,
+    Sint,
+    Uint,
+    Float,
+}
+
+/// The type of a scalar or vector value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ValueType {
+    /// The kind of the components.
+    pub kind: ScalarKind,
+    /// The width of the components in bits, 0 for booleans.
+    pub width: u32,
+    /// The number of components, 1 for scalars.
+    pub components: u32,
+}
+
+/// An Input or Output variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct StageVariable {
+    /// The name of the variable, if any.
+    pub name: Option<String>,
+    /// The Location decoration, if any.
+    pub location: Option<u32>,
+    /// The built-in the variable is decorated as, if any, e.g.,
+    /// `"Position"`.
+    pub builtin: Option<String>,
+    /// The type of the variable, if a scalar or vector.
+    pub ty: Option<ValueType>,
+}
+
+/// The default value of a specialization constant.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ScalarValue {
+    Bool(bool),
+    Sint(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+/// A scalar specialization constant decorated with a SpecId.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SpecConstant {
+    /// The SpecId of the constant.
+    pub id: u32,
+    /// The name of the constant, if any.
+    pub name: Option<String>,
+    /// The type of the constant.
+    pub ty: Option<ValueType>,
+    /// The default value of the constant.
+    pub default: Option<ScalarValue>,
+}
+
+/// The definitions of the types and constants of a module.
+struct Definitions<'a> {
+    defs: HashMap<Word, &'a mr::Instruction>,
+}
+
+impl<'a> Definitions<'a> {
+    fn new(module: &'a mr::Module) -> Self {
+        Definitions {
+            defs: module
+                .types_global_values
+                .iter()
+                .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+                .collect(),
+        }
+    }
+
+    fn get(&self, id: Word) -> Option<&'a mr::Instruction> {
+        self.defs.get(&id).cloned()
+    }
+
+    /// Returns the type pointed to by the type of the given variable.
+    fn pointee(&self, variable: Word) -> Option<Word> {
+        let pointer = self.get(self.get(variable)?.result_type?)?;
+        match pointer.operands.get(1) {
+            Some(&mr::Operand::IdRef(pointee)) => Some(pointee),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the given 32-bit integer constant, ignoring
+    /// specialization.
+    fn constant_u32(&self, id: Word) -> Option<u32> {
+        let inst = self.get(id)?;
+        match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::Constant, Some(&mr::Operand::LiteralInt32(value))) |
+            (spirv::Op::SpecConstant, Some(&mr::Operand::LiteralInt32(value))) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn value_type(&self, ty: Word) -> Option<ValueType> {
+        let inst = self.get(ty)?;
+        match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::TypeBool, _) => {
+                Some(ValueType { kind: ScalarKind::Bool, width: 0, components: 1 })
+            }
+            (spirv::Op::TypeInt,
+             &[mr::Operand::LiteralInt32(width), mr::Operand::LiteralInt32(signedness)]) => {
+                let kind = if signedness == 0 { ScalarKind::Uint } else { ScalarKind::Sint };
+                Some(ValueType { kind, width, components: 1 })
+            }
+            (spirv::Op::TypeFloat, &[mr::Operand::LiteralInt32(width), ..]) => {
+                Some(ValueType { kind: ScalarKind::Float, width, components: 1 })
+            }
+            (spirv::Op::TypeVector,
+             &[mr::Operand::IdRef(component), mr::Operand::LiteralInt32(components)]) => {
+                self.value_type(component).map(|ty| ValueType { components, ..ty })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the default value of the given specialization constant.
+    fn spec_value(&self, inst: &mr::Instruction) -> Option<ScalarValue> {
+        let ty = inst.result_type.and_then(|ty| self.value_type(ty));
+        match (inst.class.opcode, inst.operands.first(), ty) {
+            (spirv::Op::SpecConstantTrue, _, _) => Some(ScalarValue::Bool(true)),
+            (spirv::Op::SpecConstantFalse, _, _) => Some(ScalarValue::Bool(false)),
+            (_, Some(&mr::Operand::LiteralInt32(v)), Some(ty)) => match ty.kind {
+                ScalarKind::Sint => Some(ScalarValue::Sint(v as i32 as i64)),
+                _ => Some(ScalarValue::Uint(v as u64)),
+            },
+            (_, Some(&mr::Operand::LiteralInt64(v)), Some(ty)) => match ty.kind {
+                ScalarKind::Sint => Some(ScalarValue::Sint(v as i64)),
+                _ => Some(ScalarValue::Uint(v)),
+            },
+            (_, Some(&mr::Operand::LiteralFloat32(v)), _) => Some(ScalarValue::Float(v as f64)),
+            (_, Some(&mr::Operand::LiteralFloat64(v)), _) => Some(ScalarValue::Float(v)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the resource kind and count of the descriptor variable of the
+/// given pointee type.
+fn resource(
+    defs: &Definitions,
+    decorations: &mr::DecorationIndex,
+    storage_class: spirv::StorageClass,
+    ty: Word,
+) -> Option<(ResourceKind, u32)> {
+    let inst = defs.get(ty)?;
+    let (element, count) = match (inst.class.opcode, &inst.operands[..]) {
+        (spirv::Op::TypeArray, &[mr::Operand::IdRef(element), mr::Operand::IdRef(length)]) => {
+            (element, defs.constant_u32(length)?)
+        }
+        (spirv::Op::TypeRuntimeArray, &[mr::Operand::IdRef(element)]) => (element, 0),
+        _ => (ty, 1),
+    };
+    let kind = match defs.get(element)?.class.opcode {
+        spirv::Op::TypeSampler => ResourceKind::Sampler,
+        spirv::Op::TypeSampledImage => ResourceKind::CombinedImageSampler,
+        _ => match descriptor_kind(&defs.defs, decorations, storage_class, element)? {
+            DescriptorKind::UniformBuffer => ResourceKind::UniformBuffer,
+            DescriptorKind::StorageBuffer => ResourceKind::StorageBuffer,
+            DescriptorKind::SampledImage => ResourceKind::SampledImage,
+            DescriptorKind::StorageImage => ResourceKind::StorageImage,
+            DescriptorKind::UniformTexelBuffer => ResourceKind::UniformTexelBuffer,
+            DescriptorKind::StorageTexelBuffer => ResourceKind::StorageTexelBuffer,
+            DescriptorKind::InputAttachment => ResourceKind::InputAttachment,
+        },
+    };
+    Some((kind, count))
+}
+
+/// Returns the workgroup size of the given entry point, from the
+/// WorkgroupSize built-in if declared, or from its LocalSize or LocalSizeId
+/// execution mode. Specialization constants count with their defaults.
+fn workgroup_size(
+    module: &mr::Module,
+    defs: &Definitions,
+    decorations: &mr::DecorationIndex,
+    entry: Word,
+) -> Option<[u32; 3]> {
+    let builtin = module.types_global_values.iter().find(|inst| {
+        decorations.get_decorations(inst.result_id.unwrap_or(0)).any(|d| {
+            d.operands.get(2) == Some(&mr::Operand::BuiltIn(spirv::BuiltIn::WorkgroupSize))
+        })
+    });
+    if let Some(inst) = builtin {
+        if let [mr::Operand::IdRef(x), mr::Operand::IdRef(y), mr::Operand::IdRef(z)] =
+            inst.operands[..]
+        {
+            return Some([defs.constant_u32(x)?, defs.constant_u32(y)?, defs.constant_u32(z)?]);
+        }
+    }
+    module.execution_modes.iter().find_map(|inst| match inst.operands[..] {
+        [mr::Operand::IdRef(id),
+         mr::Operand::ExecutionMode(spirv::ExecutionMode::LocalSize),
+         mr::Operand::LiteralInt32(x),
+         mr::Operand::LiteralInt32(y),
+         mr::Operand::LiteralInt32(z)] if id == entry => Some([x, y, z]),
+        [mr::Operand::IdRef(id),
+         mr::Operand::ExecutionMode(spirv::ExecutionMode::LocalSizeId),
+         mr::Operand::IdRef(x),
+         mr::Operand::IdRef(y),
+         mr::Operand::IdRef(z)] if id == entry => {
+            Some([defs.constant_u32(x)?, defs.constant_u32(y)?, defs.constant_u32(z)?])
+        }
+        _ => None,
+    })
+}
+
+/// Exports the interface of the given module, i.e., the resources and
+/// Input and Output variables statically used by each entry point (see
+/// [`entry_point_resources`](fn.entry_point_resources.html)), the
+/// workgroup sizes, and the specialization constants, into a neutral
+/// schema.
+///
+/// Descriptor variables of types which are not resources, e.g., structs
+/// in UniformConstant, are left out.
+pub fn shader_interface(module: &mr::Module) -> ShaderInterface {
+    let defs = Definitions::new(module);
+    let decorations = module.decoration_index();
+    let name = |id: Word| module.name_of(id).map(|name| name.to_string());
+    let stage_variable = |variable: Word| StageVariable {
+        name: name(variable),
+        location: decorations.get_decoration_value(variable, spirv::Decoration::Location),
+        builtin: decorations.get_decorations(variable).find_map(|inst| {
+            match inst.operands[..] {
+                [_, mr::Operand::Decoration(spirv::Decoration::BuiltIn),
+                 mr::Operand::BuiltIn(builtin)] => Some(builtin.to_string()),
+                _ => None,
+            }
+        }),
+        ty: defs.pointee(variable).and_then(|ty| defs.value_type(ty)),
+    };
+
+    let entry_points = entry_point_resources(module)
+        .into_iter()
+        .map(|resources| {
+            let stage = ShaderStage::from(resources.execution_model);
+            let workgroup_size = match stage {
+                ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh |
+                ShaderStage::Kernel => {
+                    workgroup_size(module, &defs, &decorations, resources.function)
+                }
+                _ => None,
+            };
+            let bindings = resources
+                .bindings
+                .iter()
+                .filter_map(|binding| {
+                    let ty = defs.pointee(binding.variable)?;
+                    let (kind, count) = resource(&defs, &decorations, binding.storage_class, ty)?;
+                    Some(ResourceBinding {
+                        group: binding.set,
+                        binding: binding.binding,
+                        name: name(binding.variable),
+                        kind,
+                        count,
+                    })
+                })
+                .collect();
+            EntryPointInterface {
+                stage,
+                workgroup_size,
+                bindings,
+                inputs: resources.inputs.iter().map(|&v| stage_variable(v)).collect(),
+                outputs: resources.outputs.iter().map(|&v| stage_variable(v)).collect(),
+                name: resources.name,
+            }
+        })
+        .collect();
+
+    let mut spec_constants: Vec<SpecConstant> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| {
+            let id = inst.result_id?;
+            let spec_id = decorations.get_decoration_value(id, spirv::Decoration::SpecId)?;
+            Some(SpecConstant {
+                id: spec_id,
+                name: name(id),
+                ty: inst.result_type.and_then(|ty| defs.value_type(ty)),
+                default: defs.spec_value(inst),
+            })
+        })
+        .collect();
+    spec_constants.sort_by_key(|constant| constant.id);
+
+    ShaderInterface { entry_points, spec_constants }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::*;
+
+    #[test]
+    fn test_shader_interface() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let block = b.type_struct(vec![uint]);
+        let ssbo_ptr = b.type_pointer(None, spirv::StorageClass::StorageBuffer, block);
+        let ssbo = b.variable(ssbo_ptr, None, spirv::StorageClass::StorageBuffer, None);
+        let sampler = b.type_sampler();
+        let four = b.constant_u32(uint, 4);
+        let samplers_type = b.type_array(sampler, four);
+        let samplers_ptr =
+            b.type_pointer(None, spirv::StorageClass::UniformConstant, samplers_type);
+        let samplers =
+            b.variable(samplers_ptr, None, spirv::StorageClass::UniformConstant, None);
+        let sampler_ptr = b.type_pointer(None, spirv::StorageClass::UniformConstant, sampler);
+        let uint_ptr = b.type_pointer(None, spirv::StorageClass::StorageBuffer, uint);
+        let zero = b.constant_u32(uint, 0);
+        let output_ptr = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let output = b.variable(output_ptr, None, spirv::StorageClass::Output, None);
+        let size = b.spec_constant_u32(uint, 64);
+        let null = b.constant_null(vec4);
+        let voidf = b.type_function(void, vec![]);
+        let cs = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.access_chain(uint_ptr, None, ssbo, vec![zero]).unwrap();
+        b.access_chain(sampler_ptr, None, samplers, vec![zero]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let fs = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.store(output, null, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, cs, "main_cs", vec![]);
+        b.execution_mode(cs, spirv::ExecutionMode::LocalSize, [8, 8, 1]);
+        b.entry_point(spirv::ExecutionModel::Fragment, fs, "main_fs", vec![output]);
+        b.execution_mode(fs, spirv::ExecutionMode::OriginUpperLeft, []);
+        b.name(ssbo, "data");
+        b.name(size, "SIZE");
+        let literal = |v: u32| vec![mr::Operand::LiteralInt32(v)];
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.member_decorate(block, 0, spirv::Decoration::Offset, literal(0));
+        b.decorate(ssbo, spirv::Decoration::DescriptorSet, literal(0));
+        b.decorate(ssbo, spirv::Decoration::Binding, literal(2));
+        b.decorate(samplers, spirv::Decoration::DescriptorSet, literal(1));
+        b.decorate(samplers, spirv::Decoration::Binding, literal(0));
+        b.decorate(output, spirv::Decoration::Location, literal(0));
+        b.decorate(size, spirv::Decoration::SpecId, literal(3));
+
+        let interface = shader_interface(&b.module());
+        assert_eq!(
+            vec![
+                EntryPointInterface {
+                    name: "main_cs".to_string(),
+                    stage: ShaderStage::Compute,
+                    workgroup_size: Some([8, 8, 1]),
+                    bindings: vec![
+                        ResourceBinding {
+                            group: Some(0),
+                            binding: Some(2),
+                            name: Some("data".to_string()),
+                            kind: ResourceKind::StorageBuffer,
+                            count: 1,
+                        },
+                        ResourceBinding {
+                            group: Some(1),
+                            binding: Some(0),
+                            name: None,
+                            kind: ResourceKind::Sampler,
+                            count: 4,
+                        },
+                    ],
+                    inputs: vec![],
+                    outputs: vec![],
+                },
+                EntryPointInterface {
+                    name: "main_fs".to_string(),
+                    stage: ShaderStage::Fragment,
+                    workgroup_size: None,
+                    bindings: vec![],
+                    inputs: vec![],
+                    outputs: vec![StageVariable {
+                        name: None,
+                        location: Some(0),
+                        builtin: None,
+                        ty: Some(ValueType { kind: ScalarKind::Float, width: 32, components: 4 }),
+                    }],
+                },
+            ],
+            interface.entry_points
+        );
+        assert_eq!(
+            vec![SpecConstant {
+                id: 3,
+                name: Some("SIZE".to_string()),
+                ty: Some(ValueType { kind: ScalarKind::Uint, width: 32, components: 1 }),
+                default: Some(ScalarValue::Uint(64)),
+            }],
+            interface.spec_constants
+        );
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Analyses over the data representation of SPIR-V modules.
+//!
+//! Analyses only inspect modules; they never change them. Their results
+//! are plain data structures which can be queried by transformations and
+//! tools.
+
+pub use self::access_chain::{AccessChainResolver, AccessPath, AccessStep};
+pub use self::cfg::{block_successors, ControlFlowGraph};
+pub use self::divergence::DivergenceAnalysis;
+pub use self::dominators::DominatorTree;
+pub use self::interface::{shader_interface, EntryPointInterface, ResourceBinding, ResourceKind};
+pub use self::interface::{ScalarKind, ScalarValue, ShaderInterface, ShaderStage, SpecConstant};
+pub use self::interface::{StageVariable, ValueType};
+pub use self::reflect::{descriptor_indexing, DescriptorArray, DescriptorIndexing, DescriptorKind};
+pub use self::reflect::{entry_point_resources, DescriptorBinding, EntryPointResources};
+pub use self::runtime_array::{runtime_array_buffers, ArrayLengthQuery, RuntimeArrayBuffer};
+pub use self::stats::{function_stats, usage_stats, FunctionStats, FunctionUsage, UsageStats};
+pub use self::synchronization::{memory_model_capabilities, sync_operations, SyncOperation};
+pub use self::unused_members::{unused_block_members, UnusedBlockMembers};
+pub use self::wgsl::{check_wgsl_compatibility, WgslIssue};
+pub(crate) use self::unused_members::find_unused_block_members;
+
+pub mod ssa;
+
+mod access_chain;
+mod cfg;
+mod divergence;
+mod dominators;
+mod interface;
+mod reflect;
+mod runtime_array;
+mod stats;
+mod synchronization;
+mod unused_members;
+mod wgsl;
@@ -0,0 +1,546 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{BTreeSet, HashMap};
+
+/// A global variable bound through a descriptor, i.e., of the
+/// `UniformConstant`, `Uniform` or `StorageBuffer` storage class.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    /// The result id of the variable.
+    pub variable: Word,
+    /// The storage class of the variable.
+    pub storage_class: spirv::StorageClass,
+    /// The DescriptorSet of the variable, if decorated.
+    pub set: Option<u32>,
+    /// The Binding of the variable, if decorated.
+    pub binding: Option<u32>,
+}
+
+/// The resources statically used by one entry point of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPointResources {
+    /// The name of the entry point.
+    pub name: String,
+    /// The execution model of the entry point.
+    pub execution_model: spirv::ExecutionModel,
+    /// The result id of the entry point function.
+    pub function: Word,
+    /// The descriptor bindings statically used by the entry point, in the
+    /// order of their variables.
+    pub bindings: Vec<DescriptorBinding>,
+    /// The Input variables statically used by the entry point, in the
+    /// order of their declarations.
+    pub inputs: Vec<Word>,
+    /// The Output variables statically used by the entry point, in the
+    /// order of their declarations.
+    pub outputs: Vec<Word>,
+}
+
+/// Returns the global variables referenced by the given function and all
+/// functions it transitively calls. Calls to functions not defined in the
+/// module are ignored.
+fn statically_used_globals(
+    module: &mr::Module,
+    globals: &HashMap<Word, spirv::StorageClass>,
+    function: Word,
+) -> BTreeSet<Word> {
+    let mut used = BTreeSet::new();
+    let mut visited = BTreeSet::new();
+    let mut worklist = vec![function];
+    visited.insert(function);
+    while let Some(id) = worklist.pop() {
+        let function = match module
+            .functions
+            .iter()
+            .find(|f| f.def.as_ref().and_then(|d| d.result_id) == Some(id))
+        {
+            Some(function) => function,
+            None => continue,
+        };
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            for operand in &inst.operands {
+                if let mr::Operand::IdRef(v) = *operand {
+                    if globals.contains_key(&v) {
+                        used.insert(v);
+                    }
+                }
+            }
+            if inst.class.opcode == spirv::Op::FunctionCall {
+                if let Some(&mr::Operand::IdRef(callee)) = inst.operands.first() {
+                    if visited.insert(callee) {
+                        worklist.push(callee);
+                    }
+                }
+            }
+        }
+    }
+    used
+}
+
+/// Groups the descriptor bindings and I/O variables of the given module
+/// per OpEntryPoint, in the order of the entry points.
+///
+/// Only variables statically used by an entry point, i.e., referenced by
+/// its function or any function it calls, are reported for it. This keeps
+/// the resources of modules with several entry points apart, as HLSL
+/// libraries commonly produce. The interface lists of the entry points are
+/// not consulted, since they only list Input and Output variables before
+/// SPIR-V 1.4.
+pub fn entry_point_resources(module: &mr::Module) -> Vec<EntryPointResources> {
+    let globals: HashMap<Word, spirv::StorageClass> = module
+        .types_global_values
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::Variable)
+        .filter_map(|inst| match (inst.result_id, inst.operands.first()) {
+            (Some(id), Some(&mr::Operand::StorageClass(sc))) => Some((id, sc)),
+            _ => None,
+        })
+        .collect();
+    let decorations = module.decoration_index();
+
+    let mut entries = vec![];
+    for entry in &module.entry_points {
+        let (execution_model, function, name) = match &entry.operands[..] {
+            [mr::Operand::ExecutionModel(model), mr::Operand::IdRef(function),
+             mr::Operand::LiteralString(name), ..] => (*model, *function, name.to_string()),
+            _ => continue,
+        };
+        let used = statically_used_globals(module, &globals, function);
+        let mut resources = EntryPointResources {
+            name,
+            execution_model,
+            function,
+            bindings: vec![],
+            inputs: vec![],
+            outputs: vec![],
+        };
+        // Walk the declarations rather than the set to keep their order.
+        for inst in &module.types_global_values {
+            let variable = match inst.result_id {
+                Some(id) if used.contains(&id) => id,
+                _ => continue,
+            };
+            match globals[&variable] {
+                spirv::StorageClass::Input => resources.inputs.push(variable),
+                spirv::StorageClass::Output => resources.outputs.push(variable),
+                storage_class @ spirv::StorageClass::UniformConstant |
+                storage_class @ spirv::StorageClass::Uniform |
+                storage_class @ spirv::StorageClass::StorageBuffer => {
+                    resources.bindings.push(DescriptorBinding {
+                        variable,
+                        storage_class,
+                        set: decorations
+                            .get_decoration_value(variable, spirv::Decoration::DescriptorSet),
+                        binding: decorations
+                            .get_decoration_value(variable, spirv::Decoration::Binding),
+                    })
+                }
+                _ => {}
+            }
+        }
+        entries.push(resources);
+    }
+    entries
+}
+
+/// The kind of descriptor bound to a variable, as far as descriptor
+/// indexing features distinguish them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorKind {
+    UniformBuffer,
+    StorageBuffer,
+    /// Sampled images, samplers, and combined image samplers.
+    SampledImage,
+    StorageImage,
+    UniformTexelBuffer,
+    StorageTexelBuffer,
+    InputAttachment,
+}
+
+impl DescriptorKind {
+    /// Returns the capability needed to index arrays of this kind of
+    /// descriptor with dynamically uniform indices.
+    pub fn dynamic_indexing_capability(self) -> spirv::Capability {
+        match self {
+            DescriptorKind::UniformBuffer => spirv::Capability::UniformBufferArrayDynamicIndexing,
+            DescriptorKind::StorageBuffer => spirv::Capability::StorageBufferArrayDynamicIndexing,
+            DescriptorKind::SampledImage => spirv::Capability::SampledImageArrayDynamicIndexing,
+            DescriptorKind::StorageImage => spirv::Capability::StorageImageArrayDynamicIndexing,
+            DescriptorKind::UniformTexelBuffer => {
+                spirv::Capability::UniformTexelBufferArrayDynamicIndexingEXT
+            }
+            DescriptorKind::StorageTexelBuffer => {
+                spirv::Capability::StorageTexelBufferArrayDynamicIndexingEXT
+            }
+            DescriptorKind::InputAttachment => {
+                spirv::Capability::InputAttachmentArrayDynamicIndexingEXT
+            }
+        }
+    }
+
+    /// Returns the capability needed to index arrays of this kind of
+    /// descriptor with non-uniform indices.
+    pub fn non_uniform_indexing_capability(self) -> spirv::Capability {
+        match self {
+            DescriptorKind::UniformBuffer => {
+                spirv::Capability::UniformBufferArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::StorageBuffer => {
+                spirv::Capability::StorageBufferArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::SampledImage => {
+                spirv::Capability::SampledImageArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::StorageImage => {
+                spirv::Capability::StorageImageArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::UniformTexelBuffer => {
+                spirv::Capability::UniformTexelBufferArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::StorageTexelBuffer => {
+                spirv::Capability::StorageTexelBufferArrayNonUniformIndexingEXT
+            }
+            DescriptorKind::InputAttachment => {
+                spirv::Capability::InputAttachmentArrayNonUniformIndexingEXT
+            }
+        }
+    }
+}
+
+/// An array of descriptors bound to a single variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescriptorArray {
+    /// The result id of the variable.
+    pub variable: Word,
+    /// The kind of the descriptors.
+    pub kind: DescriptorKind,
+    /// Whether the array is runtime-sized, i.e., bindless.
+    pub runtime: bool,
+    /// Whether the array is indexed with an index which is not a constant.
+    pub dynamically_indexed: bool,
+    /// Whether the array is indexed non-uniformly, i.e., the index or the
+    /// resulting access chain is decorated as NonUniform.
+    pub non_uniform_indexed: bool,
+}
+
+/// The descriptor indexing features used by a module.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DescriptorIndexing {
+    /// All arrays of descriptors, in the order of their variables.
+    pub arrays: Vec<DescriptorArray>,
+    /// The capabilities needed for indexing them, in the order of
+    /// the first array needing each, preceded by `RuntimeDescriptorArrayEXT`
+    /// and `ShaderNonUniformEXT` if needed.
+    pub capabilities: Vec<spirv::Capability>,
+}
+
+/// Returns the kind of the descriptor of the given type in the given
+/// storage class, if it is one.
+pub(super) fn descriptor_kind(
+    defs: &HashMap<Word, &mr::Instruction>,
+    decorations: &mr::DecorationIndex,
+    storage_class: spirv::StorageClass,
+    ty: Word,
+) -> Option<DescriptorKind> {
+    let def = defs.get(&ty)?;
+    let image_kind = |image: &mr::Instruction| {
+        match (image.operands.get(1), image.operands.get(5)) {
+            (Some(&mr::Operand::Dim(spirv::Dim::DimSubpassData)), _) => {
+                DescriptorKind::InputAttachment
+            }
+            (Some(&mr::Operand::Dim(spirv::Dim::DimBuffer)),
+             Some(&mr::Operand::LiteralInt32(2))) => DescriptorKind::StorageTexelBuffer,
+            (Some(&mr::Operand::Dim(spirv::Dim::DimBuffer)), _) => {
+                DescriptorKind::UniformTexelBuffer
+            }
+            (_, Some(&mr::Operand::LiteralInt32(2))) => DescriptorKind::StorageImage,
+            _ => DescriptorKind::SampledImage,
+        }
+    };
+    match (storage_class, def.class.opcode) {
+        (spirv::StorageClass::StorageBuffer, _) => Some(DescriptorKind::StorageBuffer),
+        (spirv::StorageClass::Uniform, spirv::Op::TypeStruct) => {
+            if decorations.has_decoration(ty, spirv::Decoration::BufferBlock) {
+                Some(DescriptorKind::StorageBuffer)
+            } else {
+                Some(DescriptorKind::UniformBuffer)
+            }
+        }
+        (spirv::StorageClass::UniformConstant, spirv::Op::TypeImage) => Some(image_kind(def)),
+        (spirv::StorageClass::UniformConstant, spirv::Op::TypeSampledImage) => {
+            match def.operands.first() {
+                Some(&mr::Operand::IdRef(image)) => defs.get(&image).map(|i| image_kind(i)),
+                _ => None,
+            }
+        }
+        (spirv::StorageClass::UniformConstant, spirv::Op::TypeSampler) => {
+            Some(DescriptorKind::SampledImage)
+        }
+        _ => None,
+    }
+}
+
+/// Detects the use of descriptor indexing features in the given module:
+/// runtime-sized (bindless) arrays of descriptors, dynamic and non-uniform
+/// indexing into arrays of descriptors, and the capabilities they need, so
+/// that engines can enable the matching device features at pipeline
+/// creation.
+///
+/// An array is indexed non-uniformly if the index of an access chain into
+/// it, or the access chain itself, is decorated as NonUniform.
+pub fn descriptor_indexing(module: &mr::Module) -> DescriptorIndexing {
+    let defs: HashMap<Word, &mr::Instruction> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+        .collect();
+    let decorations = module.decoration_index();
+    let non_uniform = |id: Word| decorations.has_decoration(id, spirv::Decoration::NonUniformEXT);
+    let id_operand = |inst: &mr::Instruction, index: usize| match inst.operands.get(index) {
+        Some(&mr::Operand::IdRef(id)) => Some(id),
+        _ => None,
+    };
+
+    let mut indexing = DescriptorIndexing::default();
+    for inst in &module.types_global_values {
+        let (variable, storage_class) = match (inst.result_id, inst.operands.first()) {
+            (Some(id), Some(&mr::Operand::StorageClass(sc)))
+                if inst.class.opcode == spirv::Op::Variable => (id, sc),
+            _ => continue,
+        };
+        let array = match inst
+            .result_type
+            .and_then(|ty| defs.get(&ty))
+            .and_then(|pointer| id_operand(pointer, 1))
+            .and_then(|pointee| defs.get(&pointee))
+        {
+            Some(array) if array.class.opcode == spirv::Op::TypeArray ||
+                array.class.opcode == spirv::Op::TypeRuntimeArray => array,
+            _ => continue,
+        };
+        let kind = match id_operand(array, 0)
+            .and_then(|element| descriptor_kind(&defs, &decorations, storage_class, element))
+        {
+            Some(kind) => kind,
+            None => continue,
+        };
+        indexing.arrays.push(DescriptorArray {
+            variable,
+            kind,
+            runtime: array.class.opcode == spirv::Op::TypeRuntimeArray,
+            dynamically_indexed: false,
+            non_uniform_indexed: false,
+        });
+    }
+
+    for inst in module.functions.iter().flat_map(|f| f.all_inst_iter()) {
+        match inst.class.opcode {
+            spirv::Op::AccessChain | spirv::Op::InBoundsAccessChain => {}
+            _ => continue,
+        }
+        let array = match id_operand(inst, 0)
+            .and_then(|base| indexing.arrays.iter_mut().find(|a| a.variable == base))
+        {
+            Some(array) => array,
+            None => continue,
+        };
+        let index = match id_operand(inst, 1) {
+            Some(index) => index,
+            None => continue,
+        };
+        let constant = defs.get(&index).is_some_and(|def| def.class.opcode == spirv::Op::Constant);
+        array.dynamically_indexed |= !constant;
+        array.non_uniform_indexed |=
+            non_uniform(index) || inst.result_id.is_some_and(&non_uniform);
+    }
+
+    let mut capabilities = vec![];
+    if indexing.arrays.iter().any(|a| a.runtime) {
+        capabilities.push(spirv::Capability::RuntimeDescriptorArrayEXT);
+    }
+    if module.annotations.iter().any(|inst| {
+        inst.operands.get(1) == Some(&mr::Operand::Decoration(spirv::Decoration::NonUniformEXT))
+    }) {
+        capabilities.push(spirv::Capability::ShaderNonUniformEXT);
+    }
+    for array in &indexing.arrays {
+        if array.dynamically_indexed {
+            capabilities.push(array.kind.dynamic_indexing_capability());
+        }
+        if array.non_uniform_indexed {
+            capabilities.push(array.kind.non_uniform_indexing_capability());
+        }
+    }
+    for capability in capabilities {
+        if !indexing.capabilities.contains(&capability) {
+            indexing.capabilities.push(capability);
+        }
+    }
+    indexing
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::{descriptor_indexing, entry_point_resources, DescriptorBinding};
+    use analysis::{DescriptorArray, DescriptorKind};
+    use mr;
+    use spirv;
+
+    #[test]
+    fn test_entry_point_resources() {
+        use spirv::Decoration::{Binding, DescriptorSet};
+        use spirv::StorageClass::{Input, Output, StorageBuffer, Uniform};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let block = b.type_struct(vec![float]);
+        let ptr_uniform = b.type_pointer(None, Uniform, block);
+        let ptr_storage = b.type_pointer(None, StorageBuffer, block);
+        let ptr_input = b.type_pointer(None, Input, float);
+        let ptr_output = b.type_pointer(None, Output, float);
+        let voidf = b.type_function(void, vec![]);
+        let ubo = b.variable(ptr_uniform, None, Uniform, None);
+        let ssbo = b.variable(ptr_storage, None, StorageBuffer, None);
+        let input = b.variable(ptr_input, None, Input, None);
+        let output = b.variable(ptr_output, None, Output, None);
+        b.decorate(ubo, DescriptorSet, vec![mr::Operand::LiteralInt32(0)]);
+        b.decorate(ubo, Binding, vec![mr::Operand::LiteralInt32(1)]);
+        b.decorate(ssbo, DescriptorSet, vec![mr::Operand::LiteralInt32(2)]);
+
+        // A helper reading the uniform buffer, called by the first entry.
+        let helper = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.load(block, None, ubo, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let first = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.function_call(void, None, helper, vec![]).unwrap();
+        let value = b.load(float, None, input, None, vec![]).unwrap();
+        b.store(output, value, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let second = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.load(block, None, ssbo, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        b.entry_point(spirv::ExecutionModel::Fragment, first, "first", vec![input, output]);
+        b.entry_point(spirv::ExecutionModel::GLCompute, second, "second", vec![]);
+
+        let entries = entry_point_resources(&b.module());
+        assert_eq!(2, entries.len());
+        assert_eq!("first", entries[0].name);
+        assert_eq!(first, entries[0].function);
+        assert_eq!(
+            vec![DescriptorBinding {
+                variable: ubo,
+                storage_class: Uniform,
+                set: Some(0),
+                binding: Some(1),
+            }],
+            entries[0].bindings
+        );
+        assert_eq!(vec![input], entries[0].inputs);
+        assert_eq!(vec![output], entries[0].outputs);
+
+        assert_eq!(spirv::ExecutionModel::GLCompute, entries[1].execution_model);
+        assert_eq!(
+            vec![DescriptorBinding {
+                variable: ssbo,
+                storage_class: StorageBuffer,
+                set: Some(2),
+                binding: None,
+            }],
+            entries[1].bindings
+        );
+        assert!(entries[1].inputs.is_empty());
+        assert!(entries[1].outputs.is_empty());
+    }
+
+    #[test]
+    fn test_descriptor_indexing() {
+        use spirv::Capability::*;
+        use spirv::StorageClass::{StorageBuffer, UniformConstant};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let uint = b.type_int(32, 0);
+        let block = b.type_struct(vec![float]);
+        let image = b.type_image(float, spirv::Dim::Dim2D, 0, 0, 0, 1,
+                                 spirv::ImageFormat::Unknown, None);
+        let four = b.constant_u32(uint, 4);
+        let zero = b.constant_u32(uint, 0);
+        let images = b.type_runtime_array(image);
+        let blocks = b.type_array(block, four);
+        let ptr_images = b.type_pointer(None, UniformConstant, images);
+        let ptr_image = b.type_pointer(None, UniformConstant, image);
+        let ptr_blocks = b.type_pointer(None, StorageBuffer, blocks);
+        let ptr_block = b.type_pointer(None, StorageBuffer, block);
+        let ptr_uint = b.type_pointer(None, spirv::StorageClass::Function, uint);
+        let voidf = b.type_function(void, vec![]);
+        let textures = b.variable(ptr_images, None, UniformConstant, None);
+        let buffers = b.variable(ptr_blocks, None, StorageBuffer, None);
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(ptr_uint, None, spirv::StorageClass::Function, None);
+        let index = b.load(uint, None, var, None, vec![]).unwrap();
+        b.decorate(index, spirv::Decoration::NonUniformEXT, vec![]);
+        b.access_chain(ptr_image, None, textures, vec![index]).unwrap();
+        b.access_chain(ptr_block, None, buffers, vec![zero]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let indexing = descriptor_indexing(&b.module());
+        assert_eq!(
+            vec![
+                DescriptorArray {
+                    variable: textures,
+                    kind: DescriptorKind::SampledImage,
+                    runtime: true,
+                    dynamically_indexed: true,
+                    non_uniform_indexed: true,
+                },
+                DescriptorArray {
+                    variable: buffers,
+                    kind: DescriptorKind::StorageBuffer,
+                    runtime: false,
+                    dynamically_indexed: false,
+                    non_uniform_indexed: false,
+                },
+            ],
+            indexing.arrays
+        );
+        assert_eq!(
+            vec![
+                RuntimeDescriptorArrayEXT,
+                ShaderNonUniformEXT,
+                SampledImageArrayDynamicIndexing,
+                SampledImageArrayNonUniformIndexingEXT,
+            ],
+            indexing.capabilities
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// An OpArrayLength instruction querying the length of a runtime-sized
+/// array.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ArrayLengthQuery {
+    /// The result id of the function containing the instruction.
+    pub function: Word,
+    /// The result id of the OpArrayLength instruction.
+    pub result_id: Word,
+}
+
+/// A buffer variable whose block ends in a runtime-sized array member.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuntimeArrayBuffer {
+    /// The result id of the variable.
+    pub variable: Word,
+    /// The result id of the block struct type. For arrays of buffers, this
+    /// is the element type of the (possibly nested) arrays.
+    pub block: Word,
+    /// The index of the runtime-sized array member in the block.
+    pub member: u32,
+    /// The result id of the OpTypeRuntimeArray.
+    pub array: Word,
+    /// The Offset of the runtime-sized array member, i.e., the size of the
+    /// fixed part of the block, if decorated.
+    pub offset: Option<u32>,
+    /// The ArrayStride of the runtime-sized array, if decorated.
+    pub stride: Option<u32>,
+    /// All OpArrayLength instructions querying the length of the array
+    /// through this variable.
+    pub length_queries: Vec<ArrayLengthQuery>,
+}
+
+impl RuntimeArrayBuffer {
+    /// Returns the minimum size in bytes of a buffer bound to this variable
+    /// which holds `count` array elements, i.e., offset + stride × count.
+    ///
+    /// Returns `None` if the layout of the block is not fully decorated.
+    pub fn min_buffer_size(&self, count: u64) -> Option<u64> {
+        Some(u64::from(self.offset?) + u64::from(self.stride?) * count)
+    }
+}
+
+impl fmt::Display for RuntimeArrayBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{}: member {} of %{}, ", self.variable, self.member, self.block)?;
+        match (self.offset, self.stride) {
+            (Some(offset), Some(stride)) => write!(f, "{} + {} * N bytes", offset, stride)?,
+            _ => f.write_str("unknown layout")?,
+        }
+        write!(f, ", {} length queries", self.length_queries.len())
+    }
+}
+
+/// Finds all global variables of blocks ending in a runtime-sized array,
+/// in the order of the variables, together with the OpArrayLength
+/// instructions querying the array lengths.
+///
+/// Engines need the fixed part and the stride of such blocks to validate
+/// the size of the buffers bound to them; see
+/// [`RuntimeArrayBuffer::min_buffer_size`](struct.RuntimeArrayBuffer.html#method.min_buffer_size).
+pub fn runtime_array_buffers(module: &mr::Module) -> Vec<RuntimeArrayBuffer> {
+    let defs: HashMap<Word, &mr::Instruction> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+        .collect();
+    let mut offsets = HashMap::new();
+    let mut strides = HashMap::new();
+    for inst in &module.annotations {
+        match &inst.operands[..] {
+            [mr::Operand::IdRef(target),
+             mr::Operand::Decoration(spirv::Decoration::ArrayStride),
+             mr::Operand::LiteralInt32(stride)] => {
+                strides.insert(*target, *stride);
+            }
+            [mr::Operand::IdRef(target),
+             mr::Operand::LiteralInt32(member),
+             mr::Operand::Decoration(spirv::Decoration::Offset),
+             mr::Operand::LiteralInt32(offset)] => {
+                offsets.insert((*target, *member), *offset);
+            }
+            _ => {}
+        }
+    }
+    let id_operand = |inst: &mr::Instruction, index: usize| match inst.operands.get(index) {
+        Some(&mr::Operand::IdRef(id)) => Some(id),
+        _ => None,
+    };
+
+    let mut buffers = vec![];
+    for inst in &module.types_global_values {
+        if inst.class.opcode != spirv::Op::Variable {
+            continue;
+        }
+        let pointer = inst.result_type.and_then(|ty| defs.get(&ty));
+        let mut block = match pointer.and_then(|p| id_operand(p, 1)) {
+            Some(pointee) => pointee,
+            None => continue,
+        };
+        // Look through arrays of buffers.
+        while let Some(def) = defs.get(&block) {
+            match def.class.opcode {
+                spirv::Op::TypeArray | spirv::Op::TypeRuntimeArray => match id_operand(def, 0) {
+                    Some(element) => block = element,
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        let members = match defs.get(&block) {
+            Some(def) if def.class.opcode == spirv::Op::TypeStruct && !def.operands.is_empty() => {
+                def.operands.len()
+            }
+            _ => continue,
+        };
+        let member = (members - 1) as u32;
+        let array = match id_operand(defs[&block], members - 1) {
+            Some(array) if defs.get(&array).map(|d| d.class.opcode) ==
+                Some(spirv::Op::TypeRuntimeArray) => array,
+            _ => continue,
+        };
+        buffers.push(RuntimeArrayBuffer {
+            variable: inst.result_id.unwrap_or(0),
+            block,
+            member,
+            array,
+            offset: offsets.get(&(block, member)).cloned(),
+            stride: strides.get(&array).cloned(),
+            length_queries: vec![],
+        });
+    }
+
+    for function in &module.functions {
+        let function_id = function.def.as_ref().and_then(|d| d.result_id).unwrap_or(0);
+        // The variable each pointer in the function is derived from.
+        let mut roots: HashMap<Word, Word> = HashMap::new();
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            match inst.class.opcode {
+                spirv::Op::AccessChain |
+                spirv::Op::InBoundsAccessChain |
+                spirv::Op::PtrAccessChain |
+                spirv::Op::InBoundsPtrAccessChain |
+                spirv::Op::CopyObject => {
+                    if let (Some(id), Some(base)) = (inst.result_id, id_operand(inst, 0)) {
+                        let root = roots.get(&base).cloned().unwrap_or(base);
+                        roots.insert(id, root);
+                    }
+                }
+                spirv::Op::ArrayLength => {
+                    let (structure, member) = match (id_operand(inst, 0), inst.operands.get(1)) {
+                        (Some(s), Some(&mr::Operand::LiteralInt32(m))) => (s, m),
+                        _ => continue,
+                    };
+                    let root = roots.get(&structure).cloned().unwrap_or(structure);
+                    let buffer = buffers
+                        .iter_mut()
+                        .find(|b| b.variable == root && b.member == member);
+                    if let (Some(buffer), Some(result_id)) = (buffer, inst.result_id) {
+                        buffer.length_queries.push(ArrayLengthQuery {
+                            function: function_id,
+                            result_id,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    buffers
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::{runtime_array_buffers, ArrayLengthQuery};
+    use mr;
+    use spirv;
+
+    #[test]
+    fn test_runtime_array_buffers() {
+        use spirv::Decoration::{ArrayStride, Block, Offset};
+        use spirv::StorageClass::{StorageBuffer, Uniform};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let runtime = b.type_runtime_array(vec4);
+        let block = b.type_struct(vec![uint, runtime]);
+        let two = b.constant_u32(uint, 2);
+        let blocks = b.type_array(block, two);
+        let fixed = b.type_struct(vec![vec4]);
+        let ptr = b.type_pointer(None, StorageBuffer, block);
+        let ptr_array = b.type_pointer(None, StorageBuffer, blocks);
+        let ptr_fixed = b.type_pointer(None, Uniform, fixed);
+        let voidf = b.type_function(void, vec![]);
+        let arrayed = b.variable(ptr_array, None, StorageBuffer, None);
+        let single = b.variable(ptr, None, StorageBuffer, None);
+        b.variable(ptr_fixed, None, Uniform, None);
+        b.decorate(block, Block, vec![]);
+        b.decorate(runtime, ArrayStride, vec![mr::Operand::LiteralInt32(16)]);
+        b.member_decorate(block, 0, Offset, vec![mr::Operand::LiteralInt32(0)]);
+        b.member_decorate(block, 1, Offset, vec![mr::Operand::LiteralInt32(16)]);
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let element = b.access_chain(ptr, None, arrayed, vec![two]).unwrap();
+        let first = b.array_length(uint, None, element, 1).unwrap();
+        let second = b.array_length(uint, None, single, 1).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let buffers = runtime_array_buffers(&b.module());
+        assert_eq!(2, buffers.len());
+        assert_eq!(arrayed, buffers[0].variable);
+        assert_eq!(block, buffers[0].block);
+        assert_eq!((1, runtime), (buffers[0].member, buffers[0].array));
+        assert_eq!(
+            vec![ArrayLengthQuery { function: main, result_id: first }],
+            buffers[0].length_queries
+        );
+        assert_eq!(Some(16), buffers[0].min_buffer_size(0));
+        assert_eq!(Some(16 + 16 * 100), buffers[0].min_buffer_size(100));
+        assert_eq!(
+            vec![ArrayLengthQuery { function: main, result_id: second }],
+            buffers[1].length_queries
+        );
+        assert_eq!(
+            "%15: member 1 of %6, 16 + 16 * N bytes, 1 length queries",
+            buffers[1].to_string()
+        );
+    }
+}
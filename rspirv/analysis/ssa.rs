@@ -0,0 +1,566 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for the static single assignment (SSA) form of functions.
+//!
+//! [`promote_variables`](fn.promote_variables.html) rewrites function-local
+//! variables only accessed via whole OpLoad and OpStore instructions into
+//! SSA values, inserting OpPhi instructions where needed (mem2reg).
+//! [`eliminate_phis`](fn.eliminate_phis.html) does the reverse for all
+//! OpPhi instructions.
+
+use mr;
+use spirv;
+
+use super::{ControlFlowGraph, DominatorTree};
+use spirv::Word;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::btree_map::Entry;
+
+/// Returns the incoming (value, parent block label) pairs of the given
+/// OpPhi instruction.
+pub fn phi_incoming(inst: &mr::Instruction) -> Vec<(Word, Word)> {
+    inst.operands
+        .chunks(2)
+        .filter_map(|pair| match *pair {
+            [mr::Operand::IdRef(value), mr::Operand::IdRef(parent)] => Some((value, parent)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Promotes function-local variables to SSA values.
+///
+/// A variable is promoted if it is declared in the Function storage class
+/// and is only used as the pointer of OpLoad and OpStore instructions
+/// without memory access operands. Loads are replaced by the value last
+/// stored (or the initializer, or an OpUndef if there is none), and
+/// OpPhi instructions are inserted at the iterated dominance frontiers of
+/// the stores. The variables, loads, and stores are removed afterwards,
+/// together with debug names and decorations of the variables.
+///
+/// Inserted OpPhi instructions are not pruned, so some of them may be
+/// unused.
+///
+/// Returns the number of variables promoted.
+pub fn promote_variables(module: &mut mr::Module) -> usize {
+    let mut promoted = HashSet::new();
+    let mut undefs = HashMap::new();
+    for index in 0..module.functions.len() {
+        let mut function = ::std::mem::take(&mut module.functions[index]);
+        promoted.extend(promote_in(module, &mut undefs, &mut function));
+        module.functions[index] = function;
+    }
+    if !promoted.is_empty() {
+        let refers_to_promoted = |inst: &mr::Instruction| match inst.operands.first() {
+            Some(mr::Operand::IdRef(id)) => promoted.contains(id),
+            _ => false,
+        };
+        module.debugs.retain(|inst| !refers_to_promoted(inst));
+        module.annotations.retain(|inst| !refers_to_promoted(inst));
+    }
+    promoted.len()
+}
+
+/// A load or store of a promoted variable.
+enum Access {
+    Load { var: Word, result: Word },
+    Store { var: Word, value: Word },
+}
+
+struct Promotion<'a> {
+    cfg: ControlFlowGraph,
+    children: Vec<Vec<usize>>,
+    /// Accesses of promoted variables in each block, in order.
+    accesses: Vec<Vec<Access>>,
+    /// Phi result ids for (block, variable).
+    phis: BTreeMap<(usize, Word), Word>,
+    /// Incoming (value, parent) pairs for each phi result id.
+    incoming: HashMap<Word, Vec<(Word, Word)>>,
+    /// Initial value of each variable.
+    initial: &'a HashMap<Word, Word>,
+    /// Values replacing load results.
+    replacements: HashMap<Word, Word>,
+}
+
+impl<'a> Promotion<'a> {
+    fn rename(&mut self, block: usize, stacks: &mut HashMap<Word, Vec<Word>>) {
+        let mut pushed = vec![];
+        for (&(_, var), &phi) in self.phis.range((block, 0)..(block + 1, 0)) {
+            stacks.get_mut(&var).unwrap().push(phi);
+            pushed.push(var);
+        }
+        for access in &self.accesses[block] {
+            match *access {
+                Access::Load { var, result } => {
+                    let value = *stacks[&var].last().unwrap_or(&self.initial[&var]);
+                    self.replacements.insert(result, value);
+                }
+                Access::Store { var, value } => {
+                    stacks.get_mut(&var).unwrap().push(value);
+                    pushed.push(var);
+                }
+            }
+        }
+        let label = self.cfg.label(block);
+        for &succ in self.cfg.successors(block) {
+            for (&(_, var), &phi) in self.phis.range((succ, 0)..(succ + 1, 0)) {
+                let value = *stacks[&var].last().unwrap_or(&self.initial[&var]);
+                self.incoming.entry(phi).or_default().push((value, label));
+            }
+        }
+        for child in self.children[block].clone() {
+            self.rename(child, stacks);
+        }
+        for var in pushed {
+            stacks.get_mut(&var).unwrap().pop();
+        }
+    }
+}
+
+/// Promotes variables in the given `function` of `module`, and returns the
+/// promoted variables.
+fn promote_in(
+    module: &mut mr::Module,
+    undefs: &mut HashMap<Word, Word>,
+    function: &mut mr::Function,
+) -> Vec<Word> {
+    if function.basic_blocks.is_empty() {
+        return vec![];
+    }
+
+    // Candidate variables and their pointee types.
+    let mut types: HashMap<Word, Word> = HashMap::new();
+    let mut initializers: HashMap<Word, Word> = HashMap::new();
+    for inst in &function.basic_blocks[0].instructions {
+        if inst.class.opcode != spirv::Op::Variable {
+            continue;
+        }
+        let is_local = inst.operands.first() ==
+            Some(&mr::Operand::StorageClass(spirv::StorageClass::Function));
+        let pointee = inst.result_type.and_then(|ty| pointee_type(module, ty));
+        if let (true, Some(id), Some(pointee)) = (is_local, inst.result_id, pointee) {
+            types.insert(id, pointee);
+            if let Some(&mr::Operand::IdRef(init)) = inst.operands.get(1) {
+                initializers.insert(id, init);
+            }
+        }
+    }
+    for inst in function.all_inst_iter() {
+        if inst.class.opcode == spirv::Op::Variable {
+            continue;
+        }
+        for (index, operand) in inst.operands.iter().enumerate() {
+            if let mr::Operand::IdRef(id) = *operand {
+                let allowed = index == 0 &&
+                    match inst.class.opcode {
+                        spirv::Op::Load => inst.operands.len() == 1,
+                        spirv::Op::Store => inst.operands.len() == 2,
+                        _ => false,
+                    };
+                if !allowed {
+                    types.remove(&id);
+                }
+            }
+        }
+    }
+    if types.is_empty() {
+        return vec![];
+    }
+    let mut vars: Vec<Word> = types.keys().cloned().collect();
+    vars.sort();
+
+    let mut initial = HashMap::new();
+    for &var in &vars {
+        let value = match initializers.get(&var) {
+            Some(&init) => init,
+            None => undef(module, undefs, types[&var]),
+        };
+        initial.insert(var, value);
+    }
+
+    let cfg = ControlFlowGraph::new(function);
+    let domtree = DominatorTree::new(&cfg);
+    let frontiers = domtree.dominance_frontiers(&cfg);
+    let accesses: Vec<Vec<Access>> = function
+        .basic_blocks
+        .iter()
+        .map(|bb| {
+            bb.instructions
+                .iter()
+                .filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+                    (spirv::Op::Load, &[mr::Operand::IdRef(var)]) if types.contains_key(&var) => {
+                        inst.result_id.map(|result| Access::Load { var, result })
+                    }
+                    (spirv::Op::Store, &[mr::Operand::IdRef(var), mr::Operand::IdRef(value)])
+                        if types.contains_key(&var) => {
+                        Some(Access::Store { var, value })
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect();
+
+    // Place phis at the iterated dominance frontiers of the stores.
+    let mut phis = BTreeMap::new();
+    for &var in &vars {
+        let mut worklist: Vec<usize> = (0..cfg.len())
+            .filter(|&b| {
+                accesses[b].iter().any(|a| matches!(*a, Access::Store { var: v, .. } if v == var))
+            })
+            .collect();
+        while let Some(block) = worklist.pop() {
+            for &frontier in &frontiers[block] {
+                if let Entry::Vacant(e) = phis.entry((frontier, var)) {
+                    e.insert(module.allocate_id());
+                    worklist.push(frontier);
+                }
+            }
+        }
+    }
+
+    let mut promotion = Promotion {
+        cfg,
+        children: domtree.children(),
+        accesses,
+        phis,
+        incoming: HashMap::new(),
+        initial: &initial,
+        replacements: HashMap::new(),
+    };
+    let mut stacks = vars.iter().map(|&v| (v, vec![])).collect();
+    promotion.rename(0, &mut stacks);
+
+    // Loads in unreachable blocks read undefined values; unreachable
+    // predecessors of phis provide undefined values.
+    for (block, accesses) in promotion.accesses.iter().enumerate() {
+        if domtree.is_reachable(block) {
+            continue;
+        }
+        for access in accesses {
+            if let Access::Load { var, result } = *access {
+                let value = undef(module, undefs, types[&var]);
+                promotion.replacements.insert(result, value);
+            }
+        }
+    }
+    for (&(block, var), &phi) in &promotion.phis {
+        for &pred in promotion.cfg.predecessors(block) {
+            if !domtree.is_reachable(pred) {
+                let value = undef(module, undefs, types[&var]);
+                let label = promotion.cfg.label(pred);
+                promotion.incoming.entry(phi).or_default().push((value, label));
+            }
+        }
+    }
+
+    let Promotion {
+        phis,
+        incoming,
+        replacements,
+        ..
+    } = promotion;
+    let resolve = |mut id: Word| {
+        while let Some(&next) = replacements.get(&id) {
+            id = next;
+        }
+        id
+    };
+    for (index, block) in function.basic_blocks.iter_mut().enumerate() {
+        block.instructions.retain(|inst| match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::Variable, _) => !inst.result_id.is_some_and(|id| types.contains_key(&id)),
+            (spirv::Op::Load, Some(mr::Operand::IdRef(var))) |
+            (spirv::Op::Store, Some(mr::Operand::IdRef(var))) => !types.contains_key(var),
+            _ => true,
+        });
+        let phis = phis.range((index, 0)..(index + 1, 0)).map(|(&(_, var), &phi)| {
+            let operands = incoming[&phi]
+                .iter()
+                .flat_map(|&(value, parent)| vec![value, parent])
+                .map(mr::Operand::IdRef)
+                .collect();
+            mr::Instruction::new(spirv::Op::Phi, Some(types[&var]), Some(phi), operands)
+        });
+        let rest = ::std::mem::take(&mut block.instructions);
+        block.instructions = phis.chain(rest).collect();
+        for inst in &mut block.instructions {
+            for operand in &mut inst.operands {
+                if let mr::Operand::IdRef(ref mut id) = *operand {
+                    *id = resolve(*id);
+                }
+            }
+        }
+    }
+    vars
+}
+
+/// Returns the pointee type of the given pointer type.
+fn pointee_type(module: &mr::Module, pointer: Word) -> Option<Word> {
+    module
+        .types_global_values
+        .iter()
+        .find(|inst| inst.result_id == Some(pointer) && inst.class.opcode == spirv::Op::TypePointer)
+        .and_then(|inst| match inst.operands.get(1) {
+            Some(&mr::Operand::IdRef(pointee)) => Some(pointee),
+            _ => None,
+        })
+}
+
+/// Returns the id of a global OpUndef of the given type, creating it if
+/// needed.
+fn undef(module: &mut mr::Module, undefs: &mut HashMap<Word, Word>, ty: Word) -> Word {
+    if let Some(&id) = undefs.get(&ty) {
+        return id;
+    }
+    let existing = module.types_global_values.iter().find(|inst| {
+        inst.class.opcode == spirv::Op::Undef && inst.result_type == Some(ty)
+    });
+    let id = match existing.and_then(|inst| inst.result_id) {
+        Some(id) => id,
+        None => {
+            let id = module.allocate_id();
+            module
+                .types_global_values
+                .push(mr::Instruction::new(spirv::Op::Undef, Some(ty), Some(id), vec![]));
+            id
+        }
+    };
+    undefs.insert(ty, id);
+    id
+}
+
+/// Returns the id of the pointer type to the given type in the Function
+/// storage class, creating it if needed.
+fn function_pointer_type(module: &mut mr::Module, ty: Word) -> Word {
+    let operands = [
+        mr::Operand::StorageClass(spirv::StorageClass::Function),
+        mr::Operand::IdRef(ty),
+    ];
+    let existing = module.types_global_values.iter().find(|inst| {
+        inst.class.opcode == spirv::Op::TypePointer && inst.operands == operands
+    });
+    if let Some(id) = existing.and_then(|inst| inst.result_id) {
+        return id;
+    }
+    let id = module.allocate_id();
+    module.types_global_values.push(mr::Instruction::new(
+        spirv::Op::TypePointer,
+        None,
+        Some(id),
+        operands.to_vec(),
+    ));
+    id
+}
+
+/// Replaces all OpPhi instructions with function-local variables.
+///
+/// For each OpPhi, a variable is declared in the entry block of the
+/// function; each predecessor stores the incoming value into the variable
+/// right before its terminator, and the OpPhi is replaced by an OpLoad of
+/// the variable with the same result id.
+///
+/// Returns the number of OpPhi instructions eliminated.
+pub fn eliminate_phis(module: &mut mr::Module) -> usize {
+    let mut count = 0;
+    for index in 0..module.functions.len() {
+        let mut function = ::std::mem::take(&mut module.functions[index]);
+        count += eliminate_phis_in(module, &mut function);
+        module.functions[index] = function;
+    }
+    count
+}
+
+fn eliminate_phis_in(module: &mut mr::Module, function: &mut mr::Function) -> usize {
+    let labels: HashMap<Word, usize> = function
+        .basic_blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, bb)| bb.label.as_ref().and_then(|l| l.result_id).map(|l| (l, i)))
+        .collect();
+    let mut variables = vec![];
+    // Stores to insert into each block before its terminator.
+    let mut stores: HashMap<usize, Vec<mr::Instruction>> = HashMap::new();
+    for block in &mut function.basic_blocks {
+        for inst in &mut block.instructions {
+            if inst.class.opcode != spirv::Op::Phi {
+                continue;
+            }
+            let ty = inst.result_type.unwrap();
+            let pointer = function_pointer_type(module, ty);
+            let var = module.allocate_id();
+            variables.push(mr::Instruction::new(
+                spirv::Op::Variable,
+                Some(pointer),
+                Some(var),
+                vec![mr::Operand::StorageClass(spirv::StorageClass::Function)],
+            ));
+            for (value, parent) in phi_incoming(inst) {
+                if let Some(&pred) = labels.get(&parent) {
+                    stores.entry(pred).or_default().push(mr::Instruction::new(
+                        spirv::Op::Store,
+                        None,
+                        None,
+                        vec![mr::Operand::IdRef(var), mr::Operand::IdRef(value)],
+                    ));
+                }
+            }
+            *inst = mr::Instruction::new(
+                spirv::Op::Load,
+                Some(ty),
+                inst.result_id,
+                vec![mr::Operand::IdRef(var)],
+            );
+        }
+    }
+    for (pred, stores) in stores {
+        let block = &mut function.basic_blocks[pred];
+        let mut at = block.instructions.len().saturating_sub(1);
+        if at > 0 {
+            let opcode = block.instructions[at - 1].class.opcode;
+            if opcode == spirv::Op::SelectionMerge || opcode == spirv::Op::LoopMerge {
+                at -= 1;
+            }
+        }
+        block.instructions.splice(at..at, stores);
+    }
+    let count = variables.len();
+    if let Some(entry) = function.basic_blocks.first_mut() {
+        entry.instructions.splice(0..0, variables);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::{eliminate_phis, phi_incoming, promote_variables};
+
+    fn disas_function(module: &mr::Module) -> Vec<String> {
+        module.functions[0]
+            .all_inst_iter()
+            .skip(1)
+            .map(|i| i.disassemble())
+            .filter(|s| s != "OpFunctionEnd")
+            .collect()
+    }
+
+    /// Builds an if-else storing 1 or 2 into a variable named "x", and
+    /// returning the value loaded at the merge block.
+    fn if_else() -> mr::Module {
+        let mut b = mr::Builder::new();
+        let int = b.type_int(32, 1);
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let one = b.constant_u32(int, 1);
+        let two = b.constant_u32(int, 2);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Function, int);
+        let intf = b.type_function(int, vec![]);
+        b.begin_function(int, None, spirv::FunctionControl::NONE, intf).unwrap();
+        let labels: Vec<spirv::Word> = (0..4).map(|_| b.id()).collect();
+        b.begin_basic_block(Some(labels[0])).unwrap();
+        let x = b.variable(ptr, None, spirv::StorageClass::Function, None);
+        b.name(x, "x");
+        b.selection_merge(labels[3], spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, labels[1], labels[2], vec![]).unwrap();
+        b.begin_basic_block(Some(labels[1])).unwrap();
+        b.store(x, one, None, vec![]).unwrap();
+        b.branch(labels[3]).unwrap();
+        b.begin_basic_block(Some(labels[2])).unwrap();
+        b.store(x, two, None, vec![]).unwrap();
+        b.branch(labels[3]).unwrap();
+        b.begin_basic_block(Some(labels[3])).unwrap();
+        let value = b.load(int, None, x, None, vec![]).unwrap();
+        b.ret_value(value).unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_promote_variables() {
+        let mut m = if_else();
+        assert_eq!(1, promote_variables(&mut m));
+        assert!(m.debugs.is_empty());
+        assert_eq!(
+            vec![
+                "%9 = OpLabel",
+                "OpSelectionMerge %12 None",
+                "OpBranchConditional %3 %10 %11",
+                "%10 = OpLabel",
+                "OpBranch %12",
+                "%11 = OpLabel",
+                "OpBranch %12",
+                "%12 = OpLabel",
+                "%16 = OpPhi  %1  %4 %10 %5 %11",
+                "OpReturnValue %16",
+            ],
+            disas_function(&m)
+        );
+        // An OpUndef was created for the variable without initializer.
+        assert_eq!(
+            "%15 = OpUndef  %1 ",
+            m.types_global_values.last().unwrap().disassemble()
+        );
+        let phi = &m.functions[0].basic_blocks[3].instructions[0];
+        assert_eq!(vec![(4, 10), (5, 11)], phi_incoming(phi));
+    }
+
+    #[test]
+    fn test_keep_escaping_variables() {
+        let mut m = if_else();
+        {
+            let block = &mut m.functions[0].basic_blocks[3];
+            let inst = block.instructions.pop().unwrap();
+            block.instructions.push(mr::Instruction::new(
+                spirv::Op::CopyObject,
+                Some(6),
+                Some(30),
+                vec![mr::Operand::IdRef(13)],
+            ));
+            block.instructions.push(inst);
+        }
+        assert_eq!(0, promote_variables(&mut m));
+    }
+
+    #[test]
+    fn test_eliminate_phis() {
+        let mut m = if_else();
+        promote_variables(&mut m);
+        assert_eq!(1, eliminate_phis(&mut m));
+        assert_eq!(
+            vec![
+                "%9 = OpLabel",
+                "%17 = OpVariable  %6  Function",
+                "OpSelectionMerge %12 None",
+                "OpBranchConditional %3 %10 %11",
+                "%10 = OpLabel",
+                "OpStore %17 %4",
+                "OpBranch %12",
+                "%11 = OpLabel",
+                "OpStore %17 %5",
+                "OpBranch %12",
+                "%12 = OpLabel",
+                "%16 = OpLoad  %1  %17",
+                "OpReturnValue %16",
+            ],
+            disas_function(&m)
+        );
+        // Round trip.
+        assert_eq!(1, promote_variables(&mut m));
+        assert_eq!(1, eliminate_phis(&mut m));
+    }
+}
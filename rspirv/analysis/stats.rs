@@ -0,0 +1,409 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use grammar::{OpClass, Opcode};
+use super::{ControlFlowGraph, DivergenceAnalysis, DominatorTree};
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Control flow metrics of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// The result id of the function.
+    pub function: Word,
+    /// The number of basic blocks.
+    pub blocks: usize,
+    /// The maximal number of natural loops containing the same block.
+    pub max_loop_depth: usize,
+    /// The cyclomatic complexity of the control flow graph of the blocks
+    /// reachable from the entry block, i.e., edges - blocks + 2.
+    pub cyclomatic_complexity: usize,
+    /// The number of conditional branches and switches on divergent values.
+    /// See [`DivergenceAnalysis`](struct.DivergenceAnalysis.html).
+    pub divergent_branches: usize,
+}
+
+impl fmt::Display for FunctionStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "%{}: {} blocks, loop depth {}, cyclomatic complexity {}, {} divergent branches",
+            self.function,
+            self.blocks,
+            self.max_loop_depth,
+            self.cyclomatic_complexity,
+            self.divergent_branches
+        )
+    }
+}
+
+/// Computes the control flow metrics of all functions in the given
+/// `module`, in the order of the functions.
+///
+/// Functions with the largest metrics are usually the ones most worth
+/// optimizing by hand.
+pub fn function_stats(module: &mr::Module) -> Vec<FunctionStats> {
+    module.functions
+        .iter()
+        .map(|function| {
+            let cfg = ControlFlowGraph::new(function);
+            let reachable = cfg.reachable_blocks();
+            let reachable_count = reachable.iter().filter(|&&r| r).count();
+            let edges: usize = (0..cfg.len())
+                .filter(|&index| reachable[index])
+                .map(|index| cfg.successors(index).len())
+                .sum();
+            let divergence = DivergenceAnalysis::new(module, function);
+            FunctionStats {
+                function: function.def.as_ref().and_then(|d| d.result_id).unwrap_or(0),
+                blocks: function.basic_blocks.len(),
+                max_loop_depth: max_loop_depth(&cfg),
+                cyclomatic_complexity: if reachable_count == 0 {
+                    0
+                } else {
+                    edges + 2 - reachable_count
+                },
+                divergent_branches: function.basic_blocks
+                    .iter()
+                    .filter(|bb| divergence.is_divergent_branch(bb))
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the maximal nesting depth of the natural loops in `cfg`.
+///
+/// Each back edge, i.e., an edge to a block dominating its source, forms
+/// a natural loop. Back edges to the same header form the same loop.
+fn max_loop_depth(cfg: &ControlFlowGraph) -> usize {
+    let dominators = DominatorTree::new(cfg);
+    let mut loops: Vec<(usize, Vec<bool>)> = vec![];
+    for block in 0..cfg.len() {
+        for &header in cfg.successors(block) {
+            if !dominators.is_reachable(block) || !dominators.dominates(header, block) {
+                continue;
+            }
+            let index = match loops.iter().position(|&(h, _)| h == header) {
+                Some(index) => index,
+                None => {
+                    let mut body = vec![false; cfg.len()];
+                    body[header] = true;
+                    loops.push((header, body));
+                    loops.len() - 1
+                }
+            };
+            let body = &mut loops[index].1;
+            let mut stack = vec![block];
+            while let Some(member) = stack.pop() {
+                if !body[member] {
+                    body[member] = true;
+                    stack.extend_from_slice(cfg.predecessors(member));
+                }
+            }
+        }
+    }
+    (0..cfg.len())
+        .map(|block| loops.iter().filter(|&(_, body)| body[block]).count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The instruction counts and estimated register pressure of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionUsage {
+    /// The result id of the function.
+    pub function: Word,
+    /// The number of instructions in the basic blocks, not counting labels.
+    pub instructions: usize,
+    /// The maximal number of SSA values live at the same time, as a hint
+    /// of the registers the function needs. Values are assumed to live
+    /// from their definition to their last use, with OpPhi operands used
+    /// at the start of the block of the OpPhi.
+    pub max_live_values: usize,
+}
+
+/// Cheap complexity metrics of a module, computed by
+/// [`usage_stats`](fn.usage_stats.html).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    /// The number of instructions in functions per opcode class.
+    pub classes: HashMap<OpClass, usize>,
+    /// The metrics of each function, in the order of the functions.
+    pub functions: Vec<FunctionUsage>,
+    /// The number of OpBranchConditional and OpSwitch instructions.
+    pub branches: usize,
+    /// The number of OpLoopMerge instructions.
+    pub loops: usize,
+    /// The number of image sampling instructions, including sparse and
+    /// depth-comparison ones.
+    pub texture_samples: usize,
+    /// The number of image fetch, gather, and read instructions.
+    pub texture_fetches: usize,
+}
+
+impl UsageStats {
+    /// Returns the total number of instructions in functions.
+    pub fn instructions(&self) -> usize {
+        self.functions.iter().map(|f| f.instructions).sum()
+    }
+
+    /// Returns the maximal number of values live at the same time in any
+    /// function.
+    pub fn max_live_values(&self) -> usize {
+        self.functions.iter().map(|f| f.max_live_values).max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for UsageStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} instructions, {} branches, {} loops, {} texture samples, {} texture fetches, \
+             max {} live values",
+            self.instructions(),
+            self.branches,
+            self.loops,
+            self.texture_samples,
+            self.texture_fetches,
+            self.max_live_values()
+        )
+    }
+}
+
+/// Counts the instructions of the given `module` by kind, as complexity
+/// metrics for build systems which cannot afford running a compiler.
+pub fn usage_stats(module: &mr::Module) -> UsageStats {
+    use spirv::Op::*;
+
+    let mut stats = UsageStats::default();
+    for function in &module.functions {
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            let opcode = inst.class.opcode;
+            *stats.classes.entry(opcode.class()).or_insert(0) += 1;
+            match opcode {
+                BranchConditional | Switch => stats.branches += 1,
+                LoopMerge => stats.loops += 1,
+                ImageSampleImplicitLod |
+                ImageSampleExplicitLod |
+                ImageSampleDrefImplicitLod |
+                ImageSampleDrefExplicitLod |
+                ImageSampleProjImplicitLod |
+                ImageSampleProjExplicitLod |
+                ImageSampleProjDrefImplicitLod |
+                ImageSampleProjDrefExplicitLod |
+                ImageSparseSampleImplicitLod |
+                ImageSparseSampleExplicitLod |
+                ImageSparseSampleDrefImplicitLod |
+                ImageSparseSampleDrefExplicitLod |
+                ImageSparseSampleProjImplicitLod |
+                ImageSparseSampleProjExplicitLod |
+                ImageSparseSampleProjDrefImplicitLod |
+                ImageSparseSampleProjDrefExplicitLod => stats.texture_samples += 1,
+                ImageFetch | ImageGather | ImageDrefGather | ImageRead | ImageSparseFetch |
+                ImageSparseGather | ImageSparseDrefGather | ImageSparseRead => {
+                    stats.texture_fetches += 1
+                }
+                _ => {}
+            }
+        }
+        stats.functions.push(FunctionUsage {
+            function: function.def.as_ref().and_then(|d| d.result_id).unwrap_or(0),
+            instructions: function.basic_blocks.iter().map(|bb| bb.instructions.len()).sum(),
+            max_live_values: max_live_values(function),
+        });
+    }
+    stats
+}
+
+/// Returns the maximal number of SSA values of `function` live at the same
+/// time, following the usual backward data flow over the basic blocks.
+fn max_live_values(function: &mr::Function) -> usize {
+    // Values exclude variables, which live in memory.
+    let values: HashSet<Word> = function
+        .parameters
+        .iter()
+        .chain(function.basic_blocks.iter().flat_map(|bb| &bb.instructions))
+        .filter(|inst| inst.result_type.is_some() && inst.class.opcode != spirv::Op::Variable)
+        .filter_map(|inst| inst.result_id)
+        .collect();
+    let uses = |inst: &mr::Instruction| -> Vec<Word> {
+        inst.operands
+            .iter()
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(id) if values.contains(&id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let cfg = ControlFlowGraph::new(function);
+    // The values used in each block before being defined there, and the
+    // values defined there.
+    let mut used = vec![];
+    let mut defined = vec![];
+    for block in &function.basic_blocks {
+        let mut block_used = HashSet::new();
+        let mut block_defined = HashSet::new();
+        for inst in &block.instructions {
+            for id in uses(inst) {
+                if !block_defined.contains(&id) {
+                    block_used.insert(id);
+                }
+            }
+            block_defined.extend(inst.result_id.filter(|id| values.contains(id)));
+        }
+        used.push(block_used);
+        defined.push(block_defined);
+    }
+    let mut live_in: Vec<HashSet<Word>> = used.clone();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in (0..cfg.len()).rev() {
+            let mut live: HashSet<Word> = cfg
+                .successors(index)
+                .iter()
+                .flat_map(|&succ| live_in[succ].iter().cloned())
+                .filter(|id| !defined[index].contains(id))
+                .collect();
+            live.extend(used[index].iter().cloned());
+            if live.len() != live_in[index].len() {
+                live_in[index] = live;
+                changed = true;
+            }
+        }
+    }
+
+    let mut max = 0;
+    for (index, block) in function.basic_blocks.iter().enumerate() {
+        let mut live: HashSet<Word> = cfg
+            .successors(index)
+            .iter()
+            .flat_map(|&succ| live_in[succ].iter().cloned())
+            .collect();
+        max = max.max(live.len());
+        for inst in block.instructions.iter().rev() {
+            if let Some(id) = inst.result_id {
+                live.remove(&id);
+            }
+            live.extend(uses(inst));
+            max = max.max(live.len());
+        }
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use grammar::OpClass;
+    use super::{function_stats, usage_stats, FunctionStats, FunctionUsage};
+
+    #[test]
+    fn test_function_stats() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.branch(11).unwrap();
+        // Outer loop.
+        b.begin_basic_block(Some(11)).unwrap();
+        b.loop_merge(16, 15, spirv::LoopControl::NONE, vec![]).unwrap();
+        b.branch(12).unwrap();
+        // Inner loop.
+        b.begin_basic_block(Some(12)).unwrap();
+        b.loop_merge(14, 13, spirv::LoopControl::NONE, vec![]).unwrap();
+        b.branch_conditional(cond, 13, 14, vec![]).unwrap();
+        b.begin_basic_block(Some(13)).unwrap();
+        b.branch(12).unwrap();
+        b.begin_basic_block(Some(14)).unwrap();
+        b.branch(15).unwrap();
+        b.begin_basic_block(Some(15)).unwrap();
+        b.branch_conditional(cond, 11, 16, vec![]).unwrap();
+        b.begin_basic_block(Some(16)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let stats = function_stats(&b.module());
+        let expected = FunctionStats {
+            function: f,
+            blocks: 7,
+            max_loop_depth: 2,
+            cyclomatic_complexity: 3,
+            divergent_branches: 0,
+        };
+        assert_eq!(vec![expected], stats);
+        assert_eq!(
+            "%5: 7 blocks, loop depth 2, cyclomatic complexity 3, 0 divergent branches",
+            stats[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_usage_stats() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec2 = b.type_vector(float, 2);
+        let vec4 = b.type_vector(float, 4);
+        let image = b.type_image(
+            float,
+            spirv::Dim::Dim2D,
+            0,
+            0,
+            0,
+            1,
+            spirv::ImageFormat::Unknown,
+            None,
+        );
+        let sampled_image = b.type_sampled_image(image);
+        let ptr = b.type_pointer(None, spirv::StorageClass::UniformConstant, sampled_image);
+        let texture = b.variable(ptr, None, spirv::StorageClass::UniformConstant, None);
+        let half = b.constant_f32(float, 0.5);
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let s = b.load(sampled_image, None, texture, None, vec![]).unwrap();
+        let coord = b.composite_construct(vec2, None, vec![half, half]).unwrap();
+        let texel = b.image_sample_implicit_lod(vec4, None, s, coord, None, vec![]).unwrap();
+        b.fadd(vec4, None, texel, texel).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let stats = usage_stats(&b.module());
+        assert_eq!(
+            vec![FunctionUsage { function: f, instructions: 5, max_live_values: 2 }],
+            stats.functions
+        );
+        assert_eq!(Some(&1), stats.classes.get(&OpClass::Image));
+        assert_eq!(Some(&1), stats.classes.get(&OpClass::Arithmetic));
+        assert_eq!(None, stats.classes.get(&OpClass::Atomic));
+        assert_eq!(1, stats.texture_samples);
+        assert_eq!(
+            "5 instructions, 0 branches, 0 loops, 1 texture samples, 0 texture fetches, \
+             max 2 live values",
+            stats.to_string()
+        );
+    }
+}
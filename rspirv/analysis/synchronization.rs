@@ -0,0 +1,259 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use num::FromPrimitive;
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// An atomic operation or barrier, with its scope and memory semantics
+/// operands decoded.
+///
+/// Scopes and semantics are given by ids, usually of constants; those not
+/// given by an OpConstant, e.g., specialization constants, or with values
+/// unknown to this library cannot be decoded and are `None`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyncOperation {
+    /// The result id of the function containing the instruction.
+    pub function: Word,
+    /// The opcode of the instruction.
+    pub opcode: spirv::Op,
+    /// The result id of the instruction, if any.
+    pub result_id: Option<Word>,
+    /// The pointer operated on by an atomic operation.
+    pub pointer: Option<Word>,
+    /// The execution scope of an OpControlBarrier.
+    pub execution_scope: Option<spirv::Scope>,
+    /// The memory scope.
+    pub memory_scope: Option<spirv::Scope>,
+    /// The memory semantics, two for the equal and unequal cases of
+    /// compare-exchange operations.
+    pub semantics: Vec<Option<spirv::MemorySemantics>>,
+}
+
+impl SyncOperation {
+    /// Returns true if this is a barrier instruction rather than an atomic
+    /// operation.
+    pub fn is_barrier(&self) -> bool {
+        !is_atomic(self.opcode)
+    }
+}
+
+fn write_decoded<T: fmt::Display>(f: &mut fmt::Formatter, value: &Option<T>) -> fmt::Result {
+    match *value {
+        Some(ref value) => write!(f, "{}", value),
+        None => f.write_str("?"),
+    }
+}
+
+impl fmt::Display for SyncOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(id) = self.result_id {
+            write!(f, "%{} = ", id)?;
+        }
+        write!(f, "Op{:?}", self.opcode)?;
+        if let Some(pointer) = self.pointer {
+            write!(f, " %{}", pointer)?;
+        }
+        if self.opcode == spirv::Op::ControlBarrier {
+            f.write_str(" execution ")?;
+            write_decoded(f, &self.execution_scope)?;
+        }
+        f.write_str(" memory ")?;
+        write_decoded(f, &self.memory_scope)?;
+        for semantics in &self.semantics {
+            f.write_str(" ")?;
+            write_decoded(f, semantics)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_atomic(opcode: spirv::Op) -> bool {
+    use spirv::Op::*;
+    matches!(
+        opcode,
+        AtomicLoad | AtomicStore | AtomicExchange | AtomicCompareExchange |
+            AtomicCompareExchangeWeak | AtomicIIncrement | AtomicIDecrement | AtomicIAdd |
+            AtomicISub | AtomicSMin | AtomicUMin | AtomicSMax | AtomicUMax | AtomicAnd |
+            AtomicOr | AtomicXor | AtomicFlagTestAndSet | AtomicFlagClear
+    )
+}
+
+fn is_barrier(opcode: spirv::Op) -> bool {
+    matches!(
+        opcode,
+        spirv::Op::ControlBarrier | spirv::Op::MemoryBarrier | spirv::Op::MemoryNamedBarrier
+    )
+}
+
+/// Lists all atomic operations and barriers of the given module, in the
+/// order of the functions and of the instructions within them.
+///
+/// Compute framework authors can check these against their memory model
+/// assumptions, and derive the capabilities they need with
+/// [`memory_model_capabilities`](fn.memory_model_capabilities.html).
+pub fn sync_operations(module: &mr::Module) -> Vec<SyncOperation> {
+    let constants: HashMap<Word, u32> = module
+        .types_global_values
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::Constant)
+        .filter_map(|inst| match (inst.result_id, inst.operands.first()) {
+            (Some(id), Some(&mr::Operand::LiteralInt32(value))) => Some((id, value)),
+            _ => None,
+        })
+        .collect();
+
+    let mut operations = vec![];
+    for function in &module.functions {
+        let function_id = function.def.as_ref().and_then(|d| d.result_id).unwrap_or(0);
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            let atomic = is_atomic(inst.class.opcode);
+            if !atomic && !is_barrier(inst.class.opcode) {
+                continue;
+            }
+            let mut scopes = vec![];
+            let mut semantics = vec![];
+            for operand in &inst.operands {
+                match *operand {
+                    mr::Operand::IdScope(id) => {
+                        scopes.push(constants.get(&id).and_then(|&v| spirv::Scope::from_u32(v)))
+                    }
+                    mr::Operand::IdMemorySemantics(id) => semantics.push(
+                        constants.get(&id).and_then(|&v| spirv::MemorySemantics::from_bits(v)),
+                    ),
+                    _ => {}
+                }
+            }
+            let execution_scope = if inst.class.opcode == spirv::Op::ControlBarrier {
+                scopes.first().copied().flatten()
+            } else {
+                None
+            };
+            let pointer = match (atomic, inst.operands.first()) {
+                (true, Some(&mr::Operand::IdRef(pointer))) => Some(pointer),
+                _ => None,
+            };
+            operations.push(SyncOperation {
+                function: function_id,
+                opcode: inst.class.opcode,
+                result_id: inst.result_id,
+                pointer,
+                execution_scope,
+                memory_scope: scopes.last().copied().flatten(),
+                semantics,
+            });
+        }
+    }
+    operations
+}
+
+/// Returns the Vulkan memory model capabilities needed by the given
+/// operations: `VulkanMemoryModelKHR` if any of them uses the
+/// `QueueFamilyKHR` scope or the availability, visibility, or output memory
+/// semantics, and additionally `VulkanMemoryModelDeviceScopeKHR` if any of
+/// them then uses the `Device` scope.
+pub fn memory_model_capabilities(operations: &[SyncOperation]) -> Vec<spirv::Capability> {
+    let vulkan_semantics = spirv::MemorySemantics::OUTPUT_MEMORY_KHR |
+        spirv::MemorySemantics::MAKE_AVAILABLE_KHR |
+        spirv::MemorySemantics::MAKE_VISIBLE_KHR;
+    let scopes = || {
+        operations.iter().flat_map(|op| op.execution_scope.iter().chain(&op.memory_scope))
+    };
+    let vulkan = scopes().any(|&scope| scope == spirv::Scope::QueueFamilyKHR) ||
+        operations
+            .iter()
+            .flat_map(|op| op.semantics.iter().flatten())
+            .any(|semantics| semantics.intersects(vulkan_semantics));
+    let mut capabilities = vec![];
+    if vulkan {
+        capabilities.push(spirv::Capability::VulkanMemoryModelKHR);
+        if scopes().any(|&scope| scope == spirv::Scope::Device) {
+            capabilities.push(spirv::Capability::VulkanMemoryModelDeviceScopeKHR);
+        }
+    }
+    capabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::{memory_model_capabilities, sync_operations, SyncOperation};
+    use mr;
+    use spirv;
+
+    #[test]
+    fn test_sync_operations() {
+        use spirv::MemorySemantics as S;
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Workgroup, uint);
+        let voidf = b.type_function(void, vec![]);
+        let counter = b.variable(ptr, None, spirv::StorageClass::Workgroup, None);
+        let device = b.constant_u32(uint, spirv::Scope::Device as u32);
+        let workgroup = b.constant_u32(uint, spirv::Scope::Workgroup as u32);
+        let relaxed = b.constant_u32(uint, 0);
+        let acq_rel = b.constant_u32(uint, (S::ACQUIRE_RELEASE | S::WORKGROUP_MEMORY).bits());
+        let one = b.constant_u32(uint, 1);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let add = b.atomic_iadd(uint, None, counter, device, relaxed, one).unwrap();
+        b.control_barrier(workgroup, workgroup, acq_rel).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let operations = sync_operations(&b.module());
+        assert_eq!(
+            vec![
+                SyncOperation {
+                    function: main,
+                    opcode: spirv::Op::AtomicIAdd,
+                    result_id: Some(add),
+                    pointer: Some(counter),
+                    execution_scope: None,
+                    memory_scope: Some(spirv::Scope::Device),
+                    semantics: vec![Some(S::RELAXED)],
+                },
+                SyncOperation {
+                    function: main,
+                    opcode: spirv::Op::ControlBarrier,
+                    result_id: None,
+                    pointer: None,
+                    execution_scope: Some(spirv::Scope::Workgroup),
+                    memory_scope: Some(spirv::Scope::Workgroup),
+                    semantics: vec![Some(S::ACQUIRE_RELEASE | S::WORKGROUP_MEMORY)],
+                },
+            ],
+            operations
+        );
+        assert!(!operations[0].is_barrier() && operations[1].is_barrier());
+        assert_eq!(
+            "OpControlBarrier execution Workgroup memory Workgroup AcquireRelease|WorkgroupMemory",
+            operations[1].to_string()
+        );
+        assert!(memory_model_capabilities(&operations).is_empty());
+
+        let mut available = operations[1].clone();
+        available.semantics = vec![Some(S::RELEASE | S::MAKE_AVAILABLE_KHR)];
+        assert_eq!(
+            vec![spirv::Capability::VulkanMemoryModelKHR,
+                 spirv::Capability::VulkanMemoryModelDeviceScopeKHR],
+            memory_model_capabilities(&[operations[0].clone(), available])
+        );
+    }
+}
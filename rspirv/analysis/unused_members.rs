@@ -0,0 +1,321 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use super::{AccessChainResolver, AccessStep};
+use spirv::Word;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+/// The members of a uniform or storage block never accessed by any entry
+/// point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnusedBlockMembers {
+    /// The result id of the block struct type.
+    pub block: Word,
+    /// The result ids of the Uniform and StorageBuffer variables of the
+    /// block, including arrays of it.
+    pub variables: Vec<Word>,
+    /// The indices of the unused members, in increasing order.
+    pub members: Vec<u32>,
+}
+
+impl fmt::Display for UnusedBlockMembers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{}: member", self.block)?;
+        if self.members.len() > 1 {
+            f.write_str("s")?;
+        }
+        for (i, member) in self.members.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, member)?;
+        }
+        f.write_str(" unused")
+    }
+}
+
+/// The usage of the members of one block.
+struct BlockUsage {
+    variables: Vec<Word>,
+    used: Vec<bool>,
+    /// Whether the block is accessed in a way which cannot be traced to
+    /// individual members, or cannot be rewritten member by member.
+    opaque: bool,
+}
+
+/// Finds the members of uniform and storage blocks which no function
+/// called from an entry point accesses, in the order of the blocks.
+///
+/// Members are traced through access chains (see
+/// [`AccessChainResolver`](struct.AccessChainResolver.html)) and
+/// OpArrayLength. Any other use of a whole block, e.g., loading it or
+/// passing it to a function, counts as accessing all of its members.
+/// Blocks also used for variables of other storage classes, or nested in
+/// other structs, are never reported.
+///
+/// Decoration groups are not followed; see
+/// [`transform::expand_decoration_groups`](../transform/fn.expand_decoration_groups.html).
+pub fn unused_block_members(module: &mr::Module) -> Vec<UnusedBlockMembers> {
+    find_unused_block_members(module, false)
+}
+
+/// Implements [`unused_block_members`](fn.unused_block_members.html),
+/// considering accesses in all functions if `all_functions` is set instead
+/// of only in the functions called from entry points.
+pub(crate) fn find_unused_block_members(module: &mr::Module,
+                                        all_functions: bool)
+                                        -> Vec<UnusedBlockMembers> {
+    let defs: HashMap<Word, &mr::Instruction> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+        .collect();
+    let id_operand = |inst: &mr::Instruction, index: usize| match inst.operands.get(index) {
+        Some(&mr::Operand::IdRef(id)) => Some(id),
+        _ => None,
+    };
+    // The struct at the core of the given type, looking through arrays.
+    let struct_of = |mut ty: Word| loop {
+        let def = defs.get(&ty)?;
+        match def.class.opcode {
+            spirv::Op::TypeArray | spirv::Op::TypeRuntimeArray => ty = id_operand(def, 0)?,
+            spirv::Op::TypeStruct => return Some(ty),
+            _ => return None,
+        }
+    };
+    let pointee_struct = |pointer: Option<Word>| {
+        let def = defs.get(&pointer?)?;
+        if def.class.opcode != spirv::Op::TypePointer {
+            return None;
+        }
+        struct_of(id_operand(def, 1)?)
+    };
+
+    let index = module.decoration_index();
+    let mut blocks = BTreeMap::new();
+    for inst in &module.types_global_values {
+        let id = match inst.result_id {
+            Some(id) if inst.class.opcode == spirv::Op::TypeStruct => id,
+            _ => continue,
+        };
+        if index.has_decoration(id, spirv::Decoration::Block) ||
+            index.has_decoration(id, spirv::Decoration::BufferBlock) {
+            let usage = BlockUsage {
+                variables: vec![],
+                used: vec![false; inst.operands.len()],
+                opaque: false,
+            };
+            blocks.insert(id, usage);
+        }
+    }
+    if blocks.is_empty() {
+        return vec![];
+    }
+
+    // Sort out blocks which cannot be rewritten member by member.
+    let mut whole = HashMap::new();
+    for inst in &module.types_global_values {
+        match inst.class.opcode {
+            spirv::Op::TypeStruct => {
+                for member in inst.operands.iter().filter_map(|operand| match *operand {
+                    mr::Operand::IdRef(ty) => struct_of(ty),
+                    _ => None,
+                }) {
+                    if let Some(usage) = blocks.get_mut(&member) {
+                        usage.opaque = true;
+                    }
+                }
+            }
+            spirv::Op::Variable => {
+                let usage = match pointee_struct(inst.result_type) {
+                    Some(block) => match blocks.get_mut(&block) {
+                        Some(usage) => usage,
+                        None => continue,
+                    },
+                    None => continue,
+                };
+                match inst.operands.first() {
+                    Some(mr::Operand::StorageClass(spirv::StorageClass::Uniform)) |
+                    Some(mr::Operand::StorageClass(spirv::StorageClass::StorageBuffer)) => {
+                        let variable = inst.result_id.unwrap_or(0);
+                        usage.variables.push(variable);
+                        whole.insert(variable, pointee_struct(inst.result_type).unwrap());
+                    }
+                    _ => usage.opaque = true,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let resolver = AccessChainResolver::new(module);
+    for function in scanned_functions(module, all_functions) {
+        // Pointers to whole blocks (or arrays of them) in this function.
+        let mut whole = whole.clone();
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            let base = match inst.operands.first() {
+                Some(&mr::Operand::IdRef(base)) => whole.get(&base).cloned(),
+                _ => None,
+            };
+            let skip = match (inst.class.opcode, base) {
+                (spirv::Op::AccessChain, Some(block)) |
+                (spirv::Op::InBoundsAccessChain, Some(block)) |
+                (spirv::Op::PtrAccessChain, Some(block)) |
+                (spirv::Op::InBoundsPtrAccessChain, Some(block)) => {
+                    let usage = blocks.get_mut(&block).unwrap();
+                    let member = resolver.resolve(inst).map(|path| {
+                        path.steps.into_iter().filter_map(|step| match step {
+                            AccessStep::Member(member, _) => Some(member),
+                            _ => None,
+                        }).next()
+                    });
+                    match member {
+                        Some(Some(member)) => usage.used[member as usize] = true,
+                        Some(None) => {
+                            whole.insert(inst.result_id.unwrap_or(0), block);
+                        }
+                        None => usage.opaque = true,
+                    }
+                    1
+                }
+                (spirv::Op::ArrayLength, Some(block)) => {
+                    let usage = blocks.get_mut(&block).unwrap();
+                    match inst.operands.get(1) {
+                        Some(&mr::Operand::LiteralInt32(member))
+                            if (member as usize) < usage.used.len() => {
+                            usage.used[member as usize] = true
+                        }
+                        _ => usage.opaque = true,
+                    }
+                    1
+                }
+                _ => 0,
+            };
+            for operand in &inst.operands[skip..] {
+                if let mr::Operand::IdRef(id) = *operand {
+                    if let Some(block) = whole.get(&id) {
+                        blocks.get_mut(block).unwrap().opaque = true;
+                    }
+                }
+            }
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter(|(_, usage)| !usage.opaque && !usage.variables.is_empty())
+        .filter_map(|(block, usage)| {
+            let members: Vec<u32> = usage.used
+                .iter()
+                .enumerate()
+                .filter(|&(_, &used)| !used)
+                .map(|(member, _)| member as u32)
+                .collect();
+            if members.is_empty() {
+                return None;
+            }
+            Some(UnusedBlockMembers { block, variables: usage.variables, members })
+        })
+        .collect()
+}
+
+/// Returns all functions of `module` if `all` is set, or otherwise those
+/// called from entry points.
+fn scanned_functions(module: &mr::Module, all: bool) -> Vec<&mr::Function> {
+    if all {
+        return module.functions.iter().collect();
+    }
+    let functions: HashMap<Word, &mr::Function> = module
+        .functions
+        .iter()
+        .filter_map(|f| f.def.as_ref().and_then(|def| def.result_id).map(|id| (id, f)))
+        .collect();
+    let mut worklist: Vec<Word> = module
+        .entry_points
+        .iter()
+        .filter_map(|inst| match inst.operands.get(1) {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        })
+        .collect();
+    let mut visited: HashSet<Word> = worklist.iter().cloned().collect();
+    let mut scanned = vec![];
+    while let Some(id) = worklist.pop() {
+        let function = match functions.get(&id) {
+            Some(function) => function,
+            None => continue,
+        };
+        scanned.push(*function);
+        for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+            if inst.class.opcode == spirv::Op::FunctionCall {
+                if let Some(&mr::Operand::IdRef(callee)) = inst.operands.first() {
+                    if visited.insert(callee) {
+                        worklist.push(callee);
+                    }
+                }
+            }
+        }
+    }
+    scanned
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{unused_block_members, UnusedBlockMembers};
+
+    #[test]
+    fn test_unused_block_members() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let block = b.type_struct(vec![float, float, float]);
+        let loaded = b.type_struct(vec![float, float]);
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.decorate(loaded, spirv::Decoration::Block, vec![]);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Uniform, block);
+        let loaded_ptr = b.type_pointer(None, spirv::StorageClass::Uniform, loaded);
+        let float_ptr = b.type_pointer(None, spirv::StorageClass::Uniform, float);
+        let one = b.constant_u32(uint, 1);
+        let two = b.constant_u32(uint, 2);
+        let ubo = b.variable(ptr, None, spirv::StorageClass::Uniform, None);
+        let whole = b.variable(loaded_ptr, None, spirv::StorageClass::Uniform, None);
+        let voidf = b.type_function(void, vec![]);
+
+        // Not called from the entry point.
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.access_chain(float_ptr, None, ubo, vec![two]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let member = b.access_chain(float_ptr, None, ubo, vec![one]).unwrap();
+        b.load(float, None, member, None, vec![]).unwrap();
+        b.load(loaded, None, whole, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, main, "main", vec![]);
+
+        let unused = unused_block_members(&b.module());
+        assert_eq!(vec![UnusedBlockMembers { block, variables: vec![ubo], members: vec![0, 2] }],
+                   unused);
+        assert_eq!("%4: members 0, 2 unused", unused[0].to_string());
+    }
+}
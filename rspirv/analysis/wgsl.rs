@@ -0,0 +1,232 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A construct in a SPIR-V module which cannot be represented in WGSL.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WgslIssue {
+    /// The entry point uses an execution model other than vertex, fragment,
+    /// or compute.
+    ExecutionModel {
+        entry_point: Word,
+        name: String,
+        model: spirv::ExecutionModel,
+    },
+    /// The image type uses a format WGSL storage textures do not support.
+    ImageFormat {
+        type_id: Word,
+        format: spirv::ImageFormat,
+    },
+    /// The integer or floating point type has a width other than 32 bits
+    /// (or 16 bits for floating point types).
+    ScalarWidth { type_id: Word, opcode: spirv::Op, width: u32 },
+    /// The runtime array is not the last member of the struct.
+    UnsizedArrayNotLast { struct_id: Word, member: u32 },
+}
+
+impl fmt::Display for WgslIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WgslIssue::ExecutionModel { entry_point, ref name, model } => write!(
+                f,
+                "entry point %{} ({}) uses the unsupported {:?} execution model",
+                entry_point, name, model
+            ),
+            WgslIssue::ImageFormat { type_id, format } => {
+                write!(f, "image type %{} uses the unsupported {:?} format", type_id, format)
+            }
+            WgslIssue::ScalarWidth { type_id, opcode, width } => write!(
+                f,
+                "type %{} (Op{:?}) has the unsupported width {}",
+                type_id, opcode, width
+            ),
+            WgslIssue::UnsizedArrayNotLast { struct_id, member } => write!(
+                f,
+                "member {} of struct %{} is a runtime array but not the last member",
+                member, struct_id
+            ),
+        }
+    }
+}
+
+/// Returns true if WGSL supports the given image format for storage
+/// textures.
+fn is_wgsl_format(format: spirv::ImageFormat) -> bool {
+    use spirv::ImageFormat::*;
+    matches!(
+        format,
+        Rgba8 | Rgba8Snorm | Rgba8ui | Rgba8i | Rgba16ui | Rgba16i | Rgba16f | R32ui | R32i |
+            R32f | Rg32ui | Rg32i | Rg32f | Rgba32ui | Rgba32i | Rgba32f
+    )
+}
+
+/// Audits the given module for constructs not representable in WGSL.
+///
+/// The following constructs are reported:
+///
+/// * Entry points with execution models other than Vertex, Fragment, and
+///   GLCompute, e.g., geometry or tessellation stages.
+/// * Image types with formats WGSL storage textures lack, and storage
+///   images without a format.
+/// * Integer types that are not 32 bits wide (e.g., 8-bit types), and
+///   floating point types that are neither 16 nor 32 bits wide.
+/// * Runtime arrays which are not the last member of a struct.
+///
+/// The audit is conservative in what it reports: an empty result does not
+/// guarantee that a translation to WGSL succeeds.
+pub fn check_wgsl_compatibility(module: &mr::Module) -> Vec<WgslIssue> {
+    let mut issues = vec![];
+    for inst in &module.entry_points {
+        if let [mr::Operand::ExecutionModel(model),
+                mr::Operand::IdRef(entry_point),
+                mr::Operand::LiteralString(ref name), ..] = inst.operands[..]
+        {
+            if !matches!(
+                model,
+                spirv::ExecutionModel::Vertex | spirv::ExecutionModel::Fragment |
+                    spirv::ExecutionModel::GLCompute
+            ) {
+                issues.push(WgslIssue::ExecutionModel {
+                    entry_point,
+                    name: name.to_string(),
+                    model,
+                });
+            }
+        }
+    }
+
+    let runtime_arrays: HashSet<Word> = module.types_global_values
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::TypeRuntimeArray)
+        .filter_map(|inst| inst.result_id)
+        .collect();
+    for inst in &module.types_global_values {
+        let type_id = match inst.result_id {
+            Some(id) => id,
+            None => continue,
+        };
+        match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::TypeInt, &[mr::Operand::LiteralInt32(width), ..]) if width != 32 => {
+                issues.push(WgslIssue::ScalarWidth { type_id, opcode: inst.class.opcode, width })
+            }
+            (spirv::Op::TypeFloat, &[mr::Operand::LiteralInt32(width), ..])
+                if width != 16 && width != 32 =>
+            {
+                issues.push(WgslIssue::ScalarWidth { type_id, opcode: inst.class.opcode, width })
+            }
+            (spirv::Op::TypeImage, operands) => {
+                let sampled = match operands.get(5) {
+                    Some(&mr::Operand::LiteralInt32(v)) => v,
+                    _ => 0,
+                };
+                if let Some(&mr::Operand::ImageFormat(format)) = operands.get(6) {
+                    let unknown = format == spirv::ImageFormat::Unknown;
+                    if (!unknown && !is_wgsl_format(format)) || (unknown && sampled == 2) {
+                        issues.push(WgslIssue::ImageFormat { type_id, format });
+                    }
+                }
+            }
+            (spirv::Op::TypeStruct, members) => {
+                let last = members.len().saturating_sub(1);
+                for (member, operand) in members.iter().enumerate() {
+                    if let mr::Operand::IdRef(id) = *operand {
+                        if member != last && runtime_arrays.contains(&id) {
+                            issues.push(WgslIssue::UnsizedArrayNotLast {
+                                struct_id: type_id,
+                                member: member as u32,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{check_wgsl_compatibility, WgslIssue};
+
+    #[test]
+    fn test_compatible_module() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let runtime = b.type_runtime_array(float);
+        b.type_struct(vec![float, runtime]);
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, main, "main", vec![]);
+        assert!(check_wgsl_compatibility(&b.module()).is_empty());
+    }
+
+    #[test]
+    fn test_incompatible_module() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let byte = b.type_int(8, 0);
+        let runtime = b.type_runtime_array(float);
+        let block = b.type_struct(vec![runtime, float]);
+        let image = b.type_image(
+            float,
+            spirv::Dim::Dim2D,
+            0,
+            0,
+            0,
+            2,
+            spirv::ImageFormat::Rgb10A2,
+            None,
+        );
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Geometry, main, "main", vec![]);
+
+        let issues = check_wgsl_compatibility(&b.module());
+        assert_eq!(
+            vec![
+                WgslIssue::ExecutionModel {
+                    entry_point: main,
+                    name: "main".to_string(),
+                    model: spirv::ExecutionModel::Geometry,
+                },
+                WgslIssue::ScalarWidth { type_id: byte, opcode: spirv::Op::TypeInt, width: 8 },
+                WgslIssue::UnsizedArrayNotLast { struct_id: block, member: 0 },
+                WgslIssue::ImageFormat { type_id: image, format: spirv::ImageFormat::Rgb10A2 },
+            ],
+            issues
+        );
+        assert_eq!(
+            "member 0 of struct %5 is a runtime array but not the last member",
+            issues[2].to_string()
+        );
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measures the throughput of parsing, assembling, disassembling, and
+//! lifting generated modules of different sizes.
+//!
+//! Run with `cargo bench`; pass a filter to only run matching benchmarks,
+//! e.g., `cargo bench -- parse/large`. Criterion compares each run with
+//! the previous one and keeps its reports under `target/criterion`.
+
+#[macro_use]
+extern crate criterion;
+extern crate rspirv;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use rspirv::binary::{Assemble, Disassemble};
+use rspirv::{mr, sr, testing};
+
+const SEED: u64 = 0x5eed;
+
+fn throughput(c: &mut Criterion) {
+    // Generate and assemble the corpus once, outside of the measurements.
+    let corpus: Vec<(&str, mr::Module, Vec<u32>)> = testing::corpus(SEED)
+        .into_iter()
+        .map(|(size, module)| {
+            let words = module.assemble();
+            (size, module, words)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("parse");
+    for &(size, _, ref words) in &corpus {
+        group.throughput(Throughput::Bytes(words.len() as u64 * 4));
+        group.bench_with_input(BenchmarkId::from_parameter(size), words, |b, words| {
+            b.iter(|| mr::load_words(words).unwrap())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("assemble");
+    for &(size, ref module, ref words) in &corpus {
+        group.throughput(Throughput::Bytes(words.len() as u64 * 4));
+        group.bench_with_input(BenchmarkId::from_parameter(size), module, |b, module| {
+            b.iter(|| module.assemble())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("disassemble");
+    for &(size, ref module, ref words) in &corpus {
+        group.throughput(Throughput::Bytes(words.len() as u64 * 4));
+        group.bench_with_input(BenchmarkId::from_parameter(size), module, |b, module| {
+            b.iter(|| module.disassemble())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("lift");
+    for &(size, ref module, ref words) in &corpus {
+        group.throughput(Throughput::Bytes(words.len() as u64 * 4));
+        group.bench_with_input(BenchmarkId::from_parameter(size), module, |b, module| {
+            b.iter(|| sr::Module::from_data(module).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
@@ -14,12 +14,42 @@
 
 use mr;
 
-use utils::num::{bytes_to_u32_le, f32_to_u32};
+#[cfg(feature = "std")]
+use std::io;
+use utils::num::{bytes_to_u32_le, f32_to_u32, f64_to_u64};
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Trait for assembling functionalities.
 pub trait Assemble {
     /// Assembles the current object and returns the binary code.
     fn assemble(&self) -> Vec<u32>;
+
+    /// Assembles the current object and returns the binary code as bytes.
+    ///
+    /// Words are always encoded in little-endian byte order, regardless of
+    /// the endianness of the host.
+    fn assemble_bytes(&self) -> Vec<u8> {
+        words_to_bytes(&self.assemble())
+    }
+
+    /// Assembles the current object and writes the binary code as bytes
+    /// into `writer`.
+    ///
+    /// Words are always encoded in little-endian byte order. Modules,
+    /// functions, and basic blocks are written instruction by instruction,
+    /// without assembling the whole binary code in memory first.
+    #[cfg(feature = "std")]
+    fn assemble_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        writer.write_all(&self.assemble_bytes())
+    }
+}
+
+fn words_to_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
 }
 
 impl Assemble for mr::ModuleHeader {
@@ -49,6 +79,8 @@ impl Assemble for mr::Operand {
             mr::Operand::MemorySemantics(v) => vec![v.bits()],
             mr::Operand::MemoryAccess(v) => vec![v.bits()],
             mr::Operand::KernelProfilingInfo(v) => vec![v.bits()],
+            mr::Operand::RayFlags(v) => vec![v.bits()],
+            mr::Operand::CooperativeMatrixOperands(v) => vec![v.bits()],
             mr::Operand::SourceLanguage(v) => vec![v as u32],
             mr::Operand::ExecutionModel(v) => vec![v as u32],
             mr::Operand::AddressingModel(v) => vec![v as u32],
@@ -71,14 +103,23 @@ impl Assemble for mr::Operand {
             mr::Operand::GroupOperation(v) => vec![v as u32],
             mr::Operand::KernelEnqueueFlags(v) => vec![v as u32],
             mr::Operand::Capability(v) => vec![v as u32],
+            mr::Operand::RayQueryIntersection(v) => vec![v as u32],
+            mr::Operand::RayQueryCommittedIntersectionType(v) => vec![v as u32],
+            mr::Operand::RayQueryCandidateIntersectionType(v) => vec![v as u32],
+            mr::Operand::CooperativeMatrixLayout(v) => vec![v as u32],
+            mr::Operand::CooperativeMatrixUse(v) => vec![v as u32],
             mr::Operand::IdMemorySemantics(v) |
             mr::Operand::IdScope(v) |
             mr::Operand::IdRef(v) |
             mr::Operand::LiteralInt32(v) |
             mr::Operand::LiteralExtInstInteger(v) => vec![v],
-            mr::Operand::LiteralInt64(_) => unimplemented!(),
+            mr::Operand::ExtInstOpcode(v) => vec![v.opcode],
+            mr::Operand::LiteralInt64(v) => vec![v as u32, (v >> 32) as u32],
             mr::Operand::LiteralFloat32(v) => vec![f32_to_u32(v)],
-            mr::Operand::LiteralFloat64(_) => unimplemented!(),
+            mr::Operand::LiteralFloat64(v) => {
+                let bits = f64_to_u64(v);
+                vec![bits as u32, (bits >> 32) as u32]
+            }
             mr::Operand::LiteralSpecConstantOpInteger(v) => vec![v as u32],
             mr::Operand::LiteralString(ref v) => assemble_str(v),
         }
@@ -87,6 +128,14 @@ impl Assemble for mr::Operand {
 
 impl Assemble for mr::Instruction {
     fn assemble(&self) -> Vec<u32> {
+        if let Some(opcode) = self.unknown_opcode() {
+            let mut code = vec![opcode as u32];
+            code.extend(self.result_type);
+            code.extend(self.result_id);
+            code.extend(self.operands[1..].iter().flat_map(|operand| operand.assemble()));
+            code[0] |= (code.len() as u32) << 16;
+            return code;
+        }
         let mut code = vec![self.class.opcode as u32];
         if let Some(r) = self.result_type {
             code.push(r);
@@ -113,6 +162,14 @@ impl Assemble for mr::BasicBlock {
         }
         code
     }
+
+    #[cfg(feature = "std")]
+    fn assemble_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for inst in self.label.iter().chain(&self.instructions) {
+            inst.assemble_into(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl Assemble for mr::Function {
@@ -132,6 +189,20 @@ impl Assemble for mr::Function {
         }
         code
     }
+
+    #[cfg(feature = "std")]
+    fn assemble_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for inst in self.def.iter().chain(&self.parameters) {
+            inst.assemble_into(writer)?;
+        }
+        for bb in &self.basic_blocks {
+            bb.assemble_into(writer)?;
+        }
+        if let Some(ref e) = self.end {
+            e.assemble_into(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl Assemble for mr::Module {
@@ -148,6 +219,20 @@ impl Assemble for mr::Module {
         }
         code
     }
+
+    #[cfg(feature = "std")]
+    fn assemble_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        if let Some(ref h) = self.header {
+            h.assemble_into(writer)?;
+        }
+        for inst in self.global_inst_iter() {
+            inst.assemble_into(writer)?;
+        }
+        for f in &self.functions {
+            f.assemble_into(writer)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +316,43 @@ mod tests {
                        .assemble());
     }
 
+    #[test]
+    fn test_assemble_inst_switch_int64() {
+        let operands = vec![mr::Operand::IdRef(1),
+                            mr::Operand::IdRef(2),
+                            mr::Operand::LiteralInt64(0x1234_5678_9abc_def0),
+                            mr::Operand::IdRef(3)];
+        assert_eq!(vec![wc_op(6, spirv::Op::Switch), 1, 2, 0x9abc_def0, 0x1234_5678, 3],
+                   mr::Instruction::new(spirv::Op::Switch, None, None, operands).assemble());
+    }
+
+    #[test]
+    fn test_assemble_bytes() {
+        let inst = mr::Instruction::new(spirv::Op::Nop, None, None, vec![]);
+        assert_eq!(vec![0x00, 0x00, 0x01, 0x00], inst.assemble_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_assemble_into() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let m = b.module();
+
+        let mut bytes = vec![];
+        m.assemble_into(&mut bytes).unwrap();
+        assert_eq!(m.assemble_bytes(), bytes);
+        assert_eq!(m.assemble().len() * 4, bytes.len());
+        assert_eq!(&[0x03, 0x02, 0x23, 0x07], &bytes[..4]);
+    }
+
     #[test]
     fn test_assemble_function_void() {
         let mut b = mr::Builder::new();
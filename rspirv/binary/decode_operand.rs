@@ -99,6 +99,26 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V RayFlags value.
+    pub fn ray_flags(&mut self) -> Result<spirv::RayFlags> {
+        if let Ok(word) = self.word() {
+            spirv::RayFlags::from_bits(word).ok_or(Error::RayFlagsUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V CooperativeMatrixOperands value.
+    pub fn cooperative_matrix_operands(&mut self) -> Result<spirv::CooperativeMatrixOperands> {
+        if let Ok(word) = self.word() {
+            spirv::CooperativeMatrixOperands::from_bits(word).ok_or(Error::CooperativeMatrixOperandsUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
     /// Decodes and returns the next SPIR-V word as
     /// a SPIR-V SourceLanguage value.
     pub fn source_language(&mut self) -> Result<spirv::SourceLanguage> {
@@ -318,4 +338,54 @@ impl<'a> Decoder<'a> {
             Err(Error::StreamExpected(self.offset))
         }
     }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V RayQueryIntersection value.
+    pub fn ray_query_intersection(&mut self) -> Result<spirv::RayQueryIntersection> {
+        if let Ok(word) = self.word() {
+            spirv::RayQueryIntersection::from_u32(word).ok_or(Error::RayQueryIntersectionUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V RayQueryCommittedIntersectionType value.
+    pub fn ray_query_committed_intersection_type(&mut self) -> Result<spirv::RayQueryCommittedIntersectionType> {
+        if let Ok(word) = self.word() {
+            spirv::RayQueryCommittedIntersectionType::from_u32(word).ok_or(Error::RayQueryCommittedIntersectionTypeUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V RayQueryCandidateIntersectionType value.
+    pub fn ray_query_candidate_intersection_type(&mut self) -> Result<spirv::RayQueryCandidateIntersectionType> {
+        if let Ok(word) = self.word() {
+            spirv::RayQueryCandidateIntersectionType::from_u32(word).ok_or(Error::RayQueryCandidateIntersectionTypeUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V CooperativeMatrixLayout value.
+    pub fn cooperative_matrix_layout(&mut self) -> Result<spirv::CooperativeMatrixLayout> {
+        if let Ok(word) = self.word() {
+            spirv::CooperativeMatrixLayout::from_u32(word).ok_or(Error::CooperativeMatrixLayoutUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
+
+    /// Decodes and returns the next SPIR-V word as
+    /// a SPIR-V CooperativeMatrixUse value.
+    pub fn cooperative_matrix_use(&mut self) -> Result<spirv::CooperativeMatrixUse> {
+        if let Ok(word) = self.word() {
+            spirv::CooperativeMatrixUse::from_u32(word).ok_or(Error::CooperativeMatrixUseUnknown(self.offset - WORD_NUM_BYTES, word))
+        } else {
+            Err(Error::StreamExpected(self.offset))
+        }
+    }
 }
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use mr;
 use spirv;
 
 use std::{mem, result};
 use super::error::Error;
 
 use utils::num::u32_to_bytes;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 pub type Result<T> = result::Result<T, Error>;
 
@@ -96,11 +99,26 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Creates a new `Decoder` instance starting decoding at the given byte
+    /// `offset`.
+    pub(super) fn with_offset(bytes: &'a [u8], offset: usize) -> Decoder<'a> {
+        Decoder {
+            bytes,
+            offset,
+            limit: None,
+        }
+    }
+
     /// Returns the offset of the byte to decode next.
     pub fn offset(&self) -> usize {
         self.offset
     }
 
+    /// Sets the offset of the byte to decode next.
+    pub(super) fn set_offset(&mut self, offset: usize) {
+        self.offset = offset
+    }
+
     /// Decodes and returns the next raw SPIR-V word.
     pub fn word(&mut self) -> Result<spirv::Word> {
         if self.has_limit() {
@@ -146,6 +164,12 @@ impl<'a> Decoder<'a> {
         self.limit = None
     }
 
+    /// Returns the number of words left before reaching the limit, if one
+    /// has been set.
+    pub(super) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
     /// Returns true if a limit has been set on this decoder.
     pub fn has_limit(&self) -> bool {
         self.limit.is_some()
@@ -191,6 +215,13 @@ impl<'a> Decoder<'a> {
             .map_err(|e| Error::DecodeStringFailed(start_offset, format!("{}", e)))
     }
 
+    /// Decodes and returns a literal string as a symbol.
+    ///
+    /// This method behaves the same as [`string`](#method.string).
+    pub fn symbol(&mut self) -> Result<mr::Symbol> {
+        self.string().map(mr::Symbol::from)
+    }
+
     /// Decodes and returns the next SPIR-V word as a 32-bit
     /// literal integer.
     pub fn int32(&mut self) -> Result<u32> {
@@ -239,6 +270,8 @@ mod tests {
 
     use utils::num::f32_to_bytes;
     use utils::num::f64_to_bytes;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
 
     #[test]
     fn test_decoding_word_from_one_bytes() {
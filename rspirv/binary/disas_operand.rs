@@ -125,4 +125,35 @@ impl Disassemble for spirv::KernelProfilingInfo {
         if self.contains(spirv::KernelProfilingInfo::CMD_EXEC_TIME) { bits.push("CmdExecTime") }
         bits.join("|")
     }
+}
+
+impl Disassemble for spirv::RayFlags {
+    fn disassemble(&self) -> String {
+        if self.is_empty() { return "None".to_string() }
+        let mut bits = vec![];
+        if self.contains(spirv::RayFlags::OPAQUE_KHR) { bits.push("OpaqueKHR") }
+        if self.contains(spirv::RayFlags::NO_OPAQUE_KHR) { bits.push("NoOpaqueKHR") }
+        if self.contains(spirv::RayFlags::TERMINATE_ON_FIRST_HIT_KHR) { bits.push("TerminateOnFirstHitKHR") }
+        if self.contains(spirv::RayFlags::SKIP_CLOSEST_HIT_SHADER_KHR) { bits.push("SkipClosestHitShaderKHR") }
+        if self.contains(spirv::RayFlags::CULL_BACK_FACING_TRIANGLES_KHR) { bits.push("CullBackFacingTrianglesKHR") }
+        if self.contains(spirv::RayFlags::CULL_FRONT_FACING_TRIANGLES_KHR) { bits.push("CullFrontFacingTrianglesKHR") }
+        if self.contains(spirv::RayFlags::CULL_OPAQUE_KHR) { bits.push("CullOpaqueKHR") }
+        if self.contains(spirv::RayFlags::CULL_NO_OPAQUE_KHR) { bits.push("CullNoOpaqueKHR") }
+        if self.contains(spirv::RayFlags::SKIP_TRIANGLES_KHR) { bits.push("SkipTrianglesKHR") }
+        if self.contains(spirv::RayFlags::SKIP_AABBS_KHR) { bits.push("SkipAABBsKHR") }
+        bits.join("|")
+    }
+}
+
+impl Disassemble for spirv::CooperativeMatrixOperands {
+    fn disassemble(&self) -> String {
+        if self.is_empty() { return "None".to_string() }
+        let mut bits = vec![];
+        if self.contains(spirv::CooperativeMatrixOperands::MATRIX_ASIGNED_COMPONENTS_KHR) { bits.push("MatrixASignedComponentsKHR") }
+        if self.contains(spirv::CooperativeMatrixOperands::MATRIX_BSIGNED_COMPONENTS_KHR) { bits.push("MatrixBSignedComponentsKHR") }
+        if self.contains(spirv::CooperativeMatrixOperands::MATRIX_CSIGNED_COMPONENTS_KHR) { bits.push("MatrixCSignedComponentsKHR") }
+        if self.contains(spirv::CooperativeMatrixOperands::MATRIX_RESULT_SIGNED_COMPONENTS_KHR) { bits.push("MatrixResultSignedComponentsKHR") }
+        if self.contains(spirv::CooperativeMatrixOperands::SATURATING_ACCUMULATION_KHR) { bits.push("SaturatingAccumulationKHR") }
+        bits.join("|")
+    }
 }
\ No newline at end of file
@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use grammar;
 use mr;
 use spirv;
 
+use std::collections::BTreeMap;
+use std::ptr;
 use super::tracker;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Trait for disassembling functionalities.
 pub trait Disassemble {
@@ -51,6 +56,8 @@ impl Disassemble for mr::Operand {
             mr::Operand::MemorySemantics(v) => v.disassemble(),
             mr::Operand::MemoryAccess(v) => v.disassemble(),
             mr::Operand::KernelProfilingInfo(v) => v.disassemble(),
+            mr::Operand::RayFlags(v) => v.disassemble(),
+            mr::Operand::CooperativeMatrixOperands(v) => v.disassemble(),
             _ => format!("{}", self),
         }
     }
@@ -67,6 +74,15 @@ fn disas_join<T: Disassemble>(insts: &[T], delimiter: &str) -> String {
 
 impl Disassemble for mr::Instruction {
     fn disassemble(&self) -> String {
+        let mut operands = &self.operands[..];
+        if let Some(opcode) = self.unknown_opcode() {
+            if ptr::eq(self.class, &grammar::UNKNOWN_INSTRUCTION) {
+                let words: Vec<String> = operands[1..].iter().map(|w| w.to_string()).collect();
+                return format!("OpUnknown({}) {}", opcode, words.join(" ")).trim_end().to_string();
+            }
+            // Decoded by a dynamic grammar.
+            operands = &operands[1..];
+        }
         format!("{rid}{opcode}{rtype}{space}{operands}",
                 rid = self.result_id
                           .map_or(String::new(), |w| format!("%{} = ", w)),
@@ -74,12 +90,12 @@ impl Disassemble for mr::Instruction {
                 // extra space both before and after the reseult type
                 rtype = self.result_type
                             .map_or(String::new(), |w| format!("  %{} ", w)),
-                space = if !self.operands.is_empty() {
+                space = if !operands.is_empty() {
                     " "
                 } else {
                     ""
                 },
-                operands = disas_join(&self.operands, " "))
+                operands = disas_join(operands, " "))
     }
 }
 
@@ -125,48 +141,245 @@ macro_rules! push {
     });
 }
 
-impl Disassemble for mr::Module {
-    fn disassemble(&self) -> String {
-        let mut ext_inst_set_tracker = tracker::ExtInstSetTracker::new();
-        for i in &self.ext_inst_imports {
-            ext_inst_set_tracker.track(i)
+/// How to print floating point numbers in disassembly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest decimal representation that round trips, e.g., `0.1`.
+    Shortest,
+    /// Scientific notation with all significant digits of the type, e.g.,
+    /// `1.00000001e-1`.
+    FullPrecision,
+    /// Hexadecimal floating point notation as in C99, e.g.,
+    /// `0x1.99999ap-4`.
+    Hex,
+}
+
+/// Options for disassembling modules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisassembleOptions {
+    /// How to print the values of floating point constants.
+    pub float_format: FloatFormat,
+}
+
+impl Default for DisassembleOptions {
+    fn default() -> Self {
+        DisassembleOptions { float_format: FloatFormat::Shortest }
+    }
+}
+
+/// A scalar type constants can be of.
+#[derive(Clone, Copy)]
+enum ScalarType {
+    Int(u32, bool),
+    Float(u32),
+}
+
+fn scalar_type(inst: &mr::Instruction) -> Option<ScalarType> {
+    match (inst.class.opcode, &inst.operands[..]) {
+        (spirv::Op::TypeInt,
+         &[mr::Operand::LiteralInt32(width), mr::Operand::LiteralInt32(signedness)]) => {
+            Some(ScalarType::Int(width, signedness != 0))
+        }
+        (spirv::Op::TypeFloat, &[mr::Operand::LiteralInt32(width)]) => {
+            Some(ScalarType::Float(width))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the raw bits of the value of a numeric constant.
+fn constant_bits(operands: &[mr::Operand]) -> Option<u64> {
+    match *operands {
+        [mr::Operand::LiteralInt32(v)] => Some(v as u64),
+        [mr::Operand::LiteralInt32(low), mr::Operand::LiteralInt32(high)] => {
+            Some(((high as u64) << 32) | low as u64)
         }
+        [mr::Operand::LiteralInt64(v)] => Some(v),
+        [mr::Operand::LiteralFloat32(v)] => Some(v.to_bits() as u64),
+        [mr::Operand::LiteralFloat64(v)] => Some(v.to_bits()),
+        _ => None,
+    }
+}
+
+/// Formats the floating point number with the given `bits` and
+/// (exponent, mantissa) widths in C99 hexadecimal notation.
+fn format_hex_float(bits: u64, exponent_bits: u32, mantissa_bits: u32) -> String {
+    let sign = if (bits >> (exponent_bits + mantissa_bits)) & 1 == 1 { "-" } else { "" };
+    let bias = (1i64 << (exponent_bits - 1)) - 1;
+    let exponent = ((bits >> mantissa_bits) & ((1 << exponent_bits) - 1)) as i64;
+    let mantissa = bits & ((1 << mantissa_bits) - 1);
+    let (leading, exponent) = match (exponent, mantissa) {
+        (0, 0) => return format!("{}0x0p+0", sign),
+        // Subnormal numbers.
+        (0, _) => (0, 1 - bias),
+        // Normal numbers. Infinity and NaN are shown with the maximal
+        // exponent, like spirv-dis does.
+        _ => (1, exponent - bias),
+    };
+    // Align the mantissa to whole hexadecimal digits.
+    let padding = (4 - mantissa_bits % 4) % 4;
+    let digits = ((mantissa_bits + padding) / 4) as usize;
+    let fraction = format!("{:0width$x}", mantissa << padding, width = digits);
+    let fraction = fraction.trim_end_matches('0');
+    format!("{}0x{}{}{}p{:+}",
+            sign,
+            leading,
+            if fraction.is_empty() { "" } else { "." },
+            fraction,
+            exponent)
+}
+
+/// Converts the given half-precision floating point number to single
+/// precision.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    // 2^e for normal single precision exponents, without relying on `std`.
+    let exp2 = |e: i32| f32::from_bits(((e + 127) as u32) << 23);
+    sign *
+    match exponent {
+        0 => mantissa * exp2(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * exp2(exponent - 15),
+    }
+}
+
+fn format_float(bits: u64, width: u32, format: FloatFormat) -> Option<String> {
+    let value = match width {
+        16 => f16_to_f32(bits as u16) as f64,
+        32 => f32::from_bits(bits as u32) as f64,
+        64 => f64::from_bits(bits),
+        _ => return None,
+    };
+    Some(match (format, width) {
+        (FloatFormat::Hex, 16) => format_hex_float(bits, 5, 10),
+        (FloatFormat::Hex, 32) => format_hex_float(bits, 8, 23),
+        (FloatFormat::Hex, _) => format_hex_float(bits, 11, 52),
+        (FloatFormat::FullPrecision, 64) => format!("{:.16e}", value),
+        (FloatFormat::FullPrecision, 32) => format!("{:.8e}", value as f32),
+        (FloatFormat::FullPrecision, _) => format!("{:.4e}", value as f32),
+        (FloatFormat::Shortest, 64) => format!("{:?}", value),
+        (FloatFormat::Shortest, _) => format!("{:?}", value as f32),
+    })
+}
 
-        let mut text = vec![];
-        if let Some(ref header) = self.header {
-            push!(&mut text, header.disassemble());
+/// Disassembles the given OpConstant or OpSpecConstant instruction,
+/// decoding its value according to its type.
+fn disas_constant(inst: &mr::Instruction,
+                  types: &BTreeMap<spirv::Word, ScalarType>,
+                  options: &DisassembleOptions)
+                  -> String {
+    let ty = inst.result_type.and_then(|t| types.get(&t));
+    let bits = constant_bits(&inst.operands);
+    let value = match (ty, bits) {
+        (Some(&ScalarType::Float(width)), Some(bits)) => {
+            format_float(bits, width, options.float_format)
+        }
+        (Some(&ScalarType::Int(width, true)), Some(bits)) if width > 0 && width <= 64 => {
+            // Sign extend from the type width.
+            let shift = 64 - width;
+            Some(format!("{}", ((bits << shift) as i64) >> shift))
+        }
+        (Some(&ScalarType::Int(..)), Some(bits)) => Some(format!("{}", bits)),
+        _ => None,
+    };
+    match (value, inst.result_id, inst.result_type) {
+        (Some(value), Some(rid), Some(rtype)) => {
+            format!("%{} = Op{}  %{}  {}", rid, inst.class.opname, rtype, value)
         }
+        _ => inst.disassemble(),
+    }
+}
+
+/// Disassembles the given `module` with the given `options`.
+///
+/// The values of OpConstant and OpSpecConstant instructions are decoded
+/// according to their result types: e.g., signed integers are printed as
+/// such, and floating point numbers are printed in the requested format
+/// even if they are stored as raw words in the module.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::binary::{disassemble_with_options, DisassembleOptions, FloatFormat};
+///
+/// fn main() {
+///     let mut b = rspirv::mr::Builder::new();
+///     let float = b.type_float(32);
+///     b.constant_f32(float, 0.5);
+///     let options = DisassembleOptions { float_format: FloatFormat::Hex };
+///
+///     assert_eq!(disassemble_with_options(&b.module(), &options),
+///                "; SPIR-V\n\
+///                 ; Version: 1.3\n\
+///                 ; Generator: rspirv\n\
+///                 ; Bound: 3\n\
+///                 %1 = OpTypeFloat 32\n\
+///                 %2 = OpConstant  %1  0x1p-1");
+/// }
+/// ```
+pub fn disassemble_with_options(module: &mr::Module, options: &DisassembleOptions) -> String {
+    let mut ext_inst_set_tracker = tracker::ExtInstSetTracker::new();
+    for i in &module.ext_inst_imports {
+        ext_inst_set_tracker.track(i)
+    }
+    let mut types = BTreeMap::new();
+    for i in &module.types_global_values {
+        if let (Some(id), Some(ty)) = (i.result_id, scalar_type(i)) {
+            types.insert(id, ty);
+        }
+    }
 
-        let global_insts = self.global_inst_iter()
-                               .map(|i| i.disassemble())
-                               .collect::<Vec<String>>()
-                               .join("\n");
-        push!(&mut text, global_insts);
+    let mut text = vec![];
+    if let Some(ref header) = module.header {
+        push!(&mut text, header.disassemble());
+    }
 
-        // TODO: Code here is essentially duplicated.
-        for f in &self.functions {
+    let global_insts = module.global_inst_iter()
+                             .map(|i| match i.class.opcode {
+                                 spirv::Op::Constant | spirv::Op::SpecConstant => {
+                                     disas_constant(i, &types, options)
+                                 }
+                                 _ => i.disassemble(),
+                             })
+                             .collect::<Vec<String>>()
+                             .join("\n");
+    push!(&mut text, global_insts);
+
+    // TODO: Code here is essentially duplicated.
+    for f in &module.functions {
+        push!(&mut text,
+              f.def.as_ref().map_or(String::new(), |i| i.disassemble()));
+        push!(&mut text, disas_join(&f.parameters, "\n"));
+        for bb in &f.basic_blocks {
             push!(&mut text,
-                  f.def.as_ref().map_or(String::new(), |i| i.disassemble()));
-            push!(&mut text, disas_join(&f.parameters, "\n"));
-            for bb in &f.basic_blocks {
-                push!(&mut text,
-                      bb.label
-                        .as_ref()
-                        .map_or(String::new(), |i| i.disassemble()));
-                for inst in &bb.instructions {
-                    match inst.class.opcode {
-                        spirv::Op::ExtInst => {
-                            push!(&mut text, disas_ext_inst(inst, &ext_inst_set_tracker))
-                        }
-                        _ => push!(&mut text, inst.disassemble()),
+                  bb.label
+                    .as_ref()
+                    .map_or(String::new(), |i| i.disassemble()));
+            for inst in &bb.instructions {
+                match inst.class.opcode {
+                    spirv::Op::ExtInst => {
+                        push!(&mut text, disas_ext_inst(inst, &ext_inst_set_tracker))
                     }
+                    _ => push!(&mut text, inst.disassemble()),
                 }
             }
-            push!(&mut text,
-                  f.end.as_ref().map_or(String::new(), |i| i.disassemble()));
         }
+        push!(&mut text,
+              f.end.as_ref().map_or(String::new(), |i| i.disassemble()));
+    }
 
-        text.join("\n")
+    text.join("\n")
+}
+
+impl Disassemble for mr::Module {
+    fn disassemble(&self) -> String {
+        disassemble_with_options(self, &DisassembleOptions::default())
     }
 }
 
@@ -209,6 +422,8 @@ mod tests {
     use spirv;
 
     use binary::Disassemble;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
 
     #[test]
     fn test_disassemble_operand_function_control() {
@@ -363,4 +578,113 @@ mod tests {
                     OpReturn\n\
                     OpFunctionEnd");
     }
+
+    #[test]
+    fn test_disassemble_ext_inst_debug_info() {
+        let mut b = mr::Builder::new();
+
+        let debug = b.ext_inst_import("NonSemantic.Shader.DebugInfo.100");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+
+        let file = b.string("shader.glsl");
+        let void = b.type_void();
+        let voidfvoid = b.type_function(void, vec![]);
+
+        assert!(b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).is_ok());
+        b.begin_basic_block(None).unwrap();
+        let op = spirv::DebugInfoOp::DebugSource as u32;
+        assert!(b.ext_inst(void, None, debug, op, vec![file]).is_ok());
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        assert_eq!(b.module().disassemble(),
+                   "; SPIR-V\n\
+                    ; Version: 1.3\n\
+                    ; Generator: rspirv\n\
+                    ; Bound: 8\n\
+                    %1 = OpExtInstImport \"NonSemantic.Shader.DebugInfo.100\"\n\
+                    OpMemoryModel Logical GLSL450\n\
+                    %2 = OpString \"shader.glsl\"\n\
+                    %3 = OpTypeVoid\n\
+                    %4 = OpTypeFunction %3\n\
+                    %5 = OpFunction  %3  None %4\n\
+                    %6 = OpLabel\n\
+                    %7 = OpExtInst  %3  %1 DebugSource %2\n\
+                    OpReturn\n\
+                    OpFunctionEnd");
+    }
+
+    #[test]
+    fn test_disassemble_constants_by_type() {
+        let mut b = mr::Builder::new();
+        let int = b.type_int(32, 1);
+        let uint = b.type_int(32, 0);
+        let short = b.type_int(16, 1);
+        let half = b.type_float(16);
+        let float = b.type_float(32);
+        let double = b.type_float(64);
+        b.constant_u32(int, 0xffff_fffe);
+        b.constant_u32(uint, 0xffff_fffe);
+        b.constant_u32(short, 0xffff);
+        b.constant_u32(half, 0x3c00);
+        // A float stored as raw word.
+        b.constant_u32(float, 0x3dcc_cccd);
+        b.spec_constant_f32(float, -2.5);
+        let mut module = b.module();
+        module.types_global_values.push(mr::Instruction::new(
+            spirv::Op::Constant,
+            Some(double),
+            Some(20),
+            vec![mr::Operand::LiteralInt32(0), mr::Operand::LiteralInt32(0x3ff8_0000)],
+        ));
+        let constants = |format| {
+            let options = super::DisassembleOptions { float_format: format };
+            super::disassemble_with_options(&module, &options)
+                .lines()
+                .filter(|l| l.contains("Constant"))
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            vec![
+                "%7 = OpConstant  %1  -2",
+                "%8 = OpConstant  %2  4294967294",
+                "%9 = OpConstant  %3  -1",
+                "%10 = OpConstant  %4  1.0",
+                "%11 = OpConstant  %5  0.1",
+                "%12 = OpSpecConstant  %5  -2.5",
+                "%20 = OpConstant  %6  1.5",
+            ],
+            constants(super::FloatFormat::Shortest)
+        );
+        assert_eq!(
+            vec![
+                "%10 = OpConstant  %4  1.0000e0",
+                "%11 = OpConstant  %5  1.00000001e-1",
+                "%12 = OpSpecConstant  %5  -2.50000000e0",
+                "%20 = OpConstant  %6  1.5000000000000000e0",
+            ],
+            &constants(super::FloatFormat::FullPrecision)[3..]
+        );
+        assert_eq!(
+            vec![
+                "%10 = OpConstant  %4  0x1p+0",
+                "%11 = OpConstant  %5  0x1.99999ap-4",
+                "%12 = OpSpecConstant  %5  -0x1.4p+1",
+                "%20 = OpConstant  %6  0x1.8p+0",
+            ],
+            &constants(super::FloatFormat::Hex)[3..]
+        );
+    }
+
+    #[test]
+    fn test_format_hex_float() {
+        use super::format_hex_float;
+        assert_eq!("0x0p+0", format_hex_float(0, 8, 23));
+        assert_eq!("-0x0p+0", format_hex_float(0x8000_0000, 8, 23));
+        assert_eq!("0x1p+128", format_hex_float(0x7f80_0000, 8, 23));
+        assert_eq!("0x0.000002p-126", format_hex_float(1, 8, 23));
+        assert_eq!("0x1.ffcp+15", format_hex_float(0x7bff, 5, 10));
+        assert_eq!("0x1.0000000000001p+0", format_hex_float(0x3ff0_0000_0000_0001, 11, 52));
+    }
 }
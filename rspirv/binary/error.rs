@@ -20,6 +20,8 @@
 
 use spirv;
 use std::{error, fmt};
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Decoder Error.
 #[derive(Debug, PartialEq)]
@@ -34,6 +36,8 @@ pub enum Error {
     MemorySemanticsUnknown(usize, spirv::Word),
     MemoryAccessUnknown(usize, spirv::Word),
     KernelProfilingInfoUnknown(usize, spirv::Word),
+    RayFlagsUnknown(usize, spirv::Word),
+    CooperativeMatrixOperandsUnknown(usize, spirv::Word),
     SourceLanguageUnknown(usize, spirv::Word),
     ExecutionModelUnknown(usize, spirv::Word),
     AddressingModelUnknown(usize, spirv::Word),
@@ -56,6 +60,11 @@ pub enum Error {
     GroupOperationUnknown(usize, spirv::Word),
     KernelEnqueueFlagsUnknown(usize, spirv::Word),
     CapabilityUnknown(usize, spirv::Word),
+    RayQueryIntersectionUnknown(usize, spirv::Word),
+    RayQueryCommittedIntersectionTypeUnknown(usize, spirv::Word),
+    RayQueryCandidateIntersectionTypeUnknown(usize, spirv::Word),
+    CooperativeMatrixLayoutUnknown(usize, spirv::Word),
+    CooperativeMatrixUseUnknown(usize, spirv::Word),
     /// Failed to decode a string.
     ///
     /// For structured error handling, the second element could be
@@ -77,6 +86,8 @@ impl fmt::Display for Error {
             Error::MemorySemanticsUnknown(index, word) => write!(f, "unknown value {} for operand kind MemorySemantics at index {}", word, index),
             Error::MemoryAccessUnknown(index, word) => write!(f, "unknown value {} for operand kind MemoryAccess at index {}", word, index),
             Error::KernelProfilingInfoUnknown(index, word) => write!(f, "unknown value {} for operand kind KernelProfilingInfo at index {}", word, index),
+            Error::RayFlagsUnknown(index, word) => write!(f, "unknown value {} for operand kind RayFlags at index {}", word, index),
+            Error::CooperativeMatrixOperandsUnknown(index, word) => write!(f, "unknown value {} for operand kind CooperativeMatrixOperands at index {}", word, index),
             Error::SourceLanguageUnknown(index, word) => write!(f, "unknown value {} for operand kind SourceLanguage at index {}", word, index),
             Error::ExecutionModelUnknown(index, word) => write!(f, "unknown value {} for operand kind ExecutionModel at index {}", word, index),
             Error::AddressingModelUnknown(index, word) => write!(f, "unknown value {} for operand kind AddressingModel at index {}", word, index),
@@ -99,6 +110,11 @@ impl fmt::Display for Error {
             Error::GroupOperationUnknown(index, word) => write!(f, "unknown value {} for operand kind GroupOperation at index {}", word, index),
             Error::KernelEnqueueFlagsUnknown(index, word) => write!(f, "unknown value {} for operand kind KernelEnqueueFlags at index {}", word, index),
             Error::CapabilityUnknown(index, word) => write!(f, "unknown value {} for operand kind Capability at index {}", word, index),
+            Error::RayQueryIntersectionUnknown(index, word) => write!(f, "unknown value {} for operand kind RayQueryIntersection at index {}", word, index),
+            Error::RayQueryCommittedIntersectionTypeUnknown(index, word) => write!(f, "unknown value {} for operand kind RayQueryCommittedIntersectionType at index {}", word, index),
+            Error::RayQueryCandidateIntersectionTypeUnknown(index, word) => write!(f, "unknown value {} for operand kind RayQueryCandidateIntersectionType at index {}", word, index),
+            Error::CooperativeMatrixLayoutUnknown(index, word) => write!(f, "unknown value {} for operand kind CooperativeMatrixLayout at index {}", word, index),
+            Error::CooperativeMatrixUseUnknown(index, word) => write!(f, "unknown value {} for operand kind CooperativeMatrixUse at index {}", word, index),
             Error::DecodeStringFailed(index, ref e) => write!(f, "cannot decode string at index {}: {}", index, e),
         }
     }
@@ -23,15 +23,27 @@
 //!   of the SPIR-V grammar. It works with the
 //!   [`Consumer`](trait.Consumer.html) to process a SPIR-V binary on the
 //!   instruction level.
+//!
+//! For quickly indexing a SPIR-V binary without decoding operands, see
+//! [`scan_words`](fn.scan_words.html). For quickly finding out the entry
+//! points of a SPIR-V binary, see
+//! [`parse_entry_points_only`](fn.parse_entry_points_only.html). To check
+//! that a binary survives parsing and assembling bit for bit, see
+//! [`verify_roundtrip`](fn.verify_roundtrip.html).
 
 pub use self::decoder::Decoder;
 pub use self::error::Error as DecodeError;
 pub use self::parser::{Consumer, parse_bytes, parse_words, Parser};
 pub use self::parser::Action as ParseAction;
+pub use self::parser::Diagnostic as ParseDiagnostic;
 pub use self::parser::Result as ParseResult;
 pub use self::parser::State as ParseState;
+pub use self::roundtrip::{verify_roundtrip, RoundtripError};
+pub use self::scan::{instruction_word_count, is_word_count_valid, scan_words, Scanner};
+pub use self::scan::{parse_entry_points_only, parse_header};
 
-pub use self::disassemble::Disassemble;
+pub use self::disassemble::{disassemble_with_options, Disassemble, DisassembleOptions};
+pub use self::disassemble::FloatFormat;
 pub use self::assemble::Assemble;
 
 mod assemble;
@@ -39,4 +51,6 @@ mod decoder;
 mod disassemble;
 mod error;
 mod parser;
+mod roundtrip;
+mod scan;
 mod tracker;
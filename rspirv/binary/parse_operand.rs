@@ -17,76 +17,83 @@
 // DO NOT MODIFY!
 
 impl<'c, 'd> Parser<'c, 'd> {
-    fn parse_operand(&mut self, kind: GOpKind) -> Result<Vec<mr::Operand>> {
+    fn parse_operand(&mut self, kind: GOpKind) -> Result<mr::Operands> {
         Ok(match kind {
-            GOpKind::FPFastMathMode => vec![mr::Operand::FPFastMathMode(try_decode!(self.decoder.fpfast_math_mode()))],
-            GOpKind::SelectionControl => vec![mr::Operand::SelectionControl(try_decode!(self.decoder.selection_control()))],
-            GOpKind::FunctionControl => vec![mr::Operand::FunctionControl(try_decode!(self.decoder.function_control()))],
-            GOpKind::MemorySemantics => vec![mr::Operand::MemorySemantics(try_decode!(self.decoder.memory_semantics()))],
-            GOpKind::KernelProfilingInfo => vec![mr::Operand::KernelProfilingInfo(try_decode!(self.decoder.kernel_profiling_info()))],
-            GOpKind::SourceLanguage => vec![mr::Operand::SourceLanguage(try_decode!(self.decoder.source_language()))],
-            GOpKind::ExecutionModel => vec![mr::Operand::ExecutionModel(try_decode!(self.decoder.execution_model()))],
-            GOpKind::AddressingModel => vec![mr::Operand::AddressingModel(try_decode!(self.decoder.addressing_model()))],
-            GOpKind::MemoryModel => vec![mr::Operand::MemoryModel(try_decode!(self.decoder.memory_model()))],
-            GOpKind::StorageClass => vec![mr::Operand::StorageClass(try_decode!(self.decoder.storage_class()))],
-            GOpKind::Dim => vec![mr::Operand::Dim(try_decode!(self.decoder.dim()))],
-            GOpKind::SamplerAddressingMode => vec![mr::Operand::SamplerAddressingMode(try_decode!(self.decoder.sampler_addressing_mode()))],
-            GOpKind::SamplerFilterMode => vec![mr::Operand::SamplerFilterMode(try_decode!(self.decoder.sampler_filter_mode()))],
-            GOpKind::ImageFormat => vec![mr::Operand::ImageFormat(try_decode!(self.decoder.image_format()))],
-            GOpKind::ImageChannelOrder => vec![mr::Operand::ImageChannelOrder(try_decode!(self.decoder.image_channel_order()))],
-            GOpKind::ImageChannelDataType => vec![mr::Operand::ImageChannelDataType(try_decode!(self.decoder.image_channel_data_type()))],
-            GOpKind::FPRoundingMode => vec![mr::Operand::FPRoundingMode(try_decode!(self.decoder.fprounding_mode()))],
-            GOpKind::LinkageType => vec![mr::Operand::LinkageType(try_decode!(self.decoder.linkage_type()))],
-            GOpKind::AccessQualifier => vec![mr::Operand::AccessQualifier(try_decode!(self.decoder.access_qualifier()))],
-            GOpKind::FunctionParameterAttribute => vec![mr::Operand::FunctionParameterAttribute(try_decode!(self.decoder.function_parameter_attribute()))],
-            GOpKind::BuiltIn => vec![mr::Operand::BuiltIn(try_decode!(self.decoder.built_in()))],
-            GOpKind::Scope => vec![mr::Operand::Scope(try_decode!(self.decoder.scope()))],
-            GOpKind::GroupOperation => vec![mr::Operand::GroupOperation(try_decode!(self.decoder.group_operation()))],
-            GOpKind::KernelEnqueueFlags => vec![mr::Operand::KernelEnqueueFlags(try_decode!(self.decoder.kernel_enqueue_flags()))],
-            GOpKind::Capability => vec![mr::Operand::Capability(try_decode!(self.decoder.capability()))],
-            GOpKind::IdMemorySemantics => vec![mr::Operand::IdMemorySemantics(try_decode!(self.decoder.id()))],
-            GOpKind::IdScope => vec![mr::Operand::IdScope(try_decode!(self.decoder.id()))],
-            GOpKind::IdRef => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            GOpKind::LiteralInteger => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            GOpKind::LiteralString => vec![mr::Operand::LiteralString(try_decode!(self.decoder.string()))],
-            GOpKind::LiteralExtInstInteger => vec![mr::Operand::LiteralExtInstInteger(try_decode!(self.decoder.ext_inst_integer()))],
+            GOpKind::FPFastMathMode => mr::Operands::from([mr::Operand::FPFastMathMode(try_decode!(self, self.decoder.fpfast_math_mode()))]),
+            GOpKind::SelectionControl => mr::Operands::from([mr::Operand::SelectionControl(try_decode!(self, self.decoder.selection_control()))]),
+            GOpKind::FunctionControl => mr::Operands::from([mr::Operand::FunctionControl(try_decode!(self, self.decoder.function_control()))]),
+            GOpKind::MemorySemantics => mr::Operands::from([mr::Operand::MemorySemantics(try_decode!(self, self.decoder.memory_semantics()))]),
+            GOpKind::KernelProfilingInfo => mr::Operands::from([mr::Operand::KernelProfilingInfo(try_decode!(self, self.decoder.kernel_profiling_info()))]),
+            GOpKind::RayFlags => mr::Operands::from([mr::Operand::RayFlags(try_decode!(self, self.decoder.ray_flags()))]),
+            GOpKind::CooperativeMatrixOperands => mr::Operands::from([mr::Operand::CooperativeMatrixOperands(try_decode!(self, self.decoder.cooperative_matrix_operands()))]),
+            GOpKind::SourceLanguage => mr::Operands::from([mr::Operand::SourceLanguage(try_decode!(self, self.decoder.source_language()))]),
+            GOpKind::ExecutionModel => mr::Operands::from([mr::Operand::ExecutionModel(try_decode!(self, self.decoder.execution_model()))]),
+            GOpKind::AddressingModel => mr::Operands::from([mr::Operand::AddressingModel(try_decode!(self, self.decoder.addressing_model()))]),
+            GOpKind::MemoryModel => mr::Operands::from([mr::Operand::MemoryModel(try_decode!(self, self.decoder.memory_model()))]),
+            GOpKind::StorageClass => mr::Operands::from([mr::Operand::StorageClass(try_decode!(self, self.decoder.storage_class()))]),
+            GOpKind::Dim => mr::Operands::from([mr::Operand::Dim(try_decode!(self, self.decoder.dim()))]),
+            GOpKind::SamplerAddressingMode => mr::Operands::from([mr::Operand::SamplerAddressingMode(try_decode!(self, self.decoder.sampler_addressing_mode()))]),
+            GOpKind::SamplerFilterMode => mr::Operands::from([mr::Operand::SamplerFilterMode(try_decode!(self, self.decoder.sampler_filter_mode()))]),
+            GOpKind::ImageFormat => mr::Operands::from([mr::Operand::ImageFormat(try_decode!(self, self.decoder.image_format()))]),
+            GOpKind::ImageChannelOrder => mr::Operands::from([mr::Operand::ImageChannelOrder(try_decode!(self, self.decoder.image_channel_order()))]),
+            GOpKind::ImageChannelDataType => mr::Operands::from([mr::Operand::ImageChannelDataType(try_decode!(self, self.decoder.image_channel_data_type()))]),
+            GOpKind::FPRoundingMode => mr::Operands::from([mr::Operand::FPRoundingMode(try_decode!(self, self.decoder.fprounding_mode()))]),
+            GOpKind::LinkageType => mr::Operands::from([mr::Operand::LinkageType(try_decode!(self, self.decoder.linkage_type()))]),
+            GOpKind::AccessQualifier => mr::Operands::from([mr::Operand::AccessQualifier(try_decode!(self, self.decoder.access_qualifier()))]),
+            GOpKind::FunctionParameterAttribute => mr::Operands::from([mr::Operand::FunctionParameterAttribute(try_decode!(self, self.decoder.function_parameter_attribute()))]),
+            GOpKind::BuiltIn => mr::Operands::from([mr::Operand::BuiltIn(try_decode!(self, self.decoder.built_in()))]),
+            GOpKind::Scope => mr::Operands::from([mr::Operand::Scope(try_decode!(self, self.decoder.scope()))]),
+            GOpKind::GroupOperation => mr::Operands::from([mr::Operand::GroupOperation(try_decode!(self, self.decoder.group_operation()))]),
+            GOpKind::KernelEnqueueFlags => mr::Operands::from([mr::Operand::KernelEnqueueFlags(try_decode!(self, self.decoder.kernel_enqueue_flags()))]),
+            GOpKind::Capability => mr::Operands::from([mr::Operand::Capability(try_decode!(self, self.decoder.capability()))]),
+            GOpKind::RayQueryIntersection => mr::Operands::from([mr::Operand::RayQueryIntersection(try_decode!(self, self.decoder.ray_query_intersection()))]),
+            GOpKind::RayQueryCommittedIntersectionType => mr::Operands::from([mr::Operand::RayQueryCommittedIntersectionType(try_decode!(self, self.decoder.ray_query_committed_intersection_type()))]),
+            GOpKind::RayQueryCandidateIntersectionType => mr::Operands::from([mr::Operand::RayQueryCandidateIntersectionType(try_decode!(self, self.decoder.ray_query_candidate_intersection_type()))]),
+            GOpKind::CooperativeMatrixLayout => mr::Operands::from([mr::Operand::CooperativeMatrixLayout(try_decode!(self, self.decoder.cooperative_matrix_layout()))]),
+            GOpKind::CooperativeMatrixUse => mr::Operands::from([mr::Operand::CooperativeMatrixUse(try_decode!(self, self.decoder.cooperative_matrix_use()))]),
+            GOpKind::IdMemorySemantics => mr::Operands::from([mr::Operand::IdMemorySemantics(try_decode!(self, self.decoder.id()))]),
+            GOpKind::IdScope => mr::Operands::from([mr::Operand::IdScope(try_decode!(self, self.decoder.id()))]),
+            GOpKind::IdRef => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            GOpKind::LiteralInteger => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            GOpKind::LiteralString => mr::Operands::from([mr::Operand::LiteralString(try_decode!(self, self.decoder.symbol()))]),
+            GOpKind::LiteralExtInstInteger => mr::Operands::from([mr::Operand::LiteralExtInstInteger(try_decode!(self, self.decoder.ext_inst_integer()))]),
             GOpKind::PairLiteralIntegerIdRef => {
-                vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32())), mr::Operand::IdRef(try_decode!(self.decoder.id()))]
+                mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32())), mr::Operand::IdRef(try_decode!(self, self.decoder.id()))])
             }
             GOpKind::PairIdRefLiteralInteger => {
-                vec![mr::Operand::IdRef(try_decode!(self.decoder.id())), mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))]
+                mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id())), mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))])
             }
             GOpKind::PairIdRefIdRef => {
-                vec![mr::Operand::IdRef(try_decode!(self.decoder.id())), mr::Operand::IdRef(try_decode!(self.decoder.id()))]
+                mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id())), mr::Operand::IdRef(try_decode!(self, self.decoder.id()))])
             }
             GOpKind::ImageOperands => {
-                let val = try_decode!(self.decoder.image_operands());
-                let mut ops = vec![mr::Operand::ImageOperands(val)];
-                ops.append(&mut self.parse_image_operands_arguments(val)?);
+                let val = try_decode!(self, self.decoder.image_operands());
+                let mut ops = mr::Operands::from([mr::Operand::ImageOperands(val)]);
+                ops.extend(self.parse_image_operands_arguments(val)?);
                 ops
             }
             GOpKind::LoopControl => {
-                let val = try_decode!(self.decoder.loop_control());
-                let mut ops = vec![mr::Operand::LoopControl(val)];
-                ops.append(&mut self.parse_loop_control_arguments(val)?);
+                let val = try_decode!(self, self.decoder.loop_control());
+                let mut ops = mr::Operands::from([mr::Operand::LoopControl(val)]);
+                ops.extend(self.parse_loop_control_arguments(val)?);
                 ops
             }
             GOpKind::MemoryAccess => {
-                let val = try_decode!(self.decoder.memory_access());
-                let mut ops = vec![mr::Operand::MemoryAccess(val)];
-                ops.append(&mut self.parse_memory_access_arguments(val)?);
+                let val = try_decode!(self, self.decoder.memory_access());
+                let mut ops = mr::Operands::from([mr::Operand::MemoryAccess(val)]);
+                ops.extend(self.parse_memory_access_arguments(val)?);
                 ops
             }
             GOpKind::ExecutionMode => {
-                let val = try_decode!(self.decoder.execution_mode());
-                let mut ops = vec![mr::Operand::ExecutionMode(val)];
-                ops.append(&mut self.parse_execution_mode_arguments(val)?);
+                let val = try_decode!(self, self.decoder.execution_mode());
+                let mut ops = mr::Operands::from([mr::Operand::ExecutionMode(val)]);
+                ops.extend(self.parse_execution_mode_arguments(val)?);
                 ops
             }
             GOpKind::Decoration => {
-                let val = try_decode!(self.decoder.decoration());
-                let mut ops = vec![mr::Operand::Decoration(val)];
-                ops.append(&mut self.parse_decoration_arguments(val)?);
+                let val = try_decode!(self, self.decoder.decoration());
+                let mut ops = mr::Operands::from([mr::Operand::Decoration(val)]);
+                ops.extend(self.parse_decoration_arguments(val)?);
                 ops
             }
             GOpKind::IdResultType => panic!(),  // not handled here
@@ -96,107 +103,113 @@ impl<'c, 'd> Parser<'c, 'd> {
         })
     }
 
-    fn parse_image_operands_arguments(&mut self, image_operands: spirv::ImageOperands) -> Result<Vec<mr::Operand>> {
-        let mut params = vec![];
+    fn parse_image_operands_arguments(&mut self, image_operands: spirv::ImageOperands) -> Result<mr::Operands> {
+        let mut params = mr::Operands::new();
         if image_operands.contains(spirv::ImageOperands::BIAS) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::LOD) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::GRAD) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id())), mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id())), mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::CONST_OFFSET) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::OFFSET) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::CONST_OFFSETS) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::SAMPLE) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::MIN_LOD) {
-            params.append(&mut vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::MAKE_TEXEL_AVAILABLE_KHR) {
-            params.append(&mut vec![mr::Operand::IdScope(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdScope(try_decode!(self, self.decoder.id()))]);
         }
         if image_operands.contains(spirv::ImageOperands::MAKE_TEXEL_VISIBLE_KHR) {
-            params.append(&mut vec![mr::Operand::IdScope(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdScope(try_decode!(self, self.decoder.id()))]);
         }
         Ok(params)
     }
 
-    fn parse_loop_control_arguments(&mut self, loop_control: spirv::LoopControl) -> Result<Vec<mr::Operand>> {
-        let mut params = vec![];
+    fn parse_loop_control_arguments(&mut self, loop_control: spirv::LoopControl) -> Result<mr::Operands> {
+        let mut params = mr::Operands::new();
         if loop_control.contains(spirv::LoopControl::DEPENDENCY_LENGTH) {
-            params.append(&mut vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))]);
+            params.extend([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]);
         }
         Ok(params)
     }
 
-    fn parse_memory_access_arguments(&mut self, memory_access: spirv::MemoryAccess) -> Result<Vec<mr::Operand>> {
-        let mut params = vec![];
+    fn parse_memory_access_arguments(&mut self, memory_access: spirv::MemoryAccess) -> Result<mr::Operands> {
+        let mut params = mr::Operands::new();
         if memory_access.contains(spirv::MemoryAccess::ALIGNED) {
-            params.append(&mut vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))]);
+            params.extend([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]);
         }
         if memory_access.contains(spirv::MemoryAccess::MAKE_POINTER_AVAILABLE_KHR) {
-            params.append(&mut vec![mr::Operand::IdScope(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdScope(try_decode!(self, self.decoder.id()))]);
         }
         if memory_access.contains(spirv::MemoryAccess::MAKE_POINTER_VISIBLE_KHR) {
-            params.append(&mut vec![mr::Operand::IdScope(try_decode!(self.decoder.id()))]);
+            params.extend([mr::Operand::IdScope(try_decode!(self, self.decoder.id()))]);
         }
         Ok(params)
     }
 
-    fn parse_execution_mode_arguments(&mut self, execution_mode: spirv::ExecutionMode) -> Result<Vec<mr::Operand>> {
+    fn parse_execution_mode_arguments(&mut self, execution_mode: spirv::ExecutionMode) -> Result<mr::Operands> {
         Ok(match execution_mode {
-            spirv::ExecutionMode::Invocations => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::LocalSize => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::LocalSizeHint => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::OutputVertices => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::VecTypeHint => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::SubgroupSize => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::SubgroupsPerWorkgroup => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::ExecutionMode::SubgroupsPerWorkgroupId => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            spirv::ExecutionMode::LocalSizeId => vec![mr::Operand::IdRef(try_decode!(self.decoder.id())), mr::Operand::IdRef(try_decode!(self.decoder.id())), mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            spirv::ExecutionMode::LocalSizeHintId => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            _ => vec![]
+            spirv::ExecutionMode::Invocations => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::LocalSize => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::LocalSizeHint => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32())), mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::OutputVertices => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::VecTypeHint => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::SubgroupSize => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::SubgroupsPerWorkgroup => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::SubgroupsPerWorkgroupId => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::ExecutionMode::LocalSizeId => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id())), mr::Operand::IdRef(try_decode!(self, self.decoder.id())), mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::ExecutionMode::LocalSizeHintId => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::ExecutionMode::DenormPreserve => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::DenormFlushToZero => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::SignedZeroInfNanPreserve => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::RoundingModeRTE => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::RoundingModeRTZ => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::ExecutionMode::OutputPrimitivesEXT => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            _ => mr::Operands::new()
         })
     }
 
-    fn parse_decoration_arguments(&mut self, decoration: spirv::Decoration) -> Result<Vec<mr::Operand>> {
+    fn parse_decoration_arguments(&mut self, decoration: spirv::Decoration) -> Result<mr::Operands> {
         Ok(match decoration {
-            spirv::Decoration::SpecId => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::ArrayStride => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::MatrixStride => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::BuiltIn => vec![mr::Operand::BuiltIn(try_decode!(self.decoder.built_in()))],
-            spirv::Decoration::Stream => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Location => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Component => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Index => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Binding => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::DescriptorSet => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Offset => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::XfbBuffer => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::XfbStride => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::FuncParamAttr => vec![mr::Operand::FunctionParameterAttribute(try_decode!(self.decoder.function_parameter_attribute()))],
-            spirv::Decoration::FPRoundingMode => vec![mr::Operand::FPRoundingMode(try_decode!(self.decoder.fprounding_mode()))],
-            spirv::Decoration::FPFastMathMode => vec![mr::Operand::FPFastMathMode(try_decode!(self.decoder.fpfast_math_mode()))],
-            spirv::Decoration::LinkageAttributes => vec![mr::Operand::LiteralString(try_decode!(self.decoder.string())), mr::Operand::LinkageType(try_decode!(self.decoder.linkage_type()))],
-            spirv::Decoration::InputAttachmentIndex => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::Alignment => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::MaxByteOffset => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::AlignmentId => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            spirv::Decoration::MaxByteOffsetId => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            spirv::Decoration::SecondaryViewportRelativeNV => vec![mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))],
-            spirv::Decoration::HlslCounterBufferGOOGLE => vec![mr::Operand::IdRef(try_decode!(self.decoder.id()))],
-            spirv::Decoration::HlslSemanticGOOGLE => vec![mr::Operand::LiteralString(try_decode!(self.decoder.string()))],
-            _ => vec![]
+            spirv::Decoration::SpecId => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::ArrayStride => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::MatrixStride => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::BuiltIn => mr::Operands::from([mr::Operand::BuiltIn(try_decode!(self, self.decoder.built_in()))]),
+            spirv::Decoration::Stream => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Location => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Component => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Index => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Binding => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::DescriptorSet => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Offset => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::XfbBuffer => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::XfbStride => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::FuncParamAttr => mr::Operands::from([mr::Operand::FunctionParameterAttribute(try_decode!(self, self.decoder.function_parameter_attribute()))]),
+            spirv::Decoration::FPRoundingMode => mr::Operands::from([mr::Operand::FPRoundingMode(try_decode!(self, self.decoder.fprounding_mode()))]),
+            spirv::Decoration::FPFastMathMode => mr::Operands::from([mr::Operand::FPFastMathMode(try_decode!(self, self.decoder.fpfast_math_mode()))]),
+            spirv::Decoration::LinkageAttributes => mr::Operands::from([mr::Operand::LiteralString(try_decode!(self, self.decoder.symbol())), mr::Operand::LinkageType(try_decode!(self, self.decoder.linkage_type()))]),
+            spirv::Decoration::InputAttachmentIndex => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::Alignment => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::MaxByteOffset => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::AlignmentId => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::Decoration::MaxByteOffsetId => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::Decoration::SecondaryViewportRelativeNV => mr::Operands::from([mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))]),
+            spirv::Decoration::HlslCounterBufferGOOGLE => mr::Operands::from([mr::Operand::IdRef(try_decode!(self, self.decoder.id()))]),
+            spirv::Decoration::HlslSemanticGOOGLE => mr::Operands::from([mr::Operand::LiteralString(try_decode!(self, self.decoder.symbol()))]),
+            _ => mr::Operands::new()
         })
     }
 }
\ No newline at end of file
@@ -17,17 +17,21 @@ use grammar;
 use spirv;
 
 use std::{error, fmt, result, slice};
+#[cfg(feature = "std")]
+use std::io;
 use super::decoder;
 use super::error::Error as DecodeError;
-use super::tracker::{Type, TypeTracker};
+use super::tracker::{ExtInstSetTracker, Type, TypeTracker};
 
 use grammar::CoreInstructionTable as GInstTable;
 use grammar::OperandKind as GOpKind;
 use grammar::OperandQuantifier as GOpCount;
 
-use utils::version;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 type GInstRef = &'static grammar::Instruction<'static>;
+type GExtInstRef = &'static grammar::ExtendedInstruction<'static>;
 
 const WORD_NUM_BYTES: usize = 4;
 
@@ -57,12 +61,45 @@ pub enum State {
     OperandExpected(usize, usize),
     /// found redundant operands (byte offset, inst number)
     OperandExceeded(usize, usize),
-    /// Errored out when decoding operand with the given error
-    OperandError(DecodeError),
+    /// Errored out when decoding operand at (byte offset, inst number) with
+    /// the given error
+    OperandError(usize, usize, DecodeError),
     /// Unsupported type (byte offset, inst number)
     TypeUnsupported(usize, usize),
     /// Incorrect SpecConstantOp Integer (byte offset, inst number)
     SpecConstantOpIntegerIncorrect(usize, usize),
+    /// Errored out when reading the binary with the given error
+    #[cfg(feature = "std")]
+    ReadError(io::Error),
+}
+
+impl State {
+    /// Returns the error returned by the consumer if this is a
+    /// `State::ConsumerError` with an error of type `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rspirv;
+    ///
+    /// use rspirv::binary::{ParseAction, ParseState};
+    /// use rspirv::mr::Error;
+    ///
+    /// fn main() {
+    ///     let state = match ParseAction::error(Error::DetachedInstruction) {
+    ///         ParseAction::Error(err) => ParseState::ConsumerError(err),
+    ///         _ => unreachable!(),
+    ///     };
+    ///     assert!(state.consumer_error::<Error>().is_some());
+    ///     assert!(state.consumer_error::<std::fmt::Error>().is_none());
+    /// }
+    /// ```
+    pub fn consumer_error<E: error::Error + 'static>(&self) -> Option<&E> {
+        match *self {
+            State::ConsumerError(ref err) => err.downcast_ref::<E>(),
+            _ => None,
+        }
+    }
 }
 
 impl error::Error for State {
@@ -78,9 +115,21 @@ impl error::Error for State {
             State::OpcodeUnknown(..) => "unknown opcode",
             State::OperandExpected(..) => "expected more operands",
             State::OperandExceeded(..) => "found extra operands",
-            State::OperandError(_) => "operand decoding error",
+            State::OperandError(..) => "operand decoding error",
             State::TypeUnsupported(..) => "unsupported type",
             State::SpecConstantOpIntegerIncorrect(..) => "incorrect SpecConstantOp Integer",
+            #[cfg(feature = "std")]
+            State::ReadError(_) => "read error",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            State::ConsumerError(ref err) => Some(&**err),
+            State::HeaderIncomplete(ref err) | State::OperandError(_, _, ref err) => Some(err),
+            #[cfg(feature = "std")]
+            State::ReadError(ref err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -120,7 +169,13 @@ impl fmt::Display for State {
                        index,
                        offset)
             }
-            State::OperandError(ref err) => write!(f, "operand decoding error: {}", err),
+            State::OperandError(offset, index, ref err) => {
+                write!(f,
+                       "operand decoding error for instruction #{} at offset {}: {}",
+                       index,
+                       offset,
+                       err)
+            }
             State::TypeUnsupported(offset, index) => {
                 write!(f,
                        "unsupported type for instruction #{} at offset {}",
@@ -133,14 +188,60 @@ impl fmt::Display for State {
                        index,
                        offset)
             }
+            #[cfg(feature = "std")]
+            State::ReadError(ref err) => write!(f, "read error: {}", err),
         }
     }
 }
 
 pub type Result<T> = result::Result<T, State>;
 
+/// A problem found and skipped over when parsing in lenient mode.
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// The byte offset of the problematic instruction, or the end of the
+    /// binary if the problem is not about a specific instruction.
+    pub offset: usize,
+    /// The number (starting from 1) of the problematic instruction, if any.
+    pub index: Option<usize>,
+    /// The opcode of the problematic instruction, if any.
+    pub opcode: Option<u16>,
+    /// What went wrong.
+    pub reason: State,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.index, self.opcode) {
+            (Some(index), Some(opcode)) => {
+                write!(f,
+                       "opcode {} of instruction #{} at offset {}: {}",
+                       opcode,
+                       index,
+                       self.offset,
+                       self.reason)
+            }
+            (None, Some(opcode)) => {
+                write!(f, "opcode {} at offset {}: {}", opcode, self.offset, self.reason)
+            }
+            (_, None) => write!(f, "at offset {}: {}", self.offset, self.reason),
+        }
+    }
+}
+
 const HEADER_NUM_WORDS: usize = 5;
 
+/// Returns true if `err` is about an enumerant unknown to this library, as
+/// opposed to a malformed binary.
+fn is_unknown_enumerant(err: &DecodeError) -> bool {
+    !matches!(
+        *err,
+        DecodeError::StreamExpected(_) |
+            DecodeError::LimitReached(_) |
+            DecodeError::DecodeStringFailed(..)
+    )
+}
+
 /// Orders consumer sent to the parser after each consuming call.
 #[derive(Debug)]
 pub enum Action {
@@ -152,6 +253,18 @@ pub enum Action {
     Error(Box<error::Error>),
 }
 
+impl Action {
+    /// Creates an action erroring out with the given consumer-defined error.
+    ///
+    /// The error is surfaced as
+    /// [`State::ConsumerError`](enum.ParseState.html) from the parser and
+    /// can be recovered with
+    /// [`State::consumer_error`](enum.ParseState.html#method.consumer_error).
+    pub fn error<E: error::Error + 'static>(err: E) -> Action {
+        Action::Error(Box::new(err))
+    }
+}
+
 /// The binary consumer trait.
 ///
 /// The parser will call `initialize` before parsing the SPIR-V binary and
@@ -173,6 +286,18 @@ pub trait Consumer {
     fn consume_header(&mut self, module: mr::ModuleHeader) -> Action;
     /// Consume the given instruction.
     fn consume_instruction(&mut self, inst: mr::Instruction) -> Action;
+
+    /// Consume the given instruction found at the given location in the
+    /// binary.
+    ///
+    /// The default implementation ignores the location and calls
+    /// [`consume_instruction`](#tymethod.consume_instruction).
+    fn consume_located_instruction(&mut self,
+                                   inst: mr::Instruction,
+                                   _location: mr::InstructionLocation)
+                                   -> Action {
+        self.consume_instruction(inst)
+    }
 }
 
 /// Parses the given `binary` and consumes the module using the given
@@ -240,17 +365,22 @@ pub struct Parser<'c, 'd> {
     decoder: decoder::Decoder<'d>,
     consumer: &'c mut Consumer,
     type_tracker: TypeTracker,
+    ext_inst_set_tracker: ExtInstSetTracker,
+    grammar: Option<&'d grammar::DynamicGrammar>,
     /// The index of the current instructions
     ///
     /// Starting from 1, 0 means invalid
     inst_index: usize,
 }
 
-/// Tries to decode `$e` and returns the error if errored out.
+/// Tries to decode `$e` and returns the error together with the current
+/// location of `$parser` if errored out.
 macro_rules! try_decode {
-    ($e: expr) => (match $e {
+    ($parser: ident, $e: expr) => (match $e {
         Ok(val) => val,
-        Err(err) => return Err(State::OperandError(err))
+        Err(err) => {
+            return Err(State::OperandError($parser.decoder.offset(), $parser.inst_index, err))
+        }
     });
 }
 
@@ -262,12 +392,44 @@ impl<'c, 'd> Parser<'c, 'd> {
             decoder: decoder::Decoder::new(binary),
             consumer: consumer,
             type_tracker: TypeTracker::new(),
+            ext_inst_set_tracker: ExtInstSetTracker::new(),
+            grammar: None,
             inst_index: 0,
         }
     }
 
+    /// Makes the parser decode instructions with opcodes unknown to the
+    /// compiled-in tables according to the given `grammar`, instead of
+    /// keeping them as raw words.
+    ///
+    /// Instructions not matching their entries in `grammar` are still kept
+    /// as raw words.
+    pub fn with_grammar(mut self, grammar: &'d grammar::DynamicGrammar) -> Parser<'c, 'd> {
+        self.grammar = Some(grammar);
+        self
+    }
+
     /// Does the parsing.
-    pub fn parse(mut self) -> Result<()> {
+    pub fn parse(self) -> Result<()> {
+        self.parse_impl(None)
+    }
+
+    /// Does the parsing in lenient mode.
+    ///
+    /// Different from [`parse`](#method.parse), malformed instructions do
+    /// not abort the parsing. Instead, they are recorded as diagnostics and
+    /// skipped using their word count. Errors returned by the consumer for
+    /// instructions or in finalization are recorded as diagnostics too.
+    ///
+    /// Errors are still returned for problems in the module header, or if
+    /// the consumer requests to stop or fails to initialize.
+    pub fn parse_lenient(self) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = vec![];
+        self.parse_impl(Some(&mut diagnostics))?;
+        Ok(diagnostics)
+    }
+
+    fn parse_impl(mut self, mut diagnostics: Option<&mut Vec<Diagnostic>>) -> Result<()> {
         match self.consumer.initialize() {
             Action::Continue => (),
             Action::Stop => return Err(State::ConsumerStopRequested),
@@ -281,28 +443,77 @@ impl<'c, 'd> Parser<'c, 'd> {
         }
 
         loop {
+            let start = self.decoder.offset();
             let result = self.parse_inst();
             match result {
                 Ok(inst) => {
                     self.type_tracker.track(&inst);
-                    match self.consumer.consume_instruction(inst) {
+                    self.ext_inst_set_tracker.track(&inst);
+                    let opcode = inst.class.opcode as u16;
+                    let location = mr::InstructionLocation {
+                        offset: start / WORD_NUM_BYTES,
+                        index: self.inst_index,
+                        result_id: inst.result_id,
+                    };
+                    match self.consumer.consume_located_instruction(inst, location) {
                         Action::Continue => (),
                         Action::Stop => return Err(State::ConsumerStopRequested),
-                        Action::Error(err) => return Err(State::ConsumerError(err)),
+                        Action::Error(err) => match diagnostics {
+                            Some(ref mut diagnostics) => diagnostics.push(Diagnostic {
+                                offset: start,
+                                index: Some(self.inst_index),
+                                opcode: Some(opcode),
+                                reason: State::ConsumerError(err),
+                            }),
+                            None => return Err(State::ConsumerError(err)),
+                        },
                     }
                 }
                 Err(State::Complete) => break,
-                Err(error) => return Err(error),
+                Err(error) => match diagnostics {
+                    Some(ref mut diagnostics) => {
+                        let opcode = self.skip_inst(start);
+                        diagnostics.push(Diagnostic {
+                            offset: start,
+                            index: Some(self.inst_index),
+                            opcode: Some(opcode),
+                            reason: error,
+                        });
+                    }
+                    None => return Err(error),
+                },
             };
         }
         match self.consumer.finalize() {
             Action::Continue => (),
             Action::Stop => return Err(State::ConsumerStopRequested),
-            Action::Error(err) => return Err(State::ConsumerError(err)),
+            Action::Error(err) => match diagnostics {
+                Some(diagnostics) => diagnostics.push(Diagnostic {
+                    offset: self.decoder.offset(),
+                    index: None,
+                    opcode: None,
+                    reason: State::ConsumerError(err),
+                }),
+                None => return Err(State::ConsumerError(err)),
+            },
         }
         Ok(())
     }
 
+    /// Moves the decoder past the instruction starting at the given byte
+    /// offset according to its word count, and returns its opcode.
+    ///
+    /// Zero word counts are treated as one.
+    fn skip_inst(&mut self, start: usize) -> u16 {
+        self.decoder.clear_limit();
+        self.decoder.set_offset(start);
+        // The first word has been successfully decoded before.
+        let word = self.decoder.word().unwrap_or(0);
+        let (wc, opcode) = Parser::split_into_word_count_and_opcode(word);
+        self.decoder.set_offset(start + (wc.max(1) as usize) * WORD_NUM_BYTES);
+        opcode
+    }
+
     fn split_into_word_count_and_opcode(word: spirv::Word) -> (u16, u16) {
         ((word >> 16) as u16, (word & 0xffff) as u16)
     }
@@ -318,11 +529,14 @@ impl<'c, 'd> Parser<'c, 'd> {
                     }
                 }
                 
-                let mut header = mr::ModuleHeader::new(words[3]);
-                let (major, minor) = version::create_version_from_word(words[1]);
-                header.set_version(major, minor);
-
-                Ok(header)
+                // Keep all header words as is, so the module round-trips.
+                Ok(mr::ModuleHeader {
+                    magic_number: words[0],
+                    version: words[1],
+                    generator: words[2],
+                    bound: words[3],
+                    reserved_word: words[4],
+                })
             }
             Err(err) => Err(State::HeaderIncomplete(err)),
         }
@@ -337,23 +551,97 @@ impl<'c, 'd> Parser<'c, 'd> {
                                                 self.inst_index));
             }
             if let Some(grammar) = GInstTable::lookup_opcode(opcode) {
+                let start = self.decoder.offset();
                 self.decoder.set_limit((wc - 1) as usize);
                 let result = self.parse_operands(grammar);
+                if let Err(State::OperandError(_, _, ref err)) = result {
+                    if is_unknown_enumerant(err) {
+                        self.decoder.set_offset(start);
+                        return self.parse_unknown_inst(wc, opcode);
+                    }
+                }
                 if !self.decoder.limit_reached() {
                     return Err(State::OperandExceeded(self.decoder.offset(), self.inst_index));
                 }
                 self.decoder.clear_limit();
                 result
+            } else if let Some(grammar) = self.grammar.and_then(|g| g.lookup_opcode(opcode)) {
+                self.parse_dynamic_inst(grammar, wc, opcode)
             } else {
-                Err(State::OpcodeUnknown(self.decoder.offset() - WORD_NUM_BYTES,
-                                         self.inst_index,
-                                         opcode))
+                self.parse_unknown_inst(wc, opcode)
             }
         } else {
             Err(State::Complete)
         }
     }
 
+    /// Parses the rest of an instruction not understood by this library
+    /// into raw words, so that it survives round trips.
+    fn parse_unknown_inst(&mut self, wc: u16, opcode: u16) -> Result<mr::Instruction> {
+        self.decoder.set_limit((wc - 1) as usize);
+        let words = try_decode!(self, self.decoder.words((wc - 1) as usize));
+        self.decoder.clear_limit();
+        Ok(mr::Instruction::new_unknown(opcode, words))
+    }
+
+    /// Parses the rest of an instruction with an opcode unknown to the
+    /// compiled-in tables according to its entry in the dynamic grammar,
+    /// falling back to raw words if the entry does not match.
+    fn parse_dynamic_inst(&mut self,
+                          grammar: GInstRef,
+                          wc: u16,
+                          opcode: u16)
+                          -> Result<mr::Instruction> {
+        let start = self.decoder.offset();
+        self.decoder.set_limit((wc - 1) as usize);
+        match self.parse_logical_operands(grammar) {
+            Ok((rtype, rid, operands)) if self.decoder.limit_reached() => {
+                self.decoder.clear_limit();
+                Ok(mr::Instruction::with_dynamic_grammar(grammar, opcode, rtype, rid, operands))
+            }
+            _ => {
+                self.decoder.set_offset(start);
+                self.parse_unknown_inst(wc, opcode)
+            }
+        }
+    }
+
+    /// Decodes the rest of an OpExtInst according to the grammar of its
+    /// extended instruction set.
+    ///
+    /// Returns `None` without consuming any words if they do not match.
+    fn parse_ext_inst_operands(&mut self, grammar: GExtInstRef) -> Option<mr::Operands> {
+        let (start, limit) = (self.decoder.offset(), self.decoder.limit());
+        let mut operands = mr::Operands::new();
+        let mut matched = true;
+        let mut index = 0;
+        while index < grammar.operands.len() {
+            let loperand = &grammar.operands[index];
+            if self.decoder.limit_reached() {
+                matched = loperand.quantifier != GOpCount::One;
+                break;
+            }
+            match self.parse_operand(loperand.kind) {
+                Ok(decoded) => operands.extend(decoded),
+                Err(_) => {
+                    matched = false;
+                    break;
+                }
+            }
+            if loperand.quantifier != GOpCount::ZeroOrMore {
+                index += 1;
+            }
+        }
+        if matched && self.decoder.limit_reached() {
+            return Some(operands);
+        }
+        self.decoder.set_offset(start);
+        if let Some(limit) = limit {
+            self.decoder.set_limit(limit);
+        }
+        None
+    }
+
     fn parse_literal(&mut self, type_id: spirv::Word) -> Result<mr::Operand> {
         let tracked_type = self.type_tracker.resolve(type_id);
         match tracked_type {
@@ -361,8 +649,15 @@ impl<'c, 'd> Parser<'c, 'd> {
                 match t {
                     Type::Integer(size, _) => {
                         match size {
-                            32 => Ok(mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))),
-                            64 => Ok(mr::Operand::LiteralInt64(try_decode!(self.decoder.int64()))),
+                            // Narrower literals still occupy a whole word.
+                            8 | 16 | 32 => {
+                                let value = try_decode!(self, self.decoder.int32());
+                                Ok(mr::Operand::LiteralInt32(value))
+                            }
+                            64 => {
+                                let value = try_decode!(self, self.decoder.int64());
+                                Ok(mr::Operand::LiteralInt64(value))
+                            }
                             _ => {
                                 Err(State::TypeUnsupported(self.decoder.offset(), self.inst_index))
                             }
@@ -370,11 +665,18 @@ impl<'c, 'd> Parser<'c, 'd> {
                     }
                     Type::Float(size) => {
                         match size {
+                            // Keep the raw bits for half precision floats.
+                            16 => {
+                                let value = try_decode!(self, self.decoder.int32());
+                                Ok(mr::Operand::LiteralInt32(value))
+                            }
                             32 => {
-                                Ok(mr::Operand::LiteralFloat32(try_decode!(self.decoder.float32())))
+                                let value = try_decode!(self, self.decoder.float32());
+                                Ok(mr::Operand::LiteralFloat32(value))
                             }
                             64 => {
-                                Ok(mr::Operand::LiteralFloat64(try_decode!(self.decoder.float64())))
+                                let value = try_decode!(self, self.decoder.float64());
+                                Ok(mr::Operand::LiteralFloat64(value))
                             }
                             _ => {
                                 Err(State::TypeUnsupported(self.decoder.offset(), self.inst_index))
@@ -385,21 +687,49 @@ impl<'c, 'd> Parser<'c, 'd> {
             }
             // Treat as a normal SPIR-V word if we don't know the type.
             // TODO: find a better way to handle this.
-            None => Ok(mr::Operand::LiteralInt32(try_decode!(self.decoder.int32()))),
+            None => Ok(mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))),
         }
     }
 
-    fn parse_spec_constant_op(&mut self) -> Result<Vec<mr::Operand>> {
-        let mut operands = vec![];
+    /// Decodes an OpSwitch case literal, whose width is given by the type
+    /// of the selector.
+    fn parse_switch_literal(&mut self, selector: Option<spirv::Word>) -> Result<mr::Operand> {
+        match selector.and_then(|id| self.type_tracker.resolve(id)) {
+            Some(Type::Integer(64, _)) => {
+                Ok(mr::Operand::LiteralInt64(try_decode!(self, self.decoder.int64())))
+            }
+            // Treat as a normal SPIR-V word if we don't know the type.
+            _ => Ok(mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))),
+        }
+    }
+
+    fn parse_spec_constant_op(&mut self) -> Result<mr::Operands> {
+        let mut operands = mr::Operands::new();
 
-        let number = try_decode!(self.decoder.int32());
-        if let Some(g) = GInstTable::lookup_opcode(number as u16) {
-            // TODO: check whether this opcode is allowed here.
+        let number = try_decode!(self, self.decoder.int32());
+        let allowed = GInstTable::lookup_opcode(number as u16)
+            .filter(|g| grammar::reflect::is_spec_constant_op_allowed(g.opcode));
+        if let Some(g) = allowed {
             operands.push(mr::Operand::LiteralSpecConstantOpInteger(g.opcode));
-            // We need id parameters to this SpecConstantOp.
+            // We need id and literal index parameters to this SpecConstantOp.
             for operand in g.operands {
-                if operand.kind == GOpKind::IdRef {
-                    operands.push(mr::Operand::IdRef(try_decode!(self.decoder.id())))
+                let literal = match operand.kind {
+                    GOpKind::IdRef => false,
+                    GOpKind::LiteralInteger => true,
+                    _ => continue,
+                };
+                loop {
+                    if operand.quantifier != GOpCount::One && self.decoder.limit_reached() {
+                        break;
+                    }
+                    operands.push(if literal {
+                        mr::Operand::LiteralInt32(try_decode!(self, self.decoder.int32()))
+                    } else {
+                        mr::Operand::IdRef(try_decode!(self, self.decoder.id()))
+                    });
+                    if operand.quantifier != GOpCount::ZeroOrMore {
+                        break;
+                    }
                 }
             }
             Ok(operands)
@@ -409,9 +739,19 @@ impl<'c, 'd> Parser<'c, 'd> {
     }
 
     fn parse_operands(&mut self, grammar: GInstRef) -> Result<mr::Instruction> {
+        let (rtype, rid, coperands) = self.parse_logical_operands(grammar)?;
+        Ok(mr::Instruction::with_operands(grammar.opcode, rtype, rid, coperands))
+    }
+
+    /// Decodes the result type id, the result id, and the concrete operands
+    /// for the logical operands in `grammar`.
+    fn parse_logical_operands(
+        &mut self,
+        grammar: GInstRef,
+    ) -> Result<(Option<spirv::Word>, Option<spirv::Word>, mr::Operands)> {
         let mut rtype = None;
         let mut rid = None;
-        let mut coperands = vec![]; // concrete operands
+        let mut coperands = mr::Operands::new(); // concrete operands
 
         let mut loperand_index: usize = 0; // logical operand index
         while loperand_index < grammar.operands.len() {
@@ -419,8 +759,8 @@ impl<'c, 'd> Parser<'c, 'd> {
             let has_more_coperands = !self.decoder.limit_reached();
             if has_more_coperands {
                 match loperand.kind {
-                    GOpKind::IdResultType => rtype = Some(try_decode!(self.decoder.id())),
-                    GOpKind::IdResult => rid = Some(try_decode!(self.decoder.id())),
+                    GOpKind::IdResultType => rtype = Some(try_decode!(self, self.decoder.id())),
+                    GOpKind::IdResult => rid = Some(try_decode!(self, self.decoder.id())),
                     GOpKind::LiteralContextDependentNumber => {
                         // Only constant defining instructions use this kind.
                         // If it is not true, that means the grammar is wrong
@@ -432,9 +772,38 @@ impl<'c, 'd> Parser<'c, 'd> {
                         coperands.push(self.parse_literal(id)?)
                     }
                     GOpKind::LiteralSpecConstantOpInteger => {
-                        coperands.append(&mut self.parse_spec_constant_op()?)
+                        coperands.extend(self.parse_spec_constant_op()?)
+                    }
+                    GOpKind::PairLiteralIntegerIdRef => {
+                        // Only OpSwitch uses this kind, with the selector as
+                        // the first operand.
+                        let selector = match coperands.first() {
+                            Some(&mr::Operand::IdRef(id)) => Some(id),
+                            _ => None,
+                        };
+                        coperands.push(self.parse_switch_literal(selector)?);
+                        coperands.push(mr::Operand::IdRef(try_decode!(self, self.decoder.id())));
                     }
-                    _ => coperands.append(&mut self.parse_operand(loperand.kind)?),
+                    GOpKind::LiteralExtInstInteger => {
+                        // Only OpExtInst uses this kind, with the extended
+                        // instruction set as the first operand.
+                        let opcode = try_decode!(self, self.decoder.ext_inst_integer());
+                        let ext = match coperands.first() {
+                            Some(&mr::Operand::IdRef(set)) => {
+                                self.ext_inst_set_tracker.resolve(set, opcode)
+                            }
+                            _ => None,
+                        };
+                        if let Some(ext) = ext {
+                            if let Some(operands) = self.parse_ext_inst_operands(ext) {
+                                coperands.push(mr::Operand::ExtInstOpcode(ext));
+                                coperands.extend(operands);
+                                break;
+                            }
+                        }
+                        coperands.push(mr::Operand::LiteralExtInstInteger(opcode));
+                    }
+                    _ => coperands.extend(self.parse_operand(loperand.kind)?),
                 }
                 match loperand.quantifier {
                     GOpCount::One | GOpCount::ZeroOrOne => loperand_index += 1,
@@ -450,7 +819,7 @@ impl<'c, 'd> Parser<'c, 'd> {
                 }
             }
         }
-        Ok(mr::Instruction::new(grammar.opcode, rtype, rid, coperands))
+        Ok((rtype, rid, coperands))
     }
 }
 
@@ -467,6 +836,8 @@ mod tests {
 
     use utils::num::f32_to_bytes;
     use utils::num::f64_to_bytes;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
 
     // TODO: It's unfortunate that we have these numbers directly coded here
     // and repeat them in the following tests. Should have a better way.
@@ -607,6 +978,7 @@ mod tests {
         }
         let mut header = mr::ModuleHeader::new(0);
         header.set_version(1, 0);
+        header.generator = 0;
         assert_eq!(Some(header), c.header);
     }
 
@@ -664,7 +1036,7 @@ mod tests {
         // The missing operand to the OpMemoryModel instruction starts at
         // byte offset (20 + 4 + 4 + 4).
         assert_matches!(p.parse(),
-                        Err(State::OperandError(Error::StreamExpected(32))));
+                        Err(State::OperandError(32, 2, Error::StreamExpected(32))));
     }
 
     #[test]
@@ -699,7 +1071,7 @@ mod tests {
         let mut c = RetainingConsumer::new();
         let p = Parser::new(&v, &mut c);
         assert_matches!(p.parse(),
-                        Err(State::OperandError(Error::StreamExpected(32))));
+                        Err(State::OperandError(32, 1, Error::StreamExpected(32))));
     }
 
     #[test]
@@ -817,6 +1189,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_source() {
+        let v = vec![];
+        let mut c = InitializeErrorConsumer {};
+        let state = Parser::new(&v, &mut c).parse().unwrap_err();
+        let source = error::Error::source(&state).unwrap();
+        assert_eq!("init error", format!("{}", source));
+
+        let state = State::OperandError(4, 1, Error::StreamExpected(4));
+        let source = error::Error::source(&state).unwrap();
+        assert_eq!(Some(&Error::StreamExpected(4)), source.downcast_ref::<Error>());
+        assert!(error::Error::source(&State::HeaderIncorrect).is_none());
+    }
+
     struct FinalizeErrorConsumer;
     impl Consumer for FinalizeErrorConsumer {
         fn initialize(&mut self) -> Action {
@@ -909,6 +1295,58 @@ mod tests {
         }
     }
 
+    /// A consumer-defined error carrying data.
+    #[derive(Debug, PartialEq)]
+    enum CountError {
+        TooMany(usize),
+    }
+    impl error::Error for CountError {}
+    impl fmt::Display for CountError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                CountError::TooMany(n) => write!(f, "too many instructions: {}", n),
+            }
+        }
+    }
+
+    struct CountingConsumer {
+        count: usize,
+    }
+    impl Consumer for CountingConsumer {
+        fn initialize(&mut self) -> Action {
+            Action::Continue
+        }
+        fn finalize(&mut self) -> Action {
+            Action::Continue
+        }
+        fn consume_header(&mut self, _: mr::ModuleHeader) -> Action {
+            Action::Continue
+        }
+        fn consume_instruction(&mut self, _: mr::Instruction) -> Action {
+            self.count += 1;
+            if self.count > 1 {
+                return Action::error(CountError::TooMany(self.count));
+            }
+            Action::Continue
+        }
+    }
+
+    #[test]
+    fn test_consumer_custom_error() {
+        let mut b = ModuleBuilder::new();
+        b.inst(spirv::Op::Nop, vec![]);
+        b.inst(spirv::Op::Nop, vec![]);
+        b.inst(spirv::Op::Nop, vec![]);
+        let mut c = CountingConsumer { count: 0 };
+        let ret = Parser::new(b.get(), &mut c).parse();
+        // Parsing stops at the error.
+        assert_eq!(2, c.count);
+        let state = ret.unwrap_err();
+        assert_eq!(Some(&CountError::TooMany(2)), state.consumer_error::<CountError>());
+        assert_eq!(None, state.consumer_error::<ErrorString>().map(|e| e.0));
+        assert_eq!("consumer error: too many instructions: 2", format!("{}", state));
+    }
+
     #[test]
     fn test_parsing_int32() {
         let mut v = ZERO_BOUND_HEADER.to_vec();
@@ -961,6 +1399,60 @@ mod tests {
                    inst.operands);
     }
 
+    #[test]
+    fn test_parsing_int16() {
+        let mut v = ZERO_BOUND_HEADER.to_vec();
+        v.append(&mut vec![0x15, 0x00, 0x04, 0x00]); // OpTypeInt
+        v.append(&mut vec![0x01, 0x00, 0x00, 0x00]); // result id: 1
+        v.append(&mut vec![0x10, 0x00, 0x00, 0x00]); // 16
+        v.append(&mut vec![0x00, 0x00, 0x00, 0x00]); // 0 (unsigned)
+
+        v.append(&mut vec![0x2b, 0x00, 0x04, 0x00]); // OpConstant
+        v.append(&mut vec![0x01, 0x00, 0x00, 0x00]); // result type: 1
+        v.append(&mut vec![0x02, 0x00, 0x00, 0x00]); // result id: 2
+        v.append(&mut vec![0x34, 0x12, 0x00, 0x00]);
+        let mut c = RetainingConsumer::new();
+        {
+            let p = Parser::new(&v, &mut c);
+            assert_matches!(p.parse(), Ok(()));
+        }
+        assert_eq!(2, c.insts.len());
+        assert_eq!(vec![mr::Operand::LiteralInt32(0x1234)], c.insts[1].operands);
+    }
+
+    #[test]
+    fn test_parsing_switch_int64() {
+        let mut v = ZERO_BOUND_HEADER.to_vec();
+        v.append(&mut vec![0x15, 0x00, 0x04, 0x00]); // OpTypeInt
+        v.append(&mut vec![0x01, 0x00, 0x00, 0x00]); // result id: 1
+        v.append(&mut vec![0x40, 0x00, 0x00, 0x00]); // 64
+        v.append(&mut vec![0x00, 0x00, 0x00, 0x00]); // 0 (unsigned)
+
+        v.append(&mut vec![0x01, 0x00, 0x03, 0x00]); // OpUndef
+        v.append(&mut vec![0x01, 0x00, 0x00, 0x00]); // result type: 1
+        v.append(&mut vec![0x02, 0x00, 0x00, 0x00]); // result id: 2
+
+        v.append(&mut vec![0xfb, 0x00, 0x06, 0x00]); // OpSwitch
+        v.append(&mut vec![0x02, 0x00, 0x00, 0x00]); // selector: 2
+        v.append(&mut vec![0x03, 0x00, 0x00, 0x00]); // default: 3
+        v.append(&mut vec![0x12, 0x34, 0x56, 0x78]);
+        v.append(&mut vec![0x90, 0xab, 0xcd, 0xef]);
+        v.append(&mut vec![0x04, 0x00, 0x00, 0x00]); // target: 4
+        let mut c = RetainingConsumer::new();
+        {
+            let p = Parser::new(&v, &mut c);
+            assert_matches!(p.parse(), Ok(()));
+        }
+        assert_eq!(3, c.insts.len());
+        let inst = &c.insts[2];
+        assert_eq!("Switch", inst.class.opname);
+        assert_eq!(vec![mr::Operand::IdRef(2),
+                        mr::Operand::IdRef(3),
+                        mr::Operand::LiteralInt64(0xefcdab9078563412),
+                        mr::Operand::IdRef(4)],
+                   inst.operands);
+    }
+
     #[test]
     fn test_parsing_float32() {
         let mut v = ZERO_BOUND_HEADER.to_vec();
@@ -1045,7 +1537,7 @@ mod tests {
         assert_matches!(p.parse(),
                         // The header has 5 words, the above instruction has 5 words,
                         // so in total 40 bytes.
-                        Err(State::OperandError(Error::LimitReached(40))));
+                        Err(State::OperandError(40, 1, Error::LimitReached(40))));
     }
 
     #[test]
@@ -1125,7 +1617,7 @@ mod tests {
         assert_matches!(p.parse(),
                         // The header has 5 words, the above instruction has 4 words,
                         // so in total 36 bytes.
-                        Err(State::OperandError(Error::LimitReached(36))));
+                        Err(State::OperandError(36, 1, Error::LimitReached(36))));
     }
     #[test]
     fn test_parsing_bitmasks_requiring_params_img_operands_param_order() {
@@ -1171,4 +1663,85 @@ mod tests {
         assert_eq!(vec![mr::Operand::Capability(spirv::Capability::Int16)],
                    inst.operands);
     }
+
+    #[test]
+    fn test_parsing_with_dynamic_grammar() {
+        use binary::{Assemble, Disassemble};
+        use grammar::{DynamicGrammar, LogicalOperand, OperandKind, OperandQuantifier};
+
+        let mut grammar = DynamicGrammar::new();
+        let operand = |kind, quantifier| LogicalOperand { kind, quantifier };
+        grammar.add_instruction("VendorBlendINTERNAL", 0xfff0, vec![
+            operand(OperandKind::IdResultType, OperandQuantifier::One),
+            operand(OperandKind::IdResult, OperandQuantifier::One),
+            operand(OperandKind::StorageClass, OperandQuantifier::One),
+            operand(OperandKind::IdRef, OperandQuantifier::ZeroOrMore),
+        ]).unwrap();
+
+        let mut bin = ZERO_BOUND_HEADER.to_vec();
+        // OpVendorBlendINTERNAL %1 %2 Private %3 %4
+        for &word in &[0x0006_fff0, 1, 2, 6, 3, 4] {
+            bin.append(&mut w2b(word));
+        }
+        // Operands not matching the grammar.
+        for &word in &[0x0003_fff0, 1, 2] {
+            bin.append(&mut w2b(word));
+        }
+        // Not in the grammar.
+        for &word in &[0x0002_fff1, 1] {
+            bin.append(&mut w2b(word));
+        }
+
+        let mut c = RetainingConsumer::new();
+        {
+            let p = Parser::new(&bin, &mut c).with_grammar(&grammar);
+            assert_matches!(p.parse(), Ok(()));
+        }
+        assert_eq!(3, c.insts.len());
+        let inst = &c.insts[0];
+        assert_eq!(Some(0xfff0), inst.unknown_opcode());
+        assert_eq!("%2 = OpVendorBlendINTERNAL  %1  Private %3 %4", inst.disassemble());
+        assert_eq!(vec![0x0006_fff0, 1, 2, 6, 3, 4], inst.assemble());
+        assert_eq!("OpUnknown(65520) 1 2", c.insts[1].disassemble());
+        assert_eq!("OpUnknown(65521) 1", c.insts[2].disassemble());
+    }
+
+    #[test]
+    fn test_parsing_ext_inst_operands() {
+        use binary::{Assemble, Disassemble};
+        use grammar::GlslStd450InstructionTable;
+
+        let mut b = mr::Builder::new();
+        let glsl = b.ext_inst_import("GLSL.std.450");
+        let cl = b.ext_inst_import("OpenCL.std");
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let pointer = b.type_pointer(None, spirv::StorageClass::Function, float);
+        let voidfvoid = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(pointer, None, spirv::StorageClass::Function, None);
+        let x = b.load(float, None, var, None, vec![]).unwrap();
+        let sign = b.ext_inst(float, None, glsl, 6, [x]).unwrap(); // FSign
+        b.ext_inst(float, None, glsl, 6, [x, x]).unwrap(); // Extra operand
+        b.ext_inst(float, None, cl, 171, [x, var, 4]).unwrap(); // vloadn, with n = 4
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let words = b.module().assemble();
+
+        let module = mr::load_words(&words).unwrap();
+        let insts = &module.functions[0].basic_blocks[0].instructions;
+        assert_eq!(
+            mr::Operand::ExtInstOpcode(GlslStd450InstructionTable::get(spirv::GLOp::FSign)),
+            insts[2].operands[1]
+        );
+        assert_eq!(
+            format!("%{} = OpExtInst  %{}  %{} FSign %{}", sign, float, glsl, x),
+            insts[2].disassemble()
+        );
+        assert_eq!(mr::Operand::LiteralExtInstInteger(6), insts[3].operands[1]);
+        assert_eq!(Some((cl, 171)), insts[4].ext_inst());
+        assert_eq!(mr::Operand::LiteralInt32(4), insts[4].operands[4]);
+        assert_eq!(words, module.assemble());
+    }
 }
@@ -0,0 +1,169 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use std::{error, fmt};
+use super::assemble::Assemble;
+use super::parser::State;
+
+/// The ways a binary can fail to round-trip.
+#[derive(Debug)]
+pub enum RoundtripError {
+    /// The binary could not be parsed, e.g., because it is truncated or
+    /// malformed.
+    Parse(State),
+    /// Assembling the parsed module did not reproduce the binary.
+    Mismatch {
+        /// The offset, in words, of the first differing word.
+        offset: usize,
+        /// The offset, in words, of the input instruction containing the
+        /// differing word, or `None` if it is in the header.
+        instruction: Option<usize>,
+        /// The input word, or `None` if the output is longer.
+        expected: Option<spirv::Word>,
+        /// The output word, or `None` if the output is shorter.
+        actual: Option<spirv::Word>,
+    },
+}
+
+impl RoundtripError {
+    /// Returns a short description of the error.
+    pub fn describe(&self) -> &str {
+        match *self {
+            RoundtripError::Parse(_) => "failed to parse",
+            RoundtripError::Mismatch { .. } => "reassembled words differ",
+        }
+    }
+}
+
+impl error::Error for RoundtripError {
+    fn description(&self) -> &str {
+        self.describe()
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            RoundtripError::Parse(ref state) => Some(state),
+            RoundtripError::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RoundtripError::Parse(ref state) => write!(f, "failed to parse: {}", state),
+            RoundtripError::Mismatch { offset, expected, actual, .. } => {
+                let word = |w: Option<spirv::Word>| match w {
+                    Some(w) => format!("{:#010x}", w),
+                    None => "end of binary".into(),
+                };
+                write!(f,
+                       "reassembled words differ at word {}: expected {}, found {}",
+                       offset,
+                       word(expected),
+                       word(actual))
+            }
+        }
+    }
+}
+
+/// Checks that parsing `words` into a `Module` and assembling it again
+/// reproduces `words` bit for bit.
+///
+/// Modules are kept as is by the parser: the header, including the
+/// generator and the reserved word, ids, instruction order within each
+/// module section, and operands are all preserved. Binaries which do not
+/// round-trip are those which fail parsing, e.g., truncated binaries, or
+/// have instructions out of the order of the logical layout, which the
+/// loader moves into their sections and are reported as mismatches.
+/// Instructions with unknown opcodes or enumerants are kept as raw words
+/// and round-trip.
+pub fn verify_roundtrip(words: &[u32]) -> Result<(), RoundtripError> {
+    let module = mr::load_words(words).map_err(RoundtripError::Parse)?;
+    let output = module.assemble();
+    let offset = match words.iter().zip(&output).position(|(a, b)| a != b) {
+        Some(offset) => offset,
+        None if words.len() == output.len() => return Ok(()),
+        None => words.len().min(output.len()),
+    };
+    Err(RoundtripError::Mismatch {
+        offset,
+        instruction: instruction_at(words, offset),
+        expected: words.get(offset).cloned(),
+        actual: output.get(offset).cloned(),
+    })
+}
+
+/// Returns the offset of the instruction containing the word at `offset`.
+fn instruction_at(words: &[u32], offset: usize) -> Option<usize> {
+    const HEADER_NUM_WORDS: usize = 5;
+    let mut start = HEADER_NUM_WORDS;
+    if offset < start {
+        return None;
+    }
+    while let Some(&first) = words.get(start) {
+        let count = (first >> 16) as usize;
+        if count == 0 || offset < start + count {
+            break;
+        }
+        start += count;
+    }
+    Some(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::Assemble;
+    use mr;
+    use spirv;
+
+    use super::{verify_roundtrip, RoundtripError};
+
+    #[test]
+    fn test_verify_roundtrip() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let float = b.type_float(32);
+        b.name(float, "f32");
+        b.decorate(float, spirv::Decoration::RelaxedPrecision, vec![]);
+        let mut words = b.module().assemble();
+        words[2] = 0xdead_beef; // generator
+        words[4] = 7; // reserved word
+        verify_roundtrip(&words).unwrap();
+
+        // The loader moves OpName back in front of OpDecorate.
+        let (name, decorate) = (words.len() - 6, words.len() - 3);
+        let mut swapped = words[..name].to_vec();
+        swapped.extend_from_slice(&words[decorate..decorate + 3]);
+        swapped.extend_from_slice(&words[name..decorate]);
+        match verify_roundtrip(&swapped) {
+            Err(RoundtripError::Mismatch { offset, instruction, expected, actual }) => {
+                assert_eq!(name, offset);
+                assert_eq!(Some(name), instruction);
+                assert_eq!(Some(swapped[name]), expected);
+                assert_eq!(Some(words[name]), actual);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        words.extend_from_slice(&[0x0002_ffff, 42]); // unknown opcode
+        verify_roundtrip(&words).unwrap();
+
+        words.push(0x0003_0005); // truncated OpName
+        assert_matches!(verify_roundtrip(&words), Err(RoundtripError::Parse(_)));
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use grammar::CoreInstructionTable as GInstTable;
+use grammar::OperandKind as GOpKind;
+use grammar::OperandQuantifier as GOpCount;
+
+use std::slice;
+use super::decoder::Decoder;
+use super::error::Error as DecodeError;
+use super::parser::{Result, State};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+const HEADER_NUM_WORDS: usize = 5;
+const WORD_NUM_BYTES: usize = 4;
+
+fn operand_word_count(operand: &mr::Operand) -> usize {
+    match *operand {
+        mr::Operand::LiteralInt64(_) | mr::Operand::LiteralFloat64(_) => 2,
+        // Strings are nul-terminated and padded to word boundary.
+        mr::Operand::LiteralString(ref s) => s.len() / WORD_NUM_BYTES + 1,
+        _ => 1,
+    }
+}
+
+/// Returns the number of words the given instruction occupies once encoded,
+/// including the word containing the opcode and the word count.
+///
+/// This is equivalent to `inst.assemble().len()` without actually encoding
+/// the instruction.
+pub fn instruction_word_count(inst: &mr::Instruction) -> usize {
+    // The opcode operand of unknown instructions stands in for the first word.
+    let operands = if inst.unknown_opcode().is_some() {
+        &inst.operands[1..]
+    } else {
+        &inst.operands[..]
+    };
+    1 + inst.result_type.iter().count() + inst.result_id.iter().count() +
+        operands.iter().map(operand_word_count).sum::<usize>()
+}
+
+/// Returns the (minimal, maximal) number of words a logical operand of the
+/// given kind occupies. The maximum is `None` if it is not bounded.
+fn operand_kind_word_count(kind: GOpKind) -> (usize, Option<usize>) {
+    match kind {
+        GOpKind::PairLiteralIntegerIdRef |
+        GOpKind::PairIdRefLiteralInteger |
+        GOpKind::PairIdRefIdRef => (2, Some(2)),
+        // Operands with trailing parameters or of variable length.
+        GOpKind::ImageOperands |
+        GOpKind::LoopControl |
+        GOpKind::MemoryAccess |
+        GOpKind::ExecutionMode |
+        GOpKind::Decoration |
+        GOpKind::LiteralString |
+        GOpKind::LiteralContextDependentNumber |
+        GOpKind::LiteralSpecConstantOpInteger => (1, None),
+        _ => (1, Some(1)),
+    }
+}
+
+/// Returns true if the given encoded `word_count` is allowed by the grammar
+/// for instructions with the given `opcode`.
+///
+/// Returns false for unknown opcodes. The check only considers the number
+/// of operands the grammar allows, so a valid word count does not mean the
+/// operands themselves can be decoded.
+pub fn is_word_count_valid(opcode: u16, word_count: u16) -> bool {
+    let grammar = match GInstTable::lookup_opcode(opcode) {
+        Some(g) => g,
+        None => return false,
+    };
+    let (mut min, mut max) = (1, Some(1));
+    for operand in grammar.operands {
+        let (kind_min, kind_max) = operand_kind_word_count(operand.kind);
+        match operand.quantifier {
+            GOpCount::One => {
+                min += kind_min;
+                max = max.and_then(|m| kind_max.map(|k| m + k));
+            }
+            GOpCount::ZeroOrOne => max = max.and_then(|m| kind_max.map(|k| m + k)),
+            GOpCount::ZeroOrMore => max = None,
+        }
+    }
+    let word_count = word_count as usize;
+    word_count >= min && max.is_none_or(|m| word_count <= m)
+}
+
+/// Scans the given SPIR-V `binary` and returns an iterator over the
+/// instructions in it, without decoding any operands.
+///
+/// The module header is checked eagerly. Each item of the iterator is a
+/// tuple of (opcode, word count, word offset) for one instruction, where
+/// the word offset is from the beginning of `binary`. So the words of the
+/// instruction are `binary[offset..offset + word_count]`.
+///
+/// Opcodes are not looked up in the grammar, so instructions unknown to
+/// this library are returned as well. Use
+/// [`is_word_count_valid`](fn.is_word_count_valid.html) to check the word
+/// counts against the grammar if needed.
+///
+/// Errors use byte offsets, like the [`Parser`](struct.Parser.html). The
+/// iterator stops after returning the first error.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::binary::{scan_words, Assemble};
+/// use rspirv::mr::Builder;
+///
+/// fn main() {
+///     let mut b = Builder::new();
+///     b.capability(spirv::Capability::Shader);
+///     b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+///     let words = b.module().assemble();
+///
+///     let insts: Vec<_> = scan_words(&words).unwrap().map(|i| i.unwrap()).collect();
+///     assert_eq!(vec![(spirv::Op::Capability as u16, 2, 5),
+///                     (spirv::Op::MemoryModel as u16, 3, 7)],
+///                insts);
+/// }
+/// ```
+pub fn scan_words(binary: &[u32]) -> Result<Scanner<'_>> {
+    check_header(binary)?;
+    Ok(Scanner {
+        binary,
+        offset: HEADER_NUM_WORDS,
+        inst_index: 0,
+        errored: false,
+    })
+}
+
+fn check_header(binary: &[u32]) -> Result<()> {
+    if binary.len() < HEADER_NUM_WORDS {
+        return Err(State::HeaderIncomplete(
+            DecodeError::StreamExpected(binary.len() * WORD_NUM_BYTES),
+        ));
+    }
+    if binary[0] != spirv::MAGIC_NUMBER {
+        if binary[0] == spirv::MAGIC_NUMBER.swap_bytes() {
+            return Err(State::EndiannessUnsupported);
+        }
+        return Err(State::HeaderIncorrect);
+    }
+    Ok(())
+}
+
+/// Parses only the module header of the given SPIR-V `binary`.
+///
+/// Different from the [`Parser`](struct.Parser.html), all the words of the
+/// header are kept as is, including the generator magic number.
+pub fn parse_header(binary: &[u32]) -> Result<mr::ModuleHeader> {
+    check_header(binary)?;
+    Ok(mr::ModuleHeader {
+        magic_number: binary[0],
+        version: binary[1],
+        generator: binary[2],
+        bound: binary[3],
+        reserved_word: binary[4],
+    })
+}
+
+/// Parses only the OpEntryPoint instructions of the given SPIR-V `binary`.
+///
+/// This is a fast path for finding out the execution models and names of
+/// the entry points in a module: only the first few sections of the module
+/// are scanned, and only the OpEntryPoint instructions are decoded.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::binary::{parse_entry_points_only, Assemble};
+/// use rspirv::mr::{Builder, Operand};
+///
+/// fn main() {
+///     let mut b = Builder::new();
+///     b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+///     b.entry_point(spirv::ExecutionModel::Fragment, 1, "main", vec![]);
+///     let words = b.module().assemble();
+///
+///     let entry_points = parse_entry_points_only(&words).unwrap();
+///     assert_eq!(1, entry_points.len());
+///     assert_eq!(Operand::ExecutionModel(spirv::ExecutionModel::Fragment),
+///                entry_points[0].operands[0]);
+///     assert_eq!(Operand::LiteralString("main".into()),
+///                entry_points[0].operands[2]);
+/// }
+/// ```
+pub fn parse_entry_points_only(binary: &[u32]) -> Result<Vec<mr::Instruction>> {
+    let len = binary.len() * WORD_NUM_BYTES;
+    let bytes = unsafe { slice::from_raw_parts(binary.as_ptr() as *const u8, len) };
+    let mut entry_points = vec![];
+    for (index, inst) in scan_words(binary)?.enumerate() {
+        let (opcode, word_count, offset) = inst?;
+        match opcode {
+            o if o == spirv::Op::EntryPoint as u16 => {}
+            // Entry points are only preceded by these in a valid module.
+            o if o == spirv::Op::Capability as u16 ||
+                o == spirv::Op::Extension as u16 ||
+                o == spirv::Op::ExtInstImport as u16 ||
+                o == spirv::Op::MemoryModel as u16 => continue,
+            _ => break,
+        }
+        let mut decoder = Decoder::with_offset(bytes, (offset + 1) * WORD_NUM_BYTES);
+        decoder.set_limit(word_count as usize - 1);
+        let decode = |decoder: &mut Decoder| -> ::std::result::Result<_, DecodeError> {
+            let mut operands = vec![
+                mr::Operand::ExecutionModel(decoder.execution_model()?),
+                mr::Operand::IdRef(decoder.id()?),
+                mr::Operand::LiteralString(decoder.symbol()?),
+            ];
+            while !decoder.limit_reached() {
+                operands.push(mr::Operand::IdRef(decoder.id()?));
+            }
+            Ok(operands)
+        };
+        let operands = decode(&mut decoder)
+            .map_err(|err| State::OperandError(decoder.offset(), index + 1, err))?;
+        entry_points.push(mr::Instruction::new(spirv::Op::EntryPoint, None, None, operands));
+    }
+    Ok(entry_points)
+}
+
+/// Iterator over the instructions in a SPIR-V binary.
+///
+/// See [`scan_words`](fn.scan_words.html) for details.
+pub struct Scanner<'a> {
+    binary: &'a [u32],
+    /// Word offset of the next instruction.
+    offset: usize,
+    /// The index of the last instruction, starting from 1.
+    inst_index: usize,
+    errored: bool,
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = Result<(u16, u16, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.errored || self.offset >= self.binary.len() {
+            return None;
+        }
+        self.inst_index += 1;
+        let word = self.binary[self.offset];
+        let (word_count, opcode) = ((word >> 16) as u16, (word & 0xffff) as u16);
+        let byte_offset = self.offset * WORD_NUM_BYTES;
+        if word_count == 0 {
+            self.errored = true;
+            return Some(Err(State::WordCountZero(byte_offset, self.inst_index)));
+        }
+        if self.offset + word_count as usize > self.binary.len() {
+            self.errored = true;
+            return Some(Err(State::OperandExpected(byte_offset, self.inst_index)));
+        }
+        let offset = self.offset;
+        self.offset += word_count as usize;
+        Some(Ok((opcode, word_count, offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::{Assemble, Disassemble, ParseState};
+    use super::{instruction_word_count, is_word_count_valid, parse_entry_points_only,
+                parse_header, scan_words};
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_instruction_word_count() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        b.extension("abc");
+        let int = b.type_int(32, 0);
+        b.constant_u32(int, 42);
+        let m = b.module();
+        for inst in m.global_inst_iter() {
+            assert_eq!(inst.assemble().len(), instruction_word_count(inst));
+        }
+        assert_eq!(2, instruction_word_count(&m.extensions[1]));
+
+        let inst = mr::Instruction::new(
+            spirv::Op::Constant,
+            Some(1),
+            Some(2),
+            vec![mr::Operand::LiteralInt64(42)],
+        );
+        assert_eq!(5, instruction_word_count(&inst));
+    }
+
+    #[test]
+    fn test_is_word_count_valid() {
+        assert!(is_word_count_valid(spirv::Op::Nop as u16, 1));
+        assert!(!is_word_count_valid(spirv::Op::Nop as u16, 2));
+        assert!(is_word_count_valid(spirv::Op::TypeInt as u16, 4));
+        assert!(!is_word_count_valid(spirv::Op::TypeInt as u16, 3));
+        assert!(!is_word_count_valid(spirv::Op::TypeInt as u16, 5));
+        // Optional access qualifier.
+        assert!(is_word_count_valid(spirv::Op::TypeImage as u16, 9));
+        assert!(is_word_count_valid(spirv::Op::TypeImage as u16, 10));
+        assert!(!is_word_count_valid(spirv::Op::TypeImage as u16, 11));
+        // Unbounded number of members.
+        assert!(is_word_count_valid(spirv::Op::TypeStruct as u16, 2));
+        assert!(is_word_count_valid(spirv::Op::TypeStruct as u16, 100));
+        // Switch targets come in pairs, but only the lower bound is checked.
+        assert!(!is_word_count_valid(spirv::Op::Switch as u16, 2));
+        assert!(is_word_count_valid(spirv::Op::Switch as u16, 5));
+        // Decorations may have parameters.
+        assert!(is_word_count_valid(spirv::Op::Decorate as u16, 4));
+        assert!(!is_word_count_valid(0xfff0, 1));
+    }
+
+    #[test]
+    fn test_scan_words() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let mut words = b.module().assemble();
+
+        let insts: Vec<_> = scan_words(&words).unwrap().map(|i| i.unwrap()).collect();
+        assert_eq!(
+            vec![
+                (spirv::Op::Capability as u16, 2, 5),
+                (spirv::Op::Extension as u16, 11, 7),
+                (spirv::Op::MemoryModel as u16, 3, 18),
+            ],
+            insts
+        );
+        assert_eq!(21, words.len());
+
+        // Truncated last instruction.
+        words.pop();
+        let mut scanner = scan_words(&words).unwrap();
+        assert!(scanner.next().unwrap().is_ok());
+        assert!(scanner.next().unwrap().is_ok());
+        match scanner.next() {
+            Some(Err(ParseState::OperandExpected(72, 3))) => (),
+            _ => panic!(),
+        }
+        assert!(scanner.next().is_none());
+
+        // Zero word count.
+        words[5] &= 0xffff;
+        match scan_words(&words).unwrap().next() {
+            Some(Err(ParseState::WordCountZero(20, 1))) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_scan_words_header() {
+        match scan_words(&[spirv::MAGIC_NUMBER, 0x00010000]) {
+            Err(ParseState::HeaderIncomplete(_)) => (),
+            _ => panic!(),
+        }
+        match scan_words(&[0, 0, 0, 0, 0]) {
+            Err(ParseState::HeaderIncorrect) => (),
+            _ => panic!(),
+        }
+        match scan_words(&[spirv::MAGIC_NUMBER.swap_bytes(), 0, 0, 0, 0]) {
+            Err(ParseState::EndiannessUnsupported) => (),
+            _ => panic!(),
+        }
+        assert_eq!(0, scan_words(&[spirv::MAGIC_NUMBER, 0, 0, 0, 0]).unwrap().count());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 1);
+        b.capability(spirv::Capability::Shader);
+        let mut words = b.module().assemble();
+        words[2] = 0x00080001;
+        let header = parse_header(&words).unwrap();
+        assert_eq!((1, 1), header.version());
+        assert_eq!((mr::Generator::Glslang, 1), header.generator());
+        assert_eq!(1, header.bound);
+        match parse_header(&words[..4]) {
+            Err(ParseState::HeaderIncomplete(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_entry_points_only() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        b.entry_point(spirv::ExecutionModel::Vertex, 1, "vs", vec![3, 4]);
+        b.entry_point(spirv::ExecutionModel::Fragment, 2, "main_fragment", vec![]);
+        b.execution_mode(2, spirv::ExecutionMode::OriginUpperLeft, vec![]);
+        // Not decoded, so no error is returned for this unknown instruction.
+        b.module_mut().types_global_values.push(mr::Instruction::new(
+            spirv::Op::Nop,
+            None,
+            None,
+            vec![],
+        ));
+        let mut words = b.module().assemble();
+        let nop = words.len() - 1;
+        words[nop] = 0x0001ffff;
+
+        let entry_points: Vec<String> = parse_entry_points_only(&words)
+            .unwrap()
+            .iter()
+            .map(|inst| inst.disassemble())
+            .collect();
+        assert_eq!(
+            vec![
+                "OpEntryPoint Vertex %1 \"vs\" %3 %4",
+                "OpEntryPoint Fragment %2 \"main_fragment\"",
+            ],
+            entry_points
+        );
+
+        // Malformed entry point.
+        words[11] = 42;
+        match parse_entry_points_only(&words) {
+            Err(ParseState::OperandError(..)) => (),
+            _ => panic!(),
+        }
+    }
+}
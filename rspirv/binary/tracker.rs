@@ -20,6 +20,7 @@ use std::collections;
 
 use grammar::GlslStd450InstructionTable as GGlInstTable;
 use grammar::OpenCLStd100InstructionTable as GClInstTable;
+use grammar::NonSemanticShaderDebugInfo100InstructionTable as GDebugInfoInstTable;
 
 type GExtInstRef = &'static grammar::ExtendedInstruction<'static>;
 
@@ -40,12 +41,12 @@ pub struct TypeTracker {
     /// Mapping from an id to its type.
     ///
     /// Ids for both defining and using types are all kept here.
-    types: collections::HashMap<spirv::Word, Type>,
+    types: collections::BTreeMap<spirv::Word, Type>,
 }
 
 impl TypeTracker {
     pub fn new() -> TypeTracker {
-        TypeTracker { types: collections::HashMap::new() }
+        TypeTracker { types: collections::BTreeMap::new() }
     }
 
     pub fn track(&mut self, inst: &mr::Instruction) {
@@ -84,6 +85,7 @@ impl TypeTracker {
 enum ExtInstSet {
     GlslStd450,
     OpenCLStd100,
+    NonSemanticShaderDebugInfo100,
 }
 
 /// Struct for tracking extended instruction sets.
@@ -91,12 +93,12 @@ enum ExtInstSet {
 /// If a given extended instruction set is not supported, it will just be
 /// silently ignored.
 pub struct ExtInstSetTracker {
-    sets: collections::HashMap<spirv::Word, ExtInstSet>,
+    sets: collections::BTreeMap<spirv::Word, ExtInstSet>,
 }
 
 impl ExtInstSetTracker {
     pub fn new() -> ExtInstSetTracker {
-        ExtInstSetTracker { sets: collections::HashMap::new() }
+        ExtInstSetTracker { sets: collections::BTreeMap::new() }
     }
 
     /// Tracks the extended instruction set declared by the given `inst`.
@@ -115,6 +117,9 @@ impl ExtInstSetTracker {
             } else if s == "OpenCL.std" {
                 self.sets
                     .insert(inst.result_id.unwrap(), ExtInstSet::OpenCLStd100);
+            } else if s == "NonSemantic.Shader.DebugInfo.100" {
+                self.sets
+                    .insert(inst.result_id.unwrap(), ExtInstSet::NonSemanticShaderDebugInfo100);
             }
         }
     }
@@ -134,6 +139,9 @@ impl ExtInstSetTracker {
             match *ext_inst_set {
                 ExtInstSet::GlslStd450 => GGlInstTable::lookup_opcode(opcode),
                 ExtInstSet::OpenCLStd100 => GClInstTable::lookup_opcode(opcode),
+                ExtInstSet::NonSemanticShaderDebugInfo100 => {
+                    GDebugInfoInstTable::lookup_opcode(opcode)
+                }
             }
         } else {
             None
@@ -0,0 +1,485 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structural diffing of SPIR-V modules in data representation.
+//!
+//! [`diff_modules`](fn.diff_modules.html) aligns the instructions of two
+//! modules section by section and reports the instructions added, removed,
+//! or changed. Ids are compared by what they denote instead of by their
+//! numbers, so renumbering ids alone does not produce differences.
+
+use binary::Disassemble;
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A section of a module, following the logical layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Section {
+    Capabilities,
+    Extensions,
+    ExtInstImports,
+    MemoryModel,
+    EntryPoints,
+    ExecutionModes,
+    Debugs,
+    Annotations,
+    TypesGlobalValues,
+    /// A function, identified by its debug name or entry point name if
+    /// any, or by its index among unnamed functions otherwise.
+    Function(String),
+}
+
+/// A difference between two modules.
+///
+/// Instructions are given as disassembled from their own module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffEvent {
+    /// The instruction only exists in the new module.
+    Added { section: Section, instruction: String },
+    /// The instruction only exists in the old module.
+    Removed { section: Section, instruction: String },
+    /// The instruction exists in both modules but with different operands.
+    Changed {
+        section: Section,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Section::Function(ref name) => write!(f, "function {}", name),
+            ref section => write!(f, "{:?}", section),
+        }
+    }
+}
+
+impl fmt::Display for DiffEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DiffEvent::Added {
+                ref section,
+                ref instruction,
+            } => write!(f, "[{}] + {}", section, instruction),
+            DiffEvent::Removed {
+                ref section,
+                ref instruction,
+            } => write!(f, "[{}] - {}", section, instruction),
+            DiffEvent::Changed {
+                ref section,
+                ref old,
+                ref new,
+            } => write!(f, "[{}] {} => {}", section, old, new),
+        }
+    }
+}
+
+/// Computes the differences between the `old` and the `new` module.
+///
+/// Each id is given a canonical name derived from what it denotes: its
+/// debug name, the structure of the type or constant it defines, or its
+/// position inside the function defining it. Instructions are then compared
+/// with ids replaced by canonical names, and aligned by longest common
+/// subsequence per section. Functions are matched by their names. The module
+/// headers are not compared.
+///
+/// The alignment takes time quadratic in the length of the differing parts
+/// of each section.
+pub fn diff_modules(old: &mr::Module, new: &mr::Module) -> Vec<DiffEvent> {
+    let old = CanonicalModule::new(old);
+    let new = CanonicalModule::new(new);
+
+    let mut events = vec![];
+    for (index, section) in GLOBAL_SECTIONS.iter().enumerate() {
+        diff_section(section, &old.globals[index], &new.globals[index], &mut events);
+    }
+    for (name, old_insts) in &old.functions {
+        let section = Section::Function(name.clone());
+        let new_insts = new.functions
+            .iter()
+            .find(|f| &f.0 == name)
+            .map_or(&[][..], |f| &f.1[..]);
+        diff_section(&section, old_insts, new_insts, &mut events);
+    }
+    for (name, new_insts) in &new.functions {
+        if !old.functions.iter().any(|f| &f.0 == name) {
+            diff_section(&Section::Function(name.clone()), &[], new_insts, &mut events);
+        }
+    }
+    events
+}
+
+const GLOBAL_SECTIONS: &[Section] = &[
+    Section::Capabilities,
+    Section::Extensions,
+    Section::ExtInstImports,
+    Section::MemoryModel,
+    Section::EntryPoints,
+    Section::ExecutionModes,
+    Section::Debugs,
+    Section::Annotations,
+    Section::TypesGlobalValues,
+];
+
+/// An instruction with its canonical form and disassembly.
+struct CanonicalInst {
+    opcode: spirv::Op,
+    canonical: String,
+    disassembly: String,
+}
+
+/// A module with all instructions in canonical form.
+struct CanonicalModule {
+    /// Instructions of each section in `GLOBAL_SECTIONS`.
+    globals: Vec<Vec<CanonicalInst>>,
+    /// Function names and their instructions.
+    functions: Vec<(String, Vec<CanonicalInst>)>,
+}
+
+impl CanonicalModule {
+    fn new(module: &mr::Module) -> CanonicalModule {
+        let mut names = Namer::new(module);
+        let sections: Vec<&[mr::Instruction]> = vec![
+            &module.capabilities,
+            &module.extensions,
+            &module.ext_inst_imports,
+            module.memory_model.as_slice(),
+            &module.entry_points,
+            &module.execution_modes,
+            &module.debugs,
+            &module.annotations,
+            &module.types_global_values,
+        ];
+        // Globals must be named before any instruction referring to them is
+        // canonicalized.
+        for inst in sections.iter().flat_map(|s| s.iter()) {
+            names.name_global(inst);
+        }
+        let globals = sections
+            .iter()
+            .map(|s| s.iter().map(|inst| names.canonicalize(inst)).collect())
+            .collect();
+
+        let mut unnamed = 0;
+        let mut functions = vec![];
+        for function in &module.functions {
+            let name = match function.def.as_ref().and_then(|d| d.result_id) {
+                Some(id) if names.debug_names.contains_key(&id) => names.debug_names[&id].clone(),
+                _ => {
+                    unnamed += 1;
+                    format!("#{}", unnamed - 1)
+                }
+            };
+            names.name_locals(&name, function);
+            let insts = function.all_inst_iter().map(|inst| names.canonicalize(inst)).collect();
+            functions.push((name, insts));
+        }
+        CanonicalModule { globals, functions }
+    }
+}
+
+/// Assigns canonical names to ids.
+struct Namer {
+    /// Unique debug names and entry point names of ids.
+    debug_names: HashMap<Word, String>,
+    canonical: HashMap<Word, String>,
+    /// Number of occurrences of each canonical name so far, to tell apart
+    /// ids which would otherwise get the same name.
+    occurrences: HashMap<String, usize>,
+}
+
+impl Namer {
+    fn new(module: &mr::Module) -> Namer {
+        let mut candidates: HashMap<Word, String> = HashMap::new();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let entry_names = module.entry_points.iter().filter_map(|inst| {
+            match (inst.operands.get(1), inst.operands.get(2)) {
+                (Some(&mr::Operand::IdRef(id)), Some(mr::Operand::LiteralString(name))) => {
+                    Some((id, name))
+                }
+                _ => None,
+            }
+        });
+        let debug_names = module.debugs.iter().filter_map(|inst| {
+            match (inst.class.opcode, inst.operands.first(), inst.operands.get(1)) {
+                (spirv::Op::Name, Some(&mr::Operand::IdRef(id)), Some(mr::Operand::LiteralString(name))) => {
+                    Some((id, name))
+                }
+                _ => None,
+            }
+        });
+        for (id, name) in entry_names.chain(debug_names) {
+            if !candidates.contains_key(&id) && !name.is_empty() {
+                candidates.insert(id, name.to_string());
+                *counts.entry(name.to_string()).or_insert(0) += 1;
+            }
+        }
+        candidates.retain(|_, name| counts[name] == 1);
+        Namer {
+            debug_names: candidates,
+            canonical: HashMap::new(),
+            occurrences: HashMap::new(),
+        }
+    }
+
+    /// Names the result id of the given global instruction, if any.
+    fn name_global(&mut self, inst: &mr::Instruction) {
+        let id = match inst.result_id {
+            Some(id) => id,
+            None => return,
+        };
+        let name = if let Some(name) = self.debug_names.get(&id) {
+            format!("name:{}", name)
+        } else {
+            let signature = match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::ExtInstImport, Some(mr::Operand::LiteralString(set))) => {
+                    format!("import:{}", set)
+                }
+                (spirv::Op::String, Some(mr::Operand::LiteralString(s))) => format!("string:{}", s),
+                _ => format!("{:x}", hash(&self.signature(inst))),
+            };
+            self.disambiguate(signature)
+        };
+        self.canonical.insert(id, name);
+    }
+
+    /// Names the function and all ids defined in the given `function`.
+    fn name_locals(&mut self, name: &str, function: &mr::Function) {
+        if let Some(id) = function.def.as_ref().and_then(|d| d.result_id) {
+            self.canonical.insert(id, format!("fn:{}", name));
+        }
+        // Labels are named in order first since they are referred to before
+        // being defined.
+        for (index, block) in function.basic_blocks.iter().enumerate() {
+            if let Some(id) = block.label.as_ref().and_then(|l| l.result_id) {
+                self.canonical.insert(id, format!("{}.bb{}", name, index));
+            }
+        }
+        let insts = function
+            .parameters
+            .iter()
+            .chain(function.basic_blocks.iter().flat_map(|bb| &bb.instructions));
+        for inst in insts {
+            if let Some(id) = inst.result_id {
+                let signature = format!("{}.{:x}", name, hash(&self.signature(inst)));
+                let local = self.disambiguate(signature);
+                self.canonical.insert(id, local);
+            }
+        }
+    }
+
+    fn disambiguate(&mut self, name: String) -> String {
+        let count = self.occurrences.entry(name.clone()).or_insert(0);
+        *count += 1;
+        format!("{}#{}", name, *count - 1)
+    }
+
+    fn id(&self, id: Word) -> String {
+        self.canonical.get(&id).cloned().unwrap_or_else(|| "?".to_string())
+    }
+
+    /// Returns the instruction without its result id, with referenced ids
+    /// replaced by their canonical names.
+    fn signature(&self, inst: &mr::Instruction) -> String {
+        let mut s = format!("Op{}", inst.class.opname);
+        if let Some(ty) = inst.result_type {
+            s.push(' ');
+            s.push_str(&self.id(ty));
+        }
+        for operand in &inst.operands {
+            s.push(' ');
+            match *operand {
+                mr::Operand::IdMemorySemantics(id) |
+                mr::Operand::IdScope(id) |
+                mr::Operand::IdRef(id) => s.push_str(&self.id(id)),
+                _ => s.push_str(&operand.disassemble()),
+            }
+        }
+        s
+    }
+
+    fn canonicalize(&self, inst: &mr::Instruction) -> CanonicalInst {
+        let canonical = match inst.result_id {
+            Some(id) => format!("{} = {}", self.id(id), self.signature(inst)),
+            None => self.signature(inst),
+        };
+        CanonicalInst {
+            opcode: inst.class.opcode,
+            canonical,
+            disassembly: inst.disassemble(),
+        }
+    }
+}
+
+fn hash(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Aligns `old` and `new` and appends the differences to `events`.
+fn diff_section(
+    section: &Section,
+    old: &[CanonicalInst],
+    new: &[CanonicalInst],
+    events: &mut Vec<DiffEvent>,
+) {
+    let prefix = old.iter()
+        .zip(new)
+        .take_while(|&(o, n)| o.canonical == n.canonical)
+        .count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+    let suffix = old.iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|&(o, n)| o.canonical == n.canonical)
+        .count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    // lcs[i][j]: the length of the longest common subsequence of old[i..]
+    // and new[j..].
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i].canonical == new[j].canonical {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut removed: Vec<&CanonicalInst> = vec![];
+    let mut added: Vec<&CanonicalInst> = vec![];
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i].canonical == new[j].canonical {
+            flush(section, &mut removed, &mut added, events);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            removed.push(&old[i]);
+            i += 1;
+        } else {
+            added.push(&new[j]);
+            j += 1;
+        }
+    }
+    flush(section, &mut removed, &mut added, events);
+}
+
+/// Reports a run of removed and added instructions between two aligned
+/// instructions. Removed and added instructions with the same opcode are
+/// paired up in order as changes.
+fn flush(
+    section: &Section,
+    removed: &mut Vec<&CanonicalInst>,
+    added: &mut Vec<&CanonicalInst>,
+    events: &mut Vec<DiffEvent>,
+) {
+    let mut added_iter = added.drain(..).peekable();
+    for old in removed.drain(..) {
+        while let Some(new) = added_iter.next_if(|n| n.opcode != old.opcode) {
+            events.push(DiffEvent::Added {
+                section: section.clone(),
+                instruction: new.disassembly.clone(),
+            });
+        }
+        match added_iter.next() {
+            Some(new) => events.push(DiffEvent::Changed {
+                section: section.clone(),
+                old: old.disassembly.clone(),
+                new: new.disassembly.clone(),
+            }),
+            None => events.push(DiffEvent::Removed {
+                section: section.clone(),
+                instruction: old.disassembly.clone(),
+            }),
+        }
+    }
+    for new in added_iter {
+        events.push(DiffEvent::Added {
+            section: section.clone(),
+            instruction: new.disassembly.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{diff_modules, DiffEvent, Section};
+
+    fn build(first_id: spirv::Word, constant: u32, extra_capability: bool) -> mr::Module {
+        let mut b = mr::Builder::new();
+        for _ in 1..first_id {
+            b.id();
+        }
+        b.capability(spirv::Capability::Shader);
+        if extra_capability {
+            b.capability(spirv::Capability::Float64);
+        }
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let c = b.constant_u32(int, constant);
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.name(f, "main");
+        b.begin_basic_block(None).unwrap();
+        b.iadd(int, None, c, c).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_renumbered_modules_are_equal() {
+        assert_eq!(Vec::<DiffEvent>::new(), diff_modules(&build(1, 7, false), &build(10, 7, false)));
+    }
+
+    #[test]
+    fn test_added_and_changed() {
+        let events = diff_modules(&build(1, 7, false), &build(10, 8, true));
+        assert_eq!(
+            vec![
+                "[Capabilities] + OpCapability Float64",
+                "[TypesGlobalValues] %3 = OpConstant  %2  7 => %12 = OpConstant  %11  8",
+                "[function main] %7 = OpIAdd  %2  %3 %3 => %16 = OpIAdd  %11  %12 %12",
+            ],
+            events.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_removed() {
+        let events = diff_modules(&build(1, 7, true), &build(1, 7, false));
+        assert_eq!(
+            vec![DiffEvent::Removed {
+                section: Section::Capabilities,
+                instruction: "OpCapability Float64".to_string(),
+            }],
+            events
+        );
+    }
+}
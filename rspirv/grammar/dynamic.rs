@@ -0,0 +1,263 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use std::collections::BTreeMap;
+use std::{error, fmt};
+use super::syntax::Instruction;
+use super::{LogicalOperand, OperandKind};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+#[cfg(feature = "json-grammar")]
+use serde_json::Value;
+#[cfg(feature = "json-grammar")]
+use super::{CoreInstructionTable, OperandQuantifier};
+
+/// Errors in building a `DynamicGrammar`.
+#[derive(Debug)]
+pub enum DynamicGrammarError {
+    /// The JSON grammar is malformed, for the given reason.
+    Malformed(String),
+    /// The operand kind with the given name is neither known to this library
+    /// nor defined as an enum in the JSON grammar.
+    UnknownOperandKind(String),
+    /// The operand kind can only be decoded for specific core instructions.
+    UnsupportedOperandKind(OperandKind),
+}
+
+impl DynamicGrammarError {
+    /// Returns a short description of the error.
+    pub fn describe(&self) -> &str {
+        match *self {
+            DynamicGrammarError::Malformed(_) => "malformed grammar",
+            DynamicGrammarError::UnknownOperandKind(_) => "unknown operand kind",
+            DynamicGrammarError::UnsupportedOperandKind(_) => "unsupported operand kind",
+        }
+    }
+}
+
+impl error::Error for DynamicGrammarError {
+    fn description(&self) -> &str {
+        self.describe()
+    }
+}
+
+impl fmt::Display for DynamicGrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DynamicGrammarError::Malformed(ref reason) => {
+                write!(f, "{}: {}", self.describe(), reason)
+            }
+            DynamicGrammarError::UnknownOperandKind(ref kind) => {
+                write!(f, "{} '{}'", self.describe(), kind)
+            }
+            DynamicGrammarError::UnsupportedOperandKind(kind) => {
+                write!(f, "{} {:?}", self.describe(), kind)
+            }
+        }
+    }
+}
+
+/// Instruction grammar supplied at runtime, for opcodes unknown to the
+/// compiled-in tables, e.g., those of private vendor extensions.
+///
+/// Handing it to [`Parser::with_grammar`](../binary/struct.Parser.html#method.with_grammar)
+/// makes the parser decode the operands of such instructions instead of
+/// keeping them as raw words, and the disassembler print their opnames.
+/// The resulting instructions are still unknown instructions as far as
+/// [`mr::Instruction::unknown_opcode`](../mr/struct.Instruction.html#method.unknown_opcode)
+/// is concerned.
+///
+/// Instructions refer to their grammar entries for as long as they live,
+/// so entries are leaked like the compiled-in tables are static. Grammars
+/// are meant to be loaded once per program.
+#[derive(Debug, Default)]
+pub struct DynamicGrammar {
+    instructions: BTreeMap<u16, &'static Instruction<'static>>,
+}
+
+impl DynamicGrammar {
+    /// Creates an empty grammar.
+    pub fn new() -> Self {
+        DynamicGrammar { instructions: BTreeMap::new() }
+    }
+
+    /// Adds the grammar for the instruction with the given `opname`,
+    /// without the `Op` prefix, and `opcode`, replacing any previous entry
+    /// for `opcode`.
+    ///
+    /// Entries for opcodes known to the compiled-in tables are never
+    /// consulted by the parser. Operands of the kinds only used by
+    /// `OpConstant`, `OpSpecConstant`, and `OpSpecConstantOp` are rejected.
+    pub fn add_instruction(&mut self,
+                           opname: &str,
+                           opcode: u16,
+                           operands: Vec<LogicalOperand>)
+                           -> Result<(), DynamicGrammarError> {
+        for operand in &operands {
+            match operand.kind {
+                OperandKind::LiteralContextDependentNumber |
+                OperandKind::LiteralSpecConstantOpInteger => {
+                    return Err(DynamicGrammarError::UnsupportedOperandKind(operand.kind))
+                }
+                _ => (),
+            }
+        }
+        let inst = Instruction {
+            opname: Box::leak(opname.to_owned().into_boxed_str()),
+            // Like grammar::UNKNOWN_INSTRUCTION, as spirv::Op cannot hold
+            // unknown opcodes.
+            opcode: spirv::Op::Nop,
+            capabilities: &[],
+            operands: Box::leak(operands.into_boxed_slice()),
+        };
+        self.instructions.insert(opcode, Box::leak(Box::new(inst)));
+        Ok(())
+    }
+
+    /// Looks up the given `opcode` in this grammar and returns a reference
+    /// to the instruction grammar entry if found.
+    pub fn lookup_opcode(&self, opcode: u16) -> Option<&'static Instruction<'static>> {
+        self.instructions.get(&opcode).cloned()
+    }
+
+    /// Returns the number of instructions in this grammar.
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Returns true if this grammar has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Loads the instructions in the given SPIR-V JSON grammar, in the
+    /// format of `spirv.core.grammar.json`, skipping those with opcodes
+    /// known to the compiled-in tables.
+    ///
+    /// Operand kinds unknown to this library but defined as value or bit
+    /// enums in the grammar are decoded as literal integers.
+    #[cfg(feature = "json-grammar")]
+    pub fn from_json(json: &str) -> Result<Self, DynamicGrammarError> {
+        let malformed = |reason: &str| DynamicGrammarError::Malformed(reason.to_string());
+        let root: Value = serde_json::from_str(json)
+            .map_err(|err| DynamicGrammarError::Malformed(err.to_string()))?;
+
+        // Names of enums defined by the grammar itself.
+        let mut enums = vec![];
+        if let Some(kinds) = root.get("operand_kinds").and_then(Value::as_array) {
+            for kind in kinds {
+                match kind.get("category").and_then(Value::as_str) {
+                    Some("ValueEnum") | Some("BitEnum") => {
+                        enums.extend(kind.get("kind").and_then(Value::as_str))
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        let mut grammar = DynamicGrammar::new();
+        let instructions = root.get("instructions")
+                               .and_then(Value::as_array)
+                               .ok_or_else(|| malformed("no instructions array"))?;
+        for inst in instructions {
+            let opname = inst.get("opname")
+                             .and_then(Value::as_str)
+                             .ok_or_else(|| malformed("instruction without opname"))?;
+            let opcode = match inst.get("opcode").and_then(Value::as_u64) {
+                Some(opcode) if opcode <= u16::MAX as u64 => opcode as u16,
+                _ => return Err(malformed(&format!("bad opcode for {}", opname))),
+            };
+            if CoreInstructionTable::lookup_opcode(opcode).is_some() {
+                continue;
+            }
+            let mut operands = vec![];
+            let empty = vec![];
+            let loperands = match inst.get("operands") {
+                Some(loperands) => loperands
+                    .as_array()
+                    .ok_or_else(|| malformed(&format!("bad operands for {}", opname)))?,
+                None => &empty,
+            };
+            for loperand in loperands {
+                let name = match loperand.get("kind").and_then(Value::as_str) {
+                    Some(name) => name,
+                    None => return Err(malformed(&format!("bad operand for {}", opname))),
+                };
+                let kind = match OperandKind::from_name(name) {
+                    Some(kind) => kind,
+                    None if enums.contains(&name) => OperandKind::LiteralInteger,
+                    None => return Err(DynamicGrammarError::UnknownOperandKind(name.to_string())),
+                };
+                let quantifier = match loperand.get("quantifier").and_then(Value::as_str) {
+                    None => OperandQuantifier::One,
+                    Some("?") => OperandQuantifier::ZeroOrOne,
+                    Some("*") => OperandQuantifier::ZeroOrMore,
+                    Some(q) => return Err(malformed(&format!("bad quantifier '{}'", q))),
+                };
+                operands.push(LogicalOperand { kind, quantifier });
+            }
+            let opname = opname.strip_prefix("Op").unwrap_or(opname);
+            grammar.add_instruction(opname, opcode, operands)?;
+        }
+        Ok(grammar)
+    }
+}
+
+#[cfg(all(test, feature = "json-grammar"))]
+mod tests {
+    use grammar::{OperandKind, OperandQuantifier};
+    use super::{DynamicGrammar, DynamicGrammarError};
+
+    #[test]
+    fn test_from_json() {
+        let json = r#"{
+            "operand_kinds": [{"category": "ValueEnum", "kind": "VendorMode"}],
+            "instructions": [
+                {"opname": "OpNop", "opcode": 0},
+                {"opname": "OpVendorBlendINTERNAL", "opcode": 60000, "operands": [
+                    {"kind": "IdResultType"},
+                    {"kind": "IdResult"},
+                    {"kind": "VendorMode", "name": "'Mode'"},
+                    {"kind": "IdRef", "quantifier": "*"}
+                ]}
+            ]
+        }"#;
+        let grammar = DynamicGrammar::from_json(json).unwrap();
+        assert_eq!(1, grammar.len());
+        assert!(grammar.lookup_opcode(0).is_none());
+        let inst = grammar.lookup_opcode(60000).unwrap();
+        assert_eq!("VendorBlendINTERNAL", inst.opname);
+        let kinds: Vec<_> = inst.operands.iter().map(|o| (o.kind, o.quantifier)).collect();
+        assert_eq!(
+            vec![
+                (OperandKind::IdResultType, OperandQuantifier::One),
+                (OperandKind::IdResult, OperandQuantifier::One),
+                (OperandKind::LiteralInteger, OperandQuantifier::One),
+                (OperandKind::IdRef, OperandQuantifier::ZeroOrMore),
+            ],
+            kinds
+        );
+
+        let json = r#"{"instructions": [{"opname": "OpFoo", "opcode": 60001,
+                       "operands": [{"kind": "Mystery"}]}]}"#;
+        match DynamicGrammar::from_json(json) {
+            Err(DynamicGrammarError::UnknownOperandKind(ref kind)) if kind == "Mystery" => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert_matches!(DynamicGrammar::from_json("{}"), Err(DynamicGrammarError::Malformed(_)));
+    }
+}
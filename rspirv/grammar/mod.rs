@@ -17,13 +17,22 @@
 //! It defines the syntax grammar of all instructions (their layouts
 //! and operands).
 //!
-//! It also provides many reflect functions.
+//! It also provides many reflect functions, and runtime queries about
+//! opcodes via the [`Opcode`](trait.Opcode.html) trait.
+//!
+//! Grammar for instructions unknown to the compiled-in tables can be
+//! supplied at runtime with a [`DynamicGrammar`](struct.DynamicGrammar.html).
 
+pub use self::dynamic::{DynamicGrammar, DynamicGrammarError};
+pub use self::opcode::{OpClass, Opcode};
 pub use self::syntax::{Instruction, ExtendedInstruction};
-pub use self::syntax::CoreInstructionTable;
+pub use self::syntax::{CoreInstructionTable, UNKNOWN_INSTRUCTION};
 pub use self::syntax::GlslStd450InstructionTable;
 pub use self::syntax::OpenCLStd100InstructionTable;
+pub use self::syntax::NonSemanticShaderDebugInfo100InstructionTable;
 pub use self::syntax::{LogicalOperand, OperandKind, OperandQuantifier};
 
 pub mod reflect;
+mod dynamic;
+mod opcode;
 mod syntax;
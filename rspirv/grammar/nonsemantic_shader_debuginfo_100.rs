@@ -0,0 +1,65 @@
+// Copyright 2016 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// AUTOMATICALLY GENERATED from the SPIR-V JSON grammar:
+//   external/spirv.core.grammar.json.
+// DO NOT MODIFY!
+
+#[cfg_attr(rustfmt, rustfmt_skip)]
+static NONSEMANTIC_SHADER_DEBUGINFO_100_INSTRUCTION_TABLE: &[ExtendedInstruction<'static>] = &[
+    ext_inst!(DebugInfoNone, 0, [], []),
+    ext_inst!(DebugCompilationUnit, 1, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeBasic, 2, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypePointer, 3, [], [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeQualifier, 4, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeArray, 5, [], [(IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugTypeVector, 6, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypedef, 7, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeFunction, 8, [], [(IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugTypeEnum, 9, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (PairIdRefIdRef, ZeroOrMore)]),
+    ext_inst!(DebugTypeComposite, 10, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugTypeMember, 11, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugTypeInheritance, 12, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypePtrToMember, 13, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeTemplate, 14, [], [(IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugTypeTemplateParameter, 15, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeTemplateTemplateParameter, 16, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeTemplateParameterPack, 17, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugGlobalVariable, 18, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugFunctionDeclaration, 19, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugFunction, 20, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugLexicalBlock, 21, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugLexicalBlockDiscriminator, 22, [], [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugScope, 23, [], [(IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugNoScope, 24, [], []),
+    ext_inst!(DebugInlinedAt, 25, [], [(IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugLocalVariable, 26, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugInlinedVariable, 27, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugDeclare, 28, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugValue, 29, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugOperation, 30, [], [(IdRef, One), (IdRef, ZeroOrMore)]),
+    ext_inst!(DebugExpression, 31, [], [(IdRef, ZeroOrMore)]),
+    ext_inst!(DebugMacroDef, 32, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugMacroUndef, 33, [], [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugImportedEntity, 34, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugSource, 35, [], [(IdRef, One), (IdRef, ZeroOrOne)]),
+    ext_inst!(DebugFunctionDefinition, 101, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugSourceContinued, 102, [], [(IdRef, One)]),
+    ext_inst!(DebugLine, 103, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugNoLine, 104, [], []),
+    ext_inst!(DebugBuildIdentifier, 105, [], [(IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugStoragePath, 106, [], [(IdRef, One)]),
+    ext_inst!(DebugEntryPoint, 107, [], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    ext_inst!(DebugTypeMatrix, 108, [], [(IdRef, One), (IdRef, One), (IdRef, One)]),
+];
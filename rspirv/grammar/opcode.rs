@@ -0,0 +1,270 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{reflect, CoreInstructionTable, OperandKind};
+
+/// The class of an instruction, following the grouping of instructions in
+/// the SPIR-V specification.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OpClass {
+    Miscellaneous,
+    Debug,
+    Annotation,
+    Extension,
+    ModeSetting,
+    TypeDeclaration,
+    ConstantCreation,
+    Memory,
+    Function,
+    Image,
+    Conversion,
+    Composite,
+    Arithmetic,
+    Bit,
+    RelationalAndLogical,
+    Derivative,
+    ControlFlow,
+    Atomic,
+    Primitive,
+    Barrier,
+    Group,
+    DeviceSideEnqueue,
+    Pipe,
+    NonUniform,
+    RayTracing,
+}
+
+/// Runtime queries about opcodes.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::grammar::{OpClass, Opcode};
+///
+/// fn main() {
+///     assert_eq!(OpClass::Arithmetic, spirv::Op::IAdd.class());
+///     assert!(spirv::Op::IAdd.has_result());
+///     assert!(spirv::Op::IAdd.is_commutative());
+///     assert!(spirv::Op::Store.may_have_side_effects());
+/// }
+/// ```
+pub trait Opcode {
+    /// Returns the class of this opcode.
+    fn class(&self) -> OpClass;
+    /// Returns true if instructions with this opcode have a result id.
+    fn has_result(&self) -> bool;
+    /// Returns true if instructions with this opcode have a result type id.
+    fn has_result_type(&self) -> bool;
+    /// Returns true if instructions with this opcode terminate basic blocks.
+    fn is_terminator(&self) -> bool;
+    /// Returns true if executing instructions with this opcode may have
+    /// effects other than producing their result, i.e., they cannot be
+    /// removed even if their result is unused.
+    ///
+    /// This is conservative and only depends on the opcode: for example,
+    /// OpLoad is considered free of side effects even if it is volatile,
+    /// and OpExtInst is always considered to have side effects.
+    fn may_have_side_effects(&self) -> bool;
+    /// Returns true if this opcode is for a binary operation whose two
+    /// operands can be swapped without changing the result.
+    fn is_commutative(&self) -> bool;
+}
+
+impl Opcode for spirv::Op {
+    fn class(&self) -> OpClass {
+        use spirv::Op::*;
+        let opcode = *self as u32;
+        match *self {
+            Nop | Undef | SizeOf | CooperativeMatrixLengthKHR => OpClass::Miscellaneous,
+            SourceContinued | Source | SourceExtension | Name | MemberName | String | Line |
+            NoLine | ModuleProcessed => OpClass::Debug,
+            Decorate | MemberDecorate | DecorationGroup | GroupDecorate | GroupMemberDecorate |
+            DecorateId | DecorateStringGOOGLE | MemberDecorateStringGOOGLE => OpClass::Annotation,
+            Extension | ExtInstImport | ExtInst => OpClass::Extension,
+            MemoryModel | EntryPoint | ExecutionMode | Capability | ExecutionModeId => {
+                OpClass::ModeSetting
+            }
+            TypePipeStorage | TypeNamedBarrier | TypeRayQueryKHR | TypeAccelerationStructureKHR |
+            TypeCooperativeMatrixKHR => OpClass::TypeDeclaration,
+            ConstantPipeStorage => OpClass::ConstantCreation,
+            Variable | ImageTexelPointer | Load | Store | CopyMemory | CopyMemorySized |
+            AccessChain | InBoundsAccessChain | PtrAccessChain | ArrayLength |
+            GenericPtrMemSemantics | InBoundsPtrAccessChain | CooperativeMatrixLoadKHR |
+            CooperativeMatrixStoreKHR => OpClass::Memory,
+            CooperativeMatrixMulAddKHR => OpClass::Arithmetic,
+            Function | FunctionParameter | FunctionEnd | FunctionCall => OpClass::Function,
+            FragmentMaskFetchAMD | FragmentFetchAMD | ImageSparseRead => OpClass::Image,
+            ControlBarrier | MemoryBarrier | NamedBarrierInitialize | MemoryNamedBarrier => {
+                OpClass::Barrier
+            }
+            AtomicFlagTestAndSet | AtomicFlagClear => OpClass::Atomic,
+            CreatePipeFromPipeStorage => OpClass::Pipe,
+            GetKernelLocalSizeForSubgroupCount | GetKernelMaxNumSubgroups => {
+                OpClass::DeviceSideEnqueue
+            }
+            GroupNonUniformPartitionNV => OpClass::NonUniform,
+            IgnoreIntersectionKHR | TerminateRayKHR | EmitMeshTasksEXT => OpClass::ControlFlow,
+            SetMeshOutputsEXT => OpClass::Primitive,
+            TraceRayKHR | ExecuteCallableKHR | ConvertUToAccelerationStructureKHR |
+            RayQueryInitializeKHR | RayQueryTerminateKHR | RayQueryGenerateIntersectionKHR |
+            RayQueryConfirmIntersectionKHR | RayQueryProceedKHR |
+            RayQueryGetIntersectionTypeKHR => OpClass::RayTracing,
+            _ => match opcode {
+                19..=39 => OpClass::TypeDeclaration,
+                41..=52 => OpClass::ConstantCreation,
+                77..=84 => OpClass::Composite,
+                86..=107 | 305..=316 => OpClass::Image,
+                109..=124 => OpClass::Conversion,
+                126..=152 => OpClass::Arithmetic,
+                154..=191 => OpClass::RelationalAndLogical,
+                194..=205 => OpClass::Bit,
+                207..=215 => OpClass::Derivative,
+                218..=221 => OpClass::Primitive,
+                227..=242 => OpClass::Atomic,
+                245..=257 => OpClass::ControlFlow,
+                274..=288 => OpClass::Pipe,
+                291..=304 => OpClass::DeviceSideEnqueue,
+                333..=366 => OpClass::NonUniform,
+                6016..=6032 => OpClass::RayTracing,
+                // Group instructions, including the vendor extensions for
+                // subgroup operations.
+                _ => OpClass::Group,
+            },
+        }
+    }
+
+    fn has_result(&self) -> bool {
+        CoreInstructionTable::get(*self)
+            .operands
+            .iter()
+            .any(|operand| operand.kind == OperandKind::IdResult)
+    }
+
+    fn has_result_type(&self) -> bool {
+        CoreInstructionTable::get(*self)
+            .operands
+            .iter()
+            .any(|operand| operand.kind == OperandKind::IdResultType)
+    }
+
+    fn is_terminator(&self) -> bool {
+        reflect::is_terminator(*self)
+    }
+
+    fn may_have_side_effects(&self) -> bool {
+        use spirv::Op::*;
+        match self.class() {
+            OpClass::Atomic | OpClass::Barrier | OpClass::Primitive => return true,
+            OpClass::ControlFlow => return *self != Phi,
+            _ => {}
+        }
+        matches!(
+            *self,
+            ExtInst | Store | CopyMemory | CopyMemorySized | FunctionCall | ImageWrite |
+                ReadPipe | WritePipe | ReservedReadPipe | ReservedWritePipe |
+                ReserveReadPipePackets | ReserveWritePipePackets | CommitReadPipe |
+                CommitWritePipe | GroupReserveReadPipePackets | GroupReserveWritePipePackets |
+                GroupCommitReadPipe | GroupCommitWritePipe | EnqueueMarker | EnqueueKernel |
+                RetainEvent | ReleaseEvent | CreateUserEvent | SetUserEventStatus |
+                CaptureEventProfilingInfo | GroupAsyncCopy | GroupWaitEvents |
+                SubgroupBlockWriteINTEL | SubgroupImageBlockWriteINTEL | TraceRayKHR |
+                ExecuteCallableKHR | RayQueryInitializeKHR | RayQueryTerminateKHR |
+                RayQueryGenerateIntersectionKHR | RayQueryConfirmIntersectionKHR |
+                RayQueryProceedKHR | CooperativeMatrixStoreKHR
+        )
+    }
+
+    fn is_commutative(&self) -> bool {
+        use spirv::Op::*;
+        matches!(
+            *self,
+            IAdd | FAdd | IMul | FMul | Dot | IAddCarry | UMulExtended | SMulExtended |
+                LessOrGreater | Ordered | Unordered | LogicalEqual | LogicalNotEqual |
+                LogicalOr | LogicalAnd | IEqual | INotEqual | FOrdEqual | FUnordEqual |
+                FOrdNotEqual | FUnordNotEqual | BitwiseOr | BitwiseXor | BitwiseAnd
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spirv;
+
+    use super::{OpClass, Opcode};
+
+    #[test]
+    fn test_class() {
+        assert_eq!(OpClass::Miscellaneous, spirv::Op::Nop.class());
+        assert_eq!(OpClass::TypeDeclaration, spirv::Op::TypeForwardPointer.class());
+        assert_eq!(OpClass::ConstantCreation, spirv::Op::SpecConstantOp.class());
+        assert_eq!(OpClass::Image, spirv::Op::ImageSparseFetch.class());
+        assert_eq!(OpClass::ControlFlow, spirv::Op::Unreachable.class());
+        assert_eq!(OpClass::Group, spirv::Op::SubgroupBallotKHR.class());
+        assert_eq!(OpClass::Group, spirv::Op::GroupSMax.class());
+        assert_eq!(OpClass::NonUniform, spirv::Op::GroupNonUniformQuadSwap.class());
+        assert_eq!(OpClass::Debug, spirv::Op::NoLine.class());
+    }
+
+    #[test]
+    fn test_class_ray_query() {
+        for &op in &[
+            spirv::Op::RayQueryInitializeKHR,
+            spirv::Op::RayQueryTerminateKHR,
+            spirv::Op::RayQueryGenerateIntersectionKHR,
+            spirv::Op::RayQueryConfirmIntersectionKHR,
+            spirv::Op::RayQueryProceedKHR,
+            spirv::Op::RayQueryGetIntersectionTypeKHR,
+            spirv::Op::RayQueryGetWorldRayOriginKHR,
+        ] {
+            assert_eq!(OpClass::RayTracing, op.class());
+        }
+        assert_eq!(OpClass::TypeDeclaration, spirv::Op::TypeRayQueryKHR.class());
+    }
+
+    #[test]
+    fn test_result() {
+        assert!(spirv::Op::IAdd.has_result());
+        assert!(spirv::Op::IAdd.has_result_type());
+        assert!(spirv::Op::TypeInt.has_result());
+        assert!(!spirv::Op::TypeInt.has_result_type());
+        assert!(!spirv::Op::Store.has_result());
+        assert!(!spirv::Op::Store.has_result_type());
+    }
+
+    #[test]
+    fn test_side_effects() {
+        assert!(spirv::Op::Store.may_have_side_effects());
+        assert!(spirv::Op::AtomicLoad.may_have_side_effects());
+        assert!(spirv::Op::Return.may_have_side_effects());
+        assert!(spirv::Op::FunctionCall.may_have_side_effects());
+        assert!(!spirv::Op::Load.may_have_side_effects());
+        assert!(!spirv::Op::Phi.may_have_side_effects());
+        assert!(!spirv::Op::IAdd.may_have_side_effects());
+    }
+
+    #[test]
+    fn test_commutative() {
+        assert!(spirv::Op::IAdd.is_commutative());
+        assert!(spirv::Op::BitwiseXor.is_commutative());
+        assert!(!spirv::Op::ISub.is_commutative());
+        assert!(!spirv::Op::SLessThan.is_commutative());
+        assert!(spirv::Op::Branch.is_terminator());
+        assert!(!spirv::Op::LoopMerge.is_terminator());
+    }
+}
@@ -32,7 +32,8 @@ pub fn is_nonlocation_debug(opcode: spirv::Op) -> bool {
         spirv::Op::SourceExtension |
         spirv::Op::Name |
         spirv::Op::MemberName |
-        spirv::Op::String => true,
+        spirv::Op::String |
+        spirv::Op::ModuleProcessed => true,
         _ => false,
     }
 }
@@ -80,7 +81,10 @@ pub fn is_type(opcode: spirv::Op) -> bool {
         spirv::Op::TypeReserveId |
         spirv::Op::TypeQueue |
         spirv::Op::TypePipe |
-        spirv::Op::TypeForwardPointer => true,
+        spirv::Op::TypeForwardPointer |
+        spirv::Op::TypeRayQueryKHR |
+        spirv::Op::TypeAccelerationStructureKHR |
+        spirv::Op::TypeCooperativeMatrixKHR => true,
         _ => false,
     }
 }
@@ -103,6 +107,27 @@ pub fn is_constant(opcode: spirv::Op) -> bool {
     }
 }
 
+/// Returns true if the given opcode is allowed as the operation of an
+/// OpSpecConstantOp instruction.
+pub fn is_spec_constant_op_allowed(opcode: spirv::Op) -> bool {
+    use spirv::Op::*;
+    match opcode {
+        // Allowed in shaders.
+        SConvert | FConvert | SNegate | Not | IAdd | ISub | IMul | UDiv | SDiv | UMod | SRem |
+        SMod | ShiftRightLogical | ShiftRightArithmetic | ShiftLeftLogical | BitwiseOr |
+        BitwiseXor | BitwiseAnd | VectorShuffle | CompositeExtract | CompositeInsert |
+        LogicalOr | LogicalAnd | LogicalNot | LogicalEqual | LogicalNotEqual | Select | IEqual |
+        INotEqual | ULessThan | SLessThan | UGreaterThan | SGreaterThan | ULessThanEqual |
+        SLessThanEqual | UGreaterThanEqual | SGreaterThanEqual | QuantizeToF16 => true,
+        // Additionally allowed in kernels.
+        ConvertFToS | ConvertSToF | ConvertFToU | ConvertUToF | UConvert | ConvertPtrToU |
+        ConvertUToPtr | GenericCastToPtr | PtrCastToGeneric | Bitcast | FNegate | FAdd | FSub |
+        FMul | FDiv | FRem | FMod | AccessChain | InBoundsAccessChain | PtrAccessChain |
+        InBoundsPtrAccessChain => true,
+        _ => false,
+    }
+}
+
 /// Returns true if the given opcode is for a variable-defining instruction.
 pub fn is_variable(opcode: spirv::Op) -> bool {
     opcode == spirv::Op::Variable
@@ -117,7 +142,10 @@ pub fn is_terminator(opcode: spirv::Op) -> bool {
         spirv::Op::Kill |
         spirv::Op::Return |
         spirv::Op::ReturnValue |
-        spirv::Op::Unreachable => true,
+        spirv::Op::Unreachable |
+        spirv::Op::IgnoreIntersectionKHR |
+        spirv::Op::TerminateRayKHR |
+        spirv::Op::EmitMeshTasksEXT => true,
         _ => false,
     }
 }
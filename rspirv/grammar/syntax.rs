@@ -30,6 +30,7 @@ pub struct Instruction<'a> {
 }
 
 /// Grammar for an extended instruction.
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct ExtendedInstruction<'a> {
     /// OpName.
     pub opname: &'a str,
@@ -126,6 +127,24 @@ impl CoreInstructionTable {
 
 include!("table.rs");
 
+/// The grammar entry for instructions not understood by this library, e.g.,
+/// those with opcodes or enumerants from newer versions of SPIR-V.
+///
+/// Such instructions are kept as raw words: their first operand is their
+/// opcode, followed by all their words after the first one. As `spirv::Op`
+/// cannot hold unknown opcodes, the entry claims to be `Op::Nop`; use
+/// [`mr::Instruction::unknown_opcode`](../mr/struct.Instruction.html#method.unknown_opcode)
+/// to tell them apart.
+pub static UNKNOWN_INSTRUCTION: Instruction<'static> = Instruction {
+    opname: "Unknown",
+    opcode: spirv::Op::Nop,
+    capabilities: &[],
+    operands: &[LogicalOperand {
+        kind: OperandKind::LiteralInteger,
+        quantifier: OperandQuantifier::ZeroOrMore,
+    }],
+};
+
 /// The table for all `GLSLstd450` extended instructions.
 ///
 /// This table is staic data stored in the library.
@@ -177,3 +196,30 @@ impl OpenCLStd100InstructionTable {
 }
 
 include!("opencl_std_100.rs");
+
+/// The table for all `NonSemantic.Shader.DebugInfo.100` extended
+/// instructions.
+///
+/// This table is staic data stored in the library.
+pub struct NonSemanticShaderDebugInfo100InstructionTable;
+
+impl NonSemanticShaderDebugInfo100InstructionTable {
+    /// Looks up the given `opcode` in the instruction table and returns
+    /// a reference to the instruction grammar entry if found.
+    pub fn lookup_opcode(opcode: u32) -> Option<&'static ExtendedInstruction<'static>> {
+        NONSEMANTIC_SHADER_DEBUGINFO_100_INSTRUCTION_TABLE.iter().find(|inst| {
+            inst.opcode == opcode
+        })
+    }
+
+    /// Returns a reference to the instruction grammar entry with the given
+    /// `opcode`.
+    pub fn get(opcode: spirv::DebugInfoOp) -> &'static ExtendedInstruction<'static> {
+        NONSEMANTIC_SHADER_DEBUGINFO_100_INSTRUCTION_TABLE
+            .iter()
+            .find(|inst| inst.opcode == opcode as spirv::Word)
+            .expect("internal error")
+    }
+}
+
+include!("nonsemantic_shader_debuginfo_100.rs");
@@ -27,6 +27,8 @@ pub enum OperandKind {
     MemorySemantics,
     MemoryAccess,
     KernelProfilingInfo,
+    RayFlags,
+    CooperativeMatrixOperands,
     SourceLanguage,
     ExecutionModel,
     AddressingModel,
@@ -49,6 +51,11 @@ pub enum OperandKind {
     GroupOperation,
     KernelEnqueueFlags,
     Capability,
+    RayQueryIntersection,
+    RayQueryCommittedIntersectionType,
+    RayQueryCandidateIntersectionType,
+    CooperativeMatrixLayout,
+    CooperativeMatrixUse,
     IdResultType,
     IdResult,
     IdMemorySemantics,
@@ -64,6 +71,65 @@ pub enum OperandKind {
     PairIdRefIdRef,
 }
 
+impl OperandKind {
+    /// Returns the operand kind with the given `name` in the SPIR-V grammar.
+    pub fn from_name(name: &str) -> Option<OperandKind> {
+        match name {
+            "ImageOperands" => Some(OperandKind::ImageOperands),
+            "FPFastMathMode" => Some(OperandKind::FPFastMathMode),
+            "SelectionControl" => Some(OperandKind::SelectionControl),
+            "LoopControl" => Some(OperandKind::LoopControl),
+            "FunctionControl" => Some(OperandKind::FunctionControl),
+            "MemorySemantics" => Some(OperandKind::MemorySemantics),
+            "MemoryAccess" => Some(OperandKind::MemoryAccess),
+            "KernelProfilingInfo" => Some(OperandKind::KernelProfilingInfo),
+            "RayFlags" => Some(OperandKind::RayFlags),
+            "CooperativeMatrixOperands" => Some(OperandKind::CooperativeMatrixOperands),
+            "SourceLanguage" => Some(OperandKind::SourceLanguage),
+            "ExecutionModel" => Some(OperandKind::ExecutionModel),
+            "AddressingModel" => Some(OperandKind::AddressingModel),
+            "MemoryModel" => Some(OperandKind::MemoryModel),
+            "ExecutionMode" => Some(OperandKind::ExecutionMode),
+            "StorageClass" => Some(OperandKind::StorageClass),
+            "Dim" => Some(OperandKind::Dim),
+            "SamplerAddressingMode" => Some(OperandKind::SamplerAddressingMode),
+            "SamplerFilterMode" => Some(OperandKind::SamplerFilterMode),
+            "ImageFormat" => Some(OperandKind::ImageFormat),
+            "ImageChannelOrder" => Some(OperandKind::ImageChannelOrder),
+            "ImageChannelDataType" => Some(OperandKind::ImageChannelDataType),
+            "FPRoundingMode" => Some(OperandKind::FPRoundingMode),
+            "LinkageType" => Some(OperandKind::LinkageType),
+            "AccessQualifier" => Some(OperandKind::AccessQualifier),
+            "FunctionParameterAttribute" => Some(OperandKind::FunctionParameterAttribute),
+            "Decoration" => Some(OperandKind::Decoration),
+            "BuiltIn" => Some(OperandKind::BuiltIn),
+            "Scope" => Some(OperandKind::Scope),
+            "GroupOperation" => Some(OperandKind::GroupOperation),
+            "KernelEnqueueFlags" => Some(OperandKind::KernelEnqueueFlags),
+            "Capability" => Some(OperandKind::Capability),
+            "RayQueryIntersection" => Some(OperandKind::RayQueryIntersection),
+            "RayQueryCommittedIntersectionType" => Some(OperandKind::RayQueryCommittedIntersectionType),
+            "RayQueryCandidateIntersectionType" => Some(OperandKind::RayQueryCandidateIntersectionType),
+            "CooperativeMatrixLayout" => Some(OperandKind::CooperativeMatrixLayout),
+            "CooperativeMatrixUse" => Some(OperandKind::CooperativeMatrixUse),
+            "IdResultType" => Some(OperandKind::IdResultType),
+            "IdResult" => Some(OperandKind::IdResult),
+            "IdMemorySemantics" => Some(OperandKind::IdMemorySemantics),
+            "IdScope" => Some(OperandKind::IdScope),
+            "IdRef" => Some(OperandKind::IdRef),
+            "LiteralInteger" => Some(OperandKind::LiteralInteger),
+            "LiteralString" => Some(OperandKind::LiteralString),
+            "LiteralContextDependentNumber" => Some(OperandKind::LiteralContextDependentNumber),
+            "LiteralExtInstInteger" => Some(OperandKind::LiteralExtInstInteger),
+            "LiteralSpecConstantOpInteger" => Some(OperandKind::LiteralSpecConstantOpInteger),
+            "PairLiteralIntegerIdRef" => Some(OperandKind::PairLiteralIntegerIdRef),
+            "PairIdRefLiteralInteger" => Some(OperandKind::PairIdRefLiteralInteger),
+            "PairIdRefIdRef" => Some(OperandKind::PairIdRefIdRef),
+            _ => None,
+        }
+    }
+}
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 static INSTRUCTION_TABLE: &'static [Instruction<'static>] = &[
     inst!(Nop, [], []),
@@ -412,6 +478,23 @@ static INSTRUCTION_TABLE: &'static [Instruction<'static>] = &[
     inst!(SubgroupAnyKHR, [SubgroupVoteKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
     inst!(SubgroupAllEqualKHR, [SubgroupVoteKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
     inst!(SubgroupReadInvocationKHR, [SubgroupBallotKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(TraceRayKHR, [RayTracingKHR], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    inst!(ExecuteCallableKHR, [RayTracingKHR], [(IdRef, One), (IdRef, One)]),
+    inst!(ConvertUToAccelerationStructureKHR, [RayQueryKHR, RayTracingKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(IgnoreIntersectionKHR, [RayTracingKHR], []),
+    inst!(TerminateRayKHR, [RayTracingKHR], []),
+    inst!(TypeCooperativeMatrixKHR, [CooperativeMatrixKHR], [(IdResult, One), (IdRef, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    inst!(CooperativeMatrixLoadKHR, [CooperativeMatrixKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    inst!(CooperativeMatrixStoreKHR, [CooperativeMatrixKHR], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    inst!(CooperativeMatrixMulAddKHR, [CooperativeMatrixKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (CooperativeMatrixOperands, ZeroOrOne)]),
+    inst!(CooperativeMatrixLengthKHR, [CooperativeMatrixKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(TypeRayQueryKHR, [RayQueryKHR], [(IdResult, One)]),
+    inst!(RayQueryInitializeKHR, [RayQueryKHR], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryTerminateKHR, [RayQueryKHR], [(IdRef, One)]),
+    inst!(RayQueryGenerateIntersectionKHR, [RayQueryKHR], [(IdRef, One), (IdRef, One)]),
+    inst!(RayQueryConfirmIntersectionKHR, [RayQueryKHR], [(IdRef, One)]),
+    inst!(RayQueryProceedKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionTypeKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
     inst!(GroupIAddNonUniformAMD, [Groups], [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
     inst!(GroupFAddNonUniformAMD, [Groups], [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
     inst!(GroupFMinNonUniformAMD, [Groups], [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
@@ -422,6 +505,9 @@ static INSTRUCTION_TABLE: &'static [Instruction<'static>] = &[
     inst!(GroupSMaxNonUniformAMD, [Groups], [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
     inst!(FragmentMaskFetchAMD, [FragmentMaskAMD], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
     inst!(FragmentFetchAMD, [FragmentMaskAMD], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    inst!(EmitMeshTasksEXT, [MeshShadingEXT], [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    inst!(SetMeshOutputsEXT, [MeshShadingEXT], [(IdRef, One), (IdRef, One)]),
+    inst!(TypeAccelerationStructureKHR, [RayQueryKHR, RayTracingKHR], [(IdResult, One)]),
     inst!(SubgroupShuffleINTEL, [SubgroupShuffleINTEL], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
     inst!(SubgroupShuffleDownINTEL, [SubgroupShuffleINTEL], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
     inst!(SubgroupShuffleUpINTEL, [SubgroupShuffleINTEL], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
@@ -433,4 +519,21 @@ static INSTRUCTION_TABLE: &'static [Instruction<'static>] = &[
     inst!(DecorateStringGOOGLE, [], [(IdRef, One), (Decoration, One)]),
     inst!(MemberDecorateStringGOOGLE, [], [(IdRef, One), (LiteralInteger, One), (Decoration, One)]),
     inst!(GroupNonUniformPartitionNV, [GroupNonUniformPartitionedNV], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetRayTMinKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetRayFlagsKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionTKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionInstanceCustomIndexKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionInstanceIdKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionGeometryIndexKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionPrimitiveIndexKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionBarycentricsKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionFrontFaceKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionCandidateAABBOpaqueKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionObjectRayDirectionKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionObjectRayOriginKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetWorldRayDirectionKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetWorldRayOriginKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionObjectToWorldKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    inst!(RayQueryGetIntersectionWorldToObjectKHR, [RayQueryKHR], [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
 ];
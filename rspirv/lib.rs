@@ -26,6 +26,13 @@
 //!   (under developing)
 //! * SPIR-V [binary](binary/index.html) module decoding and parsing
 //!   functionalities
+//! * [Analyses](analysis/index.html) over the data representation, like
+//!   control flow graphs and dominator trees
+//! * [Transformations](transform/index.html) over the data representation
+//! * Structural [diffing](diff/index.html) of modules
+//! * [Testing](testing/index.html) utilities like templates for generating
+//!   families of SPIR-V assembly texts
+//! * A (partial) [validator](validate/index.html) for the data representation
 //!
 //! The data representation (DR) focuses on presenting the data within a
 //! SPIR-V module; it uses plain vectors to hold data of SPIR-V instructions,
@@ -96,18 +103,65 @@
 
 #![cfg_attr(feature = "clippy", feature(plugin))]
 #![cfg_attr(feature = "clippy", plugin(clippy))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
 #[cfg(test)]
 #[macro_use]
 extern crate assert_matches;
 #[macro_use]
 extern crate derive_more;
 extern crate num;
+#[cfg(feature = "serialize")]
+extern crate serde;
+#[cfg(feature = "serialize")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "json-grammar")]
+extern crate serde_json;
 extern crate spirv_headers as spirv;
 
+#[cfg(feature = "std")]
+pub mod analysis;
 pub mod binary;
+#[cfg(feature = "std")]
+pub mod diff;
 pub mod grammar;
 pub mod mr;
+#[cfg(feature = "std")]
 pub mod sr;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod validate;
 
 mod utils;
+
+/// The parts of `core` and `alloc` used by the crate, under the paths of
+/// `std`, so that the modules available without `std` can refer to `std`
+/// in either case.
+#[cfg(not(feature = "std"))]
+mod std {
+    pub use core::{borrow, cmp, convert, error, fmt, hash, iter, mem, ops, ptr, result, slice};
+
+    pub mod collections {
+        pub use alloc::collections::*;
+    }
+
+    pub mod rc {
+        pub use alloc::rc::*;
+    }
+}
+
+/// The items of the `std` prelude not in the `core` prelude.
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
@@ -0,0 +1,385 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{BasicBlock, Function, Instruction};
+use std::ops;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Stable handle to an instruction stored in a `FunctionArena`.
+///
+/// Handles stay valid across insertions and removals of other
+/// instructions, and are never reused for different instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InstructionId(u32);
+
+impl InstructionId {
+    /// Returns the index of this handle in its arena.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    inst: Option<Instruction>,
+    block: usize,
+    prev: Option<InstructionId>,
+    next: Option<InstructionId>,
+}
+
+#[derive(Clone, Debug)]
+struct Block {
+    label: Option<Instruction>,
+    first: Option<InstructionId>,
+    last: Option<InstructionId>,
+}
+
+/// Arena-based storage for the instructions of a function.
+///
+/// Instructions of basic blocks are addressed by `InstructionId` handles
+/// instead of positions in vectors, so passes can hold on to instructions
+/// while inserting or removing others around them. Each basic block keeps
+/// its instructions in a doubly linked list, making insertions and removals
+/// constant time.
+///
+/// Basic blocks are addressed by their indices, as in `Function`. Convert
+/// back with `into_function()` once done editing.
+#[derive(Clone, Debug, Default)]
+pub struct FunctionArena {
+    /// First (defining) instruction in this function.
+    pub def: Option<Instruction>,
+    /// Last (ending) instruction in this function.
+    pub end: Option<Instruction>,
+    /// Function parameters.
+    pub parameters: Vec<Instruction>,
+    nodes: Vec<Node>,
+    blocks: Vec<Block>,
+    len: usize,
+}
+
+impl FunctionArena {
+    /// Moves the instructions of the given function into a new arena.
+    pub fn new(function: Function) -> Self {
+        let mut arena = FunctionArena {
+            def: function.def,
+            end: function.end,
+            parameters: function.parameters,
+            nodes: vec![],
+            blocks: vec![],
+            len: 0,
+        };
+        for bb in function.basic_blocks {
+            let block = arena.push_block(bb.label);
+            for inst in bb.instructions {
+                arena.push_back(block, inst);
+            }
+        }
+        arena
+    }
+
+    /// Moves the instructions back into a `Function`, in their current
+    /// order.
+    pub fn into_function(self) -> Function {
+        let FunctionArena { def, end, parameters, mut nodes, blocks, .. } = self;
+        let basic_blocks = blocks
+            .into_iter()
+            .map(|block| {
+                let mut instructions = vec![];
+                let mut cursor = block.first;
+                while let Some(id) = cursor {
+                    let node = &mut nodes[id.index()];
+                    instructions.extend(node.inst.take());
+                    cursor = node.next;
+                }
+                BasicBlock { label: block.label, instructions }
+            })
+            .collect();
+        Function { def, end, parameters, basic_blocks }
+    }
+
+    /// Returns the number of instructions in all basic blocks, excluding
+    /// labels.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no instructions in any basic block.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of basic blocks.
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the label of the given basic block.
+    pub fn label(&self, block: usize) -> Option<&Instruction> {
+        self.blocks[block].label.as_ref()
+    }
+
+    /// Appends a new basic block with the given label, returning its index.
+    pub fn push_block(&mut self, label: Option<Instruction>) -> usize {
+        self.blocks.push(Block { label, first: None, last: None });
+        self.blocks.len() - 1
+    }
+
+    /// Returns true if `id` refers to an instruction still in this arena.
+    pub fn contains(&self, id: InstructionId) -> bool {
+        self.nodes.get(id.index()).is_some_and(|node| node.inst.is_some())
+    }
+
+    /// Returns the instruction referred to by `id`, unless it was removed.
+    pub fn get(&self, id: InstructionId) -> Option<&Instruction> {
+        self.nodes.get(id.index()).and_then(|node| node.inst.as_ref())
+    }
+
+    /// Returns the instruction referred to by `id` mutably, unless it was
+    /// removed.
+    pub fn get_mut(&mut self, id: InstructionId) -> Option<&mut Instruction> {
+        self.nodes.get_mut(id.index()).and_then(|node| node.inst.as_mut())
+    }
+
+    /// Returns the index of the basic block containing the instruction
+    /// referred to by `id`, unless it was removed.
+    pub fn block_of(&self, id: InstructionId) -> Option<usize> {
+        self.live(id).map(|node| node.block)
+    }
+
+    /// Returns the instruction following the one referred to by `id` in
+    /// its basic block.
+    pub fn next(&self, id: InstructionId) -> Option<InstructionId> {
+        self.live(id).and_then(|node| node.next)
+    }
+
+    /// Returns the instruction preceding the one referred to by `id` in
+    /// its basic block.
+    pub fn prev(&self, id: InstructionId) -> Option<InstructionId> {
+        self.live(id).and_then(|node| node.prev)
+    }
+
+    /// Returns the first instruction of the given basic block.
+    pub fn first(&self, block: usize) -> Option<InstructionId> {
+        self.blocks[block].first
+    }
+
+    /// Returns the last instruction of the given basic block, which is its
+    /// terminator in valid functions.
+    pub fn last(&self, block: usize) -> Option<InstructionId> {
+        self.blocks[block].last
+    }
+
+    /// Returns an iterator over the handles of the instructions in the
+    /// given basic block, in order.
+    pub fn block_iter(&self, block: usize) -> impl Iterator<Item = InstructionId> + '_ {
+        let mut cursor = self.blocks[block].first;
+        ::std::iter::from_fn(move || {
+            let id = cursor?;
+            cursor = self.nodes[id.index()].next;
+            Some(id)
+        })
+    }
+
+    /// Returns an iterator over the handles of all instructions in basic
+    /// blocks, in order.
+    pub fn iter(&self) -> impl Iterator<Item = InstructionId> + '_ {
+        (0..self.blocks.len()).flat_map(move |block| self.block_iter(block))
+    }
+
+    /// Appends an instruction to the given basic block.
+    pub fn push_back(&mut self, block: usize, inst: Instruction) -> InstructionId {
+        let last = self.blocks[block].last;
+        let id = self.alloc(inst, block, last, None);
+        self.link(block, last, id, None);
+        id
+    }
+
+    /// Prepends an instruction to the given basic block.
+    pub fn push_front(&mut self, block: usize, inst: Instruction) -> InstructionId {
+        let first = self.blocks[block].first;
+        let id = self.alloc(inst, block, None, first);
+        self.link(block, None, id, first);
+        id
+    }
+
+    /// Inserts an instruction before the one referred to by `anchor`.
+    ///
+    /// Panics if `anchor` was removed.
+    pub fn insert_before(&mut self, anchor: InstructionId, inst: Instruction) -> InstructionId {
+        let (block, prev) = {
+            let node = self.live(anchor).expect("inserting before a removed instruction");
+            (node.block, node.prev)
+        };
+        let id = self.alloc(inst, block, prev, Some(anchor));
+        self.link(block, prev, id, Some(anchor));
+        id
+    }
+
+    /// Inserts an instruction after the one referred to by `anchor`.
+    ///
+    /// Panics if `anchor` was removed.
+    pub fn insert_after(&mut self, anchor: InstructionId, inst: Instruction) -> InstructionId {
+        let (block, next) = {
+            let node = self.live(anchor).expect("inserting after a removed instruction");
+            (node.block, node.next)
+        };
+        let id = self.alloc(inst, block, Some(anchor), next);
+        self.link(block, Some(anchor), id, next);
+        id
+    }
+
+    /// Removes the instruction referred to by `id` and returns it, unless
+    /// it was already removed.
+    ///
+    /// The handle is not reused for instructions inserted later.
+    pub fn remove(&mut self, id: InstructionId) -> Option<Instruction> {
+        let (inst, block, prev, next) = {
+            let node = self.nodes.get_mut(id.index())?;
+            (node.inst.take()?, node.block, node.prev.take(), node.next.take())
+        };
+        match prev {
+            Some(prev) => self.nodes[prev.index()].next = next,
+            None => self.blocks[block].first = next,
+        }
+        match next {
+            Some(next) => self.nodes[next.index()].prev = prev,
+            None => self.blocks[block].last = prev,
+        }
+        self.len -= 1;
+        Some(inst)
+    }
+
+    /// Removes all instructions for which `keep` returns false.
+    pub fn retain<F: FnMut(&Instruction) -> bool>(&mut self, mut keep: F) {
+        let removed: Vec<InstructionId> =
+            self.iter().filter(|&id| !keep(&self[id])).collect();
+        for id in removed {
+            self.remove(id);
+        }
+    }
+
+    fn live(&self, id: InstructionId) -> Option<&Node> {
+        self.nodes.get(id.index()).filter(|node| node.inst.is_some())
+    }
+
+    fn alloc(&mut self,
+             inst: Instruction,
+             block: usize,
+             prev: Option<InstructionId>,
+             next: Option<InstructionId>)
+             -> InstructionId {
+        let id = InstructionId(self.nodes.len() as u32);
+        self.nodes.push(Node { inst: Some(inst), block, prev, next });
+        self.len += 1;
+        id
+    }
+
+    fn link(&mut self,
+            block: usize,
+            prev: Option<InstructionId>,
+            id: InstructionId,
+            next: Option<InstructionId>) {
+        match prev {
+            Some(prev) => self.nodes[prev.index()].next = Some(id),
+            None => self.blocks[block].first = Some(id),
+        }
+        match next {
+            Some(next) => self.nodes[next.index()].prev = Some(id),
+            None => self.blocks[block].last = Some(id),
+        }
+    }
+}
+
+impl ops::Index<InstructionId> for FunctionArena {
+    type Output = Instruction;
+
+    fn index(&self, id: InstructionId) -> &Instruction {
+        self.get(id).expect("accessing a removed instruction")
+    }
+}
+
+impl ops::IndexMut<InstructionId> for FunctionArena {
+    fn index_mut(&mut self, id: InstructionId) -> &mut Instruction {
+        self.get_mut(id).expect("accessing a removed instruction")
+    }
+}
+
+impl From<Function> for FunctionArena {
+    fn from(function: Function) -> Self {
+        FunctionArena::new(function)
+    }
+}
+
+impl From<FunctionArena> for Function {
+    fn from(arena: FunctionArena) -> Self {
+        arena.into_function()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::FunctionArena;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_stable_handles() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let one = b.constant_u32(int, 1);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.iadd(int, None, one, one).unwrap();
+        b.isub(int, None, one, one).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        let mut arena = FunctionArena::new(m.functions.pop().unwrap());
+        assert_eq!(3, arena.len());
+        let ids: Vec<_> = arena.block_iter(0).collect();
+        let (add, sub, ret) = (ids[0], ids[1], ids[2]);
+
+        let mul = arena.insert_after(add, mr::Instruction::new(
+            spirv::Op::IMul, Some(int), Some(10), vec![mr::Operand::IdRef(one); 2]));
+        assert_eq!(Some(spirv::Op::ISub), arena.remove(sub).map(|inst| inst.class.opcode));
+        assert!(arena.remove(sub).is_none());
+        assert!(!arena.contains(sub));
+        let nop = mr::Instruction::new(spirv::Op::Nop, None, None, vec![]);
+        arena.push_front(0, nop.clone());
+        let nop = arena.insert_before(ret, nop);
+        assert_eq!(Some(nop), arena.next(mul));
+        assert_eq!(Some(ret), arena.last(0));
+        arena[add].result_id = Some(11);
+        arena.retain(|inst| inst.class.opcode != spirv::Op::Nop);
+        assert_eq!(Some(0), arena.block_of(mul));
+        assert_eq!(None, arena.block_of(nop));
+        assert_eq!(3, arena.len());
+
+        let function = arena.into_function();
+        let disas: Vec<String> =
+            function.basic_blocks[0].instructions.iter().map(|inst| inst.disassemble()).collect();
+        assert_eq!(disas,
+                   vec!["%11 = OpIAdd  %2  %3 %3",
+                        "%10 = OpIMul  %2  %3 %3",
+                        "OpReturn"]);
+    }
+}
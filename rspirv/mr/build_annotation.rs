@@ -19,21 +19,21 @@
 impl Builder {
     /// Appends an OpDecorate instruction.
     pub fn decorate<T: AsRef<[mr::Operand]>>(&mut self, target: spirv::Word, decoration: spirv::Decoration, additional_params: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::Decorate, None, None, vec![mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Decorate, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         self.module.annotations.push(inst);
     }
 
     /// Appends an OpMemberDecorate instruction.
     pub fn member_decorate<T: AsRef<[mr::Operand]>>(&mut self, structure_type: spirv::Word, member: u32, decoration: spirv::Decoration, additional_params: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::MemberDecorate, None, None, vec![mr::Operand::IdRef(structure_type), mr::Operand::LiteralInt32(member), mr::Operand::Decoration(decoration)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::MemberDecorate, None, None, mr::Operands::from([mr::Operand::IdRef(structure_type), mr::Operand::LiteralInt32(member), mr::Operand::Decoration(decoration)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         self.module.annotations.push(inst);
     }
 
     /// Appends an OpGroupDecorate instruction.
     pub fn group_decorate<T: AsRef<[spirv::Word]>>(&mut self, decoration_group: spirv::Word, targets: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::GroupDecorate, None, None, vec![mr::Operand::IdRef(decoration_group)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupDecorate, None, None, mr::Operands::from([mr::Operand::IdRef(decoration_group)]));
         for v in targets.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
@@ -42,7 +42,7 @@ impl Builder {
 
     /// Appends an OpGroupMemberDecorate instruction.
     pub fn group_member_decorate<T: AsRef<[(spirv::Word, u32)]>>(&mut self, decoration_group: spirv::Word, targets: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::GroupMemberDecorate, None, None, vec![mr::Operand::IdRef(decoration_group)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupMemberDecorate, None, None, mr::Operands::from([mr::Operand::IdRef(decoration_group)]));
         for v in targets.as_ref() {
             inst.operands.push(mr::Operand::IdRef(v.0));
             inst.operands.push(mr::Operand::LiteralInt32(v.1));
@@ -52,21 +52,21 @@ impl Builder {
 
     /// Appends an OpDecorateId instruction.
     pub fn decorate_id<T: AsRef<[mr::Operand]>>(&mut self, target: spirv::Word, decoration: spirv::Decoration, additional_params: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::DecorateId, None, None, vec![mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::DecorateId, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         self.module.annotations.push(inst);
     }
 
     /// Appends an OpDecorateStringGOOGLE instruction.
     pub fn decorate_string_google<T: AsRef<[mr::Operand]>>(&mut self, target: spirv::Word, decoration: spirv::Decoration, additional_params: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::DecorateStringGOOGLE, None, None, vec![mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::DecorateStringGOOGLE, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         self.module.annotations.push(inst);
     }
 
     /// Appends an OpMemberDecorateStringGOOGLE instruction.
     pub fn member_decorate_string_google<T: AsRef<[mr::Operand]>>(&mut self, struct_type: spirv::Word, member: u32, decoration: spirv::Decoration, additional_params: T) {
-        let mut inst = mr::Instruction::new(spirv::Op::MemberDecorateStringGOOGLE, None, None, vec![mr::Operand::IdRef(struct_type), mr::Operand::LiteralInt32(member), mr::Operand::Decoration(decoration)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::MemberDecorateStringGOOGLE, None, None, mr::Operands::from([mr::Operand::IdRef(struct_type), mr::Operand::LiteralInt32(member), mr::Operand::Decoration(decoration)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         self.module.annotations.push(inst);
     }
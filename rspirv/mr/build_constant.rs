@@ -20,7 +20,7 @@ impl Builder {
     /// Appends an OpConstantTrue instruction.
     pub fn constant_true(&mut self, result_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::ConstantTrue, Some(result_type), Some(id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConstantTrue, Some(result_type), Some(id), mr::Operands::from([]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -28,7 +28,7 @@ impl Builder {
     /// Appends an OpConstantFalse instruction.
     pub fn constant_false(&mut self, result_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::ConstantFalse, Some(result_type), Some(id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConstantFalse, Some(result_type), Some(id), mr::Operands::from([]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -36,7 +36,7 @@ impl Builder {
     /// Appends an OpConstantComposite instruction.
     pub fn constant_composite<T: AsRef<[spirv::Word]>>(&mut self, result_type: spirv::Word, constituents: T) -> spirv::Word {
         let id = self.id();
-        let mut inst = mr::Instruction::new(spirv::Op::ConstantComposite, Some(result_type), Some(id), vec![]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ConstantComposite, Some(result_type), Some(id), mr::Operands::from([]));
         for v in constituents.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
@@ -47,7 +47,7 @@ impl Builder {
     /// Appends an OpConstantSampler instruction.
     pub fn constant_sampler(&mut self, result_type: spirv::Word, sampler_addressing_mode: spirv::SamplerAddressingMode, param: u32, sampler_filter_mode: spirv::SamplerFilterMode) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::ConstantSampler, Some(result_type), Some(id), vec![mr::Operand::SamplerAddressingMode(sampler_addressing_mode), mr::Operand::LiteralInt32(param), mr::Operand::SamplerFilterMode(sampler_filter_mode)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConstantSampler, Some(result_type), Some(id), mr::Operands::from([mr::Operand::SamplerAddressingMode(sampler_addressing_mode), mr::Operand::LiteralInt32(param), mr::Operand::SamplerFilterMode(sampler_filter_mode)]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -55,7 +55,7 @@ impl Builder {
     /// Appends an OpConstantNull instruction.
     pub fn constant_null(&mut self, result_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::ConstantNull, Some(result_type), Some(id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConstantNull, Some(result_type), Some(id), mr::Operands::from([]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -63,7 +63,7 @@ impl Builder {
     /// Appends an OpSpecConstantTrue instruction.
     pub fn spec_constant_true(&mut self, result_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::SpecConstantTrue, Some(result_type), Some(id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SpecConstantTrue, Some(result_type), Some(id), mr::Operands::from([]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -71,7 +71,7 @@ impl Builder {
     /// Appends an OpSpecConstantFalse instruction.
     pub fn spec_constant_false(&mut self, result_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::SpecConstantFalse, Some(result_type), Some(id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SpecConstantFalse, Some(result_type), Some(id), mr::Operands::from([]));
         self.module.types_global_values.push(inst);
         id
     }
@@ -79,7 +79,7 @@ impl Builder {
     /// Appends an OpSpecConstantComposite instruction.
     pub fn spec_constant_composite<T: AsRef<[spirv::Word]>>(&mut self, result_type: spirv::Word, constituents: T) -> spirv::Word {
         let id = self.id();
-        let mut inst = mr::Instruction::new(spirv::Op::SpecConstantComposite, Some(result_type), Some(id), vec![]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::SpecConstantComposite, Some(result_type), Some(id), mr::Operands::from([]));
         for v in constituents.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
@@ -87,18 +87,10 @@ impl Builder {
         id
     }
 
-    /// Appends an OpSpecConstantOp instruction.
-    pub fn spec_constant_op(&mut self, result_type: spirv::Word, opcode: spirv::Op) -> spirv::Word {
-        let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::SpecConstantOp, Some(result_type), Some(id), vec![mr::Operand::LiteralSpecConstantOpInteger(opcode)]);
-        self.module.types_global_values.push(inst);
-        id
-    }
-
     /// Appends an OpConstantPipeStorage instruction.
     pub fn constant_pipe_storage(&mut self, result_type: spirv::Word, packet_size: u32, packet_alignment: u32, capacity: u32) -> spirv::Word {
         let id = self.id();
-        let inst = mr::Instruction::new(spirv::Op::ConstantPipeStorage, Some(result_type), Some(id), vec![mr::Operand::LiteralInt32(packet_size), mr::Operand::LiteralInt32(packet_alignment), mr::Operand::LiteralInt32(capacity)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConstantPipeStorage, Some(result_type), Some(id), mr::Operands::from([mr::Operand::LiteralInt32(packet_size), mr::Operand::LiteralInt32(packet_alignment), mr::Operand::LiteralInt32(capacity)]));
         self.module.types_global_values.push(inst);
         id
     }
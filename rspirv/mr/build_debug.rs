@@ -18,14 +18,14 @@
 
 impl Builder {
     /// Appends an OpSourceContinued instruction.
-    pub fn source_continued<T: Into<String>>(&mut self, continued_source: T) {
-        let inst = mr::Instruction::new(spirv::Op::SourceContinued, None, None, vec![mr::Operand::LiteralString(continued_source.into())]);
+    pub fn source_continued<T: Into<mr::Symbol>>(&mut self, continued_source: T) {
+        let inst = mr::Instruction::with_operands(spirv::Op::SourceContinued, None, None, mr::Operands::from([mr::Operand::LiteralString(continued_source.into())]));
         self.module.debugs.push(inst);
     }
 
     /// Appends an OpSource instruction.
-    pub fn source<T: Into<String>>(&mut self, source_language: spirv::SourceLanguage, version: u32, file: Option<spirv::Word>, source: Option<T>) {
-        let mut inst = mr::Instruction::new(spirv::Op::Source, None, None, vec![mr::Operand::SourceLanguage(source_language), mr::Operand::LiteralInt32(version)]);
+    pub fn source<T: Into<mr::Symbol>>(&mut self, source_language: spirv::SourceLanguage, version: u32, file: Option<spirv::Word>, source: Option<T>) {
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Source, None, None, mr::Operands::from([mr::Operand::SourceLanguage(source_language), mr::Operand::LiteralInt32(version)]));
         if let Some(v) = file {
             inst.operands.push(mr::Operand::IdRef(v));
         };
@@ -36,26 +36,26 @@ impl Builder {
     }
 
     /// Appends an OpSourceExtension instruction.
-    pub fn source_extension<T: Into<String>>(&mut self, extension: T) {
-        let inst = mr::Instruction::new(spirv::Op::SourceExtension, None, None, vec![mr::Operand::LiteralString(extension.into())]);
+    pub fn source_extension<T: Into<mr::Symbol>>(&mut self, extension: T) {
+        let inst = mr::Instruction::with_operands(spirv::Op::SourceExtension, None, None, mr::Operands::from([mr::Operand::LiteralString(extension.into())]));
         self.module.debugs.push(inst);
     }
 
     /// Appends an OpName instruction.
-    pub fn name<T: Into<String>>(&mut self, target: spirv::Word, name: T) {
-        let inst = mr::Instruction::new(spirv::Op::Name, None, None, vec![mr::Operand::IdRef(target), mr::Operand::LiteralString(name.into())]);
+    pub fn name<T: Into<mr::Symbol>>(&mut self, target: spirv::Word, name: T) {
+        let inst = mr::Instruction::with_operands(spirv::Op::Name, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::LiteralString(name.into())]));
         self.module.debugs.push(inst);
     }
 
     /// Appends an OpMemberName instruction.
-    pub fn member_name<T: Into<String>>(&mut self, target_type: spirv::Word, member: u32, name: T) {
-        let inst = mr::Instruction::new(spirv::Op::MemberName, None, None, vec![mr::Operand::IdRef(target_type), mr::Operand::LiteralInt32(member), mr::Operand::LiteralString(name.into())]);
+    pub fn member_name<T: Into<mr::Symbol>>(&mut self, target_type: spirv::Word, member: u32, name: T) {
+        let inst = mr::Instruction::with_operands(spirv::Op::MemberName, None, None, mr::Operands::from([mr::Operand::IdRef(target_type), mr::Operand::LiteralInt32(member), mr::Operand::LiteralString(name.into())]));
         self.module.debugs.push(inst);
     }
 
     /// Appends an OpModuleProcessed instruction.
-    pub fn module_processed<T: Into<String>>(&mut self, process: T) {
-        let inst = mr::Instruction::new(spirv::Op::ModuleProcessed, None, None, vec![mr::Operand::LiteralString(process.into())]);
+    pub fn module_processed<T: Into<mr::Symbol>>(&mut self, process: T) {
+        let inst = mr::Instruction::with_operands(spirv::Op::ModuleProcessed, None, None, mr::Operands::from([mr::Operand::LiteralString(process.into())]));
         self.module.debugs.push(inst);
     }
 }
\ No newline at end of file
@@ -22,7 +22,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::Nop, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Nop, None, None, mr::Operands::from([]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -35,10 +35,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ExtInst, Some(result_type), Some(_id), vec![mr::Operand::IdRef(set), mr::Operand::LiteralExtInstInteger(instruction)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ExtInst, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(set), mr::Operand::LiteralExtInstInteger(instruction)]));
         for v in operands.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -52,10 +57,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::FunctionCall, Some(result_type), Some(_id), vec![mr::Operand::IdRef(function)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::FunctionCall, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(function)]));
         for v in arguments.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -69,7 +79,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageTexelPointer, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(sample)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageTexelPointer, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(sample)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -83,11 +98,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::Load, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Load, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
         if let Some(v) = memory_access {
             inst.operands.push(mr::Operand::MemoryAccess(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -97,7 +117,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let mut inst = mr::Instruction::new(spirv::Op::Store, None, None, vec![mr::Operand::IdRef(pointer), mr::Operand::IdRef(object)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Store, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdRef(object)]));
         if let Some(v) = memory_access {
             inst.operands.push(mr::Operand::MemoryAccess(v));
         };
@@ -110,7 +130,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let mut inst = mr::Instruction::new(spirv::Op::CopyMemory, None, None, vec![mr::Operand::IdRef(target), mr::Operand::IdRef(source)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CopyMemory, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::IdRef(source)]));
         if let Some(v) = memory_access {
             inst.operands.push(mr::Operand::MemoryAccess(v));
         };
@@ -123,7 +143,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let mut inst = mr::Instruction::new(spirv::Op::CopyMemorySized, None, None, vec![mr::Operand::IdRef(target), mr::Operand::IdRef(source), mr::Operand::IdRef(size)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CopyMemorySized, None, None, mr::Operands::from([mr::Operand::IdRef(target), mr::Operand::IdRef(source), mr::Operand::IdRef(size)]));
         if let Some(v) = memory_access {
             inst.operands.push(mr::Operand::MemoryAccess(v));
         };
@@ -140,10 +160,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::AccessChain, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::AccessChain, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -157,10 +182,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::InBoundsAccessChain, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::InBoundsAccessChain, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -174,10 +204,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::PtrAccessChain, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(element)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::PtrAccessChain, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(element)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -191,7 +226,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ArrayLength, Some(result_type), Some(_id), vec![mr::Operand::IdRef(structure), mr::Operand::LiteralInt32(array_member)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ArrayLength, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(structure), mr::Operand::LiteralInt32(array_member)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -205,7 +245,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GenericPtrMemSemantics, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GenericPtrMemSemantics, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -219,10 +264,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::InBoundsPtrAccessChain, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(element)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::InBoundsPtrAccessChain, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(element)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -236,7 +286,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::VectorExtractDynamic, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector), mr::Operand::IdRef(index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::VectorExtractDynamic, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector), mr::Operand::IdRef(index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -250,7 +305,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::VectorInsertDynamic, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector), mr::Operand::IdRef(component), mr::Operand::IdRef(index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::VectorInsertDynamic, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector), mr::Operand::IdRef(component), mr::Operand::IdRef(index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -264,10 +324,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::VectorShuffle, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::VectorShuffle, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]));
         for v in components.as_ref() {
             inst.operands.push(mr::Operand::LiteralInt32(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -281,10 +346,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::CompositeConstruct, Some(result_type), Some(_id), vec![]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CompositeConstruct, Some(result_type), Some(_id), mr::Operands::from([]));
         for v in constituents.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -298,10 +368,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::CompositeExtract, Some(result_type), Some(_id), vec![mr::Operand::IdRef(composite)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CompositeExtract, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(composite)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::LiteralInt32(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -315,10 +390,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::CompositeInsert, Some(result_type), Some(_id), vec![mr::Operand::IdRef(object), mr::Operand::IdRef(composite)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CompositeInsert, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(object), mr::Operand::IdRef(composite)]));
         for v in indexes.as_ref() {
             inst.operands.push(mr::Operand::LiteralInt32(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -332,7 +412,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::CopyObject, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CopyObject, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -346,7 +431,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Transpose, Some(result_type), Some(_id), vec![mr::Operand::IdRef(matrix)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Transpose, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(matrix)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -360,7 +450,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SampledImage, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(sampler)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SampledImage, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(sampler)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -374,11 +469,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -392,8 +492,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -407,11 +512,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleDrefImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleDrefImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -425,8 +535,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleDrefExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleDrefExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -440,11 +555,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleProjImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleProjImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -458,8 +578,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleProjExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleProjExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -473,11 +598,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleProjDrefImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleProjDrefImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -491,8 +621,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSampleProjDrefExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSampleProjDrefExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -506,11 +641,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageFetch, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageFetch, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -524,11 +664,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageGather, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(component)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageGather, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(component)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -542,11 +687,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageDrefGather, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageDrefGather, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -560,11 +710,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageRead, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageRead, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -574,7 +729,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let mut inst = mr::Instruction::new(spirv::Op::ImageWrite, None, None, vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(texel)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageWrite, None, None, mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(texel)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
@@ -591,7 +746,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Image, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Image, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -605,7 +765,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQueryFormat, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQueryFormat, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -619,7 +784,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQueryOrder, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQueryOrder, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -633,7 +803,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQuerySizeLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(level_of_detail)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQuerySizeLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(level_of_detail)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -647,7 +822,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQuerySize, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQuerySize, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -661,7 +841,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQueryLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQueryLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -675,7 +860,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQueryLevels, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQueryLevels, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -689,7 +879,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageQuerySamples, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageQuerySamples, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -703,7 +898,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertFToU, Some(result_type), Some(_id), vec![mr::Operand::IdRef(float_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertFToU, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(float_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -717,7 +917,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertFToS, Some(result_type), Some(_id), vec![mr::Operand::IdRef(float_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertFToS, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(float_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -731,7 +936,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertSToF, Some(result_type), Some(_id), vec![mr::Operand::IdRef(signed_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertSToF, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(signed_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -745,7 +955,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertUToF, Some(result_type), Some(_id), vec![mr::Operand::IdRef(unsigned_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertUToF, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(unsigned_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -759,7 +974,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UConvert, Some(result_type), Some(_id), vec![mr::Operand::IdRef(unsigned_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UConvert, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(unsigned_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -773,7 +993,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SConvert, Some(result_type), Some(_id), vec![mr::Operand::IdRef(signed_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SConvert, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(signed_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -787,7 +1012,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FConvert, Some(result_type), Some(_id), vec![mr::Operand::IdRef(float_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FConvert, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(float_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -801,7 +1031,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::QuantizeToF16, Some(result_type), Some(_id), vec![mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::QuantizeToF16, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -815,7 +1050,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertPtrToU, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertPtrToU, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -829,7 +1069,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SatConvertSToU, Some(result_type), Some(_id), vec![mr::Operand::IdRef(signed_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SatConvertSToU, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(signed_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -843,7 +1088,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SatConvertUToS, Some(result_type), Some(_id), vec![mr::Operand::IdRef(unsigned_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SatConvertUToS, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(unsigned_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -857,7 +1107,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ConvertUToPtr, Some(result_type), Some(_id), vec![mr::Operand::IdRef(integer_value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertUToPtr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(integer_value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -871,7 +1126,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::PtrCastToGeneric, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::PtrCastToGeneric, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -885,7 +1145,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GenericCastToPtr, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GenericCastToPtr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -899,7 +1164,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GenericCastToPtrExplicit, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::StorageClass(storage)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GenericCastToPtrExplicit, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::StorageClass(storage)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -913,7 +1183,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Bitcast, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Bitcast, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -927,7 +1202,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SNegate, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SNegate, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -941,7 +1221,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FNegate, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FNegate, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -955,7 +1240,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IAdd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -969,7 +1259,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FAdd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -983,7 +1278,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ISub, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ISub, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -997,7 +1297,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FSub, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FSub, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1011,7 +1316,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IMul, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IMul, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1025,7 +1335,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FMul, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FMul, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1039,7 +1354,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UDiv, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UDiv, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1053,7 +1373,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SDiv, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SDiv, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1067,7 +1392,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FDiv, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FDiv, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1081,7 +1411,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UMod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UMod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1095,7 +1430,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SRem, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SRem, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1109,7 +1449,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SMod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SMod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1123,7 +1468,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FRem, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FRem, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1137,7 +1487,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FMod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FMod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1151,7 +1506,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::VectorTimesScalar, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector), mr::Operand::IdRef(scalar)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::VectorTimesScalar, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector), mr::Operand::IdRef(scalar)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1165,7 +1525,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::MatrixTimesScalar, Some(result_type), Some(_id), vec![mr::Operand::IdRef(matrix), mr::Operand::IdRef(scalar)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::MatrixTimesScalar, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(matrix), mr::Operand::IdRef(scalar)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1179,7 +1544,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::VectorTimesMatrix, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector), mr::Operand::IdRef(matrix)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::VectorTimesMatrix, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector), mr::Operand::IdRef(matrix)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1193,7 +1563,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::MatrixTimesVector, Some(result_type), Some(_id), vec![mr::Operand::IdRef(matrix), mr::Operand::IdRef(vector)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::MatrixTimesVector, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(matrix), mr::Operand::IdRef(vector)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1207,7 +1582,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::MatrixTimesMatrix, Some(result_type), Some(_id), vec![mr::Operand::IdRef(left_matrix), mr::Operand::IdRef(right_matrix)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::MatrixTimesMatrix, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(left_matrix), mr::Operand::IdRef(right_matrix)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1221,7 +1601,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::OuterProduct, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::OuterProduct, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1235,7 +1620,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Dot, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Dot, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector_1), mr::Operand::IdRef(vector_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1249,7 +1639,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IAddCarry, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IAddCarry, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1263,7 +1658,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ISubBorrow, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ISubBorrow, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1277,7 +1677,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UMulExtended, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UMulExtended, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1291,7 +1696,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SMulExtended, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SMulExtended, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1305,7 +1715,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Any, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Any, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1319,7 +1734,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::All, Some(result_type), Some(_id), vec![mr::Operand::IdRef(vector)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::All, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(vector)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1333,7 +1753,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsNan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsNan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1347,7 +1772,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsInf, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsInf, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1361,7 +1791,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsFinite, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsFinite, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1375,7 +1810,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsNormal, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsNormal, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1389,7 +1829,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SignBitSet, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SignBitSet, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1403,7 +1848,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LessOrGreater, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x), mr::Operand::IdRef(y)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LessOrGreater, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x), mr::Operand::IdRef(y)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1417,7 +1867,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Ordered, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x), mr::Operand::IdRef(y)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Ordered, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x), mr::Operand::IdRef(y)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1431,7 +1886,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Unordered, Some(result_type), Some(_id), vec![mr::Operand::IdRef(x), mr::Operand::IdRef(y)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Unordered, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(x), mr::Operand::IdRef(y)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1445,7 +1905,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LogicalEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LogicalEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1459,7 +1924,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LogicalNotEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LogicalNotEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1473,7 +1943,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LogicalOr, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LogicalOr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1487,7 +1962,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LogicalAnd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LogicalAnd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1501,7 +1981,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::LogicalNot, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LogicalNot, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1515,7 +2000,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Select, Some(result_type), Some(_id), vec![mr::Operand::IdRef(condition), mr::Operand::IdRef(object_1), mr::Operand::IdRef(object_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Select, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(condition), mr::Operand::IdRef(object_1), mr::Operand::IdRef(object_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1529,7 +2019,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1543,7 +2038,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::INotEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::INotEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1557,7 +2057,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UGreaterThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UGreaterThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1571,7 +2076,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SGreaterThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SGreaterThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1585,7 +2095,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::UGreaterThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::UGreaterThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1599,7 +2114,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SGreaterThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SGreaterThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1613,7 +2133,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ULessThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ULessThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1627,7 +2152,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SLessThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SLessThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1641,7 +2171,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ULessThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ULessThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1655,7 +2190,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SLessThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SLessThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1669,7 +2209,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1683,7 +2228,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1697,7 +2247,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdNotEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdNotEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1711,7 +2266,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordNotEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordNotEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1725,7 +2285,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdLessThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdLessThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1739,7 +2304,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordLessThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordLessThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1753,7 +2323,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdGreaterThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdGreaterThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1767,7 +2342,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordGreaterThan, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordGreaterThan, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1781,7 +2361,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdLessThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdLessThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1795,7 +2380,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordLessThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordLessThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1809,7 +2399,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FOrdGreaterThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FOrdGreaterThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1823,7 +2418,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FUnordGreaterThanEqual, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FUnordGreaterThanEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1837,7 +2437,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ShiftRightLogical, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ShiftRightLogical, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1851,7 +2456,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ShiftRightArithmetic, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ShiftRightArithmetic, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1865,7 +2475,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ShiftLeftLogical, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ShiftLeftLogical, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(shift)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1879,7 +2494,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitwiseOr, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitwiseOr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1893,7 +2513,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitwiseXor, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitwiseXor, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1907,7 +2532,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitwiseAnd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitwiseAnd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1921,7 +2551,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Not, Some(result_type), Some(_id), vec![mr::Operand::IdRef(operand)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Not, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(operand)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1935,7 +2570,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitFieldInsert, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(insert), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitFieldInsert, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(insert), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1949,7 +2589,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitFieldSExtract, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitFieldSExtract, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1963,7 +2608,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitFieldUExtract, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitFieldUExtract, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base), mr::Operand::IdRef(offset), mr::Operand::IdRef(count)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1977,7 +2627,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitReverse, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitReverse, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -1991,7 +2646,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BitCount, Some(result_type), Some(_id), vec![mr::Operand::IdRef(base)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BitCount, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(base)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2005,7 +2665,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdx, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdx, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2019,7 +2684,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdy, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdy, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2033,7 +2703,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::Fwidth, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Fwidth, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2047,7 +2722,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdxFine, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdxFine, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2061,7 +2741,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdyFine, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdyFine, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2075,7 +2760,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FwidthFine, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FwidthFine, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2089,7 +2779,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdxCoarse, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdxCoarse, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2103,7 +2798,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::DPdyCoarse, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::DPdyCoarse, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2117,7 +2817,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FwidthCoarse, Some(result_type), Some(_id), vec![mr::Operand::IdRef(p)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FwidthCoarse, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(p)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2127,7 +2832,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::EmitVertex, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::EmitVertex, None, None, mr::Operands::from([]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2136,7 +2841,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::EndPrimitive, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::EndPrimitive, None, None, mr::Operands::from([]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2145,7 +2850,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::EmitStreamVertex, None, None, vec![mr::Operand::IdRef(stream)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::EmitStreamVertex, None, None, mr::Operands::from([mr::Operand::IdRef(stream)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2154,7 +2859,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::EndStreamPrimitive, None, None, vec![mr::Operand::IdRef(stream)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::EndStreamPrimitive, None, None, mr::Operands::from([mr::Operand::IdRef(stream)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2163,7 +2868,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::ControlBarrier, None, None, vec![mr::Operand::IdScope(execution), mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ControlBarrier, None, None, mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2172,7 +2877,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::MemoryBarrier, None, None, vec![mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::MemoryBarrier, None, None, mr::Operands::from([mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2185,7 +2890,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicLoad, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicLoad, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2195,7 +2905,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::AtomicStore, None, None, vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicStore, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2208,7 +2918,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicExchange, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicExchange, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2222,7 +2937,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicCompareExchange, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(equal), mr::Operand::IdMemorySemantics(unequal), mr::Operand::IdRef(value), mr::Operand::IdRef(comparator)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicCompareExchange, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(equal), mr::Operand::IdMemorySemantics(unequal), mr::Operand::IdRef(value), mr::Operand::IdRef(comparator)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2236,7 +2956,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicCompareExchangeWeak, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(equal), mr::Operand::IdMemorySemantics(unequal), mr::Operand::IdRef(value), mr::Operand::IdRef(comparator)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicCompareExchangeWeak, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(equal), mr::Operand::IdMemorySemantics(unequal), mr::Operand::IdRef(value), mr::Operand::IdRef(comparator)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2250,7 +2975,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicIIncrement, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicIIncrement, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2264,7 +2994,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicIDecrement, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicIDecrement, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2278,7 +3013,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicIAdd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicIAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2292,7 +3032,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicISub, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicISub, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2306,7 +3051,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicSMin, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicSMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2320,7 +3070,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicUMin, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicUMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2334,7 +3089,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicSMax, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicSMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2348,7 +3108,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicUMax, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicUMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2362,7 +3127,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicAnd, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicAnd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2376,7 +3146,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicOr, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicOr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2390,7 +3165,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicXor, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicXor, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2404,11 +3184,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::Phi, Some(result_type), Some(_id), vec![]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Phi, Some(result_type), Some(_id), mr::Operands::from([]));
         for v in value_label_pairs.as_ref() {
             inst.operands.push(mr::Operand::IdRef(v.0));
             inst.operands.push(mr::Operand::IdRef(v.1));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2418,7 +3203,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let mut inst = mr::Instruction::new(spirv::Op::LoopMerge, None, None, vec![mr::Operand::IdRef(merge_block), mr::Operand::IdRef(continue_target), mr::Operand::LoopControl(loop_control)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::LoopMerge, None, None, mr::Operands::from([mr::Operand::IdRef(merge_block), mr::Operand::IdRef(continue_target), mr::Operand::LoopControl(loop_control)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
@@ -2428,7 +3213,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::SelectionMerge, None, None, vec![mr::Operand::IdRef(merge_block), mr::Operand::SelectionControl(selection_control)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SelectionMerge, None, None, mr::Operands::from([mr::Operand::IdRef(merge_block), mr::Operand::SelectionControl(selection_control)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2437,7 +3222,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::LifetimeStart, None, None, vec![mr::Operand::IdRef(pointer), mr::Operand::LiteralInt32(size)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LifetimeStart, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::LiteralInt32(size)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2446,7 +3231,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::LifetimeStop, None, None, vec![mr::Operand::IdRef(pointer), mr::Operand::LiteralInt32(size)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::LifetimeStop, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::LiteralInt32(size)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2459,7 +3244,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupAsyncCopy, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(destination), mr::Operand::IdRef(source), mr::Operand::IdRef(num_elements), mr::Operand::IdRef(stride), mr::Operand::IdRef(event)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupAsyncCopy, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(destination), mr::Operand::IdRef(source), mr::Operand::IdRef(num_elements), mr::Operand::IdRef(stride), mr::Operand::IdRef(event)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2469,7 +3259,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::GroupWaitEvents, None, None, vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(num_events), mr::Operand::IdRef(events_list)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupWaitEvents, None, None, mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(num_events), mr::Operand::IdRef(events_list)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2482,7 +3272,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupAll, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupAll, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2496,7 +3291,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupAny, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupAny, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2510,7 +3310,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupBroadcast, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(local_id)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupBroadcast, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(local_id)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2524,7 +3329,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupIAdd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupIAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2538,7 +3348,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFAdd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2552,7 +3367,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2566,7 +3386,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupUMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupUMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2580,7 +3405,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupSMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupSMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2594,7 +3424,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2608,7 +3443,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupUMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupUMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2622,7 +3462,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupSMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupSMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2636,7 +3481,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ReadPipe, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReadPipe, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2650,7 +3500,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::WritePipe, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::WritePipe, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2664,7 +3519,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ReservedReadPipe, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(index), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReservedReadPipe, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(index), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2678,7 +3538,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ReservedWritePipe, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(index), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReservedWritePipe, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(index), mr::Operand::IdRef(pointer), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2692,7 +3557,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ReserveReadPipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReserveReadPipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2706,7 +3576,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ReserveWritePipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReserveWritePipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2716,7 +3591,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::CommitReadPipe, None, None, vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CommitReadPipe, None, None, mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2725,7 +3600,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::CommitWritePipe, None, None, vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CommitWritePipe, None, None, mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2738,7 +3613,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsValidReserveId, Some(result_type), Some(_id), vec![mr::Operand::IdRef(reserve_id)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsValidReserveId, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(reserve_id)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2752,7 +3632,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetNumPipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetNumPipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2766,7 +3651,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetMaxPipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetMaxPipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2780,7 +3670,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupReserveReadPipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupReserveReadPipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2794,7 +3689,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupReserveWritePipePackets, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupReserveWritePipePackets, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(num_packets), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2804,7 +3704,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::GroupCommitReadPipe, None, None, vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupCommitReadPipe, None, None, mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2813,7 +3713,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::GroupCommitWritePipe, None, None, vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupCommitWritePipe, None, None, mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(pipe), mr::Operand::IdRef(reserve_id), mr::Operand::IdRef(packet_size), mr::Operand::IdRef(packet_alignment)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2826,7 +3726,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::EnqueueMarker, Some(result_type), Some(_id), vec![mr::Operand::IdRef(queue), mr::Operand::IdRef(num_events), mr::Operand::IdRef(wait_events), mr::Operand::IdRef(ret_event)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::EnqueueMarker, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(queue), mr::Operand::IdRef(num_events), mr::Operand::IdRef(wait_events), mr::Operand::IdRef(ret_event)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2840,10 +3745,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::EnqueueKernel, Some(result_type), Some(_id), vec![mr::Operand::IdRef(queue), mr::Operand::IdRef(flags), mr::Operand::IdRef(nd_range), mr::Operand::IdRef(num_events), mr::Operand::IdRef(wait_events), mr::Operand::IdRef(ret_event), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::EnqueueKernel, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(queue), mr::Operand::IdRef(flags), mr::Operand::IdRef(nd_range), mr::Operand::IdRef(num_events), mr::Operand::IdRef(wait_events), mr::Operand::IdRef(ret_event), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
         for v in local_size.as_ref() {
             inst.operands.push(mr::Operand::IdRef(*v))
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2857,7 +3767,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelNDrangeSubGroupCount, Some(result_type), Some(_id), vec![mr::Operand::IdRef(nd_range), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelNDrangeSubGroupCount, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(nd_range), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2871,7 +3786,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelNDrangeMaxSubGroupSize, Some(result_type), Some(_id), vec![mr::Operand::IdRef(nd_range), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelNDrangeMaxSubGroupSize, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(nd_range), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2885,7 +3805,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelWorkGroupSize, Some(result_type), Some(_id), vec![mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelWorkGroupSize, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2899,7 +3824,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelPreferredWorkGroupSizeMultiple, Some(result_type), Some(_id), vec![mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelPreferredWorkGroupSizeMultiple, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2909,7 +3839,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::RetainEvent, None, None, vec![mr::Operand::IdRef(event)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::RetainEvent, None, None, mr::Operands::from([mr::Operand::IdRef(event)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2918,7 +3848,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::ReleaseEvent, None, None, vec![mr::Operand::IdRef(event)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReleaseEvent, None, None, mr::Operands::from([mr::Operand::IdRef(event)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2931,7 +3861,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::CreateUserEvent, Some(result_type), Some(_id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CreateUserEvent, Some(result_type), Some(_id), mr::Operands::from([]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2945,7 +3880,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::IsValidEvent, Some(result_type), Some(_id), vec![mr::Operand::IdRef(event)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::IsValidEvent, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(event)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2955,7 +3895,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::SetUserEventStatus, None, None, vec![mr::Operand::IdRef(event), mr::Operand::IdRef(status)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SetUserEventStatus, None, None, mr::Operands::from([mr::Operand::IdRef(event), mr::Operand::IdRef(status)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2964,7 +3904,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::CaptureEventProfilingInfo, None, None, vec![mr::Operand::IdRef(event), mr::Operand::IdRef(profiling_info), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CaptureEventProfilingInfo, None, None, mr::Operands::from([mr::Operand::IdRef(event), mr::Operand::IdRef(profiling_info), mr::Operand::IdRef(value)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -2977,7 +3917,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetDefaultQueue, Some(result_type), Some(_id), vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetDefaultQueue, Some(result_type), Some(_id), mr::Operands::from([]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -2991,7 +3936,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::BuildNDRange, Some(result_type), Some(_id), vec![mr::Operand::IdRef(global_work_size), mr::Operand::IdRef(local_work_size), mr::Operand::IdRef(global_work_offset)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::BuildNDRange, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(global_work_size), mr::Operand::IdRef(local_work_size), mr::Operand::IdRef(global_work_offset)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3005,11 +3955,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3023,8 +3978,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3038,11 +3998,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleDrefImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleDrefImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3056,8 +4021,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleDrefExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleDrefExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3071,11 +4041,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleProjImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleProjImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3089,8 +4064,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleProjExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleProjExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3104,11 +4084,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleProjDrefImplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleProjDrefImplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3122,8 +4107,13 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseSampleProjDrefExplicitLod, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseSampleProjDrefExplicitLod, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref), mr::Operand::ImageOperands(image_operands)]));
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3137,11 +4127,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseFetch, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseFetch, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3155,11 +4150,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseGather, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(component)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseGather, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(component)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3173,11 +4173,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseDrefGather, Some(result_type), Some(_id), vec![mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseDrefGather, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(sampled_image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(dref)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3191,7 +4196,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::ImageSparseTexelsResident, Some(result_type), Some(_id), vec![mr::Operand::IdRef(resident_code)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ImageSparseTexelsResident, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(resident_code)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3205,7 +4215,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::AtomicFlagTestAndSet, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicFlagTestAndSet, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3215,7 +4230,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::AtomicFlagClear, None, None, vec![mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::AtomicFlagClear, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdScope(scope), mr::Operand::IdMemorySemantics(semantics)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -3228,11 +4243,16 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::ImageSparseRead, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::ImageSparseRead, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
         if let Some(v) = image_operands {
             inst.operands.push(mr::Operand::ImageOperands(v));
         };
         inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3246,7 +4266,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SizeOf, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pointer)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SizeOf, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3260,7 +4285,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::CreatePipeFromPipeStorage, Some(result_type), Some(_id), vec![mr::Operand::IdRef(pipe_storage)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::CreatePipeFromPipeStorage, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pipe_storage)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3274,7 +4304,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelLocalSizeForSubgroupCount, Some(result_type), Some(_id), vec![mr::Operand::IdRef(subgroup_count), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelLocalSizeForSubgroupCount, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(subgroup_count), mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3288,7 +4323,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GetKernelMaxNumSubgroups, Some(result_type), Some(_id), vec![mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GetKernelMaxNumSubgroups, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(invoke), mr::Operand::IdRef(param), mr::Operand::IdRef(param_size), mr::Operand::IdRef(param_align)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3302,7 +4342,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::NamedBarrierInitialize, Some(result_type), Some(_id), vec![mr::Operand::IdRef(subgroup_count)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::NamedBarrierInitialize, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(subgroup_count)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3312,7 +4357,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::MemoryNamedBarrier, None, None, vec![mr::Operand::IdRef(named_barrier), mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::MemoryNamedBarrier, None, None, mr::Operands::from([mr::Operand::IdRef(named_barrier), mr::Operand::IdScope(memory), mr::Operand::IdMemorySemantics(semantics)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -3325,7 +4370,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformElect, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformElect, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3339,7 +4389,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformAll, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformAll, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3353,7 +4408,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformAny, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformAny, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3367,7 +4427,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformAllEqual, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformAllEqual, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3381,7 +4446,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBroadcast, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(id)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBroadcast, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(id)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3395,7 +4465,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBroadcastFirst, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBroadcastFirst, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3409,7 +4484,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBallot, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBallot, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3423,7 +4503,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformInverseBallot, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformInverseBallot, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3437,7 +4522,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBallotBitExtract, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBallotBitExtract, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3451,7 +4541,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBallotBitCount, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBallotBitCount, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3465,7 +4560,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBallotFindLSB, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBallotFindLSB, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3479,7 +4579,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformBallotFindMSB, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBallotFindMSB, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3493,7 +4598,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformShuffle, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(id)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformShuffle, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(id)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3507,7 +4617,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformShuffleXor, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(mask)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformShuffleXor, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(mask)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3521,7 +4636,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformShuffleUp, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(delta)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformShuffleUp, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(delta)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3535,7 +4655,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformShuffleDown, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(delta)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformShuffleDown, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(delta)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3549,10 +4674,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformIAdd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformIAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3566,10 +4696,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformFAdd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformFAdd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3583,10 +4718,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformIMul, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformIMul, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3600,10 +4740,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformFMul, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformFMul, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3617,10 +4762,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformSMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformSMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3634,10 +4784,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformUMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformUMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3651,10 +4806,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformFMin, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformFMin, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3668,10 +4828,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformSMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformSMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3685,10 +4850,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformUMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformUMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3702,10 +4872,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformFMax, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformFMax, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3719,10 +4894,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformBitwiseAnd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBitwiseAnd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3736,10 +4916,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformBitwiseOr, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBitwiseOr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3753,10 +4938,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformBitwiseXor, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformBitwiseXor, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3770,10 +4960,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformLogicalAnd, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformLogicalAnd, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3787,10 +4982,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformLogicalOr, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformLogicalOr, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3804,10 +5004,15 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let mut inst = mr::Instruction::new(spirv::Op::GroupNonUniformLogicalXor, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformLogicalXor, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(value)]));
         if let Some(v) = cluster_size {
             inst.operands.push(mr::Operand::IdRef(v));
         };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3821,7 +5026,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformQuadBroadcast, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformQuadBroadcast, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3835,7 +5045,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformQuadSwap, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(direction)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformQuadSwap, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::IdRef(value), mr::Operand::IdRef(direction)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3849,7 +5064,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupBallotKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupBallotKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3863,7 +5083,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupFirstInvocationKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupFirstInvocationKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3877,7 +5102,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupAllKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupAllKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3891,7 +5121,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupAnyKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupAnyKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3905,7 +5140,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupAllEqualKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(predicate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupAllEqualKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(predicate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3919,22 +5159,226 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupReadInvocationKHR, Some(result_type), Some(_id), vec![mr::Operand::IdRef(value), mr::Operand::IdRef(index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupReadInvocationKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(value), mr::Operand::IdRef(index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
 
-    /// Appends an OpGroupIAddNonUniformAMD instruction to the current basic block.
-    pub fn group_iadd_non_uniform_amd(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, execution: spirv::Word, operation: spirv::GroupOperation, x: spirv::Word) -> BuildResult<spirv::Word> {
+    /// Appends an OpTraceRayKHR instruction to the current basic block.
+    pub fn trace_ray_khr(&mut self, accel: spirv::Word, ray_flags: spirv::Word, cull_mask: spirv::Word, sbt_offset: spirv::Word, sbt_stride: spirv::Word, miss_index: spirv::Word, ray_origin: spirv::Word, ray_tmin: spirv::Word, ray_direction: spirv::Word, ray_tmax: spirv::Word, payload: spirv::Word) -> BuildResult<()> {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let _id = match result_id {
-            Some(v) => v,
-            None => self.id(),
-        };
-        let inst = mr::Instruction::new(spirv::Op::GroupIAddNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
-        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        let inst = mr::Instruction::with_operands(spirv::Op::TraceRayKHR, None, None, mr::Operands::from([mr::Operand::IdRef(accel), mr::Operand::IdRef(ray_flags), mr::Operand::IdRef(cull_mask), mr::Operand::IdRef(sbt_offset), mr::Operand::IdRef(sbt_stride), mr::Operand::IdRef(miss_index), mr::Operand::IdRef(ray_origin), mr::Operand::IdRef(ray_tmin), mr::Operand::IdRef(ray_direction), mr::Operand::IdRef(ray_tmax), mr::Operand::IdRef(payload)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpExecuteCallableKHR instruction to the current basic block.
+    pub fn execute_callable_khr(&mut self, sbt_index: spirv::Word, callable_data: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::ExecuteCallableKHR, None, None, mr::Operands::from([mr::Operand::IdRef(sbt_index), mr::Operand::IdRef(callable_data)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpConvertUToAccelerationStructureKHR instruction to the current basic block.
+    pub fn convert_uto_acceleration_structure_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, accel: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::ConvertUToAccelerationStructureKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(accel)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpCooperativeMatrixLoadKHR instruction to the current basic block.
+    pub fn cooperative_matrix_load_khr<T: AsRef<[mr::Operand]>>(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, pointer: spirv::Word, memory_layout: spirv::Word, stride: Option<spirv::Word>, memory_operand: Option<spirv::MemoryAccess>, additional_params: T) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CooperativeMatrixLoadKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdRef(memory_layout)]));
+        if let Some(v) = stride {
+            inst.operands.push(mr::Operand::IdRef(v));
+        };
+        if let Some(v) = memory_operand {
+            inst.operands.push(mr::Operand::MemoryAccess(v));
+        };
+        inst.operands.extend_from_slice(additional_params.as_ref());
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpCooperativeMatrixStoreKHR instruction to the current basic block.
+    pub fn cooperative_matrix_store_khr<T: AsRef<[mr::Operand]>>(&mut self, pointer: spirv::Word, object: spirv::Word, memory_layout: spirv::Word, stride: Option<spirv::Word>, memory_operand: Option<spirv::MemoryAccess>, additional_params: T) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CooperativeMatrixStoreKHR, None, None, mr::Operands::from([mr::Operand::IdRef(pointer), mr::Operand::IdRef(object), mr::Operand::IdRef(memory_layout)]));
+        if let Some(v) = stride {
+            inst.operands.push(mr::Operand::IdRef(v));
+        };
+        if let Some(v) = memory_operand {
+            inst.operands.push(mr::Operand::MemoryAccess(v));
+        };
+        inst.operands.extend_from_slice(additional_params.as_ref());
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpCooperativeMatrixMulAddKHR instruction to the current basic block.
+    pub fn cooperative_matrix_mul_add_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, a: spirv::Word, b: spirv::Word, c: spirv::Word, cooperative_matrix_operands: Option<spirv::CooperativeMatrixOperands>) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let mut inst = mr::Instruction::with_operands(spirv::Op::CooperativeMatrixMulAddKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(a), mr::Operand::IdRef(b), mr::Operand::IdRef(c)]));
+        if let Some(v) = cooperative_matrix_operands {
+            inst.operands.push(mr::Operand::CooperativeMatrixOperands(v));
+        };
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpCooperativeMatrixLengthKHR instruction to the current basic block.
+    pub fn cooperative_matrix_length_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, type_: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::CooperativeMatrixLengthKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(type_)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryInitializeKHR instruction to the current basic block.
+    pub fn ray_query_initialize_khr(&mut self, ray_query: spirv::Word, accel: spirv::Word, ray_flags: spirv::Word, cull_mask: spirv::Word, ray_origin: spirv::Word, ray_tmin: spirv::Word, ray_direction: spirv::Word, ray_tmax: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryInitializeKHR, None, None, mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(accel), mr::Operand::IdRef(ray_flags), mr::Operand::IdRef(cull_mask), mr::Operand::IdRef(ray_origin), mr::Operand::IdRef(ray_tmin), mr::Operand::IdRef(ray_direction), mr::Operand::IdRef(ray_tmax)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpRayQueryTerminateKHR instruction to the current basic block.
+    pub fn ray_query_terminate_khr(&mut self, ray_query: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryTerminateKHR, None, None, mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpRayQueryGenerateIntersectionKHR instruction to the current basic block.
+    pub fn ray_query_generate_intersection_khr(&mut self, ray_query: spirv::Word, hit_t: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGenerateIntersectionKHR, None, None, mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(hit_t)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpRayQueryConfirmIntersectionKHR instruction to the current basic block.
+    pub fn ray_query_confirm_intersection_khr(&mut self, ray_query: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryConfirmIntersectionKHR, None, None, mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
+    /// Appends an OpRayQueryProceedKHR instruction to the current basic block.
+    pub fn ray_query_proceed_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryProceedKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionTypeKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_type_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionTypeKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpGroupIAddNonUniformAMD instruction to the current basic block.
+    pub fn group_iadd_non_uniform_amd(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, execution: spirv::Word, operation: spirv::GroupOperation, x: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupIAddNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
 
@@ -3947,7 +5391,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFAddNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFAddNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3961,7 +5410,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFMinNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFMinNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3975,7 +5429,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupUMinNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupUMinNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -3989,7 +5448,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupSMinNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupSMinNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4003,7 +5467,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupFMaxNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupFMaxNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4017,7 +5486,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupUMaxNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupUMaxNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4031,7 +5505,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupSMaxNonUniformAMD, Some(result_type), Some(_id), vec![mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupSMaxNonUniformAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdScope(execution), mr::Operand::GroupOperation(operation), mr::Operand::IdRef(x)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4045,7 +5524,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FragmentMaskFetchAMD, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FragmentMaskFetchAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4059,11 +5543,25 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::FragmentFetchAMD, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(fragment_index)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::FragmentFetchAMD, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(fragment_index)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
 
+    /// Appends an OpSetMeshOutputsEXT instruction to the current basic block.
+    pub fn set_mesh_outputs_ext(&mut self, vertex_count: spirv::Word, primitive_count: spirv::Word) -> BuildResult<()> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let inst = mr::Instruction::with_operands(spirv::Op::SetMeshOutputsEXT, None, None, mr::Operands::from([mr::Operand::IdRef(vertex_count), mr::Operand::IdRef(primitive_count)]));
+        Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
+    }
+
     /// Appends an OpSubgroupShuffleINTEL instruction to the current basic block.
     pub fn subgroup_shuffle_intel(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, data: spirv::Word, invocation_id: spirv::Word) -> BuildResult<spirv::Word> {
         if self.basic_block.is_none() {
@@ -4073,7 +5571,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupShuffleINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(data), mr::Operand::IdRef(invocation_id)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupShuffleINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(data), mr::Operand::IdRef(invocation_id)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4087,7 +5590,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupShuffleDownINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(current), mr::Operand::IdRef(next), mr::Operand::IdRef(delta)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupShuffleDownINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(current), mr::Operand::IdRef(next), mr::Operand::IdRef(delta)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4101,7 +5609,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupShuffleUpINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(previous), mr::Operand::IdRef(current), mr::Operand::IdRef(delta)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupShuffleUpINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(previous), mr::Operand::IdRef(current), mr::Operand::IdRef(delta)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4115,7 +5628,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupShuffleXorINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(data), mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupShuffleXorINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(data), mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4129,7 +5647,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupBlockReadINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(ptr)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupBlockReadINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ptr)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4139,7 +5662,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::SubgroupBlockWriteINTEL, None, None, vec![mr::Operand::IdRef(ptr), mr::Operand::IdRef(data)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupBlockWriteINTEL, None, None, mr::Operands::from([mr::Operand::IdRef(ptr), mr::Operand::IdRef(data)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -4152,7 +5675,12 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::SubgroupImageBlockReadINTEL, Some(result_type), Some(_id), vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupImageBlockReadINTEL, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -4162,7 +5690,7 @@ impl Builder {
         if self.basic_block.is_none() {
             return Err(Error::DetachedInstruction);
         }
-        let inst = mr::Instruction::new(spirv::Op::SubgroupImageBlockWriteINTEL, None, None, vec![mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(data)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::SubgroupImageBlockWriteINTEL, None, None, mr::Operands::from([mr::Operand::IdRef(image), mr::Operand::IdRef(coordinate), mr::Operand::IdRef(data)]));
         Ok(self.basic_block.as_mut().unwrap().instructions.push(inst))
     }
 
@@ -4175,7 +5703,335 @@ impl Builder {
             Some(v) => v,
             None => self.id(),
         };
-        let inst = mr::Instruction::new(spirv::Op::GroupNonUniformPartitionNV, Some(result_type), Some(_id), vec![mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::GroupNonUniformPartitionNV, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(value)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetRayTMinKHR instruction to the current basic block.
+    pub fn ray_query_get_ray_tmin_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetRayTMinKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetRayFlagsKHR instruction to the current basic block.
+    pub fn ray_query_get_ray_flags_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetRayFlagsKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionTKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_tkhr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionTKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionInstanceCustomIndexKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_instance_custom_index_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionInstanceCustomIndexKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionInstanceIdKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_instance_id_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionInstanceIdKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_instance_shader_binding_table_record_offset_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionGeometryIndexKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_geometry_index_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionGeometryIndexKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionPrimitiveIndexKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_primitive_index_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionPrimitiveIndexKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionBarycentricsKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_barycentrics_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionBarycentricsKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionFrontFaceKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_front_face_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionFrontFaceKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionCandidateAABBOpaqueKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_candidate_aabbopaque_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionCandidateAABBOpaqueKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionObjectRayDirectionKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_object_ray_direction_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionObjectRayDirectionKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionObjectRayOriginKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_object_ray_origin_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionObjectRayOriginKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetWorldRayDirectionKHR instruction to the current basic block.
+    pub fn ray_query_get_world_ray_direction_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetWorldRayDirectionKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetWorldRayOriginKHR instruction to the current basic block.
+    pub fn ray_query_get_world_ray_origin_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetWorldRayOriginKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionObjectToWorldKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_object_to_world_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionObjectToWorldKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(_id)
+    }
+
+    /// Appends an OpRayQueryGetIntersectionWorldToObjectKHR instruction to the current basic block.
+    pub fn ray_query_get_intersection_world_to_object_khr(&mut self, result_type: spirv::Word, result_id: Option<spirv::Word>, ray_query: spirv::Word, intersection: spirv::Word) -> BuildResult<spirv::Word> {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let _id = match result_id {
+            Some(v) => v,
+            None => self.id(),
+        };
+        let inst = mr::Instruction::with_operands(spirv::Op::RayQueryGetIntersectionWorldToObjectKHR, Some(result_type), Some(_id), mr::Operands::from([mr::Operand::IdRef(ray_query), mr::Operand::IdRef(intersection)]));
+        if result_id.is_none() {
+            if let Some(id) = self.fold(&inst) {
+                return Ok(id);
+            }
+        }
         self.basic_block.as_mut().unwrap().instructions.push(inst);
         Ok(_id)
     }
@@ -19,13 +19,13 @@
 impl Builder {
     /// Appends an OpBranch instruction and ends the current basic block.
     pub fn branch(&mut self, target_label: spirv::Word) -> BuildResult<()> {
-        let inst = mr::Instruction::new(spirv::Op::Branch, None, None, vec![mr::Operand::IdRef(target_label)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Branch, None, None, mr::Operands::from([mr::Operand::IdRef(target_label)]));
         self.end_basic_block(inst)
     }
 
     /// Appends an OpBranchConditional instruction and ends the current basic block.
     pub fn branch_conditional<T: AsRef<[u32]>>(&mut self, condition: spirv::Word, true_label: spirv::Word, false_label: spirv::Word, branch_weights: T) -> BuildResult<()> {
-        let mut inst = mr::Instruction::new(spirv::Op::BranchConditional, None, None, vec![mr::Operand::IdRef(condition), mr::Operand::IdRef(true_label), mr::Operand::IdRef(false_label)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::BranchConditional, None, None, mr::Operands::from([mr::Operand::IdRef(condition), mr::Operand::IdRef(true_label), mr::Operand::IdRef(false_label)]));
         for v in branch_weights.as_ref() {
             inst.operands.push(mr::Operand::LiteralInt32(*v))
         };
@@ -34,7 +34,7 @@ impl Builder {
 
     /// Appends an OpSwitch instruction and ends the current basic block.
     pub fn switch<T: AsRef<[(u32, spirv::Word)]>>(&mut self, selector: spirv::Word, default: spirv::Word, target: T) -> BuildResult<()> {
-        let mut inst = mr::Instruction::new(spirv::Op::Switch, None, None, vec![mr::Operand::IdRef(selector), mr::Operand::IdRef(default)]);
+        let mut inst = mr::Instruction::with_operands(spirv::Op::Switch, None, None, mr::Operands::from([mr::Operand::IdRef(selector), mr::Operand::IdRef(default)]));
         for v in target.as_ref() {
             inst.operands.push(mr::Operand::LiteralInt32(v.0));
             inst.operands.push(mr::Operand::IdRef(v.1));
@@ -44,25 +44,46 @@ impl Builder {
 
     /// Appends an OpKill instruction and ends the current basic block.
     pub fn kill(&mut self) -> BuildResult<()> {
-        let inst = mr::Instruction::new(spirv::Op::Kill, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Kill, None, None, mr::Operands::from([]));
         self.end_basic_block(inst)
     }
 
     /// Appends an OpReturn instruction and ends the current basic block.
     pub fn ret(&mut self) -> BuildResult<()> {
-        let inst = mr::Instruction::new(spirv::Op::Return, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Return, None, None, mr::Operands::from([]));
         self.end_basic_block(inst)
     }
 
     /// Appends an OpReturnValue instruction and ends the current basic block.
     pub fn ret_value(&mut self, value: spirv::Word) -> BuildResult<()> {
-        let inst = mr::Instruction::new(spirv::Op::ReturnValue, None, None, vec![mr::Operand::IdRef(value)]);
+        let inst = mr::Instruction::with_operands(spirv::Op::ReturnValue, None, None, mr::Operands::from([mr::Operand::IdRef(value)]));
         self.end_basic_block(inst)
     }
 
     /// Appends an OpUnreachable instruction and ends the current basic block.
     pub fn unreachable(&mut self) -> BuildResult<()> {
-        let inst = mr::Instruction::new(spirv::Op::Unreachable, None, None, vec![]);
+        let inst = mr::Instruction::with_operands(spirv::Op::Unreachable, None, None, mr::Operands::from([]));
+        self.end_basic_block(inst)
+    }
+
+    /// Appends an OpIgnoreIntersectionKHR instruction and ends the current basic block.
+    pub fn ignore_intersection_khr(&mut self) -> BuildResult<()> {
+        let inst = mr::Instruction::with_operands(spirv::Op::IgnoreIntersectionKHR, None, None, mr::Operands::from([]));
+        self.end_basic_block(inst)
+    }
+
+    /// Appends an OpTerminateRayKHR instruction and ends the current basic block.
+    pub fn terminate_ray_khr(&mut self) -> BuildResult<()> {
+        let inst = mr::Instruction::with_operands(spirv::Op::TerminateRayKHR, None, None, mr::Operands::from([]));
+        self.end_basic_block(inst)
+    }
+
+    /// Appends an OpEmitMeshTasksEXT instruction and ends the current basic block.
+    pub fn emit_mesh_tasks_ext(&mut self, group_count_x: spirv::Word, group_count_y: spirv::Word, group_count_z: spirv::Word, payload: Option<spirv::Word>) -> BuildResult<()> {
+        let mut inst = mr::Instruction::with_operands(spirv::Op::EmitMeshTasksEXT, None, None, mr::Operands::from([mr::Operand::IdRef(group_count_x), mr::Operand::IdRef(group_count_y), mr::Operand::IdRef(group_count_z)]));
+        if let Some(v) = payload {
+            inst.operands.push(mr::Operand::IdRef(v));
+        };
         self.end_basic_block(inst)
     }
 }
\ No newline at end of file
@@ -20,49 +20,49 @@ impl Builder {
     /// Appends an OpTypeVoid instruction and returns the result id.
     pub fn type_void(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeVoid, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeVoid, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeBool instruction and returns the result id.
     pub fn type_bool(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeBool, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeBool, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeInt instruction and returns the result id.
     pub fn type_int(&mut self, width: u32, signedness: u32) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeInt, None, Some(id), vec![mr::Operand::LiteralInt32(width), mr::Operand::LiteralInt32(signedness)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeInt, None, Some(id), mr::Operands::from([mr::Operand::LiteralInt32(width), mr::Operand::LiteralInt32(signedness)])));
         id
     }
 
     /// Appends an OpTypeFloat instruction and returns the result id.
     pub fn type_float(&mut self, width: u32) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeFloat, None, Some(id), vec![mr::Operand::LiteralInt32(width)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeFloat, None, Some(id), mr::Operands::from([mr::Operand::LiteralInt32(width)])));
         id
     }
 
     /// Appends an OpTypeVector instruction and returns the result id.
     pub fn type_vector(&mut self, component_type: spirv::Word, component_count: u32) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeVector, None, Some(id), vec![mr::Operand::IdRef(component_type), mr::Operand::LiteralInt32(component_count)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeVector, None, Some(id), mr::Operands::from([mr::Operand::IdRef(component_type), mr::Operand::LiteralInt32(component_count)])));
         id
     }
 
     /// Appends an OpTypeMatrix instruction and returns the result id.
     pub fn type_matrix(&mut self, column_type: spirv::Word, column_count: u32) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeMatrix, None, Some(id), vec![mr::Operand::IdRef(column_type), mr::Operand::LiteralInt32(column_count)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeMatrix, None, Some(id), mr::Operands::from([mr::Operand::IdRef(column_type), mr::Operand::LiteralInt32(column_count)])));
         id
     }
 
     /// Appends an OpTypeImage instruction and returns the result id.
     pub fn type_image(&mut self, sampled_type: spirv::Word, dim: spirv::Dim, depth: u32, arrayed: u32, ms: u32, sampled: u32, image_format: spirv::ImageFormat, access_qualifier: Option<spirv::AccessQualifier>) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeImage, None, Some(id), vec![mr::Operand::IdRef(sampled_type), mr::Operand::Dim(dim), mr::Operand::LiteralInt32(depth), mr::Operand::LiteralInt32(arrayed), mr::Operand::LiteralInt32(ms), mr::Operand::LiteralInt32(sampled), mr::Operand::ImageFormat(image_format)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeImage, None, Some(id), mr::Operands::from([mr::Operand::IdRef(sampled_type), mr::Operand::Dim(dim), mr::Operand::LiteralInt32(depth), mr::Operand::LiteralInt32(arrayed), mr::Operand::LiteralInt32(ms), mr::Operand::LiteralInt32(sampled), mr::Operand::ImageFormat(image_format)])));
         if let Some(v) = access_qualifier {
             self.module.types_global_values.last_mut().expect("interal error").operands.push(mr::Operand::AccessQualifier(v));
         };
@@ -72,35 +72,35 @@ impl Builder {
     /// Appends an OpTypeSampler instruction and returns the result id.
     pub fn type_sampler(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeSampler, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeSampler, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeSampledImage instruction and returns the result id.
     pub fn type_sampled_image(&mut self, image_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeSampledImage, None, Some(id), vec![mr::Operand::IdRef(image_type)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeSampledImage, None, Some(id), mr::Operands::from([mr::Operand::IdRef(image_type)])));
         id
     }
 
     /// Appends an OpTypeArray instruction and returns the result id.
     pub fn type_array(&mut self, element_type: spirv::Word, length: spirv::Word) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeArray, None, Some(id), vec![mr::Operand::IdRef(element_type), mr::Operand::IdRef(length)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeArray, None, Some(id), mr::Operands::from([mr::Operand::IdRef(element_type), mr::Operand::IdRef(length)])));
         id
     }
 
     /// Appends an OpTypeRuntimeArray instruction and returns the result id.
     pub fn type_runtime_array(&mut self, element_type: spirv::Word) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeRuntimeArray, None, Some(id), vec![mr::Operand::IdRef(element_type)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeRuntimeArray, None, Some(id), mr::Operands::from([mr::Operand::IdRef(element_type)])));
         id
     }
 
     /// Appends an OpTypeStruct instruction and returns the result id.
     pub fn type_struct<T: AsRef<[spirv::Word]>>(&mut self, field_types: T) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeStruct, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeStruct, None, Some(id), mr::Operands::from([])));
         for v in field_types.as_ref() {
             self.module.types_global_values.last_mut().expect("interal error").operands.push(mr::Operand::IdRef(*v))
         };
@@ -110,7 +110,7 @@ impl Builder {
     /// Appends an OpTypeFunction instruction and returns the result id.
     pub fn type_function<T: AsRef<[spirv::Word]>>(&mut self, return_type: spirv::Word, parameter_types: T) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeFunction, None, Some(id), vec![mr::Operand::IdRef(return_type)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeFunction, None, Some(id), mr::Operands::from([mr::Operand::IdRef(return_type)])));
         for v in parameter_types.as_ref() {
             self.module.types_global_values.last_mut().expect("interal error").operands.push(mr::Operand::IdRef(*v))
         };
@@ -120,49 +120,70 @@ impl Builder {
     /// Appends an OpTypeEvent instruction and returns the result id.
     pub fn type_event(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeEvent, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeEvent, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeDeviceEvent instruction and returns the result id.
     pub fn type_device_event(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeDeviceEvent, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeDeviceEvent, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeReserveId instruction and returns the result id.
     pub fn type_reserve_id(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeReserveId, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeReserveId, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeQueue instruction and returns the result id.
     pub fn type_queue(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeQueue, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeQueue, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypePipe instruction and returns the result id.
     pub fn type_pipe(&mut self, qualifier: spirv::AccessQualifier) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypePipe, None, Some(id), vec![mr::Operand::AccessQualifier(qualifier)]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypePipe, None, Some(id), mr::Operands::from([mr::Operand::AccessQualifier(qualifier)])));
         id
     }
 
     /// Appends an OpTypePipeStorage instruction and returns the result id.
     pub fn type_pipe_storage(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypePipeStorage, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypePipeStorage, None, Some(id), mr::Operands::from([])));
         id
     }
 
     /// Appends an OpTypeNamedBarrier instruction and returns the result id.
     pub fn type_named_barrier(&mut self) -> spirv::Word {
         let id = self.id();
-        self.module.types_global_values.push(mr::Instruction::new(spirv::Op::TypeNamedBarrier, None, Some(id), vec![]));
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeNamedBarrier, None, Some(id), mr::Operands::from([])));
+        id
+    }
+
+    /// Appends an OpTypeCooperativeMatrixKHR instruction and returns the result id.
+    pub fn type_cooperative_matrix_khr(&mut self, component_type: spirv::Word, scope: spirv::Word, rows: spirv::Word, columns: spirv::Word, use_: spirv::Word) -> spirv::Word {
+        let id = self.id();
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeCooperativeMatrixKHR, None, Some(id), mr::Operands::from([mr::Operand::IdRef(component_type), mr::Operand::IdScope(scope), mr::Operand::IdRef(rows), mr::Operand::IdRef(columns), mr::Operand::IdRef(use_)])));
+        id
+    }
+
+    /// Appends an OpTypeRayQueryKHR instruction and returns the result id.
+    pub fn type_ray_query_khr(&mut self) -> spirv::Word {
+        let id = self.id();
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeRayQueryKHR, None, Some(id), mr::Operands::from([])));
+        id
+    }
+
+    /// Appends an OpTypeAccelerationStructureKHR instruction and returns the result id.
+    pub fn type_acceleration_structure_khr(&mut self) -> spirv::Word {
+        let id = self.id();
+        self.module.types_global_values.push(mr::Instruction::with_operands(spirv::Op::TypeAccelerationStructureKHR, None, Some(id), mr::Operands::from([])));
         id
     }
 }
\ No newline at end of file
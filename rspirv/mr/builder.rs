@@ -14,11 +14,16 @@
 
 #![cfg_attr(feature = "clippy", allow(too_many_arguments))]
 
+use grammar;
 use mr;
 use spirv;
 
+use grammar::{OpClass, Opcode};
+use std::collections::BTreeMap;
 use std::result;
 use super::Error;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 type BuildResult<T> = result::Result<T, Error>;
 
@@ -62,6 +67,12 @@ type BuildResult<T> = result::Result<T, Error>;
 /// Errors returned are enumerants related to function structure from the
 /// [`Error`](enum.Error.html) enum.
 ///
+/// Arbitrary instructions can be appended via `insert_into_block()` and
+/// `insert_global()`. Their operands are checked against the grammar, so
+/// that malformed instructions are rejected with
+/// `Error::MissingOperand`, `Error::WrongOperand`, or
+/// `Error::MismatchedResult`.
+///
 /// # Examples
 ///
 /// ```
@@ -107,6 +118,14 @@ pub struct Builder {
     function: Option<mr::Function>,
     basic_block: Option<mr::BasicBlock>,
     version: Option<(u8, u8)>,
+    folding: bool,
+    phi_checking: bool,
+    target_env: Option<mr::TargetEnv>,
+    /// Declarations inserted into or removed from the middle of the
+    /// function list, so that rollbacks can undo them.
+    function_edits: Vec<FunctionEdit>,
+    /// The scalar constants among the global values, for folding.
+    scalars: ScalarConstants,
 }
 
 impl Builder {
@@ -118,6 +137,129 @@ impl Builder {
             function: None,
             basic_block: None,
             version: None,
+            folding: false,
+            phi_checking: false,
+            target_env: None,
+            function_edits: vec![],
+            scalars: ScalarConstants::default(),
+        }
+    }
+
+    /// Creates a new builder for modules consumed by the given target
+    /// environment `env`.
+    ///
+    /// The SPIR-V version is set to the newest one accepted by `env`. For
+    /// shader-only environments, the Shader capability and the memory model
+    /// mandated by `env` are declared. For OpenCL, the Addresses and Kernel
+    /// capabilities and the Physical64 OpenCL memory model are declared.
+    /// Modules can then be checked against `env` with
+    /// [`checked_module()`](#method.checked_module).
+    pub fn for_target_env(env: mr::TargetEnv) -> Builder {
+        let mut b = Builder::new();
+        b.target_env = Some(env);
+        let (major, minor) = env.spirv_version();
+        b.set_version(major, minor);
+        if env.is_shader_only() {
+            b.capability(spirv::Capability::Shader);
+        }
+        if let Some((addressing_model, memory_model)) = env.memory_model() {
+            b.memory_model(addressing_model, memory_model);
+        }
+        if env.is_opencl() {
+            b.capability(spirv::Capability::Addresses);
+            b.capability(spirv::Capability::Kernel);
+            b.memory_model(spirv::AddressingModel::Physical64, spirv::MemoryModel::OpenCL);
+        }
+        b
+    }
+
+    /// Creates a new builder for Vulkan 1.0; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_vulkan_1_0() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::Vulkan1_0)
+    }
+
+    /// Creates a new builder for Vulkan 1.1; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_vulkan_1_1() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::Vulkan1_1)
+    }
+
+    /// Creates a new builder for Vulkan 1.2; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_vulkan_1_2() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::Vulkan1_2)
+    }
+
+    /// Creates a new builder for Vulkan 1.3; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_vulkan_1_3() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::Vulkan1_3)
+    }
+
+    /// Creates a new builder for OpenGL 4.5; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_opengl_4_5() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::OpenGL4_5)
+    }
+
+    /// Creates a new builder for OpenGL 4.6; see
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn for_opengl_4_6() -> Builder {
+        Builder::for_target_env(mr::TargetEnv::OpenGL4_6)
+    }
+
+    /// Creates a new builder from an existing `module` so that it can be
+    /// further edited.
+    ///
+    /// The next id is restored from the module header's bound, or computed
+    /// from all ids used in the module if there is no header. If the last
+    /// function of the module is not ended (i.e., has no OpFunctionEnd), it
+    /// becomes the function under construction; similarly, if its last
+    /// basic block has no terminator, it becomes the basic block under
+    /// construction.
+    pub fn from_module(module: mr::Module) -> Builder {
+        let mut module = module;
+        let header = module.header.take();
+        let next_id = match header {
+            Some(ref h) => h.bound.max(module.max_id() + 1),
+            None => module.max_id() + 1,
+        };
+        let version = header.as_ref().map(|h| h.version());
+
+        let reopen_function = module.functions.last().is_some_and(|f| f.end.is_none());
+        let mut function = if reopen_function {
+            module.functions.pop()
+        } else {
+            None
+        };
+        let basic_block = match function {
+            Some(ref mut f) => {
+                let reopen_block = f.basic_blocks.last().is_some_and(|bb| {
+                    bb.instructions
+                        .last()
+                        .is_none_or(|i| !grammar::reflect::is_terminator(i.class.opcode))
+                });
+                if reopen_block {
+                    f.basic_blocks.pop()
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        Builder {
+            module,
+            next_id,
+            function,
+            basic_block,
+            version,
+            folding: false,
+            phi_checking: false,
+            target_env: None,
+            function_edits: vec![],
+            scalars: ScalarConstants::default(),
         }
     }
 
@@ -129,11 +271,52 @@ impl Builder {
         self.version = Some((major, minor));
     }
 
+    /// Returns the target environment of this builder, if created with
+    /// [`for_target_env()`](#method.for_target_env).
+    pub fn target_env(&self) -> Option<mr::TargetEnv> {
+        self.target_env
+    }
+
+    /// Returns the version-dependent rules of the SPIR-V version set by
+    /// [`set_version()`](#method.set_version), or of the newest version
+    /// supported if not set.
+    pub fn version_rules(&self) -> mr::VersionRules {
+        let (major, minor) = self
+            .version
+            .unwrap_or((spirv::MAJOR_VERSION as u8, spirv::MINOR_VERSION as u8));
+        mr::VersionRules::new(major, minor)
+    }
+
+    /// Returns a reference to the `Module` under construction.
+    ///
+    /// The function and basic block currently under construction, if any,
+    /// are not yet part of the returned module; neither is the module
+    /// header, which is only filled in by [`module()`](#method.module).
+    pub fn module_ref(&self) -> &mr::Module {
+        &self.module
+    }
+
+    /// Returns a mutable reference to the `Module` under construction.
+    ///
+    /// See [`module_ref()`](#method.module_ref) for what is contained in the
+    /// returned module. Ids introduced by editing the module directly are
+    /// not tracked; use [`id()`](#method.id) to allocate them.
+    pub fn module_mut(&mut self) -> &mut mr::Module {
+        self.scalars = ScalarConstants::default();
+        &mut self.module
+    }
+
     /// Returns the `Module` under construction.
+    ///
+    /// Instructions are kept in the order they were added in; use
+    /// [`Module::sort_global_section`](struct.Module.html#method.sort_global_section)
+    /// to move late definitions in front of their uses.
     pub fn module(self) -> mr::Module {
         let mut module = self.module;
 
         let mut header = mr::ModuleHeader::new(self.next_id);
+        // Instructions added through module_mut() may use larger ids.
+        header.update_bound(module.max_id());
         if let Some((major, minor)) = self.version {
             header.set_version(major, minor);
         }
@@ -142,6 +325,24 @@ impl Builder {
         module
     }
 
+    /// Returns the `Module` under construction if it can be consumed by the
+    /// target environment of this builder, or all the issues found by
+    /// [`Module::check_target_env()`](struct.Module.html#method.check_target_env)
+    /// otherwise.
+    ///
+    /// Builders without target environment always succeed.
+    pub fn checked_module(self) -> result::Result<mr::Module, Vec<mr::TargetEnvIssue>> {
+        let env = self.target_env;
+        let module = self.module();
+        if let Some(env) = env {
+            let issues = module.check_target_env(env);
+            if !issues.is_empty() {
+                return Err(issues);
+            }
+        }
+        Ok(module)
+    }
+
     /// Returns the next unused id.
     pub fn id(&mut self) -> spirv::Word {
         let id = self.next_id;
@@ -189,6 +390,9 @@ impl Builder {
         if self.function.is_none() {
             return Err(Error::MismatchedFunctionEnd);
         }
+        if self.phi_checking {
+            check_phis(self.function.as_ref().unwrap())?;
+        }
 
         let mut f = self.function.take().unwrap();
         f.end = Some(mr::Instruction::new(
@@ -200,6 +404,111 @@ impl Builder {
         Ok(self.module.functions.push(f))
     }
 
+    /// Declares a function with the given signature without building its
+    /// body, and returns its id.
+    ///
+    /// The declared function can be called right away, so functions can be
+    /// called before their definition, including mutually recursive ones.
+    /// The body can be built later after calling
+    /// [`define_function`](struct.Builder.html#method.define_function) with
+    /// the returned id. Functions never defined are kept as function
+    /// declarations, e.g., for linkage, ahead of all function definitions.
+    ///
+    /// `function_type` must be an OpTypeFunction already added to the
+    /// module; otherwise `Error::WrongOperand` is returned.
+    pub fn declare_function(
+        &mut self,
+        return_type: spirv::Word,
+        control: spirv::FunctionControl,
+        function_type: spirv::Word,
+    ) -> BuildResult<spirv::Word> {
+        let parameter_types: Vec<spirv::Word> = match self
+            .module
+            .types_global_values
+            .iter()
+            .find(|inst| {
+                inst.class.opcode == spirv::Op::TypeFunction &&
+                    inst.result_id == Some(function_type)
+            }) {
+            Some(inst) => inst.operands[1..]
+                .iter()
+                .filter_map(|operand| match *operand {
+                    mr::Operand::IdRef(id) => Some(id),
+                    _ => None,
+                })
+                .collect(),
+            None => return Err(Error::WrongOperand(spirv::Op::Function, 1)),
+        };
+
+        let id = self.id();
+        let mut f = mr::Function::new();
+        f.def = Some(mr::Instruction::new(
+            spirv::Op::Function,
+            Some(return_type),
+            Some(id),
+            vec![
+                mr::Operand::FunctionControl(control),
+                mr::Operand::IdRef(function_type),
+            ],
+        ));
+        for ty in parameter_types {
+            let parameter = self.id();
+            f.parameters.push(mr::Instruction::new(
+                spirv::Op::FunctionParameter,
+                Some(ty),
+                Some(parameter),
+                vec![],
+            ));
+        }
+        f.end = Some(mr::Instruction::new(
+            spirv::Op::FunctionEnd,
+            None,
+            None,
+            vec![],
+        ));
+
+        // Function declarations precede all function definitions.
+        let position = self
+            .module
+            .functions
+            .iter()
+            .position(|f| !f.basic_blocks.is_empty())
+            .unwrap_or(self.module.functions.len());
+        self.module.functions.insert(position, f);
+        self.function_edits.push(FunctionEdit::Inserted(position));
+        Ok(id)
+    }
+
+    /// Begins building the body of the function previously declared with
+    /// [`declare_function`](struct.Builder.html#method.declare_function)
+    /// under the given `id`, and returns the ids of its parameters.
+    ///
+    /// The function becomes the function under construction, just like
+    /// after [`begin_function`](struct.Builder.html#method.begin_function),
+    /// with its parameters already declared.
+    pub fn define_function(&mut self, id: spirv::Word) -> BuildResult<Vec<spirv::Word>> {
+        if self.function.is_some() {
+            return Err(Error::NestedFunction);
+        }
+        let index = self
+            .module
+            .functions
+            .iter()
+            .position(|f| {
+                f.basic_blocks.is_empty() &&
+                    f.def.as_ref().and_then(|def| def.result_id) == Some(id)
+            })
+            .ok_or(Error::FunctionNotFound(id))?;
+
+        let declaration = self.module.functions.remove(index);
+        let mut f = declaration.clone();
+        self.function_edits.push(FunctionEdit::Removed(index, Box::new(declaration)));
+        f.end = None;
+        let parameters = f.parameters.iter().filter_map(|p| p.result_id).collect();
+        self.function = Some(f);
+        Ok(parameters)
+    }
+
     /// Declares a formal parameter for the current function.
     pub fn function_parameter(&mut self, result_type: spirv::Word) -> BuildResult<spirv::Word> {
         if self.function.is_none() {
@@ -257,6 +566,26 @@ impl Builder {
         ))
     }
 
+    /// Appends an OpSwitch instruction with a 64-bit integer `selector` and
+    /// ends the current basic block.
+    ///
+    /// Each case literal is encoded as two words; use
+    /// [`switch`](struct.Builder.html#method.switch) for narrower selectors.
+    pub fn switch_u64<T: AsRef<[(u64, spirv::Word)]>>(
+        &mut self,
+        selector: spirv::Word,
+        default: spirv::Word,
+        target: T,
+    ) -> BuildResult<()> {
+        let mut operands = vec![mr::Operand::IdRef(selector), mr::Operand::IdRef(default)];
+        for &(literal, label) in target.as_ref() {
+            operands.push(mr::Operand::LiteralInt64(literal));
+            operands.push(mr::Operand::IdRef(label));
+        }
+        let inst = mr::Instruction::new(spirv::Op::Switch, None, None, operands);
+        self.end_basic_block(inst)
+    }
+
     /// Appends an OpCapability instruction.
     pub fn capability(&mut self, capability: spirv::Capability) {
         let inst = mr::Instruction::new(
@@ -269,7 +598,7 @@ impl Builder {
     }
 
     /// Appends an OpExtension instruction.
-    pub fn extension<T: Into<String>>(&mut self, extension: T) {
+    pub fn extension<T: Into<mr::Symbol>>(&mut self, extension: T) {
         let inst = mr::Instruction::new(
             spirv::Op::Extension,
             None,
@@ -280,7 +609,7 @@ impl Builder {
     }
 
     /// Appends an OpExtInstImport instruction and returns the result id.
-    pub fn ext_inst_import<T: Into<String>>(&mut self, extended_inst_set: T) -> spirv::Word {
+    pub fn ext_inst_import<T: Into<mr::Symbol>>(&mut self, extended_inst_set: T) -> spirv::Word {
         let id = self.id();
         let inst = mr::Instruction::new(
             spirv::Op::ExtInstImport,
@@ -311,7 +640,7 @@ impl Builder {
     }
 
     /// Appends an OpEntryPoint instruction.
-    pub fn entry_point<T: Into<String>, U: AsRef<[spirv::Word]>>(
+    pub fn entry_point<T: Into<mr::Symbol>, U: AsRef<[spirv::Word]>>(
         &mut self,
         execution_model: spirv::ExecutionModel,
         entry_point: spirv::Word,
@@ -331,6 +660,27 @@ impl Builder {
         self.module.entry_points.push(inst);
     }
 
+    /// Appends an OpEntryPoint instruction whose interface lists the global
+    /// variables statically used by the function `entry_point` and all
+    /// functions it calls.
+    ///
+    /// Before SPIR-V 1.4, only Input and Output variables are listed; since
+    /// SPIR-V 1.4, all global variables are. The function must already be
+    /// ended. Returns the computed interface.
+    pub fn entry_point_auto_interface<T: Into<mr::Symbol>>(
+        &mut self,
+        execution_model: spirv::ExecutionModel,
+        entry_point: spirv::Word,
+        name: T,
+    ) -> BuildResult<Vec<spirv::Word>> {
+        let interface = self
+            .version_rules()
+            .entry_point_interface(&self.module, entry_point)
+            .map_err(Error::FunctionNotFound)?;
+        self.entry_point(execution_model, entry_point, name, &interface);
+        Ok(interface)
+    }
+
     /// Appends an OpExecutionMode instruction.
     pub fn execution_mode<T: AsRef<[u32]>>(
         &mut self,
@@ -370,7 +720,7 @@ impl Builder {
         id
     }
 
-    pub fn string<T: Into<String>>(&mut self, s: T) -> spirv::Word {
+    pub fn string<T: Into<mr::Symbol>>(&mut self, s: T) -> spirv::Word {
         let id = self.id();
         self.module.debugs.push(mr::Instruction::new(
             spirv::Op::String,
@@ -381,12 +731,54 @@ impl Builder {
         id
     }
 
-    #[allow(unused_variables)]
+    /// Returns the id of an OpString instruction with the given string,
+    /// appending a new one only if there is none yet.
+    ///
+    /// This allows sharing, e.g., file names across OpSource and OpLine
+    /// instructions, which is not the case for strings appended by
+    /// [`string`](struct.Builder.html#method.string).
+    pub fn shared_string<T: Into<mr::Symbol>>(&mut self, s: T) -> spirv::Word {
+        let s = s.into();
+        let existing = self.module.debugs.iter().find(|inst| {
+            match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::String, Some(mr::Operand::LiteralString(t))) => *t == s,
+                _ => false,
+            }
+        });
+        match existing.and_then(|inst| inst.result_id) {
+            Some(id) => id,
+            None => self.string(s),
+        }
+    }
+
+    /// Appends an OpLine instruction to the current basic block, or to the
+    /// global values if there is no basic block under construction.
     pub fn line(&mut self, file: spirv::Word, line: spirv::Word, column: spirv::Word) {
-        unimplemented!()
+        let inst = mr::Instruction::new(
+            spirv::Op::Line,
+            None,
+            None,
+            vec![
+                mr::Operand::IdRef(file),
+                mr::Operand::LiteralInt32(line),
+                mr::Operand::LiteralInt32(column),
+            ],
+        );
+        self.push_location_debug(inst)
     }
+
+    /// Appends an OpNoLine instruction to the current basic block, or to the
+    /// global values if there is no basic block under construction.
     pub fn no_line(&mut self) {
-        unimplemented!()
+        let inst = mr::Instruction::new(spirv::Op::NoLine, None, None, vec![]);
+        self.push_location_debug(inst)
+    }
+
+    fn push_location_debug(&mut self, inst: mr::Instruction) {
+        match self.basic_block {
+            Some(ref mut bb) => bb.instructions.push(inst),
+            None => self.module.types_global_values.push(inst),
+        }
     }
 }
 
@@ -432,7 +824,7 @@ impl Builder {
     }
 
     /// Appends an OpTypeOpaque instruction and returns the result id.
-    pub fn type_opaque<T: Into<String>>(&mut self, type_name: T) -> spirv::Word {
+    pub fn type_opaque<T: Into<mr::Symbol>>(&mut self, type_name: T) -> spirv::Word {
         let id = self.id();
         self.module.types_global_values.push(mr::Instruction::new(
             spirv::Op::TypeOpaque,
@@ -471,6 +863,38 @@ impl Builder {
         id
     }
 
+    /// Appends an OpConstant instruction with the given 64-bit float `value`.
+    ///
+    /// The value is encoded as two literal words; `result_type` must be a
+    /// 64-bit type.
+    pub fn constant_f64(&mut self, result_type: spirv::Word, value: f64) -> spirv::Word {
+        let id = self.id();
+        let inst = mr::Instruction::new(
+            spirv::Op::Constant,
+            Some(result_type),
+            Some(id),
+            vec![mr::Operand::LiteralFloat64(value)],
+        );
+        self.module.types_global_values.push(inst);
+        id
+    }
+
+    /// Appends an OpConstant instruction with the given 64-bit integer `value`.
+    ///
+    /// The value is encoded as two literal words; `result_type` must be a
+    /// 64-bit type.
+    pub fn constant_u64(&mut self, result_type: spirv::Word, value: u64) -> spirv::Word {
+        let id = self.id();
+        let inst = mr::Instruction::new(
+            spirv::Op::Constant,
+            Some(result_type),
+            Some(id),
+            vec![mr::Operand::LiteralInt64(value)],
+        );
+        self.module.types_global_values.push(inst);
+        id
+    }
+
     /// Appends an OpSpecConstant instruction with the given 32-bit float `value`.
     /// or the module if no basic block is under construction.
     pub fn spec_constant_f32(&mut self, result_type: spirv::Word, value: f32) -> spirv::Word {
@@ -499,6 +923,229 @@ impl Builder {
         id
     }
 
+    /// Appends an OpSpecConstant instruction with the given 64-bit float `value`.
+    ///
+    /// The value is encoded as two literal words; `result_type` must be a
+    /// 64-bit type.
+    pub fn spec_constant_f64(&mut self, result_type: spirv::Word, value: f64) -> spirv::Word {
+        let id = self.id();
+        let inst = mr::Instruction::new(
+            spirv::Op::SpecConstant,
+            Some(result_type),
+            Some(id),
+            vec![mr::Operand::LiteralFloat64(value)],
+        );
+        self.module.types_global_values.push(inst);
+        id
+    }
+
+    /// Appends an OpSpecConstant instruction with the given 64-bit integer `value`.
+    ///
+    /// The value is encoded as two literal words; `result_type` must be a
+    /// 64-bit type.
+    pub fn spec_constant_u64(&mut self, result_type: spirv::Word, value: u64) -> spirv::Word {
+        let id = self.id();
+        let inst = mr::Instruction::new(
+            spirv::Op::SpecConstant,
+            Some(result_type),
+            Some(id),
+            vec![mr::Operand::LiteralInt64(value)],
+        );
+        self.module.types_global_values.push(inst);
+        id
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing the given `opcode`
+    /// on the given `operands`, which are the operands of the instruction
+    /// with that opcode excluding its result type and result id.
+    ///
+    /// Returns an error if `opcode` is not allowed in OpSpecConstantOp or
+    /// the operands do not match the grammar of `opcode`.
+    pub fn spec_constant_op(
+        &mut self,
+        result_type: spirv::Word,
+        opcode: spirv::Op,
+        operands: Vec<mr::Operand>,
+    ) -> BuildResult<spirv::Word> {
+        if !grammar::reflect::is_spec_constant_op_allowed(opcode) {
+            return Err(Error::DisallowedSpecConstantOp(opcode));
+        }
+        // Check the operands as if they were used in a normal instruction.
+        let inst = mr::Instruction::new(opcode, Some(result_type), Some(0), operands);
+        check_operands(&inst)?;
+
+        let id = self.id();
+        let mut operands = vec![mr::Operand::LiteralSpecConstantOpInteger(opcode)];
+        operands.extend(inst.operands);
+        self.module.types_global_values.push(mr::Instruction::new(
+            spirv::Op::SpecConstantOp,
+            Some(result_type),
+            Some(id),
+            operands,
+        ));
+        Ok(id)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing the binary `opcode`
+    /// on `operand_1` and `operand_2`.
+    fn spec_constant_binary(
+        &mut self,
+        result_type: spirv::Word,
+        opcode: spirv::Op,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        let operands = vec![mr::Operand::IdRef(operand_1), mr::Operand::IdRef(operand_2)];
+        self.spec_constant_op(result_type, opcode, operands).expect("allowed binary opcode")
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpIAdd.
+    pub fn spec_constant_iadd(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::IAdd, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpISub.
+    pub fn spec_constant_isub(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::ISub, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpIMul.
+    pub fn spec_constant_imul(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::IMul, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpUDiv.
+    pub fn spec_constant_udiv(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::UDiv, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpSDiv.
+    pub fn spec_constant_sdiv(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::SDiv, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpIEqual.
+    pub fn spec_constant_iequal(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::IEqual, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpINotEqual.
+    pub fn spec_constant_inot_equal(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::INotEqual, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpULessThan.
+    pub fn spec_constant_uless_than(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::ULessThan, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpSLessThan.
+    pub fn spec_constant_sless_than(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::SLessThan, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpUGreaterThan.
+    pub fn spec_constant_ugreater_than(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::UGreaterThan, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpSGreaterThan.
+    pub fn spec_constant_sgreater_than(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::SGreaterThan, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpLogicalAnd.
+    pub fn spec_constant_logical_and(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::LogicalAnd, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction performing an OpLogicalOr.
+    pub fn spec_constant_logical_or(
+        &mut self,
+        result_type: spirv::Word,
+        operand_1: spirv::Word,
+        operand_2: spirv::Word,
+    ) -> spirv::Word {
+        self.spec_constant_binary(result_type, spirv::Op::LogicalOr, operand_1, operand_2)
+    }
+
+    /// Appends an OpSpecConstantOp instruction selecting between `object_1`
+    /// and `object_2` depending on `condition` (OpSelect).
+    pub fn spec_constant_select(
+        &mut self,
+        result_type: spirv::Word,
+        condition: spirv::Word,
+        object_1: spirv::Word,
+        object_2: spirv::Word,
+    ) -> spirv::Word {
+        let operands = vec![
+            mr::Operand::IdRef(condition),
+            mr::Operand::IdRef(object_1),
+            mr::Operand::IdRef(object_2),
+        ];
+        self.spec_constant_op(result_type, spirv::Op::Select, operands).expect("allowed opcode")
+    }
+
     /// Appends an OpVariable instruction to either the current basic block
     /// or the module if no basic block is under construction.
     pub fn variable(
@@ -546,17 +1193,914 @@ impl Builder {
     }
 }
 
-include!("build_norm_insts.rs");
+/// A scalar constant known to the folding layer, with its bit width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Scalar {
+    Int(u64, u32),
+    Float(f64, u32),
+}
 
-#[cfg(test)]
-mod tests {
-    use mr;
-    use spirv;
+/// The scalar constants of 32 or 64 bits among the global values of the
+/// module under construction, indexed by id.
+///
+/// Global values are only ever appended while building, so the index is
+/// brought up to date by scanning the instructions added since.
+#[derive(Default)]
+struct ScalarConstants {
+    /// The number of global values scanned so far.
+    scanned: usize,
+    /// The opcodes and widths of the integer and floating-point types.
+    types: BTreeMap<spirv::Word, (spirv::Op, u32)>,
+    values: BTreeMap<spirv::Word, Scalar>,
+}
+
+impl ScalarConstants {
+    /// Indexes the global values not scanned yet.
+    fn update(&mut self, globals: &[mr::Instruction]) {
+        if globals.len() < self.scanned {
+            *self = ScalarConstants::default();
+        }
+        for inst in &globals[self.scanned..] {
+            let id = match inst.result_id {
+                Some(id) => id,
+                None => continue,
+            };
+            let opcode = inst.class.opcode;
+            match (opcode, inst.operands.first()) {
+                (spirv::Op::TypeInt, Some(&mr::Operand::LiteralInt32(width))) |
+                (spirv::Op::TypeFloat, Some(&mr::Operand::LiteralInt32(width))) => {
+                    self.types.insert(id, (opcode, width));
+                }
+                (spirv::Op::Constant, Some(value)) => {
+                    let ty = inst.result_type.and_then(|ty| self.types.get(&ty));
+                    let scalar = match (ty, value) {
+                        (Some(&(spirv::Op::TypeInt, 32)), &mr::Operand::LiteralInt32(v)) => {
+                            Scalar::Int(v as u64, 32)
+                        }
+                        (Some(&(spirv::Op::TypeInt, 64)), &mr::Operand::LiteralInt64(v)) => {
+                            Scalar::Int(v, 64)
+                        }
+                        (Some(&(spirv::Op::TypeFloat, 32)), &mr::Operand::LiteralFloat32(v)) => {
+                            Scalar::Float(v as f64, 32)
+                        }
+                        (Some(&(spirv::Op::TypeFloat, 64)), &mr::Operand::LiteralFloat64(v)) => {
+                            Scalar::Float(v, 64)
+                        }
+                        _ => continue,
+                    };
+                    self.values.insert(id, scalar);
+                }
+                _ => {}
+            }
+        }
+        self.scanned = globals.len();
+    }
+}
+
+/// Returns true if instructions of the given `opcode` may be folded.
+fn is_foldable(opcode: spirv::Op) -> bool {
+    matches!(
+        opcode,
+        spirv::Op::Bitcast |
+            spirv::Op::SNegate |
+            spirv::Op::Not |
+            spirv::Op::FNegate |
+            spirv::Op::IAdd |
+            spirv::Op::ISub |
+            spirv::Op::IMul |
+            spirv::Op::UDiv |
+            spirv::Op::SDiv |
+            spirv::Op::UMod |
+            spirv::Op::BitwiseAnd |
+            spirv::Op::BitwiseOr |
+            spirv::Op::BitwiseXor |
+            spirv::Op::ShiftLeftLogical |
+            spirv::Op::ShiftRightLogical |
+            spirv::Op::ShiftRightArithmetic |
+            spirv::Op::FAdd |
+            spirv::Op::FSub |
+            spirv::Op::FMul |
+            spirv::Op::FDiv
+    )
+}
+
+/// Returns the mask covering the bits of an integer of the given `width`.
+fn int_mask(width: u32) -> u64 {
+    if width == 64 { !0 } else { (1 << width) - 1 }
+}
+
+/// Returns true if `value` is the identity element `e` of the binary
+/// `opcode` when used as its second operand.
+fn is_right_identity(opcode: spirv::Op, value: Option<Scalar>) -> bool {
+    match value {
+        Some(Scalar::Int(v, width)) => {
+            let e = match opcode {
+                spirv::Op::IAdd |
+                spirv::Op::ISub |
+                spirv::Op::BitwiseOr |
+                spirv::Op::BitwiseXor |
+                spirv::Op::ShiftLeftLogical |
+                spirv::Op::ShiftRightLogical |
+                spirv::Op::ShiftRightArithmetic => 0,
+                spirv::Op::IMul | spirv::Op::UDiv | spirv::Op::SDiv => 1,
+                spirv::Op::BitwiseAnd => int_mask(width),
+                _ => return false,
+            };
+            v == e
+        }
+        Some(Scalar::Float(v, _)) => {
+            let e: f64 = match opcode {
+                // x + 0.0 is not x for x = -0.0, but x + -0.0 always is.
+                spirv::Op::FAdd => -0.0,
+                spirv::Op::FSub => 0.0,
+                spirv::Op::FMul | spirv::Op::FDiv => 1.0,
+                _ => return false,
+            };
+            // Compare bitwise to tell 0.0 and -0.0 apart.
+            v.to_bits() == e.to_bits()
+        }
+        None => false,
+    }
+}
+
+/// Evaluates the unary `opcode` on the constant `a`.
+fn eval_unary(opcode: spirv::Op, a: Scalar) -> Option<Scalar> {
+    match (opcode, a) {
+        (spirv::Op::SNegate, Scalar::Int(a, width)) => {
+            Some(Scalar::Int(a.wrapping_neg() & int_mask(width), width))
+        }
+        (spirv::Op::Not, Scalar::Int(a, width)) => Some(Scalar::Int(!a & int_mask(width), width)),
+        (spirv::Op::FNegate, Scalar::Float(a, width)) => Some(Scalar::Float(-a, width)),
+        _ => None,
+    }
+}
+
+/// Evaluates the binary `opcode` on the constants `a` and `b`.
+fn eval_binary(opcode: spirv::Op, a: Scalar, b: Scalar) -> Option<Scalar> {
+    match (a, b) {
+        (Scalar::Int(a, width), Scalar::Int(b, b_width)) if width == b_width => {
+            let value = match opcode {
+                spirv::Op::IAdd => a.wrapping_add(b),
+                spirv::Op::ISub => a.wrapping_sub(b),
+                spirv::Op::IMul => a.wrapping_mul(b),
+                spirv::Op::UDiv if b != 0 => a / b,
+                spirv::Op::UMod if b != 0 => a % b,
+                spirv::Op::BitwiseAnd => a & b,
+                spirv::Op::BitwiseOr => a | b,
+                spirv::Op::BitwiseXor => a ^ b,
+                spirv::Op::ShiftLeftLogical if b < width as u64 => a << b,
+                spirv::Op::ShiftRightLogical if b < width as u64 => a >> b,
+                _ => return None,
+            };
+            Some(Scalar::Int(value & int_mask(width), width))
+        }
+        // Single precision results computed in double precision are still
+        // correctly rounded once converted back.
+        (Scalar::Float(a, width), Scalar::Float(b, b_width)) if width == b_width => {
+            let value = match opcode {
+                spirv::Op::FAdd => a + b,
+                spirv::Op::FSub => a - b,
+                spirv::Op::FMul => a * b,
+                spirv::Op::FDiv => a / b,
+                _ => return None,
+            };
+            Some(Scalar::Float(value, width))
+        }
+        _ => None,
+    }
+}
+
+impl Builder {
+    /// Enables or disables folding instructions at emission time.
+    ///
+    /// When enabled, appending an arithmetic instruction on scalar integer
+    /// or floating-point constants of 32 or 64 bits emits the resulting
+    /// OpConstant instead, and identity operations (e.g., adding zero,
+    /// multiplying by one, or bitcasting to the same type) return their
+    /// operand instead of emitting anything. The returned id is then that
+    /// of the constant or the operand. Instructions with an explicitly
+    /// given result id are never folded. Folding is disabled by default.
+    pub fn set_folding(&mut self, folding: bool) {
+        self.folding = folding;
+    }
+
+    /// Enables or disables checking OpPhi instructions when ending
+    /// functions.
+    ///
+    /// When enabled, [`end_function`](struct.Builder.html#method.end_function)
+    /// checks that every parent block named by an OpPhi instruction is a
+    /// predecessor of the basic block containing the OpPhi, and returns
+    /// `Error::WrongPhiParent` otherwise. This allows building OpPhi
+    /// instructions referring to blocks not built yet, while still catching
+    /// mistakes once the whole function is known. Checking is disabled by
+    /// default.
+    pub fn set_phi_checking(&mut self, checking: bool) {
+        self.phi_checking = checking;
+    }
+
+    /// Returns the instruction defining `id` among the global values and
+    /// the function under construction.
+    fn find_def(&self, id: spirv::Word) -> Option<&mr::Instruction> {
+        let function = self.function.iter().flat_map(|f| {
+            f.parameters.iter().chain(f.basic_blocks.iter().flat_map(|bb| &bb.instructions))
+        });
+        let basic_block = self.basic_block.iter().flat_map(|bb| &bb.instructions);
+        self.module.types_global_values
+            .iter()
+            .chain(function)
+            .chain(basic_block)
+            .find(|inst| inst.result_id == Some(id))
+    }
+
+    /// Returns the value of `id` if it is a scalar OpConstant of 32 or 64
+    /// bits, as of the last update of the scalar constants.
+    fn find_scalar(&self, id: spirv::Word) -> Option<Scalar> {
+        self.scalars.values.get(&id).cloned()
+    }
+
+    /// Folds the given instruction, just built with a freshly allocated
+    /// result id, returning the id to use instead if possible.
+    fn fold(&mut self, inst: &mr::Instruction) -> Option<spirv::Word> {
+        let opcode = inst.class.opcode;
+        if !self.folding || !is_foldable(opcode) {
+            return None;
+        }
+        let result_type = inst.result_type?;
+        self.scalars.update(&self.module.types_global_values);
+        let ids: Vec<spirv::Word> = inst.operands
+            .iter()
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        if ids.len() != inst.operands.len() {
+            return None;
+        }
+        let values: Vec<Option<Scalar>> = ids.iter().map(|&id| self.find_scalar(id)).collect();
+        let has_result_type = |id| {
+            self.find_def(id).and_then(|def| def.result_type) == Some(result_type)
+        };
+
+        let (identity, value) = match (&ids[..], &values[..]) {
+            (&[x], _) if opcode == spirv::Op::Bitcast => {
+                (Some(x).filter(|&x| has_result_type(x)), None)
+            }
+            (&[_], &[Some(a)]) => (None, eval_unary(opcode, a)),
+            (&[x, y], &[a, b]) => {
+                let identity = if is_right_identity(opcode, b) {
+                    Some(x)
+                } else if opcode.is_commutative() && is_right_identity(opcode, a) {
+                    Some(y)
+                } else {
+                    None
+                };
+                let value = match (a, b) {
+                    (Some(a), Some(b)) => eval_binary(opcode, a, b),
+                    _ => None,
+                };
+                // Operands may differ from the result in signedness.
+                (identity.filter(|&id| has_result_type(id)), value)
+            }
+            _ => (None, None),
+        };
+        if identity.is_none() && value.is_none() {
+            return None;
+        }
+
+        // Reclaim the result id allocated for the folded instruction.
+        if inst.result_id == Some(self.next_id - 1) {
+            self.next_id -= 1;
+        }
+        match (identity, value) {
+            (Some(id), _) => Some(id),
+            (None, Some(Scalar::Int(v, 32))) => Some(self.constant_u32(result_type, v as u32)),
+            (None, Some(Scalar::Int(v, _))) => Some(self.constant_u64(result_type, v)),
+            (None, Some(Scalar::Float(v, 32))) => Some(self.constant_f32(result_type, v as f32)),
+            (None, Some(Scalar::Float(v, _))) => Some(self.constant_f64(result_type, v)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Builder {
+    /// Appends an OpAccessChain instruction to the current basic block
+    /// with the result type inferred, and returns the result id.
+    ///
+    /// The type pointed to by `base` is walked through the `indexes`:
+    /// structures are indexed by their members, which must be given as
+    /// integer OpConstants, and arrays, runtime arrays, vectors, and
+    /// matrices by their elements, components, and columns, respectively.
+    /// The result type is a pointer to the final type in the storage class
+    /// of `base`; if no such OpTypePointer exists yet, one is added.
+    ///
+    /// Returns `Error::WrongAccessChainBase` if the type of `base` is not
+    /// known to be a pointer, and `Error::WrongAccessChainIndex` with the
+    /// position of the first index not selecting anything.
+    pub fn access_chain_typed<T: AsRef<[spirv::Word]>>(
+        &mut self,
+        base: spirv::Word,
+        indexes: T,
+    ) -> BuildResult<spirv::Word> {
+        let (storage_class, mut ty) = self
+            .find_def(base)
+            .and_then(|inst| inst.result_type)
+            .and_then(|ty| self.find_def(ty))
+            .and_then(|ty| match (ty.class.opcode, ty.operands.first(), ty.operands.get(1)) {
+                (spirv::Op::TypePointer,
+                 Some(&mr::Operand::StorageClass(storage_class)),
+                 Some(&mr::Operand::IdRef(pointee))) => Some((storage_class, pointee)),
+                _ => None,
+            })
+            .ok_or(Error::WrongAccessChainBase(base))?;
+
+        self.scalars.update(&self.module.types_global_values);
+        for (position, &index) in indexes.as_ref().iter().enumerate() {
+            let inst = self.find_def(ty).ok_or(Error::WrongAccessChainIndex(position))?;
+            let element = match inst.class.opcode {
+                spirv::Op::TypeStruct => match self.find_scalar(index) {
+                    Some(Scalar::Int(member, _)) => inst.operands.get(member as usize),
+                    _ => None,
+                },
+                spirv::Op::TypeArray |
+                spirv::Op::TypeRuntimeArray |
+                spirv::Op::TypeVector |
+                spirv::Op::TypeMatrix => inst.operands.first(),
+                _ => None,
+            };
+            ty = match element {
+                Some(&mr::Operand::IdRef(element)) => element,
+                _ => return Err(Error::WrongAccessChainIndex(position)),
+            };
+        }
+
+        let pointer = self.module.types_global_values.iter().find(|inst| {
+            inst.class.opcode == spirv::Op::TypePointer &&
+                inst.operands[0] == mr::Operand::StorageClass(storage_class) &&
+                inst.operands[1] == mr::Operand::IdRef(ty)
+        });
+        let result_type = match pointer.and_then(|inst| inst.result_id) {
+            Some(id) => id,
+            None => self.type_pointer(None, storage_class, ty),
+        };
+        self.access_chain(result_type, None, base, indexes)
+    }
+}
+
+/// Returns true if the given basic `block` ends with a branch to `label`.
+fn branches_to(block: &mr::BasicBlock, label: spirv::Word) -> bool {
+    let terminator = match block.instructions.last() {
+        Some(inst) => inst,
+        None => return false,
+    };
+    // Skip the condition of OpBranchConditional and the selector of OpSwitch.
+    let skip = match terminator.class.opcode {
+        spirv::Op::Branch => 0,
+        spirv::Op::BranchConditional | spirv::Op::Switch => 1,
+        _ => return false,
+    };
+    terminator.operands[skip..].contains(&mr::Operand::IdRef(label))
+}
+
+/// Checks that the parents of all OpPhi instructions in the given
+/// `function` are predecessors of the basic blocks containing them.
+fn check_phis(function: &mr::Function) -> BuildResult<()> {
+    for block in &function.basic_blocks {
+        let label = match block.label.as_ref().and_then(|l| l.result_id) {
+            Some(label) => label,
+            None => continue,
+        };
+        let phis = block.instructions.iter().take_while(|i| i.class.opcode == spirv::Op::Phi);
+        for phi in phis {
+            // (value, parent) pairs.
+            for operand in phi.operands.iter().skip(1).step_by(2) {
+                let parent = match *operand {
+                    mr::Operand::IdRef(parent) => parent,
+                    _ => continue,
+                };
+                let is_predecessor = function.basic_blocks.iter().any(|bb| {
+                    bb.label.as_ref().and_then(|l| l.result_id) == Some(parent) &&
+                        branches_to(bb, label)
+                });
+                if !is_predecessor {
+                    return Err(Error::WrongPhiParent(phi.result_id.unwrap_or(0), parent));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Labels of the basic blocks making up a loop built with
+/// [`Builder::loop_`](struct.Builder.html#method.loop_).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoopBlocks {
+    /// The loop header, evaluating the loop condition.
+    pub header: spirv::Word,
+    /// The first basic block of the loop body.
+    pub body: spirv::Word,
+    /// The continue target, branching back to the header.
+    pub continue_target: spirv::Word,
+    /// The merge block, following the loop.
+    pub merge: spirv::Word,
+}
+
+impl Builder {
+    /// Ends the current basic block, if any, with an OpBranch to `target`.
+    fn branch_if_open(&mut self, target: spirv::Word) -> BuildResult<()> {
+        match self.basic_block {
+            Some(_) => self.branch(target),
+            None => Ok(()),
+        }
+    }
+
+    /// Builds a structured if-then-else construct ending the current basic
+    /// block, and begins its merge block.
+    ///
+    /// The current basic block is ended with an OpSelectionMerge and an
+    /// OpBranchConditional on `condition`. `then_` and `else_` are then
+    /// called to fill in the two branches, each with a new basic block
+    /// begun. If they leave a basic block open, it is ended with an
+    /// OpBranch to the merge block. Returns the label of the merge block,
+    /// which is the basic block under construction afterwards.
+    pub fn if_then_else<T, E>(
+        &mut self,
+        condition: spirv::Word,
+        then_: T,
+        else_: E,
+    ) -> BuildResult<spirv::Word>
+    where
+        T: FnOnce(&mut Builder) -> BuildResult<()>,
+        E: FnOnce(&mut Builder) -> BuildResult<()>,
+    {
+        let then_label = self.id();
+        let else_label = self.id();
+        let merge = self.id();
+        self.selection_merge(merge, spirv::SelectionControl::NONE)?;
+        self.branch_conditional(condition, then_label, else_label, vec![])?;
+
+        self.begin_basic_block(Some(then_label))?;
+        then_(self)?;
+        self.branch_if_open(merge)?;
+        self.begin_basic_block(Some(else_label))?;
+        else_(self)?;
+        self.branch_if_open(merge)?;
+
+        self.begin_basic_block(Some(merge))
+    }
+
+    /// Builds a structured loop ending the current basic block, and begins
+    /// its merge block.
+    ///
+    /// The current basic block is ended with an OpBranch to the loop
+    /// header. `header` is called with the header begun and returns the
+    /// loop condition; the header is then ended with an OpLoopMerge and an
+    /// OpBranchConditional to the body or the merge block. `body` and
+    /// `continue_` are called with the body and the continue target begun,
+    /// respectively. If they leave a basic block open, it is ended with an
+    /// OpBranch to the continue target and the header, respectively. All
+    /// closures get the labels of the loop blocks, e.g., for OpPhi
+    /// instructions in the header or early exits from the body. Returns the
+    /// label of the merge block, which is the basic block under
+    /// construction afterwards.
+    pub fn loop_<H, B, C>(&mut self, header: H, body: B, continue_: C) -> BuildResult<spirv::Word>
+    where
+        H: FnOnce(&mut Builder, LoopBlocks) -> BuildResult<spirv::Word>,
+        B: FnOnce(&mut Builder, LoopBlocks) -> BuildResult<()>,
+        C: FnOnce(&mut Builder, LoopBlocks) -> BuildResult<()>,
+    {
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        let blocks = LoopBlocks {
+            header: self.id(),
+            body: self.id(),
+            continue_target: self.id(),
+            merge: self.id(),
+        };
+        self.branch(blocks.header)?;
+
+        self.begin_basic_block(Some(blocks.header))?;
+        let condition = header(self, blocks)?;
+        self.loop_merge(blocks.merge, blocks.continue_target, spirv::LoopControl::NONE, vec![])?;
+        self.branch_conditional(condition, blocks.body, blocks.merge, vec![])?;
+
+        self.begin_basic_block(Some(blocks.body))?;
+        body(self, blocks)?;
+        self.branch_if_open(blocks.continue_target)?;
+        self.begin_basic_block(Some(blocks.continue_target))?;
+        continue_(self, blocks)?;
+        self.branch_if_open(blocks.header)?;
+
+        self.begin_basic_block(Some(blocks.merge))
+    }
+}
+
+/// A snapshot of the state of a [`Builder`](struct.Builder.html), to which
+/// the builder can be rolled back.
+///
+/// See [`Builder::snapshot`](struct.Builder.html#method.snapshot).
+#[derive(Clone, Debug)]
+pub struct BuilderSnapshot {
+    next_id: u32,
+    version: Option<(u8, u8)>,
+    memory_model: Option<mr::Instruction>,
+    /// Lengths of the module sections; see `sections_mut`.
+    sections: [usize; 8],
+    functions: usize,
+    function_edits: usize,
+    /// Lengths of the parameters and basic blocks of the function under
+    /// construction.
+    function: Option<(usize, usize)>,
+    /// Length of the instructions of the basic block under construction.
+    basic_block: Option<usize>,
+}
+
+/// An edit to the function list of a [`Builder`](struct.Builder.html)
+/// other than appending a function.
+#[derive(Clone, Debug)]
+enum FunctionEdit {
+    /// A function declaration was inserted at the given index.
+    Inserted(usize),
+    /// The function declaration at the given index was removed.
+    Removed(usize, Box<mr::Function>),
+}
+
+/// Returns the module sections consisting of plain instruction vectors.
+fn sections_mut(module: &mut mr::Module) -> [&mut Vec<mr::Instruction>; 8] {
+    [
+        &mut module.capabilities,
+        &mut module.extensions,
+        &mut module.ext_inst_imports,
+        &mut module.entry_points,
+        &mut module.execution_modes,
+        &mut module.debugs,
+        &mut module.annotations,
+        &mut module.types_global_values,
+    ]
+}
+
+impl Builder {
+    /// Takes a snapshot of the current state of the builder.
+    ///
+    /// Taking a snapshot is cheap: it only records the lengths of the
+    /// module sections and the position of the function and basic block
+    /// under construction. Together with
+    /// [`rollback`](struct.Builder.html#method.rollback), this allows code
+    /// generators to speculatively emit instructions, measure them, and
+    /// discard them again.
+    pub fn snapshot(&self) -> BuilderSnapshot {
+        let mut sections = [0; 8];
+        let module = &self.module;
+        for (length, section) in sections.iter_mut().zip(&[
+            &module.capabilities,
+            &module.extensions,
+            &module.ext_inst_imports,
+            &module.entry_points,
+            &module.execution_modes,
+            &module.debugs,
+            &module.annotations,
+            &module.types_global_values,
+        ]) {
+            *length = section.len();
+        }
+        BuilderSnapshot {
+            next_id: self.next_id,
+            version: self.version,
+            memory_model: module.memory_model.clone(),
+            sections,
+            functions: module.functions.len(),
+            function_edits: self.function_edits.len(),
+            function: self.function.as_ref().map(|f| (f.parameters.len(), f.basic_blocks.len())),
+            basic_block: self.basic_block.as_ref().map(|bb| bb.instructions.len()),
+        }
+    }
+
+    /// Takes a checkpoint of the current state of the builder.
+    ///
+    /// This is the same as [`snapshot`](struct.Builder.html#method.snapshot).
+    pub fn checkpoint(&self) -> BuilderSnapshot {
+        self.snapshot()
+    }
+
+    /// Rolls the builder back to the state of the given `snapshot`.
+    ///
+    /// All instructions appended since the snapshot was taken are removed,
+    /// including functions and basic blocks begun or ended, and the id
+    /// counter is restored, so the discarded ids are handed out again.
+    /// Function declarations defined since are restored as well.
+    /// Changes made via [`module_mut`](struct.Builder.html#method.module_mut)
+    /// other than appending are not undone. The snapshot must have been
+    /// taken from this builder and not rolled over by an earlier rollback.
+    pub fn rollback(&mut self, snapshot: BuilderSnapshot) {
+        self.next_id = snapshot.next_id;
+        self.version = snapshot.version;
+        self.module.memory_model = snapshot.memory_model;
+        self.scalars = ScalarConstants::default();
+        let mut sections = sections_mut(&mut self.module);
+        for (section, &length) in sections.iter_mut().zip(&snapshot.sections) {
+            section.truncate(length);
+        }
+
+        let functions = &mut self.module.functions;
+        // Undo the declarations inserted and removed since, which leaves
+        // the functions ended since at the end of the list.
+        for edit in self.function_edits.drain(snapshot.function_edits..).rev() {
+            match edit {
+                FunctionEdit::Inserted(index) => {
+                    functions.remove(index);
+                }
+                FunctionEdit::Removed(index, declaration) => functions.insert(index, *declaration),
+            }
+        }
+        let (parameters, basic_blocks) = match snapshot.function {
+            Some(lengths) => lengths,
+            None => {
+                functions.truncate(snapshot.functions);
+                self.function = None;
+                self.basic_block = None;
+                return;
+            }
+        };
+        // The function under construction may have been ended since, even
+        // with its basic block still open, which is then kept below.
+        let mut function = if functions.len() > snapshot.functions {
+            functions.truncate(snapshot.functions + 1);
+            functions.pop().unwrap()
+        } else {
+            self.function.take().unwrap()
+        };
+        function.end = None;
+        function.parameters.truncate(parameters);
+
+        // Similarly for the basic block under construction.
+        self.basic_block = match snapshot.basic_block {
+            Some(instructions) => {
+                let mut bb = if function.basic_blocks.len() > basic_blocks {
+                    function.basic_blocks.truncate(basic_blocks + 1);
+                    function.basic_blocks.pop().unwrap()
+                } else {
+                    self.basic_block.take().unwrap()
+                };
+                bb.instructions.truncate(instructions);
+                Some(bb)
+            }
+            None => None,
+        };
+        function.basic_blocks.truncate(basic_blocks);
+        self.function = Some(function);
+    }
+
+    /// Appends the given instruction to the current basic block.
+    ///
+    /// The operands of the instruction are checked against the grammar
+    /// first. If the instruction is a terminator, the current basic block
+    /// is ended.
+    pub fn insert_into_block(&mut self, inst: mr::Instruction) -> BuildResult<()> {
+        check_operands(&inst)?;
+        if self.basic_block.is_none() {
+            return Err(Error::DetachedInstruction);
+        }
+        if grammar::reflect::is_terminator(inst.class.opcode) {
+            return self.end_basic_block(inst);
+        }
+        self.basic_block.as_mut().unwrap().instructions.push(inst);
+        Ok(())
+    }
+
+    /// Appends the given instruction to the module section it belongs to.
+    ///
+    /// The operands of the instruction are checked against the grammar
+    /// first. Instructions that cannot appear outside of functions are
+    /// rejected with `Error::DetachedInstruction`.
+    pub fn insert_global(&mut self, inst: mr::Instruction) -> BuildResult<()> {
+        check_operands(&inst)?;
+        let opcode = inst.class.opcode;
+        match opcode {
+            spirv::Op::Capability => self.module.capabilities.push(inst),
+            spirv::Op::Extension => self.module.extensions.push(inst),
+            spirv::Op::ExtInstImport => self.module.ext_inst_imports.push(inst),
+            spirv::Op::MemoryModel => self.module.memory_model = Some(inst),
+            spirv::Op::EntryPoint => self.module.entry_points.push(inst),
+            spirv::Op::ExecutionMode | spirv::Op::ExecutionModeId => {
+                self.module.execution_modes.push(inst)
+            }
+            spirv::Op::Line | spirv::Op::NoLine | spirv::Op::Variable | spirv::Op::Undef => {
+                self.module.types_global_values.push(inst)
+            }
+            _ if grammar::reflect::is_nonlocation_debug(opcode) => self.module.debugs.push(inst),
+            _ => match opcode.class() {
+                OpClass::Annotation => self.module.annotations.push(inst),
+                OpClass::TypeDeclaration | OpClass::ConstantCreation => {
+                    self.module.types_global_values.push(inst)
+                }
+                _ => return Err(Error::DetachedInstruction),
+            },
+        }
+        Ok(())
+    }
+
+    /// Appends the given hand-built instruction to where it belongs.
+    ///
+    /// This is an escape hatch for instructions lacking generated helpers.
+    /// If the instruction has a result id but `inst.result_id` is `None`, a
+    /// new id is allocated; explicitly given result ids are accounted for
+    /// in the id bound. The operands are checked against the grammar
+    /// first. Module-level instructions (e.g., types, constants, debug
+    /// names, and annotations) are appended to their module section via
+    /// [`insert_global`](struct.Builder.html#method.insert_global); other
+    /// instructions are appended to the current basic block via
+    /// [`insert_into_block`](struct.Builder.html#method.insert_into_block)
+    /// if there is one.
+    ///
+    /// Returns the result id of the instruction, if any.
+    pub fn insert_raw(&mut self, mut inst: mr::Instruction) -> BuildResult<Option<spirv::Word>> {
+        let opcode = inst.class.opcode;
+        if inst.result_id.is_none() && opcode.has_result() {
+            inst.result_id = Some(self.next_id);
+        }
+        check_operands(&inst)?;
+        let result_id = inst.result_id;
+        let module_level = grammar::reflect::is_nonlocation_debug(opcode) ||
+            match opcode {
+                spirv::Op::Variable | spirv::Op::Undef | spirv::Op::Line | spirv::Op::NoLine => {
+                    false
+                }
+                _ => matches!(
+                    opcode.class(),
+                    OpClass::Annotation | OpClass::TypeDeclaration |
+                        OpClass::ConstantCreation | OpClass::ModeSetting |
+                        OpClass::Extension
+                ),
+            };
+        if module_level || self.basic_block.is_none() {
+            self.insert_global(inst)?;
+        } else {
+            self.insert_into_block(inst)?;
+        }
+        if let Some(id) = result_id {
+            self.next_id = self.next_id.max(id + 1);
+        }
+        Ok(result_id)
+    }
+}
+
+/// Returns the number of concrete operands at the beginning of `operands`
+/// matching the given logical operand `kind`, or `None` if they don't match.
+fn match_operand(kind: grammar::OperandKind, operands: &[mr::Operand]) -> Option<usize> {
+    use grammar::OperandKind as K;
+    use mr::Operand as O;
+
+    let is_id =
+        |operand: &O| matches!(*operand, O::IdRef(_) | O::IdScope(_) | O::IdMemorySemantics(_));
+    let is_literal_integer =
+        |operand: &O| matches!(*operand, O::LiteralInt32(_) | O::LiteralInt64(_));
+    let first = operands.first()?;
+    let matched = match (kind, first) {
+        (K::IdRef, _) | (K::IdScope, _) | (K::IdMemorySemantics, _) => is_id(first),
+        (K::LiteralInteger, _) => is_literal_integer(first),
+        (K::LiteralContextDependentNumber, _) => matches!(
+            *first,
+            O::LiteralInt32(_) | O::LiteralInt64(_) | O::LiteralFloat32(_) | O::LiteralFloat64(_)
+        ),
+        (K::PairLiteralIntegerIdRef, _) => {
+            return match operands.get(1) {
+                Some(second) if is_literal_integer(first) && is_id(second) => Some(2),
+                _ => None,
+            };
+        }
+        (K::PairIdRefLiteralInteger, _) => {
+            return match operands.get(1) {
+                Some(second) if is_id(first) && is_literal_integer(second) => Some(2),
+                _ => None,
+            };
+        }
+        (K::PairIdRefIdRef, _) => {
+            return match operands.get(1) {
+                Some(second) if is_id(first) && is_id(second) => Some(2),
+                _ => None,
+            };
+        }
+        (K::LiteralString, &O::LiteralString(_)) |
+        (K::LiteralExtInstInteger, &O::LiteralExtInstInteger(_)) |
+        (K::LiteralExtInstInteger, &O::ExtInstOpcode(_)) |
+        (K::LiteralSpecConstantOpInteger, &O::LiteralSpecConstantOpInteger(_)) |
+        (K::ImageOperands, &O::ImageOperands(_)) |
+        (K::FPFastMathMode, &O::FPFastMathMode(_)) |
+        (K::SelectionControl, &O::SelectionControl(_)) |
+        (K::LoopControl, &O::LoopControl(_)) |
+        (K::FunctionControl, &O::FunctionControl(_)) |
+        (K::MemorySemantics, &O::MemorySemantics(_)) |
+        (K::MemoryAccess, &O::MemoryAccess(_)) |
+        (K::KernelProfilingInfo, &O::KernelProfilingInfo(_)) |
+        (K::RayFlags, &O::RayFlags(_)) |
+        (K::CooperativeMatrixOperands, &O::CooperativeMatrixOperands(_)) |
+        (K::SourceLanguage, &O::SourceLanguage(_)) |
+        (K::ExecutionModel, &O::ExecutionModel(_)) |
+        (K::AddressingModel, &O::AddressingModel(_)) |
+        (K::MemoryModel, &O::MemoryModel(_)) |
+        (K::ExecutionMode, &O::ExecutionMode(_)) |
+        (K::StorageClass, &O::StorageClass(_)) |
+        (K::Dim, &O::Dim(_)) |
+        (K::SamplerAddressingMode, &O::SamplerAddressingMode(_)) |
+        (K::SamplerFilterMode, &O::SamplerFilterMode(_)) |
+        (K::ImageFormat, &O::ImageFormat(_)) |
+        (K::ImageChannelOrder, &O::ImageChannelOrder(_)) |
+        (K::ImageChannelDataType, &O::ImageChannelDataType(_)) |
+        (K::FPRoundingMode, &O::FPRoundingMode(_)) |
+        (K::LinkageType, &O::LinkageType(_)) |
+        (K::AccessQualifier, &O::AccessQualifier(_)) |
+        (K::FunctionParameterAttribute, &O::FunctionParameterAttribute(_)) |
+        (K::Decoration, &O::Decoration(_)) |
+        (K::BuiltIn, &O::BuiltIn(_)) |
+        (K::Scope, &O::Scope(_)) |
+        (K::GroupOperation, &O::GroupOperation(_)) |
+        (K::KernelEnqueueFlags, &O::KernelEnqueueFlags(_)) |
+        (K::Capability, &O::Capability(_)) |
+        (K::RayQueryIntersection, &O::RayQueryIntersection(_)) |
+        (K::RayQueryCommittedIntersectionType, &O::RayQueryCommittedIntersectionType(_)) |
+        (K::RayQueryCandidateIntersectionType, &O::RayQueryCandidateIntersectionType(_)) |
+        (K::CooperativeMatrixLayout, &O::CooperativeMatrixLayout(_)) |
+        (K::CooperativeMatrixUse, &O::CooperativeMatrixUse(_)) => true,
+        _ => false,
+    };
+    if !matched {
+        return None;
+    }
+    match kind {
+        // These are followed by parameters depending on their values, and
+        // are always the last logical operand.
+        K::ImageOperands |
+        K::LoopControl |
+        K::MemoryAccess |
+        K::ExecutionMode |
+        K::Decoration |
+        K::LiteralSpecConstantOpInteger => Some(operands.len()),
+        _ => Some(1),
+    }
+}
+
+/// Checks the result ids and operands of the given instruction against the
+/// grammar.
+fn check_operands(inst: &mr::Instruction) -> BuildResult<()> {
+    let opcode = inst.class.opcode;
+    if inst.result_type.is_some() != opcode.has_result_type() ||
+        inst.result_id.is_some() != opcode.has_result()
+    {
+        return Err(Error::MismatchedResult(opcode));
+    }
+
+    // Operands following decoded extended instruction opcodes are checked
+    // against the grammar of their extended instruction set instead.
+    let logicals = inst.class.operands;
+    let (logicals, ext_logicals) = match inst.operands.get(1) {
+        Some(&mr::Operand::ExtInstOpcode(ext)) => {
+            let split = logicals
+                .iter()
+                .position(|l| l.kind == grammar::OperandKind::LiteralExtInstInteger)
+                .map_or(logicals.len(), |p| p + 1);
+            (&logicals[..split], ext.operands)
+        }
+        _ => (logicals, &[][..]),
+    };
+
+    let mut index = 0;
+    for logical in logicals.iter().chain(ext_logicals) {
+        match logical.kind {
+            grammar::OperandKind::IdResultType | grammar::OperandKind::IdResult => continue,
+            _ => {}
+        }
+        loop {
+            let rest = &inst.operands[index..];
+            match match_operand(logical.kind, rest) {
+                Some(count) => index += count,
+                None if logical.quantifier != grammar::OperandQuantifier::One => break,
+                None if rest.is_empty() => return Err(Error::MissingOperand(opcode, index)),
+                None => return Err(Error::WrongOperand(opcode, index)),
+            }
+            if logical.quantifier != grammar::OperandQuantifier::ZeroOrMore {
+                break;
+            }
+        }
+    }
+    if index < inst.operands.len() {
+        return Err(Error::WrongOperand(opcode, index));
+    }
+    Ok(())
+}
+
+include!("build_norm_insts.rs");
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
 
-    use std::f32;
     use super::Builder;
 
     use binary::Disassemble;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
 
     fn has_only_one_global_inst(module: &mr::Module) -> bool {
         if !module.functions.is_empty() {
@@ -579,6 +2123,83 @@ mod tests {
         assert_eq!((1, 2), header.as_ref().unwrap().version());
     }
 
+    #[test]
+    fn test_module_ref_and_mut() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        assert_eq!(1, b.module_ref().types_global_values.len());
+        b.module_mut().types_global_values.clear();
+        assert!(b.module_ref().types_global_values.is_empty());
+        // Id allocation is unaffected.
+        assert_eq!(void + 1, b.id());
+    }
+
+    #[test]
+    fn test_from_module() {
+        let mut b = Builder::new();
+        b.set_version(1, 1);
+        let void = b.type_void();
+        let voidfvoid = b.type_function(void, vec![void]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let m = b.module();
+
+        let mut b = Builder::from_module(m);
+        // The bound of the original module is 5.
+        assert_eq!(5, b.id());
+        let float = b.type_float(32);
+        assert_eq!(6, float);
+        let m = b.module();
+        assert_eq!((1, 1), m.header.as_ref().unwrap().version());
+        assert_eq!(7, m.header.as_ref().unwrap().bound);
+        assert_eq!(1, m.functions.len());
+    }
+
+    #[test]
+    fn test_from_module_reopens_function_and_block() {
+        let mut m = mr::Module::new();
+        let mut f = mr::Function::new();
+        f.def = Some(mr::Instruction::new(
+            spirv::Op::Function,
+            Some(1),
+            Some(3),
+            vec![
+                mr::Operand::FunctionControl(spirv::FunctionControl::NONE),
+                mr::Operand::IdRef(2),
+            ],
+        ));
+        let mut bb = mr::BasicBlock::new();
+        bb.label = Some(mr::Instruction::new(spirv::Op::Label, None, Some(4), vec![]));
+        f.basic_blocks.push(bb);
+        m.functions.push(f);
+
+        let mut b = Builder::from_module(m);
+        assert!(b.begin_basic_block(None).is_err());
+        assert!(b.ret().is_ok());
+        assert!(b.end_function().is_ok());
+        let m = b.module();
+        assert_eq!(5, m.header.as_ref().unwrap().bound);
+        assert_eq!(
+            m.functions[0].disassemble(),
+            "%3 = OpFunction  %1  None %2\n\
+             %4 = OpLabel\n\
+             OpReturn\n\
+             OpFunctionEnd"
+        );
+    }
+
+    #[test]
+    fn test_shared_string() {
+        let mut b = Builder::new();
+        let file = b.shared_string("shader.glsl");
+        assert_eq!(file, b.shared_string("shader.glsl"));
+        assert_ne!(file, b.shared_string("common.glsl"));
+        assert_ne!(file, b.string("shader.glsl"));
+        assert_eq!(3, b.module_ref().debugs.len());
+    }
+
     #[test]
     fn test_memory_model() {
         let mut b = Builder::new();
@@ -970,4 +2591,691 @@ mod tests {
                     OpFunctionEnd"
         );
     }
+
+    #[test]
+    fn test_insert_global() {
+        let mut b = Builder::new();
+        let int = b.id();
+        let inst = mr::Instruction::new(
+            spirv::Op::TypeInt,
+            None,
+            Some(int),
+            vec![mr::Operand::LiteralInt32(32), mr::Operand::LiteralInt32(1)],
+        );
+        assert!(b.insert_global(inst).is_ok());
+        let inst = mr::Instruction::new(
+            spirv::Op::Decorate,
+            None,
+            None,
+            vec![
+                mr::Operand::IdRef(int),
+                mr::Operand::Decoration(spirv::Decoration::SpecId),
+                mr::Operand::LiteralInt32(3),
+            ],
+        );
+        assert!(b.insert_global(inst).is_ok());
+        let m = b.module();
+        assert_eq!("%1 = OpTypeInt 32 1", m.types_global_values[0].disassemble());
+        assert_eq!("OpDecorate %1 SpecId 3", m.annotations[0].disassemble());
+    }
+
+    #[test]
+    fn test_insert_checks_operands() {
+        let mut b = Builder::new();
+        let inst = mr::Instruction::new(
+            spirv::Op::TypeInt,
+            None,
+            Some(1),
+            vec![mr::Operand::LiteralInt32(32)],
+        );
+        assert_eq!(
+            "missing operand #1 for OpTypeInt",
+            b.insert_global(inst).unwrap_err().to_string()
+        );
+        let inst = mr::Instruction::new(
+            spirv::Op::TypeInt,
+            None,
+            Some(1),
+            vec![mr::Operand::LiteralInt32(32), mr::Operand::IdRef(2)],
+        );
+        assert_eq!(
+            "wrong operand #1 for OpTypeInt",
+            b.insert_global(inst).unwrap_err().to_string()
+        );
+        let inst = mr::Instruction::new(
+            spirv::Op::TypeVoid,
+            None,
+            Some(1),
+            vec![mr::Operand::IdRef(2)],
+        );
+        assert_eq!(
+            "wrong operand #0 for OpTypeVoid",
+            b.insert_global(inst).unwrap_err().to_string()
+        );
+        let inst = mr::Instruction::new(spirv::Op::TypeVoid, None, None, vec![]);
+        assert_eq!(
+            "mismatched result type id or result id for OpTypeVoid",
+            b.insert_global(inst).unwrap_err().to_string()
+        );
+        // Instructions not allowed outside of functions.
+        let inst = mr::Instruction::new(spirv::Op::Return, None, None, vec![]);
+        assert_eq!(
+            "found instruction not inside basic block",
+            b.insert_global(inst).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_insert_into_block() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let ret = || mr::Instruction::new(spirv::Op::Return, None, None, vec![]);
+        assert!(b.insert_into_block(ret()).is_err());
+        b.begin_basic_block(None).unwrap();
+        let inst = mr::Instruction::new(
+            spirv::Op::Switch,
+            None,
+            None,
+            vec![
+                mr::Operand::IdRef(1),
+                mr::Operand::IdRef(2),
+                mr::Operand::LiteralInt32(3),
+            ],
+        );
+        assert_eq!(
+            "wrong operand #2 for OpSwitch",
+            b.insert_into_block(inst).unwrap_err().to_string()
+        );
+        let inst = mr::Instruction::new(
+            spirv::Op::Store,
+            None,
+            None,
+            vec![
+                mr::Operand::IdRef(1),
+                mr::Operand::IdRef(2),
+                mr::Operand::MemoryAccess(spirv::MemoryAccess::ALIGNED),
+                mr::Operand::LiteralInt32(4),
+            ],
+        );
+        assert!(b.insert_into_block(inst).is_ok());
+        assert!(b.insert_into_block(ret()).is_ok());
+        b.end_function().unwrap();
+        let m = b.module();
+        let block = &m.functions[0].basic_blocks[0];
+        assert_eq!(2, block.instructions.len());
+        assert_eq!("OpStore %1 %2 Aligned 4", block.instructions[0].disassemble());
+    }
+
+    #[test]
+    fn test_build_switch_u64() {
+        use binary::Assemble;
+
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let uint64 = b.type_int(64, 0);
+        let voidf = b.type_function(void, vec![uint64]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let selector = b.function_parameter(uint64).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.switch_u64(selector, 11, vec![(1 << 40, 12)]).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.ret().unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = mr::load_words(b.module().assemble()).unwrap();
+        let inst = m.functions[0].basic_blocks[0].instructions.last().unwrap();
+        assert_eq!("OpSwitch %5 %11 1099511627776 %12", inst.disassemble());
+    }
+
+    #[test]
+    fn test_build_constants_64bit() {
+        use binary::Assemble;
+
+        let mut b = Builder::new();
+        let uint64 = b.type_int(64, 0);
+        let double = b.type_float(64);
+        b.constant_u64(uint64, 0x1234_5678_9abc_def0);
+        b.constant_f64(double, 0.1);
+        b.spec_constant_u64(uint64, u64::MAX);
+
+        let words = b.module().assemble();
+        // Header, OpTypeInt, OpTypeFloat, and three constants with 5 words each.
+        assert_eq!(5 + 4 + 3 + 5 * 3, words.len());
+        let m = mr::load_words(words).unwrap();
+        let disas: Vec<String> =
+            m.types_global_values[2..].iter().map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "%3 = OpConstant  %1  1311768467463790320",
+                "%4 = OpConstant  %2  0.1",
+                "%5 = OpSpecConstant  %1  18446744073709551615",
+            ],
+            disas
+        );
+    }
+
+    #[test]
+    fn test_folding() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let int = b.type_int(32, 1);
+        let ulong = b.type_int(64, 0);
+        let float = b.type_float(32);
+        let zero = b.constant_u32(uint, 0);
+        let one = b.constant_u32(uint, 1);
+        let max = b.constant_u64(ulong, !0);
+        let half = b.constant_f32(float, 0.5);
+        let fzero = b.constant_f32(float, 0.0);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let x = b.undef(uint, None);
+        let f = b.undef(float, None);
+        b.set_folding(true);
+
+        // Identity operations.
+        assert_eq!(x, b.iadd(uint, None, x, zero).unwrap());
+        assert_eq!(x, b.imul(uint, None, one, x).unwrap());
+        assert_eq!(x, b.bitcast(uint, None, x).unwrap());
+        assert_eq!(f, b.fsub(float, None, f, fzero).unwrap());
+        // Not identities: different result type, or x + 0.0 with x = -0.0.
+        assert_ne!(x, b.iadd(int, None, x, zero).unwrap());
+        assert_ne!(x, b.bitcast(int, None, x).unwrap());
+        assert_ne!(f, b.fadd(float, None, f, fzero).unwrap());
+        // Explicit result ids are kept.
+        assert_eq!(100, b.iadd(uint, Some(100), x, zero).unwrap());
+        // Only the unfolded instructions are emitted.
+        assert_eq!(6, b.basic_block.as_ref().unwrap().instructions.len());
+
+        // Constant folding.
+        let next = b.id() + 1;
+        assert_eq!(next, b.isub(uint, None, zero, one).unwrap());
+        assert_eq!(next + 1, b.iadd(ulong, None, max, max).unwrap());
+        assert_eq!(next + 2, b.fmul(float, None, half, half).unwrap());
+        // Division by zero is left alone.
+        assert_eq!(next + 3, b.udiv(uint, None, one, zero).unwrap());
+        assert_eq!(7, b.basic_block.as_ref().unwrap().instructions.len());
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        let globals: Vec<String> =
+            m.types_global_values.iter().skip(11).map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "%20 = OpConstant  %2  4294967295",
+                "%21 = OpConstant  %4  18446744073709551614",
+                "%22 = OpConstant  %5  0.25",
+            ],
+            globals
+        );
+    }
+
+    #[test]
+    fn test_module_bound() {
+        let mut b = Builder::new();
+        b.type_void();
+        let inst = mr::Instruction::new(spirv::Op::TypeBool, None, Some(42), vec![]);
+        b.module_mut().types_global_values.push(inst);
+        let mut m = b.module();
+        assert_eq!(43, m.header.as_ref().unwrap().bound());
+        assert_eq!(43, m.allocate_id());
+        assert_eq!(44, m.header.as_ref().unwrap().bound());
+    }
+
+    #[test]
+    fn test_spec_constant_op() {
+        use binary::Assemble;
+
+        let mut b = Builder::new();
+        let int = b.type_int(32, 1);
+        let bool_type = b.type_bool();
+        let vec2 = b.type_vector(int, 2);
+        let x = b.spec_constant_u32(int, 3);
+        let y = b.spec_constant_u32(int, 4);
+        let sum = b.spec_constant_iadd(int, x, y);
+        let less = b.spec_constant_sless_than(bool_type, x, y);
+        b.spec_constant_select(int, less, x, sum);
+        let pair = b.spec_constant_composite(vec2, vec![x, y]);
+        let args = vec![mr::Operand::IdRef(pair), mr::Operand::LiteralInt32(1)];
+        b.spec_constant_op(int, spirv::Op::CompositeExtract, args).unwrap();
+
+        let args = vec![mr::Operand::IdRef(x)];
+        assert_eq!(
+            "OpFunctionCall not allowed in OpSpecConstantOp",
+            b.spec_constant_op(int, spirv::Op::FunctionCall, args)
+                .unwrap_err()
+                .to_string()
+        );
+        let args = vec![mr::Operand::IdRef(x)];
+        assert!(b.spec_constant_op(int, spirv::Op::IAdd, args).is_err());
+
+        let m = b.module();
+        let globals: Vec<String> =
+            m.types_global_values.iter().skip(5).map(|i| i.disassemble()).collect();
+        let expected = vec![
+            "%6 = OpSpecConstantOp  %1  IAdd %4 %5",
+            "%7 = OpSpecConstantOp  %2  SLessThan %4 %5",
+            "%8 = OpSpecConstantOp  %1  Select %7 %4 %6",
+            "%9 = OpSpecConstantComposite  %3  %4 %5",
+            "%10 = OpSpecConstantOp  %1  CompositeExtract %9 1",
+        ];
+        assert_eq!(expected, globals);
+
+        // Instructions round-trip through the binary form.
+        let m = mr::load_words(m.assemble()).unwrap();
+        let globals: Vec<String> =
+            m.types_global_values.iter().skip(5).map(|i| i.disassemble()).collect();
+        assert_eq!(expected, globals);
+    }
+
+    #[test]
+    fn test_entry_point_auto_interface() {
+        let build = |version: Option<(u8, u8)>| {
+            let mut b = Builder::new();
+            if let Some((major, minor)) = version {
+                b.set_version(major, minor);
+            }
+            let void = b.type_void();
+            let float = b.type_float(32);
+            let voidf = b.type_function(void, vec![]);
+            let ptr_in = b.type_pointer(None, spirv::StorageClass::Input, float);
+            let ptr_out = b.type_pointer(None, spirv::StorageClass::Output, float);
+            let ptr_uni = b.type_pointer(None, spirv::StorageClass::Uniform, float);
+            let ptr_priv = b.type_pointer(None, spirv::StorageClass::Private, float);
+            let input = b.variable(ptr_in, None, spirv::StorageClass::Input, None);
+            let output = b.variable(ptr_out, None, spirv::StorageClass::Output, None);
+            let uniform = b.variable(ptr_uni, None, spirv::StorageClass::Uniform, None);
+            b.variable(ptr_priv, None, spirv::StorageClass::Private, None);
+
+            // A helper storing a uniform into the output.
+            let helper = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf)
+                .unwrap();
+            b.begin_basic_block(None).unwrap();
+            let value = b.load(float, None, uniform, None, vec![]).unwrap();
+            b.store(output, value, None, vec![]).unwrap();
+            b.ret().unwrap();
+            b.end_function().unwrap();
+
+            let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+            b.begin_basic_block(None).unwrap();
+            b.load(float, None, input, None, vec![]).unwrap();
+            b.function_call(void, None, helper, vec![]).unwrap();
+            b.ret().unwrap();
+            b.end_function().unwrap();
+
+            let interface = b.entry_point_auto_interface(
+                spirv::ExecutionModel::Fragment,
+                main,
+                "main",
+            ).unwrap();
+            assert_eq!(
+                "no function with id %42",
+                b.entry_point_auto_interface(spirv::ExecutionModel::Fragment, 42, "f")
+                    .unwrap_err()
+                    .to_string()
+            );
+            (interface, b.module())
+        };
+
+        let (interface, m) = build(None);
+        assert_eq!(vec![8, 9], interface);
+        assert_eq!(
+            "OpEntryPoint Fragment %15 \"main\" %8 %9",
+            m.entry_points[0].disassemble()
+        );
+        let (interface, _) = build(Some((1, 4)));
+        assert_eq!(vec![8, 10, 9], interface);
+    }
+
+    #[test]
+    fn test_insert_raw() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        // Module-level instructions go to their sections even within blocks.
+        let inst = mr::Instruction::new(
+            spirv::Op::TypeVector,
+            None,
+            None,
+            vec![mr::Operand::IdRef(float), mr::Operand::LiteralInt32(4)],
+        );
+        let vec4 = b.insert_raw(inst).unwrap().unwrap();
+        assert_eq!(6, vec4);
+        let inst = mr::Instruction::new(spirv::Op::Undef, Some(vec4), Some(20), vec![]);
+        assert_eq!(Some(20), b.insert_raw(inst).unwrap());
+        let inst = mr::Instruction::new(spirv::Op::Nop, None, Some(21), vec![]);
+        assert_eq!(
+            "mismatched result type id or result id for OpNop",
+            b.insert_raw(inst).unwrap_err().to_string()
+        );
+        let ret = mr::Instruction::new(spirv::Op::Return, None, None, vec![]);
+        assert_eq!(None, b.insert_raw(ret).unwrap());
+        b.end_function().unwrap();
+        // Ids continue after the explicitly given result id.
+        assert_eq!(21, b.id());
+
+        let m = b.module();
+        assert_eq!("%6 = OpTypeVector %2 4", m.types_global_values[3].disassemble());
+        let disas: Vec<String> =
+            m.functions[0].basic_blocks[0].instructions.iter().map(|i| i.disassemble()).collect();
+        assert_eq!(vec!["%20 = OpUndef  %6 ", "OpReturn"], disas);
+    }
+
+    #[test]
+    fn test_if_then_else() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let voidf = b.type_function(void, vec![]);
+        let cond = b.constant_true(boolean);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let merge = b.if_then_else(cond, |b| b.ret(), |_| Ok(())).unwrap();
+        assert_eq!(9, merge);
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        assert_eq!(
+            m.functions[0].disassemble(),
+            "%5 = OpFunction  %1  None %3\n\
+             %6 = OpLabel\n\
+             OpSelectionMerge %9 None\n\
+             OpBranchConditional %4 %7 %8\n\
+             %7 = OpLabel\n\
+             OpReturn\n\
+             %8 = OpLabel\n\
+             OpBranch %9\n\
+             %9 = OpLabel\n\
+             OpReturn\n\
+             OpFunctionEnd"
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let int = b.type_int(32, 1);
+        let voidf = b.type_function(void, vec![]);
+        let zero = b.constant_u32(int, 0);
+        let ten = b.constant_u32(int, 10);
+        let one = b.constant_u32(int, 1);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let entry = b.begin_basic_block(None).unwrap();
+        let i = b.id();
+        let next = b.id();
+        let merge = b
+            .loop_(
+                |b, blocks| {
+                    b.phi(int, Some(i), vec![(zero, entry), (next, blocks.continue_target)])?;
+                    b.sless_than(boolean, None, i, ten)
+                },
+                |_, _| Ok(()),
+                |b, _| b.iadd(int, Some(next), i, one).map(|_| ()),
+            )
+            .unwrap();
+        assert_eq!(15, merge);
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        assert_eq!(
+            m.functions[0].disassemble(),
+            "%8 = OpFunction  %1  None %4\n\
+             %9 = OpLabel\n\
+             OpBranch %12\n\
+             %12 = OpLabel\n\
+             %10 = OpPhi  %3  %5 %9 %11 %14\n\
+             %16 = OpSLessThan  %2  %10 %6\n\
+             OpLoopMerge %15 %14 None\n\
+             OpBranchConditional %16 %13 %15\n\
+             %13 = OpLabel\n\
+             OpBranch %14\n\
+             %14 = OpLabel\n\
+             %11 = OpIAdd  %3  %10 %7\n\
+             OpBranch %12\n\
+             %15 = OpLabel\n\
+             OpReturn\n\
+             OpFunctionEnd"
+        );
+    }
+
+    #[test]
+    fn test_phi_checking() {
+        let build = |wrong_parent: bool| {
+            let mut b = Builder::new();
+            b.set_phi_checking(true);
+            let void = b.type_void();
+            let boolean = b.type_bool();
+            let int = b.type_int(32, 1);
+            let voidf = b.type_function(void, vec![]);
+            let cond = b.constant_true(boolean);
+            let c0 = b.constant_u32(int, 0);
+            let c1 = b.constant_u32(int, 1);
+            b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+            let entry = b.begin_basic_block(None).unwrap();
+            let then = b.id();
+            let merge = b.id();
+            b.branch_conditional(cond, then, merge, vec![]).unwrap();
+            b.begin_basic_block(Some(then)).unwrap();
+            b.branch(merge).unwrap();
+            b.begin_basic_block(Some(merge)).unwrap();
+            // Parents may be forward referenced; they are only checked when
+            // ending the function.
+            let parent = if wrong_parent { merge } else { then };
+            let phi = b.phi(int, None, vec![(c0, entry), (c1, parent)]).unwrap();
+            b.ret().unwrap();
+            (b.end_function(), phi, merge)
+        };
+
+        assert_matches!(build(false), (Ok(()), _, _));
+        let (result, phi, merge) = build(true);
+        assert_matches!(result, Err(mr::Error::WrongPhiParent(p, m)) if p == phi && m == merge);
+    }
+
+    #[test]
+    fn test_declare_function() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let intfint = b.type_function(int, vec![int]);
+        let even = b.declare_function(int, spirv::FunctionControl::NONE, intfint).unwrap();
+        let odd = b.declare_function(int, spirv::FunctionControl::NONE, intfint).unwrap();
+        let external = b.declare_function(int, spirv::FunctionControl::NONE, intfint).unwrap();
+        assert_eq!(3, b.module_ref().functions.len());
+
+        for &(f, other) in &[(even, odd), (odd, even)] {
+            let parameters = b.define_function(f).unwrap();
+            assert_eq!(1, parameters.len());
+            b.begin_basic_block(None).unwrap();
+            let result = b.function_call(int, None, other, parameters).unwrap();
+            b.ret_value(result).unwrap();
+            b.end_function().unwrap();
+        }
+        assert_matches!(
+            b.define_function(even),
+            Err(mr::Error::FunctionNotFound(id)) if id == even
+        );
+        assert_matches!(
+            b.declare_function(void, spirv::FunctionControl::NONE, int),
+            Err(mr::Error::WrongOperand(spirv::Op::Function, 1))
+        );
+
+        let m = b.module();
+        let ids: Vec<spirv::Word> = m
+            .functions
+            .iter()
+            .map(|f| f.def.as_ref().unwrap().result_id.unwrap())
+            .collect();
+        // The remaining declaration precedes the definitions.
+        assert_eq!(vec![external, even, odd], ids);
+        assert_eq!(
+            m.functions[0].disassemble(),
+            "%8 = OpFunction  %2  None %3\n\
+             %9 = OpFunctionParameter  %2 \n\
+             \n\
+             OpFunctionEnd"
+        );
+        assert_eq!(
+            m.functions[1].disassemble(),
+            "%4 = OpFunction  %2  None %3\n\
+             %5 = OpFunctionParameter  %2 \n\
+             %10 = OpLabel\n\
+             %11 = OpFunctionCall  %2  %6 %5\n\
+             OpReturnValue %11\n\
+             OpFunctionEnd"
+        );
+    }
+
+    #[test]
+    fn test_rollback_function_definition() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        let f = b.declare_function(void, spirv::FunctionControl::NONE, voidf).unwrap();
+        let checkpoint = b.checkpoint();
+        b.define_function(f).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let g = b.declare_function(void, spirv::FunctionControl::NONE, voidf).unwrap();
+        let ids: Vec<_> = b
+            .module_ref()
+            .functions
+            .iter()
+            .map(|f| f.def.as_ref().unwrap().result_id.unwrap())
+            .collect();
+        assert_eq!(vec![g, f], ids);
+
+        b.rollback(checkpoint);
+        let m = b.module();
+        assert_eq!(1, m.functions.len());
+        assert!(m.functions[0].basic_blocks.is_empty());
+        assert_eq!(Some(f), m.functions[0].def.as_ref().unwrap().result_id);
+    }
+
+    #[test]
+    fn test_rollback_function_ended_in_open_block() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let label = b.begin_basic_block(None).unwrap();
+        let snapshot = b.snapshot();
+        b.end_function().unwrap();
+
+        b.rollback(snapshot);
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let m = b.module();
+        assert_eq!(1, m.functions.len());
+        assert_eq!(Some(f), m.functions[0].def.as_ref().unwrap().result_id);
+        assert_eq!(1, m.functions[0].basic_blocks.len());
+        assert_eq!(Some(label), m.functions[0].basic_blocks[0].label_id());
+    }
+
+    #[test]
+    fn test_access_chain_typed() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let uint = b.type_int(32, 0);
+        let c0 = b.constant_u32(uint, 0);
+        let c1 = b.constant_u32(uint, 1);
+        let c2 = b.constant_u32(uint, 2);
+        let c4 = b.constant_u32(uint, 4);
+        let array = b.type_array(float, c4);
+        let light = b.type_struct(vec![vec4, array]);
+        let light_ptr = b.type_pointer(None, spirv::StorageClass::Uniform, light);
+        let var = b.variable(light_ptr, None, spirv::StorageClass::Uniform, None);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+
+        let element = b.access_chain_typed(var, vec![c1, c2]).unwrap();
+        let color = b.access_chain_typed(var, vec![c0]).unwrap();
+        let red = b.access_chain_typed(color, vec![c0]).unwrap();
+        assert_matches!(
+            b.access_chain_typed(var, vec![c1, c0, c0]),
+            Err(mr::Error::WrongAccessChainIndex(2))
+        );
+        assert_matches!(
+            b.access_chain_typed(var, vec![c4]),
+            Err(mr::Error::WrongAccessChainIndex(0))
+        );
+        assert_matches!(
+            b.access_chain_typed(c0, vec![]),
+            Err(mr::Error::WrongAccessChainBase(id)) if id == c0
+        );
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        let disas: Vec<String> =
+            m.types_global_values[13..].iter().map(|i| i.disassemble()).collect();
+        assert_eq!(vec!["%16 = OpTypePointer Uniform %2", "%18 = OpTypePointer Uniform %3"], disas);
+        let disas: Vec<String> =
+            m.functions[0].basic_blocks[0].instructions.iter().map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                format!("%{} = OpAccessChain  %16  %12 %6 %7", element),
+                format!("%{} = OpAccessChain  %18  %12 %5", color),
+                format!("%{} = OpAccessChain  %16  %19 %5", red),
+                "OpReturn".to_string(),
+            ],
+            disas
+        );
+    }
+
+    #[test]
+    fn test_snapshot_rollback() {
+        let mut b = Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let snapshot = b.snapshot();
+        let before = b.module_ref().types_global_values.len();
+
+        // Speculatively emit a candidate sequence, ending the block and
+        // the function.
+        let one = b.constant_f32(float, 1.0);
+        b.fadd(float, None, one, one).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.capability(spirv::Capability::Shader);
+        assert_eq!(1, b.module_ref().functions.len());
+
+        b.rollback(snapshot.clone());
+        assert_eq!(before, b.module_ref().types_global_values.len());
+        assert!(b.module_ref().capabilities.is_empty());
+        assert!(b.module_ref().functions.is_empty());
+        // Discarded ids are handed out again.
+        assert_eq!(one, b.constant_f32(float, 2.0));
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        assert_eq!(7, m.header.as_ref().unwrap().bound);
+        let disas: Vec<String> = m.functions[0]
+            .basic_blocks
+            .iter()
+            .flat_map(|bb| bb.label.iter().chain(&bb.instructions))
+            .map(|i| i.disassemble())
+            .collect();
+        assert_eq!(vec!["%5 = OpLabel", "OpReturn"], disas);
+    }
 }
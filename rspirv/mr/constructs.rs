@@ -14,10 +14,15 @@
 
 use grammar;
 use spirv;
+#[cfg(feature = "std")]
+use transform;
 
 use spirv::Word;
+use super::{Generator, Operands, Symbol};
 use utils::version;
-use std::{convert, fmt};
+use std::{convert, fmt, ptr};
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Data representation of a SPIR-V module.
 ///
@@ -27,7 +32,7 @@ use std::{convert, fmt};
 /// The order of its fields basically reveal the requirements in the
 /// [Logical Layout of a Module](https://goo.gl/2kVnfX) of the SPIR-V
 /// of the SPIR-V specification.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Module {
     /// The module header.
     pub header: Option<ModuleHeader>,
@@ -57,10 +62,28 @@ pub struct Module {
     pub types_global_values: Vec<Instruction>,
     /// All functions.
     pub functions: Vec<Function>,
+    /// Locations of the instructions in the binary this module was loaded
+    /// from, in the order they appear in the binary.
+    ///
+    /// Only recorded when requested through
+    /// [`Loader::with_locations`](struct.Loader.html#method.with_locations).
+    /// They are not updated when the module is modified.
+    pub locations: Option<Vec<InstructionLocation>>,
+}
+
+/// Location of an instruction in a SPIR-V binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InstructionLocation {
+    /// The word offset of the instruction, counting the module header.
+    pub offset: usize,
+    /// The number of the instruction, starting from 1.
+    pub index: usize,
+    /// The result id of the instruction, if any.
+    pub result_id: Option<Word>,
 }
 
 /// Data representation of a SPIR-V module header.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ModuleHeader {
     pub magic_number: Word,
     pub version: Word,
@@ -70,7 +93,7 @@ pub struct ModuleHeader {
 }
 
 /// Data representation of a SPIR-V function.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Function {
     /// First (defining) instruction in this function.
     pub def: Option<Instruction>,
@@ -83,7 +106,7 @@ pub struct Function {
 }
 
 /// Data representation of a SPIR-V basic block.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct BasicBlock {
     /// The label starting this basic block.
     pub label: Option<Instruction>,
@@ -91,8 +114,34 @@ pub struct BasicBlock {
     pub instructions: Vec<Instruction>,
 }
 
+/// A structured view of a complete basic block, as returned by
+/// [`BasicBlock::structure`](struct.BasicBlock.html#method.structure).
+#[derive(Copy, Clone, Debug)]
+pub struct BasicBlockRef<'a> {
+    /// The result id of the label starting the basic block.
+    pub label: Word,
+    /// The OpPhi instructions at the start of the basic block.
+    pub phis: &'a [Instruction],
+    /// The instructions between the OpPhi instructions and the terminator,
+    /// including a merge instruction if any.
+    pub body: &'a [Instruction],
+    /// The instruction terminating the basic block.
+    pub terminator: &'a Instruction,
+}
+
+impl<'a> BasicBlockRef<'a> {
+    /// Returns the OpSelectionMerge or OpLoopMerge instruction declaring
+    /// the basic block as a header of a structured construct, if any.
+    pub fn merge(&self) -> Option<&'a Instruction> {
+        self.body.last().filter(|inst| {
+            inst.class.opcode == spirv::Op::SelectionMerge ||
+                inst.class.opcode == spirv::Op::LoopMerge
+        })
+    }
+}
+
 /// Data representation of a SPIR-V instruction.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Instruction {
     /// The class (grammar specification) of this instruction.
     pub class: &'static grammar::Instruction<'static>,
@@ -101,7 +150,7 @@ pub struct Instruction {
     /// Result id.
     pub result_id: Option<Word>,
     /// Operands.
-    pub operands: Vec<Operand>,
+    pub operands: Operands,
 }
 
 include!("operand.rs");
@@ -121,9 +170,16 @@ impl Module {
             annotations: vec![],
             types_global_values: vec![],
             functions: vec![],
+            locations: None,
         }
     }
 
+    /// Returns the location in the original binary of the instruction
+    /// defining the given `id`, if locations were recorded when loading.
+    pub fn location(&self, id: Word) -> Option<&InstructionLocation> {
+        self.locations.as_ref()?.iter().find(|location| location.result_id == Some(id))
+    }
+
     /// Returns an iterator over all global instructions.
     ///
     /// This method internally creates a vector of references to all global
@@ -140,6 +196,55 @@ impl Module {
             .chain(&self.annotations)
             .chain(&self.types_global_values)
     }
+
+    /// Returns an iterator over all instructions in this module, including
+    /// those inside functions, in their logical order.
+    pub fn all_inst_iter(&self) -> impl Iterator<Item = &Instruction> {
+        self.global_inst_iter()
+            .chain(self.functions.iter().flat_map(|f| f.all_inst_iter()))
+    }
+
+    /// Returns the maximal id defined or referenced in this module, or
+    /// zero if there is none.
+    pub fn max_id(&self) -> Word {
+        let mut max = 0;
+        for inst in self.all_inst_iter() {
+            max = max.max(inst.result_id.unwrap_or(0)).max(inst.result_type.unwrap_or(0));
+            for operand in &inst.operands {
+                match *operand {
+                    Operand::IdMemorySemantics(id) |
+                    Operand::IdScope(id) |
+                    Operand::IdRef(id) => max = max.max(id),
+                    _ => {}
+                }
+            }
+        }
+        max
+    }
+
+    /// Removes all debug information from this module.
+    ///
+    /// This is a convenience for
+    /// [`transform::strip_debug_info`](../transform/fn.strip_debug_info.html);
+    /// returns the number of instructions removed.
+    #[cfg(feature = "std")]
+    pub fn strip_debug(&mut self) -> usize {
+        transform::strip_debug_info(self)
+    }
+
+    /// Allocates a new unused id, bumping the bound in the module header.
+    ///
+    /// If this module has no header yet, one is created with a bound
+    /// computed from all ids used in the module.
+    pub fn allocate_id(&mut self) -> Word {
+        if self.header.is_none() {
+            self.header = Some(ModuleHeader::new(self.max_id() + 1));
+        }
+        let header = self.header.as_mut().unwrap();
+        let id = header.bound();
+        header.update_bound(id);
+        id
+    }
 }
 
 impl ModuleHeader {
@@ -148,7 +253,7 @@ impl ModuleHeader {
         ModuleHeader {
             magic_number: spirv::MAGIC_NUMBER,
             version: (spirv::MAJOR_VERSION << 16) | (spirv::MINOR_VERSION << 8),
-            generator: 0x000f0000, // TODO: lower 16-bit: tool version number
+            generator: (Generator::Rspirv.tool() as u32) << 16, // TODO: tool version number
             bound: bound,
             reserved_word: 0,
         }
@@ -164,30 +269,29 @@ impl ModuleHeader {
         version::create_version_from_word(self.version)
     }
 
-    /// Returns the generator's name and version as a tuple.
-    pub fn generator(&self) -> (&str, u16) {
-        let tool = (self.generator & 0xffff0000) >> 16;
+    /// Returns the generator and its version as a tuple.
+    ///
+    /// Use [`Generator::name`](enum.Generator.html#method.name) or its
+    /// `Display` implementation for the name of the generator.
+    pub fn generator(&self) -> (Generator, u16) {
+        let tool = (self.generator >> 16) as u16;
         let version = (self.generator & 0xffff) as u16;
-        let tool: &str = match tool {
-            0 => "The Khronos Group",
-            1 => "LunarG",
-            2 => "Valve",
-            3 => "Codeplay",
-            4 => "NVIDIA",
-            5 => "ARM",
-            6 => "LLVM/SPIR-V Translator",
-            7 => "SPIR-V Tools Assembler",
-            8 => "Glslang",
-            9 => "Qualcomm",
-            10 => "AMD",
-            11 => "Intel",
-            12 => "Imagination",
-            13 => "Shaderc",
-            14 => "spiregg",
-            15 => "rspirv",
-            _ => "Unknown",
-        };
-        (tool, version)
+        (Generator::from_tool(tool), version)
+    }
+
+    /// Sets the generator to the given `generator` and `version`.
+    pub fn set_generator(&mut self, generator: Generator, version: u16) {
+        self.generator = ((generator.tool() as u32) << 16) | version as u32;
+    }
+
+    /// Returns the id bound, i.e., all ids in the module are less than it.
+    pub fn bound(&self) -> Word {
+        self.bound
+    }
+
+    /// Raises the id bound if needed so that it covers the given `id`.
+    pub fn update_bound(&mut self, id: Word) {
+        self.bound = self.bound.max(id + 1);
     }
 }
 
@@ -203,6 +307,19 @@ impl Function {
     }
 }
 
+impl Function {
+    /// Returns an iterator over all instructions in this function,
+    /// including the defining and ending instructions, parameters, and
+    /// basic block labels.
+    pub fn all_inst_iter(&self) -> impl Iterator<Item = &Instruction> {
+        self.def
+            .iter()
+            .chain(&self.parameters)
+            .chain(self.basic_blocks.iter().flat_map(|bb| bb.label.iter().chain(&bb.instructions)))
+            .chain(&self.end)
+    }
+}
+
 impl BasicBlock {
     /// Creates a new empty `BasicBlock` instance.
     pub fn new() -> Self {
@@ -211,6 +328,30 @@ impl BasicBlock {
             instructions: vec![],
         }
     }
+
+    /// Returns the result id of the label starting this basic block.
+    pub fn label_id(&self) -> Option<Word> {
+        self.label.as_ref().and_then(|inst| inst.result_id)
+    }
+
+    /// Splits the instructions of this basic block into its OpPhi
+    /// instructions, its body, and its terminator.
+    ///
+    /// Returns `None` if this basic block has no label with a result id or
+    /// does not end with a terminator, e.g., while it is still being built.
+    pub fn structure(&self) -> Option<BasicBlockRef<'_>> {
+        let label = self.label_id()?;
+        let (terminator, rest) = self.instructions.split_last()?;
+        if !grammar::reflect::is_terminator(terminator.class.opcode) {
+            return None;
+        }
+        let phi_count = rest
+            .iter()
+            .take_while(|inst| inst.class.opcode == spirv::Op::Phi)
+            .count();
+        let (phis, body) = rest.split_at(phi_count);
+        Some(BasicBlockRef { label, phis, body, terminator })
+    }
 }
 
 impl Instruction {
@@ -220,6 +361,16 @@ impl Instruction {
                result_id: Option<Word>,
                operands: Vec<Operand>)
                -> Self {
+        Self::with_operands(opcode, result_type, result_id, operands.into())
+    }
+
+    /// Creates a new `Instruction` instance from an `Operands` list, which
+    /// avoids allocating for instructions with few operands.
+    pub fn with_operands(opcode: spirv::Op,
+                         result_type: Option<Word>,
+                         result_id: Option<Word>,
+                         operands: Operands)
+                         -> Self {
         Instruction {
             class: grammar::CoreInstructionTable::get(opcode),
             result_type: result_type,
@@ -227,12 +378,72 @@ impl Instruction {
             operands: operands,
         }
     }
+
+    /// Creates an instruction not understood by this library, keeping the
+    /// given `opcode` and the `words` following the first word as is.
+    ///
+    /// See [`grammar::UNKNOWN_INSTRUCTION`](../grammar/static.UNKNOWN_INSTRUCTION.html).
+    pub fn new_unknown(opcode: u16, words: Vec<Word>) -> Self {
+        let mut operands = Operands::new();
+        operands.push(Operand::LiteralInt32(opcode as u32));
+        operands.extend(words.into_iter().map(Operand::LiteralInt32));
+        Instruction {
+            class: &grammar::UNKNOWN_INSTRUCTION,
+            result_type: None,
+            result_id: None,
+            operands,
+        }
+    }
+
+    /// Creates an instruction with the given `opcode` unknown to the
+    /// compiled-in tables, decoded according to the given entry of a
+    /// [`DynamicGrammar`](../grammar/struct.DynamicGrammar.html).
+    pub fn with_dynamic_grammar(class: &'static grammar::Instruction<'static>,
+                                opcode: u16,
+                                result_type: Option<Word>,
+                                result_id: Option<Word>,
+                                operands: Operands)
+                                -> Self {
+        let mut all = Operands::new();
+        all.push(Operand::LiteralInt32(opcode as u32));
+        all.extend(operands);
+        Instruction {
+            class,
+            result_type,
+            result_id,
+            operands: all,
+        }
+    }
+
+    /// Returns the opcode of an instruction created by
+    /// [`new_unknown`](#method.new_unknown) or
+    /// [`with_dynamic_grammar`](#method.with_dynamic_grammar), or `None` for
+    /// all other instructions.
+    ///
+    /// The opcode is kept as the first operand of such instructions.
+    pub fn unknown_opcode(&self) -> Option<u16> {
+        // Entries for unknown opcodes claim to be OpNop.
+        if self.class.opcode != spirv::Op::Nop ||
+           ptr::eq(self.class, grammar::CoreInstructionTable::get(spirv::Op::Nop)) {
+            return None;
+        }
+        match self.operands.first() {
+            Some(&Operand::LiteralInt32(opcode)) => Some(opcode as u16),
+            _ => None,
+        }
+    }
 }
 
-// Sadly cannot use impl<T: Into<String>> here.
+// Sadly cannot use impl<T: Into<Symbol>> here.
 impl<'a> convert::From<&'a str> for Operand {
     fn from(val: &'a str) -> Self {
-        Operand::LiteralString(val.to_owned())
+        Operand::LiteralString(val.into())
+    }
+}
+
+impl convert::From<String> for Operand {
+    fn from(val: String) -> Self {
+        Operand::LiteralString(val.into())
     }
 }
 
@@ -247,12 +458,66 @@ impl convert::From<u32> for Operand {
 mod tests {
     use mr;
     use spirv;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_header_accessors() {
+        let mut header = mr::ModuleHeader::new(5);
+        assert_eq!((mr::Generator::Rspirv, 0), header.generator());
+        assert_eq!("rspirv", header.generator().0.name());
+        header.set_generator(mr::Generator::Naga, 3);
+        assert_eq!((mr::Generator::Naga, 3), header.generator());
+        assert_eq!(0x001c_0003, header.generator);
+        header.update_bound(2);
+        assert_eq!(5, header.bound());
+        header.update_bound(9);
+        assert_eq!(10, header.bound());
+    }
+
+    #[test]
+    fn test_basic_block_structure() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.branch(11).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.phi(boolean, None, vec![(cond, 10), (cond, 11)]).unwrap();
+        b.loop_merge(12, 11, spirv::LoopControl::NONE, vec![]).unwrap();
+        b.branch_conditional(cond, 11, 12, vec![]).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let module = b.module();
+        let blocks = &module.functions[0].basic_blocks;
+
+        let entry = blocks[0].structure().unwrap();
+        assert_eq!(10, entry.label);
+        assert!(entry.phis.is_empty() && entry.body.is_empty());
+        assert_eq!(spirv::Op::Branch, entry.terminator.class.opcode);
+        assert!(entry.merge().is_none());
+
+        let header = blocks[1].structure().unwrap();
+        assert_eq!(1, header.phis.len());
+        assert_eq!(1, header.body.len());
+        assert_eq!(spirv::Op::LoopMerge, header.merge().unwrap().class.opcode);
+        assert_eq!(spirv::Op::BranchConditional, header.terminator.class.opcode);
+
+        let mut unterminated = blocks[2].clone();
+        unterminated.instructions.clear();
+        assert!(unterminated.structure().is_none());
+        assert_eq!(Some(12), unterminated.label_id());
+    }
 
     #[test]
     fn test_convert_from_string() {
-        assert_eq!(mr::Operand::LiteralString("wow".to_string()),
+        assert_eq!(mr::Operand::LiteralString("wow".into()),
                    mr::Operand::from("wow"));
-        assert_eq!(mr::Operand::LiteralString("wow".to_string()),
+        assert_eq!(mr::Operand::LiteralString("wow".into()),
                    mr::Operand::from("wow".to_string()));
     }
 
@@ -0,0 +1,231 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Instruction, Module, Operand};
+use spirv::Word;
+use std::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Returns the target id of the given decoration instruction, and the
+/// member index for member decorations.
+///
+/// Returns `None` if the instruction is not an OpDecorate, OpDecorateId,
+/// OpDecorateString, OpMemberDecorate, or OpMemberDecorateString.
+fn decoration_target(inst: &Instruction) -> Option<(Word, Option<u32>)> {
+    match (inst.class.opcode, &inst.operands[..]) {
+        (spirv::Op::Decorate, &[Operand::IdRef(id), ..]) |
+        (spirv::Op::DecorateId, &[Operand::IdRef(id), ..]) |
+        (spirv::Op::DecorateStringGOOGLE, &[Operand::IdRef(id), ..]) => Some((id, None)),
+        (spirv::Op::MemberDecorate, &[Operand::IdRef(id), Operand::LiteralInt32(member), ..]) |
+        (spirv::Op::MemberDecorateStringGOOGLE,
+         &[Operand::IdRef(id), Operand::LiteralInt32(member), ..]) => Some((id, Some(member))),
+        _ => None,
+    }
+}
+
+/// Returns the first literal value of the given decoration instruction if
+/// it applies the given decoration.
+fn decoration_value(inst: &Instruction, decoration: spirv::Decoration) -> Option<u32> {
+    let operands = match inst.operands.get(1) {
+        Some(&Operand::LiteralInt32(_)) => &inst.operands[2..],
+        Some(_) => &inst.operands[1..],
+        None => return None,
+    };
+    match operands {
+        [Operand::Decoration(d), Operand::LiteralInt32(value), ..] if *d == decoration => {
+            Some(*value)
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the given decoration instruction applies the given
+/// decoration.
+fn applies(inst: &Instruction, decoration: spirv::Decoration) -> bool {
+    inst.operands.iter().take(3).any(|operand| *operand == Operand::Decoration(decoration))
+}
+
+/// An index of the decoration instructions of a module, keyed by their
+/// targets.
+///
+/// Querying [`Module`](struct.Module.html) directly scans all annotations
+/// each time; building this index once pays off when querying many ids,
+/// as reflection and passes usually do. The index borrows the module, so
+/// it cannot go stale.
+///
+/// Decoration groups are not followed; use
+/// [`transform::expand_decoration_groups`](../transform/fn.expand_decoration_groups.html)
+/// beforehand if the module may contain them.
+#[derive(Debug, Default)]
+pub struct DecorationIndex<'a> {
+    decorations: BTreeMap<Word, Vec<&'a Instruction>>,
+    member_decorations: BTreeMap<(Word, u32), Vec<&'a Instruction>>,
+}
+
+impl<'a> DecorationIndex<'a> {
+    /// Builds the index of all decoration instructions in the given module.
+    pub fn new(module: &'a Module) -> Self {
+        let mut index = DecorationIndex::default();
+        for inst in &module.annotations {
+            match decoration_target(inst) {
+                Some((id, None)) => index.decorations.entry(id).or_default().push(inst),
+                Some((id, Some(member))) => {
+                    index.member_decorations.entry((id, member)).or_default().push(inst)
+                }
+                None => {}
+            }
+        }
+        index
+    }
+
+    /// Returns an iterator over all instructions decorating the given id,
+    /// excluding member decorations.
+    pub fn get_decorations(&self, id: Word) -> impl Iterator<Item = &'a Instruction> + '_ {
+        self.decorations.get(&id).into_iter().flat_map(|insts| insts.iter().cloned())
+    }
+
+    /// Returns an iterator over all instructions decorating the given
+    /// member of the given struct type.
+    pub fn get_member_decorations(&self, id: Word, member: u32)
+                                  -> impl Iterator<Item = &'a Instruction> + '_ {
+        self.member_decorations
+            .get(&(id, member))
+            .into_iter()
+            .flat_map(|insts| insts.iter().cloned())
+    }
+
+    /// Returns whether the given id is decorated with the given decoration.
+    pub fn has_decoration(&self, id: Word, decoration: spirv::Decoration) -> bool {
+        self.get_decorations(id).any(|inst| applies(inst, decoration))
+    }
+
+    /// Returns the literal value of the given decoration on the given id,
+    /// e.g., the binding number for `Decoration::Binding`.
+    pub fn get_decoration_value(&self, id: Word, decoration: spirv::Decoration) -> Option<u32> {
+        self.get_decorations(id).filter_map(|inst| decoration_value(inst, decoration)).next()
+    }
+
+    /// Returns the literal value of the given decoration on the given
+    /// member of the given struct type, e.g., the offset for
+    /// `Decoration::Offset`.
+    pub fn get_member_decoration_value(&self,
+                                       id: Word,
+                                       member: u32,
+                                       decoration: spirv::Decoration)
+                                       -> Option<u32> {
+        self.get_member_decorations(id, member)
+            .filter_map(|inst| decoration_value(inst, decoration))
+            .next()
+    }
+}
+
+impl Module {
+    /// Returns an iterator over all instructions decorating the given id,
+    /// excluding member decorations.
+    ///
+    /// This scans all annotations; use
+    /// [`decoration_index()`](#method.decoration_index) for repeated
+    /// queries.
+    pub fn get_decorations(&self, id: Word) -> impl Iterator<Item = &Instruction> {
+        self.annotations
+            .iter()
+            .filter(move |inst| decoration_target(inst) == Some((id, None)))
+    }
+
+    /// Returns an iterator over all instructions decorating the given
+    /// member of the given struct type.
+    pub fn get_member_decorations(&self, id: Word, member: u32)
+                                  -> impl Iterator<Item = &Instruction> {
+        self.annotations
+            .iter()
+            .filter(move |inst| decoration_target(inst) == Some((id, Some(member))))
+    }
+
+    /// Returns whether the given id is decorated with the given decoration.
+    pub fn has_decoration(&self, id: Word, decoration: spirv::Decoration) -> bool {
+        self.get_decorations(id).any(|inst| applies(inst, decoration))
+    }
+
+    /// Returns the literal value of the given decoration on the given id,
+    /// e.g., the binding number for `Decoration::Binding`.
+    pub fn get_decoration_value(&self, id: Word, decoration: spirv::Decoration) -> Option<u32> {
+        self.get_decorations(id).filter_map(|inst| decoration_value(inst, decoration)).next()
+    }
+
+    /// Returns the literal value of the given decoration on the given
+    /// member of the given struct type, e.g., the offset for
+    /// `Decoration::Offset`.
+    pub fn get_member_decoration_value(&self,
+                                       id: Word,
+                                       member: u32,
+                                       decoration: spirv::Decoration)
+                                       -> Option<u32> {
+        self.get_member_decorations(id, member)
+            .filter_map(|inst| decoration_value(inst, decoration))
+            .next()
+    }
+
+    /// Builds an index of all decoration instructions in this module, for
+    /// answering many decoration queries without rescanning annotations.
+    pub fn decoration_index(&self) -> DecorationIndex<'_> {
+        DecorationIndex::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use spirv::Decoration as D;
+
+    #[test]
+    fn test_decoration_queries() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let block = b.type_struct(vec![float, float]);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Uniform, block);
+        let var = b.variable(ptr, None, spirv::StorageClass::Uniform, None);
+        b.decorate(block, D::Block, vec![]);
+        b.decorate(var, D::DescriptorSet, vec![mr::Operand::LiteralInt32(1)]);
+        b.decorate(var, D::Binding, vec![mr::Operand::LiteralInt32(3)]);
+        b.member_decorate(block, 0, D::Offset, vec![mr::Operand::LiteralInt32(0)]);
+        b.member_decorate(block, 1, D::Offset, vec![mr::Operand::LiteralInt32(16)]);
+        b.member_decorate(block, 1, D::NonWritable, vec![]);
+        let m = b.module();
+
+        assert_eq!(2, m.get_decorations(var).count());
+        assert_eq!(Some(3), m.get_decoration_value(var, D::Binding));
+        assert_eq!(Some(1), m.get_decoration_value(var, D::DescriptorSet));
+        assert_eq!(None, m.get_decoration_value(var, D::Location));
+        assert_eq!(None, m.get_decoration_value(block, D::Block));
+        assert!(m.has_decoration(block, D::Block));
+        assert!(!m.has_decoration(block, D::Offset));
+        assert_eq!(2, m.get_member_decorations(block, 1).count());
+        assert_eq!(Some(16), m.get_member_decoration_value(block, 1, D::Offset));
+        assert_eq!(None, m.get_member_decoration_value(block, 0, D::NonWritable));
+
+        let index = m.decoration_index();
+        assert_eq!(2, index.get_decorations(var).count());
+        assert_eq!(0, index.get_decorations(float).count());
+        assert_eq!(Some(3), index.get_decoration_value(var, D::Binding));
+        assert!(index.has_decoration(block, D::Block));
+        assert_eq!(Some(0), index.get_member_decoration_value(block, 0, D::Offset));
+        assert_eq!(Some(16), index.get_member_decoration_value(block, 1, D::Offset));
+        assert_eq!(1, index.get_member_decorations(block, 0).count());
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Instruction, Module, Operand};
+use spirv::Word;
+
+impl Module {
+    /// Returns the result id of the OpExtInstImport importing the extended
+    /// instruction set `name`, if any.
+    pub fn ext_inst_set(&self, name: &str) -> Option<Word> {
+        self.ext_inst_sets().find(|&(_, n)| n == name).map(|(id, _)| id)
+    }
+
+    /// Returns the name of the extended instruction set imported as `id`,
+    /// if any.
+    pub fn ext_inst_set_name(&self, id: Word) -> Option<&str> {
+        self.ext_inst_sets().find(|&(set, _)| set == id).map(|(_, name)| name)
+    }
+
+    /// Returns an iterator over the (result id, name) pairs of all
+    /// OpExtInstImport instructions.
+    pub fn ext_inst_sets(&self) -> impl Iterator<Item = (Word, &str)> {
+        self.ext_inst_imports.iter().filter_map(|inst| match (inst.result_id, &inst.operands[..]) {
+            (Some(id), &[Operand::LiteralString(ref name)]) => Some((id, name.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Returns the result id of the OpExtInstImport importing the extended
+    /// instruction set `name`, appending a new OpExtInstImport with a
+    /// freshly allocated id if the set is not imported yet.
+    pub fn get_or_insert_ext_inst_set(&mut self, name: &str) -> Word {
+        if let Some(id) = self.ext_inst_set(name) {
+            return id;
+        }
+        let id = self.allocate_id();
+        self.ext_inst_imports.push(Instruction::new(
+            spirv::Op::ExtInstImport,
+            None,
+            Some(id),
+            vec![Operand::LiteralString(name.into())],
+        ));
+        id
+    }
+}
+
+impl Instruction {
+    /// Returns the extended instruction set id and the instruction number of
+    /// an OpExtInst, whether its instruction number has been decoded into an
+    /// `Operand::ExtInstOpcode` or not.
+    pub fn ext_inst(&self) -> Option<(Word, u32)> {
+        if self.class.opcode != spirv::Op::ExtInst {
+            return None;
+        }
+        match (self.operands.first(), self.operands.get(1)) {
+            (Some(&Operand::IdRef(set)), Some(&Operand::LiteralExtInstInteger(opcode))) => {
+                Some((set, opcode))
+            }
+            (Some(&Operand::IdRef(set)), Some(&Operand::ExtInstOpcode(grammar))) => {
+                Some((set, grammar.opcode))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_ext_inst_sets() {
+        let mut b = mr::Builder::new();
+        let glsl = b.ext_inst_import("GLSL.std.450");
+        let mut m = b.module();
+
+        assert_eq!(Some(glsl), m.ext_inst_set("GLSL.std.450"));
+        assert_eq!(None, m.ext_inst_set("OpenCL.std"));
+        assert_eq!(Some("GLSL.std.450"), m.ext_inst_set_name(glsl));
+        assert_eq!(None, m.ext_inst_set_name(glsl + 1));
+
+        assert_eq!(glsl, m.get_or_insert_ext_inst_set("GLSL.std.450"));
+        assert_eq!(1, m.ext_inst_imports.len());
+        let printf = m.get_or_insert_ext_inst_set("NonSemantic.DebugPrintf");
+        assert_eq!(glsl + 1, printf);
+        assert_eq!(printf + 1, m.header.as_ref().unwrap().bound());
+        assert_eq!(printf, m.get_or_insert_ext_inst_set("NonSemantic.DebugPrintf"));
+        assert_eq!(vec![(glsl, "GLSL.std.450"), (printf, "NonSemantic.DebugPrintf")],
+                   m.ext_inst_sets().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Instruction, Module, Operand};
+use spirv::Word;
+use std::collections::{BTreeMap, BTreeSet};
+use std::{error, fmt};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// How denormalized floating-point values are treated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DenormMode {
+    /// Denormalized values are preserved (`DenormPreserve`).
+    Preserve,
+    /// Denormalized values are flushed to zero (`DenormFlushToZero`).
+    FlushToZero,
+}
+
+/// The default rounding mode of floating-point operations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest even (`RoundingModeRTE`).
+    Rte,
+    /// Round towards zero (`RoundingModeRTZ`).
+    Rtz,
+}
+
+/// The floating-point controls of an entry point, as declared by the
+/// execution modes of `SPV_KHR_float_controls`.
+///
+/// All modes are keyed by the bit width of the floating-point type they
+/// apply to. Widths without an entry use the implementation default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FloatControls {
+    /// The denorm mode for each bit width.
+    pub denorm: BTreeMap<u32, DenormMode>,
+    /// The rounding mode for each bit width.
+    pub rounding: BTreeMap<u32, RoundingMode>,
+    /// The bit widths for which signed zeros, infinities and NaNs are
+    /// preserved (`SignedZeroInfNanPreserve`).
+    pub signed_zero_inf_nan_preserve: BTreeSet<u32>,
+}
+
+impl FloatControls {
+    /// Returns the execution modes declaring these controls, each with
+    /// its bit width.
+    fn execution_modes(&self) -> Vec<(spirv::ExecutionMode, u32)> {
+        let mut modes = vec![];
+        for (&width, &mode) in &self.denorm {
+            modes.push((
+                match mode {
+                    DenormMode::Preserve => spirv::ExecutionMode::DenormPreserve,
+                    DenormMode::FlushToZero => spirv::ExecutionMode::DenormFlushToZero,
+                },
+                width,
+            ));
+        }
+        for &width in &self.signed_zero_inf_nan_preserve {
+            modes.push((spirv::ExecutionMode::SignedZeroInfNanPreserve, width));
+        }
+        for (&width, &mode) in &self.rounding {
+            modes.push((
+                match mode {
+                    RoundingMode::Rte => spirv::ExecutionMode::RoundingModeRTE,
+                    RoundingMode::Rtz => spirv::ExecutionMode::RoundingModeRTZ,
+                },
+                width,
+            ));
+        }
+        modes
+    }
+}
+
+/// Returns true if `mode` is one of the execution modes of
+/// `SPV_KHR_float_controls`.
+fn is_float_control(mode: spirv::ExecutionMode) -> bool {
+    matches!(
+        mode,
+        spirv::ExecutionMode::DenormPreserve |
+            spirv::ExecutionMode::DenormFlushToZero |
+            spirv::ExecutionMode::SignedZeroInfNanPreserve |
+            spirv::ExecutionMode::RoundingModeRTE |
+            spirv::ExecutionMode::RoundingModeRTZ
+    )
+}
+
+/// An error setting the floating-point controls of an entry point.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FloatControlsError {
+    /// The given id is not declared by any `OpEntryPoint`.
+    NotAnEntryPoint(Word),
+    /// The given bit width is not one of 16, 32 and 64.
+    InvalidWidth(u32),
+    /// The module does not declare the capability required by the given
+    /// execution mode.
+    MissingCapability(spirv::ExecutionMode, spirv::Capability),
+}
+
+impl FloatControlsError {
+    fn describe(&self) -> &str {
+        match *self {
+            FloatControlsError::NotAnEntryPoint(_) => "not an entry point",
+            FloatControlsError::InvalidWidth(_) => "invalid floating-point width",
+            FloatControlsError::MissingCapability(..) => "missing capability",
+        }
+    }
+}
+
+impl error::Error for FloatControlsError {
+    fn description(&self) -> &str {
+        self.describe()
+    }
+}
+
+impl fmt::Display for FloatControlsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FloatControlsError::NotAnEntryPoint(id) => write!(f, "%{} is not an entry point", id),
+            FloatControlsError::InvalidWidth(width) => {
+                write!(f, "invalid floating-point width {}", width)
+            }
+            FloatControlsError::MissingCapability(mode, capability) => {
+                write!(f, "execution mode {} requires capability {}", mode, capability)
+            }
+        }
+    }
+}
+
+impl Module {
+    /// Returns the floating-point controls declared for the entry point
+    /// `entry`.
+    pub fn float_controls(&self, entry: Word) -> FloatControls {
+        let mut controls = FloatControls::default();
+        for inst in &self.execution_modes {
+            let (mode, width) = match inst.operands[..] {
+                [Operand::IdRef(id), Operand::ExecutionMode(mode), Operand::LiteralInt32(width)]
+                    if id == entry => (mode, width),
+                _ => continue,
+            };
+            match mode {
+                spirv::ExecutionMode::DenormPreserve => {
+                    controls.denorm.insert(width, DenormMode::Preserve);
+                }
+                spirv::ExecutionMode::DenormFlushToZero => {
+                    controls.denorm.insert(width, DenormMode::FlushToZero);
+                }
+                spirv::ExecutionMode::SignedZeroInfNanPreserve => {
+                    controls.signed_zero_inf_nan_preserve.insert(width);
+                }
+                spirv::ExecutionMode::RoundingModeRTE => {
+                    controls.rounding.insert(width, RoundingMode::Rte);
+                }
+                spirv::ExecutionMode::RoundingModeRTZ => {
+                    controls.rounding.insert(width, RoundingMode::Rtz);
+                }
+                _ => {}
+            }
+        }
+        controls
+    }
+
+    /// Replaces the floating-point controls declared for the entry point
+    /// `entry` with `controls`.
+    ///
+    /// Each execution mode requires the capability of the same name, which
+    /// the module must already declare. On error, the module is left
+    /// unchanged.
+    pub fn set_float_controls(
+        &mut self,
+        entry: Word,
+        controls: FloatControls,
+    ) -> Result<(), FloatControlsError> {
+        let is_entry_point = self.entry_points.iter().any(|inst| {
+            matches!(inst.operands.get(1), Some(&Operand::IdRef(id)) if id == entry)
+        });
+        if !is_entry_point {
+            return Err(FloatControlsError::NotAnEntryPoint(entry));
+        }
+
+        let declared: BTreeSet<spirv::Capability> = self
+            .capabilities
+            .iter()
+            .filter_map(|inst| match inst.operands.first() {
+                Some(&Operand::Capability(c)) => Some(c),
+                _ => None,
+            })
+            .collect();
+        let modes = controls.execution_modes();
+        for &(mode, width) in &modes {
+            if !matches!(width, 16 | 32 | 64) {
+                return Err(FloatControlsError::InvalidWidth(width));
+            }
+            if let Some(&capability) =
+                mode.required_capabilities().iter().find(|c| !declared.contains(c))
+            {
+                return Err(FloatControlsError::MissingCapability(mode, capability));
+            }
+        }
+
+        self.execution_modes.retain(|inst| match inst.operands[..] {
+            [Operand::IdRef(id), Operand::ExecutionMode(mode), ..] => {
+                id != entry || !is_float_control(mode)
+            }
+            _ => true,
+        });
+        for (mode, width) in modes {
+            self.execution_modes.push(Instruction::new(
+                spirv::Op::ExecutionMode,
+                None,
+                None,
+                vec![
+                    Operand::IdRef(entry),
+                    Operand::ExecutionMode(mode),
+                    Operand::LiteralInt32(width),
+                ],
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::Disassemble;
+    use mr;
+    use spirv;
+
+    use super::{DenormMode, FloatControls, FloatControlsError, RoundingMode};
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_set_float_controls() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::DenormFlushToZero);
+        b.capability(spirv::Capability::RoundingModeRTE);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, f, "main", vec![]);
+        b.execution_mode(f, spirv::ExecutionMode::LocalSize, [1, 1, 1]);
+        b.execution_mode(f, spirv::ExecutionMode::DenormFlushToZero, [16]);
+        let mut module = b.module();
+
+        let mut controls = module.float_controls(f);
+        assert_eq!(Some(&DenormMode::FlushToZero), controls.denorm.get(&16));
+        assert!(controls.rounding.is_empty());
+
+        controls.denorm.insert(32, DenormMode::FlushToZero);
+        controls.rounding.insert(32, RoundingMode::Rte);
+        assert_eq!(Ok(()), module.set_float_controls(f, controls.clone()));
+        assert_eq!(controls, module.float_controls(f));
+        let modes: Vec<String> =
+            module.execution_modes.iter().map(|inst| inst.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "OpExecutionMode %3 LocalSize 1 1 1",
+                "OpExecutionMode %3 DenormFlushToZero 16",
+                "OpExecutionMode %3 DenormFlushToZero 32",
+                "OpExecutionMode %3 RoundingModeRTE 32",
+            ],
+            modes
+        );
+
+        let mut preserve = FloatControls::default();
+        preserve.denorm.insert(64, DenormMode::Preserve);
+        assert_eq!(
+            Err(FloatControlsError::MissingCapability(
+                spirv::ExecutionMode::DenormPreserve,
+                spirv::Capability::DenormPreserve
+            )),
+            module.set_float_controls(f, preserve)
+        );
+        let mut odd = FloatControls::default();
+        odd.signed_zero_inf_nan_preserve.insert(8);
+        assert_eq!(
+            Err(FloatControlsError::InvalidWidth(8)),
+            module.set_float_controls(f, odd)
+        );
+        assert_eq!(
+            Err(FloatControlsError::NotAnEntryPoint(1)),
+            module.set_float_controls(1, FloatControls::default())
+        );
+        assert_eq!(4, module.execution_modes.len());
+
+        assert_eq!(Ok(()), module.set_float_controls(f, FloatControls::default()));
+        assert_eq!(1, module.execution_modes.len());
+    }
+}
@@ -0,0 +1,630 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use spirv;
+
+use spirv::Word;
+use std::collections::{BTreeMap, BTreeSet};
+use super::{BasicBlock, Function, Instruction, Module, ModuleHeader, Operand};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Returns the ids referenced by the given instruction, including its
+/// result type.
+fn referenced_ids(inst: &Instruction) -> impl Iterator<Item = Word> + '_ {
+    inst.result_type.into_iter().chain(inst.operands.iter().filter_map(|operand| match *operand {
+        Operand::IdMemorySemantics(id) | Operand::IdScope(id) | Operand::IdRef(id) => Some(id),
+        _ => None,
+    }))
+}
+
+/// Returns the id of the target of the given annotation, if it targets a
+/// single id, which is not the case for group decorations.
+fn annotation_target(inst: &Instruction) -> Option<Word> {
+    match (inst.class.opcode, inst.operands.first()) {
+        (spirv::Op::GroupDecorate, _) | (spirv::Op::GroupMemberDecorate, _) => None,
+        (_, Some(&Operand::IdRef(id))) => Some(id),
+        _ => None,
+    }
+}
+
+fn function_id(function: &Function) -> Option<Word> {
+    function.def.as_ref().and_then(|def| def.result_id)
+}
+
+/// Extracts the function with the given result `id` from `module` as a
+/// standalone fragment, e.g., for caching or distributing it on its own.
+///
+/// The fragment is a module containing the function first, followed by
+/// all functions it transitively calls. It also contains all types,
+/// constants, global variables, extended instruction set imports, and
+/// OpString instructions these functions transitively use, together with
+/// their decorations and debug names, as well as all capabilities,
+/// extensions, and the memory model of `module`. Entry points are not
+/// included. Decoration groups are not followed either; expand them first
+/// if needed. Ids are kept as they are in `module`, so the fragment can be
+/// assembled like any module, and reinserted into a host module with
+/// [`insert_fragment`](fn.insert_fragment.html).
+///
+/// Returns `None` if there is no function with the given `id`.
+pub fn extract_function(module: &Module, id: Word) -> Option<Module> {
+    extract_function_with(module, id, &[])
+}
+
+/// Extracts the function with the given result `id` from `module` like
+/// [`extract_function`](fn.extract_function.html), additionally keeping
+/// the global values in `roots` and everything they use.
+pub(crate) fn extract_function_with(module: &Module, id: Word, roots: &[Word]) -> Option<Module> {
+    let functions: BTreeMap<Word, &Function> = module.functions
+        .iter()
+        .filter_map(|f| Some((function_id(f)?, f)))
+        .collect();
+    functions.get(&id)?;
+    let globals: BTreeMap<Word, &Instruction> = module.ext_inst_imports
+        .iter()
+        .chain(&module.types_global_values)
+        .filter_map(|inst| Some((inst.result_id?, inst)))
+        .collect();
+    let mut annotations: BTreeMap<Word, Vec<&Instruction>> = BTreeMap::new();
+    for inst in &module.annotations {
+        if let Some(target) = annotation_target(inst) {
+            annotations.entry(target).or_default().push(inst);
+        }
+    }
+
+    let mut used = BTreeSet::new();
+    let mut worklist = vec![id];
+    worklist.extend_from_slice(roots);
+    while let Some(id) = worklist.pop() {
+        if !used.insert(id) {
+            continue;
+        }
+        let insts: Vec<&Instruction> = match functions.get(&id) {
+            Some(f) => f.all_inst_iter().collect(),
+            None => globals.get(&id).cloned().into_iter().collect(),
+        };
+        for inst in insts.into_iter().chain(annotations.get(&id).into_iter().flatten().cloned()) {
+            // Also collect local result ids for their decorations and names.
+            worklist.extend(inst.result_id);
+            worklist.extend(referenced_ids(inst));
+        }
+    }
+    let is_used = |inst: &Instruction| match (inst.result_id, inst.class.opcode) {
+        (Some(id), _) => used.contains(&id),
+        (None, spirv::Op::TypeForwardPointer) => match inst.operands.first() {
+            Some(&Operand::IdRef(id)) => used.contains(&id),
+            _ => false,
+        },
+        _ => false,
+    };
+
+    let mut fragment = Module::new();
+    fragment.capabilities = module.capabilities.clone();
+    fragment.extensions = module.extensions.clone();
+    fragment.ext_inst_imports =
+        module.ext_inst_imports.iter().filter(|i| is_used(i)).cloned().collect();
+    fragment.memory_model = module.memory_model.clone();
+    fragment.debugs = module.debugs
+        .iter()
+        .filter(|inst| match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::String, _) => is_used(inst),
+            (spirv::Op::Name, Some(&Operand::IdRef(id))) |
+            (spirv::Op::MemberName, Some(&Operand::IdRef(id))) => used.contains(&id),
+            _ => false,
+        })
+        .cloned()
+        .collect();
+    fragment.annotations = module.annotations
+        .iter()
+        .filter(|inst| annotation_target(inst).is_some_and(|target| used.contains(&target)))
+        .cloned()
+        .collect();
+    fragment.types_global_values =
+        module.types_global_values.iter().filter(|i| is_used(i)).cloned().collect();
+    fragment.functions.push(functions[&id].clone());
+    fragment.functions.extend(
+        module.functions
+            .iter()
+            .filter(|f| function_id(f).is_some_and(|f| f != id && used.contains(&f)))
+            .cloned(),
+    );
+    let mut header = ModuleHeader::new(fragment.max_id() + 1);
+    if let Some(ref h) = module.header {
+        let (major, minor) = h.version();
+        header.set_version(major, minor);
+    }
+    fragment.header = Some(header);
+    Some(fragment)
+}
+
+/// Maps ids of a fragment to ids of the host module it is inserted into,
+/// allocating fresh host ids for ids not mapped yet.
+struct IdMap {
+    ids: BTreeMap<Word, Word>,
+    bound: Word,
+}
+
+impl IdMap {
+    fn get(&mut self, id: Word) -> Word {
+        let bound = &mut self.bound;
+        *self.ids.entry(id).or_insert_with(|| {
+            *bound += 1;
+            *bound - 1
+        })
+    }
+
+    /// Returns the host ids for the given fragment ids without allocating
+    /// any, or `None` if some id is not mapped yet.
+    fn lookup_operands(&self, operands: &[Operand]) -> Option<Vec<Operand>> {
+        operands
+            .iter()
+            .map(|operand| {
+                Some(match *operand {
+                    Operand::IdMemorySemantics(id) => {
+                        Operand::IdMemorySemantics(*self.ids.get(&id)?)
+                    }
+                    Operand::IdScope(id) => Operand::IdScope(*self.ids.get(&id)?),
+                    Operand::IdRef(id) => Operand::IdRef(*self.ids.get(&id)?),
+                    ref operand => operand.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn operands(&mut self, operands: &[Operand]) -> Vec<Operand> {
+        operands
+            .iter()
+            .map(|operand| match *operand {
+                Operand::IdMemorySemantics(id) => Operand::IdMemorySemantics(self.get(id)),
+                Operand::IdScope(id) => Operand::IdScope(self.get(id)),
+                Operand::IdRef(id) => Operand::IdRef(self.get(id)),
+                ref operand => operand.clone(),
+            })
+            .collect()
+    }
+
+    fn instruction(&mut self, inst: &Instruction) -> Instruction {
+        let mut inst = inst.clone();
+        inst.result_type = inst.result_type.map(|id| self.get(id));
+        inst.result_id = inst.result_id.map(|id| self.get(id));
+        inst.operands = self.operands(&inst.operands).into();
+        inst
+    }
+}
+
+/// Renders the given annotation with the given `operands`, which exclude
+/// the target, for comparing decorations.
+fn decoration_key(inst: &Instruction, operands: &[Operand]) -> String {
+    format!("{:?} {:?}", inst.class.opcode, operands)
+}
+
+/// Returns the decorations of all ids in the given `annotations`, each
+/// rendered as a sorted list of strings excluding the targets.
+fn decorations<'a, I>(annotations: I) -> BTreeMap<Word, Vec<String>>
+where
+    I: Iterator<Item = &'a Instruction>,
+{
+    let mut decorations: BTreeMap<Word, Vec<String>> = BTreeMap::new();
+    for inst in annotations {
+        if let Some(target) = annotation_target(inst) {
+            let decoration = decoration_key(inst, &inst.operands[1..]);
+            decorations.entry(target).or_default().push(decoration);
+        }
+    }
+    for decorations in decorations.values_mut() {
+        decorations.sort();
+    }
+    decorations
+}
+
+/// Returns a key identifying the type or constant defined by the given
+/// instruction together with its `decorations`, if instructions with the
+/// same key can be unified.
+///
+/// Global variables are only unified if they are decorated, e.g., with
+/// the same built-in or descriptor set and binding.
+fn global_key(inst: &Instruction, decorations: Option<&Vec<String>>) -> Option<String> {
+    let opcode = inst.class.opcode;
+    let unifiable = match opcode {
+        spirv::Op::TypeForwardPointer => false,
+        spirv::Op::ConstantTrue |
+        spirv::Op::ConstantFalse |
+        spirv::Op::Constant |
+        spirv::Op::ConstantComposite |
+        spirv::Op::ConstantSampler |
+        spirv::Op::ConstantNull => true,
+        spirv::Op::Variable => decorations.is_some_and(|d| !d.is_empty()),
+        _ => grammar::reflect::is_type(opcode),
+    };
+    if !unifiable {
+        return None;
+    }
+    Some(format!("{:?} {:?} {:?} {:?}", opcode, inst.result_type, inst.operands, decorations))
+}
+
+/// Returns the id of the global of the host identical to the given fragment
+/// global `inst` with the given `annotations`, as found in `host_globals`.
+///
+/// Fragment ids not mapped yet do not match any host id, so this never
+/// changes `map`.
+fn find_global(
+    inst: &Instruction,
+    annotations: &[&Instruction],
+    map: &IdMap,
+    host_globals: &BTreeMap<String, Word>,
+) -> Option<Word> {
+    let mut decorations = annotations
+        .iter()
+        .map(|a| Some(decoration_key(a, &map.lookup_operands(&a.operands[1..])?)))
+        .collect::<Option<Vec<String>>>()?;
+    decorations.sort();
+    let mut key_inst = inst.clone();
+    key_inst.result_type = match inst.result_type {
+        Some(id) => Some(*map.ids.get(&id)?),
+        None => None,
+    };
+    key_inst.operands = map.lookup_operands(&inst.operands)?.into();
+    let decorations = if decorations.is_empty() { None } else { Some(&decorations) };
+    host_globals.get(&global_key(&key_inst, decorations)?).cloned()
+}
+
+/// Inserts the functions of the given `fragment`, as extracted by
+/// [`extract_function`](fn.extract_function.html), into `module`.
+///
+/// All ids of the fragment are renumbered to fresh ids of `module`, except
+/// that types, constants, decorated global variables, extended
+/// instruction set imports, and OpString instructions identical to ones in
+/// `module`, including their decorations, are unified with them.
+/// Capabilities, extensions, and the memory model are added as needed.
+/// The bound in the module header is updated accordingly.
+///
+/// Returns the new id of the first function of the fragment, or `None` if
+/// the fragment has no functions or lacks result ids of functions,
+/// extended instruction set imports, or OpString instructions. `module` is
+/// left untouched then.
+pub fn insert_fragment(module: &mut Module, fragment: &Module) -> Option<Word> {
+    let root = function_id(fragment.functions.first()?)?;
+    let strings = fragment.debugs.iter().filter(|inst| inst.class.opcode == spirv::Op::String);
+    if fragment.ext_inst_imports.iter().chain(strings).any(|inst| inst.result_id.is_none()) {
+        return None;
+    }
+
+    let bound = module.header.as_ref().map_or(0, |h| h.bound()).max(module.max_id() + 1);
+    let mut map = IdMap {
+        ids: BTreeMap::new(),
+        bound,
+    };
+
+    for inst in &fragment.capabilities {
+        if !module.capabilities.iter().any(|i| i.operands == inst.operands) {
+            module.capabilities.push(inst.clone());
+        }
+    }
+    for inst in &fragment.extensions {
+        if !module.extensions.iter().any(|i| i.operands == inst.operands) {
+            module.extensions.push(inst.clone());
+        }
+    }
+    if module.memory_model.is_none() {
+        module.memory_model = fragment.memory_model.clone();
+    }
+    for inst in &fragment.ext_inst_imports {
+        let existing = module.ext_inst_imports
+            .iter()
+            .find(|i| i.operands == inst.operands)
+            .and_then(|i| i.result_id);
+        match (inst.result_id, existing) {
+            (Some(id), Some(existing)) => {
+                map.ids.insert(id, existing);
+            }
+            _ => module.ext_inst_imports.push(map.instruction(inst)),
+        }
+    }
+
+    // Unify types and constants, remembering which ids are unified so that
+    // their decorations and names are not duplicated.
+    let host_decorations = decorations(module.annotations.iter());
+    let host_globals: BTreeMap<String, Word> = module.types_global_values
+        .iter()
+        .filter_map(|inst| {
+            let id = inst.result_id?;
+            Some((global_key(inst, host_decorations.get(&id))?, id))
+        })
+        .collect();
+    let mut annotations: BTreeMap<Word, Vec<&Instruction>> = BTreeMap::new();
+    for inst in &fragment.annotations {
+        if let Some(target) = annotation_target(inst) {
+            annotations.entry(target).or_default().push(inst);
+        }
+    }
+    let mut unified = BTreeSet::new();
+    for inst in &fragment.types_global_values {
+        if let Some(id) = inst.result_id {
+            // Ids already mapped, e.g., by forward pointers, are kept.
+            if !map.ids.contains_key(&id) {
+                let annotations = annotations.get(&id).map_or(&[][..], |a| &a[..]);
+                if let Some(existing) = find_global(inst, annotations, &map, &host_globals) {
+                    map.ids.insert(id, existing);
+                    unified.insert(id);
+                    continue;
+                }
+            }
+        }
+        let inst = map.instruction(inst);
+        module.types_global_values.push(inst);
+    }
+
+    for inst in &fragment.annotations {
+        if annotation_target(inst).is_some_and(|target| !unified.contains(&target)) {
+            let inst = map.instruction(inst);
+            module.annotations.push(inst);
+        }
+    }
+    // Keep the debug instructions in their logical layout: strings before
+    // names before OpModuleProcessed.
+    for inst in &fragment.debugs {
+        match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::String, _) => {
+                let existing = module.debugs.iter().find(|i| {
+                    i.class.opcode == spirv::Op::String && i.operands == inst.operands
+                });
+                match (inst.result_id, existing.and_then(|i| i.result_id)) {
+                    (Some(id), Some(existing)) => {
+                        map.ids.insert(id, existing);
+                    }
+                    _ => {
+                        let index = module.debugs
+                            .iter()
+                            .position(|i| {
+                                !matches!(
+                                    i.class.opcode,
+                                    spirv::Op::String |
+                                        spirv::Op::SourceExtension |
+                                        spirv::Op::Source |
+                                        spirv::Op::SourceContinued
+                                )
+                            })
+                            .unwrap_or(module.debugs.len());
+                        let inst = map.instruction(inst);
+                        module.debugs.insert(index, inst);
+                    }
+                }
+            }
+            (_, Some(&Operand::IdRef(target))) if !unified.contains(&target) => {
+                let index = module.debugs
+                    .iter()
+                    .position(|i| i.class.opcode == spirv::Op::ModuleProcessed)
+                    .unwrap_or(module.debugs.len());
+                let inst = map.instruction(inst);
+                module.debugs.insert(index, inst);
+            }
+            _ => {}
+        }
+    }
+
+    for function in &fragment.functions {
+        let function = Function {
+            def: function.def.as_ref().map(|inst| map.instruction(inst)),
+            end: function.end.clone(),
+            parameters: function.parameters.iter().map(|inst| map.instruction(inst)).collect(),
+            basic_blocks: function.basic_blocks
+                .iter()
+                .map(|bb| {
+                    let label = bb.label.as_ref().map(|inst| map.instruction(inst));
+                    let instructions = bb.instructions.iter().map(|i| map.instruction(i)).collect();
+                    BasicBlock { label, instructions }
+                })
+                .collect(),
+        };
+        module.functions.push(function);
+    }
+
+    if let Some(ref mut header) = module.header {
+        header.update_bound(map.bound - 1);
+    }
+    Some(map.get(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::{extract_function, insert_fragment};
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    /// Returns a module with functions `main` calling `helper`, and an
+    /// unrelated function `other`, with the id of `main`.
+    fn module() -> (mr::Module, spirv::Word) {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let int = b.type_int(32, 1);
+        let output = b.type_pointer(None, spirv::StorageClass::Output, float);
+        let color = b.variable(output, None, spirv::StorageClass::Output, None);
+        b.decorate(color, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(0)]);
+        let one = b.constant_f32(float, 1.0);
+        let zero = b.constant_u32(int, 0);
+        let voidf = b.type_function(void, vec![]);
+        let floatf = b.type_function(float, vec![]);
+
+        let helper = b.begin_function(float, None, spirv::FunctionControl::NONE, floatf).unwrap();
+        b.name(helper, "helper");
+        b.begin_basic_block(None).unwrap();
+        b.ret_value(one).unwrap();
+        b.end_function().unwrap();
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.name(main, "main");
+        b.begin_basic_block(None).unwrap();
+        let value = b.function_call(float, None, helper, vec![]).unwrap();
+        b.store(color, value, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        b.begin_function(int, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret_value(zero).unwrap();
+        b.end_function().unwrap();
+        (b.module(), main)
+    }
+
+    #[test]
+    fn test_extract_function() {
+        let (m, main) = module();
+        assert!(extract_function(&m, 1000).is_none());
+        let fragment = extract_function(&m, main).unwrap();
+        assert_eq!(
+            "; SPIR-V\n\
+             ; Version: 1.3\n\
+             ; Generator: rspirv\n\
+             ; Bound: 15\n\
+             OpCapability Shader\n\
+             OpMemoryModel Logical GLSL450\n\
+             OpName %10 \"helper\"\n\
+             OpName %12 \"main\"\n\
+             OpDecorate %5 Location 0\n\
+             %1 = OpTypeVoid\n\
+             %2 = OpTypeFloat 32\n\
+             %4 = OpTypePointer Output %2\n\
+             %5 = OpVariable  %4  Output\n\
+             %6 = OpConstant  %2  1.0\n\
+             %8 = OpTypeFunction %1\n\
+             %9 = OpTypeFunction %2\n\
+             %12 = OpFunction  %1  None %8\n\
+             %13 = OpLabel\n\
+             %14 = OpFunctionCall  %2  %10\n\
+             OpStore %5 %14\n\
+             OpReturn\n\
+             OpFunctionEnd\n\
+             %10 = OpFunction  %2  None %9\n\
+             %11 = OpLabel\n\
+             OpReturnValue %6\n\
+             OpFunctionEnd",
+            fragment.disassemble()
+        );
+    }
+
+    #[test]
+    fn test_insert_fragment() {
+        let (m, main) = module();
+        let fragment = extract_function(&m, main).unwrap();
+
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        let float = b.type_float(32);
+        let output = b.type_pointer(None, spirv::StorageClass::Output, float);
+        let color = b.variable(output, None, spirv::StorageClass::Output, None);
+        b.decorate(color, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(0)]);
+        let mut host = b.module();
+
+        assert_eq!(Some(9), insert_fragment(&mut host, &fragment));
+        assert_eq!(
+            "; SPIR-V\n\
+             ; Version: 1.3\n\
+             ; Generator: rspirv\n\
+             ; Bound: 13\n\
+             OpCapability Shader\n\
+             OpMemoryModel Logical GLSL450\n\
+             OpName %8 \"helper\"\n\
+             OpName %9 \"main\"\n\
+             OpDecorate %3 Location 0\n\
+             %1 = OpTypeFloat 32\n\
+             %2 = OpTypePointer Output %1\n\
+             %3 = OpVariable  %2  Output\n\
+             %4 = OpTypeVoid\n\
+             %5 = OpConstant  %1  1.0\n\
+             %6 = OpTypeFunction %4\n\
+             %7 = OpTypeFunction %1\n\
+             %9 = OpFunction  %4  None %6\n\
+             %10 = OpLabel\n\
+             %11 = OpFunctionCall  %1  %8\n\
+             OpStore %3 %11\n\
+             OpReturn\n\
+             OpFunctionEnd\n\
+             %8 = OpFunction  %1  None %7\n\
+             %12 = OpLabel\n\
+             OpReturnValue %5\n\
+             OpFunctionEnd",
+            host.disassemble()
+        );
+
+        // Inserting again unifies all globals with the ones inserted before.
+        assert_eq!(Some(14), insert_fragment(&mut host, &fragment));
+        assert_eq!(7, host.types_global_values.len());
+        assert_eq!(1, host.annotations.len());
+        assert_eq!(4, host.functions.len());
+        assert_eq!(18, host.header.unwrap().bound());
+    }
+
+    #[test]
+    fn test_insert_fragment_unifies_later_decoration_operands() {
+        let build = |with_struct: bool| {
+            let mut b = mr::Builder::new();
+            let void = b.type_void();
+            let uint = b.type_int(32, 0);
+            let alignment = b.id();
+            if with_struct {
+                let s = b.type_struct(vec![uint]);
+                let operands = vec![mr::Operand::IdRef(alignment)];
+                b.decorate_id(s, spirv::Decoration::AlignmentId, operands);
+            }
+            b.constant_u32(uint, 16);
+            let last = b.module_ref().types_global_values.len() - 1;
+            b.module_mut().types_global_values[last].result_id = Some(alignment);
+            let voidf = b.type_function(void, vec![]);
+            b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+            b.begin_basic_block(None).unwrap();
+            b.ret().unwrap();
+            b.end_function().unwrap();
+            b.module()
+        };
+        let fragment = build(true);
+        let mut host = build(false);
+
+        assert_eq!(Some(9), insert_fragment(&mut host, &fragment));
+        assert_eq!(
+            vec![
+                "%1 = OpTypeVoid",
+                "%2 = OpTypeInt 32 0",
+                "%3 = OpConstant  %2  16",
+                "%5 = OpTypeFunction %1",
+                "%8 = OpTypeStruct %2",
+            ],
+            host.types_global_values.iter().map(|i| i.disassemble()).collect::<Vec<_>>()
+        );
+        assert_eq!("OpDecorateId %8 AlignmentId %3", host.annotations[0].disassemble());
+        assert_eq!(11, host.header.unwrap().bound());
+    }
+
+    #[test]
+    fn test_insert_malformed_fragment() {
+        let (m, main) = module();
+        let mut fragment = extract_function(&m, main).unwrap();
+        let mut string = mr::Instruction::new(spirv::Op::String, None, Some(1), vec![]);
+        string.operands.push(mr::Operand::LiteralString("a.hlsl".into()));
+        string.result_id = None;
+        fragment.debugs.push(string);
+
+        let (mut host, _) = module();
+        let before = host.disassemble();
+        assert_eq!(None, insert_fragment(&mut host, &fragment));
+        assert_eq!(before, host.disassemble());
+    }
+}
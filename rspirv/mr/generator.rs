@@ -0,0 +1,140 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+/// Tools registered for generating SPIR-V modules.
+///
+/// The upper 16 bits of the generator magic number in the module header
+/// identify the tool; see the `<ids type="vendor">` section of the SPIR-V
+/// XML registry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Generator {
+    KhronosGroup,
+    LunarG,
+    Valve,
+    Codeplay,
+    Nvidia,
+    Arm,
+    LlvmSpirvTranslator,
+    SpirvToolsAssembler,
+    Glslang,
+    Qualcomm,
+    Amd,
+    Intel,
+    Imagination,
+    Shaderc,
+    Spiregg,
+    Rspirv,
+    MesaIrTranslator,
+    SpirvToolsLinker,
+    Vkd3d,
+    Clay,
+    Whlsl,
+    Clspv,
+    MlirSerializer,
+    Tint,
+    Angle,
+    Messiah,
+    Xenia,
+    RustGpu,
+    Naga,
+    /// A tool not known to this crate, with the given tool id.
+    Unknown(u16),
+}
+
+/// Known generators, indexed by their tool ids.
+const KNOWN: &[(Generator, &str)] = &[
+    (Generator::KhronosGroup, "The Khronos Group"),
+    (Generator::LunarG, "LunarG"),
+    (Generator::Valve, "Valve"),
+    (Generator::Codeplay, "Codeplay"),
+    (Generator::Nvidia, "NVIDIA"),
+    (Generator::Arm, "ARM"),
+    (Generator::LlvmSpirvTranslator, "LLVM/SPIR-V Translator"),
+    (Generator::SpirvToolsAssembler, "SPIR-V Tools Assembler"),
+    (Generator::Glslang, "Glslang"),
+    (Generator::Qualcomm, "Qualcomm"),
+    (Generator::Amd, "AMD"),
+    (Generator::Intel, "Intel"),
+    (Generator::Imagination, "Imagination"),
+    (Generator::Shaderc, "Shaderc"),
+    (Generator::Spiregg, "spiregg"),
+    (Generator::Rspirv, "rspirv"),
+    (Generator::MesaIrTranslator, "Mesa-IR/SPIR-V Translator"),
+    (Generator::SpirvToolsLinker, "SPIR-V Tools Linker"),
+    (Generator::Vkd3d, "VKD3D Shader Compiler"),
+    (Generator::Clay, "Clay Shader Compiler"),
+    (Generator::Whlsl, "WHLSL Shader Translator"),
+    (Generator::Clspv, "Clspv"),
+    (Generator::MlirSerializer, "MLIR SPIR-V Serializer"),
+    (Generator::Tint, "Tint Compiler"),
+    (Generator::Angle, "ANGLE Shader Compiler"),
+    (Generator::Messiah, "Messiah Shader Compiler"),
+    (Generator::Xenia, "Xenia Emulator Microcode Translator"),
+    (Generator::RustGpu, "Rust GPU Compiler Backend"),
+    (Generator::Naga, "Naga"),
+];
+
+impl Generator {
+    /// Returns the generator with the given tool id.
+    pub fn from_tool(tool: u16) -> Generator {
+        match KNOWN.get(tool as usize) {
+            Some(&(generator, _)) => generator,
+            None => Generator::Unknown(tool),
+        }
+    }
+
+    /// Returns the tool id of this generator.
+    pub fn tool(self) -> u16 {
+        match self {
+            Generator::Unknown(tool) => tool,
+            _ => KNOWN.iter().position(|&(g, _)| g == self).unwrap() as u16,
+        }
+    }
+
+    /// Returns the name of this generator, or "Unknown" for unknown tools.
+    pub fn name(self) -> &'static str {
+        match self {
+            Generator::Unknown(_) => "Unknown",
+            _ => KNOWN[self.tool() as usize].1,
+        }
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_generator() {
+        assert_eq!(Generator::Glslang, Generator::from_tool(8));
+        assert_eq!(15, Generator::Rspirv.tool());
+        assert_eq!("rspirv", Generator::Rspirv.name());
+        assert_eq!(Generator::Unknown(0xffff), Generator::from_tool(0xffff));
+        assert_eq!(0xffff, Generator::Unknown(0xffff).tool());
+        assert_eq!("Unknown", Generator::Unknown(0xffff).to_string());
+        for tool in 0..40 {
+            assert_eq!(tool, Generator::from_tool(tool).tool());
+        }
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+
+use spirv::{ImageOperands, Word};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Composes the ImageOperands mask of image instructions together with the
+/// operands following it.
+///
+/// The operands required by the bits set in the mask are emitted in the
+/// order given by the grammar, i.e., in the order of the bits, no matter
+/// in which order they are set on this builder.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::mr::{ImageOperandsBuilder, Operand};
+///
+/// fn main() {
+///     let (mask, operands) = ImageOperandsBuilder::new().const_offset(7).lod(5).build();
+///     assert_eq!(spirv::ImageOperands::LOD | spirv::ImageOperands::CONST_OFFSET, mask);
+///     assert_eq!(vec![Operand::IdRef(5), Operand::IdRef(7)], operands);
+/// }
+/// ```
+///
+/// The results can be passed to the `image_*` methods of the
+/// [`Builder`](struct.Builder.html), e.g.,
+/// [`image_sample_explicit_lod`](struct.Builder.html#method.image_sample_explicit_lod).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageOperandsBuilder {
+    bias: Option<Word>,
+    lod: Option<Word>,
+    grad: Option<(Word, Word)>,
+    const_offset: Option<Word>,
+    offset: Option<Word>,
+    const_offsets: Option<Word>,
+    sample: Option<Word>,
+    min_lod: Option<Word>,
+    make_texel_available: Option<Word>,
+    make_texel_visible: Option<Word>,
+    non_private_texel: bool,
+    volatile_texel: bool,
+}
+
+impl ImageOperandsBuilder {
+    /// Creates a new builder with no image operands.
+    pub fn new() -> ImageOperandsBuilder {
+        ImageOperandsBuilder::default()
+    }
+
+    /// Sets the Bias image operand to the given `bias` id.
+    pub fn bias(mut self, bias: Word) -> ImageOperandsBuilder {
+        self.bias = Some(bias);
+        self
+    }
+
+    /// Sets the Lod image operand to the given `lod` id.
+    pub fn lod(mut self, lod: Word) -> ImageOperandsBuilder {
+        self.lod = Some(lod);
+        self
+    }
+
+    /// Sets the Grad image operand to the given `dx` and `dy` ids.
+    pub fn grad(mut self, dx: Word, dy: Word) -> ImageOperandsBuilder {
+        self.grad = Some((dx, dy));
+        self
+    }
+
+    /// Sets the ConstOffset image operand to the given constant `offset`.
+    pub fn const_offset(mut self, offset: Word) -> ImageOperandsBuilder {
+        self.const_offset = Some(offset);
+        self
+    }
+
+    /// Sets the Offset image operand to the given `offset` id.
+    pub fn offset(mut self, offset: Word) -> ImageOperandsBuilder {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sets the ConstOffsets image operand to the given constant array of
+    /// `offsets`.
+    pub fn const_offsets(mut self, offsets: Word) -> ImageOperandsBuilder {
+        self.const_offsets = Some(offsets);
+        self
+    }
+
+    /// Sets the Sample image operand to the given `sample` id.
+    pub fn sample(mut self, sample: Word) -> ImageOperandsBuilder {
+        self.sample = Some(sample);
+        self
+    }
+
+    /// Sets the MinLod image operand to the given `min_lod` id.
+    pub fn min_lod(mut self, min_lod: Word) -> ImageOperandsBuilder {
+        self.min_lod = Some(min_lod);
+        self
+    }
+
+    /// Sets the MakeTexelAvailableKHR image operand to the given `scope` id.
+    pub fn make_texel_available(mut self, scope: Word) -> ImageOperandsBuilder {
+        self.make_texel_available = Some(scope);
+        self
+    }
+
+    /// Sets the MakeTexelVisibleKHR image operand to the given `scope` id.
+    pub fn make_texel_visible(mut self, scope: Word) -> ImageOperandsBuilder {
+        self.make_texel_visible = Some(scope);
+        self
+    }
+
+    /// Sets the NonPrivateTexelKHR image operand.
+    pub fn non_private_texel(mut self) -> ImageOperandsBuilder {
+        self.non_private_texel = true;
+        self
+    }
+
+    /// Sets the VolatileTexelKHR image operand.
+    pub fn volatile_texel(mut self) -> ImageOperandsBuilder {
+        self.volatile_texel = true;
+        self
+    }
+
+    /// Returns true if no image operand is set.
+    pub fn is_empty(&self) -> bool {
+        self.mask().is_empty()
+    }
+
+    /// Returns the ImageOperands mask of the image operands set.
+    pub fn mask(&self) -> ImageOperands {
+        let flags = [
+            (ImageOperands::BIAS, self.bias.is_some()),
+            (ImageOperands::LOD, self.lod.is_some()),
+            (ImageOperands::GRAD, self.grad.is_some()),
+            (ImageOperands::CONST_OFFSET, self.const_offset.is_some()),
+            (ImageOperands::OFFSET, self.offset.is_some()),
+            (ImageOperands::CONST_OFFSETS, self.const_offsets.is_some()),
+            (ImageOperands::SAMPLE, self.sample.is_some()),
+            (ImageOperands::MIN_LOD, self.min_lod.is_some()),
+            (ImageOperands::MAKE_TEXEL_AVAILABLE_KHR, self.make_texel_available.is_some()),
+            (ImageOperands::MAKE_TEXEL_VISIBLE_KHR, self.make_texel_visible.is_some()),
+            (ImageOperands::NON_PRIVATE_TEXEL_KHR, self.non_private_texel),
+            (ImageOperands::VOLATILE_TEXEL_KHR, self.volatile_texel),
+        ];
+        flags
+            .iter()
+            .filter(|&&(_, set)| set)
+            .fold(ImageOperands::NONE, |mask, &(flag, _)| mask | flag)
+    }
+
+    /// Returns the operands following the ImageOperands mask, in the order
+    /// given by the grammar.
+    pub fn operands(&self) -> Vec<mr::Operand> {
+        let mut operands = vec![];
+        let ids = self.bias.iter().chain(&self.lod);
+        operands.extend(ids.map(|&id| mr::Operand::IdRef(id)));
+        if let Some((dx, dy)) = self.grad {
+            operands.push(mr::Operand::IdRef(dx));
+            operands.push(mr::Operand::IdRef(dy));
+        }
+        let ids = self
+            .const_offset
+            .iter()
+            .chain(&self.offset)
+            .chain(&self.const_offsets)
+            .chain(&self.sample)
+            .chain(&self.min_lod);
+        operands.extend(ids.map(|&id| mr::Operand::IdRef(id)));
+        let scopes = self.make_texel_available.iter().chain(&self.make_texel_visible);
+        operands.extend(scopes.map(|&id| mr::Operand::IdScope(id)));
+        operands
+    }
+
+    /// Returns the ImageOperands mask together with the operands following
+    /// it.
+    pub fn build(&self) -> (ImageOperands, Vec<mr::Operand>) {
+        (self.mask(), self.operands())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::ImageOperandsBuilder;
+
+    #[test]
+    fn test_image_operands_order() {
+        let builder = ImageOperandsBuilder::new()
+            .make_texel_visible(9)
+            .min_lod(8)
+            .grad(2, 3)
+            .volatile_texel()
+            .sample(7)
+            .bias(1);
+        let (mask, operands) = builder.build();
+        assert_eq!(
+            spirv::ImageOperands::BIAS | spirv::ImageOperands::GRAD |
+                spirv::ImageOperands::SAMPLE | spirv::ImageOperands::MIN_LOD |
+                spirv::ImageOperands::MAKE_TEXEL_VISIBLE_KHR |
+                spirv::ImageOperands::VOLATILE_TEXEL_KHR,
+            mask
+        );
+        assert_eq!(
+            vec![
+                mr::Operand::IdRef(1),
+                mr::Operand::IdRef(2),
+                mr::Operand::IdRef(3),
+                mr::Operand::IdRef(7),
+                mr::Operand::IdRef(8),
+                mr::Operand::IdScope(9),
+            ],
+            operands
+        );
+        assert!(!builder.is_empty());
+        assert!(ImageOperandsBuilder::new().is_empty());
+    }
+
+    #[test]
+    fn test_image_sample_with_operands() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let (mask, operands) = ImageOperandsBuilder::new().const_offset(12).lod(11).build();
+        let id = b.image_sample_explicit_lod(10, None, 20, 21, mask, operands).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        let inst = &m.functions[0].basic_blocks[0].instructions[0];
+        assert_eq!(Some(id), inst.result_id);
+        assert_eq!(
+            vec![
+                mr::Operand::IdRef(20),
+                mr::Operand::IdRef(21),
+                mr::Operand::ImageOperands(
+                    spirv::ImageOperands::LOD | spirv::ImageOperands::CONST_OFFSET,
+                ),
+                mr::Operand::IdRef(11),
+                mr::Operand::IdRef(12),
+            ],
+            inst.operands
+        );
+    }
+}
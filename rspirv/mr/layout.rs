@@ -0,0 +1,277 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use spirv;
+
+use grammar::reflect;
+use super::{Instruction, Module, Operand};
+use spirv::Word;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Returns the opcode the given instruction has in the compiled-in tables,
+/// looking through instructions kept as raw words because of unknown
+/// enumerants.
+fn known_opcode(inst: &Instruction) -> Option<spirv::Op> {
+    match inst.unknown_opcode() {
+        Some(opcode) => grammar::CoreInstructionTable::lookup_opcode(opcode).map(|g| g.opcode),
+        None => Some(inst.class.opcode),
+    }
+}
+
+/// Returns the rank of the given debug instruction among the three groups
+/// of debug instructions required by the logical layout.
+fn debug_rank(inst: &Instruction) -> u32 {
+    match known_opcode(inst) {
+        Some(spirv::Op::String) |
+        Some(spirv::Op::SourceExtension) |
+        Some(spirv::Op::Source) |
+        Some(spirv::Op::SourceContinued) => 0,
+        Some(spirv::Op::ModuleProcessed) => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the id annotated by the given instruction, and whether it
+/// applies decoration groups.
+fn annotation_target(inst: &Instruction) -> (Option<Word>, bool) {
+    match (known_opcode(inst), inst.operands.first()) {
+        (Some(spirv::Op::DecorationGroup), _) => (inst.result_id, false),
+        (Some(spirv::Op::GroupDecorate), _) |
+        (Some(spirv::Op::GroupMemberDecorate), _) => (None, true),
+        (_, Some(&Operand::IdRef(id))) => (Some(id), false),
+        _ => (None, false),
+    }
+}
+
+/// Returns the ids referenced by the given instruction.
+fn referenced_ids(inst: &Instruction) -> impl Iterator<Item = Word> + '_ {
+    inst.result_type.into_iter().chain(inst.operands.iter().filter_map(|operand| {
+        match *operand {
+            Operand::IdMemorySemantics(id) | Operand::IdScope(id) | Operand::IdRef(id) => Some(id),
+            _ => None,
+        }
+    }))
+}
+
+/// Sorts the given types, constants, and global variables so that all ids
+/// are defined before being used, moving as few instructions as possible.
+///
+/// Instructions in dependency cycles, which are invalid, are kept last in
+/// their original order.
+fn sort_definitions(insts: &mut Vec<Instruction>) {
+    // The instruction each id must follow: its definition, or its forward
+    // declaration for pointers.
+    let mut defs = BTreeMap::new();
+    for (index, inst) in insts.iter().enumerate() {
+        if let Some(id) = inst.result_id {
+            defs.entry(id).or_insert(index);
+        }
+    }
+    for (index, inst) in insts.iter().enumerate() {
+        if let (spirv::Op::TypeForwardPointer, Some(&Operand::IdRef(id))) =
+            (inst.class.opcode, inst.operands.first())
+        {
+            defs.insert(id, index);
+        }
+    }
+
+    let mut pending = vec![0; insts.len()];
+    let mut dependents = vec![vec![]; insts.len()];
+    for (index, inst) in insts.iter().enumerate() {
+        let mut deps: Vec<usize> = referenced_ids(inst)
+            .filter_map(|id| defs.get(&id).cloned())
+            .filter(|&def| def != index)
+            .collect();
+        deps.sort_unstable();
+        deps.dedup();
+        pending[index] = deps.len();
+        for def in deps {
+            dependents[def].push(index);
+        }
+    }
+
+    // Always emitting the first ready instruction keeps the original order
+    // wherever it is already valid.
+    let mut ready: BinaryHeap<_> =
+        (0..insts.len()).filter(|&i| pending[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(insts.len());
+    while let Some(Reverse(index)) = ready.pop() {
+        order.push(index);
+        for &dependent in &dependents[index] {
+            pending[dependent] -= 1;
+            if pending[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
+    }
+    if order.len() < insts.len() {
+        order.extend((0..insts.len()).filter(|&i| pending[i] != 0));
+    }
+
+    let mut slots: Vec<Option<Instruction>> = insts.drain(..).map(Some).collect();
+    insts.extend(order.into_iter().map(|index| slots[index].take().unwrap()));
+}
+
+impl Module {
+    /// Reorders the debug, annotation, and type-constant-global-variable
+    /// sections into the logical layout required by the specification.
+    ///
+    /// - Debug instructions are grouped into, in order, OpString and
+    ///   OpSource* instructions, OpName and OpMemberName instructions, and
+    ///   OpModuleProcessed instructions.
+    /// - Annotations are grouped by the id they decorate, in the order in
+    ///   which the ids are first decorated, with each OpDecorationGroup
+    ///   after the decorations it collects, and OpGroupDecorate and
+    ///   OpGroupMemberDecorate last.
+    /// - Types, constants, and global variables are sorted so that all ids
+    ///   are defined before use.
+    ///
+    /// Sorting is stable: instructions are only moved when needed, so
+    /// calling this on an already ordered module changes nothing. Modules
+    /// edited directly or through builders inserting late definitions can
+    /// be put back into order this way before being assembled.
+    pub fn sort_global_section(&mut self) {
+        self.debugs.sort_by_key(debug_rank);
+
+        let mut first = BTreeMap::new();
+        for (index, inst) in self.annotations.iter().enumerate() {
+            if let (Some(id), _) = annotation_target(inst) {
+                first.entry(id).or_insert(index);
+            }
+        }
+        let keys: Vec<_> = self.annotations
+            .iter()
+            .enumerate()
+            .map(|(index, inst)| match annotation_target(inst) {
+                (_, true) => (1, index, 0),
+                (Some(id), _) => {
+                    let group = known_opcode(inst) == Some(spirv::Op::DecorationGroup);
+                    (0, first[&id], group as u32)
+                }
+                (None, false) => (0, index, 0),
+            })
+            .collect();
+        let mut annotations: Vec<_> = keys.into_iter().zip(self.annotations.drain(..)).collect();
+        annotations.sort_by_key(|&(key, _)| key);
+        self.annotations.extend(annotations.into_iter().map(|(_, inst)| inst));
+
+        sort_definitions(&mut self.types_global_values);
+    }
+
+    /// Returns an iterator over the OpString, OpSourceExtension, OpSource,
+    /// and OpSourceContinued instructions.
+    pub fn debug_sources(&self) -> impl Iterator<Item = &Instruction> {
+        self.debugs.iter().filter(|inst| debug_rank(inst) == 0)
+    }
+
+    /// Returns an iterator over the OpName and OpMemberName instructions.
+    pub fn debug_names(&self) -> impl Iterator<Item = &Instruction> {
+        self.debugs.iter().filter(|inst| debug_rank(inst) == 1)
+    }
+
+    /// Returns an iterator over the OpModuleProcessed instructions.
+    pub fn debug_module_processed(&self) -> impl Iterator<Item = &Instruction> {
+        self.debugs.iter().filter(|inst| debug_rank(inst) == 2)
+    }
+
+    /// Returns an iterator over the type declarations, including
+    /// OpTypeForwardPointer.
+    pub fn types(&self) -> impl Iterator<Item = &Instruction> {
+        self.types_global_values.iter().filter(|inst| reflect::is_type(inst.class.opcode))
+    }
+
+    /// Returns an iterator over the constants, including specialization
+    /// constants.
+    pub fn constants(&self) -> impl Iterator<Item = &Instruction> {
+        self.types_global_values.iter().filter(|inst| reflect::is_constant(inst.class.opcode))
+    }
+
+    /// Returns an iterator over the global variables.
+    pub fn global_variables(&self) -> impl Iterator<Item = &Instruction> {
+        self.types_global_values.iter().filter(|inst| reflect::is_variable(inst.class.opcode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::Disassemble;
+    use mr;
+    use spirv;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    fn disassemble(insts: &[mr::Instruction]) -> Vec<String> {
+        insts.iter().map(|inst| inst.disassemble()).collect()
+    }
+
+    #[test]
+    fn test_sort_global_section() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let one = b.constant_f32(float, 1.0);
+        let private = b.type_pointer(None, spirv::StorageClass::Private, float);
+        let var = b.variable(private, None, spirv::StorageClass::Private, Some(one));
+        b.name(var, "v");
+        b.source(spirv::SourceLanguage::GLSL, 450, None, None::<String>);
+        b.decorate(float, spirv::Decoration::RelaxedPrecision, vec![]);
+        b.decorate(var, spirv::Decoration::RelaxedPrecision, vec![]);
+        b.decorate(float, spirv::Decoration::Restrict, vec![]);
+        let mut module = b.module();
+        // A late definition of the float type.
+        module.types_global_values.rotate_left(1);
+
+        module.sort_global_section();
+        assert_eq!(
+            vec!["OpSource GLSL 450".to_string(), format!("OpName %{} \"v\"", var)],
+            disassemble(&module.debugs)
+        );
+        assert_eq!(
+            vec![
+                format!("OpDecorate %{} RelaxedPrecision", float),
+                format!("OpDecorate %{} Restrict", float),
+                format!("OpDecorate %{} RelaxedPrecision", var),
+            ],
+            disassemble(&module.annotations)
+        );
+        let ids: Vec<_> = module.types_global_values.iter().map(|i| i.result_id).collect();
+        assert_eq!(vec![Some(float), Some(one), Some(private), Some(var)], ids);
+
+        assert_eq!(1, module.debug_sources().count());
+        assert_eq!(1, module.debug_names().count());
+        assert_eq!(0, module.debug_module_processed().count());
+        assert_eq!(2, module.types().count());
+        assert_eq!(Some(one), module.constants().next().unwrap().result_id);
+        assert_eq!(Some(var), module.global_variables().next().unwrap().result_id);
+    }
+
+    #[test]
+    fn test_sort_global_section_forward_pointer() {
+        let mut b = mr::Builder::new();
+        let storage = spirv::StorageClass::CrossWorkgroup;
+        let node_ptr = b.id();
+        b.type_forward_pointer(node_ptr, storage);
+        let node = b.type_struct(vec![node_ptr]);
+        b.type_pointer(Some(node_ptr), storage, node);
+        let mut module = b.module();
+        let sorted = disassemble(&module.types_global_values);
+
+        module.types_global_values.reverse();
+        module.sort_global_section();
+        assert_eq!(sorted, disassemble(&module.types_global_values));
+    }
+}
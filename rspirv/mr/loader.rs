@@ -19,6 +19,10 @@ use grammar;
 
 use binary::{ParseAction, ParseResult};
 use std::{error, fmt};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Data representation loading errors.
 #[derive(Debug)]
@@ -37,6 +41,28 @@ pub enum Error {
     WrongOpExtInstImportOperand,
     WrongOpMemoryModelOperand,
     WrongOpNameOperand,
+    /// The operand at the given index is required by the grammar of the
+    /// given instruction but missing.
+    MissingOperand(spirv::Op, usize),
+    /// The operand at the given index does not match the grammar of the
+    /// given instruction, or is not expected at all.
+    WrongOperand(spirv::Op, usize),
+    /// The presence of result type id or result id does not match the
+    /// grammar of the given instruction.
+    MismatchedResult(spirv::Op),
+    /// The given opcode is not allowed in OpSpecConstantOp.
+    DisallowedSpecConstantOp(spirv::Op),
+    /// No function with the given id is defined.
+    FunctionNotFound(spirv::Word),
+    /// The OpPhi with the given result id names the given label as a parent
+    /// although its basic block is not a predecessor.
+    WrongPhiParent(spirv::Word, spirv::Word),
+    /// The type of the given access chain base is not known to be a
+    /// pointer.
+    WrongAccessChainBase(spirv::Word),
+    /// The access chain index at the given position does not select a
+    /// member, element, component, or column of the type indexed.
+    WrongAccessChainIndex(usize),
 }
 
 impl Error {
@@ -62,6 +88,14 @@ impl Error {
             Error::WrongOpExtInstImportOperand => "wrong OpExtInstImport operand",
             Error::WrongOpMemoryModelOperand => "wrong OpMemoryModel operand",
             Error::WrongOpNameOperand => "wrong OpName operand",
+            Error::MissingOperand(..) => "found missing operand",
+            Error::WrongOperand(..) => "found operand not matching the grammar",
+            Error::MismatchedResult(_) => "found result ids not matching the grammar",
+            Error::DisallowedSpecConstantOp(_) => "found opcode not allowed in OpSpecConstantOp",
+            Error::FunctionNotFound(_) => "found no function with the given id",
+            Error::WrongPhiParent(..) => "found OpPhi parent not being a predecessor",
+            Error::WrongAccessChainBase(_) => "found access chain base not being a pointer",
+            Error::WrongAccessChainIndex(_) => "found access chain index not selecting anything",
         }
     }
 }
@@ -74,7 +108,31 @@ impl error::Error for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.describe())
+        match *self {
+            Error::MissingOperand(opcode, index) => {
+                write!(f, "missing operand #{} for Op{:?}", index, opcode)
+            }
+            Error::WrongOperand(opcode, index) => {
+                write!(f, "wrong operand #{} for Op{:?}", index, opcode)
+            }
+            Error::MismatchedResult(opcode) => {
+                write!(f, "mismatched result type id or result id for Op{:?}", opcode)
+            }
+            Error::DisallowedSpecConstantOp(opcode) => {
+                write!(f, "Op{:?} not allowed in OpSpecConstantOp", opcode)
+            }
+            Error::FunctionNotFound(id) => write!(f, "no function with id %{}", id),
+            Error::WrongPhiParent(phi, parent) => {
+                write!(f, "parent %{} of OpPhi %{} is not a predecessor", parent, phi)
+            }
+            Error::WrongAccessChainBase(base) => {
+                write!(f, "access chain base %{} is not a pointer", base)
+            }
+            Error::WrongAccessChainIndex(position) => {
+                write!(f, "access chain index #{} does not select anything", position)
+            }
+            _ => write!(f, "{}", self.describe()),
+        }
     }
 }
 
@@ -85,11 +143,14 @@ impl fmt::Display for Error {
 ///
 /// It implements the [`Consumer`](../binary/trait.Consumer.html) trait and
 /// works with the [`Parser`](../binary/struct.Parser.html).
+///
+/// Equal literal strings in the loaded module share the same `Symbol`.
 #[derive(Default)]
 pub struct Loader {
     module: mr::Module,
     function: Option<mr::Function>,
     block: Option<mr::BasicBlock>,
+    strings: mr::Interner,
 }
 
 impl Loader {
@@ -99,13 +160,75 @@ impl Loader {
             module: mr::Module::new(),
             function: None,
             block: None,
+            strings: mr::Interner::new(),
         }
     }
 
+    /// Makes the loader record the location of every instruction in the
+    /// binary into [`Module::locations`](struct.Module.html#structfield.locations).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rspirv;
+    /// extern crate spirv_headers as spirv;
+    ///
+    /// use rspirv::binary::{Assemble, Parser};
+    /// use rspirv::mr::{Builder, Loader};
+    ///
+    /// fn main() {
+    ///     let mut b = Builder::new();
+    ///     b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+    ///     let float = b.type_float(32);
+    ///     let bytes: Vec<u8> = b.module().assemble_bytes();
+    ///
+    ///     let mut loader = Loader::new().with_locations();
+    ///     Parser::new(&bytes, &mut loader).parse().unwrap();
+    ///     let module = loader.module();
+    ///
+    ///     // The header has 5 words, OpMemoryModel has 3 words.
+    ///     let location = module.location(float).unwrap();
+    ///     assert_eq!(8, location.offset);
+    ///     assert_eq!(2, location.index);
+    /// }
+    /// ```
+    pub fn with_locations(mut self) -> Loader {
+        self.module.locations = Some(vec![]);
+        self
+    }
+
     /// Returns the `Module` under construction.
     pub fn module(self) -> mr::Module {
         self.module
     }
+
+    /// Puts an instruction with an unknown opcode into the basic block under
+    /// construction, or among the types and global values outside of
+    /// functions.
+    fn consume_unknown_instruction(&mut self, inst: mr::Instruction) -> ParseAction {
+        match (&mut self.function, &mut self.block) {
+            (_, &mut Some(ref mut block)) => block.instructions.push(inst),
+            (&mut None, _) => self.module.types_global_values.push(inst),
+            (&mut Some(_), &mut None) => {
+                return ParseAction::Error(Box::new(Error::DetachedInstruction))
+            }
+        }
+        ParseAction::Continue
+    }
+
+    /// Returns the `Module` under construction, including the basic block
+    /// and function still under construction, if any.
+    fn partial_module(mut self) -> mr::Module {
+        if let Some(block) = self.block.take() {
+            if let Some(ref mut function) = self.function {
+                function.basic_blocks.push(block);
+            }
+        }
+        if let Some(function) = self.function.take() {
+            self.module.functions.push(function);
+        }
+        self.module
+    }
 }
 
 /// Returns `$error` if `$condition` evaluates to false.
@@ -131,8 +254,21 @@ impl binary::Consumer for Loader {
         ParseAction::Continue
     }
 
-    fn consume_instruction(&mut self, inst: mr::Instruction) -> ParseAction {
-        let opcode = inst.class.opcode;
+    fn consume_instruction(&mut self, mut inst: mr::Instruction) -> ParseAction {
+        for operand in &mut inst.operands {
+            if let mr::Operand::LiteralString(ref mut s) = *operand {
+                *s = self.strings.intern_symbol(s.clone());
+            }
+        }
+        let opcode = match inst.unknown_opcode() {
+            // Instructions with known opcodes but unknown enumerants still
+            // go where their opcodes belong.
+            Some(opcode) => match grammar::CoreInstructionTable::lookup_opcode(opcode) {
+                Some(grammar) => grammar.opcode,
+                None => return self.consume_unknown_instruction(inst),
+            },
+            None => inst.class.opcode,
+        };
         match opcode {
             spirv::Op::Capability => self.module.capabilities.push(inst),
             spirv::Op::Extension => self.module.extensions.push(inst),
@@ -195,6 +331,16 @@ impl binary::Consumer for Loader {
         }
         ParseAction::Continue
     }
+
+    fn consume_located_instruction(&mut self,
+                                   inst: mr::Instruction,
+                                   location: mr::InstructionLocation)
+                                   -> ParseAction {
+        if let Some(ref mut locations) = self.module.locations {
+            locations.push(location);
+        }
+        self.consume_instruction(inst)
+    }
 }
 
 /// Loads the SPIR-V `binary` into memory and returns a `Module`.
@@ -225,7 +371,7 @@ impl binary::Consumer for Loader {
 /// assert_eq!(dis,
 ///            "; SPIR-V\n\
 ///             ; Version: 1.0\n\
-///             ; Generator: rspirv\n\
+///             ; Generator: The Khronos Group\n\
 ///             ; Bound: 0\n\
 ///             OpMemoryModel Logical GLSL450");
 /// ```
@@ -262,7 +408,7 @@ pub fn load_bytes<T: AsRef<[u8]>>(binary: T) -> ParseResult<mr::Module> {
 /// assert_eq!(dis,
 ///            "; SPIR-V\n\
 ///             ; Version: 1.0\n\
-///             ; Generator: rspirv\n\
+///             ; Generator: The Khronos Group\n\
 ///             ; Bound: 0\n\
 ///             OpMemoryModel Logical GLSL450");
 /// ```
@@ -272,10 +418,59 @@ pub fn load_words<T: AsRef<[u32]>>(binary: T) -> ParseResult<mr::Module> {
     Ok(loader.module())
 }
 
+/// Reads the SPIR-V binary from `reader` into memory and returns a `Module`.
+///
+/// The binary may be in either byte order; big-endian binaries are
+/// converted to little-endian before parsing. The reader does not need to
+/// provide whole words at once. Errors from the reader are returned as
+/// `State::ReadError`.
+#[cfg(feature = "std")]
+pub fn load_reader<R: io::Read>(mut reader: R) -> ParseResult<mr::Module> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes).map_err(binary::ParseState::ReadError)?;
+    if bytes.len() >= 4 && bytes[..4] == spirv::MAGIC_NUMBER.to_be_bytes() {
+        for word in bytes.chunks_mut(4) {
+            word.reverse();
+        }
+    }
+    load_bytes(bytes)
+}
+
+/// Loads the SPIR-V `binary` into memory in lenient mode and returns the
+/// partial `Module` together with diagnostics for the problems skipped.
+///
+/// Malformed instructions and instructions violating the module structure
+/// are skipped. A function or basic block not properly ended is still
+/// kept in the returned module. Only problems with the module header are
+/// returned as errors.
+///
+/// See [`Parser::parse_lenient`](../binary/struct.Parser.html#method.parse_lenient).
+pub fn load_bytes_lenient<T: AsRef<[u8]>>(
+    binary: T,
+) -> ParseResult<(mr::Module, Vec<binary::ParseDiagnostic>)> {
+    let mut loader = Loader::new();
+    let diagnostics = binary::Parser::new(binary.as_ref(), &mut loader).parse_lenient()?;
+    Ok((loader.partial_module(), diagnostics))
+}
+
+/// Loads the SPIR-V `binary` into memory in lenient mode and returns the
+/// partial `Module` together with diagnostics for the problems skipped.
+///
+/// See [`load_bytes_lenient`](fn.load_bytes_lenient.html).
+pub fn load_words_lenient<T: AsRef<[u32]>>(
+    binary: T,
+) -> ParseResult<(mr::Module, Vec<binary::ParseDiagnostic>)> {
+    let binary = binary.as_ref();
+    let bytes: Vec<u8> = binary.iter().flat_map(|w| w.to_le_bytes().to_vec()).collect();
+    load_bytes_lenient(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use mr;
     use spirv;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
 
     #[test]
     fn test_load_variable() {
@@ -312,6 +507,26 @@ mod tests {
         assert_eq!(inst.result_id.unwrap(), local);
     }
 
+    #[test]
+    fn test_load_interns_strings() {
+        use binary::Assemble;
+
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let light = b.type_struct(vec![float]);
+        b.name(light, "light");
+        b.member_name(light, 0, "light");
+        let m = mr::load_words(b.module().assemble()).unwrap();
+
+        match (&m.debugs[0].operands[1], &m.debugs[1].operands[2]) {
+            (mr::Operand::LiteralString(name), mr::Operand::LiteralString(member)) => {
+                assert_eq!("light", name.as_str());
+                assert!(name.ptr_eq(member));
+            }
+            _ => panic!("expected literal strings"),
+        }
+    }
+
     #[test]
     fn test_load_undef() {
         let mut b = mr::Builder::new();
@@ -346,4 +561,151 @@ mod tests {
         assert_eq!(inst.class.opcode, spirv::Op::Undef);
         assert_eq!(inst.result_id.unwrap(), local);
     }
+
+    #[test]
+    fn test_load_locations() {
+        use binary::{self, Assemble};
+
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidfvoid = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let words = b.module().assemble();
+
+        assert!(mr::load_words(&words).unwrap().locations.is_none());
+
+        let mut loader = mr::Loader::new().with_locations();
+        binary::parse_words(&words, &mut loader).unwrap();
+        let m = loader.module();
+        let locations = m.locations.as_ref().unwrap();
+        assert_eq!(7, locations.len());
+        assert_eq!(mr::InstructionLocation { offset: 5, index: 1, result_id: None },
+                   locations[0]);
+        // OpMemoryModel has 3 words, OpTypeVoid 2 words, OpTypeFunction
+        // 3 words.
+        assert_eq!(Some(&mr::InstructionLocation { offset: 13, index: 4, result_id: Some(f) }),
+                   m.location(f));
+        assert_eq!(words.len() - 1, locations[6].offset);
+        assert_eq!(None, m.location(42));
+    }
+
+    #[test]
+    fn test_load_words_lenient() {
+        use binary::{Assemble, Disassemble, ParseState};
+
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidfvoid = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut words = b.module().assemble();
+        // Missing operand for OpTypeFunction; the orphaned operand is then
+        // decoded as an instruction with zero word count.
+        words[10] = (words[10] & 0xffff) | 0x0002_0000;
+        // Cut off OpReturn and OpFunctionEnd.
+        words.truncate(words.len() - 2);
+
+        assert!(mr::load_words(&words).is_err());
+        let (module, diagnostics) = mr::load_words_lenient(&words).unwrap();
+        let diagnostics: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(3, diagnostics.len());
+        assert!(diagnostics[0]
+            .starts_with("opcode 33 of instruction #3 at offset 40: expected more operands"));
+        assert!(diagnostics[1]
+            .starts_with("opcode 1 of instruction #4 at offset 48: zero word count"));
+        assert_eq!(
+            "at offset 80: consumer error: found basic block without terminator",
+            diagnostics[2]
+        );
+        assert_eq!(1, module.functions.len());
+        assert_eq!(1, module.functions[0].basic_blocks.len());
+        assert_eq!(
+            "OpMemoryModel Logical GLSL450",
+            module.memory_model.as_ref().unwrap().disassemble()
+        );
+
+        // Errors in the header are still fatal.
+        match mr::load_words_lenient(&words[..3]) {
+            Err(ParseState::HeaderIncomplete(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_load_unknown_instructions() {
+        use binary::{Assemble, Disassemble};
+
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidfvoid = b.type_function(void, vec![]);
+        b.decorate(void, spirv::Decoration::RelaxedPrecision, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut words = b.module().assemble();
+        // Unknown decoration for OpDecorate.
+        assert_eq!(0x0003_0047, words[8]);
+        words[10] = 0x7fff;
+        // Unknown opcode before OpReturn.
+        let ret = words.len() - 2;
+        words.splice(ret..ret, vec![0x0003_ffff, 1, 2]);
+
+        let module = mr::load_words(&words).unwrap();
+        assert_eq!(1, module.annotations.len());
+        assert_eq!(Some(71), module.annotations[0].unknown_opcode());
+        assert_eq!("OpUnknown(71) 1 32767", module.annotations[0].disassemble());
+        let inst = &module.functions[0].basic_blocks[0].instructions[0];
+        assert_eq!(Some(0xffff), inst.unknown_opcode());
+        assert_eq!("OpUnknown(65535) 1 2", inst.disassemble());
+        assert_eq!(words, module.assemble());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_load_reader() {
+        use binary::{Assemble, Disassemble, ParseState};
+        use std::io;
+
+        /// A reader returning at most 3 bytes at a time.
+        struct Trickle<'a>(&'a [u8]);
+        impl<'a> io::Read for Trickle<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let len = buf.len().min(self.0.len()).min(3);
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        b.type_void();
+        let module = b.module();
+        let little = module.assemble_bytes();
+        let big: Vec<u8> = module.assemble().iter().flat_map(|w| w.to_be_bytes()).collect();
+        for bytes in &[little, big] {
+            let loaded = mr::load_reader(Trickle(bytes)).unwrap();
+            assert_eq!(module.disassemble(), loaded.disassemble());
+        }
+
+        struct Failing;
+        impl io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+        match mr::load_reader(Failing) {
+            Err(ParseState::ReadError(ref err)) if err.to_string() == "broken" => (),
+            _ => panic!(),
+        }
+    }
 }
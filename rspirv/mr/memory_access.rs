@@ -0,0 +1,205 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+
+use spirv::{MemoryAccess, Word};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Composes the MemoryAccess mask of OpLoad, OpStore, and OpCopyMemory
+/// together with the operands following it.
+///
+/// The operands required by the bits set in the mask are emitted in the
+/// order given by the grammar, i.e., in the order of the bits, no matter
+/// in which order they are set on this builder.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rspirv;
+/// extern crate spirv_headers as spirv;
+///
+/// use rspirv::mr::{Builder, MemoryAccessBuilder};
+///
+/// fn main() {
+///     let mut b = Builder::new();
+///     let void = b.type_void();
+///     let float = b.type_float(32);
+///     let pointer = b.type_pointer(None, spirv::StorageClass::StorageBuffer, float);
+///     let voidf = b.type_function(void, vec![]);
+///     b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+///     b.begin_basic_block(None).unwrap();
+///
+///     let (mask, operands) = MemoryAccessBuilder::new().aligned(4).volatile().build();
+///     assert_eq!(Some(spirv::MemoryAccess::VOLATILE | spirv::MemoryAccess::ALIGNED), mask);
+///     b.load(float, None, pointer, mask, operands).unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryAccessBuilder {
+    volatile: bool,
+    aligned: Option<u32>,
+    nontemporal: bool,
+    make_pointer_available: Option<Word>,
+    make_pointer_visible: Option<Word>,
+    non_private_pointer: bool,
+}
+
+impl MemoryAccessBuilder {
+    /// Creates a new builder with no memory operands.
+    pub fn new() -> MemoryAccessBuilder {
+        MemoryAccessBuilder::default()
+    }
+
+    /// Sets the Volatile memory operand.
+    pub fn volatile(mut self) -> MemoryAccessBuilder {
+        self.volatile = true;
+        self
+    }
+
+    /// Sets the Aligned memory operand to the given `alignment` in bytes.
+    pub fn aligned(mut self, alignment: u32) -> MemoryAccessBuilder {
+        self.aligned = Some(alignment);
+        self
+    }
+
+    /// Sets the Nontemporal memory operand.
+    pub fn nontemporal(mut self) -> MemoryAccessBuilder {
+        self.nontemporal = true;
+        self
+    }
+
+    /// Sets the MakePointerAvailableKHR memory operand to the given `scope`
+    /// id.
+    ///
+    /// This also sets NonPrivatePointerKHR, which the Vulkan memory model
+    /// requires together with it.
+    pub fn make_pointer_available(mut self, scope: Word) -> MemoryAccessBuilder {
+        self.make_pointer_available = Some(scope);
+        self.non_private_pointer = true;
+        self
+    }
+
+    /// Sets the MakePointerVisibleKHR memory operand to the given `scope`
+    /// id.
+    ///
+    /// This also sets NonPrivatePointerKHR, which the Vulkan memory model
+    /// requires together with it.
+    pub fn make_pointer_visible(mut self, scope: Word) -> MemoryAccessBuilder {
+        self.make_pointer_visible = Some(scope);
+        self.non_private_pointer = true;
+        self
+    }
+
+    /// Sets the NonPrivatePointerKHR memory operand.
+    pub fn non_private_pointer(mut self) -> MemoryAccessBuilder {
+        self.non_private_pointer = true;
+        self
+    }
+
+    /// Returns true if no memory operand is set.
+    pub fn is_empty(&self) -> bool {
+        self.mask().is_empty()
+    }
+
+    /// Returns the MemoryAccess mask of the memory operands set.
+    pub fn mask(&self) -> MemoryAccess {
+        let flags = [
+            (MemoryAccess::VOLATILE, self.volatile),
+            (MemoryAccess::ALIGNED, self.aligned.is_some()),
+            (MemoryAccess::NONTEMPORAL, self.nontemporal),
+            (MemoryAccess::MAKE_POINTER_AVAILABLE_KHR, self.make_pointer_available.is_some()),
+            (MemoryAccess::MAKE_POINTER_VISIBLE_KHR, self.make_pointer_visible.is_some()),
+            (MemoryAccess::NON_PRIVATE_POINTER_KHR, self.non_private_pointer),
+        ];
+        flags
+            .iter()
+            .filter(|&&(_, set)| set)
+            .fold(MemoryAccess::NONE, |mask, &(flag, _)| mask | flag)
+    }
+
+    /// Returns the operands following the MemoryAccess mask, in the order
+    /// given by the grammar.
+    pub fn operands(&self) -> Vec<mr::Operand> {
+        let alignment = self.aligned.iter().map(|&a| mr::Operand::LiteralInt32(a));
+        let scopes = self.make_pointer_available.iter().chain(&self.make_pointer_visible);
+        alignment.chain(scopes.map(|&id| mr::Operand::IdScope(id))).collect()
+    }
+
+    /// Returns the MemoryAccess mask, or `None` if no memory operand is
+    /// set, together with the operands following it.
+    ///
+    /// The results can be passed to
+    /// [`Builder::load`](struct.Builder.html#method.load),
+    /// [`Builder::store`](struct.Builder.html#method.store), and
+    /// [`Builder::copy_memory`](struct.Builder.html#method.copy_memory).
+    pub fn build(&self) -> (Option<MemoryAccess>, Vec<mr::Operand>) {
+        let mask = if self.is_empty() { None } else { Some(self.mask()) };
+        (mask, self.operands())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::MemoryAccessBuilder;
+
+    #[test]
+    fn test_memory_access_order() {
+        let (mask, operands) = MemoryAccessBuilder::new()
+            .make_pointer_visible(3)
+            .aligned(16)
+            .make_pointer_available(2)
+            .build();
+        assert_eq!(
+            Some(spirv::MemoryAccess::ALIGNED | spirv::MemoryAccess::MAKE_POINTER_AVAILABLE_KHR |
+                 spirv::MemoryAccess::MAKE_POINTER_VISIBLE_KHR |
+                 spirv::MemoryAccess::NON_PRIVATE_POINTER_KHR),
+            mask
+        );
+        assert_eq!(
+            vec![mr::Operand::LiteralInt32(16), mr::Operand::IdScope(2), mr::Operand::IdScope(3)],
+            operands
+        );
+        assert_eq!((None, vec![]), MemoryAccessBuilder::new().build());
+    }
+
+    #[test]
+    fn test_store_with_memory_access() {
+        let mut b = mr::Builder::new();
+        let (mask, operands) = MemoryAccessBuilder::new().nontemporal().aligned(8).build();
+        b.begin_function(1, None, spirv::FunctionControl::NONE, 2).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.store(10, 11, mask, operands).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = b.module();
+        let inst = &m.functions[0].basic_blocks[0].instructions[0];
+        assert_eq!(
+            vec![
+                mr::Operand::IdRef(10),
+                mr::Operand::IdRef(11),
+                mr::Operand::MemoryAccess(
+                    spirv::MemoryAccess::ALIGNED | spirv::MemoryAccess::NONTEMPORAL,
+                ),
+                mr::Operand::LiteralInt32(8),
+            ],
+            inst.operands
+        );
+    }
+}
@@ -34,11 +34,47 @@
 //! [builder](struct.Builder.html) for building a SPIR-V data representation
 //! interactively.
 
-pub use self::builder::Builder;
-pub use self::constructs::{BasicBlock, Function, Instruction};
+pub use self::arena::{FunctionArena, InstructionId};
+pub use self::builder::{Builder, BuilderSnapshot, LoopBlocks};
+pub use self::constructs::{BasicBlock, BasicBlockRef, Function, Instruction};
+pub use self::constructs::InstructionLocation;
 pub use self::constructs::{Module, ModuleHeader, Operand};
+pub use self::decorations::DecorationIndex;
+pub use self::float_controls::{DenormMode, FloatControls, FloatControlsError, RoundingMode};
+pub use self::fragment::{extract_function, insert_fragment};
+#[cfg(feature = "std")]
+pub(crate) use self::fragment::extract_function_with;
+pub use self::generator::Generator;
+pub use self::image_operands::ImageOperandsBuilder;
 pub use self::loader::{Error, load_bytes, load_words, Loader};
+#[cfg(feature = "std")]
+pub use self::loader::load_reader;
+pub use self::loader::{load_bytes_lenient, load_words_lenient};
+pub use self::memory_access::MemoryAccessBuilder;
+pub use self::operands::Operands;
+pub use self::size::{FunctionSize, SizeReport};
+pub use self::source::EmbeddedSource;
+pub use self::symbol::{Interner, Symbol};
+pub use self::target_env::{TargetEnv, TargetEnvIssue};
+pub use self::version::{VersionRequirement, VersionRequirements, VersionRules};
 
+mod arena;
 mod builder;
 mod constructs;
+mod decorations;
+mod ext_inst;
+mod float_controls;
+mod fragment;
+mod generator;
+mod image_operands;
+mod layout;
 mod loader;
+mod memory_access;
+mod names;
+mod operands;
+mod remap;
+mod size;
+mod source;
+mod symbol;
+mod target_env;
+mod version;
@@ -0,0 +1,115 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Module, Operand};
+use spirv::Word;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+impl Module {
+    /// Returns the names given by OpName instructions, keyed by their
+    /// targets.
+    ///
+    /// If an id is named more than once, the last name wins.
+    #[cfg(feature = "std")]
+    pub fn id_names(&self) -> HashMap<Word, &str> {
+        self.names().collect()
+    }
+
+    /// Returns the name given to `id` by OpName, if any.
+    pub fn name_of(&self, id: Word) -> Option<&str> {
+        self.names().filter(|&(target, _)| target == id).map(|(_, name)| name).last()
+    }
+
+    /// Returns the name given to the given member of the struct type
+    /// `struct_id` by OpMemberName, if any.
+    pub fn member_name_of(&self, struct_id: Word, member: u32) -> Option<&str> {
+        self.debugs
+            .iter()
+            .filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+                (spirv::Op::MemberName,
+                 &[Operand::IdRef(target),
+                   Operand::LiteralInt32(index),
+                   Operand::LiteralString(ref name)])
+                    if target == struct_id && index == member => Some(name.as_str()),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// Returns an iterator over all ids named `name` by OpName, in the
+    /// order of the OpName instructions.
+    ///
+    /// Names are not unique in SPIR-V; e.g., compilers may give the same
+    /// name to a variable and to its type.
+    pub fn ids_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = Word> + 'a {
+        self.names().filter(move |&(_, n)| n == name).map(|(id, _)| id)
+    }
+
+    /// Returns the first id named `name` by OpName, if any.
+    pub fn id_by_name(&self, name: &str) -> Option<Word> {
+        self.ids_named(name).next()
+    }
+
+    /// Returns an iterator over the (target, name) pairs of all OpName
+    /// instructions.
+    fn names(&self) -> impl Iterator<Item = (Word, &str)> {
+        self.debugs.iter().filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Name, &[Operand::IdRef(target), Operand::LiteralString(ref name)]) => {
+                Some((target, name.as_str()))
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_name_lookup() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let light = b.type_struct(vec![float, float]);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Private, light);
+        let var = b.variable(ptr, None, spirv::StorageClass::Private, None);
+        b.name(light, "Light");
+        b.member_name(light, 0, "intensity");
+        b.member_name(light, 1, "range");
+        b.name(var, "light");
+        b.name(ptr, "light");
+        let m = b.module();
+
+        assert_eq!(Some("Light"), m.name_of(light));
+        assert_eq!(None, m.name_of(float));
+        assert_eq!(Some("range"), m.member_name_of(light, 1));
+        assert_eq!(None, m.member_name_of(light, 2));
+        assert_eq!(None, m.member_name_of(var, 0));
+        assert_eq!(vec![var, ptr], m.ids_named("light").collect::<Vec<_>>());
+        assert_eq!(Some(light), m.id_by_name("Light"));
+        assert_eq!(None, m.id_by_name("Sun"));
+        #[cfg(feature = "std")]
+        {
+            let names = m.id_names();
+            assert_eq!(3, names.len());
+            assert_eq!(Some(&"light"), names.get(&var));
+        }
+    }
+}
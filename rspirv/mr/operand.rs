@@ -27,6 +27,8 @@ pub enum Operand {
     MemorySemantics(spirv::MemorySemantics),
     MemoryAccess(spirv::MemoryAccess),
     KernelProfilingInfo(spirv::KernelProfilingInfo),
+    RayFlags(spirv::RayFlags),
+    CooperativeMatrixOperands(spirv::CooperativeMatrixOperands),
     SourceLanguage(spirv::SourceLanguage),
     ExecutionModel(spirv::ExecutionModel),
     AddressingModel(spirv::AddressingModel),
@@ -49,6 +51,11 @@ pub enum Operand {
     GroupOperation(spirv::GroupOperation),
     KernelEnqueueFlags(spirv::KernelEnqueueFlags),
     Capability(spirv::Capability),
+    RayQueryIntersection(spirv::RayQueryIntersection),
+    RayQueryCommittedIntersectionType(spirv::RayQueryCommittedIntersectionType),
+    RayQueryCandidateIntersectionType(spirv::RayQueryCandidateIntersectionType),
+    CooperativeMatrixLayout(spirv::CooperativeMatrixLayout),
+    CooperativeMatrixUse(spirv::CooperativeMatrixUse),
     IdMemorySemantics(spirv::Word),
     IdScope(spirv::Word),
     IdRef(spirv::Word),
@@ -57,8 +64,9 @@ pub enum Operand {
     LiteralFloat32(f32),
     LiteralFloat64(f64),
     LiteralExtInstInteger(u32),
+    ExtInstOpcode(&'static grammar::ExtendedInstruction<'static>),
     LiteralSpecConstantOpInteger(spirv::Op),
-    LiteralString(String),
+    LiteralString(Symbol),
 }
 
 impl fmt::Display for Operand {
@@ -72,6 +80,8 @@ impl fmt::Display for Operand {
             Operand::MemorySemantics(ref v) => write!(f, "{:?}", v),
             Operand::MemoryAccess(ref v) => write!(f, "{:?}", v),
             Operand::KernelProfilingInfo(ref v) => write!(f, "{:?}", v),
+            Operand::RayFlags(ref v) => write!(f, "{:?}", v),
+            Operand::CooperativeMatrixOperands(ref v) => write!(f, "{:?}", v),
             Operand::SourceLanguage(ref v) => write!(f, "{:?}", v),
             Operand::ExecutionModel(ref v) => write!(f, "{:?}", v),
             Operand::AddressingModel(ref v) => write!(f, "{:?}", v),
@@ -94,11 +104,17 @@ impl fmt::Display for Operand {
             Operand::GroupOperation(ref v) => write!(f, "{:?}", v),
             Operand::KernelEnqueueFlags(ref v) => write!(f, "{:?}", v),
             Operand::Capability(ref v) => write!(f, "{:?}", v),
+            Operand::RayQueryIntersection(ref v) => write!(f, "{:?}", v),
+            Operand::RayQueryCommittedIntersectionType(ref v) => write!(f, "{:?}", v),
+            Operand::RayQueryCandidateIntersectionType(ref v) => write!(f, "{:?}", v),
+            Operand::CooperativeMatrixLayout(ref v) => write!(f, "{:?}", v),
+            Operand::CooperativeMatrixUse(ref v) => write!(f, "{:?}", v),
             Operand::IdMemorySemantics(ref v) => write!(f, "{:?}", v),
             Operand::IdScope(ref v) => write!(f, "{:?}", v),
             Operand::IdRef(ref v) => write!(f, "{:?}", v),
             Operand::LiteralString(ref v) => write!(f, "{:?}", v),
             Operand::LiteralExtInstInteger(ref v) => write!(f, "{:?}", v),
+            Operand::ExtInstOpcode(v) => write!(f, "{}", v.opname),
             Operand::LiteralSpecConstantOpInteger(ref v) => write!(f, "{:?}", v),
             Operand::LiteralInt32(ref v) => write!(f, "{:?}", v),
             Operand::LiteralInt64(ref v) => write!(f, "{:?}", v),
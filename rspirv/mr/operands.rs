@@ -0,0 +1,346 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Operand;
+use std::{fmt, iter, mem, ops, slice};
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// The number of operands stored without allocating.
+const INLINE_CAPACITY: usize = 4;
+
+/// Fills the unused inline slots; never observable from outside.
+const FILLER: Operand = Operand::LiteralInt32(0);
+
+/// The operands of an instruction.
+///
+/// Up to four operands, which covers most instructions, are stored inline
+/// without allocating; more spill into a `Vec`. It dereferences to a slice
+/// of operands and supports the usual `Vec` mutators.
+#[derive(Clone)]
+pub struct Operands(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline(usize, [Operand; INLINE_CAPACITY]),
+    Heap(Vec<Operand>),
+}
+
+impl Operands {
+    /// Creates a new empty operand list.
+    pub fn new() -> Self {
+        Operands(Repr::Inline(0, [FILLER; INLINE_CAPACITY]))
+    }
+
+    /// Returns true if the operands spilled into a heap allocation.
+    pub fn spilled(&self) -> bool {
+        match self.0 {
+            Repr::Inline(..) => false,
+            Repr::Heap(_) => true,
+        }
+    }
+
+    /// Returns the operands as a slice.
+    pub fn as_slice(&self) -> &[Operand] {
+        match self.0 {
+            Repr::Inline(len, ref items) => &items[..len],
+            Repr::Heap(ref v) => v,
+        }
+    }
+
+    /// Returns the operands as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [Operand] {
+        match self.0 {
+            Repr::Inline(len, ref mut items) => &mut items[..len],
+            Repr::Heap(ref mut v) => v,
+        }
+    }
+
+    /// Appends an operand.
+    pub fn push(&mut self, operand: Operand) {
+        match self.0 {
+            Repr::Inline(ref mut len, ref mut items) if *len < INLINE_CAPACITY => {
+                items[*len] = operand;
+                *len += 1;
+                return;
+            }
+            Repr::Heap(ref mut v) => {
+                v.push(operand);
+                return;
+            }
+            Repr::Inline(..) => {}
+        }
+        let mut v = self.take_vec();
+        v.push(operand);
+        self.0 = Repr::Heap(v);
+    }
+
+    /// Removes the last operand and returns it, if any.
+    pub fn pop(&mut self) -> Option<Operand> {
+        match self.0 {
+            Repr::Inline(0, _) => None,
+            Repr::Inline(ref mut len, ref mut items) => {
+                *len -= 1;
+                Some(mem::replace(&mut items[*len], FILLER))
+            }
+            Repr::Heap(ref mut v) => v.pop(),
+        }
+    }
+
+    /// Inserts an operand at position `index`, shifting all operands after
+    /// it to the right.
+    ///
+    /// Panics if `index` is greater than the number of operands.
+    pub fn insert(&mut self, index: usize, operand: Operand) {
+        assert!(index <= self.len(), "insertion index out of bounds");
+        self.push(operand);
+        self.as_mut_slice()[index..].rotate_right(1);
+    }
+
+    /// Removes and returns the operand at position `index`, shifting all
+    /// operands after it to the left.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Operand {
+        assert!(index < self.len(), "removal index out of bounds");
+        self.as_mut_slice()[index..].rotate_left(1);
+        self.pop().unwrap()
+    }
+
+    /// Shortens the operand list to `len` operands.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.pop();
+        }
+    }
+
+    /// Removes all operands.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Retains only the operands for which `keep` returns true, preserving
+    /// their order.
+    pub fn retain<F: FnMut(&Operand) -> bool>(&mut self, mut keep: F) {
+        let mut kept = 0;
+        {
+            let operands = self.as_mut_slice();
+            for i in 0..operands.len() {
+                if keep(&operands[i]) {
+                    operands.swap(kept, i);
+                    kept += 1;
+                }
+            }
+        }
+        self.truncate(kept)
+    }
+
+    /// Appends clones of all operands in `operands`.
+    pub fn extend_from_slice(&mut self, operands: &[Operand]) {
+        self.extend(operands.iter().cloned())
+    }
+
+    /// Converts into a `Vec` of operands.
+    pub fn into_vec(mut self) -> Vec<Operand> {
+        self.take_vec()
+    }
+
+    fn take_vec(&mut self) -> Vec<Operand> {
+        match mem::replace(&mut self.0, Repr::Inline(0, [FILLER; INLINE_CAPACITY])) {
+            Repr::Inline(len, items) => {
+                let mut v = Vec::with_capacity(INLINE_CAPACITY * 2);
+                v.extend(IntoIterator::into_iter(items).take(len));
+                v
+            }
+            Repr::Heap(v) => v,
+        }
+    }
+}
+
+impl Default for Operands {
+    fn default() -> Self {
+        Operands::new()
+    }
+}
+
+impl ops::Deref for Operands {
+    type Target = [Operand];
+
+    fn deref(&self) -> &[Operand] {
+        self.as_slice()
+    }
+}
+
+impl ops::DerefMut for Operands {
+    fn deref_mut(&mut self) -> &mut [Operand] {
+        self.as_mut_slice()
+    }
+}
+
+impl fmt::Debug for Operands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Operands {
+    fn eq(&self, other: &Operands) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialEq<Vec<Operand>> for Operands {
+    fn eq(&self, other: &Vec<Operand>) -> bool {
+        self.as_slice() == &other[..]
+    }
+}
+
+impl PartialEq<[Operand]> for Operands {
+    fn eq(&self, other: &[Operand]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<'a> PartialEq<&'a [Operand]> for Operands {
+    fn eq(&self, other: &&'a [Operand]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<[Operand; N]> for Operands {
+    fn eq(&self, other: &[Operand; N]) -> bool {
+        self.as_slice() == &other[..]
+    }
+}
+
+impl PartialEq<Operands> for Vec<Operand> {
+    fn eq(&self, other: &Operands) -> bool {
+        &self[..] == other.as_slice()
+    }
+}
+
+impl From<Vec<Operand>> for Operands {
+    fn from(v: Vec<Operand>) -> Self {
+        if v.len() <= INLINE_CAPACITY {
+            v.into_iter().collect()
+        } else {
+            Operands(Repr::Heap(v))
+        }
+    }
+}
+
+impl<const N: usize> From<[Operand; N]> for Operands {
+    fn from(operands: [Operand; N]) -> Self {
+        IntoIterator::into_iter(operands).collect()
+    }
+}
+
+impl From<Operands> for Vec<Operand> {
+    fn from(operands: Operands) -> Self {
+        operands.into_vec()
+    }
+}
+
+impl iter::Extend<Operand> for Operands {
+    fn extend<I: IntoIterator<Item = Operand>>(&mut self, operands: I) {
+        for operand in operands {
+            self.push(operand);
+        }
+    }
+}
+
+impl iter::FromIterator<Operand> for Operands {
+    fn from_iter<I: IntoIterator<Item = Operand>>(operands: I) -> Self {
+        let mut list = Operands::new();
+        list.extend(operands);
+        list
+    }
+}
+
+/// An iterator moving the operands out of an `Operands` list.
+pub struct IntoIter {
+    operands: Operands,
+    next: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = Operand;
+
+    fn next(&mut self) -> Option<Operand> {
+        let operand = self.operands.get_mut(self.next)?;
+        self.next += 1;
+        Some(mem::replace(operand, FILLER))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.operands.len() - self.next;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl IntoIterator for Operands {
+    type Item = Operand;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter { operands: self, next: 0 }
+    }
+}
+
+impl<'a> IntoIterator for &'a Operands {
+    type Item = &'a Operand;
+    type IntoIter = slice::Iter<'a, Operand>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Operands {
+    type Item = &'a mut Operand;
+    type IntoIter = slice::IterMut<'a, Operand>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr::Operand;
+    use super::Operands;
+
+    #[test]
+    fn test_inline_and_spilled() {
+        let mut operands: Operands = (0..4).map(Operand::IdRef).collect();
+        assert!(!operands.spilled());
+        operands.insert(1, Operand::LiteralInt32(7));
+        assert!(operands.spilled());
+        assert_eq!(operands,
+                   vec![Operand::IdRef(0), Operand::LiteralInt32(7), Operand::IdRef(1),
+                        Operand::IdRef(2), Operand::IdRef(3)]);
+
+        let mut operands = Operands::from([Operand::IdRef(1), Operand::IdRef(2)]);
+        operands.push(Operand::IdRef(3));
+        assert_eq!(Operand::IdRef(1), operands.remove(0));
+        operands.retain(|operand| *operand != Operand::IdRef(2));
+        assert_eq!(&[Operand::IdRef(3)], &operands[..]);
+        assert_eq!(Some(Operand::IdRef(3)), operands.pop());
+        assert_eq!(None, operands.pop());
+        assert!(operands.is_empty());
+        assert!(!operands.spilled());
+    }
+}
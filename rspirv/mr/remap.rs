@@ -0,0 +1,170 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv::Word;
+use std::collections::BTreeMap;
+use super::{Function, Instruction, Module, Operand};
+
+impl Instruction {
+    /// Replaces all ids in this instruction, i.e., its result type, its
+    /// result id, and all id operands, with the results of `f`.
+    fn map_ids<F: FnMut(Word) -> Word>(&mut self, mut f: F) {
+        self.result_type = self.result_type.map(&mut f);
+        self.result_id = self.result_id.map(&mut f);
+        for operand in &mut self.operands {
+            match *operand {
+                Operand::IdMemorySemantics(ref mut id) |
+                Operand::IdScope(ref mut id) |
+                Operand::IdRef(ref mut id) => *id = f(*id),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Module {
+    /// Returns a copy of this module with all ids increased by `offset`.
+    ///
+    /// The bound in the module header is increased accordingly, so that
+    /// the ids of the copy do not collide with ids below `offset`. This
+    /// allows instantiating a template module several times into a single
+    /// target module. Operands of instructions unknown to this library are
+    /// kept as they are.
+    pub fn clone_with_id_offset(&self, offset: Word) -> Module {
+        let mut module = self.clone();
+        let insts = module
+            .capabilities
+            .iter_mut()
+            .chain(&mut module.extensions)
+            .chain(&mut module.ext_inst_imports)
+            .chain(&mut module.memory_model)
+            .chain(&mut module.entry_points)
+            .chain(&mut module.execution_modes)
+            .chain(&mut module.debugs)
+            .chain(&mut module.annotations)
+            .chain(&mut module.types_global_values)
+            .chain(module.functions.iter_mut().flat_map(|f| {
+                f.def
+                    .iter_mut()
+                    .chain(&mut f.parameters)
+                    .chain(f.basic_blocks.iter_mut().flat_map(|bb| {
+                        bb.label.iter_mut().chain(&mut bb.instructions)
+                    }))
+                    .chain(&mut f.end)
+            }));
+        for inst in insts {
+            inst.map_ids(|id| id + offset);
+        }
+        if let Some(ref mut header) = module.header {
+            header.bound += offset;
+        }
+        if let Some(ref mut locations) = module.locations {
+            for location in locations {
+                location.result_id = location.result_id.map(|id| id + offset);
+            }
+        }
+        module
+    }
+}
+
+impl Function {
+    /// Returns a copy of this function with all ids defined in it replaced
+    /// by fresh ids from `id_allocator`.
+    ///
+    /// The result ids of the function itself, its parameters, its basic
+    /// blocks, and all instructions in them are replaced, together with
+    /// all references to them. References to ids defined outside of this
+    /// function, e.g., types, constants, global variables, and other
+    /// functions, are kept. This allows instantiating a template function
+    /// several times into a single module, e.g., with
+    /// [`Module::allocate_id`](struct.Module.html#method.allocate_id) or
+    /// [`Builder::id`](struct.Builder.html#method.id) as allocator.
+    pub fn clone_remapped<F: FnMut() -> Word>(&self, id_allocator: &mut F) -> Function {
+        let mut ids = BTreeMap::new();
+        for inst in self.all_inst_iter() {
+            if let Some(id) = inst.result_id {
+                ids.insert(id, id_allocator());
+            }
+        }
+        let mut function = self.clone();
+        let insts = function
+            .def
+            .iter_mut()
+            .chain(&mut function.parameters)
+            .chain(function.basic_blocks.iter_mut().flat_map(|bb| {
+                bb.label.iter_mut().chain(&mut bb.instructions)
+            }))
+            .chain(&mut function.end);
+        for inst in insts {
+            inst.map_ids(|id| ids.get(&id).cloned().unwrap_or(id));
+        }
+        function
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+
+    fn build() -> mr::Module {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let ff = b.type_function(float, vec![float]);
+        b.begin_function(float, None, spirv::FunctionControl::NONE, ff).unwrap();
+        let x = b.function_parameter(float).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let y = b.fmul(float, None, x, x).unwrap();
+        b.ret_value(y).unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_clone_with_id_offset() {
+        let module = build();
+        let clone = module.clone_with_id_offset(10);
+        assert_eq!(module.header.as_ref().unwrap().bound + 10,
+                   clone.header.as_ref().unwrap().bound);
+        assert_eq!("%12 = OpTypeFunction %11 %11", clone.types_global_values[1].disassemble());
+        assert_eq!(
+            clone.functions[0].disassemble(),
+            "%13 = OpFunction  %11  None %12\n\
+             %14 = OpFunctionParameter  %11 \n\
+             %15 = OpLabel\n\
+             %16 = OpFMul  %11  %14 %14\n\
+             OpReturnValue %16\n\
+             OpFunctionEnd"
+        );
+    }
+
+    #[test]
+    fn test_function_clone_remapped() {
+        let mut module = build();
+        let function = module.functions[0].clone();
+        let clone = function.clone_remapped(&mut || module.allocate_id());
+        assert_eq!(11, module.header.as_ref().unwrap().bound);
+        assert_eq!(
+            clone.disassemble(),
+            "%7 = OpFunction  %1  None %2\n\
+             %8 = OpFunctionParameter  %1 \n\
+             %9 = OpLabel\n\
+             %10 = OpFMul  %1  %8 %8\n\
+             OpReturnValue %10\n\
+             OpFunctionEnd"
+        );
+    }
+}
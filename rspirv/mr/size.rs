@@ -0,0 +1,192 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binary::Assemble;
+
+use super::{Instruction, Module};
+use spirv::Word;
+use std::cmp::Reverse;
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// The size of a function, as part of a [`SizeReport`](struct.SizeReport.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionSize {
+    /// The result id of the function.
+    pub function: Word,
+    /// The name given to the function by OpName, if any.
+    pub name: Option<String>,
+    /// The number of words of the function, from OpFunction to
+    /// OpFunctionEnd.
+    pub words: usize,
+}
+
+impl fmt::Display for FunctionSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{}", self.function)?;
+        if let Some(ref name) = self.name {
+            write!(f, " ({})", name)?;
+        }
+        write!(f, ": {} words", self.words)
+    }
+}
+
+/// The number of words of each logical section of a module, as returned
+/// by [`Module::size_report`](struct.Module.html#method.size_report).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SizeReport {
+    /// The module header.
+    pub header: usize,
+    /// Capabilities, extensions, extended instruction set imports, the
+    /// memory model, entry points, and execution modes.
+    pub mode_setting: usize,
+    /// Debug instructions outside functions.
+    pub debug: usize,
+    /// Annotations.
+    pub annotations: usize,
+    /// Types, constants, and global variables.
+    pub types_global_values: usize,
+    /// Each function, in the order of the functions.
+    pub functions: Vec<FunctionSize>,
+}
+
+impl SizeReport {
+    /// Returns the number of words of all functions.
+    pub fn function_words(&self) -> usize {
+        self.functions.iter().map(|f| f.words).sum()
+    }
+
+    /// Returns the number of words of the whole module.
+    pub fn total(&self) -> usize {
+        self.header +
+            self.mode_setting +
+            self.debug +
+            self.annotations +
+            self.types_global_values +
+            self.function_words()
+    }
+
+    /// Returns the `n` largest functions, largest first. Functions of the
+    /// same size keep their order.
+    pub fn largest_functions(&self, n: usize) -> Vec<&FunctionSize> {
+        let mut functions: Vec<&FunctionSize> = self.functions.iter().collect();
+        functions.sort_by_key(|f| Reverse(f.words));
+        functions.truncate(n);
+        functions
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sections = [
+            ("header", self.header),
+            ("mode setting", self.mode_setting),
+            ("debug", self.debug),
+            ("annotations", self.annotations),
+            ("types and global values", self.types_global_values),
+            ("functions", self.function_words()),
+        ];
+        for &(section, words) in &sections {
+            writeln!(f, "{}: {} words ({} bytes)", section, words, words * 4)?;
+        }
+        write!(f, "total: {} words ({} bytes)", self.total(), self.total() * 4)
+    }
+}
+
+/// Returns the number of words of the given instructions.
+fn words<'a, I: IntoIterator<Item = &'a Instruction>>(insts: I) -> usize {
+    insts.into_iter().map(|inst| inst.assemble().len()).sum()
+}
+
+impl Module {
+    /// Returns the number of words each logical section of this module
+    /// takes once assembled, to find out what makes a binary large.
+    pub fn size_report(&self) -> SizeReport {
+        SizeReport {
+            header: if self.header.is_some() { 5 } else { 0 },
+            mode_setting: words(
+                self.capabilities
+                    .iter()
+                    .chain(&self.extensions)
+                    .chain(&self.ext_inst_imports)
+                    .chain(&self.memory_model)
+                    .chain(&self.entry_points)
+                    .chain(&self.execution_modes),
+            ),
+            debug: words(&self.debugs),
+            annotations: words(&self.annotations),
+            types_global_values: words(&self.types_global_values),
+            functions: self
+                .functions
+                .iter()
+                .map(|function| {
+                    let id = function.def.as_ref().and_then(|d| d.result_id).unwrap_or(0);
+                    FunctionSize {
+                        function: id,
+                        name: self.name_of(id).map(|name| name.to_owned()),
+                        words: words(function.all_inst_iter()),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Assemble;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_size_report() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        let small = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let large = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.function_call(void, None, small, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.name(large, "main");
+        b.decorate(large, spirv::Decoration::NoContraction, vec![]);
+        let module = b.module();
+
+        let report = module.size_report();
+        assert_eq!(5, report.header);
+        // OpCapability, OpMemoryModel.
+        assert_eq!(2 + 3, report.mode_setting);
+        // OpName with "main" and its null terminator.
+        assert_eq!(2 + 2, report.debug);
+        assert_eq!(3, report.annotations);
+        assert_eq!(2 + 3, report.types_global_values);
+        assert_eq!(module.assemble().len(), report.total());
+
+        let largest = report.largest_functions(1);
+        assert_eq!(1, largest.len());
+        assert_eq!("%5 (main): 13 words", largest[0].to_string());
+        assert_eq!(13 + 9, report.function_words());
+        assert!(report.to_string().ends_with("total: 44 words (176 bytes)"));
+    }
+}
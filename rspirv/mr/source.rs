@@ -0,0 +1,320 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Instruction, Module, Operand};
+use spirv::Word;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// The largest number of bytes of source text put into one OpSource or
+/// OpSourceContinued instruction, leaving room for the other operands and
+/// the terminating nul within the 65535-word limit of instructions.
+const MAX_PIECE_BYTES: usize = (0xFFFF - 5) * 4;
+
+/// A source file declared by OpSource, with its text reconstructed from
+/// the OpSource and OpSourceContinued instructions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedSource {
+    /// The source language.
+    pub language: spirv::SourceLanguage,
+    /// The version of the source language.
+    pub version: u32,
+    /// The name of the file, given by the OpString referenced by OpSource,
+    /// if any.
+    pub file: Option<String>,
+    /// The text of the file, if embedded.
+    pub text: Option<String>,
+}
+
+/// Splits the given text into pieces of at most `max` bytes, on character
+/// boundaries. Empty texts give one empty piece.
+fn split_text(mut text: &str, max: usize) -> Vec<&str> {
+    let mut pieces = vec![];
+    while text.len() > max {
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (piece, rest) = text.split_at(end);
+        pieces.push(piece);
+        text = rest;
+    }
+    pieces.push(text);
+    pieces
+}
+
+/// Returns whether the given debug instruction belongs to the first group
+/// of debug instructions, i.e., OpString and OpSource* instructions.
+fn is_source_debug(inst: &Instruction) -> bool {
+    matches!(
+        inst.class.opcode,
+        spirv::Op::String |
+            spirv::Op::SourceExtension |
+            spirv::Op::Source |
+            spirv::Op::SourceContinued
+    )
+}
+
+impl Module {
+    /// Returns the source files declared by OpSource instructions, in
+    /// order, with the text continued by the OpSourceContinued
+    /// instructions directly following each OpSource.
+    pub fn embedded_sources(&self) -> Vec<EmbeddedSource> {
+        let mut sources: Vec<EmbeddedSource> = vec![];
+        let mut continuing = false;
+        for inst in &self.debugs {
+            match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::Source, _) => {
+                    sources.extend(self.embedded_source(inst));
+                    continuing = true;
+                }
+                (spirv::Op::SourceContinued, Some(Operand::LiteralString(piece)))
+                    if continuing => {
+                    if let Some(source) = sources.last_mut() {
+                        source.text.get_or_insert_with(String::new).push_str(piece);
+                    }
+                }
+                _ => continuing = false,
+            }
+        }
+        sources
+    }
+
+    /// Returns the text of the given source file, if embedded.
+    ///
+    /// The file is looked up by the name given to it by OpString, or, if
+    /// `file` is `None`, among the sources without a name.
+    pub fn embedded_source_text(&self, file: Option<&str>) -> Option<String> {
+        self.embedded_sources()
+            .into_iter()
+            .find(|source| source.file.as_deref() == file)
+            .and_then(|source| source.text)
+    }
+
+    /// Returns an iterator over the processes recorded by
+    /// OpModuleProcessed instructions, in order.
+    pub fn module_processes(&self) -> impl Iterator<Item = &str> {
+        self.debugs.iter().filter_map(|inst| match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::ModuleProcessed, Some(Operand::LiteralString(process))) => {
+                Some(process.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    /// Removes the text embedded by OpSource and OpSourceContinued
+    /// instructions, and returns the sources as they were before.
+    ///
+    /// The OpSource instructions themselves are kept, so that the language
+    /// and file names remain available to debuggers; the text can be put
+    /// back with [`embed_source`](struct.Module.html#method.embed_source).
+    pub fn strip_sources(&mut self) -> Vec<EmbeddedSource> {
+        let sources = self.embedded_sources();
+        self.debugs.retain(|inst| inst.class.opcode != spirv::Op::SourceContinued);
+        for inst in &mut self.debugs {
+            if inst.class.opcode == spirv::Op::Source {
+                if let Some(&Operand::LiteralString(_)) = inst.operands.last() {
+                    inst.operands.pop();
+                }
+            }
+        }
+        sources
+    }
+
+    /// Embeds the given source into this module, replacing the OpSource
+    /// declaring the same file and its OpSourceContinued instructions, if
+    /// any, and returns the id of the OpString naming the file.
+    ///
+    /// The file name reuses an existing OpString with the same contents,
+    /// or is added as a new one. Text too long for a single instruction is
+    /// split across OpSourceContinued instructions.
+    pub fn embed_source(&mut self, source: &EmbeddedSource) -> Option<Word> {
+        let file = source.file.as_ref().map(|name| self.find_or_add_string(name));
+
+        let name = source.file.as_deref();
+        let existing = self.debugs.iter().position(|inst| {
+            inst.class.opcode == spirv::Op::Source && self.source_file(inst) == name
+        });
+        let position = match existing {
+            Some(index) => {
+                let continued = self.debugs[index + 1..]
+                    .iter()
+                    .take_while(|inst| inst.class.opcode == spirv::Op::SourceContinued)
+                    .count();
+                self.debugs.drain(index..index + 1 + continued);
+                index
+            }
+            None => self.source_debugs_end(),
+        };
+
+        let mut operands = vec![
+            Operand::SourceLanguage(source.language),
+            Operand::LiteralInt32(source.version),
+        ];
+        operands.extend(file.map(Operand::IdRef));
+        let pieces = source.text.as_ref().map(|text| split_text(text, MAX_PIECE_BYTES));
+        let mut pieces = pieces.unwrap_or_default().into_iter();
+        operands.extend(pieces.next().map(|piece| Operand::LiteralString(piece.into())));
+        let insts = Some(Instruction::new(spirv::Op::Source, None, None, operands))
+            .into_iter()
+            .chain(pieces.map(|piece| {
+                Instruction::new(
+                    spirv::Op::SourceContinued,
+                    None,
+                    None,
+                    vec![Operand::LiteralString(piece.into())],
+                )
+            }));
+        self.debugs.splice(position..position, insts);
+        file
+    }
+
+    /// Returns the source declared by the given OpSource instruction,
+    /// without its continuations.
+    fn embedded_source(&self, inst: &Instruction) -> Option<EmbeddedSource> {
+        let (language, version) = match inst.operands[..] {
+            [Operand::SourceLanguage(language), Operand::LiteralInt32(version), ..] => {
+                (language, version)
+            }
+            _ => return None,
+        };
+        let text = inst.operands.iter().find_map(|operand| match *operand {
+            Operand::LiteralString(ref text) => Some(text.to_string()),
+            _ => None,
+        });
+        Some(EmbeddedSource {
+            language,
+            version,
+            file: self.source_file(inst).map(|name| name.to_string()),
+            text,
+        })
+    }
+
+    /// Returns the name of the file declared by the given OpSource
+    /// instruction, if any.
+    fn source_file(&self, inst: &Instruction) -> Option<&str> {
+        let file = inst.operands.iter().find_map(|operand| match *operand {
+            Operand::IdRef(id) => Some(id),
+            _ => None,
+        })?;
+        self.debugs.iter().find_map(|string| match (string.class.opcode, string.operands.first()) {
+            (spirv::Op::String, Some(Operand::LiteralString(s)))
+                if string.result_id == Some(file) => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns the id of an OpString with the given contents, adding one
+    /// after the other OpString and OpSource* instructions if there is none.
+    fn find_or_add_string(&mut self, s: &str) -> Word {
+        let existing = self.debugs.iter().find(|inst| {
+            match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::String, Some(Operand::LiteralString(t))) => *t == s,
+                _ => false,
+            }
+        });
+        if let Some(id) = existing.and_then(|inst| inst.result_id) {
+            return id;
+        }
+        let id = self.allocate_id();
+        let position = self.source_debugs_end();
+        let string = vec![Operand::LiteralString(s.into())];
+        self.debugs.insert(position, Instruction::new(spirv::Op::String, None, Some(id), string));
+        id
+    }
+
+    /// Returns the index right after the last OpString or OpSource*
+    /// instruction in the debug section, or 0 if there is none.
+    fn source_debugs_end(&self) -> usize {
+        self.debugs.iter().rposition(is_source_debug).map_or(0, |index| index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::*;
+
+    fn count(module: &mr::Module, opcode: spirv::Op) -> usize {
+        module.debugs.iter().filter(|inst| inst.class.opcode == opcode).count()
+    }
+
+    #[test]
+    fn test_strip_and_embed_source() {
+        let mut b = mr::Builder::new();
+        let file = b.string("shader.frag");
+        b.source(spirv::SourceLanguage::GLSL, 450, Some(file), Some("void main() {"));
+        b.source_continued("\n}\n");
+        b.source::<&str>(spirv::SourceLanguage::HLSL, 600, None, None);
+        b.module_processed("client vulkan100");
+        b.module_processed("entry-point main");
+        let mut module = b.module();
+
+        let frag = EmbeddedSource {
+            language: spirv::SourceLanguage::GLSL,
+            version: 450,
+            file: Some("shader.frag".to_string()),
+            text: Some("void main() {\n}\n".to_string()),
+        };
+        let hlsl = EmbeddedSource {
+            language: spirv::SourceLanguage::HLSL,
+            version: 600,
+            file: None,
+            text: None,
+        };
+        assert_eq!(vec![frag.clone(), hlsl.clone()], module.embedded_sources());
+        assert_eq!(
+            Some("void main() {\n}\n".to_string()),
+            module.embedded_source_text(Some("shader.frag"))
+        );
+        assert_eq!(
+            vec!["client vulkan100", "entry-point main"],
+            module.module_processes().collect::<Vec<_>>()
+        );
+
+        assert_eq!(vec![frag.clone(), hlsl.clone()], module.strip_sources());
+        assert_eq!(0, count(&module, spirv::Op::SourceContinued));
+        assert_eq!(None, module.embedded_source_text(Some("shader.frag")));
+
+        assert_eq!(Some(file), module.embed_source(&frag));
+        assert_eq!(vec![frag.clone(), hlsl.clone()], module.embedded_sources());
+        assert_eq!(1, count(&module, spirv::Op::String));
+        assert_eq!(2, count(&module, spirv::Op::Source));
+        assert_eq!(spirv::Op::Source, module.debugs[1].class.opcode);
+
+        let named = EmbeddedSource {
+            file: Some("shader.hlsl".to_string()),
+            text: Some("float4 main() : SV_Target { return 0; }".to_string()),
+            ..hlsl.clone()
+        };
+        let id = module.embed_source(&named).unwrap();
+        assert_eq!(vec![frag, hlsl, named], module.embedded_sources());
+        assert_eq!(3, count(&module, spirv::Op::Source));
+        // The new OpString and OpSource go before the OpModuleProcessed.
+        assert_eq!(Some(id), module.debugs[3].result_id);
+        assert_eq!(spirv::Op::Source, module.debugs[4].class.opcode);
+        assert_eq!(spirv::Op::ModuleProcessed, module.debugs[5].class.opcode);
+    }
+
+    #[test]
+    fn test_split_text() {
+        assert_eq!(vec![""], split_text("", 4));
+        assert_eq!(vec!["abcd", "ef"], split_text("abcdef", 4));
+        assert_eq!(vec!["a\u{e9}", "\u{e9}"], split_text("a\u{e9}\u{e9}", 4));
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::{cmp, fmt, hash, ops};
+use std::rc::Rc;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// An immutable, cheaply clonable string, used for literal string operands.
+///
+/// Clones share the same allocation. Symbols created by the same
+/// `Interner`, like those in modules loaded by `Loader`, share the same
+/// allocation whenever they are equal, in which case comparing them does
+/// not need to look at their contents.
+#[derive(Clone)]
+pub struct Symbol(Rc<str>);
+
+impl Symbol {
+    /// Creates a new symbol holding a copy of `s`.
+    pub fn new(s: &str) -> Self {
+        Symbol(Rc::from(s))
+    }
+
+    /// Returns the string held by this symbol.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if both symbols share the same allocation.
+    pub fn ptr_eq(&self, other: &Symbol) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl ops::Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        self.ptr_eq(other) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Symbol {
+    fn eq(&self, other: &&'a str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Symbol) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Symbol) -> cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl hash::Hash for Symbol {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl<'a> From<&'a str> for Symbol {
+    fn from(s: &'a str) -> Self {
+        Symbol::new(s)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(s: String) -> Self {
+        Symbol(Rc::from(s))
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(s: Symbol) -> Self {
+        s.as_str().to_owned()
+    }
+}
+
+/// A table of symbols, handing out the same allocation for equal strings.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    symbols: BTreeSet<Symbol>,
+}
+
+impl Interner {
+    /// Creates a new empty interner.
+    pub fn new() -> Self {
+        Interner { symbols: BTreeSet::new() }
+    }
+
+    /// Returns the symbol for `s`, creating it if this interner has not
+    /// seen an equal string yet.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(s) {
+            return symbol.clone();
+        }
+        let symbol = Symbol::new(s);
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// Returns the interned equivalent of `symbol`, adopting it if this
+    /// interner has not seen an equal string yet.
+    pub fn intern_symbol(&mut self, symbol: Symbol) -> Symbol {
+        if let Some(interned) = self.symbols.get(symbol.as_str()) {
+            return interned.clone();
+        }
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns true if no string was interned.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interner, Symbol};
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_interning() {
+        let mut strings = Interner::new();
+        let a = strings.intern("main");
+        let b = strings.intern_symbol(Symbol::from("main".to_string()));
+        let c = strings.intern("color");
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&Symbol::new("main")));
+        assert_eq!(a, Symbol::new("main"));
+        assert_eq!(a, "main");
+        assert_ne!(a, c);
+        assert_eq!(2, strings.len());
+        assert_eq!(5, c.len());
+        assert_eq!("\"main\"", format!("{:?}", a));
+    }
+}
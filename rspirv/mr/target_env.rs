@@ -0,0 +1,400 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Module, Operand};
+use std::collections::BTreeSet;
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// Environments consuming SPIR-V modules, like the target environments of
+/// SPIRV-Tools.
+///
+/// Each environment determines the newest SPIR-V version accepted, the
+/// memory model to use, and the capabilities allowed. Further rules
+/// specific to an environment are checked by
+/// [`validate::validate_for_env()`](../validate/fn.validate_for_env.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TargetEnv {
+    /// SPIR-V 1.0 without environment-specific restrictions.
+    Universal1_0,
+    /// SPIR-V 1.1 without environment-specific restrictions.
+    Universal1_1,
+    /// SPIR-V 1.2 without environment-specific restrictions.
+    Universal1_2,
+    /// SPIR-V 1.3 without environment-specific restrictions.
+    Universal1_3,
+    /// Vulkan 1.0, accepting SPIR-V 1.0.
+    Vulkan1_0,
+    /// Vulkan 1.1, accepting SPIR-V up to 1.3.
+    Vulkan1_1,
+    /// Vulkan 1.2, accepting SPIR-V up to 1.5.
+    Vulkan1_2,
+    /// Vulkan 1.3, accepting SPIR-V up to 1.6.
+    Vulkan1_3,
+    /// OpenGL 4.5 with `GL_ARB_gl_spirv`, accepting SPIR-V 1.0.
+    OpenGL4_5,
+    /// OpenGL 4.6, accepting SPIR-V 1.0.
+    OpenGL4_6,
+    /// OpenCL 2.1, accepting SPIR-V 1.0.
+    OpenCL2_1,
+    /// OpenCL 2.2, accepting SPIR-V up to 1.2.
+    OpenCL2_2,
+}
+
+impl TargetEnv {
+    /// Returns the newest SPIR-V version accepted by this environment, as
+    /// (major, minor).
+    pub fn spirv_version(self) -> (u8, u8) {
+        match self {
+            TargetEnv::Universal1_0 |
+            TargetEnv::Vulkan1_0 |
+            TargetEnv::OpenGL4_5 |
+            TargetEnv::OpenGL4_6 |
+            TargetEnv::OpenCL2_1 => (1, 0),
+            TargetEnv::Universal1_1 => (1, 1),
+            TargetEnv::Universal1_2 | TargetEnv::OpenCL2_2 => (1, 2),
+            TargetEnv::Universal1_3 | TargetEnv::Vulkan1_1 => (1, 3),
+            TargetEnv::Vulkan1_2 => (1, 5),
+            TargetEnv::Vulkan1_3 => (1, 6),
+        }
+    }
+
+    /// Returns true if this environment is a version of Vulkan.
+    pub fn is_vulkan(self) -> bool {
+        matches!(
+            self,
+            TargetEnv::Vulkan1_0 |
+                TargetEnv::Vulkan1_1 |
+                TargetEnv::Vulkan1_2 |
+                TargetEnv::Vulkan1_3
+        )
+    }
+
+    /// Returns true if this environment is a version of OpenGL.
+    pub fn is_opengl(self) -> bool {
+        matches!(self, TargetEnv::OpenGL4_5 | TargetEnv::OpenGL4_6)
+    }
+
+    /// Returns true if this environment is a version of OpenCL.
+    pub fn is_opencl(self) -> bool {
+        matches!(self, TargetEnv::OpenCL2_1 | TargetEnv::OpenCL2_2)
+    }
+
+    /// Returns true if this environment only accepts shaders, i.e., not
+    /// kernels.
+    pub fn is_shader_only(self) -> bool {
+        self.is_vulkan() || self.is_opengl()
+    }
+
+    /// Returns the addressing and memory model used by modules for this
+    /// environment, if the environment mandates one.
+    pub fn memory_model(self) -> Option<(spirv::AddressingModel, spirv::MemoryModel)> {
+        if self.is_shader_only() {
+            Some((spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if modules for this environment may declare the given
+    /// `capability`.
+    ///
+    /// Shader-only environments reject capabilities for kernels, and OpenCL
+    /// rejects capabilities for shaders. All environments reject
+    /// capabilities introduced after their newest SPIR-V version, unless
+    /// also available via an extension.
+    pub fn allows_capability(self, capability: spirv::Capability) -> bool {
+        if self.is_shader_only() && is_kernel_capability(capability) {
+            return false;
+        }
+        if self.is_opencl() && is_shader_capability(capability) {
+            return false;
+        }
+        capability.required_version().is_none_or(|v| v <= self.spirv_version()) ||
+            !capability.required_extensions().is_empty()
+    }
+}
+
+impl fmt::Display for TargetEnv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TargetEnv::Universal1_0 => "SPIR-V 1.0",
+            TargetEnv::Universal1_1 => "SPIR-V 1.1",
+            TargetEnv::Universal1_2 => "SPIR-V 1.2",
+            TargetEnv::Universal1_3 => "SPIR-V 1.3",
+            TargetEnv::Vulkan1_0 => "Vulkan 1.0",
+            TargetEnv::Vulkan1_1 => "Vulkan 1.1",
+            TargetEnv::Vulkan1_2 => "Vulkan 1.2",
+            TargetEnv::Vulkan1_3 => "Vulkan 1.3",
+            TargetEnv::OpenGL4_5 => "OpenGL 4.5",
+            TargetEnv::OpenGL4_6 => "OpenGL 4.6",
+            TargetEnv::OpenCL2_1 => "OpenCL 2.1",
+            TargetEnv::OpenCL2_2 => "OpenCL 2.2",
+        })
+    }
+}
+
+/// Returns true if the given `capability` is, or depends on, a capability
+/// only available to kernels.
+fn is_kernel_capability(capability: spirv::Capability) -> bool {
+    use spirv::Capability::*;
+    match capability {
+        Kernel | Addresses | Linkage => true,
+        _ => capability.required_capabilities().iter().any(|&c| is_kernel_capability(c)),
+    }
+}
+
+/// Returns true if the given `capability` is, or depends on, the Shader
+/// capability.
+fn is_shader_capability(capability: spirv::Capability) -> bool {
+    capability == spirv::Capability::Shader ||
+        capability.required_capabilities().iter().any(|&c| is_shader_capability(c))
+}
+
+/// Adds the given `capability` and all capabilities it implicitly
+/// declares to `set`.
+fn declare(set: &mut BTreeSet<spirv::Capability>, capability: spirv::Capability) {
+    if set.insert(capability) {
+        for &c in capability.required_capabilities() {
+            declare(set, c);
+        }
+    }
+}
+
+/// A way a module does not fit a target environment.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TargetEnvIssue {
+    /// The module declares a SPIR-V version newer than the environment
+    /// accepts.
+    UnsupportedVersion((u8, u8)),
+    /// The module declares a capability the environment does not allow.
+    DisallowedCapability(spirv::Capability),
+    /// The module uses a memory model other than the one the environment
+    /// mandates.
+    WrongMemoryModel(spirv::AddressingModel, spirv::MemoryModel),
+    /// The module uses the given feature, e.g., `OpImageQuerySize` or
+    /// `BuiltIn::SampleId`, without declaring any of the capabilities it
+    /// requires.
+    MissingCapability(String, &'static [spirv::Capability]),
+}
+
+impl fmt::Display for TargetEnvIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TargetEnvIssue::UnsupportedVersion((major, minor)) => {
+                write!(f, "SPIR-V {}.{} is not supported", major, minor)
+            }
+            TargetEnvIssue::DisallowedCapability(c) => {
+                write!(f, "capability {} is not allowed", c)
+            }
+            TargetEnvIssue::WrongMemoryModel(a, m) => {
+                write!(f, "memory model {} {} is not allowed", a, m)
+            }
+            TargetEnvIssue::MissingCapability(ref feature, capabilities) => {
+                write!(f, "{} requires capability ", feature)?;
+                for (i, c) in capabilities.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" or ")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Module {
+    /// Checks whether this module can be consumed by the given target
+    /// environment `env`.
+    ///
+    /// Besides the restrictions of the environment, all instructions and
+    /// their enumerant operands are checked to have the capabilities they
+    /// require declared.
+    pub fn check_target_env(&self, env: TargetEnv) -> Vec<TargetEnvIssue> {
+        let mut issues = vec![];
+        if let Some(header) = self.header.as_ref() {
+            if header.version() > env.spirv_version() {
+                issues.push(TargetEnvIssue::UnsupportedVersion(header.version()));
+            }
+        }
+
+        let mut declared = BTreeSet::new();
+        for inst in &self.capabilities {
+            if let Some(&Operand::Capability(c)) = inst.operands.first() {
+                if !env.allows_capability(c) {
+                    issues.push(TargetEnvIssue::DisallowedCapability(c));
+                }
+                declare(&mut declared, c);
+            }
+        }
+
+        if let (Some(inst), Some(expected)) = (self.memory_model.as_ref(), env.memory_model()) {
+            if let (Some(&Operand::AddressingModel(a)), Some(&Operand::MemoryModel(m))) =
+                (inst.operands.first(), inst.operands.get(1))
+            {
+                if (a, m) != expected {
+                    issues.push(TargetEnvIssue::WrongMemoryModel(a, m));
+                }
+            }
+        }
+
+        let mut require = |required: &'static [spirv::Capability], feature: String| {
+            if !required.is_empty() && !required.iter().any(|c| declared.contains(c)) {
+                let issue = TargetEnvIssue::MissingCapability(feature, required);
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            }
+        };
+        for inst in self.all_inst_iter() {
+            let opcode = inst.class.opcode;
+            require(opcode.required_capabilities(), format!("Op{}", inst.class.opname));
+            for operand in &inst.operands {
+                let (required, feature) = match *operand {
+                    Operand::ExecutionModel(v) => {
+                        (v.required_capabilities(), format!("ExecutionModel::{:?}", v))
+                    }
+                    Operand::AddressingModel(v) => {
+                        (v.required_capabilities(), format!("AddressingModel::{:?}", v))
+                    }
+                    Operand::MemoryModel(v) => {
+                        (v.required_capabilities(), format!("MemoryModel::{:?}", v))
+                    }
+                    Operand::ExecutionMode(v) => {
+                        (v.required_capabilities(), format!("ExecutionMode::{:?}", v))
+                    }
+                    Operand::StorageClass(v) => {
+                        (v.required_capabilities(), format!("StorageClass::{:?}", v))
+                    }
+                    Operand::Dim(v) => (v.required_capabilities(), format!("Dim::{:?}", v)),
+                    Operand::ImageFormat(v) => {
+                        (v.required_capabilities(), format!("ImageFormat::{:?}", v))
+                    }
+                    Operand::Decoration(v) => {
+                        (v.required_capabilities(), format!("Decoration::{:?}", v))
+                    }
+                    Operand::BuiltIn(v) => (v.required_capabilities(), format!("BuiltIn::{:?}", v)),
+                    _ => continue,
+                };
+                require(required, feature);
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{TargetEnv, TargetEnvIssue};
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_allowed_capabilities() {
+        let env = TargetEnv::Vulkan1_0;
+        assert!(env.allows_capability(spirv::Capability::Shader));
+        assert!(!env.allows_capability(spirv::Capability::Kernel));
+        assert!(!env.allows_capability(spirv::Capability::Vector16));
+        assert!(!env.allows_capability(spirv::Capability::GroupNonUniform));
+        // Also available via SPV_KHR_shader_draw_parameters.
+        assert!(env.allows_capability(spirv::Capability::DrawParameters));
+        assert!(TargetEnv::Vulkan1_1.allows_capability(spirv::Capability::GroupNonUniform));
+        assert!(TargetEnv::Universal1_0.allows_capability(spirv::Capability::Kernel));
+        assert_eq!(None, TargetEnv::Universal1_3.memory_model());
+
+        let env = TargetEnv::OpenCL2_1;
+        assert!(env.allows_capability(spirv::Capability::Kernel));
+        assert!(env.allows_capability(spirv::Capability::Linkage));
+        assert!(!env.allows_capability(spirv::Capability::Shader));
+        assert!(!env.allows_capability(spirv::Capability::Geometry));
+        assert!(!env.is_shader_only());
+        assert_eq!((1, 2), TargetEnv::OpenCL2_2.spirv_version());
+    }
+
+    #[test]
+    fn test_builder_presets() {
+        let b = mr::Builder::for_vulkan_1_1();
+        assert_eq!(Some(TargetEnv::Vulkan1_1), b.target_env());
+        let m = b.checked_module().unwrap();
+        assert_eq!((1, 3), m.header.as_ref().unwrap().version());
+        assert_eq!(1, m.capabilities.len());
+        assert!(m.memory_model.is_some());
+
+        let m = mr::Builder::for_vulkan_1_3().module();
+        assert_eq!((1, 6), m.header.as_ref().unwrap().version());
+        assert!(m.check_target_env(TargetEnv::Vulkan1_3).is_empty());
+        assert_eq!(
+            vec![TargetEnvIssue::UnsupportedVersion((1, 6))],
+            m.check_target_env(TargetEnv::OpenGL4_6)
+        );
+        assert!(mr::Builder::for_opengl_4_6().checked_module().is_ok());
+    }
+
+    #[test]
+    fn test_missing_capabilities() {
+        let mut b = mr::Builder::for_vulkan_1_0();
+        b.capability(spirv::Capability::Addresses);
+        let float = b.type_float(32);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Input, float);
+        let v = b.variable(ptr, None, spirv::StorageClass::Input, None);
+        b.decorate(v, spirv::Decoration::BuiltIn, vec![spirv::BuiltIn::SampleId.into()]);
+        b.decorate(v, spirv::Decoration::BuiltIn, vec![spirv::BuiltIn::SampleId.into()]);
+        let issues = b.checked_module().unwrap_err();
+        let messages: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            vec![
+                "capability Addresses is not allowed",
+                "BuiltIn::SampleId requires capability SampleRateShading",
+            ],
+            messages
+        );
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 0);
+        b.memory_model(spirv::AddressingModel::Physical32, spirv::MemoryModel::OpenCL);
+        let float = b.type_float(32);
+        b.type_image(
+            float,
+            spirv::Dim::DimSubpassData,
+            0,
+            0,
+            0,
+            2,
+            spirv::ImageFormat::Unknown,
+            None,
+        );
+        let messages: Vec<String> = b
+            .module()
+            .check_target_env(TargetEnv::OpenGL4_5)
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "memory model Physical32 OpenCL is not allowed",
+                "AddressingModel::Physical32 requires capability Addresses",
+                "MemoryModel::OpenCL requires capability Kernel",
+                "Dim::DimSubpassData requires capability InputAttachment",
+            ],
+            messages
+        );
+    }
+}
@@ -0,0 +1,436 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Module, Operand};
+use std::collections::BTreeSet;
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// A feature used in a module which requires a SPIR-V version newer than 1.0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRequirement {
+    /// The minimum SPIR-V version supporting the feature, as (major, minor).
+    pub version: (u8, u8),
+    /// The feature, e.g., `OpDecorateId` or `StorageClass::StorageBuffer`.
+    pub feature: String,
+}
+
+/// The SPIR-V version requirements of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRequirements {
+    /// The minimum SPIR-V version the module needs, as (major, minor).
+    pub minimum: (u8, u8),
+    /// The version declared in the module header, if any.
+    pub declared: Option<(u8, u8)>,
+    /// All features requiring a version newer than 1.0, in the order they
+    /// were first used.
+    pub requirements: Vec<VersionRequirement>,
+}
+
+impl VersionRequirements {
+    /// Returns true if the version declared in the module header is older
+    /// than the minimum version the module needs.
+    pub fn is_header_too_old(&self) -> bool {
+        self.declared.is_some_and(|v| v < self.minimum)
+    }
+
+    /// Returns true if the version declared in the module header is newer
+    /// than the minimum version the module needs, i.e., the module could be
+    /// consumed by older environments after lowering its header version.
+    pub fn is_header_newer_than_needed(&self) -> bool {
+        self.declared.is_some_and(|v| v > self.minimum)
+    }
+}
+
+impl fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} requires SPIR-V {}.{}", self.feature, self.version.0, self.version.1)
+    }
+}
+
+/// A feature introduced in the given SPIR-V version, which is also available
+/// in earlier versions via any of the given extensions.
+type Introduced = Option<((u8, u8), &'static [&'static str])>;
+
+const V1_1: (u8, u8) = (1, 1);
+const V1_2: (u8, u8) = (1, 2);
+const V1_3: (u8, u8) = (1, 3);
+const V1_4: (u8, u8) = (1, 4);
+const V1_6: (u8, u8) = (1, 6);
+
+/// Returns when a feature was introduced, given the version and extensions
+/// the grammar lists for it. Features of SPIR-V 1.0 and features only
+/// available through extensions do not require a newer version.
+fn introduced(version: Option<(u8, u8)>, extensions: &'static [&'static str]) -> Introduced {
+    match version {
+        Some(version) if version > (1, 0) => Some((version, extensions)),
+        _ => None,
+    }
+}
+
+fn opcode_introduced(opcode: spirv::Op) -> Introduced {
+    introduced(opcode.required_version(), opcode.required_extensions())
+}
+
+fn capability_introduced(capability: spirv::Capability) -> Introduced {
+    introduced(capability.required_version(), capability.required_extensions())
+}
+
+fn storage_class_introduced(storage_class: spirv::StorageClass) -> Introduced {
+    match storage_class {
+        spirv::StorageClass::StorageBuffer => Some((
+            V1_3,
+            &["SPV_KHR_storage_buffer_storage_class", "SPV_KHR_variable_pointers"],
+        )),
+        _ => None,
+    }
+}
+
+fn builtin_introduced(builtin: spirv::BuiltIn) -> Introduced {
+    use spirv::BuiltIn::*;
+    match builtin {
+        SubgroupEqMask | SubgroupGeMask | SubgroupGtMask | SubgroupLeMask | SubgroupLtMask => {
+            Some((V1_3, &["SPV_KHR_shader_ballot"]))
+        }
+        BaseVertex | BaseInstance | DrawIndex => Some((V1_3, &["SPV_KHR_shader_draw_parameters"])),
+        DeviceIndex => Some((V1_3, &["SPV_KHR_device_group"])),
+        ViewIndex => Some((V1_3, &["SPV_KHR_multiview"])),
+        _ => None,
+    }
+}
+
+fn decoration_introduced(decoration: spirv::Decoration) -> Introduced {
+    use spirv::Decoration::*;
+    match decoration {
+        MaxByteOffset => Some((V1_1, &[])),
+        AlignmentId | MaxByteOffsetId => Some((V1_2, &[])),
+        _ => None,
+    }
+}
+
+fn execution_mode_introduced(mode: spirv::ExecutionMode) -> Introduced {
+    use spirv::ExecutionMode::*;
+    match mode {
+        Initializer | Finalizer | SubgroupSize | SubgroupsPerWorkgroup => Some((V1_1, &[])),
+        SubgroupsPerWorkgroupId | LocalSizeId | LocalSizeHintId => Some((V1_2, &[])),
+        _ => None,
+    }
+}
+
+fn loop_control_introduced(control: spirv::LoopControl) -> Introduced {
+    if control.intersects(spirv::LoopControl::DEPENDENCY_INFINITE |
+                          spirv::LoopControl::DEPENDENCY_LENGTH) {
+        Some((V1_1, &[]))
+    } else {
+        None
+    }
+}
+
+/// The version-dependent rules of SPIR-V applying to a module.
+///
+/// The [`Builder`](struct.Builder.html) and the validator consult these
+/// rules instead of checking version numbers themselves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VersionRules {
+    version: (u8, u8),
+}
+
+impl VersionRules {
+    /// Creates the rules of the given SPIR-V major.minor version.
+    pub fn new(major: u8, minor: u8) -> VersionRules {
+        VersionRules { version: (major, minor) }
+    }
+
+    /// Returns the SPIR-V version of these rules, as (major, minor).
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+
+    /// Returns true if entry point interfaces list all global variables
+    /// statically used by the entry point, which is the case since SPIR-V
+    /// 1.4. Before, they only list Input and Output variables.
+    pub fn interface_lists_all_globals(&self) -> bool {
+        self.version >= V1_4
+    }
+
+    /// Returns true if global variables of the given `storage_class` are
+    /// listed in entry point interfaces.
+    pub fn is_interface_storage_class(&self, storage_class: spirv::StorageClass) -> bool {
+        match storage_class {
+            spirv::StorageClass::Input | spirv::StorageClass::Output => true,
+            spirv::StorageClass::Function => false,
+            _ => self.interface_lists_all_globals(),
+        }
+    }
+
+    /// Returns true if the true and false labels of OpBranchConditional
+    /// must differ, which is the case since SPIR-V 1.6. Branches to a single
+    /// target then need OpBranch instead of an OpBranchConditional (and a
+    /// possibly needless OpSelectionMerge).
+    pub fn requires_distinct_branch_targets(&self) -> bool {
+        self.version >= V1_6
+    }
+
+    /// Returns true if the given `storage_class` is available, either in
+    /// this version or via one of the given declared `extensions`.
+    pub fn allows_storage_class(
+        &self,
+        storage_class: spirv::StorageClass,
+        extensions: &[&str],
+    ) -> bool {
+        match storage_class_introduced(storage_class) {
+            Some((version, enabling)) => {
+                version <= self.version || enabling.iter().any(|e| extensions.contains(e))
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the global variables to list in the interface of an entry
+    /// point for the function `function` in the given `module`, i.e., all
+    /// variables of interface storage classes statically used by the
+    /// function and the functions it calls.
+    ///
+    /// Returns `Err` with the id of the first function not defined in the
+    /// module.
+    pub fn entry_point_interface(
+        &self,
+        module: &Module,
+        function: spirv::Word,
+    ) -> Result<Vec<spirv::Word>, spirv::Word> {
+        let globals: BTreeSet<spirv::Word> = module
+            .types_global_values
+            .iter()
+            .filter(|inst| inst.class.opcode == spirv::Op::Variable)
+            .filter(|inst| match inst.operands.first() {
+                Some(&Operand::StorageClass(sc)) => self.is_interface_storage_class(sc),
+                _ => false,
+            })
+            .filter_map(|inst| inst.result_id)
+            .collect();
+
+        let mut interface = vec![];
+        let mut visited = BTreeSet::new();
+        let mut worklist = vec![function];
+        visited.insert(function);
+        while let Some(id) = worklist.pop() {
+            let function = module
+                .functions
+                .iter()
+                .find(|f| f.def.as_ref().and_then(|d| d.result_id) == Some(id))
+                .ok_or(id)?;
+            for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+                for operand in &inst.operands {
+                    if let Operand::IdRef(v) = *operand {
+                        if globals.contains(&v) && !interface.contains(&v) {
+                            interface.push(v);
+                        }
+                    }
+                }
+                if inst.class.opcode == spirv::Op::FunctionCall {
+                    if let Some(&Operand::IdRef(callee)) = inst.operands.first() {
+                        if visited.insert(callee) {
+                            worklist.push(callee);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(interface)
+    }
+}
+
+impl Module {
+    /// Returns the version-dependent rules applying to this module.
+    ///
+    /// Modules without header follow the rules of the newest version
+    /// supported by this crate, like what the
+    /// [`Builder`](struct.Builder.html) emits.
+    pub fn version_rules(&self) -> VersionRules {
+        let (major, minor) = self.header.as_ref().map_or(
+            (spirv::MAJOR_VERSION as u8, spirv::MINOR_VERSION as u8),
+            |h| h.version(),
+        );
+        VersionRules::new(major, minor)
+    }
+
+    /// Infers the minimum SPIR-V version this module needs.
+    ///
+    /// All instructions and their enumerant operands are scanned for
+    /// features introduced after SPIR-V 1.0. Features also available via an
+    /// extension (e.g., `StorageBuffer` via
+    /// `SPV_KHR_storage_buffer_storage_class`) do not require a newer
+    /// version if this module declares the extension with OpExtension.
+    ///
+    /// Only versions up to the one supported by this crate are known.
+    pub fn infer_version_requirements(&self) -> VersionRequirements {
+        let extensions: Vec<&str> = self.extensions
+            .iter()
+            .filter_map(|inst| match inst.operands.first() {
+                Some(Operand::LiteralString(name)) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        let mut requirements: Vec<VersionRequirement> = vec![];
+        {
+            let mut require = |introduced: Introduced, feature: String| {
+                if let Some((version, enabling)) = introduced {
+                    if enabling.iter().any(|e| extensions.contains(e)) ||
+                        requirements.iter().any(|r| r.feature == feature) {
+                        return;
+                    }
+                    requirements.push(VersionRequirement { version, feature });
+                }
+            };
+            for inst in self.all_inst_iter() {
+                let opcode = inst.class.opcode;
+                require(opcode_introduced(opcode), format!("Op{}", inst.class.opname));
+                for operand in &inst.operands {
+                    match *operand {
+                        Operand::Capability(v) => {
+                            require(capability_introduced(v), format!("Capability::{:?}", v))
+                        }
+                        Operand::StorageClass(v) => {
+                            require(storage_class_introduced(v), format!("StorageClass::{:?}", v))
+                        }
+                        Operand::BuiltIn(v) => {
+                            require(builtin_introduced(v), format!("BuiltIn::{:?}", v))
+                        }
+                        Operand::Decoration(v) => {
+                            require(decoration_introduced(v), format!("Decoration::{:?}", v))
+                        }
+                        Operand::ExecutionMode(v) => {
+                            require(execution_mode_introduced(v), format!("ExecutionMode::{:?}", v))
+                        }
+                        Operand::LoopControl(v) => {
+                            require(loop_control_introduced(v), format!("LoopControl::{:?}", v))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        VersionRequirements {
+            minimum: requirements.iter().map(|r| r.version).max().unwrap_or((1, 0)),
+            declared: self.header.as_ref().map(|h| h.version()),
+            requirements,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    #[cfg(not(feature = "std"))]
+    use prelude::*;
+
+    #[test]
+    fn test_version_rules() {
+        let sc = spirv::StorageClass::Uniform;
+        let rules = mr::VersionRules::new(1, 3);
+        assert!(!rules.interface_lists_all_globals());
+        assert!(!rules.is_interface_storage_class(sc));
+        assert!(rules.is_interface_storage_class(spirv::StorageClass::Output));
+        assert!(!rules.requires_distinct_branch_targets());
+        assert!(rules.allows_storage_class(spirv::StorageClass::StorageBuffer, &[]));
+
+        let rules = mr::VersionRules::new(1, 6);
+        assert!(rules.is_interface_storage_class(sc));
+        assert!(!rules.is_interface_storage_class(spirv::StorageClass::Function));
+        assert!(rules.requires_distinct_branch_targets());
+
+        let rules = mr::VersionRules::new(1, 0);
+        let sc = spirv::StorageClass::StorageBuffer;
+        assert!(!rules.allows_storage_class(sc, &[]));
+        assert!(rules.allows_storage_class(sc, &["SPV_KHR_variable_pointers"]));
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 4);
+        assert_eq!(mr::VersionRules::new(1, 4), b.version_rules());
+        assert_eq!((1, 4), b.module().version_rules().version());
+        assert_eq!((1, 3), mr::Module::new().version_rules().version());
+    }
+
+    #[test]
+    fn test_no_requirements() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        let m = b.module();
+        let req = m.infer_version_requirements();
+        assert_eq!((1, 0), req.minimum);
+        assert!(req.requirements.is_empty());
+        assert!(req.is_header_newer_than_needed());
+        assert!(!req.is_header_too_old());
+    }
+
+    #[test]
+    fn test_requirements() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 1);
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::GroupNonUniform);
+        b.decorate_id(1, spirv::Decoration::AlignmentId, vec![mr::Operand::IdRef(2)]);
+        b.module_processed("opt");
+        let m = b.module();
+        let req = m.infer_version_requirements();
+        assert_eq!((1, 3), req.minimum);
+        assert_eq!(Some((1, 1)), req.declared);
+        assert!(req.is_header_too_old());
+        assert_eq!(
+            vec![
+                "Capability::GroupNonUniform requires SPIR-V 1.3",
+                "OpModuleProcessed requires SPIR-V 1.1",
+                "OpDecorateId requires SPIR-V 1.2",
+                "Decoration::AlignmentId requires SPIR-V 1.2",
+            ],
+            req.requirements.iter().map(|r| r.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_extension_enables_feature() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 0);
+        b.capability(spirv::Capability::DrawParameters);
+        let req = b.module_ref().infer_version_requirements();
+        assert_eq!((1, 3), req.minimum);
+
+        b.extension("SPV_KHR_shader_draw_parameters");
+        let req = b.module_ref().infer_version_requirements();
+        assert_eq!((1, 0), req.minimum);
+        assert!(!req.is_header_too_old());
+    }
+
+    #[test]
+    fn test_grammar_requirements() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::DenormPreserve);
+        b.capability(spirv::Capability::RayQueryKHR);
+        let req = b.module_ref().infer_version_requirements();
+        // Extension-only capabilities do not require a newer version.
+        assert_eq!(
+            vec!["Capability::DenormPreserve requires SPIR-V 1.4"],
+            req.requirements.iter().map(|r| r.to_string()).collect::<Vec<_>>()
+        );
+
+        b.extension("SPV_KHR_float_controls");
+        assert!(b.module_ref().infer_version_requirements().requirements.is_empty());
+    }
+}
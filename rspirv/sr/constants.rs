@@ -25,16 +25,33 @@ pub struct Constant {
 #[derive(Clone, Debug, PartialEq)]
 pub(in sr) enum ConstantEnum {
     Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
     I32(i32),
     U32(u32),
     F32(f32),
+    I64(i64),
+    U64(u64),
+    /// The raw bits of a half precision float.
+    F16(u16),
+    F64(f64),
     Composite(Vec<ConstantToken>),
     Null(TypeToken),
     Sampler(spirv::SamplerAddressingMode, u32, spirv::SamplerFilterMode),
     SpecBool(bool),
+    SpecI8(i8),
+    SpecU8(u8),
+    SpecI16(i16),
+    SpecU16(u16),
     SpecI32(i32),
     SpecU32(u32),
     SpecF32(f32),
+    SpecI64(i64),
+    SpecU64(u64),
+    SpecF16(u16),
+    SpecF64(f64),
     SpecComposite(Vec<ConstantToken>),
     SpecOp(spirv::Op, Vec<ConstantToken>),
 }
@@ -54,6 +71,38 @@ impl Constant {
         }
     }
 
+    pub fn is_i8_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::I8 { .. } |
+            ConstantEnum::SpecI8 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_u8_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::U8 { .. } |
+            ConstantEnum::SpecU8 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_i16_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::I16 { .. } |
+            ConstantEnum::SpecI16 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_u16_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::U16 { .. } |
+            ConstantEnum::SpecU16 { .. } => true,
+            _ => false,
+        }
+    }
+
     pub fn is_i32_constant(&self) -> bool {
         match self.c {
             ConstantEnum::I32 { .. } |
@@ -78,6 +127,38 @@ impl Constant {
         }
     }
 
+    pub fn is_i64_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::I64 { .. } |
+            ConstantEnum::SpecI64 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_u64_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::U64 { .. } |
+            ConstantEnum::SpecU64 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_f16_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::F16 { .. } |
+            ConstantEnum::SpecF16 { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_f64_constant(&self) -> bool {
+        match self.c {
+            ConstantEnum::F64 { .. } |
+            ConstantEnum::SpecF64 { .. } => true,
+            _ => false,
+        }
+    }
+
     pub fn is_composite_constant(&self) -> bool {
         match self.c {
             ConstantEnum::Composite { .. } |
@@ -103,9 +184,17 @@ impl Constant {
     pub fn is_spec_constant(&self) -> bool {
         match self.c {
             ConstantEnum::SpecBool { .. } |
+            ConstantEnum::SpecI8 { .. } |
+            ConstantEnum::SpecU8 { .. } |
+            ConstantEnum::SpecI16 { .. } |
+            ConstantEnum::SpecU16 { .. } |
             ConstantEnum::SpecI32 { .. } |
             ConstantEnum::SpecU32 { .. } |
             ConstantEnum::SpecF32 { .. } |
+            ConstantEnum::SpecI64 { .. } |
+            ConstantEnum::SpecU64 { .. } |
+            ConstantEnum::SpecF16 { .. } |
+            ConstantEnum::SpecF64 { .. } |
             ConstantEnum::SpecComposite { .. } |
             ConstantEnum::SpecOp { .. } => true,
             _ => false,
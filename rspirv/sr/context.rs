@@ -16,7 +16,9 @@ use spirv;
 
 use std::collections::BTreeSet;
 
-use super::{Type, TypeToken, Constant, ConstantToken};
+use super::{BasicBlock, Instruction, Operation, Parameter, Terminator, Token, Type, TypeToken};
+use super::{Constant, ConstantToken, Variable, VariableToken};
+use sr::storage::{next_generation, Object};
 use sr::constants::ConstantEnum;
 use sr::types::TypeEnum;
 
@@ -30,16 +32,27 @@ use sr::types::TypeEnum;
 /// of objects inside the context. The context serves as the memory arena.
 #[derive(Debug)]
 pub struct Context {
+    /// The generation of this context, recorded in all tokens it creates.
+    generation: usize,
     /// All type objects.
     types: Vec<Type>,
     constants: Vec<Constant>,
+    variables: Vec<Variable>,
+    blocks: Vec<BasicBlock>,
+    parameters: Vec<Parameter>,
+    operations: Vec<Operation>,
 }
 
 impl Context {
     pub fn new() -> Context {
         Context {
+            generation: next_generation(),
             types: vec![],
             constants: vec![],
+            variables: vec![],
+            blocks: vec![],
+            parameters: vec![],
+            operations: vec![],
         }
     }
 }
@@ -60,6 +73,37 @@ impl Context {
         // Note: we assume the vector doesn't shrink so we always have a valid index.
         &self.types[token.get()]
     }
+
+    /// Creates a placeholder for the pointer type declared ahead by an
+    /// OpTypeForwardPointer, to be turned into the real pointer type by
+    /// `resolve_forward_pointer` once it is defined.
+    ///
+    /// Unlike `type_forward_pointer`, placeholders are never shared, since
+    /// each one stands for a different pointer type.
+    pub(in sr) fn forward_pointer_placeholder(
+        &mut self,
+        storage_class: spirv::StorageClass,
+    ) -> TypeToken {
+        self.types.push(Type {
+            ty: TypeEnum::ForwardPointer { storage_class },
+            decorations: BTreeSet::new(),
+        });
+        TypeToken::new(self.types.len() - 1)
+    }
+
+    /// Turns the given forward pointer `placeholder` into the pointer type
+    /// to `pointee_type` it stands for, so that all types referencing the
+    /// placeholder see the real pointer type.
+    pub(in sr) fn resolve_forward_pointer(
+        &mut self,
+        placeholder: TypeToken,
+        pointee_type: TypeToken,
+    ) {
+        let ty = &mut self.types[placeholder.get()].ty;
+        if let TypeEnum::ForwardPointer { storage_class } = *ty {
+            *ty = TypeEnum::Pointer { storage_class, pointee_type };
+        }
+    }
 }
 
 macro_rules! fetch_or_append {
@@ -79,6 +123,26 @@ impl Context {
         fetch_or_append!(self.constants, v)
     }
 
+    pub fn constant_i8(&mut self, val: i8) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::I8(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn constant_u8(&mut self, val: u8) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::U8(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn constant_i16(&mut self, val: i16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::I16(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn constant_u16(&mut self, val: u16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::U16(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
     pub fn constant_i32(&mut self, val: i32) -> ConstantToken {
         let v = Constant { c: ConstantEnum::I32(val) };
         fetch_or_append!(self.constants, v)
@@ -94,6 +158,27 @@ impl Context {
         fetch_or_append!(self.constants, v)
     }
 
+    pub fn constant_i64(&mut self, val: i64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::I64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn constant_u64(&mut self, val: u64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::U64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    /// Creates a half precision float constant from its raw bits.
+    pub fn constant_f16(&mut self, bits: u16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::F16(bits) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn constant_f64(&mut self, val: f64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::F64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
     pub fn constant_composite<T: AsRef<[ConstantToken]>>(&mut self, val: T) -> ConstantToken {
         let v = Constant { c: ConstantEnum::Composite(val.as_ref().to_vec()) };
         fetch_or_append!(self.constants, v)
@@ -119,6 +204,26 @@ impl Context {
         fetch_or_append!(self.constants, v)
     }
 
+    pub fn spec_constant_i8(&mut self, val: i8) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecI8(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn spec_constant_u8(&mut self, val: u8) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecU8(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn spec_constant_i16(&mut self, val: i16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecI16(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn spec_constant_u16(&mut self, val: u16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecU16(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
     pub fn spec_constant_i32(&mut self, val: i32) -> ConstantToken {
         let v = Constant { c: ConstantEnum::SpecI32(val) };
         fetch_or_append!(self.constants, v)
@@ -134,6 +239,28 @@ impl Context {
         fetch_or_append!(self.constants, v)
     }
 
+    pub fn spec_constant_i64(&mut self, val: i64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecI64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn spec_constant_u64(&mut self, val: u64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecU64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    /// Creates a half precision float specialization constant from its raw
+    /// bits.
+    pub fn spec_constant_f16(&mut self, bits: u16) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecF16(bits) };
+        fetch_or_append!(self.constants, v)
+    }
+
+    pub fn spec_constant_f64(&mut self, val: f64) -> ConstantToken {
+        let v = Constant { c: ConstantEnum::SpecF64(val) };
+        fetch_or_append!(self.constants, v)
+    }
+
     pub fn spec_constant_composite<T: AsRef<[ConstantToken]>>(&mut self, val: T) -> ConstantToken {
         let v = Constant { c: ConstantEnum::SpecComposite(val.as_ref().to_vec()) };
         fetch_or_append!(self.constants, v)
@@ -155,6 +282,151 @@ impl Context {
     }
 }
 
+impl Context {
+    /// Creates a new variable of the given pointer type `ty` in the given
+    /// `storage_class`.
+    ///
+    /// Variables are not uniqued: each call returns a distinct token.
+    pub fn variable(
+        &mut self,
+        ty: TypeToken,
+        storage_class: spirv::StorageClass,
+        initializer: Option<ConstantToken>,
+    ) -> VariableToken {
+        self.variables.push(Variable {
+            ty,
+            storage_class,
+            initializer,
+            decorations: vec![],
+            name: None,
+        });
+        VariableToken::new(self.variables.len() - 1)
+    }
+
+    /// Returns the reference to the real variable represented by the given token.
+    pub fn get_variable(&self, token: VariableToken) -> &Variable {
+        // Note: we assume the vector doesn't shrink so we always have a valid index.
+        &self.variables[token.get()]
+    }
+
+    /// Returns the mutable reference to the real variable represented by the given token.
+    pub fn get_variable_mut(&mut self, token: VariableToken) -> &mut Variable {
+        &mut self.variables[token.get()]
+    }
+}
+
+macro_rules! impl_object {
+    ($object: ident, $field: ident) => {
+        impl Object for $object {
+            fn storage(context: &Context) -> &[$object] {
+                &context.$field
+            }
+
+            fn storage_mut(context: &mut Context) -> &mut Vec<$object> {
+                &mut context.$field
+            }
+        }
+    }
+}
+
+impl_object!(BasicBlock, blocks);
+impl_object!(Parameter, parameters);
+impl_object!(Operation, operations);
+
+impl Context {
+    /// Allocates the given `object` in this context.
+    fn alloc<T: Object>(&mut self, object: T) -> Token<T> {
+        let generation = self.generation;
+        let storage = T::storage_mut(self);
+        storage.push(object);
+        Token::new(storage.len() - 1, generation)
+    }
+
+    /// Returns true if the given token was created by this context.
+    pub fn owns<T: Object>(&self, token: Token<T>) -> bool {
+        token.generation() == self.generation && token.get() < T::storage(self).len()
+    }
+
+    /// Returns the reference to the real object represented by the given token.
+    ///
+    /// In debug builds, panics if the token was created by another context.
+    pub fn get<T: Object>(&self, token: Token<T>) -> &T {
+        debug_assert!(self.owns(token), "{:?} does not belong to this context", token);
+        &T::storage(self)[token.get()]
+    }
+
+    /// Returns the mutable reference to the real object represented by the given token.
+    ///
+    /// In debug builds, panics if the token was created by another context.
+    pub fn get_mut<T: Object>(&mut self, token: Token<T>) -> &mut T {
+        debug_assert!(self.owns(token), "{:?} does not belong to this context", token);
+        &mut T::storage_mut(self)[token.get()]
+    }
+}
+
+impl Context {
+    /// Creates a new basic block labelled by `label`, containing the given
+    /// `operations`, and ending with the given `terminator`.
+    pub fn basic_block(
+        &mut self,
+        label: spirv::Word,
+        operations: Vec<Token<Operation>>,
+        terminator: Terminator,
+    ) -> Token<BasicBlock> {
+        self.alloc(BasicBlock {
+            label,
+            operations,
+            terminator,
+        })
+    }
+
+    /// Returns the reference to the real basic block represented by the given token.
+    pub fn get_basic_block(&self, token: Token<BasicBlock>) -> &BasicBlock {
+        self.get(token)
+    }
+
+    /// Returns the mutable reference to the real basic block represented by the given token.
+    pub fn get_basic_block_mut(&mut self, token: Token<BasicBlock>) -> &mut BasicBlock {
+        self.get_mut(token)
+    }
+}
+
+impl Context {
+    /// Creates a new function parameter of the given type `ty`.
+    pub fn parameter(&mut self, id: spirv::Word, ty: TypeToken) -> Token<Parameter> {
+        self.alloc(Parameter { id, ty })
+    }
+
+    /// Returns the reference to the real parameter represented by the given token.
+    pub fn get_parameter(&self, token: Token<Parameter>) -> &Parameter {
+        self.get(token)
+    }
+
+    /// Creates a new operation out of the given `instruction`.
+    pub fn operation(
+        &mut self,
+        result_type: Option<TypeToken>,
+        result_id: Option<spirv::Word>,
+        instruction: Instruction,
+    ) -> Token<Operation> {
+        self.alloc(Operation {
+            result_id,
+            result_type,
+            instruction,
+        })
+    }
+
+    /// Returns the reference to the real operation represented by the given token.
+    pub fn get_operation(&self, token: Token<Operation>) -> &Operation {
+        self.get(token)
+    }
+
+    /// Returns the mutable reference to the real operation represented by the given token.
+    pub fn get_operation_mut(&mut self, token: Token<Operation>) -> &mut Operation {
+        self.get_mut(token)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use spirv;
@@ -240,6 +512,20 @@ mod tests {
         assert!(t2 != t3);
     }
 
+    #[test]
+    fn test_variable_non_uniqueness() {
+        let mut c = Context::new();
+        let f32t = c.type_float(32);
+        let ptrt = c.type_pointer(spirv::StorageClass::Input, f32t);
+        let v1 = c.variable(ptrt, spirv::StorageClass::Input, None);
+        let v2 = c.variable(ptrt, spirv::StorageClass::Input, None);
+        assert!(v1 != v2);
+        let v = c.get_variable(v1);
+        assert_eq!(ptrt, v.ty);
+        assert!(v.is_interface_variable());
+        assert!(v.initializer.is_none());
+    }
+
     #[test]
     fn test_get_constant() {
         let mut c = Context::new();
@@ -394,4 +680,32 @@ mod tests {
         let v3 = c.spec_constant_op(spirv::Op::FSub, vec![component, component]);
         assert!(v1 != v3);
     }
+
+    #[test]
+    fn test_token_ownership() {
+        let mut c1 = Context::new();
+        let mut c2 = Context::new();
+        let uint = c1.type_int(32, 0);
+        let p1 = c1.parameter(1, uint);
+        let p2 = c2.parameter(1, uint);
+        assert!(p1 != p2);
+        assert!(c1.owns(p1));
+        assert!(!c1.owns(p2));
+        assert!(c2.owns(p2));
+        assert_eq!(1, c1.get(p1).id);
+        c1.get_mut(p1).id = 2;
+        assert_eq!(2, c1.get_parameter(p1).id);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not belong to this context")]
+    fn test_foreign_token() {
+        let mut c1 = Context::new();
+        let mut c2 = Context::new();
+        let uint = c1.type_int(32, 0);
+        c1.parameter(1, uint);
+        let p2 = c2.parameter(1, uint);
+        c1.get(p2);
+    }
 }
@@ -0,0 +1,284 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use num::FromPrimitive;
+use spirv::DebugInfoOp as Op;
+use std::collections::HashMap;
+
+use super::module::{LiftError, LiftResult};
+
+/// The name of the extended instruction set for rich debug information.
+pub const DEBUG_INFO_SET: &str = "NonSemantic.Shader.DebugInfo.100";
+
+/// An operand of a NonSemantic.Shader.DebugInfo.100 instruction, resolved
+/// symbolically.
+///
+/// All operands of these instructions are ids. Ids referring to OpString
+/// instructions or to constants are resolved into their values if the
+/// instruction expects a string or a (flags, enum, or integer) constant at
+/// that position. Other ids, e.g., of types, variables, or other debug
+/// instructions, are kept as they are.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugOperand {
+    /// An id which is not resolved further.
+    Id(spirv::Word),
+    /// The string of an OpString.
+    String(String),
+    /// The value of a 32-bit integer constant, e.g., a line number or size.
+    Uint(u32),
+    /// The value of a boolean constant.
+    Bool(bool),
+    /// The flags of a type, member, variable, or function.
+    Flags(spirv::DebugInfoFlags),
+    /// The flags of a build identifier.
+    BuildIdentifierFlags(spirv::BuildIdentifierFlags),
+    /// The encoding of a basic type.
+    Encoding(spirv::DebugBaseTypeAttributeEncoding),
+    /// The tag of a composite type.
+    CompositeType(spirv::DebugCompositeType),
+    /// The qualifier of a qualified type.
+    TypeQualifier(spirv::DebugTypeQualifier),
+    /// The operation of a debug expression operation.
+    Operation(spirv::DebugOperation),
+    /// The tag of an imported entity.
+    ImportedEntity(spirv::DebugImportedEntity),
+    /// The storage class of a pointer type.
+    StorageClass(spirv::StorageClass),
+    /// The source language of a compilation unit.
+    SourceLanguage(spirv::SourceLanguage),
+}
+
+/// Structured representation of an OpExtInst instruction of the
+/// NonSemantic.Shader.DebugInfo.100 extended instruction set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugInstruction {
+    /// The result id of the OpExtInst instruction.
+    pub result_id: spirv::Word,
+    /// The extended instruction.
+    pub op: spirv::DebugInfoOp,
+    /// The operands following the extended instruction opcode.
+    pub operands: Vec<DebugOperand>,
+}
+
+impl DebugInstruction {
+    /// Returns the string operand at `index`, if it is one.
+    pub fn string(&self, index: usize) -> Option<&str> {
+        match self.operands.get(index) {
+            Some(DebugOperand::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer operand at `index`, if it is one.
+    pub fn uint(&self, index: usize) -> Option<u32> {
+        match self.operands.get(index) {
+            Some(&DebugOperand::Uint(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the id operand at `index`, if it is one.
+    pub fn id(&self, index: usize) -> Option<spirv::Word> {
+        match self.operands.get(index) {
+            Some(&DebugOperand::Id(id)) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// The expected kind of the value an operand id refers to.
+#[derive(Copy, Clone)]
+enum Kind {
+    Id,
+    Str,
+    Uint,
+    Bool,
+    Flags,
+    BuildFlags,
+    Encoding,
+    Composite,
+    Qualifier,
+    Operation,
+    Imported,
+    StorageClass,
+    Language,
+}
+
+/// Returns the expected operand kinds of the given instruction, and how
+/// many of the trailing kinds repeat for variadic operands.
+fn operand_kinds(op: Op) -> (&'static [Kind], usize) {
+    use self::Kind::*;
+    match op {
+        Op::DebugInfoNone | Op::DebugNoScope | Op::DebugNoLine => (&[], 0),
+        Op::DebugCompilationUnit => (&[Uint, Uint, Id, Language], 0),
+        Op::DebugTypeBasic => (&[Str, Uint, Encoding, Flags], 0),
+        Op::DebugTypePointer => (&[Id, StorageClass, Flags], 0),
+        Op::DebugTypeQualifier => (&[Id, Qualifier], 0),
+        Op::DebugTypeArray => (&[Id, Uint], 1),
+        Op::DebugTypeVector => (&[Id, Uint], 0),
+        Op::DebugTypedef => (&[Str, Id, Id, Uint, Uint, Id], 0),
+        Op::DebugTypeFunction => (&[Flags, Id, Id], 1),
+        Op::DebugTypeEnum => (&[Str, Id, Id, Uint, Uint, Id, Uint, Flags, Uint, Str], 2),
+        Op::DebugTypeComposite => (&[Str, Composite, Id, Uint, Uint, Id, Str, Uint, Flags, Id], 1),
+        Op::DebugTypeMember => (&[Str, Id, Id, Uint, Uint, Uint, Uint, Flags, Id], 0),
+        Op::DebugTypeInheritance => (&[Id, Uint, Uint, Flags], 0),
+        Op::DebugTypePtrToMember => (&[Id, Id], 0),
+        Op::DebugTypeTemplate => (&[Id, Id], 1),
+        Op::DebugTypeTemplateParameter => (&[Str, Id, Id, Id, Uint, Uint], 0),
+        Op::DebugTypeTemplateTemplateParameter => (&[Str, Str, Id, Uint, Uint], 0),
+        Op::DebugTypeTemplateParameterPack => (&[Str, Id, Uint, Uint, Id], 1),
+        Op::DebugGlobalVariable => (&[Str, Id, Id, Uint, Uint, Id, Str, Id, Flags, Id], 0),
+        Op::DebugFunctionDeclaration => (&[Str, Id, Id, Uint, Uint, Id, Str, Flags], 0),
+        Op::DebugFunction => (&[Str, Id, Id, Uint, Uint, Id, Str, Flags, Uint, Id], 0),
+        Op::DebugLexicalBlock => (&[Id, Uint, Uint, Id, Str], 0),
+        Op::DebugLexicalBlockDiscriminator => (&[Id, Uint, Id], 0),
+        Op::DebugScope => (&[Id, Id], 0),
+        Op::DebugInlinedAt => (&[Uint, Id, Id], 0),
+        Op::DebugLocalVariable => (&[Str, Id, Id, Uint, Uint, Id, Flags, Uint], 0),
+        Op::DebugInlinedVariable | Op::DebugFunctionDefinition => (&[Id, Id], 0),
+        Op::DebugDeclare | Op::DebugValue => (&[Id, Id, Id, Id], 1),
+        Op::DebugOperation => (&[Operation, Uint], 1),
+        Op::DebugExpression => (&[Id], 1),
+        Op::DebugMacroDef => (&[Id, Uint, Str, Str], 0),
+        Op::DebugMacroUndef => (&[Id, Uint, Id], 0),
+        Op::DebugImportedEntity => (&[Str, Imported, Id, Id, Uint, Uint, Id], 0),
+        Op::DebugSource => (&[Str, Str], 0),
+        Op::DebugSourceContinued | Op::DebugStoragePath => (&[Str], 0),
+        Op::DebugLine => (&[Id, Uint, Uint, Uint, Uint], 0),
+        Op::DebugBuildIdentifier => (&[Str, BuildFlags], 0),
+        Op::DebugEntryPoint => (&[Id, Id, Str, Str], 0),
+        Op::DebugTypeMatrix => (&[Id, Uint, Bool], 0),
+    }
+}
+
+/// The values OpString instructions and constants define, for resolving
+/// debug instruction operands.
+pub(in sr) struct DebugValues<'a> {
+    strings: HashMap<spirv::Word, &'a str>,
+    uints: HashMap<spirv::Word, u32>,
+    bools: HashMap<spirv::Word, bool>,
+}
+
+impl<'a> DebugValues<'a> {
+    pub(in sr) fn new(module: &'a mr::Module) -> DebugValues<'a> {
+        let mut values = DebugValues {
+            strings: HashMap::new(),
+            uints: HashMap::new(),
+            bools: HashMap::new(),
+        };
+        for inst in &module.debugs {
+            if let (spirv::Op::String, Some(id), Some(mr::Operand::LiteralString(s))) =
+                (inst.class.opcode, inst.result_id, inst.operands.first())
+            {
+                values.strings.insert(id, s.as_str());
+            }
+        }
+        for inst in &module.types_global_values {
+            let id = match inst.result_id {
+                Some(id) => id,
+                None => continue,
+            };
+            match (inst.class.opcode, inst.operands.first()) {
+                (spirv::Op::Constant, Some(&mr::Operand::LiteralInt32(v))) => {
+                    values.uints.insert(id, v);
+                }
+                (spirv::Op::ConstantTrue, _) => {
+                    values.bools.insert(id, true);
+                }
+                (spirv::Op::ConstantFalse, _) => {
+                    values.bools.insert(id, false);
+                }
+                _ => {}
+            }
+        }
+        values
+    }
+
+    /// Resolves the operand `id` expected to be of the given `kind`,
+    /// keeping it as an id if it does not refer to such a value.
+    fn resolve(&self, id: spirv::Word, kind: Kind) -> DebugOperand {
+        let uint = self.uints.get(&id).cloned();
+        let operand = match kind {
+            Kind::Id => None,
+            Kind::Str => self.strings.get(&id).map(|s| DebugOperand::String(s.to_string())),
+            Kind::Uint => uint.map(DebugOperand::Uint),
+            Kind::Bool => self.bools.get(&id).map(|&b| DebugOperand::Bool(b)),
+            Kind::Flags => {
+                uint.and_then(spirv::DebugInfoFlags::from_bits).map(DebugOperand::Flags)
+            }
+            Kind::BuildFlags => uint
+                .and_then(spirv::BuildIdentifierFlags::from_bits)
+                .map(DebugOperand::BuildIdentifierFlags),
+            Kind::Encoding => uint
+                .and_then(spirv::DebugBaseTypeAttributeEncoding::from_u32)
+                .map(DebugOperand::Encoding),
+            Kind::Composite => uint
+                .and_then(spirv::DebugCompositeType::from_u32)
+                .map(DebugOperand::CompositeType),
+            Kind::Qualifier => uint
+                .and_then(spirv::DebugTypeQualifier::from_u32)
+                .map(DebugOperand::TypeQualifier),
+            Kind::Operation => {
+                uint.and_then(spirv::DebugOperation::from_u32).map(DebugOperand::Operation)
+            }
+            Kind::Imported => uint
+                .and_then(spirv::DebugImportedEntity::from_u32)
+                .map(DebugOperand::ImportedEntity),
+            Kind::StorageClass => {
+                uint.and_then(spirv::StorageClass::from_u32).map(DebugOperand::StorageClass)
+            }
+            Kind::Language => {
+                uint.and_then(spirv::SourceLanguage::from_u32).map(DebugOperand::SourceLanguage)
+            }
+        };
+        operand.unwrap_or(DebugOperand::Id(id))
+    }
+
+    /// Lifts the given OpExtInst instruction, whose instruction set is
+    /// already known to be NonSemantic.Shader.DebugInfo.100.
+    ///
+    /// Returns `Ok(None)` for extended instructions unknown to this
+    /// library, which may be ignored since they are non-semantic.
+    pub(in sr) fn lift(&self, inst: &mr::Instruction) -> LiftResult<Option<DebugInstruction>> {
+        let wrong = || LiftError::WrongOperands(spirv::Op::ExtInst);
+        let result_id = inst.result_id.ok_or(LiftError::MissingResultId(spirv::Op::ExtInst))?;
+        let op = match inst.ext_inst() {
+            Some((_, opcode)) => match Op::from_u32(opcode) {
+                Some(op) => op,
+                None => return Ok(None),
+            },
+            None => return Err(wrong()),
+        };
+        let (kinds, repeat) = operand_kinds(op);
+        let mut operands = vec![];
+        for (i, operand) in inst.operands.iter().skip(2).enumerate() {
+            let id = match *operand {
+                mr::Operand::IdRef(id) => id,
+                _ => return Err(wrong()),
+            };
+            let kind = if i < kinds.len() {
+                kinds[i]
+            } else if repeat > 0 {
+                kinds[kinds.len() - repeat + (i - kinds.len()) % repeat]
+            } else {
+                Kind::Id
+            };
+            operands.push(self.resolve(id, kind));
+        }
+        Ok(Some(DebugInstruction { result_id, op, operands }))
+    }
+}
@@ -74,4 +74,5 @@ pub enum Decoration {
     NonUniformEXT,
     HlslCounterBufferGOOGLE(spirv::Word),
     HlslSemanticGOOGLE(String),
+    PerPrimitiveEXT,
 }
@@ -0,0 +1,77 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{Instruction, Terminator, Token, TypeToken};
+
+/// The class to represent a SPIR-V function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    /// The result id of this function.
+    pub id: spirv::Word,
+    /// The function control given by OpFunction.
+    pub control: spirv::FunctionControl,
+    /// The function type of this function.
+    pub ty: TypeToken,
+    /// All parameters of this function, in declaration order.
+    pub parameters: Vec<Token<Parameter>>,
+    /// All basic blocks of this function, in module order. The first one
+    /// is the entry block.
+    pub blocks: Vec<Token<BasicBlock>>,
+}
+
+/// The class to represent a SPIR-V function parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    /// The result id of the OpFunctionParameter.
+    pub id: spirv::Word,
+    /// The type of this parameter.
+    pub ty: TypeToken,
+}
+
+/// The class to represent a SPIR-V basic block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicBlock {
+    /// The result id of the OpLabel starting this basic block.
+    pub label: spirv::Word,
+    /// All instructions of this basic block except the terminator, in
+    /// order.
+    pub operations: Vec<Token<Operation>>,
+    /// The instruction ending this basic block.
+    pub terminator: Terminator,
+}
+
+/// The class to represent a non-terminator instruction inside a basic
+/// block, together with its result.
+///
+/// Other instructions refer to the result of an operation by its token
+/// via [`Value::Operation`](enum.Value.html), so the operations of a
+/// function form an SSA graph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    /// The result id, if the instruction has one.
+    pub result_id: Option<spirv::Word>,
+    /// The result type, if the instruction has one.
+    pub result_type: Option<TypeToken>,
+    /// The instruction with its operands.
+    pub instruction: Instruction,
+}
+
+impl Function {
+    /// Returns the entry block of this function, if it has a body.
+    pub fn entry_block(&self) -> Option<Token<BasicBlock>> {
+        self.blocks.first().cloned()
+    }
+}
@@ -16,7 +16,9 @@
 //   external/spirv.core.grammar.json.
 // DO NOT MODIFY!
 
-enum Instruction {
+/// SPIR-V instructions other than types, constants, and terminators.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
     Nop,
     Undef,
     SourceContinued {
@@ -25,18 +27,18 @@ enum Instruction {
     Source {
         source_language: spirv::SourceLanguage,
         version: u32,
-        file: Option<spirv::Word>,
+        file: Option<Value>,
         source: Option<String>,
     },
     SourceExtension {
         extension: String,
     },
     Name {
-        target: spirv::Word,
+        target: Value,
         name: String,
     },
     MemberName {
-        target_type: spirv::Word,
+        target_type: Value,
         member: u32,
         name: String,
     },
@@ -44,7 +46,7 @@ enum Instruction {
         string: String,
     },
     Line {
-        file: spirv::Word,
+        file: Value,
         line: u32,
         column: u32,
     },
@@ -55,9 +57,9 @@ enum Instruction {
         name: String,
     },
     ExtInst {
-        set: spirv::Word,
+        set: Value,
         instruction: u32,
-        operands: Vec<spirv::Word>,
+        operands: Vec<Value>,
     },
     MemoryModel {
         addressing_model: spirv::AddressingModel,
@@ -65,12 +67,12 @@ enum Instruction {
     },
     EntryPoint {
         execution_model: spirv::ExecutionModel,
-        entry_point: spirv::Word,
+        entry_point: Value,
         name: String,
-        interface: Vec<spirv::Word>,
+        interface: Vec<Value>,
     },
     ExecutionMode {
-        entry_point: spirv::Word,
+        entry_point: Value,
         mode: spirv::ExecutionMode,
     },
     Capability {
@@ -78,1386 +80,3143 @@ enum Instruction {
     },
     Function {
         function_control: spirv::FunctionControl,
-        function_type: spirv::Word,
+        function_type: Value,
     },
     FunctionParameter,
     FunctionEnd,
     FunctionCall {
-        function: spirv::Word,
-        arguments: Vec<spirv::Word>,
+        function: Value,
+        arguments: Vec<Value>,
     },
     Variable {
         storage_class: spirv::StorageClass,
-        initializer: Option<spirv::Word>,
+        initializer: Option<Value>,
     },
     ImageTexelPointer {
-        image: spirv::Word,
-        coordinate: spirv::Word,
-        sample: spirv::Word,
+        image: Value,
+        coordinate: Value,
+        sample: Value,
     },
     Load {
-        pointer: spirv::Word,
+        pointer: Value,
         memory_access: Option<spirv::MemoryAccess>,
     },
     Store {
-        pointer: spirv::Word,
-        object: spirv::Word,
+        pointer: Value,
+        object: Value,
         memory_access: Option<spirv::MemoryAccess>,
     },
     CopyMemory {
-        target: spirv::Word,
-        source: spirv::Word,
+        target: Value,
+        source: Value,
         memory_access: Option<spirv::MemoryAccess>,
     },
     CopyMemorySized {
-        target: spirv::Word,
-        source: spirv::Word,
-        size: spirv::Word,
+        target: Value,
+        source: Value,
+        size: Value,
         memory_access: Option<spirv::MemoryAccess>,
     },
     AccessChain {
-        base: spirv::Word,
-        indexes: Vec<spirv::Word>,
+        base: Value,
+        indexes: Vec<Value>,
     },
     InBoundsAccessChain {
-        base: spirv::Word,
-        indexes: Vec<spirv::Word>,
+        base: Value,
+        indexes: Vec<Value>,
     },
     PtrAccessChain {
-        base: spirv::Word,
-        element: spirv::Word,
-        indexes: Vec<spirv::Word>,
+        base: Value,
+        element: Value,
+        indexes: Vec<Value>,
     },
     ArrayLength {
-        structure: spirv::Word,
+        structure: Value,
         array_member: u32,
     },
     GenericPtrMemSemantics {
-        pointer: spirv::Word,
+        pointer: Value,
     },
     InBoundsPtrAccessChain {
-        base: spirv::Word,
-        element: spirv::Word,
-        indexes: Vec<spirv::Word>,
+        base: Value,
+        element: Value,
+        indexes: Vec<Value>,
     },
     Decorate {
-        target: spirv::Word,
+        target: Value,
         decoration: spirv::Decoration,
     },
     MemberDecorate {
-        structure_type: spirv::Word,
+        structure_type: Value,
         member: u32,
         decoration: spirv::Decoration,
     },
     DecorationGroup,
     GroupDecorate {
-        decoration_group: spirv::Word,
-        targets: Vec<spirv::Word>,
+        decoration_group: Value,
+        targets: Vec<Value>,
     },
     GroupMemberDecorate {
-        decoration_group: spirv::Word,
-        targets: Vec<(spirv::Word, u32)>,
+        decoration_group: Value,
+        targets: Vec<(Value, u32)>,
     },
     VectorExtractDynamic {
-        vector: spirv::Word,
-        index: spirv::Word,
+        vector: Value,
+        index: Value,
     },
     VectorInsertDynamic {
-        vector: spirv::Word,
-        component: spirv::Word,
-        index: spirv::Word,
+        vector: Value,
+        component: Value,
+        index: Value,
     },
     VectorShuffle {
-        vector_1: spirv::Word,
-        vector_2: spirv::Word,
+        vector_1: Value,
+        vector_2: Value,
         components: Vec<u32>,
     },
     CompositeConstruct {
-        constituents: Vec<spirv::Word>,
+        constituents: Vec<Value>,
     },
     CompositeExtract {
-        composite: spirv::Word,
+        composite: Value,
         indexes: Vec<u32>,
     },
     CompositeInsert {
-        object: spirv::Word,
-        composite: spirv::Word,
+        object: Value,
+        composite: Value,
         indexes: Vec<u32>,
     },
     CopyObject {
-        operand: spirv::Word,
+        operand: Value,
     },
     Transpose {
-        matrix: spirv::Word,
+        matrix: Value,
     },
     SampledImage {
-        image: spirv::Word,
-        sampler: spirv::Word,
+        image: Value,
+        sampler: Value,
     },
     ImageSampleImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSampleExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSampleDrefImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSampleDrefExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSampleProjImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSampleProjExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSampleProjDrefImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSampleProjDrefExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageFetch {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageGather {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        component: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        component: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageDrefGather {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageRead {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageWrite {
-        image: spirv::Word,
-        coordinate: spirv::Word,
-        texel: spirv::Word,
+        image: Value,
+        coordinate: Value,
+        texel: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     Image {
-        sampled_image: spirv::Word,
+        sampled_image: Value,
     },
     ImageQueryFormat {
-        image: spirv::Word,
+        image: Value,
     },
     ImageQueryOrder {
-        image: spirv::Word,
+        image: Value,
     },
     ImageQuerySizeLod {
-        image: spirv::Word,
-        level_of_detail: spirv::Word,
+        image: Value,
+        level_of_detail: Value,
     },
     ImageQuerySize {
-        image: spirv::Word,
+        image: Value,
     },
     ImageQueryLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
     },
     ImageQueryLevels {
-        image: spirv::Word,
+        image: Value,
     },
     ImageQuerySamples {
-        image: spirv::Word,
+        image: Value,
     },
     ConvertFToU {
-        float_value: spirv::Word,
+        float_value: Value,
     },
     ConvertFToS {
-        float_value: spirv::Word,
+        float_value: Value,
     },
     ConvertSToF {
-        signed_value: spirv::Word,
+        signed_value: Value,
     },
     ConvertUToF {
-        unsigned_value: spirv::Word,
+        unsigned_value: Value,
     },
     UConvert {
-        unsigned_value: spirv::Word,
+        unsigned_value: Value,
     },
     SConvert {
-        signed_value: spirv::Word,
+        signed_value: Value,
     },
     FConvert {
-        float_value: spirv::Word,
+        float_value: Value,
     },
     QuantizeToF16 {
-        value: spirv::Word,
+        value: Value,
     },
     ConvertPtrToU {
-        pointer: spirv::Word,
+        pointer: Value,
     },
     SatConvertSToU {
-        signed_value: spirv::Word,
+        signed_value: Value,
     },
     SatConvertUToS {
-        unsigned_value: spirv::Word,
+        unsigned_value: Value,
     },
     ConvertUToPtr {
-        integer_value: spirv::Word,
+        integer_value: Value,
     },
     PtrCastToGeneric {
-        pointer: spirv::Word,
+        pointer: Value,
     },
     GenericCastToPtr {
-        pointer: spirv::Word,
+        pointer: Value,
     },
     GenericCastToPtrExplicit {
-        pointer: spirv::Word,
+        pointer: Value,
         storage: spirv::StorageClass,
     },
     Bitcast {
-        operand: spirv::Word,
+        operand: Value,
     },
     SNegate {
-        operand: spirv::Word,
+        operand: Value,
     },
     FNegate {
-        operand: spirv::Word,
+        operand: Value,
     },
     IAdd {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FAdd {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     ISub {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FSub {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     IMul {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FMul {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     UDiv {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SDiv {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FDiv {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     UMod {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SRem {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SMod {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FRem {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FMod {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     VectorTimesScalar {
-        vector: spirv::Word,
-        scalar: spirv::Word,
+        vector: Value,
+        scalar: Value,
     },
     MatrixTimesScalar {
-        matrix: spirv::Word,
-        scalar: spirv::Word,
+        matrix: Value,
+        scalar: Value,
     },
     VectorTimesMatrix {
-        vector: spirv::Word,
-        matrix: spirv::Word,
+        vector: Value,
+        matrix: Value,
     },
     MatrixTimesVector {
-        matrix: spirv::Word,
-        vector: spirv::Word,
+        matrix: Value,
+        vector: Value,
     },
     MatrixTimesMatrix {
-        left_matrix: spirv::Word,
-        right_matrix: spirv::Word,
+        left_matrix: Value,
+        right_matrix: Value,
     },
     OuterProduct {
-        vector_1: spirv::Word,
-        vector_2: spirv::Word,
+        vector_1: Value,
+        vector_2: Value,
     },
     Dot {
-        vector_1: spirv::Word,
-        vector_2: spirv::Word,
+        vector_1: Value,
+        vector_2: Value,
     },
     IAddCarry {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     ISubBorrow {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     UMulExtended {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SMulExtended {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     Any {
-        vector: spirv::Word,
+        vector: Value,
     },
     All {
-        vector: spirv::Word,
+        vector: Value,
     },
     IsNan {
-        x: spirv::Word,
+        x: Value,
     },
     IsInf {
-        x: spirv::Word,
+        x: Value,
     },
     IsFinite {
-        x: spirv::Word,
+        x: Value,
     },
     IsNormal {
-        x: spirv::Word,
+        x: Value,
     },
     SignBitSet {
-        x: spirv::Word,
+        x: Value,
     },
     LessOrGreater {
-        x: spirv::Word,
-        y: spirv::Word,
+        x: Value,
+        y: Value,
     },
     Ordered {
-        x: spirv::Word,
-        y: spirv::Word,
+        x: Value,
+        y: Value,
     },
     Unordered {
-        x: spirv::Word,
-        y: spirv::Word,
+        x: Value,
+        y: Value,
     },
     LogicalEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     LogicalNotEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     LogicalOr {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     LogicalAnd {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     LogicalNot {
-        operand: spirv::Word,
+        operand: Value,
     },
     Select {
-        condition: spirv::Word,
-        object_1: spirv::Word,
-        object_2: spirv::Word,
+        condition: Value,
+        object_1: Value,
+        object_2: Value,
     },
     IEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     INotEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     UGreaterThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SGreaterThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     UGreaterThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SGreaterThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     ULessThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SLessThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     ULessThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     SLessThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdNotEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordNotEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdLessThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordLessThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdGreaterThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordGreaterThan {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdLessThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordLessThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FOrdGreaterThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     FUnordGreaterThanEqual {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     ShiftRightLogical {
-        base: spirv::Word,
-        shift: spirv::Word,
+        base: Value,
+        shift: Value,
     },
     ShiftRightArithmetic {
-        base: spirv::Word,
-        shift: spirv::Word,
+        base: Value,
+        shift: Value,
     },
     ShiftLeftLogical {
-        base: spirv::Word,
-        shift: spirv::Word,
+        base: Value,
+        shift: Value,
     },
     BitwiseOr {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     BitwiseXor {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     BitwiseAnd {
-        operand_1: spirv::Word,
-        operand_2: spirv::Word,
+        operand_1: Value,
+        operand_2: Value,
     },
     Not {
-        operand: spirv::Word,
+        operand: Value,
     },
     BitFieldInsert {
-        base: spirv::Word,
-        insert: spirv::Word,
-        offset: spirv::Word,
-        count: spirv::Word,
+        base: Value,
+        insert: Value,
+        offset: Value,
+        count: Value,
     },
     BitFieldSExtract {
-        base: spirv::Word,
-        offset: spirv::Word,
-        count: spirv::Word,
+        base: Value,
+        offset: Value,
+        count: Value,
     },
     BitFieldUExtract {
-        base: spirv::Word,
-        offset: spirv::Word,
-        count: spirv::Word,
+        base: Value,
+        offset: Value,
+        count: Value,
     },
     BitReverse {
-        base: spirv::Word,
+        base: Value,
     },
     BitCount {
-        base: spirv::Word,
+        base: Value,
     },
     DPdx {
-        p: spirv::Word,
+        p: Value,
     },
     DPdy {
-        p: spirv::Word,
+        p: Value,
     },
     Fwidth {
-        p: spirv::Word,
+        p: Value,
     },
     DPdxFine {
-        p: spirv::Word,
+        p: Value,
     },
     DPdyFine {
-        p: spirv::Word,
+        p: Value,
     },
     FwidthFine {
-        p: spirv::Word,
+        p: Value,
     },
     DPdxCoarse {
-        p: spirv::Word,
+        p: Value,
     },
     DPdyCoarse {
-        p: spirv::Word,
+        p: Value,
     },
     FwidthCoarse {
-        p: spirv::Word,
+        p: Value,
     },
     EmitVertex,
     EndPrimitive,
     EmitStreamVertex {
-        stream: spirv::Word,
+        stream: Value,
     },
     EndStreamPrimitive {
-        stream: spirv::Word,
+        stream: Value,
     },
     ControlBarrier {
-        execution: spirv::Word,
-        memory: spirv::Word,
-        semantics: spirv::Word,
+        execution: Value,
+        memory: Value,
+        semantics: Value,
     },
     MemoryBarrier {
-        memory: spirv::Word,
-        semantics: spirv::Word,
+        memory: Value,
+        semantics: Value,
     },
     AtomicLoad {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
     },
     AtomicStore {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicExchange {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicCompareExchange {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        equal: spirv::Word,
-        unequal: spirv::Word,
-        value: spirv::Word,
-        comparator: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        equal: Value,
+        unequal: Value,
+        value: Value,
+        comparator: Value,
     },
     AtomicCompareExchangeWeak {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        equal: spirv::Word,
-        unequal: spirv::Word,
-        value: spirv::Word,
-        comparator: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        equal: Value,
+        unequal: Value,
+        value: Value,
+        comparator: Value,
     },
     AtomicIIncrement {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
     },
     AtomicIDecrement {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
     },
     AtomicIAdd {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicISub {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicSMin {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicUMin {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicSMax {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicUMax {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicAnd {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicOr {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     AtomicXor {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
-        value: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
+        value: Value,
     },
     Phi {
-        value_label_pairs: Vec<(spirv::Word, spirv::Word)>,
+        value_label_pairs: Vec<(Value, Value)>,
     },
     LoopMerge {
-        merge_block: spirv::Word,
-        continue_target: spirv::Word,
+        merge_block: Value,
+        continue_target: Value,
         loop_control: spirv::LoopControl,
     },
     SelectionMerge {
-        merge_block: spirv::Word,
+        merge_block: Value,
         selection_control: spirv::SelectionControl,
     },
     Label,
-    Branch {
-        target_label: spirv::Word,
-    },
-    BranchConditional {
-        condition: spirv::Word,
-        true_label: spirv::Word,
-        false_label: spirv::Word,
-        branch_weights: Vec<u32>,
-    },
-    Switch {
-        selector: spirv::Word,
-        default: spirv::Word,
-        target: Vec<(u32, spirv::Word)>,
-    },
-    Kill,
-    Return,
-    ReturnValue {
-        value: spirv::Word,
-    },
-    Unreachable,
     LifetimeStart {
-        pointer: spirv::Word,
+        pointer: Value,
         size: u32,
     },
     LifetimeStop {
-        pointer: spirv::Word,
+        pointer: Value,
         size: u32,
     },
     GroupAsyncCopy {
-        execution: spirv::Word,
-        destination: spirv::Word,
-        source: spirv::Word,
-        num_elements: spirv::Word,
-        stride: spirv::Word,
-        event: spirv::Word,
+        execution: Value,
+        destination: Value,
+        source: Value,
+        num_elements: Value,
+        stride: Value,
+        event: Value,
     },
     GroupWaitEvents {
-        execution: spirv::Word,
-        num_events: spirv::Word,
-        events_list: spirv::Word,
+        execution: Value,
+        num_events: Value,
+        events_list: Value,
     },
     GroupAll {
-        execution: spirv::Word,
-        predicate: spirv::Word,
+        execution: Value,
+        predicate: Value,
     },
     GroupAny {
-        execution: spirv::Word,
-        predicate: spirv::Word,
+        execution: Value,
+        predicate: Value,
     },
     GroupBroadcast {
-        execution: spirv::Word,
-        value: spirv::Word,
-        local_id: spirv::Word,
+        execution: Value,
+        value: Value,
+        local_id: Value,
     },
     GroupIAdd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFAdd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupUMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupSMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupUMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupSMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     ReadPipe {
-        pipe: spirv::Word,
-        pointer: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        pointer: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     WritePipe {
-        pipe: spirv::Word,
-        pointer: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        pointer: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     ReservedReadPipe {
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        index: spirv::Word,
-        pointer: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        reserve_id: Value,
+        index: Value,
+        pointer: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     ReservedWritePipe {
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        index: spirv::Word,
-        pointer: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        reserve_id: Value,
+        index: Value,
+        pointer: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     ReserveReadPipePackets {
-        pipe: spirv::Word,
-        num_packets: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        num_packets: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     ReserveWritePipePackets {
-        pipe: spirv::Word,
-        num_packets: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        num_packets: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     CommitReadPipe {
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        reserve_id: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     CommitWritePipe {
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        reserve_id: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     IsValidReserveId {
-        reserve_id: spirv::Word,
+        reserve_id: Value,
     },
     GetNumPipePackets {
-        pipe: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     GetMaxPipePackets {
-        pipe: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        pipe: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     GroupReserveReadPipePackets {
-        execution: spirv::Word,
-        pipe: spirv::Word,
-        num_packets: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        execution: Value,
+        pipe: Value,
+        num_packets: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     GroupReserveWritePipePackets {
-        execution: spirv::Word,
-        pipe: spirv::Word,
-        num_packets: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        execution: Value,
+        pipe: Value,
+        num_packets: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     GroupCommitReadPipe {
-        execution: spirv::Word,
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        execution: Value,
+        pipe: Value,
+        reserve_id: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     GroupCommitWritePipe {
-        execution: spirv::Word,
-        pipe: spirv::Word,
-        reserve_id: spirv::Word,
-        packet_size: spirv::Word,
-        packet_alignment: spirv::Word,
+        execution: Value,
+        pipe: Value,
+        reserve_id: Value,
+        packet_size: Value,
+        packet_alignment: Value,
     },
     EnqueueMarker {
-        queue: spirv::Word,
-        num_events: spirv::Word,
-        wait_events: spirv::Word,
-        ret_event: spirv::Word,
+        queue: Value,
+        num_events: Value,
+        wait_events: Value,
+        ret_event: Value,
     },
     EnqueueKernel {
-        queue: spirv::Word,
-        flags: spirv::Word,
-        nd_range: spirv::Word,
-        num_events: spirv::Word,
-        wait_events: spirv::Word,
-        ret_event: spirv::Word,
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
-        local_size: Vec<spirv::Word>,
+        queue: Value,
+        flags: Value,
+        nd_range: Value,
+        num_events: Value,
+        wait_events: Value,
+        ret_event: Value,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
+        local_size: Vec<Value>,
     },
     GetKernelNDrangeSubGroupCount {
-        nd_range: spirv::Word,
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        nd_range: Value,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     GetKernelNDrangeMaxSubGroupSize {
-        nd_range: spirv::Word,
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        nd_range: Value,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     GetKernelWorkGroupSize {
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     GetKernelPreferredWorkGroupSizeMultiple {
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     RetainEvent {
-        event: spirv::Word,
+        event: Value,
     },
     ReleaseEvent {
-        event: spirv::Word,
+        event: Value,
     },
     CreateUserEvent,
     IsValidEvent {
-        event: spirv::Word,
+        event: Value,
     },
     SetUserEventStatus {
-        event: spirv::Word,
-        status: spirv::Word,
+        event: Value,
+        status: Value,
     },
     CaptureEventProfilingInfo {
-        event: spirv::Word,
-        profiling_info: spirv::Word,
-        value: spirv::Word,
+        event: Value,
+        profiling_info: Value,
+        value: Value,
     },
     GetDefaultQueue,
     BuildNDRange {
-        global_work_size: spirv::Word,
-        local_work_size: spirv::Word,
-        global_work_offset: spirv::Word,
+        global_work_size: Value,
+        local_work_size: Value,
+        global_work_offset: Value,
     },
     ImageSparseSampleImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseSampleExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSparseSampleDrefImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseSampleDrefExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSparseSampleProjImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseSampleProjExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSparseSampleProjDrefImplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseSampleProjDrefExplicitLod {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: spirv::ImageOperands,
     },
     ImageSparseFetch {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseGather {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        component: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        component: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseDrefGather {
-        sampled_image: spirv::Word,
-        coordinate: spirv::Word,
-        dref: spirv::Word,
+        sampled_image: Value,
+        coordinate: Value,
+        dref: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     ImageSparseTexelsResident {
-        resident_code: spirv::Word,
+        resident_code: Value,
     },
     NoLine,
     AtomicFlagTestAndSet {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
     },
     AtomicFlagClear {
-        pointer: spirv::Word,
-        scope: spirv::Word,
-        semantics: spirv::Word,
+        pointer: Value,
+        scope: Value,
+        semantics: Value,
     },
     ImageSparseRead {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
         image_operands: Option<spirv::ImageOperands>,
     },
     SizeOf {
-        pointer: spirv::Word,
+        pointer: Value,
     },
     CreatePipeFromPipeStorage {
-        pipe_storage: spirv::Word,
+        pipe_storage: Value,
     },
     GetKernelLocalSizeForSubgroupCount {
-        subgroup_count: spirv::Word,
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        subgroup_count: Value,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     GetKernelMaxNumSubgroups {
-        invoke: spirv::Word,
-        param: spirv::Word,
-        param_size: spirv::Word,
-        param_align: spirv::Word,
+        invoke: Value,
+        param: Value,
+        param_size: Value,
+        param_align: Value,
     },
     NamedBarrierInitialize {
-        subgroup_count: spirv::Word,
+        subgroup_count: Value,
     },
     MemoryNamedBarrier {
-        named_barrier: spirv::Word,
-        memory: spirv::Word,
-        semantics: spirv::Word,
+        named_barrier: Value,
+        memory: Value,
+        semantics: Value,
     },
     ModuleProcessed {
         process: String,
     },
     ExecutionModeId {
-        entry_point: spirv::Word,
+        entry_point: Value,
         mode: spirv::ExecutionMode,
     },
     DecorateId {
-        target: spirv::Word,
+        target: Value,
         decoration: spirv::Decoration,
     },
     GroupNonUniformElect {
-        execution: spirv::Word,
+        execution: Value,
     },
     GroupNonUniformAll {
-        execution: spirv::Word,
-        predicate: spirv::Word,
+        execution: Value,
+        predicate: Value,
     },
     GroupNonUniformAny {
-        execution: spirv::Word,
-        predicate: spirv::Word,
+        execution: Value,
+        predicate: Value,
     },
     GroupNonUniformAllEqual {
-        execution: spirv::Word,
-        value: spirv::Word,
+        execution: Value,
+        value: Value,
     },
     GroupNonUniformBroadcast {
-        execution: spirv::Word,
-        value: spirv::Word,
-        id: spirv::Word,
+        execution: Value,
+        value: Value,
+        id: Value,
     },
     GroupNonUniformBroadcastFirst {
-        execution: spirv::Word,
-        value: spirv::Word,
+        execution: Value,
+        value: Value,
     },
     GroupNonUniformBallot {
-        execution: spirv::Word,
-        predicate: spirv::Word,
+        execution: Value,
+        predicate: Value,
     },
     GroupNonUniformInverseBallot {
-        execution: spirv::Word,
-        value: spirv::Word,
+        execution: Value,
+        value: Value,
     },
     GroupNonUniformBallotBitExtract {
-        execution: spirv::Word,
-        value: spirv::Word,
-        index: spirv::Word,
+        execution: Value,
+        value: Value,
+        index: Value,
     },
     GroupNonUniformBallotBitCount {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
+        value: Value,
     },
     GroupNonUniformBallotFindLSB {
-        execution: spirv::Word,
-        value: spirv::Word,
+        execution: Value,
+        value: Value,
     },
     GroupNonUniformBallotFindMSB {
-        execution: spirv::Word,
-        value: spirv::Word,
+        execution: Value,
+        value: Value,
     },
     GroupNonUniformShuffle {
-        execution: spirv::Word,
-        value: spirv::Word,
-        id: spirv::Word,
+        execution: Value,
+        value: Value,
+        id: Value,
     },
     GroupNonUniformShuffleXor {
-        execution: spirv::Word,
-        value: spirv::Word,
-        mask: spirv::Word,
+        execution: Value,
+        value: Value,
+        mask: Value,
     },
     GroupNonUniformShuffleUp {
-        execution: spirv::Word,
-        value: spirv::Word,
-        delta: spirv::Word,
+        execution: Value,
+        value: Value,
+        delta: Value,
     },
     GroupNonUniformShuffleDown {
-        execution: spirv::Word,
-        value: spirv::Word,
-        delta: spirv::Word,
+        execution: Value,
+        value: Value,
+        delta: Value,
     },
     GroupNonUniformIAdd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformFAdd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformIMul {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformFMul {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformSMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformUMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformFMin {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformSMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformUMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformFMax {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformBitwiseAnd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformBitwiseOr {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformBitwiseXor {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformLogicalAnd {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformLogicalOr {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformLogicalXor {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        value: spirv::Word,
-        cluster_size: Option<spirv::Word>,
+        value: Value,
+        cluster_size: Option<Value>,
     },
     GroupNonUniformQuadBroadcast {
-        execution: spirv::Word,
-        value: spirv::Word,
-        index: spirv::Word,
+        execution: Value,
+        value: Value,
+        index: Value,
     },
     GroupNonUniformQuadSwap {
-        execution: spirv::Word,
-        value: spirv::Word,
-        direction: spirv::Word,
+        execution: Value,
+        value: Value,
+        direction: Value,
     },
     SubgroupBallotKHR {
-        predicate: spirv::Word,
+        predicate: Value,
     },
     SubgroupFirstInvocationKHR {
-        value: spirv::Word,
+        value: Value,
     },
     SubgroupAllKHR {
-        predicate: spirv::Word,
+        predicate: Value,
     },
     SubgroupAnyKHR {
-        predicate: spirv::Word,
+        predicate: Value,
     },
     SubgroupAllEqualKHR {
-        predicate: spirv::Word,
+        predicate: Value,
     },
     SubgroupReadInvocationKHR {
-        value: spirv::Word,
-        index: spirv::Word,
+        value: Value,
+        index: Value,
+    },
+    TraceRayKHR {
+        accel: Value,
+        ray_flags: Value,
+        cull_mask: Value,
+        sbt_offset: Value,
+        sbt_stride: Value,
+        miss_index: Value,
+        ray_origin: Value,
+        ray_tmin: Value,
+        ray_direction: Value,
+        ray_tmax: Value,
+        payload: Value,
+    },
+    ExecuteCallableKHR {
+        sbt_index: Value,
+        callable_data: Value,
+    },
+    ConvertUToAccelerationStructureKHR {
+        accel: Value,
+    },
+    CooperativeMatrixLoadKHR {
+        pointer: Value,
+        memory_layout: Value,
+        stride: Option<Value>,
+        memory_operand: Option<spirv::MemoryAccess>,
+    },
+    CooperativeMatrixStoreKHR {
+        pointer: Value,
+        object: Value,
+        memory_layout: Value,
+        stride: Option<Value>,
+        memory_operand: Option<spirv::MemoryAccess>,
+    },
+    CooperativeMatrixMulAddKHR {
+        a: Value,
+        b: Value,
+        c: Value,
+        cooperative_matrix_operands: Option<spirv::CooperativeMatrixOperands>,
+    },
+    CooperativeMatrixLengthKHR {
+        type_: Value,
+    },
+    RayQueryInitializeKHR {
+        ray_query: Value,
+        accel: Value,
+        ray_flags: Value,
+        cull_mask: Value,
+        ray_origin: Value,
+        ray_tmin: Value,
+        ray_direction: Value,
+        ray_tmax: Value,
+    },
+    RayQueryTerminateKHR {
+        ray_query: Value,
+    },
+    RayQueryGenerateIntersectionKHR {
+        ray_query: Value,
+        hit_t: Value,
+    },
+    RayQueryConfirmIntersectionKHR {
+        ray_query: Value,
+    },
+    RayQueryProceedKHR {
+        ray_query: Value,
+    },
+    RayQueryGetIntersectionTypeKHR {
+        ray_query: Value,
+        intersection: Value,
     },
     GroupIAddNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFAddNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFMinNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupUMinNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupSMinNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupFMaxNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupUMaxNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     GroupSMaxNonUniformAMD {
-        execution: spirv::Word,
+        execution: Value,
         operation: spirv::GroupOperation,
-        x: spirv::Word,
+        x: Value,
     },
     FragmentMaskFetchAMD {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
     },
     FragmentFetchAMD {
-        image: spirv::Word,
-        coordinate: spirv::Word,
-        fragment_index: spirv::Word,
+        image: Value,
+        coordinate: Value,
+        fragment_index: Value,
+    },
+    SetMeshOutputsEXT {
+        vertex_count: Value,
+        primitive_count: Value,
     },
     SubgroupShuffleINTEL {
-        data: spirv::Word,
-        invocation_id: spirv::Word,
+        data: Value,
+        invocation_id: Value,
     },
     SubgroupShuffleDownINTEL {
-        current: spirv::Word,
-        next: spirv::Word,
-        delta: spirv::Word,
+        current: Value,
+        next: Value,
+        delta: Value,
     },
     SubgroupShuffleUpINTEL {
-        previous: spirv::Word,
-        current: spirv::Word,
-        delta: spirv::Word,
+        previous: Value,
+        current: Value,
+        delta: Value,
     },
     SubgroupShuffleXorINTEL {
-        data: spirv::Word,
-        value: spirv::Word,
+        data: Value,
+        value: Value,
     },
     SubgroupBlockReadINTEL {
-        ptr: spirv::Word,
+        ptr: Value,
     },
     SubgroupBlockWriteINTEL {
-        ptr: spirv::Word,
-        data: spirv::Word,
+        ptr: Value,
+        data: Value,
     },
     SubgroupImageBlockReadINTEL {
-        image: spirv::Word,
-        coordinate: spirv::Word,
+        image: Value,
+        coordinate: Value,
     },
     SubgroupImageBlockWriteINTEL {
-        image: spirv::Word,
-        coordinate: spirv::Word,
-        data: spirv::Word,
+        image: Value,
+        coordinate: Value,
+        data: Value,
     },
     DecorateStringGOOGLE {
-        target: spirv::Word,
+        target: Value,
         decoration: spirv::Decoration,
     },
     MemberDecorateStringGOOGLE {
-        struct_type: spirv::Word,
+        struct_type: Value,
         member: u32,
         decoration: spirv::Decoration,
     },
     GroupNonUniformPartitionNV {
-        value: spirv::Word,
+        value: Value,
+    },
+    RayQueryGetRayTMinKHR {
+        ray_query: Value,
+    },
+    RayQueryGetRayFlagsKHR {
+        ray_query: Value,
+    },
+    RayQueryGetIntersectionTKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionInstanceCustomIndexKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionInstanceIdKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionGeometryIndexKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionPrimitiveIndexKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionBarycentricsKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionFrontFaceKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionCandidateAABBOpaqueKHR {
+        ray_query: Value,
+    },
+    RayQueryGetIntersectionObjectRayDirectionKHR {
+        ray_query: Value,
+        intersection: Value,
     },
+    RayQueryGetIntersectionObjectRayOriginKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetWorldRayDirectionKHR {
+        ray_query: Value,
+    },
+    RayQueryGetWorldRayOriginKHR {
+        ray_query: Value,
+    },
+    RayQueryGetIntersectionObjectToWorldKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+    RayQueryGetIntersectionWorldToObjectKHR {
+        ray_query: Value,
+        intersection: Value,
+    },
+}
+
+/// SPIR-V instructions ending a basic block.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Terminator {
+    Branch {
+        target_label: Token<BasicBlock>,
+    },
+    BranchConditional {
+        condition: Value,
+        true_label: Token<BasicBlock>,
+        false_label: Token<BasicBlock>,
+        branch_weights: Vec<u32>,
+    },
+    Switch {
+        selector: Value,
+        default: Token<BasicBlock>,
+        target: Vec<(u64, Token<BasicBlock>)>,
+    },
+    Kill,
+    Return,
+    ReturnValue {
+        value: Value,
+    },
+    Unreachable,
+    IgnoreIntersectionKHR,
+    TerminateRayKHR,
+    EmitMeshTasksEXT {
+        group_count_x: Value,
+        group_count_y: Value,
+        group_count_z: Value,
+        payload: Option<Value>,
+    },
+}
+
+impl Instruction {
+    /// Lifts the operands read by `r` into the variant for `opcode`.
+    ///
+    /// Returns `Ok(None)` if there is no variant for `opcode`.
+    pub(in sr) fn lift(
+        opcode: spirv::Op,
+        r: &mut OperandReader,
+    ) -> LiftResult<Option<Instruction>> {
+        Ok(Some(match opcode {
+            spirv::Op::Nop => Instruction::Nop,
+            spirv::Op::Undef => Instruction::Undef,
+            spirv::Op::SourceContinued => Instruction::SourceContinued {
+                continued_source: r.required(string)?,
+            },
+            spirv::Op::Source => Instruction::Source {
+                source_language: r.required(kind!(SourceLanguage))?,
+                version: r.required(literal)?,
+                file: r.optional_value()?,
+                source: r.optional(string)?,
+            },
+            spirv::Op::SourceExtension => Instruction::SourceExtension {
+                extension: r.required(string)?,
+            },
+            spirv::Op::Name => Instruction::Name {
+                target: r.value()?,
+                name: r.required(string)?,
+            },
+            spirv::Op::MemberName => Instruction::MemberName {
+                target_type: r.value()?,
+                member: r.required(literal)?,
+                name: r.required(string)?,
+            },
+            spirv::Op::String => Instruction::String {
+                string: r.required(string)?,
+            },
+            spirv::Op::Line => Instruction::Line {
+                file: r.value()?,
+                line: r.required(literal)?,
+                column: r.required(literal)?,
+            },
+            spirv::Op::Extension => Instruction::Extension {
+                name: r.required(string)?,
+            },
+            spirv::Op::ExtInstImport => Instruction::ExtInstImport {
+                name: r.required(string)?,
+            },
+            spirv::Op::ExtInst => Instruction::ExtInst {
+                set: r.value()?,
+                instruction: r.required(ext_inst_integer)?,
+                operands: r.values()?,
+            },
+            spirv::Op::MemoryModel => Instruction::MemoryModel {
+                addressing_model: r.required(kind!(AddressingModel))?,
+                memory_model: r.required(kind!(MemoryModel))?,
+            },
+            spirv::Op::EntryPoint => Instruction::EntryPoint {
+                execution_model: r.required(kind!(ExecutionModel))?,
+                entry_point: r.value()?,
+                name: r.required(string)?,
+                interface: r.values()?,
+            },
+            spirv::Op::ExecutionMode => Instruction::ExecutionMode {
+                entry_point: r.value()?,
+                mode: r.required(kind!(ExecutionMode))?,
+            },
+            spirv::Op::Capability => Instruction::Capability {
+                capability: r.required(kind!(Capability))?,
+            },
+            spirv::Op::Function => Instruction::Function {
+                function_control: r.required(kind!(FunctionControl))?,
+                function_type: r.value()?,
+            },
+            spirv::Op::FunctionParameter => Instruction::FunctionParameter,
+            spirv::Op::FunctionEnd => Instruction::FunctionEnd,
+            spirv::Op::FunctionCall => Instruction::FunctionCall {
+                function: r.value()?,
+                arguments: r.values()?,
+            },
+            spirv::Op::Variable => Instruction::Variable {
+                storage_class: r.required(kind!(StorageClass))?,
+                initializer: r.optional_value()?,
+            },
+            spirv::Op::ImageTexelPointer => Instruction::ImageTexelPointer {
+                image: r.value()?,
+                coordinate: r.value()?,
+                sample: r.value()?,
+            },
+            spirv::Op::Load => Instruction::Load {
+                pointer: r.value()?,
+                memory_access: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::Store => Instruction::Store {
+                pointer: r.value()?,
+                object: r.value()?,
+                memory_access: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::CopyMemory => Instruction::CopyMemory {
+                target: r.value()?,
+                source: r.value()?,
+                memory_access: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::CopyMemorySized => Instruction::CopyMemorySized {
+                target: r.value()?,
+                source: r.value()?,
+                size: r.value()?,
+                memory_access: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::AccessChain => Instruction::AccessChain {
+                base: r.value()?,
+                indexes: r.values()?,
+            },
+            spirv::Op::InBoundsAccessChain => Instruction::InBoundsAccessChain {
+                base: r.value()?,
+                indexes: r.values()?,
+            },
+            spirv::Op::PtrAccessChain => Instruction::PtrAccessChain {
+                base: r.value()?,
+                element: r.value()?,
+                indexes: r.values()?,
+            },
+            spirv::Op::ArrayLength => Instruction::ArrayLength {
+                structure: r.value()?,
+                array_member: r.required(literal)?,
+            },
+            spirv::Op::GenericPtrMemSemantics => Instruction::GenericPtrMemSemantics {
+                pointer: r.value()?,
+            },
+            spirv::Op::InBoundsPtrAccessChain => Instruction::InBoundsPtrAccessChain {
+                base: r.value()?,
+                element: r.value()?,
+                indexes: r.values()?,
+            },
+            spirv::Op::Decorate => Instruction::Decorate {
+                target: r.value()?,
+                decoration: r.required(kind!(Decoration))?,
+            },
+            spirv::Op::MemberDecorate => Instruction::MemberDecorate {
+                structure_type: r.value()?,
+                member: r.required(literal)?,
+                decoration: r.required(kind!(Decoration))?,
+            },
+            spirv::Op::DecorationGroup => Instruction::DecorationGroup,
+            spirv::Op::GroupDecorate => Instruction::GroupDecorate {
+                decoration_group: r.value()?,
+                targets: r.values()?,
+            },
+            spirv::Op::GroupMemberDecorate => Instruction::GroupMemberDecorate {
+                decoration_group: r.value()?,
+                targets: r.value_literal_pairs()?,
+            },
+            spirv::Op::VectorExtractDynamic => Instruction::VectorExtractDynamic {
+                vector: r.value()?,
+                index: r.value()?,
+            },
+            spirv::Op::VectorInsertDynamic => Instruction::VectorInsertDynamic {
+                vector: r.value()?,
+                component: r.value()?,
+                index: r.value()?,
+            },
+            spirv::Op::VectorShuffle => Instruction::VectorShuffle {
+                vector_1: r.value()?,
+                vector_2: r.value()?,
+                components: r.repeated(literal)?,
+            },
+            spirv::Op::CompositeConstruct => Instruction::CompositeConstruct {
+                constituents: r.values()?,
+            },
+            spirv::Op::CompositeExtract => Instruction::CompositeExtract {
+                composite: r.value()?,
+                indexes: r.repeated(literal)?,
+            },
+            spirv::Op::CompositeInsert => Instruction::CompositeInsert {
+                object: r.value()?,
+                composite: r.value()?,
+                indexes: r.repeated(literal)?,
+            },
+            spirv::Op::CopyObject => Instruction::CopyObject {
+                operand: r.value()?,
+            },
+            spirv::Op::Transpose => Instruction::Transpose { matrix: r.value()? },
+            spirv::Op::SampledImage => Instruction::SampledImage {
+                image: r.value()?,
+                sampler: r.value()?,
+            },
+            spirv::Op::ImageSampleImplicitLod => Instruction::ImageSampleImplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleExplicitLod => Instruction::ImageSampleExplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.required(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleDrefImplicitLod => Instruction::ImageSampleDrefImplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                dref: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleDrefExplicitLod => Instruction::ImageSampleDrefExplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                dref: r.value()?,
+                image_operands: r.required(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleProjImplicitLod => Instruction::ImageSampleProjImplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleProjExplicitLod => Instruction::ImageSampleProjExplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.required(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSampleProjDrefImplicitLod => {
+                Instruction::ImageSampleProjDrefImplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.optional(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSampleProjDrefExplicitLod => {
+                Instruction::ImageSampleProjDrefExplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.required(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageFetch => Instruction::ImageFetch {
+                image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageGather => Instruction::ImageGather {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                component: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageDrefGather => Instruction::ImageDrefGather {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                dref: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageRead => Instruction::ImageRead {
+                image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageWrite => Instruction::ImageWrite {
+                image: r.value()?,
+                coordinate: r.value()?,
+                texel: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::Image => Instruction::Image {
+                sampled_image: r.value()?,
+            },
+            spirv::Op::ImageQueryFormat => Instruction::ImageQueryFormat { image: r.value()? },
+            spirv::Op::ImageQueryOrder => Instruction::ImageQueryOrder { image: r.value()? },
+            spirv::Op::ImageQuerySizeLod => Instruction::ImageQuerySizeLod {
+                image: r.value()?,
+                level_of_detail: r.value()?,
+            },
+            spirv::Op::ImageQuerySize => Instruction::ImageQuerySize { image: r.value()? },
+            spirv::Op::ImageQueryLod => Instruction::ImageQueryLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+            },
+            spirv::Op::ImageQueryLevels => Instruction::ImageQueryLevels { image: r.value()? },
+            spirv::Op::ImageQuerySamples => Instruction::ImageQuerySamples { image: r.value()? },
+            spirv::Op::ConvertFToU => Instruction::ConvertFToU {
+                float_value: r.value()?,
+            },
+            spirv::Op::ConvertFToS => Instruction::ConvertFToS {
+                float_value: r.value()?,
+            },
+            spirv::Op::ConvertSToF => Instruction::ConvertSToF {
+                signed_value: r.value()?,
+            },
+            spirv::Op::ConvertUToF => Instruction::ConvertUToF {
+                unsigned_value: r.value()?,
+            },
+            spirv::Op::UConvert => Instruction::UConvert {
+                unsigned_value: r.value()?,
+            },
+            spirv::Op::SConvert => Instruction::SConvert {
+                signed_value: r.value()?,
+            },
+            spirv::Op::FConvert => Instruction::FConvert {
+                float_value: r.value()?,
+            },
+            spirv::Op::QuantizeToF16 => Instruction::QuantizeToF16 { value: r.value()? },
+            spirv::Op::ConvertPtrToU => Instruction::ConvertPtrToU {
+                pointer: r.value()?,
+            },
+            spirv::Op::SatConvertSToU => Instruction::SatConvertSToU {
+                signed_value: r.value()?,
+            },
+            spirv::Op::SatConvertUToS => Instruction::SatConvertUToS {
+                unsigned_value: r.value()?,
+            },
+            spirv::Op::ConvertUToPtr => Instruction::ConvertUToPtr {
+                integer_value: r.value()?,
+            },
+            spirv::Op::PtrCastToGeneric => Instruction::PtrCastToGeneric {
+                pointer: r.value()?,
+            },
+            spirv::Op::GenericCastToPtr => Instruction::GenericCastToPtr {
+                pointer: r.value()?,
+            },
+            spirv::Op::GenericCastToPtrExplicit => Instruction::GenericCastToPtrExplicit {
+                pointer: r.value()?,
+                storage: r.required(kind!(StorageClass))?,
+            },
+            spirv::Op::Bitcast => Instruction::Bitcast {
+                operand: r.value()?,
+            },
+            spirv::Op::SNegate => Instruction::SNegate {
+                operand: r.value()?,
+            },
+            spirv::Op::FNegate => Instruction::FNegate {
+                operand: r.value()?,
+            },
+            spirv::Op::IAdd => Instruction::IAdd {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FAdd => Instruction::FAdd {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::ISub => Instruction::ISub {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FSub => Instruction::FSub {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::IMul => Instruction::IMul {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FMul => Instruction::FMul {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::UDiv => Instruction::UDiv {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SDiv => Instruction::SDiv {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FDiv => Instruction::FDiv {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::UMod => Instruction::UMod {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SRem => Instruction::SRem {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SMod => Instruction::SMod {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FRem => Instruction::FRem {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FMod => Instruction::FMod {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::VectorTimesScalar => Instruction::VectorTimesScalar {
+                vector: r.value()?,
+                scalar: r.value()?,
+            },
+            spirv::Op::MatrixTimesScalar => Instruction::MatrixTimesScalar {
+                matrix: r.value()?,
+                scalar: r.value()?,
+            },
+            spirv::Op::VectorTimesMatrix => Instruction::VectorTimesMatrix {
+                vector: r.value()?,
+                matrix: r.value()?,
+            },
+            spirv::Op::MatrixTimesVector => Instruction::MatrixTimesVector {
+                matrix: r.value()?,
+                vector: r.value()?,
+            },
+            spirv::Op::MatrixTimesMatrix => Instruction::MatrixTimesMatrix {
+                left_matrix: r.value()?,
+                right_matrix: r.value()?,
+            },
+            spirv::Op::OuterProduct => Instruction::OuterProduct {
+                vector_1: r.value()?,
+                vector_2: r.value()?,
+            },
+            spirv::Op::Dot => Instruction::Dot {
+                vector_1: r.value()?,
+                vector_2: r.value()?,
+            },
+            spirv::Op::IAddCarry => Instruction::IAddCarry {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::ISubBorrow => Instruction::ISubBorrow {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::UMulExtended => Instruction::UMulExtended {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SMulExtended => Instruction::SMulExtended {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::Any => Instruction::Any { vector: r.value()? },
+            spirv::Op::All => Instruction::All { vector: r.value()? },
+            spirv::Op::IsNan => Instruction::IsNan { x: r.value()? },
+            spirv::Op::IsInf => Instruction::IsInf { x: r.value()? },
+            spirv::Op::IsFinite => Instruction::IsFinite { x: r.value()? },
+            spirv::Op::IsNormal => Instruction::IsNormal { x: r.value()? },
+            spirv::Op::SignBitSet => Instruction::SignBitSet { x: r.value()? },
+            spirv::Op::LessOrGreater => Instruction::LessOrGreater {
+                x: r.value()?,
+                y: r.value()?,
+            },
+            spirv::Op::Ordered => Instruction::Ordered {
+                x: r.value()?,
+                y: r.value()?,
+            },
+            spirv::Op::Unordered => Instruction::Unordered {
+                x: r.value()?,
+                y: r.value()?,
+            },
+            spirv::Op::LogicalEqual => Instruction::LogicalEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::LogicalNotEqual => Instruction::LogicalNotEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::LogicalOr => Instruction::LogicalOr {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::LogicalAnd => Instruction::LogicalAnd {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::LogicalNot => Instruction::LogicalNot {
+                operand: r.value()?,
+            },
+            spirv::Op::Select => Instruction::Select {
+                condition: r.value()?,
+                object_1: r.value()?,
+                object_2: r.value()?,
+            },
+            spirv::Op::IEqual => Instruction::IEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::INotEqual => Instruction::INotEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::UGreaterThan => Instruction::UGreaterThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SGreaterThan => Instruction::SGreaterThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::UGreaterThanEqual => Instruction::UGreaterThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SGreaterThanEqual => Instruction::SGreaterThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::ULessThan => Instruction::ULessThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SLessThan => Instruction::SLessThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::ULessThanEqual => Instruction::ULessThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::SLessThanEqual => Instruction::SLessThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdEqual => Instruction::FOrdEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordEqual => Instruction::FUnordEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdNotEqual => Instruction::FOrdNotEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordNotEqual => Instruction::FUnordNotEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdLessThan => Instruction::FOrdLessThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordLessThan => Instruction::FUnordLessThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdGreaterThan => Instruction::FOrdGreaterThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordGreaterThan => Instruction::FUnordGreaterThan {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdLessThanEqual => Instruction::FOrdLessThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordLessThanEqual => Instruction::FUnordLessThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FOrdGreaterThanEqual => Instruction::FOrdGreaterThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::FUnordGreaterThanEqual => Instruction::FUnordGreaterThanEqual {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::ShiftRightLogical => Instruction::ShiftRightLogical {
+                base: r.value()?,
+                shift: r.value()?,
+            },
+            spirv::Op::ShiftRightArithmetic => Instruction::ShiftRightArithmetic {
+                base: r.value()?,
+                shift: r.value()?,
+            },
+            spirv::Op::ShiftLeftLogical => Instruction::ShiftLeftLogical {
+                base: r.value()?,
+                shift: r.value()?,
+            },
+            spirv::Op::BitwiseOr => Instruction::BitwiseOr {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::BitwiseXor => Instruction::BitwiseXor {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::BitwiseAnd => Instruction::BitwiseAnd {
+                operand_1: r.value()?,
+                operand_2: r.value()?,
+            },
+            spirv::Op::Not => Instruction::Not {
+                operand: r.value()?,
+            },
+            spirv::Op::BitFieldInsert => Instruction::BitFieldInsert {
+                base: r.value()?,
+                insert: r.value()?,
+                offset: r.value()?,
+                count: r.value()?,
+            },
+            spirv::Op::BitFieldSExtract => Instruction::BitFieldSExtract {
+                base: r.value()?,
+                offset: r.value()?,
+                count: r.value()?,
+            },
+            spirv::Op::BitFieldUExtract => Instruction::BitFieldUExtract {
+                base: r.value()?,
+                offset: r.value()?,
+                count: r.value()?,
+            },
+            spirv::Op::BitReverse => Instruction::BitReverse { base: r.value()? },
+            spirv::Op::BitCount => Instruction::BitCount { base: r.value()? },
+            spirv::Op::DPdx => Instruction::DPdx { p: r.value()? },
+            spirv::Op::DPdy => Instruction::DPdy { p: r.value()? },
+            spirv::Op::Fwidth => Instruction::Fwidth { p: r.value()? },
+            spirv::Op::DPdxFine => Instruction::DPdxFine { p: r.value()? },
+            spirv::Op::DPdyFine => Instruction::DPdyFine { p: r.value()? },
+            spirv::Op::FwidthFine => Instruction::FwidthFine { p: r.value()? },
+            spirv::Op::DPdxCoarse => Instruction::DPdxCoarse { p: r.value()? },
+            spirv::Op::DPdyCoarse => Instruction::DPdyCoarse { p: r.value()? },
+            spirv::Op::FwidthCoarse => Instruction::FwidthCoarse { p: r.value()? },
+            spirv::Op::EmitVertex => Instruction::EmitVertex,
+            spirv::Op::EndPrimitive => Instruction::EndPrimitive,
+            spirv::Op::EmitStreamVertex => Instruction::EmitStreamVertex { stream: r.value()? },
+            spirv::Op::EndStreamPrimitive => Instruction::EndStreamPrimitive { stream: r.value()? },
+            spirv::Op::ControlBarrier => Instruction::ControlBarrier {
+                execution: r.value()?,
+                memory: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::MemoryBarrier => Instruction::MemoryBarrier {
+                memory: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::AtomicLoad => Instruction::AtomicLoad {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::AtomicStore => Instruction::AtomicStore {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicExchange => Instruction::AtomicExchange {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicCompareExchange => Instruction::AtomicCompareExchange {
+                pointer: r.value()?,
+                scope: r.value()?,
+                equal: r.value()?,
+                unequal: r.value()?,
+                value: r.value()?,
+                comparator: r.value()?,
+            },
+            spirv::Op::AtomicCompareExchangeWeak => Instruction::AtomicCompareExchangeWeak {
+                pointer: r.value()?,
+                scope: r.value()?,
+                equal: r.value()?,
+                unequal: r.value()?,
+                value: r.value()?,
+                comparator: r.value()?,
+            },
+            spirv::Op::AtomicIIncrement => Instruction::AtomicIIncrement {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::AtomicIDecrement => Instruction::AtomicIDecrement {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::AtomicIAdd => Instruction::AtomicIAdd {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicISub => Instruction::AtomicISub {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicSMin => Instruction::AtomicSMin {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicUMin => Instruction::AtomicUMin {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicSMax => Instruction::AtomicSMax {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicUMax => Instruction::AtomicUMax {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicAnd => Instruction::AtomicAnd {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicOr => Instruction::AtomicOr {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::AtomicXor => Instruction::AtomicXor {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::Phi => Instruction::Phi {
+                value_label_pairs: r.value_pairs()?,
+            },
+            spirv::Op::LoopMerge => Instruction::LoopMerge {
+                merge_block: r.value()?,
+                continue_target: r.value()?,
+                loop_control: r.required(kind!(LoopControl))?,
+            },
+            spirv::Op::SelectionMerge => Instruction::SelectionMerge {
+                merge_block: r.value()?,
+                selection_control: r.required(kind!(SelectionControl))?,
+            },
+            spirv::Op::Label => Instruction::Label,
+            spirv::Op::LifetimeStart => Instruction::LifetimeStart {
+                pointer: r.value()?,
+                size: r.required(literal)?,
+            },
+            spirv::Op::LifetimeStop => Instruction::LifetimeStop {
+                pointer: r.value()?,
+                size: r.required(literal)?,
+            },
+            spirv::Op::GroupAsyncCopy => Instruction::GroupAsyncCopy {
+                execution: r.value()?,
+                destination: r.value()?,
+                source: r.value()?,
+                num_elements: r.value()?,
+                stride: r.value()?,
+                event: r.value()?,
+            },
+            spirv::Op::GroupWaitEvents => Instruction::GroupWaitEvents {
+                execution: r.value()?,
+                num_events: r.value()?,
+                events_list: r.value()?,
+            },
+            spirv::Op::GroupAll => Instruction::GroupAll {
+                execution: r.value()?,
+                predicate: r.value()?,
+            },
+            spirv::Op::GroupAny => Instruction::GroupAny {
+                execution: r.value()?,
+                predicate: r.value()?,
+            },
+            spirv::Op::GroupBroadcast => Instruction::GroupBroadcast {
+                execution: r.value()?,
+                value: r.value()?,
+                local_id: r.value()?,
+            },
+            spirv::Op::GroupIAdd => Instruction::GroupIAdd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFAdd => Instruction::GroupFAdd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFMin => Instruction::GroupFMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupUMin => Instruction::GroupUMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupSMin => Instruction::GroupSMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFMax => Instruction::GroupFMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupUMax => Instruction::GroupUMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupSMax => Instruction::GroupSMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::ReadPipe => Instruction::ReadPipe {
+                pipe: r.value()?,
+                pointer: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::WritePipe => Instruction::WritePipe {
+                pipe: r.value()?,
+                pointer: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::ReservedReadPipe => Instruction::ReservedReadPipe {
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                index: r.value()?,
+                pointer: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::ReservedWritePipe => Instruction::ReservedWritePipe {
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                index: r.value()?,
+                pointer: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::ReserveReadPipePackets => Instruction::ReserveReadPipePackets {
+                pipe: r.value()?,
+                num_packets: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::ReserveWritePipePackets => Instruction::ReserveWritePipePackets {
+                pipe: r.value()?,
+                num_packets: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::CommitReadPipe => Instruction::CommitReadPipe {
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::CommitWritePipe => Instruction::CommitWritePipe {
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::IsValidReserveId => Instruction::IsValidReserveId {
+                reserve_id: r.value()?,
+            },
+            spirv::Op::GetNumPipePackets => Instruction::GetNumPipePackets {
+                pipe: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::GetMaxPipePackets => Instruction::GetMaxPipePackets {
+                pipe: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::GroupReserveReadPipePackets => Instruction::GroupReserveReadPipePackets {
+                execution: r.value()?,
+                pipe: r.value()?,
+                num_packets: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::GroupReserveWritePipePackets => Instruction::GroupReserveWritePipePackets {
+                execution: r.value()?,
+                pipe: r.value()?,
+                num_packets: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::GroupCommitReadPipe => Instruction::GroupCommitReadPipe {
+                execution: r.value()?,
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::GroupCommitWritePipe => Instruction::GroupCommitWritePipe {
+                execution: r.value()?,
+                pipe: r.value()?,
+                reserve_id: r.value()?,
+                packet_size: r.value()?,
+                packet_alignment: r.value()?,
+            },
+            spirv::Op::EnqueueMarker => Instruction::EnqueueMarker {
+                queue: r.value()?,
+                num_events: r.value()?,
+                wait_events: r.value()?,
+                ret_event: r.value()?,
+            },
+            spirv::Op::EnqueueKernel => Instruction::EnqueueKernel {
+                queue: r.value()?,
+                flags: r.value()?,
+                nd_range: r.value()?,
+                num_events: r.value()?,
+                wait_events: r.value()?,
+                ret_event: r.value()?,
+                invoke: r.value()?,
+                param: r.value()?,
+                param_size: r.value()?,
+                param_align: r.value()?,
+                local_size: r.values()?,
+            },
+            spirv::Op::GetKernelNDrangeSubGroupCount => Instruction::GetKernelNDrangeSubGroupCount {
+                nd_range: r.value()?,
+                invoke: r.value()?,
+                param: r.value()?,
+                param_size: r.value()?,
+                param_align: r.value()?,
+            },
+            spirv::Op::GetKernelNDrangeMaxSubGroupSize => {
+                Instruction::GetKernelNDrangeMaxSubGroupSize {
+                    nd_range: r.value()?,
+                    invoke: r.value()?,
+                    param: r.value()?,
+                    param_size: r.value()?,
+                    param_align: r.value()?,
+                }
+            }
+            spirv::Op::GetKernelWorkGroupSize => Instruction::GetKernelWorkGroupSize {
+                invoke: r.value()?,
+                param: r.value()?,
+                param_size: r.value()?,
+                param_align: r.value()?,
+            },
+            spirv::Op::GetKernelPreferredWorkGroupSizeMultiple => {
+                Instruction::GetKernelPreferredWorkGroupSizeMultiple {
+                    invoke: r.value()?,
+                    param: r.value()?,
+                    param_size: r.value()?,
+                    param_align: r.value()?,
+                }
+            }
+            spirv::Op::RetainEvent => Instruction::RetainEvent { event: r.value()? },
+            spirv::Op::ReleaseEvent => Instruction::ReleaseEvent { event: r.value()? },
+            spirv::Op::CreateUserEvent => Instruction::CreateUserEvent,
+            spirv::Op::IsValidEvent => Instruction::IsValidEvent { event: r.value()? },
+            spirv::Op::SetUserEventStatus => Instruction::SetUserEventStatus {
+                event: r.value()?,
+                status: r.value()?,
+            },
+            spirv::Op::CaptureEventProfilingInfo => Instruction::CaptureEventProfilingInfo {
+                event: r.value()?,
+                profiling_info: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GetDefaultQueue => Instruction::GetDefaultQueue,
+            spirv::Op::BuildNDRange => Instruction::BuildNDRange {
+                global_work_size: r.value()?,
+                local_work_size: r.value()?,
+                global_work_offset: r.value()?,
+            },
+            spirv::Op::ImageSparseSampleImplicitLod => Instruction::ImageSparseSampleImplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSparseSampleExplicitLod => Instruction::ImageSparseSampleExplicitLod {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.required(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSparseSampleDrefImplicitLod => {
+                Instruction::ImageSparseSampleDrefImplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.optional(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseSampleDrefExplicitLod => {
+                Instruction::ImageSparseSampleDrefExplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.required(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseSampleProjImplicitLod => {
+                Instruction::ImageSparseSampleProjImplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    image_operands: r.optional(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseSampleProjExplicitLod => {
+                Instruction::ImageSparseSampleProjExplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    image_operands: r.required(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseSampleProjDrefImplicitLod => {
+                Instruction::ImageSparseSampleProjDrefImplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.optional(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseSampleProjDrefExplicitLod => {
+                Instruction::ImageSparseSampleProjDrefExplicitLod {
+                    sampled_image: r.value()?,
+                    coordinate: r.value()?,
+                    dref: r.value()?,
+                    image_operands: r.required(kind!(ImageOperands))?,
+                }
+            }
+            spirv::Op::ImageSparseFetch => Instruction::ImageSparseFetch {
+                image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSparseGather => Instruction::ImageSparseGather {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                component: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSparseDrefGather => Instruction::ImageSparseDrefGather {
+                sampled_image: r.value()?,
+                coordinate: r.value()?,
+                dref: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::ImageSparseTexelsResident => Instruction::ImageSparseTexelsResident {
+                resident_code: r.value()?,
+            },
+            spirv::Op::NoLine => Instruction::NoLine,
+            spirv::Op::AtomicFlagTestAndSet => Instruction::AtomicFlagTestAndSet {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::AtomicFlagClear => Instruction::AtomicFlagClear {
+                pointer: r.value()?,
+                scope: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::ImageSparseRead => Instruction::ImageSparseRead {
+                image: r.value()?,
+                coordinate: r.value()?,
+                image_operands: r.optional(kind!(ImageOperands))?,
+            },
+            spirv::Op::SizeOf => Instruction::SizeOf {
+                pointer: r.value()?,
+            },
+            spirv::Op::CreatePipeFromPipeStorage => Instruction::CreatePipeFromPipeStorage {
+                pipe_storage: r.value()?,
+            },
+            spirv::Op::GetKernelLocalSizeForSubgroupCount => {
+                Instruction::GetKernelLocalSizeForSubgroupCount {
+                    subgroup_count: r.value()?,
+                    invoke: r.value()?,
+                    param: r.value()?,
+                    param_size: r.value()?,
+                    param_align: r.value()?,
+                }
+            }
+            spirv::Op::GetKernelMaxNumSubgroups => Instruction::GetKernelMaxNumSubgroups {
+                invoke: r.value()?,
+                param: r.value()?,
+                param_size: r.value()?,
+                param_align: r.value()?,
+            },
+            spirv::Op::NamedBarrierInitialize => Instruction::NamedBarrierInitialize {
+                subgroup_count: r.value()?,
+            },
+            spirv::Op::MemoryNamedBarrier => Instruction::MemoryNamedBarrier {
+                named_barrier: r.value()?,
+                memory: r.value()?,
+                semantics: r.value()?,
+            },
+            spirv::Op::ModuleProcessed => Instruction::ModuleProcessed {
+                process: r.required(string)?,
+            },
+            spirv::Op::ExecutionModeId => Instruction::ExecutionModeId {
+                entry_point: r.value()?,
+                mode: r.required(kind!(ExecutionMode))?,
+            },
+            spirv::Op::DecorateId => Instruction::DecorateId {
+                target: r.value()?,
+                decoration: r.required(kind!(Decoration))?,
+            },
+            spirv::Op::GroupNonUniformElect => Instruction::GroupNonUniformElect {
+                execution: r.value()?,
+            },
+            spirv::Op::GroupNonUniformAll => Instruction::GroupNonUniformAll {
+                execution: r.value()?,
+                predicate: r.value()?,
+            },
+            spirv::Op::GroupNonUniformAny => Instruction::GroupNonUniformAny {
+                execution: r.value()?,
+                predicate: r.value()?,
+            },
+            spirv::Op::GroupNonUniformAllEqual => Instruction::GroupNonUniformAllEqual {
+                execution: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBroadcast => Instruction::GroupNonUniformBroadcast {
+                execution: r.value()?,
+                value: r.value()?,
+                id: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBroadcastFirst => Instruction::GroupNonUniformBroadcastFirst {
+                execution: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBallot => Instruction::GroupNonUniformBallot {
+                execution: r.value()?,
+                predicate: r.value()?,
+            },
+            spirv::Op::GroupNonUniformInverseBallot => Instruction::GroupNonUniformInverseBallot {
+                execution: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBallotBitExtract => {
+                Instruction::GroupNonUniformBallotBitExtract {
+                    execution: r.value()?,
+                    value: r.value()?,
+                    index: r.value()?,
+                }
+            }
+            spirv::Op::GroupNonUniformBallotBitCount => Instruction::GroupNonUniformBallotBitCount {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBallotFindLSB => Instruction::GroupNonUniformBallotFindLSB {
+                execution: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformBallotFindMSB => Instruction::GroupNonUniformBallotFindMSB {
+                execution: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::GroupNonUniformShuffle => Instruction::GroupNonUniformShuffle {
+                execution: r.value()?,
+                value: r.value()?,
+                id: r.value()?,
+            },
+            spirv::Op::GroupNonUniformShuffleXor => Instruction::GroupNonUniformShuffleXor {
+                execution: r.value()?,
+                value: r.value()?,
+                mask: r.value()?,
+            },
+            spirv::Op::GroupNonUniformShuffleUp => Instruction::GroupNonUniformShuffleUp {
+                execution: r.value()?,
+                value: r.value()?,
+                delta: r.value()?,
+            },
+            spirv::Op::GroupNonUniformShuffleDown => Instruction::GroupNonUniformShuffleDown {
+                execution: r.value()?,
+                value: r.value()?,
+                delta: r.value()?,
+            },
+            spirv::Op::GroupNonUniformIAdd => Instruction::GroupNonUniformIAdd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformFAdd => Instruction::GroupNonUniformFAdd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformIMul => Instruction::GroupNonUniformIMul {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformFMul => Instruction::GroupNonUniformFMul {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformSMin => Instruction::GroupNonUniformSMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformUMin => Instruction::GroupNonUniformUMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformFMin => Instruction::GroupNonUniformFMin {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformSMax => Instruction::GroupNonUniformSMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformUMax => Instruction::GroupNonUniformUMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformFMax => Instruction::GroupNonUniformFMax {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformBitwiseAnd => Instruction::GroupNonUniformBitwiseAnd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformBitwiseOr => Instruction::GroupNonUniformBitwiseOr {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformBitwiseXor => Instruction::GroupNonUniformBitwiseXor {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformLogicalAnd => Instruction::GroupNonUniformLogicalAnd {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformLogicalOr => Instruction::GroupNonUniformLogicalOr {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformLogicalXor => Instruction::GroupNonUniformLogicalXor {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                value: r.value()?,
+                cluster_size: r.optional_value()?,
+            },
+            spirv::Op::GroupNonUniformQuadBroadcast => Instruction::GroupNonUniformQuadBroadcast {
+                execution: r.value()?,
+                value: r.value()?,
+                index: r.value()?,
+            },
+            spirv::Op::GroupNonUniformQuadSwap => Instruction::GroupNonUniformQuadSwap {
+                execution: r.value()?,
+                value: r.value()?,
+                direction: r.value()?,
+            },
+            spirv::Op::SubgroupBallotKHR => Instruction::SubgroupBallotKHR {
+                predicate: r.value()?,
+            },
+            spirv::Op::SubgroupFirstInvocationKHR => Instruction::SubgroupFirstInvocationKHR {
+                value: r.value()?,
+            },
+            spirv::Op::SubgroupAllKHR => Instruction::SubgroupAllKHR {
+                predicate: r.value()?,
+            },
+            spirv::Op::SubgroupAnyKHR => Instruction::SubgroupAnyKHR {
+                predicate: r.value()?,
+            },
+            spirv::Op::SubgroupAllEqualKHR => Instruction::SubgroupAllEqualKHR {
+                predicate: r.value()?,
+            },
+            spirv::Op::SubgroupReadInvocationKHR => Instruction::SubgroupReadInvocationKHR {
+                value: r.value()?,
+                index: r.value()?,
+            },
+            spirv::Op::TraceRayKHR => Instruction::TraceRayKHR {
+                accel: r.value()?,
+                ray_flags: r.value()?,
+                cull_mask: r.value()?,
+                sbt_offset: r.value()?,
+                sbt_stride: r.value()?,
+                miss_index: r.value()?,
+                ray_origin: r.value()?,
+                ray_tmin: r.value()?,
+                ray_direction: r.value()?,
+                ray_tmax: r.value()?,
+                payload: r.value()?,
+            },
+            spirv::Op::ExecuteCallableKHR => Instruction::ExecuteCallableKHR {
+                sbt_index: r.value()?,
+                callable_data: r.value()?,
+            },
+            spirv::Op::ConvertUToAccelerationStructureKHR => {
+                Instruction::ConvertUToAccelerationStructureKHR {
+                    accel: r.value()?,
+                }
+            }
+            spirv::Op::CooperativeMatrixLoadKHR => Instruction::CooperativeMatrixLoadKHR {
+                pointer: r.value()?,
+                memory_layout: r.value()?,
+                stride: r.optional_value()?,
+                memory_operand: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::CooperativeMatrixStoreKHR => Instruction::CooperativeMatrixStoreKHR {
+                pointer: r.value()?,
+                object: r.value()?,
+                memory_layout: r.value()?,
+                stride: r.optional_value()?,
+                memory_operand: r.optional(kind!(MemoryAccess))?,
+            },
+            spirv::Op::CooperativeMatrixMulAddKHR => Instruction::CooperativeMatrixMulAddKHR {
+                a: r.value()?,
+                b: r.value()?,
+                c: r.value()?,
+                cooperative_matrix_operands: r.optional(kind!(CooperativeMatrixOperands))?,
+            },
+            spirv::Op::CooperativeMatrixLengthKHR => Instruction::CooperativeMatrixLengthKHR {
+                type_: r.value()?,
+            },
+            spirv::Op::RayQueryInitializeKHR => Instruction::RayQueryInitializeKHR {
+                ray_query: r.value()?,
+                accel: r.value()?,
+                ray_flags: r.value()?,
+                cull_mask: r.value()?,
+                ray_origin: r.value()?,
+                ray_tmin: r.value()?,
+                ray_direction: r.value()?,
+                ray_tmax: r.value()?,
+            },
+            spirv::Op::RayQueryTerminateKHR => Instruction::RayQueryTerminateKHR {
+                ray_query: r.value()?,
+            },
+            spirv::Op::RayQueryGenerateIntersectionKHR => {
+                Instruction::RayQueryGenerateIntersectionKHR {
+                    ray_query: r.value()?,
+                    hit_t: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryConfirmIntersectionKHR => {
+                Instruction::RayQueryConfirmIntersectionKHR {
+                    ray_query: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryProceedKHR => Instruction::RayQueryProceedKHR {
+                ray_query: r.value()?,
+            },
+            spirv::Op::RayQueryGetIntersectionTypeKHR => {
+                Instruction::RayQueryGetIntersectionTypeKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::GroupIAddNonUniformAMD => Instruction::GroupIAddNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFAddNonUniformAMD => Instruction::GroupFAddNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFMinNonUniformAMD => Instruction::GroupFMinNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupUMinNonUniformAMD => Instruction::GroupUMinNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupSMinNonUniformAMD => Instruction::GroupSMinNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupFMaxNonUniformAMD => Instruction::GroupFMaxNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupUMaxNonUniformAMD => Instruction::GroupUMaxNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::GroupSMaxNonUniformAMD => Instruction::GroupSMaxNonUniformAMD {
+                execution: r.value()?,
+                operation: r.required(kind!(GroupOperation))?,
+                x: r.value()?,
+            },
+            spirv::Op::FragmentMaskFetchAMD => Instruction::FragmentMaskFetchAMD {
+                image: r.value()?,
+                coordinate: r.value()?,
+            },
+            spirv::Op::FragmentFetchAMD => Instruction::FragmentFetchAMD {
+                image: r.value()?,
+                coordinate: r.value()?,
+                fragment_index: r.value()?,
+            },
+            spirv::Op::SetMeshOutputsEXT => Instruction::SetMeshOutputsEXT {
+                vertex_count: r.value()?,
+                primitive_count: r.value()?,
+            },
+            spirv::Op::SubgroupShuffleINTEL => Instruction::SubgroupShuffleINTEL {
+                data: r.value()?,
+                invocation_id: r.value()?,
+            },
+            spirv::Op::SubgroupShuffleDownINTEL => Instruction::SubgroupShuffleDownINTEL {
+                current: r.value()?,
+                next: r.value()?,
+                delta: r.value()?,
+            },
+            spirv::Op::SubgroupShuffleUpINTEL => Instruction::SubgroupShuffleUpINTEL {
+                previous: r.value()?,
+                current: r.value()?,
+                delta: r.value()?,
+            },
+            spirv::Op::SubgroupShuffleXorINTEL => Instruction::SubgroupShuffleXorINTEL {
+                data: r.value()?,
+                value: r.value()?,
+            },
+            spirv::Op::SubgroupBlockReadINTEL => Instruction::SubgroupBlockReadINTEL {
+                ptr: r.value()?,
+            },
+            spirv::Op::SubgroupBlockWriteINTEL => Instruction::SubgroupBlockWriteINTEL {
+                ptr: r.value()?,
+                data: r.value()?,
+            },
+            spirv::Op::SubgroupImageBlockReadINTEL => Instruction::SubgroupImageBlockReadINTEL {
+                image: r.value()?,
+                coordinate: r.value()?,
+            },
+            spirv::Op::SubgroupImageBlockWriteINTEL => Instruction::SubgroupImageBlockWriteINTEL {
+                image: r.value()?,
+                coordinate: r.value()?,
+                data: r.value()?,
+            },
+            spirv::Op::DecorateStringGOOGLE => Instruction::DecorateStringGOOGLE {
+                target: r.value()?,
+                decoration: r.required(kind!(Decoration))?,
+            },
+            spirv::Op::MemberDecorateStringGOOGLE => Instruction::MemberDecorateStringGOOGLE {
+                struct_type: r.value()?,
+                member: r.required(literal)?,
+                decoration: r.required(kind!(Decoration))?,
+            },
+            spirv::Op::GroupNonUniformPartitionNV => Instruction::GroupNonUniformPartitionNV {
+                value: r.value()?,
+            },
+            spirv::Op::RayQueryGetRayTMinKHR => Instruction::RayQueryGetRayTMinKHR {
+                ray_query: r.value()?,
+            },
+            spirv::Op::RayQueryGetRayFlagsKHR => Instruction::RayQueryGetRayFlagsKHR {
+                ray_query: r.value()?,
+            },
+            spirv::Op::RayQueryGetIntersectionTKHR => Instruction::RayQueryGetIntersectionTKHR {
+                ray_query: r.value()?,
+                intersection: r.value()?,
+            },
+            spirv::Op::RayQueryGetIntersectionInstanceCustomIndexKHR => {
+                Instruction::RayQueryGetIntersectionInstanceCustomIndexKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionInstanceIdKHR => {
+                Instruction::RayQueryGetIntersectionInstanceIdKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR => {
+                Instruction::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionGeometryIndexKHR => {
+                Instruction::RayQueryGetIntersectionGeometryIndexKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionPrimitiveIndexKHR => {
+                Instruction::RayQueryGetIntersectionPrimitiveIndexKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionBarycentricsKHR => {
+                Instruction::RayQueryGetIntersectionBarycentricsKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionFrontFaceKHR => {
+                Instruction::RayQueryGetIntersectionFrontFaceKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionCandidateAABBOpaqueKHR => {
+                Instruction::RayQueryGetIntersectionCandidateAABBOpaqueKHR {
+                    ray_query: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionObjectRayDirectionKHR => {
+                Instruction::RayQueryGetIntersectionObjectRayDirectionKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionObjectRayOriginKHR => {
+                Instruction::RayQueryGetIntersectionObjectRayOriginKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetWorldRayDirectionKHR => {
+                Instruction::RayQueryGetWorldRayDirectionKHR {
+                    ray_query: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetWorldRayOriginKHR => Instruction::RayQueryGetWorldRayOriginKHR {
+                ray_query: r.value()?,
+            },
+            spirv::Op::RayQueryGetIntersectionObjectToWorldKHR => {
+                Instruction::RayQueryGetIntersectionObjectToWorldKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            spirv::Op::RayQueryGetIntersectionWorldToObjectKHR => {
+                Instruction::RayQueryGetIntersectionWorldToObjectKHR {
+                    ray_query: r.value()?,
+                    intersection: r.value()?,
+                }
+            }
+            _ => return Ok(None),
+        }))
+    }
+}
+
+impl Terminator {
+    /// Lifts the operands read by `r` into the variant for `opcode`.
+    ///
+    /// Returns `Ok(None)` if there is no variant for `opcode`.
+    pub(in sr) fn lift(opcode: spirv::Op, r: &mut OperandReader) -> LiftResult<Option<Terminator>> {
+        Ok(Some(match opcode {
+            spirv::Op::Branch => Terminator::Branch {
+                target_label: r.block()?,
+            },
+            spirv::Op::BranchConditional => Terminator::BranchConditional {
+                condition: r.value()?,
+                true_label: r.block()?,
+                false_label: r.block()?,
+                branch_weights: r.repeated(literal)?,
+            },
+            spirv::Op::Switch => Terminator::Switch {
+                selector: r.value()?,
+                default: r.block()?,
+                target: r.literal_block_pairs()?,
+            },
+            spirv::Op::Kill => Terminator::Kill,
+            spirv::Op::Return => Terminator::Return,
+            spirv::Op::ReturnValue => Terminator::ReturnValue { value: r.value()? },
+            spirv::Op::Unreachable => Terminator::Unreachable,
+            spirv::Op::IgnoreIntersectionKHR => Terminator::IgnoreIntersectionKHR,
+            spirv::Op::TerminateRayKHR => Terminator::TerminateRayKHR,
+            spirv::Op::EmitMeshTasksEXT => Terminator::EmitMeshTasksEXT {
+                group_count_x: r.value()?,
+                group_count_y: r.value()?,
+                group_count_z: r.value()?,
+                payload: r.optional_value()?,
+            },
+            _ => return Ok(None),
+        }))
+    }
 }
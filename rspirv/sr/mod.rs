@@ -16,10 +16,22 @@
 
 pub use self::constants::{Constant, ConstantToken};
 pub use self::context::Context;
+pub use self::debug_info::{DebugInstruction, DebugOperand, DEBUG_INFO_SET};
 pub use self::decoration::Decoration;
+pub use self::function::{BasicBlock, Function, Operation, Parameter};
+pub use self::module::{EntryPoint, LiftError, LiftResult, MeshOutputs, Module};
+pub use self::ops::{Instruction, Terminator, Value};
+pub use self::storage::{Object, Token};
 pub use self::types::{Type, TypeToken};
+pub use self::variable::{Variable, VariableToken};
 
 mod constants;
 mod context;
+mod debug_info;
 mod decoration;
+mod function;
+mod module;
+mod ops;
+mod storage;
 mod types;
+mod variable;
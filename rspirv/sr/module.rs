@@ -0,0 +1,1515 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use std::{error, fmt, result};
+use std::collections::{BTreeMap, HashMap};
+
+use super::{ConstantToken, Context, Decoration, Function, Instruction, Terminator, TypeToken, Value,
+            VariableToken};
+use super::ops::OperandReader;
+use super::debug_info::{DebugInstruction, DebugValues, DEBUG_INFO_SET};
+use super::types::TypeEnum;
+
+/// Structured representation lifting errors.
+#[derive(Debug, PartialEq)]
+pub enum LiftError {
+    /// The OpMemoryModel instruction is missing
+    MissingMemoryModel,
+    /// An instruction references an id that is not defined (or not
+    /// yet defined) as a type.
+    UndefinedType(spirv::Word),
+    /// An instruction references an id that is not defined (or not
+    /// yet defined) as a constant.
+    UndefinedConstant(spirv::Word),
+    /// An instruction references an id that is not defined as a global
+    /// variable.
+    UndefinedVariable(spirv::Word),
+    /// An instruction is missing its result id
+    MissingResultId(spirv::Op),
+    /// An instruction has operands not matching its grammar
+    WrongOperands(spirv::Op),
+    /// The constant kind is not supported by the structured representation yet
+    UnsupportedConstant(spirv::Word),
+    /// A branch targets an id that is not the label of a basic block in
+    /// the same function.
+    UndefinedLabel(spirv::Word),
+    /// The basic block with the given label does not end with a terminator
+    MissingTerminator(spirv::Word),
+    /// The instruction is not allowed inside a basic block
+    UnexpectedInstruction(spirv::Op),
+}
+
+impl LiftError {
+    /// Gives an descriptive string for each error.
+    fn describe(&self) -> &str {
+        match *self {
+            LiftError::MissingMemoryModel => "missing OpMemoryModel",
+            LiftError::UndefinedType(_) => "reference to undefined type",
+            LiftError::UndefinedConstant(_) => "reference to undefined constant",
+            LiftError::UndefinedVariable(_) => "reference to undefined global variable",
+            LiftError::MissingResultId(_) => "missing result id",
+            LiftError::WrongOperands(_) => "wrong operands",
+            LiftError::UnsupportedConstant(_) => "unsupported constant",
+            LiftError::UndefinedLabel(_) => "reference to undefined label",
+            LiftError::MissingTerminator(_) => "missing terminator in basic block",
+            LiftError::UnexpectedInstruction(_) => "unexpected instruction in basic block",
+        }
+    }
+}
+
+impl error::Error for LiftError {
+    fn description(&self) -> &str {
+        self.describe()
+    }
+}
+
+impl fmt::Display for LiftError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LiftError::MissingMemoryModel => write!(f, "{}", self.describe()),
+            LiftError::UndefinedType(id) |
+            LiftError::UndefinedConstant(id) |
+            LiftError::UndefinedVariable(id) |
+            LiftError::UnsupportedConstant(id) |
+            LiftError::UndefinedLabel(id) |
+            LiftError::MissingTerminator(id) => write!(f, "{} %{}", self.describe(), id),
+            LiftError::MissingResultId(op) |
+            LiftError::WrongOperands(op) |
+            LiftError::UnexpectedInstruction(op) => write!(f, "{} for Op{:?}", self.describe(), op),
+        }
+    }
+}
+
+pub type LiftResult<T> = result::Result<T, LiftError>;
+
+/// Structured representation of an OpEntryPoint together with all its
+/// OpExecutionModes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryPoint {
+    /// The execution model of this entry point.
+    pub execution_model: spirv::ExecutionModel,
+    /// The result id of the entry point function.
+    pub function: spirv::Word,
+    /// The name of this entry point.
+    pub name: String,
+    /// The global variables forming the interface of this entry point.
+    pub interface: Vec<VariableToken>,
+    /// All execution modes declared for this entry point, together with
+    /// their literal parameters.
+    pub execution_modes: Vec<(spirv::ExecutionMode, Vec<u32>)>,
+}
+
+/// The output limits of a mesh shader entry point, as declared by its
+/// execution modes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MeshOutputs {
+    /// The maximum number of vertices, declared by OutputVertices.
+    pub max_vertices: u32,
+    /// The maximum number of primitives, declared by OutputPrimitivesEXT.
+    pub max_primitives: u32,
+    /// The output topology: OutputPoints, OutputLinesEXT, or
+    /// OutputTrianglesEXT.
+    pub topology: spirv::ExecutionMode,
+}
+
+impl EntryPoint {
+    /// Returns the literal parameters of the given execution `mode` if it
+    /// is declared for this entry point.
+    pub fn execution_mode(&self, mode: spirv::ExecutionMode) -> Option<&[u32]> {
+        self.execution_modes
+            .iter()
+            .find(|&&(m, _)| m == mode)
+            .map(|(_, params)| &params[..])
+    }
+
+    /// Returns the output limits of this entry point if it is a mesh
+    /// shader declaring all of them.
+    pub fn mesh_outputs(&self) -> Option<MeshOutputs> {
+        use spirv::ExecutionMode::{OutputLinesEXT, OutputPoints, OutputTrianglesEXT};
+
+        if self.execution_model != spirv::ExecutionModel::MeshEXT {
+            return None;
+        }
+        let max_vertices = *self.execution_mode(spirv::ExecutionMode::OutputVertices)?.first()?;
+        let max_primitives =
+            *self.execution_mode(spirv::ExecutionMode::OutputPrimitivesEXT)?.first()?;
+        let topology = self
+            .execution_modes
+            .iter()
+            .map(|&(m, _)| m)
+            .find(|&m| m == OutputPoints || m == OutputLinesEXT || m == OutputTrianglesEXT)?;
+        Some(MeshOutputs {
+            max_vertices,
+            max_primitives,
+            topology,
+        })
+    }
+}
+
+/// Structured representation of a SPIR-V module.
+///
+/// All types, constants, and global variables are allocated in the
+/// module's [`Context`](struct.Context.html); the module itself only
+/// holds tokens into it.
+#[derive(Debug)]
+pub struct Module {
+    /// The context holding all structured objects of this module.
+    pub context: Context,
+    /// All declared capabilities.
+    pub capabilities: Vec<spirv::Capability>,
+    /// All declared extensions.
+    pub extensions: Vec<String>,
+    /// All imported extended instruction sets, keyed by the result id of
+    /// their OpExtInstImport.
+    pub ext_inst_imports: BTreeMap<spirv::Word, String>,
+    /// The addressing model of this module.
+    pub addressing_model: spirv::AddressingModel,
+    /// The memory model of this module.
+    pub memory_model: spirv::MemoryModel,
+    /// All global (i.e., non-function storage class) variables.
+    pub global_variables: Vec<VariableToken>,
+    /// All entry points.
+    pub entry_points: Vec<EntryPoint>,
+    /// All debug names given by OpName, keyed by the id of the target.
+    ///
+    /// Names of global variables are also attached to the variables
+    /// themselves.
+    pub names: BTreeMap<spirv::Word, String>,
+    /// All debug names given by OpMemberName, keyed by the id of the
+    /// struct type and the member index.
+    pub member_names: BTreeMap<(spirv::Word, u32), String>,
+    /// All instructions of the NonSemantic.Shader.DebugInfo.100 extended
+    /// instruction set, both global ones and those in functions, in module
+    /// order.
+    pub debug_info: Vec<DebugInstruction>,
+    /// All functions, in module order. Their basic blocks are allocated in
+    /// the context.
+    pub functions: Vec<Function>,
+}
+
+/// Per-module lookup tables from result ids to structured tokens.
+struct IdMap {
+    types: HashMap<spirv::Word, TypeToken>,
+    constants: HashMap<spirv::Word, ConstantToken>,
+    variables: HashMap<spirv::Word, VariableToken>,
+}
+
+impl IdMap {
+    fn ty(&self, id: spirv::Word) -> LiftResult<TypeToken> {
+        self.types.get(&id).cloned().ok_or(LiftError::UndefinedType(id))
+    }
+
+    fn constant(&self, id: spirv::Word) -> LiftResult<ConstantToken> {
+        self.constants.get(&id).cloned().ok_or(LiftError::UndefinedConstant(id))
+    }
+
+    fn variable(&self, id: spirv::Word) -> LiftResult<VariableToken> {
+        self.variables.get(&id).cloned().ok_or(LiftError::UndefinedVariable(id))
+    }
+
+    /// Resolves the given module-level `id` into the value it refers to.
+    fn value(&self, id: spirv::Word) -> Value {
+        if let Some(&token) = self.types.get(&id) {
+            Value::Type(token)
+        } else if let Some(&token) = self.constants.get(&id) {
+            Value::Constant(token)
+        } else if let Some(&token) = self.variables.get(&id) {
+            Value::Variable(token)
+        } else {
+            Value::Id(id)
+        }
+    }
+}
+
+impl Module {
+    /// Lifts the given data representation `module` into structured
+    /// representation.
+    pub fn from_data(module: &mr::Module) -> LiftResult<Module> {
+        let (addressing_model, memory_model) = match module.memory_model {
+            Some(ref inst) => match (inst.operands.first(), inst.operands.get(1)) {
+                (Some(&mr::Operand::AddressingModel(a)), Some(&mr::Operand::MemoryModel(m))) => {
+                    (a, m)
+                }
+                _ => return Err(LiftError::WrongOperands(spirv::Op::MemoryModel)),
+            },
+            None => return Err(LiftError::MissingMemoryModel),
+        };
+
+        let mut capabilities = vec![];
+        for inst in &module.capabilities {
+            match inst.operands.first() {
+                Some(&mr::Operand::Capability(c)) => capabilities.push(c),
+                _ => return Err(LiftError::WrongOperands(spirv::Op::Capability)),
+            }
+        }
+
+        let mut extensions = vec![];
+        for inst in &module.extensions {
+            match inst.operands.first() {
+                Some(mr::Operand::LiteralString(name)) => extensions.push(name.to_string()),
+                _ => return Err(LiftError::WrongOperands(spirv::Op::Extension)),
+            }
+        }
+
+        let mut ext_inst_imports = BTreeMap::new();
+        for inst in &module.ext_inst_imports {
+            let id = inst.result_id.ok_or(LiftError::MissingResultId(spirv::Op::ExtInstImport))?;
+            match inst.operands.first() {
+                Some(mr::Operand::LiteralString(name)) => {
+                    ext_inst_imports.insert(id, name.to_string());
+                }
+                _ => return Err(LiftError::WrongOperands(spirv::Op::ExtInstImport)),
+            }
+        }
+
+        let mut names = BTreeMap::new();
+        let mut member_names = BTreeMap::new();
+        for inst in &module.debugs {
+            match (inst.class.opcode, &inst.operands[..]) {
+                (spirv::Op::Name,
+                 [mr::Operand::IdRef(target), mr::Operand::LiteralString(name)]) => {
+                    names.insert(*target, name.to_string());
+                }
+                (spirv::Op::MemberName,
+                 [mr::Operand::IdRef(target),
+                  mr::Operand::LiteralInt32(member),
+                  mr::Operand::LiteralString(name)]) => {
+                    member_names.insert((*target, *member), name.to_string());
+                }
+                (spirv::Op::Name, _) | (spirv::Op::MemberName, _) => {
+                    return Err(LiftError::WrongOperands(inst.class.opcode))
+                }
+                _ => {}
+            }
+        }
+
+        let mut context = Context::new();
+        let mut ids = IdMap {
+            types: HashMap::new(),
+            constants: HashMap::new(),
+            variables: HashMap::new(),
+        };
+        let mut global_variables = vec![];
+        let decorations = module.decoration_index();
+
+        for inst in &module.types_global_values {
+            let opcode = inst.class.opcode;
+            if opcode == spirv::Op::TypeForwardPointer {
+                // The pointer type itself is defined later; register a
+                // placeholder so that references in between resolve.
+                match (inst.operands.first(), inst.operands.get(1)) {
+                    (Some(&mr::Operand::IdRef(id)), Some(&mr::Operand::StorageClass(sc))) => {
+                        let token = context.forward_pointer_placeholder(sc);
+                        ids.types.insert(id, token);
+                    }
+                    _ => return Err(LiftError::WrongOperands(opcode)),
+                }
+                continue;
+            }
+
+            let id = inst.result_id.ok_or(LiftError::MissingResultId(opcode))?;
+            let placeholder = match ids.types.get(&id) {
+                Some(&token) if context.get_type(token).is_forward_pointer_type() => Some(token),
+                _ => None,
+            };
+            if let (spirv::Op::TypePointer, Some(placeholder)) = (opcode, placeholder) {
+                // Patch the placeholder in place, so that the types
+                // referencing it in between see the real pointer type.
+                let pointee = ids.ty(id_ref(opcode, &inst.operands, 1)?)?;
+                context.resolve_forward_pointer(placeholder, pointee);
+            } else if opcode == spirv::Op::Variable {
+                let ty = ids.ty(inst.result_type.ok_or(LiftError::WrongOperands(opcode))?)?;
+                let storage_class = match inst.operands.first() {
+                    Some(&mr::Operand::StorageClass(sc)) => sc,
+                    _ => return Err(LiftError::WrongOperands(opcode)),
+                };
+                let initializer = match inst.operands.get(1) {
+                    Some(&mr::Operand::IdRef(init)) => Some(ids.constant(init)?),
+                    None => None,
+                    _ => return Err(LiftError::WrongOperands(opcode)),
+                };
+                let token = context.variable(ty, storage_class, initializer);
+                {
+                    let variable = context.get_variable_mut(token);
+                    variable.decorations = lift_decorations(&decorations, id)?;
+                    variable.name = names.get(&id).cloned();
+                }
+                ids.variables.insert(id, token);
+                global_variables.push(token);
+            } else if let Some(token) = lift_type(&mut context, &ids, inst)? {
+                ids.types.insert(id, token);
+            } else if let Some(token) = lift_constant(&mut context, &ids, inst)? {
+                ids.constants.insert(id, token);
+            }
+        }
+
+        let debug_sets: Vec<spirv::Word> = ext_inst_imports
+            .iter()
+            .filter(|&(_, name)| name == DEBUG_INFO_SET)
+            .map(|(&id, _)| id)
+            .collect();
+        let mut debug_info = vec![];
+        if !debug_sets.is_empty() {
+            let values = DebugValues::new(module);
+            let bodies = module.functions.iter().flat_map(|f| &f.basic_blocks);
+            let insts = module.types_global_values.iter()
+                .chain(bodies.flat_map(|bb| &bb.instructions));
+            for inst in insts.filter(|inst| inst.class.opcode == spirv::Op::ExtInst) {
+                match inst.operands.first() {
+                    Some(mr::Operand::IdRef(set)) if debug_sets.contains(set) => {
+                        debug_info.extend(values.lift(inst)?);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut entry_points = vec![];
+        for inst in &module.entry_points {
+            let (execution_model, function, name) = match &inst.operands[..] {
+                [mr::Operand::ExecutionModel(model),
+                 mr::Operand::IdRef(function),
+                 mr::Operand::LiteralString(name),
+                 ..] => (*model, *function, name.to_string()),
+                _ => return Err(LiftError::WrongOperands(spirv::Op::EntryPoint)),
+            };
+            let mut interface = vec![];
+            for i in 3..inst.operands.len() {
+                let id = id_ref(spirv::Op::EntryPoint, &inst.operands, i)?;
+                interface.push(ids.variable(id)?);
+            }
+            entry_points.push(EntryPoint {
+                execution_model,
+                function,
+                name,
+                interface,
+                execution_modes: vec![],
+            });
+        }
+        for inst in &module.execution_modes {
+            let (function, mode) = match &inst.operands[..] {
+                [mr::Operand::IdRef(function), mr::Operand::ExecutionMode(mode), ..] => {
+                    (*function, *mode)
+                }
+                _ => return Err(LiftError::WrongOperands(spirv::Op::ExecutionMode)),
+            };
+            let mut params = vec![];
+            for i in 2..inst.operands.len() {
+                params.push(literal(spirv::Op::ExecutionMode, &inst.operands, i)?);
+            }
+            // The same function may be the target of several entry points.
+            for entry in entry_points.iter_mut().filter(|e| e.function == function) {
+                entry.execution_modes.push((mode, params.clone()));
+            }
+        }
+
+        let mut functions = vec![];
+        for function in &module.functions {
+            functions.push(lift_function(&mut context, &ids, function)?);
+        }
+
+        Ok(Module {
+            context,
+            capabilities,
+            extensions,
+            ext_inst_imports,
+            addressing_model,
+            memory_model,
+            global_variables,
+            entry_points,
+            names,
+            member_names,
+            debug_info,
+            functions,
+        })
+    }
+
+    /// Returns the output variables of the given `entry_point` which are
+    /// written once per primitive instead of once per vertex, i.e., those
+    /// decorated with PerPrimitiveEXT.
+    pub fn per_primitive_outputs(&self, entry_point: &EntryPoint) -> Vec<VariableToken> {
+        entry_point
+            .interface
+            .iter()
+            .cloned()
+            .filter(|&token| {
+                let variable = self.context.get_variable(token);
+                variable.is_in_storage_class(spirv::StorageClass::Output) &&
+                    variable.decorations.contains(&Decoration::PerPrimitiveEXT)
+            })
+            .collect()
+    }
+}
+
+/// Extracts the literal `u32` at `index` of the given `operands`.
+fn literal(op: spirv::Op, operands: &[mr::Operand], index: usize) -> LiftResult<u32> {
+    match operands.get(index) {
+        Some(&mr::Operand::LiteralInt32(v)) => Ok(v),
+        _ => Err(LiftError::WrongOperands(op)),
+    }
+}
+
+/// Extracts the id at `index` of the given `operands`.
+fn id_ref(op: spirv::Op, operands: &[mr::Operand], index: usize) -> LiftResult<spirv::Word> {
+    match operands.get(index) {
+        Some(&mr::Operand::IdRef(v)) => Ok(v),
+        _ => Err(LiftError::WrongOperands(op)),
+    }
+}
+
+/// Lifts the given type-declaring instruction into the context.
+///
+/// Returns `Ok(None)` if `inst` does not declare a type.
+fn lift_type(
+    context: &mut Context,
+    ids: &IdMap,
+    inst: &mr::Instruction,
+) -> LiftResult<Option<TypeToken>> {
+    let op = inst.class.opcode;
+    let ops = &inst.operands[..];
+    let token = match op {
+        spirv::Op::TypeVoid => context.type_void(),
+        spirv::Op::TypeBool => context.type_bool(),
+        spirv::Op::TypeInt => context.type_int(literal(op, ops, 0)?, literal(op, ops, 1)?),
+        spirv::Op::TypeFloat => context.type_float(literal(op, ops, 0)?),
+        spirv::Op::TypeVector => {
+            let component = ids.ty(id_ref(op, ops, 0)?)?;
+            context.type_vector(component, literal(op, ops, 1)?)
+        }
+        spirv::Op::TypeMatrix => {
+            let column = ids.ty(id_ref(op, ops, 0)?)?;
+            context.type_matrix(column, literal(op, ops, 1)?)
+        }
+        spirv::Op::TypeImage => {
+            let sampled_type = ids.ty(id_ref(op, ops, 0)?)?;
+            let dim = match ops.get(1) {
+                Some(&mr::Operand::Dim(d)) => d,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            let format = match ops.get(6) {
+                Some(&mr::Operand::ImageFormat(f)) => f,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            let access = match ops.get(7) {
+                Some(&mr::Operand::AccessQualifier(a)) => Some(a),
+                None => None,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            context.type_image(
+                sampled_type,
+                dim,
+                literal(op, ops, 2)?,
+                literal(op, ops, 3)?,
+                literal(op, ops, 4)?,
+                literal(op, ops, 5)?,
+                format,
+                access,
+            )
+        }
+        spirv::Op::TypeSampler => context.type_sampler(),
+        spirv::Op::TypeSampledImage => {
+            let image = ids.ty(id_ref(op, ops, 0)?)?;
+            context.type_sampled_image(image)
+        }
+        spirv::Op::TypeArray => {
+            let element = ids.ty(id_ref(op, ops, 0)?)?;
+            let length = ids.constant(id_ref(op, ops, 1)?)?;
+            context.type_array(element, length)
+        }
+        spirv::Op::TypeRuntimeArray => {
+            let element = ids.ty(id_ref(op, ops, 0)?)?;
+            context.type_runtime_array(element)
+        }
+        spirv::Op::TypeStruct => {
+            let mut fields = vec![];
+            for i in 0..ops.len() {
+                fields.push(ids.ty(id_ref(op, ops, i)?)?);
+            }
+            context.type_struct(fields)
+        }
+        spirv::Op::TypeOpaque => match ops.first() {
+            Some(mr::Operand::LiteralString(name)) => context.type_opaque(name.to_string()),
+            _ => return Err(LiftError::WrongOperands(op)),
+        },
+        spirv::Op::TypePointer => {
+            let storage_class = match ops.first() {
+                Some(&mr::Operand::StorageClass(sc)) => sc,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            let pointee = ids.ty(id_ref(op, ops, 1)?)?;
+            context.type_pointer(storage_class, pointee)
+        }
+        spirv::Op::TypeFunction => {
+            let return_type = ids.ty(id_ref(op, ops, 0)?)?;
+            let mut params = vec![];
+            for i in 1..ops.len() {
+                params.push(ids.ty(id_ref(op, ops, i)?)?);
+            }
+            context.type_function(return_type, params)
+        }
+        spirv::Op::TypeEvent => context.type_event(),
+        spirv::Op::TypeDeviceEvent => context.type_device_event(),
+        spirv::Op::TypeReserveId => context.type_reserve_id(),
+        spirv::Op::TypeQueue => context.type_queue(),
+        spirv::Op::TypePipe => match ops.first() {
+            Some(&mr::Operand::AccessQualifier(a)) => context.type_pipe(a),
+            _ => return Err(LiftError::WrongOperands(op)),
+        },
+        spirv::Op::TypePipeStorage => context.type_pipe_storage(),
+        spirv::Op::TypeNamedBarrier => context.type_named_barrier(),
+        spirv::Op::TypeRayQueryKHR => context.type_ray_query_khr(),
+        spirv::Op::TypeAccelerationStructureKHR => context.type_acceleration_structure_khr(),
+        spirv::Op::TypeCooperativeMatrixKHR => {
+            let component = ids.ty(id_ref(op, ops, 0)?)?;
+            let scope = match ops.get(1) {
+                Some(&mr::Operand::IdScope(s)) => s,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            context.type_cooperative_matrix_khr(
+                component,
+                scope,
+                ids.constant(id_ref(op, ops, 2)?)?,
+                ids.constant(id_ref(op, ops, 3)?)?,
+                ids.constant(id_ref(op, ops, 4)?)?,
+            )
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(token))
+}
+
+/// Lifts the given constant-defining instruction into the context.
+///
+/// Returns `Ok(None)` if `inst` does not define a constant.
+fn lift_constant(
+    context: &mut Context,
+    ids: &IdMap,
+    inst: &mr::Instruction,
+) -> LiftResult<Option<ConstantToken>> {
+    let op = inst.class.opcode;
+    let ops = &inst.operands[..];
+    let id = inst.result_id.unwrap_or(0);
+    let token = match op {
+        spirv::Op::ConstantTrue => context.constant_bool(true),
+        spirv::Op::ConstantFalse => context.constant_bool(false),
+        spirv::Op::SpecConstantTrue => context.spec_constant_bool(true),
+        spirv::Op::SpecConstantFalse => context.spec_constant_bool(false),
+        spirv::Op::Constant | spirv::Op::SpecConstant => {
+            let ty = ids.ty(inst.result_type.ok_or(LiftError::WrongOperands(op))?)?;
+            // Literals are decoded by the width of their type: narrower
+            // integers and half precision floats take one word, and 64-bit
+            // types two. Narrower integers keep their width.
+            let (width, float, signed) = match context.get_type(ty).ty {
+                TypeEnum::Int { width, signedness } => (width, false, signedness != 0),
+                TypeEnum::Float { width } => (width, true, false),
+                _ => return Err(LiftError::UnsupportedConstant(id)),
+            };
+            let spec = op == spirv::Op::SpecConstant;
+            match (ops.first(), float, signed) {
+                (Some(&mr::Operand::LiteralInt32(v)), false, _) if width != 32 => {
+                    match (width, signed, spec) {
+                        (8, true, true) => context.spec_constant_i8(v as i8),
+                        (8, true, false) => context.constant_i8(v as i8),
+                        (8, false, true) => context.spec_constant_u8(v as u8),
+                        (8, false, false) => context.constant_u8(v as u8),
+                        (16, true, true) => context.spec_constant_i16(v as i16),
+                        (16, true, false) => context.constant_i16(v as i16),
+                        (16, false, true) => context.spec_constant_u16(v as u16),
+                        (16, false, false) => context.constant_u16(v as u16),
+                        _ => return Err(LiftError::UnsupportedConstant(id)),
+                    }
+                }
+                (Some(&mr::Operand::LiteralInt32(v)), false, true) if spec => {
+                    context.spec_constant_i32(v as i32)
+                }
+                (Some(&mr::Operand::LiteralInt32(v)), false, true) => {
+                    context.constant_i32(v as i32)
+                }
+                (Some(&mr::Operand::LiteralInt32(v)), false, false) if spec => {
+                    context.spec_constant_u32(v)
+                }
+                (Some(&mr::Operand::LiteralInt32(v)), false, false) => context.constant_u32(v),
+                (Some(&mr::Operand::LiteralInt64(v)), false, true) if spec => {
+                    context.spec_constant_i64(v as i64)
+                }
+                (Some(&mr::Operand::LiteralInt64(v)), false, true) => {
+                    context.constant_i64(v as i64)
+                }
+                (Some(&mr::Operand::LiteralInt64(v)), false, false) if spec => {
+                    context.spec_constant_u64(v)
+                }
+                (Some(&mr::Operand::LiteralInt64(v)), false, false) => context.constant_u64(v),
+                (Some(&mr::Operand::LiteralInt32(v)), true, _) if spec => {
+                    context.spec_constant_f16(v as u16)
+                }
+                (Some(&mr::Operand::LiteralInt32(v)), true, _) => context.constant_f16(v as u16),
+                (Some(&mr::Operand::LiteralFloat32(v)), true, _) if spec => {
+                    context.spec_constant_f32(v)
+                }
+                (Some(&mr::Operand::LiteralFloat32(v)), true, _) => context.constant_f32(v),
+                (Some(&mr::Operand::LiteralFloat64(v)), true, _) if spec => {
+                    context.spec_constant_f64(v)
+                }
+                (Some(&mr::Operand::LiteralFloat64(v)), true, _) => context.constant_f64(v),
+                _ => return Err(LiftError::UnsupportedConstant(id)),
+            }
+        }
+        spirv::Op::ConstantComposite | spirv::Op::SpecConstantComposite => {
+            let mut components = vec![];
+            for i in 0..ops.len() {
+                components.push(ids.constant(id_ref(op, ops, i)?)?);
+            }
+            if op == spirv::Op::ConstantComposite {
+                context.constant_composite(components)
+            } else {
+                context.spec_constant_composite(components)
+            }
+        }
+        spirv::Op::ConstantNull => {
+            let ty = ids.ty(inst.result_type.ok_or(LiftError::WrongOperands(op))?)?;
+            context.constant_null(ty)
+        }
+        spirv::Op::ConstantSampler => match (ops.first(), ops.get(2)) {
+            (Some(&mr::Operand::SamplerAddressingMode(a)),
+             Some(&mr::Operand::SamplerFilterMode(f))) => {
+                context.constant_sampler(a, literal(op, ops, 1)?, f)
+            }
+            _ => return Err(LiftError::WrongOperands(op)),
+        },
+        spirv::Op::SpecConstantOp => {
+            let inner = match ops.first() {
+                Some(&mr::Operand::LiteralSpecConstantOpInteger(o)) => o,
+                _ => return Err(LiftError::WrongOperands(op)),
+            };
+            let mut operands = vec![];
+            for i in 1..ops.len() {
+                operands.push(ids.constant(id_ref(op, ops, i)?)?);
+            }
+            context.spec_constant_op(inner, operands)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(token))
+}
+
+/// Lifts the given function together with all its parameters, basic blocks,
+/// and instructions into the context.
+///
+/// Id operands of instructions are resolved into the values they refer to.
+fn lift_function(
+    context: &mut Context,
+    ids: &IdMap,
+    function: &mr::Function,
+) -> LiftResult<Function> {
+    let op = spirv::Op::Function;
+    let def = function.def.as_ref().ok_or(LiftError::WrongOperands(op))?;
+    let id = def.result_id.ok_or(LiftError::MissingResultId(op))?;
+    let (control, ty) = match &def.operands[..] {
+        [mr::Operand::FunctionControl(control), mr::Operand::IdRef(ty)] => (*control, ids.ty(*ty)?),
+        _ => return Err(LiftError::WrongOperands(op)),
+    };
+
+    let mut locals = HashMap::new();
+    let mut parameters = vec![];
+    for inst in &function.parameters {
+        let op = spirv::Op::FunctionParameter;
+        let id = inst.result_id.ok_or(LiftError::MissingResultId(op))?;
+        let ty = ids.ty(inst.result_type.ok_or(LiftError::WrongOperands(op))?)?;
+        let token = context.parameter(id, ty);
+        locals.insert(id, Value::Parameter(token));
+        parameters.push(token);
+    }
+
+    // Allocate all basic blocks and operations first so that forward
+    // references resolve; they are filled in afterwards.
+    let mut blocks = vec![];
+    for bb in &function.basic_blocks {
+        let label = bb
+            .label
+            .as_ref()
+            .and_then(|inst| inst.result_id)
+            .ok_or(LiftError::MissingResultId(spirv::Op::Label))?;
+        let body = match bb.instructions.split_last() {
+            Some((_, body)) => body,
+            None => return Err(LiftError::MissingTerminator(label)),
+        };
+        let mut operations = vec![];
+        for inst in body {
+            let result_type = match inst.result_type {
+                Some(ty) => Some(ids.ty(ty)?),
+                None => None,
+            };
+            let token = context.operation(result_type, inst.result_id, Instruction::Nop);
+            if let Some(id) = inst.result_id {
+                locals.insert(id, Value::Operation(token));
+            }
+            operations.push(token);
+        }
+        let token = context.basic_block(label, operations, Terminator::Unreachable);
+        locals.insert(label, Value::BasicBlock(token));
+        blocks.push(token);
+    }
+
+    let resolve = |id: spirv::Word| locals.get(&id).cloned().unwrap_or_else(|| ids.value(id));
+    for (bb, &token) in function.basic_blocks.iter().zip(&blocks) {
+        let (terminator, body) = bb.instructions.split_last().unwrap();
+        let operations = context.get_basic_block(token).operations.clone();
+        for (inst, &operation) in body.iter().zip(&operations) {
+            let opcode = inst.class.opcode;
+            let instruction = Instruction::lift(opcode, &mut OperandReader::new(inst, &resolve))?;
+            context.get_operation_mut(operation).instruction =
+                instruction.ok_or(LiftError::UnexpectedInstruction(opcode))?;
+        }
+        let label = context.get_basic_block(token).label;
+        let opcode = terminator.class.opcode;
+        let terminator = Terminator::lift(opcode, &mut OperandReader::new(terminator, &resolve))?;
+        context.get_basic_block_mut(token).terminator =
+            terminator.ok_or(LiftError::MissingTerminator(label))?;
+    }
+
+    Ok(Function {
+        id,
+        control,
+        ty,
+        parameters,
+        blocks,
+    })
+}
+
+/// Collects all decorations directly applied to the given `target` id
+/// via OpDecorate.
+fn lift_decorations(
+    decorations: &mr::DecorationIndex,
+    target: spirv::Word,
+) -> LiftResult<Vec<Decoration>> {
+    let mut lifted = vec![];
+    for inst in decorations.get_decorations(target) {
+        if inst.class.opcode != spirv::Op::Decorate {
+            continue;
+        }
+        match inst.operands.get(1) {
+            Some(&mr::Operand::Decoration(d)) => {
+                lifted.push(lift_decoration(d, &inst.operands[2..])?)
+            }
+            _ => return Err(LiftError::WrongOperands(spirv::Op::Decorate)),
+        }
+    }
+    Ok(lifted)
+}
+
+/// Lifts the given `decoration` with its extra `params` into a structured
+/// decoration.
+pub(in sr) fn lift_decoration(
+    decoration: spirv::Decoration,
+    params: &[mr::Operand],
+) -> LiftResult<Decoration> {
+    use spirv::Decoration as D;
+
+    let op = spirv::Op::Decorate;
+    let lit = |index| literal(op, params, index);
+    let id = |index| id_ref(op, params, index);
+    let wrong = LiftError::WrongOperands(op);
+    Ok(match decoration {
+        D::RelaxedPrecision => Decoration::RelaxedPrecision,
+        D::SpecId => Decoration::SpecId(lit(0)?),
+        D::Block => Decoration::Block,
+        D::BufferBlock => Decoration::BufferBlock,
+        D::RowMajor => Decoration::RowMajor,
+        D::ColMajor => Decoration::ColMajor,
+        D::ArrayStride => Decoration::ArrayStride(lit(0)?),
+        D::MatrixStride => Decoration::MatrixStride(lit(0)?),
+        D::GLSLShared => Decoration::GLSLShared,
+        D::GLSLPacked => Decoration::GLSLPacked,
+        D::CPacked => Decoration::CPacked,
+        D::BuiltIn => match params.first() {
+            Some(&mr::Operand::BuiltIn(b)) => Decoration::BuiltIn(b),
+            _ => return Err(wrong),
+        },
+        D::NoPerspective => Decoration::NoPerspective,
+        D::Flat => Decoration::Flat,
+        D::Patch => Decoration::Patch,
+        D::Centroid => Decoration::Centroid,
+        D::Sample => Decoration::Sample,
+        D::Invariant => Decoration::Invariant,
+        D::Restrict => Decoration::Restrict,
+        D::Aliased => Decoration::Aliased,
+        D::Volatile => Decoration::Volatile,
+        D::Constant => Decoration::Constant,
+        D::Coherent => Decoration::Coherent,
+        D::NonWritable => Decoration::NonWritable,
+        D::NonReadable => Decoration::NonReadable,
+        D::Uniform => Decoration::Uniform,
+        D::SaturatedConversion => Decoration::SaturatedConversion,
+        D::Stream => Decoration::Stream(lit(0)?),
+        D::Location => Decoration::Location(lit(0)?),
+        D::Component => Decoration::Component(lit(0)?),
+        D::Index => Decoration::Index(lit(0)?),
+        D::Binding => Decoration::Binding(lit(0)?),
+        D::DescriptorSet => Decoration::DescriptorSet(lit(0)?),
+        D::Offset => Decoration::Offset(lit(0)?),
+        D::XfbBuffer => Decoration::XfbBuffer(lit(0)?),
+        D::XfbStride => Decoration::XfbStride(lit(0)?),
+        D::FuncParamAttr => match params.first() {
+            Some(&mr::Operand::FunctionParameterAttribute(a)) => Decoration::FuncParamAttr(a),
+            _ => return Err(wrong),
+        },
+        D::FPRoundingMode => match params.first() {
+            Some(&mr::Operand::FPRoundingMode(m)) => Decoration::FPRoundingMode(m),
+            _ => return Err(wrong),
+        },
+        D::FPFastMathMode => match params.first() {
+            Some(&mr::Operand::FPFastMathMode(m)) => Decoration::FPFastMathMode(m),
+            _ => return Err(wrong),
+        },
+        D::LinkageAttributes => match (params.first(), params.get(1)) {
+            (Some(mr::Operand::LiteralString(name)), Some(&mr::Operand::LinkageType(t))) => {
+                Decoration::LinkageAttributes(name.to_string(), t)
+            }
+            _ => return Err(wrong),
+        },
+        D::NoContraction => Decoration::NoContraction,
+        D::InputAttachmentIndex => Decoration::InputAttachmentIndex(lit(0)?),
+        D::Alignment => Decoration::Alignment(lit(0)?),
+        D::MaxByteOffset => Decoration::MaxByteOffset(lit(0)?),
+        D::AlignmentId => Decoration::AlignmentId(id(0)?),
+        D::MaxByteOffsetId => Decoration::MaxByteOffsetId(id(0)?),
+        D::ExplicitInterpAMD => Decoration::ExplicitInterpAMD,
+        D::OverrideCoverageNV => Decoration::OverrideCoverageNV,
+        D::PassthroughNV => Decoration::PassthroughNV,
+        D::ViewportRelativeNV => Decoration::ViewportRelativeNV,
+        D::SecondaryViewportRelativeNV => Decoration::SecondaryViewportRelativeNV(lit(0)?),
+        D::NonUniformEXT => Decoration::NonUniformEXT,
+        D::PerPrimitiveEXT => Decoration::PerPrimitiveEXT,
+        D::HlslCounterBufferGOOGLE => Decoration::HlslCounterBufferGOOGLE(id(0)?),
+        D::HlslSemanticGOOGLE => match params.first() {
+            Some(mr::Operand::LiteralString(s)) => Decoration::HlslSemanticGOOGLE(s.to_string()),
+            _ => return Err(wrong),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use sr;
+
+    use binary::Assemble;
+    use super::TypeEnum;
+    use sr::constants::ConstantEnum;
+
+    #[test]
+    fn test_missing_memory_model() {
+        let b = mr::Builder::new();
+        assert_eq!(
+            sr::LiftError::MissingMemoryModel,
+            sr::Module::from_data(&b.module()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_lift_global_variables() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let input = b.type_pointer(None, spirv::StorageClass::Input, vec4);
+        let output = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let private = b.type_pointer(None, spirv::StorageClass::Private, float);
+        let one = b.constant_f32(float, 1.0);
+        let position = b.variable(input, None, spirv::StorageClass::Input, None);
+        let color = b.variable(output, None, spirv::StorageClass::Output, None);
+        b.variable(private, None, spirv::StorageClass::Private, Some(one));
+        b.decorate(position, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(0)]);
+        b.decorate(
+            color,
+            spirv::Decoration::BuiltIn,
+            vec![mr::Operand::BuiltIn(spirv::BuiltIn::FragCoord)],
+        );
+
+        let m = sr::Module::from_data(&b.module()).unwrap();
+        assert_eq!(vec![spirv::Capability::Shader], m.capabilities);
+        assert_eq!(spirv::AddressingModel::Logical, m.addressing_model);
+        assert_eq!(spirv::MemoryModel::GLSL450, m.memory_model);
+        assert_eq!(3, m.global_variables.len());
+
+        let v = m.context.get_variable(m.global_variables[0]);
+        assert_eq!(spirv::StorageClass::Input, v.storage_class);
+        assert!(m.context.get_type(v.ty).is_pointer_type());
+        assert_eq!(vec![sr::Decoration::Location(0)], v.decorations);
+
+        let v = m.context.get_variable(m.global_variables[1]);
+        assert_eq!(spirv::StorageClass::Output, v.storage_class);
+        assert_eq!(
+            vec![sr::Decoration::BuiltIn(spirv::BuiltIn::FragCoord)],
+            v.decorations
+        );
+
+        let v = m.context.get_variable(m.global_variables[2]);
+        assert!(!v.is_interface_variable());
+        let init = m.context.get_constant(v.initializer.unwrap());
+        assert!(init.is_f32_constant());
+    }
+
+    #[test]
+    fn test_lift_module_metadata() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        let glsl = b.ext_inst_import("GLSL.std.450");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let block = b.type_struct(vec![float]);
+        b.name(block, "Block");
+        b.member_name(block, 0, "value");
+        let output = b.type_pointer(None, spirv::StorageClass::Output, float);
+        let color = b.variable(output, None, spirv::StorageClass::Output, None);
+        b.name(color, "color");
+        let voidfvoid = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::NONE, voidfvoid).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Fragment, f, "main", vec![color]);
+        b.execution_mode(f, spirv::ExecutionMode::OriginUpperLeft, vec![]);
+        b.entry_point(spirv::ExecutionModel::GLCompute, f, "cs", vec![]);
+        b.execution_mode(f, spirv::ExecutionMode::LocalSize, vec![8, 8, 1]);
+
+        let m = sr::Module::from_data(&b.module()).unwrap();
+        assert_eq!(vec!["SPV_KHR_storage_buffer_storage_class".to_string()], m.extensions);
+        assert_eq!(Some(&"GLSL.std.450".to_string()), m.ext_inst_imports.get(&glsl));
+        assert_eq!(Some(&"Block".to_string()), m.names.get(&block));
+        assert_eq!(Some(&"value".to_string()), m.member_names.get(&(block, 0)));
+        assert_eq!(
+            Some("color".to_string()),
+            m.context.get_variable(m.global_variables[0]).name
+        );
+
+        assert_eq!(2, m.entry_points.len());
+        let main = &m.entry_points[0];
+        assert_eq!(spirv::ExecutionModel::Fragment, main.execution_model);
+        assert_eq!(f, main.function);
+        assert_eq!("main", main.name);
+        assert_eq!(vec![m.global_variables[0]], main.interface);
+        assert_eq!(
+            vec![
+                (spirv::ExecutionMode::OriginUpperLeft, vec![]),
+                (spirv::ExecutionMode::LocalSize, vec![8, 8, 1]),
+            ],
+            main.execution_modes
+        );
+    }
+
+    #[test]
+    fn test_lift_forward_pointer_and_wide_constants() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Physical64, spirv::MemoryModel::OpenCL);
+        let storage = spirv::StorageClass::CrossWorkgroup;
+        let node_ptr = b.id();
+        b.type_forward_pointer(node_ptr, storage);
+        let node = b.type_struct(vec![node_ptr]);
+        b.type_pointer(Some(node_ptr), storage, node);
+        let head_ptr = b.type_pointer(None, storage, node_ptr);
+        b.variable(head_ptr, None, storage, None);
+        let ulong = b.type_int(64, 0);
+        let double = b.type_float(64);
+        let big = b.constant_u64(ulong, 1 << 40);
+        let half = b.constant_f64(double, 0.5);
+        let ulong_ptr = b.type_pointer(None, spirv::StorageClass::Private, ulong);
+        let double_ptr = b.type_pointer(None, spirv::StorageClass::Private, double);
+        b.variable(ulong_ptr, None, spirv::StorageClass::Private, Some(big));
+        b.variable(double_ptr, None, spirv::StorageClass::Private, Some(half));
+
+        let m = sr::Module::from_data(&b.module()).unwrap();
+        let pointee = |ty: sr::TypeToken| match m.context.get_type(ty).ty {
+            TypeEnum::Pointer { pointee_type, .. } => pointee_type,
+            _ => panic!("expected a pointer type"),
+        };
+        // The struct refers to the real pointer type, not a placeholder.
+        let node_ptr = pointee(m.context.get_variable(m.global_variables[0]).ty);
+        let node = pointee(node_ptr);
+        match m.context.get_type(node).ty {
+            TypeEnum::Struct { ref field_types } => assert_eq!(vec![node_ptr], *field_types),
+            _ => panic!("expected a struct type"),
+        }
+
+        let init = |index: usize| {
+            let variable = m.context.get_variable(m.global_variables[index]);
+            m.context.get_constant(variable.initializer.unwrap())
+        };
+        assert!(init(1).is_u64_constant());
+        assert!(init(2).is_f64_constant());
+    }
+
+    #[test]
+    fn test_lift_narrow_constants() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::Int16);
+        b.capability(spirv::Capability::Int8);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let short = b.type_int(16, 1);
+        let ubyte = b.type_int(8, 0);
+        // Signed literals narrower than a word are sign-extended.
+        let minus_two = b.constant_u32(short, -2i32 as u32);
+        let byte = b.constant_u32(ubyte, 0xab);
+        let short_ptr = b.type_pointer(None, spirv::StorageClass::Private, short);
+        let ubyte_ptr = b.type_pointer(None, spirv::StorageClass::Private, ubyte);
+        b.variable(short_ptr, None, spirv::StorageClass::Private, Some(minus_two));
+        b.variable(ubyte_ptr, None, spirv::StorageClass::Private, Some(byte));
+
+        let words = b.module().assemble();
+        let m = sr::Module::from_data(&mr::load_words(words).unwrap()).unwrap();
+        let init = |index: usize| {
+            let variable = m.context.get_variable(m.global_variables[index]);
+            m.context.get_constant(variable.initializer.unwrap())
+        };
+        assert!(init(0).is_i16_constant());
+        assert_eq!(ConstantEnum::I16(-2), init(0).c);
+        assert!(init(1).is_u8_constant());
+        assert_eq!(ConstantEnum::U8(0xab), init(1).c);
+    }
+
+    #[test]
+    fn test_lift_undefined_interface_variable() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        b.entry_point(spirv::ExecutionModel::Vertex, 1, "main", vec![7]);
+        assert_eq!(
+            sr::LiftError::UndefinedVariable(7),
+            sr::Module::from_data(&b.module()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_lift_interface_version() {
+        let build = |major: u8, minor: u8| {
+            let mut b = mr::Builder::new();
+            b.set_version(major, minor);
+            b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+            let float = b.type_float(32);
+            let private = b.type_pointer(None, spirv::StorageClass::Private, float);
+            let v = b.variable(private, None, spirv::StorageClass::Private, None);
+            b.entry_point(spirv::ExecutionModel::Vertex, 9, "main", vec![v]);
+            sr::Module::from_data(&b.module())
+        };
+        // Interfaces are checked by validate::check_version_rules instead.
+        assert_eq!(1, build(1, 3).unwrap().entry_points[0].interface.len());
+        assert_eq!(1, build(1, 4).unwrap().entry_points[0].interface.len());
+    }
+
+    #[test]
+    fn test_lift_undefined_type() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        b.variable(42, None, spirv::StorageClass::Input, None);
+        assert_eq!(
+            sr::LiftError::UndefinedType(42),
+            sr::Module::from_data(&b.module()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_lift_ray_tracing_pipeline() {
+        use binary::{Assemble, Disassemble};
+        use spirv::StorageClass::{CallableDataKHR, RayPayloadKHR, UniformConstant};
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 4);
+        b.capability(spirv::Capability::RayTracingKHR);
+        b.extension("SPV_KHR_ray_tracing");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let vec3 = b.type_vector(float, 3);
+        let accel = b.type_acceleration_structure_khr();
+        let accel_ptr = b.type_pointer(None, UniformConstant, accel);
+        let payload_ptr = b.type_pointer(None, RayPayloadKHR, vec3);
+        let callable_ptr = b.type_pointer(None, CallableDataKHR, float);
+        let voidf = b.type_function(void, vec![]);
+        let zero = b.constant_u32(uint, 0);
+        let mask = b.constant_u32(uint, 0xff);
+        let tmin = b.constant_f32(float, 0.0);
+        let tmax = b.constant_f32(float, 100.0);
+        let dir = b.constant_composite(vec3, vec![tmin, tmin, tmax]);
+        let tlas = b.variable(accel_ptr, None, UniformConstant, None);
+        let payload = b.variable(payload_ptr, None, RayPayloadKHR, None);
+        let callable = b.variable(callable_ptr, None, CallableDataKHR, None);
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let handle = b.load(accel, None, tlas, None, vec![]).unwrap();
+        b.trace_ray_khr(handle, zero, mask, zero, zero, zero, dir, tmin, dir, tmax, payload)
+            .unwrap();
+        b.execute_callable_khr(zero, callable).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let interface = vec![tlas, payload, callable];
+        b.entry_point(spirv::ExecutionModel::RayGenerationKHR, main, "main", interface);
+
+        // The shader round-trips through the binary form.
+        let module = b.module();
+        let disas = module.disassemble();
+        let module = mr::load_words(module.assemble()).unwrap();
+        assert_eq!(disas, module.disassemble());
+        assert!(disas.contains("OpEntryPoint RayGenerationKHR %18 \"main\" %15 %16 %17"));
+        assert!(disas.contains("%5 = OpTypeAccelerationStructureKHR"));
+        assert!(disas.contains("OpTraceRayKHR %20 %10 %11 %10 %10 %10 %14 %12 %14 %13 %16"));
+        assert!(disas.contains("OpExecuteCallableKHR %10 %17"));
+
+        let mut m = sr::Module::from_data(&module).unwrap();
+        let entry = &m.entry_points[0];
+        assert_eq!(spirv::ExecutionModel::RayGenerationKHR, entry.execution_model);
+        assert_eq!(3, entry.interface.len());
+        let v = m.context.get_variable(m.global_variables[1]);
+        assert_eq!(RayPayloadKHR, v.storage_class);
+        let token = m.context.type_acceleration_structure_khr();
+        assert!(m.context.get_type(token).is_acceleration_structure_khr_type());
+        assert!(!m.context.get_type(token).is_ray_query_khr_type());
+    }
+
+    #[test]
+    fn test_lift_mesh_shader() {
+        use binary::{Assemble, Disassemble};
+        use spirv::ExecutionMode as M;
+        use spirv::StorageClass::{Output, TaskPayloadWorkgroupEXT};
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 4);
+        b.capability(spirv::Capability::MeshShadingEXT);
+        b.extension("SPV_EXT_mesh_shader");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let uvec3 = b.type_vector(uint, 3);
+        let one = b.constant_u32(uint, 1);
+        let three = b.constant_u32(uint, 3);
+        let positions = b.type_array(vec4, three);
+        let triangles = b.type_array(uvec3, one);
+        let primitive_ids = b.type_array(uint, one);
+        let positions = b.type_pointer(None, Output, positions);
+        let triangles = b.type_pointer(None, Output, triangles);
+        let primitive_ids = b.type_pointer(None, Output, primitive_ids);
+        let payload = b.type_pointer(None, TaskPayloadWorkgroupEXT, uint);
+        let positions = b.variable(positions, None, Output, None);
+        let triangles = b.variable(triangles, None, Output, None);
+        let primitive_ids = b.variable(primitive_ids, None, Output, None);
+        let payload = b.variable(payload, None, TaskPayloadWorkgroupEXT, None);
+        let builtin = |b: spirv::BuiltIn| vec![mr::Operand::BuiltIn(b)];
+        let decoration = spirv::Decoration::BuiltIn;
+        b.decorate(positions, decoration, builtin(spirv::BuiltIn::Position));
+        b.decorate(triangles, decoration, builtin(spirv::BuiltIn::PrimitiveTriangleIndicesEXT));
+        b.decorate(primitive_ids, decoration, builtin(spirv::BuiltIn::PrimitiveId));
+        b.decorate(primitive_ids, spirv::Decoration::PerPrimitiveEXT, vec![]);
+        let voidf = b.type_function(void, vec![]);
+
+        let task = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.store(payload, three, None, vec![]).unwrap();
+        b.emit_mesh_tasks_ext(one, one, one, Some(payload)).unwrap();
+        b.end_function().unwrap();
+        let mesh = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.set_mesh_outputs_ext(three, one).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::TaskEXT, task, "task", vec![payload]);
+        let interface = vec![positions, triangles, primitive_ids];
+        b.entry_point(spirv::ExecutionModel::MeshEXT, mesh, "mesh", interface);
+        for &f in &[task, mesh] {
+            b.execution_mode(f, M::LocalSize, vec![1, 1, 1]);
+        }
+        b.execution_mode(mesh, M::OutputVertices, vec![3]);
+        b.execution_mode(mesh, M::OutputPrimitivesEXT, vec![1]);
+        b.execution_mode(mesh, M::OutputTrianglesEXT, vec![]);
+
+        // Parse, reflect, and assemble again.
+        let words = b.module().assemble();
+        let module = mr::load_words(&words).unwrap();
+        assert_eq!(words, module.assemble());
+        let disas = module.disassemble();
+        assert!(disas.contains("OpExecutionMode %22 OutputPrimitivesEXT 1"));
+        assert!(disas.contains("OpEmitMeshTasksEXT %6 %6 %6 %18"));
+        assert!(disas.contains("OpSetMeshOutputsEXT %7 %6"));
+        assert!(module.check_target_env(mr::TargetEnv::Vulkan1_2).is_empty());
+
+        let m = sr::Module::from_data(&module).unwrap();
+        assert_eq!(None, m.entry_points[0].mesh_outputs());
+        let entry = &m.entry_points[1];
+        assert_eq!(
+            Some(sr::MeshOutputs {
+                max_vertices: 3,
+                max_primitives: 1,
+                topology: M::OutputTrianglesEXT,
+            }),
+            entry.mesh_outputs()
+        );
+        assert_eq!(Some(&[1, 1, 1][..]), entry.execution_mode(M::LocalSize));
+        assert_eq!(vec![m.global_variables[2]], m.per_primitive_outputs(entry));
+    }
+
+    #[test]
+    fn test_lift_cooperative_matrix() {
+        use binary::{Assemble, Disassemble};
+        use spirv::CooperativeMatrixUse::*;
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 3);
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::CooperativeMatrixKHR);
+        b.extension("SPV_KHR_cooperative_matrix");
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let subgroup = b.constant_u32(uint, spirv::Scope::Subgroup as u32);
+        let sixteen = b.constant_u32(uint, 16);
+        let row_major = spirv::CooperativeMatrixLayout::RowMajorKHR as u32;
+        let row_major = b.constant_u32(uint, row_major);
+        let uses: Vec<_> = [MatrixAKHR, MatrixBKHR, MatrixAccumulatorKHR]
+            .iter()
+            .map(|&u| {
+                let u = b.constant_u32(uint, u as u32);
+                b.type_cooperative_matrix_khr(float, subgroup, sixteen, sixteen, u)
+            })
+            .collect();
+        let array = b.type_array(float, sixteen);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Workgroup, array);
+        let voidf = b.type_function(void, vec![]);
+        let var = b.variable(ptr, None, spirv::StorageClass::Workgroup, None);
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let mut loaded = vec![];
+        for &ty in &uses {
+            let stride = Some(sixteen);
+            let m = b.cooperative_matrix_load_khr(ty, None, var, row_major, stride, None, vec![]);
+            loaded.push(m.unwrap());
+        }
+        let operands = Some(spirv::CooperativeMatrixOperands::SATURATING_ACCUMULATION_KHR);
+        let (ma, mb, mc) = (loaded[0], loaded[1], loaded[2]);
+        let d = b.cooperative_matrix_mul_add_khr(uses[2], None, ma, mb, mc, operands).unwrap();
+        b.cooperative_matrix_length_khr(uint, None, uses[2]).unwrap();
+        b.cooperative_matrix_store_khr(var, d, row_major, None, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, main, "main", vec![]);
+
+        // The shader round-trips through the binary form.
+        let module = b.module();
+        let disas = module.disassemble();
+        let module = mr::load_words(module.assemble()).unwrap();
+        assert_eq!(disas, module.disassemble());
+        assert!(disas.contains("%8 = OpTypeCooperativeMatrixKHR %3 %4 %5 %5 %7"));
+        assert!(disas.contains("%19 = OpCooperativeMatrixLoadKHR  %8  %16 %6 %5"));
+        assert!(disas.contains(
+            "%22 = OpCooperativeMatrixMulAddKHR  %12  %19 %20 %21 SaturatingAccumulationKHR"
+        ));
+        assert!(disas.contains("%23 = OpCooperativeMatrixLengthKHR  %2  %12"));
+        assert!(disas.contains("OpCooperativeMatrixStoreKHR %16 %22 %6"));
+
+        let mut m = sr::Module::from_data(&module).unwrap();
+        let (float, sixteen, two) = (
+            m.context.type_float(32),
+            m.context.constant_u32(16),
+            m.context.constant_u32(2),
+        );
+        let token = m.context.type_cooperative_matrix_khr(float, subgroup, sixteen, sixteen, two);
+        assert!(m.context.get_type(token).is_cooperative_matrix_khr_type());
+    }
+
+    #[test]
+    fn test_lift_debug_info() {
+        use spirv::DebugInfoOp as Op;
+
+        let mut b = mr::Builder::new();
+        let set = b.ext_inst_import(sr::DEBUG_INFO_SET);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let file = b.string("shader.glsl");
+        let name = b.string("float");
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let version = b.constant_u32(uint, 100);
+        let size = b.constant_u32(uint, 32);
+        let float = b.constant_u32(uint, spirv::DebugBaseTypeAttributeEncoding::Float as u32);
+        let flags = b.constant_u32(uint, spirv::DebugInfoFlags::FLAG_IS_PUBLIC.bits());
+        let glsl = b.constant_u32(uint, spirv::SourceLanguage::GLSL as u32);
+        let mut module = b.module();
+
+        let mut debug = |id, op: Op, args: &[spirv::Word]| {
+            let mut operands = vec![
+                mr::Operand::IdRef(set),
+                mr::Operand::LiteralExtInstInteger(op as u32),
+            ];
+            operands.extend(args.iter().map(|&a| mr::Operand::IdRef(a)));
+            module.types_global_values.push(
+                mr::Instruction::new(spirv::Op::ExtInst, Some(void), Some(id), operands));
+        };
+        debug(20, Op::DebugSource, &[file]);
+        debug(21, Op::DebugCompilationUnit, &[version, version, 20, glsl]);
+        debug(22, Op::DebugTypeBasic, &[name, size, float, flags]);
+
+        let m = sr::Module::from_data(&module).unwrap();
+        assert_eq!(m.debug_info.len(), 3);
+        let source = &m.debug_info[0];
+        assert_eq!((source.result_id, source.op), (20, Op::DebugSource));
+        assert_eq!(source.string(0), Some("shader.glsl"));
+        let unit = &m.debug_info[1];
+        assert_eq!(unit.uint(0), Some(100));
+        assert_eq!(unit.id(2), Some(20));
+        assert_eq!(unit.operands[3], sr::DebugOperand::SourceLanguage(spirv::SourceLanguage::GLSL));
+        let basic = &m.debug_info[2];
+        assert_eq!(basic.string(0), Some("float"));
+        assert_eq!(basic.uint(1), Some(32));
+        assert_eq!(basic.operands[2],
+                   sr::DebugOperand::Encoding(spirv::DebugBaseTypeAttributeEncoding::Float));
+        assert_eq!(basic.operands[3],
+                   sr::DebugOperand::Flags(spirv::DebugInfoFlags::FLAG_IS_PUBLIC));
+    }
+
+    #[test]
+    fn test_lift_function_cfg() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let uint = b.type_int(32, 0);
+        let cond = b.constant_true(boolean);
+        let zero = b.constant_u32(uint, 0);
+        let voidf = b.type_function(void, vec![]);
+        let f = b.begin_function(void, None, spirv::FunctionControl::INLINE, voidf).unwrap();
+        let (then, other, exit) = (b.id(), b.id(), b.id());
+        b.begin_basic_block(None).unwrap();
+        b.branch_conditional(cond, then, other, vec![1, 2]).unwrap();
+        b.begin_basic_block(Some(then)).unwrap();
+        b.switch(zero, exit, vec![(0, other), (7, exit)]).unwrap();
+        b.begin_basic_block(Some(other)).unwrap();
+        b.branch(exit).unwrap();
+        b.begin_basic_block(Some(exit)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let m = sr::Module::from_data(&b.module()).unwrap();
+        assert_eq!(1, m.functions.len());
+        let function = &m.functions[0];
+        assert_eq!(f, function.id);
+        assert_eq!(spirv::FunctionControl::INLINE, function.control);
+        assert!(m.context.get_type(function.ty).is_function_type());
+        assert_eq!(4, function.blocks.len());
+        let blocks = &function.blocks;
+        let (entry, then_bb, other_bb, exit_bb) = (blocks[0], blocks[1], blocks[2], blocks[3]);
+        assert_eq!(Some(entry), function.entry_block());
+        assert_eq!(then, m.context.get_basic_block(then_bb).label);
+
+        assert_matches!(
+            m.context.get_basic_block(entry).terminator,
+            sr::Terminator::BranchConditional {
+                condition: sr::Value::Constant(_),
+                true_label,
+                false_label,
+                ref branch_weights,
+            } if true_label == then_bb && false_label == other_bb && *branch_weights == [1, 2]
+        );
+        let switch = &m.context.get_basic_block(then_bb).terminator;
+        assert_matches!(
+            *switch,
+            sr::Terminator::Switch { selector: sr::Value::Constant(_), default, ref target }
+                if default == exit_bb && *target == [(0, other_bb), (7, exit_bb)]
+        );
+        assert_eq!(vec![exit_bb, other_bb, exit_bb], switch.successors());
+        assert_eq!(
+            vec![exit_bb],
+            m.context.get_basic_block(other_bb).terminator.successors()
+        );
+        assert_eq!(sr::Terminator::Return, m.context.get_basic_block(exit_bb).terminator);
+    }
+
+    #[test]
+    fn test_lift_undefined_label() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.branch(42).unwrap();
+        b.end_function().unwrap();
+        assert_eq!(
+            sr::LiftError::UndefinedLabel(42),
+            sr::Module::from_data(&b.module()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_lift_function_instructions() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let uint = b.type_int(32, 0);
+        let one = b.constant_u32(uint, 1);
+        let uintf = b.type_function(uint, vec![uint]);
+        b.begin_function(uint, None, spirv::FunctionControl::NONE, uintf).unwrap();
+        let param = b.function_parameter(uint).unwrap();
+        let (entry, exit) = (b.id(), b.id());
+        b.begin_basic_block(Some(entry)).unwrap();
+        let sum = b.iadd(uint, None, param, one).unwrap();
+        b.branch(exit).unwrap();
+        b.begin_basic_block(Some(exit)).unwrap();
+        let phi = b.phi(uint, None, vec![(sum, entry)]).unwrap();
+        b.ret_value(phi).unwrap();
+        b.end_function().unwrap();
+
+        let m = sr::Module::from_data(&b.module()).unwrap();
+        let function = &m.functions[0];
+        assert_eq!(1, function.parameters.len());
+        let param_token = function.parameters[0];
+        assert_eq!(param, m.context.get_parameter(param_token).id);
+
+        let entry_bb = m.context.get_basic_block(function.blocks[0]);
+        assert_eq!(1, entry_bb.operations.len());
+        let add = m.context.get_operation(entry_bb.operations[0]);
+        assert_eq!(Some(sum), add.result_id);
+        assert!(m.context.get_type(add.result_type.unwrap()).is_int_type());
+        assert_matches!(
+            add.instruction,
+            sr::Instruction::IAdd {
+                operand_1: sr::Value::Parameter(p),
+                operand_2: sr::Value::Constant(_),
+            } if p == param_token
+        );
+
+        let exit_bb = m.context.get_basic_block(function.blocks[1]);
+        let phi_token = exit_bb.operations[0];
+        assert_eq!(
+            sr::Instruction::Phi {
+                value_label_pairs: vec![(
+                    sr::Value::Operation(entry_bb.operations[0]),
+                    sr::Value::BasicBlock(function.blocks[0]),
+                )],
+            },
+            m.context.get_operation(phi_token).instruction
+        );
+        assert_eq!(
+            sr::Terminator::ReturnValue { value: sr::Value::Operation(phi_token) },
+            exit_bb.terminator
+        );
+    }
+}
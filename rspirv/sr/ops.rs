@@ -0,0 +1,209 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use super::{
+    BasicBlock,
+    ConstantToken,
+    LiftError,
+    LiftResult,
+    Operation,
+    Parameter,
+    Token,
+    TypeToken,
+    VariableToken,
+};
+
+/// A value referenced by an id operand of an instruction inside a function.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// A type, e.g., the operand of OpCooperativeMatrixLengthKHR.
+    Type(TypeToken),
+    /// A (specialization) constant.
+    Constant(ConstantToken),
+    /// A global variable.
+    Variable(VariableToken),
+    /// A parameter of the enclosing function.
+    Parameter(Token<Parameter>),
+    /// The result of an instruction in the enclosing function.
+    Operation(Token<Operation>),
+    /// A basic block of the enclosing function, e.g., an OpPhi parent.
+    BasicBlock(Token<BasicBlock>),
+    /// An id not lifted into structured representation, e.g., of a
+    /// function, an extended instruction set, or an OpString.
+    Id(spirv::Word),
+}
+
+/// Reads the operands of a data representation instruction in order,
+/// resolving ids into values.
+pub(in sr) struct OperandReader<'a> {
+    opcode: spirv::Op,
+    operands: &'a [mr::Operand],
+    resolve: &'a dyn Fn(spirv::Word) -> Value,
+}
+
+impl<'a> OperandReader<'a> {
+    pub(in sr) fn new(
+        inst: &'a mr::Instruction,
+        resolve: &'a dyn Fn(spirv::Word) -> Value,
+    ) -> OperandReader<'a> {
+        OperandReader {
+            opcode: inst.class.opcode,
+            operands: &inst.operands[..],
+            resolve,
+        }
+    }
+
+    /// Consumes the next operand if `extract` accepts it.
+    fn next_if<T, F: Fn(&mr::Operand) -> Option<T>>(&mut self, extract: F) -> Option<T> {
+        let v = self.operands.first().and_then(extract)?;
+        self.operands = &self.operands[1..];
+        Some(v)
+    }
+
+    fn required<T, F: Fn(&mr::Operand) -> Option<T>>(&mut self, extract: F) -> LiftResult<T> {
+        self.next_if(extract).ok_or(LiftError::WrongOperands(self.opcode))
+    }
+
+    fn optional<T, F: Fn(&mr::Operand) -> Option<T>>(
+        &mut self,
+        extract: F,
+    ) -> LiftResult<Option<T>> {
+        Ok(self.next_if(extract))
+    }
+
+    fn repeated<T, F: Fn(&mr::Operand) -> Option<T>>(&mut self, extract: F) -> LiftResult<Vec<T>> {
+        let mut values = vec![];
+        while let Some(v) = self.next_if(&extract) {
+            values.push(v);
+        }
+        Ok(values)
+    }
+
+    fn value(&mut self) -> LiftResult<Value> {
+        let id = self.required(id)?;
+        Ok((self.resolve)(id))
+    }
+
+    fn optional_value(&mut self) -> LiftResult<Option<Value>> {
+        Ok(self.next_if(id).map(|id| (self.resolve)(id)))
+    }
+
+    fn values(&mut self) -> LiftResult<Vec<Value>> {
+        let ids = self.repeated(id)?;
+        Ok(ids.into_iter().map(|id| (self.resolve)(id)).collect())
+    }
+
+    fn value_literal_pairs(&mut self) -> LiftResult<Vec<(Value, u32)>> {
+        let mut pairs = vec![];
+        while let Some(id) = self.next_if(id) {
+            pairs.push(((self.resolve)(id), self.required(literal)?));
+        }
+        Ok(pairs)
+    }
+
+    fn value_pairs(&mut self) -> LiftResult<Vec<(Value, Value)>> {
+        let mut pairs = vec![];
+        while let Some(id) = self.next_if(id) {
+            pairs.push(((self.resolve)(id), self.value()?));
+        }
+        Ok(pairs)
+    }
+
+    /// Reads the label of a branch target.
+    fn block(&mut self) -> LiftResult<Token<BasicBlock>> {
+        let id = self.required(id)?;
+        match (self.resolve)(id) {
+            Value::BasicBlock(token) => Ok(token),
+            _ => Err(LiftError::UndefinedLabel(id)),
+        }
+    }
+
+    /// Reads the (literal, label) pairs of an OpSwitch.
+    fn literal_block_pairs(&mut self) -> LiftResult<Vec<(u64, Token<BasicBlock>)>> {
+        let mut pairs = vec![];
+        while let Some(v) = self.next_if(|o| match *o {
+            mr::Operand::LiteralInt32(v) => Some(u64::from(v)),
+            mr::Operand::LiteralInt64(v) => Some(v),
+            _ => None,
+        }) {
+            pairs.push((v, self.block()?));
+        }
+        Ok(pairs)
+    }
+}
+
+/// Returns a closure extracting the value of operands of the given kind.
+macro_rules! kind {
+    ($kind: ident) => {
+        |o: &mr::Operand| match *o {
+            mr::Operand::$kind(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+fn id(o: &mr::Operand) -> Option<spirv::Word> {
+    match *o {
+        mr::Operand::IdRef(v) | mr::Operand::IdScope(v) | mr::Operand::IdMemorySemantics(v) => {
+            Some(v)
+        }
+        _ => None,
+    }
+}
+
+fn literal(o: &mr::Operand) -> Option<u32> {
+    match *o {
+        mr::Operand::LiteralInt32(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn ext_inst_integer(o: &mr::Operand) -> Option<u32> {
+    match *o {
+        mr::Operand::LiteralExtInstInteger(v) => Some(v),
+        mr::Operand::ExtInstOpcode(inst) => Some(inst.opcode),
+        _ => None,
+    }
+}
+
+fn string(o: &mr::Operand) -> Option<String> {
+    match *o {
+        mr::Operand::LiteralString(ref s) => Some(s.to_string()),
+        _ => None,
+    }
+}
+
+include!("instruction.rs");
+
+impl Terminator {
+    /// Returns the basic blocks control may be transferred to by this
+    /// terminator, in operand order.
+    pub fn successors(&self) -> Vec<Token<BasicBlock>> {
+        match *self {
+            Terminator::Branch { target_label } => vec![target_label],
+            Terminator::BranchConditional { true_label, false_label, .. } => {
+                vec![true_label, false_label]
+            }
+            Terminator::Switch { default, ref target, .. } => {
+                let mut successors = vec![default];
+                successors.extend(target.iter().map(|&(_, label)| label));
+                successors
+            }
+            _ => vec![],
+        }
+    }
+}
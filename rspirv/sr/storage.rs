@@ -0,0 +1,98 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fmt, hash};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::Context;
+
+/// The generation handed out to the next created context.
+static NEXT_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a generation distinct from those of all contexts created so far.
+pub(in sr) fn next_generation() -> usize {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A token for representing an object of type `T` allocated in a
+/// [`Context`](struct.Context.html).
+///
+/// Unlike the per-kind tokens, `Token` is generic over the object it refers
+/// to, so it is `Copy` and comparable regardless of `T`. Each token also
+/// records the generation of the context that created it, so that using it
+/// with another context is detected instead of silently looking up an
+/// unrelated object.
+pub struct Token<T> {
+    index: usize,
+    generation: usize,
+    marker: PhantomData<T>,
+}
+
+/// Objects allocated in a [`Context`](struct.Context.html) and referred to
+/// by [`Token`](struct.Token.html)s.
+pub trait Object: Sized {
+    #[doc(hidden)]
+    fn storage(context: &Context) -> &[Self];
+    #[doc(hidden)]
+    fn storage_mut(context: &mut Context) -> &mut Vec<Self>;
+}
+
+impl<T> Token<T> {
+    pub(in sr) fn new(index: usize, generation: usize) -> Token<T> {
+        Token {
+            index,
+            generation,
+            marker: PhantomData,
+        }
+    }
+
+    pub(in sr) fn get(&self) -> usize {
+        self.index
+    }
+
+    pub(in sr) fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+// Implemented by hand because deriving would require `T` to implement them.
+impl<T> Copy for Token<T> {}
+
+impl<T> Clone for Token<T> {
+    fn clone(&self) -> Token<T> {
+        *self
+    }
+}
+
+impl<T> PartialEq for Token<T> {
+    fn eq(&self, other: &Token<T>) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Token<T> {}
+
+impl<T> hash::Hash for Token<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Token<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Token({})", self.index)
+    }
+}
@@ -336,4 +336,53 @@ impl Context {
             TypeToken::new(self.types.len() - 1)
         }
     }
+    pub fn type_cooperative_matrix_khr(
+        &mut self,
+        component_type: TypeToken,
+        scope: spirv::Word,
+        rows: ConstantToken,
+        columns: ConstantToken,
+        use_: ConstantToken,
+    ) -> TypeToken {
+        let t = Type {
+            ty: TypeEnum::CooperativeMatrixKHR {
+                component_type: component_type,
+                scope: scope,
+                rows: rows,
+                columns: columns,
+                use_: use_,
+            },
+            decorations: BTreeSet::new(),
+        };
+        if let Some(index) = self.types.iter().position(|x| *x == t) {
+            TypeToken::new(index)
+        } else {
+            self.types.push(t);
+            TypeToken::new(self.types.len() - 1)
+        }
+    }
+    pub fn type_ray_query_khr(&mut self) -> TypeToken {
+        let t = Type {
+            ty: TypeEnum::RayQueryKHR,
+            decorations: BTreeSet::new(),
+        };
+        if let Some(index) = self.types.iter().position(|x| *x == t) {
+            TypeToken::new(index)
+        } else {
+            self.types.push(t);
+            TypeToken::new(self.types.len() - 1)
+        }
+    }
+    pub fn type_acceleration_structure_khr(&mut self) -> TypeToken {
+        let t = Type {
+            ty: TypeEnum::AccelerationStructureKHR,
+            decorations: BTreeSet::new(),
+        };
+        if let Some(index) = self.types.iter().position(|x| *x == t) {
+            TypeToken::new(index)
+        } else {
+            self.types.push(t);
+            TypeToken::new(self.types.len() - 1)
+        }
+    }
 }
@@ -82,6 +82,15 @@ pub(in sr) enum TypeEnum {
     },
     PipeStorage,
     NamedBarrier,
+    CooperativeMatrixKHR {
+        component_type: TypeToken,
+        scope: spirv::Word,
+        rows: ConstantToken,
+        columns: ConstantToken,
+        use_: ConstantToken,
+    },
+    RayQueryKHR,
+    AccelerationStructureKHR,
 }
 impl Type {
     pub fn is_void_type(&self) -> bool {
@@ -222,4 +231,22 @@ impl Type {
             _ => false,
         }
     }
+    pub fn is_cooperative_matrix_khr_type(&self) -> bool {
+        match self.ty {
+            TypeEnum::CooperativeMatrixKHR { .. } => true,
+            _ => false,
+        }
+    }
+    pub fn is_ray_query_khr_type(&self) -> bool {
+        match self.ty {
+            TypeEnum::RayQueryKHR => true,
+            _ => false,
+        }
+    }
+    pub fn is_acceleration_structure_khr_type(&self) -> bool {
+        match self.ty {
+            TypeEnum::AccelerationStructureKHR => true,
+            _ => false,
+        }
+    }
 }
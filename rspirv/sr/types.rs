@@ -54,6 +54,13 @@ impl Type {
     pub fn is_composite_type(&self) -> bool {
         self.is_aggregate_type() || self.is_vector_type() || self.is_matrix_type()
     }
+
+    pub fn is_signed_int_type(&self) -> bool {
+        match self.ty {
+            TypeEnum::Int { signedness, .. } => signedness != 0,
+            _ => false,
+        }
+    }
 }
 
 impl TypeToken {
@@ -0,0 +1,62 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use spirv;
+
+use super::{ConstantToken, Decoration, TypeToken};
+
+/// The class to represent a SPIR-V variable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    /// The pointer type of this variable.
+    pub ty: TypeToken,
+    /// The storage class of the memory holding this variable.
+    pub storage_class: spirv::StorageClass,
+    /// The initial value of this variable, if any.
+    pub initializer: Option<ConstantToken>,
+    /// Decorations applied to this variable.
+    pub decorations: Vec<Decoration>,
+    /// The debug name of this variable given by OpName, if any.
+    pub name: Option<String>,
+}
+
+/// A token for representing a SPIR-V variable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VariableToken {
+    index: usize,
+}
+
+impl Variable {
+    /// Returns true if this variable is declared in the given storage class.
+    pub fn is_in_storage_class(&self, storage_class: spirv::StorageClass) -> bool {
+        self.storage_class == storage_class
+    }
+
+    /// Returns true if this variable is part of the shader interface, i.e.,
+    /// it is an input or output variable.
+    pub fn is_interface_variable(&self) -> bool {
+        self.storage_class == spirv::StorageClass::Input ||
+            self.storage_class == spirv::StorageClass::Output
+    }
+}
+
+impl VariableToken {
+    pub(in sr) fn new(index: usize) -> VariableToken {
+        VariableToken { index }
+    }
+
+    pub(in sr) fn get(&self) -> usize {
+        self.index
+    }
+}
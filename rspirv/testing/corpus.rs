@@ -0,0 +1,167 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use super::{record_seed, SeededRng};
+
+/// The shape of a generated corpus module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CorpusSize {
+    /// Number of functions, besides the entry point calling them.
+    pub functions: usize,
+    /// Number of basic blocks per function.
+    pub blocks: usize,
+    /// Number of arithmetic instructions per basic block.
+    pub instructions: usize,
+}
+
+impl CorpusSize {
+    /// A module of a few hundred instructions.
+    pub const SMALL: CorpusSize = CorpusSize { functions: 4, blocks: 4, instructions: 8 };
+    /// A module of some ten thousand instructions.
+    pub const MEDIUM: CorpusSize = CorpusSize { functions: 32, blocks: 16, instructions: 16 };
+    /// A module of some quarter million instructions, like large compute
+    /// shaders.
+    pub const LARGE: CorpusSize = CorpusSize { functions: 256, blocks: 32, instructions: 24 };
+}
+
+/// Generates a module of the given size for benchmarking and testing.
+///
+/// The module is a fragment shader whose entry point calls all other
+/// functions. These load from private variables, do some random float and
+/// vector arithmetic in straight-line basic blocks, and store the results
+/// back. The module is valid, can be lifted to the structured
+/// representation, and is the same for the same `size` and `seed`, which is
+/// recorded in it.
+pub fn generate_module(size: CorpusSize, seed: u64) -> mr::Module {
+    let mut rng = SeededRng::new(seed);
+    let mut b = mr::Builder::new();
+    b.capability(spirv::Capability::Shader);
+    b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+
+    let void = b.type_void();
+    let float = b.type_float(32);
+    let vec4 = b.type_vector(float, 4);
+    let float_ptr = b.type_pointer(None, spirv::StorageClass::Private, float);
+    let vec4_ptr = b.type_pointer(None, spirv::StorageClass::Private, vec4);
+    let voidf = b.type_function(void, vec![]);
+    let half = b.constant_f32(float, 0.5);
+    let scalar = b.variable(float_ptr, None, spirv::StorageClass::Private, None);
+    let vector = b.variable(vec4_ptr, None, spirv::StorageClass::Private, None);
+    b.name(scalar, "scalar");
+    b.name(vector, "vector");
+    let globals = Globals { float, vec4, half, scalar, vector };
+
+    let mut functions = vec![];
+    for index in 0..size.functions {
+        let function = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf)
+            .unwrap();
+        b.name(function, format!("function{}", index));
+        let labels: Vec<Word> = (0..size.blocks.max(1)).map(|_| b.id()).collect();
+        for (i, &label) in labels.iter().enumerate() {
+            b.begin_basic_block(Some(label)).unwrap();
+            generate_block(&mut b, &mut rng, size.instructions, &globals);
+            match labels.get(i + 1) {
+                Some(&next) => b.branch(next).unwrap(),
+                None => b.ret().unwrap(),
+            }
+        }
+        b.end_function().unwrap();
+        functions.push(function);
+    }
+
+    let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+    b.name(main, "main");
+    b.begin_basic_block(None).unwrap();
+    for &function in &functions {
+        b.function_call(void, None, function, vec![]).unwrap();
+    }
+    b.ret().unwrap();
+    b.end_function().unwrap();
+    b.entry_point(spirv::ExecutionModel::Fragment, main, "main", vec![]);
+    b.execution_mode(main, spirv::ExecutionMode::OriginUpperLeft, vec![]);
+
+    let mut module = b.module();
+    record_seed(&mut module, seed);
+    module
+}
+
+/// Generates a small, a medium, and a large module with
+/// [`generate_module`](fn.generate_module.html), named after their sizes.
+pub fn corpus(seed: u64) -> Vec<(&'static str, mr::Module)> {
+    vec![("small", generate_module(CorpusSize::SMALL, seed)),
+         ("medium", generate_module(CorpusSize::MEDIUM, seed)),
+         ("large", generate_module(CorpusSize::LARGE, seed))]
+}
+
+/// The ids of the globals used by generated basic blocks.
+struct Globals {
+    float: Word,
+    vec4: Word,
+    half: Word,
+    scalar: Word,
+    vector: Word,
+}
+
+fn generate_block(b: &mut mr::Builder, rng: &mut SeededRng, count: usize, globals: &Globals) {
+    let Globals { float, vec4, half, scalar, vector } = *globals;
+    let mut scalars = vec![half, b.load(float, None, scalar, None, vec![]).unwrap()];
+    let mut vectors = vec![b.load(vec4, None, vector, None, vec![]).unwrap()];
+    for _ in 0..count {
+        let x = *rng.choose(&scalars).unwrap();
+        let y = *rng.choose(&scalars).unwrap();
+        let v = *rng.choose(&vectors).unwrap();
+        match rng.below(6) {
+            0 => scalars.push(b.fadd(float, None, x, y).unwrap()),
+            1 => scalars.push(b.fsub(float, None, x, y).unwrap()),
+            2 => scalars.push(b.fmul(float, None, x, y).unwrap()),
+            3 => vectors.push(b.vector_times_scalar(vec4, None, v, x).unwrap()),
+            4 => vectors.push(b.composite_construct(vec4, None, vec![x, y, x, y]).unwrap()),
+            _ => {
+                let component = rng.below(4) as u32;
+                scalars.push(b.composite_extract(float, None, v, vec![component]).unwrap())
+            }
+        }
+    }
+    b.store(scalar, *scalars.last().unwrap(), None, vec![]).unwrap();
+    b.store(vector, *vectors.last().unwrap(), None, vec![]).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::{Assemble, Disassemble};
+    use mr;
+    use sr;
+    use testing::recorded_seed;
+    use validate;
+
+    use super::{generate_module, CorpusSize};
+
+    #[test]
+    fn test_generate_module() {
+        let m = generate_module(CorpusSize::SMALL, 7);
+        assert_eq!(Some(7), recorded_seed(&m));
+        assert_eq!(5, m.functions.len());
+        assert_eq!(m.disassemble(), generate_module(CorpusSize::SMALL, 7).disassemble());
+        assert_ne!(m.disassemble(), generate_module(CorpusSize::SMALL, 8).disassemble());
+
+        let loaded = mr::load_words(m.assemble()).unwrap();
+        assert_eq!(m.disassemble(), loaded.disassemble());
+        assert!(sr::Module::from_data(&m).is_ok());
+        assert!(validate::validate(&m).is_empty());
+    }
+}
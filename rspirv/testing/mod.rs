@@ -0,0 +1,38 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for testing tools built on this library.
+//!
+//! [`Template`](struct.Template.html)s generate families of SPIR-V
+//! assembly texts from a single source, e.g., "a struct with N members" or
+//! "a loop nest of depth K", instead of committing one fixture per member
+//! of the family. As this library has no text assembler, the generated text
+//! is meant to be compared against the output of
+//! [`Disassemble`](../binary/trait.Disassemble.html), or to be assembled
+//! by external tools like `spirv-as`.
+//!
+//! Tools generating or mutating modules randomly can draw their randomness
+//! from a [`SeededRng`](struct.SeededRng.html) and [record](fn.record_seed.html)
+//! its seed in the generated modules to make failures reproducible.
+//!
+//! [`generate_module`](fn.generate_module.html) generates valid modules of
+//! a given size, e.g., as corpora for benchmarks.
+
+pub use self::corpus::{corpus, generate_module, CorpusSize};
+pub use self::seed::{record_seed, recorded_seed, SeededRng};
+pub use self::template::{Bindings, Template, TemplateError, TemplateResult};
+
+mod corpus;
+mod seed;
+mod template;
@@ -0,0 +1,134 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+/// The prefix of the OpModuleProcessed string recording a seed.
+const SEED_PREFIX: &str = "rspirv-seed ";
+
+/// A small deterministic pseudo-random number generator (SplitMix64).
+///
+/// Tools generating or mutating modules for testing should draw all their
+/// randomness from a `SeededRng` and record its seed in the module with
+/// [`record_seed`](fn.record_seed.html), so that any failing module can be
+/// regenerated exactly from the seed. The sequence of numbers for a seed
+/// is stable across platforms and versions of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeededRng {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a generator with the given seed.
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { seed, state: seed }
+    }
+
+    /// Returns the seed this generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the next 64-bit number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next number in `0..bound`; `bound` must not be zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Returns the next boolean.
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 != 0
+    }
+
+    /// Returns a random element of `items`, or `None` if it is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+}
+
+/// Records `seed` in an OpModuleProcessed instruction of `module`,
+/// replacing any seed recorded before.
+pub fn record_seed(module: &mut mr::Module, seed: u64) {
+    module.debugs.retain(|inst| recorded(inst).is_none());
+    module.debugs.push(mr::Instruction::new(
+        spirv::Op::ModuleProcessed,
+        None,
+        None,
+        vec![mr::Operand::LiteralString(format!("{}{:#018x}", SEED_PREFIX, seed).into())],
+    ));
+}
+
+/// Returns the seed recorded in `module` by
+/// [`record_seed`](fn.record_seed.html), if any.
+pub fn recorded_seed(module: &mr::Module) -> Option<u64> {
+    module.debugs.iter().filter_map(recorded).next()
+}
+
+fn recorded(inst: &mr::Instruction) -> Option<u64> {
+    match (inst.class.opcode, inst.operands.first()) {
+        (spirv::Op::ModuleProcessed, Some(mr::Operand::LiteralString(process))) => {
+            let hex = process.strip_prefix(SEED_PREFIX)?.strip_prefix("0x")?;
+            u64::from_str_radix(hex, 16).ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+
+    use binary::Disassemble;
+    use super::{record_seed, recorded_seed, SeededRng};
+
+    #[test]
+    fn test_seeded_rng() {
+        let mut rng = SeededRng::new(42);
+        let first: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+        let mut again = SeededRng::new(42);
+        assert_eq!(first, (0..4).map(|_| again.next_u64()).collect::<Vec<_>>());
+        // The reference output of SplitMix64.
+        assert_eq!(0xe220_a839_7b1d_cdaf, SeededRng::new(0).next_u64());
+        assert_ne!(first[0], SeededRng::new(43).next_u64());
+        assert_eq!(42, rng.seed());
+        assert!(rng.below(10) < 10);
+        assert_eq!(None, rng.choose::<u32>(&[]));
+        assert_eq!(Some(&7), rng.choose(&[7]));
+    }
+
+    #[test]
+    fn test_record_seed() {
+        let mut m = mr::Module::new();
+        assert_eq!(None, recorded_seed(&m));
+        record_seed(&mut m, 1);
+        record_seed(&mut m, 0xdead_beef);
+        assert_eq!(1, m.debugs.len());
+        assert_eq!("OpModuleProcessed \"rspirv-seed 0x00000000deadbeef\"",
+                   m.debugs[0].disassemble());
+        assert_eq!(Some(0xdead_beef), recorded_seed(&m));
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::{error, fmt, result};
+
+/// Template parsing and rendering errors.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplateError {
+    /// A `{{` without matching `}}`, at the given byte offset.
+    UnclosedTag(usize),
+    /// A malformed tag, with its content.
+    InvalidTag(String),
+    /// A `{{#repeat}}` without matching `{{/repeat}}`.
+    UnclosedRepeat(String),
+    /// A `{{/repeat}}` without matching `{{#repeat}}`.
+    UnexpectedEnd,
+    /// A reference to a parameter without value.
+    UndefinedParameter(String),
+    /// A parameter used in arithmetic or as a bound which is not a number.
+    NotANumber(String),
+}
+
+impl TemplateError {
+    /// Gives an descriptive string for each error.
+    fn describe(&self) -> &str {
+        match *self {
+            TemplateError::UnclosedTag(_) => "unclosed tag",
+            TemplateError::InvalidTag(_) => "invalid tag",
+            TemplateError::UnclosedRepeat(_) => "unclosed repeat",
+            TemplateError::UnexpectedEnd => "{{/repeat}} without {{#repeat}}",
+            TemplateError::UndefinedParameter(_) => "undefined parameter",
+            TemplateError::NotANumber(_) => "parameter is not a number",
+        }
+    }
+}
+
+impl error::Error for TemplateError {
+    fn description(&self) -> &str {
+        self.describe()
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TemplateError::UnclosedTag(offset) => write!(f, "{} at {}", self.describe(), offset),
+            TemplateError::UnexpectedEnd => write!(f, "{}", self.describe()),
+            TemplateError::InvalidTag(ref s) |
+            TemplateError::UnclosedRepeat(ref s) |
+            TemplateError::UndefinedParameter(ref s) |
+            TemplateError::NotANumber(ref s) => write!(f, "{} '{}'", self.describe(), s),
+        }
+    }
+}
+
+pub type TemplateResult<T> = result::Result<T, TemplateError>;
+
+/// Values of template parameters.
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    values: HashMap<String, String>,
+}
+
+impl Bindings {
+    /// Creates new empty bindings.
+    pub fn new() -> Bindings {
+        Default::default()
+    }
+
+    /// Binds the parameter `name` to the given `value`.
+    pub fn set<T: ToString>(mut self, name: &str, value: T) -> Bindings {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    fn get(&self, name: &str) -> TemplateResult<&str> {
+        self.values
+            .get(name)
+            .map(|v| v.as_str())
+            .ok_or_else(|| TemplateError::UndefinedParameter(name.to_string()))
+    }
+}
+
+/// An expression in a tag: a number or a parameter, optionally plus or
+/// minus a number.
+#[derive(Clone, Debug, PartialEq)]
+struct Expr {
+    term: String,
+    offset: i64,
+}
+
+impl Expr {
+    fn parse(s: &str) -> TemplateResult<Expr> {
+        let s = s.trim();
+        let invalid = || TemplateError::InvalidTag(s.to_string());
+        let (term, offset) = match s.find(['+', '-']) {
+            Some(pos) => {
+                let offset: i64 = s[pos + 1..].trim().parse().map_err(|_| invalid())?;
+                let sign = if s.as_bytes()[pos] == b'+' { 1 } else { -1 };
+                (s[..pos].trim(), sign * offset)
+            }
+            None => (s, 0),
+        };
+        let valid = !term.is_empty() &&
+            term.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(Expr {
+            term: term.to_string(),
+            offset,
+        })
+    }
+
+    fn number(&self, bindings: &Bindings) -> TemplateResult<i64> {
+        let value = if self.term.chars().all(|c| c.is_ascii_digit()) {
+            &self.term
+        } else {
+            bindings.get(&self.term)?
+        };
+        value
+            .parse::<i64>()
+            .map(|v| v + self.offset)
+            .map_err(|_| TemplateError::NotANumber(self.term.clone()))
+    }
+
+    fn render(&self, bindings: &Bindings) -> TemplateResult<String> {
+        if self.offset != 0 || self.term.chars().all(|c| c.is_ascii_digit()) {
+            self.number(bindings).map(|v| v.to_string())
+        } else {
+            bindings.get(&self.term).map(|v| v.to_string())
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Expr(Expr),
+    Repeat {
+        var: String,
+        start: Expr,
+        end: Expr,
+        body: Vec<Node>,
+    },
+}
+
+/// A template for SPIR-V assembly text.
+///
+/// Templates are plain text with tags in double braces:
+///
+/// * `{{name}}` is replaced by the value of the parameter `name`;
+///   `{{name+1}}` and `{{name-1}}` do arithmetic on numeric parameters.
+/// * `{{#repeat i in start..end}}` ... `{{/repeat}}` repeats its body for
+///   `i` from `start` (inclusive) to `end` (exclusive), where the bounds are
+///   expressions like above. The loop variable shadows any parameter with
+///   the same name inside the body.
+///
+/// # Examples
+///
+/// ```
+/// use rspirv::testing::{Bindings, Template};
+///
+/// let t = Template::parse(
+///     "%s = OpTypeStruct{{#repeat i in 0..n}} %float{{/repeat}}\n\
+///      {{#repeat i in 0..n}}OpMemberDecorate %s {{i}} Offset {{i}}\n{{/repeat}}").unwrap();
+/// assert_eq!(t.render(&Bindings::new().set("n", 2)).unwrap(),
+///            "%s = OpTypeStruct %float %float\n\
+///             OpMemberDecorate %s 0 Offset 0\n\
+///             OpMemberDecorate %s 1 Offset 1\n");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parses the given template `source`.
+    pub fn parse(source: &str) -> TemplateResult<Template> {
+        // Stack of open repeats, with the nodes before them.
+        let mut stack: Vec<(String, Expr, Expr, Vec<Node>)> = vec![];
+        let mut nodes = vec![];
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            if start != 0 {
+                nodes.push(Node::Text(rest[..start].to_string()));
+            }
+            let offset = source.len() - rest.len() + start;
+            let end = rest[start..]
+                .find("}}")
+                .ok_or(TemplateError::UnclosedTag(offset))? + start;
+            let tag = rest[start + 2..end].trim();
+            rest = &rest[end + 2..];
+
+            if let Some(header) = tag.strip_prefix("#repeat") {
+                let (var, start, end) = parse_repeat(header)
+                    .ok_or_else(|| TemplateError::InvalidTag(tag.to_string()))?;
+                stack.push((var, start?, end?, nodes));
+                nodes = vec![];
+            } else if tag == "/repeat" {
+                let (var, start, end, outer) = stack.pop().ok_or(TemplateError::UnexpectedEnd)?;
+                let body = ::std::mem::replace(&mut nodes, outer);
+                nodes.push(Node::Repeat {
+                    var,
+                    start,
+                    end,
+                    body,
+                });
+            } else {
+                nodes.push(Node::Expr(Expr::parse(tag)?));
+            }
+        }
+        if !rest.is_empty() {
+            nodes.push(Node::Text(rest.to_string()));
+        }
+        if let Some((var, _, _, _)) = stack.pop() {
+            return Err(TemplateError::UnclosedRepeat(var));
+        }
+        Ok(Template { nodes })
+    }
+
+    /// Renders this template with the given parameter `bindings`.
+    pub fn render(&self, bindings: &Bindings) -> TemplateResult<String> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, bindings, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// Parses ` var in start..end` of a repeat tag.
+fn parse_repeat(header: &str) -> Option<(String, TemplateResult<Expr>, TemplateResult<Expr>)> {
+    let mut parts = header.split_whitespace();
+    let var = parts.next()?;
+    if parts.next()? != "in" {
+        return None;
+    }
+    let range: String = parts.collect();
+    let dots = range.find("..")?;
+    Some((
+        var.to_string(),
+        Expr::parse(&range[..dots]),
+        Expr::parse(&range[dots + 2..]),
+    ))
+}
+
+fn render_nodes(nodes: &[Node], bindings: &Bindings, out: &mut String) -> TemplateResult<()> {
+    for node in nodes {
+        match *node {
+            Node::Text(ref text) => out.push_str(text),
+            Node::Expr(ref expr) => out.push_str(&expr.render(bindings)?),
+            Node::Repeat {
+                ref var,
+                ref start,
+                ref end,
+                ref body,
+            } => {
+                for i in start.number(bindings)?..end.number(bindings)? {
+                    render_nodes(body, &bindings.clone().set(var, i), out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+
+    use binary::Disassemble;
+    use super::{Bindings, Template, TemplateError};
+
+    #[test]
+    fn test_substitution() {
+        let t = Template::parse("%{{id}} = OpTypeInt {{width}} {{signed}}").unwrap();
+        let b = Bindings::new().set("id", 1).set("width", 32).set("signed", 0);
+        assert_eq!("%1 = OpTypeInt 32 0", t.render(&b).unwrap());
+        assert_eq!(
+            Err(TemplateError::UndefinedParameter("signed".to_string())),
+            t.render(&Bindings::new().set("id", 1).set("width", 32))
+        );
+    }
+
+    #[test]
+    fn test_nested_repeat() {
+        // A loop nest of depth k: open all headers, then close them.
+        let t = Template::parse(
+            "{{#repeat i in 0..k}}loop {{i}} {\n{{/repeat}}\
+             {{#repeat i in 0..k}}} // {{k-1}}\n{{/repeat}}\
+             {{#repeat i in 1..3}}{{#repeat j in i..3}}({{i}},{{j}}){{/repeat}}{{/repeat}}",
+        ).unwrap();
+        assert_eq!(
+            "loop 0 {\nloop 1 {\n} // 1\n} // 1\n(1,1)(1,2)(2,2)",
+            t.render(&Bindings::new().set("k", 2)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err(TemplateError::UnclosedTag(2)), Template::parse("ab{{c"));
+        assert_eq!(Err(TemplateError::UnexpectedEnd), Template::parse("{{/repeat}}"));
+        assert_eq!(
+            Err(TemplateError::UnclosedRepeat("i".to_string())),
+            Template::parse("{{#repeat i in 0..2}}")
+        );
+        assert_eq!(
+            Err(TemplateError::InvalidTag("a b".to_string())),
+            Template::parse("{{a b}}")
+        );
+        let t = Template::parse("{{n+1}}").unwrap();
+        assert_eq!(
+            Err(TemplateError::NotANumber("n".to_string())),
+            t.render(&Bindings::new().set("n", "%x"))
+        );
+    }
+
+    #[test]
+    fn test_compare_with_disassembly() {
+        let t = Template::parse(
+            "%1 = OpTypeFloat 32\n\
+             %2 = OpTypeStruct{{#repeat i in 0..n}} %1{{/repeat}}",
+        ).unwrap();
+        for n in 1..4 {
+            let mut b = mr::Builder::new();
+            let float = b.type_float(32);
+            b.type_struct(vec![float; n]);
+            let m = b.module();
+            let disas: Vec<String> = m.types_global_values.iter().map(|i| i.disassemble()).collect();
+            assert_eq!(t.render(&Bindings::new().set("n", n)).unwrap(), disas.join("\n"));
+        }
+    }
+}
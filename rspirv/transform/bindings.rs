@@ -0,0 +1,190 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// How [`remap_bindings`](fn.remap_bindings.html) assigns new descriptor
+/// sets and bindings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindingRemapping {
+    /// Moves the resources at the given (set, binding) pairs to the mapped
+    /// (set, binding) pairs. Resources not in the map are kept in place.
+    Map(HashMap<(u32, u32), (u32, u32)>),
+    /// Moves all resources to set 0 with sequential bindings from 0, in the
+    /// order of their original (set, binding) pairs.
+    Flatten,
+}
+
+/// A variable moved to another descriptor set or binding by
+/// [`remap_bindings`](fn.remap_bindings.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BindingChange {
+    /// The result id of the variable.
+    pub variable: Word,
+    /// The original (set, binding) pair.
+    pub old: (u32, u32),
+    /// The new (set, binding) pair.
+    pub new: (u32, u32),
+}
+
+impl fmt::Display for BindingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{}: set {} binding {} -> set {} binding {}",
+               self.variable, self.old.0, self.old.1, self.new.0, self.new.1)
+    }
+}
+
+/// Returns the literal of the given decoration if `inst` is an OpDecorate
+/// applying it.
+fn decoration_literal(inst: &mr::Instruction, decoration: spirv::Decoration)
+                      -> Option<(Word, u32)> {
+    match (inst.class.opcode, &inst.operands[..]) {
+        (spirv::Op::Decorate,
+         &[mr::Operand::IdRef(target),
+           mr::Operand::Decoration(d),
+           mr::Operand::LiteralInt32(value)]) if d == decoration => Some((target, value)),
+        _ => None,
+    }
+}
+
+/// Rewrites the DescriptorSet and Binding decorations of the given module
+/// according to `remapping`, e.g., to port Vulkan SPIR-V to an API or layer
+/// with a different binding model.
+///
+/// Only variables decorated with both DescriptorSet and Binding are
+/// considered. Variables sharing a (set, binding) pair keep sharing it.
+/// Since reflection reads the decorations, e.g.,
+/// [`analysis::entry_point_resources`](../analysis/fn.entry_point_resources.html),
+/// it reports the new bindings afterwards. Decoration groups are not
+/// followed; use
+/// [`expand_decoration_groups`](fn.expand_decoration_groups.html)
+/// beforehand if the module may contain them.
+///
+/// Returns the changed variables, in the order of their DescriptorSet
+/// decorations.
+pub fn remap_bindings(module: &mut mr::Module, remapping: &BindingRemapping)
+                      -> Vec<BindingChange> {
+    let mut sets = vec![];
+    let mut bindings = HashMap::new();
+    for inst in &module.annotations {
+        if let Some(set) = decoration_literal(inst, spirv::Decoration::DescriptorSet) {
+            sets.push(set);
+        }
+        if let Some((target, binding)) = decoration_literal(inst, spirv::Decoration::Binding) {
+            bindings.insert(target, binding);
+        }
+    }
+    let resources: Vec<(Word, (u32, u32))> = sets
+        .into_iter()
+        .filter_map(|(target, set)| bindings.get(&target).map(|&b| (target, (set, b))))
+        .collect();
+
+    let new_pairs: HashMap<(u32, u32), (u32, u32)> = match *remapping {
+        BindingRemapping::Map(ref map) => map.clone(),
+        BindingRemapping::Flatten => {
+            let pairs: BTreeSet<(u32, u32)> = resources.iter().map(|&(_, pair)| pair).collect();
+            pairs.iter().zip(0..).map(|(&pair, binding)| (pair, (0, binding))).collect()
+        }
+    };
+    let changes: Vec<BindingChange> = resources
+        .into_iter()
+        .filter_map(|(variable, old)| match new_pairs.get(&old) {
+            Some(&new) if new != old => Some(BindingChange { variable, old, new }),
+            _ => None,
+        })
+        .collect();
+    if changes.is_empty() {
+        return changes;
+    }
+
+    let moved: HashMap<Word, (u32, u32)> =
+        changes.iter().map(|change| (change.variable, change.new)).collect();
+    for inst in &mut module.annotations {
+        let new = if let Some((target, _)) =
+            decoration_literal(inst, spirv::Decoration::DescriptorSet) {
+            moved.get(&target).map(|&(set, _)| set)
+        } else if let Some((target, _)) = decoration_literal(inst, spirv::Decoration::Binding) {
+            moved.get(&target).map(|&(_, binding)| binding)
+        } else {
+            None
+        };
+        if let Some(value) = new {
+            inst.operands[2] = mr::Operand::LiteralInt32(value);
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use std::collections::HashMap;
+    use super::{remap_bindings, BindingChange, BindingRemapping};
+
+    fn module_with_bindings(pairs: &[(u32, u32)]) -> (mr::Module, Vec<u32>) {
+        use spirv::Decoration::{Binding, DescriptorSet};
+        use spirv::StorageClass::Uniform;
+
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let block = b.type_struct(vec![float]);
+        let ptr = b.type_pointer(None, Uniform, block);
+        let mut variables = vec![];
+        for &(set, binding) in pairs {
+            let var = b.variable(ptr, None, Uniform, None);
+            b.decorate(var, DescriptorSet, vec![mr::Operand::LiteralInt32(set)]);
+            b.decorate(var, Binding, vec![mr::Operand::LiteralInt32(binding)]);
+            variables.push(var);
+        }
+        (b.module(), variables)
+    }
+
+    #[test]
+    fn test_remap_bindings_map() {
+        let (mut m, vars) = module_with_bindings(&[(0, 0), (1, 3)]);
+        let map: HashMap<(u32, u32), (u32, u32)> =
+            vec![((1, 3), (2, 0)), ((0, 0), (0, 0))].into_iter().collect();
+        let changes = remap_bindings(&mut m, &BindingRemapping::Map(map.clone()));
+        assert_eq!(vec![BindingChange { variable: vars[1], old: (1, 3), new: (2, 0) }], changes);
+        assert_eq!("%5: set 1 binding 3 -> set 2 binding 0", changes[0].to_string());
+        let index = m.decoration_index();
+        assert_eq!(Some(2), index.get_decoration_value(vars[1], spirv::Decoration::DescriptorSet));
+        assert_eq!(Some(0), index.get_decoration_value(vars[1], spirv::Decoration::Binding));
+        assert_eq!(Some(0), index.get_decoration_value(vars[0], spirv::Decoration::DescriptorSet));
+        assert!(remap_bindings(&mut m, &BindingRemapping::Map(map)).is_empty());
+    }
+
+    #[test]
+    fn test_remap_bindings_flatten() {
+        let (mut m, vars) = module_with_bindings(&[(1, 0), (0, 2), (1, 0), (0, 0)]);
+        let changes = remap_bindings(&mut m, &BindingRemapping::Flatten);
+        assert_eq!(3, changes.len());
+        let pairs: Vec<(u32, u32)> = vars
+            .iter()
+            .map(|&var| {
+                let index = m.decoration_index();
+                (index.get_decoration_value(var, spirv::Decoration::DescriptorSet).unwrap(),
+                 index.get_decoration_value(var, spirv::Decoration::Binding).unwrap())
+            })
+            .collect();
+        assert_eq!(vec![(0, 2), (0, 1), (0, 2), (0, 0)], pairs);
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use analysis;
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How the members of a block were renumbered by
+/// [`remove_unused_block_members`](fn.remove_unused_block_members.html).
+///
+/// Hosts filling the buffers bound to the block use this to find where
+/// the members they know by their original index went.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberRemapping {
+    /// The result id of the block struct type.
+    pub block: Word,
+    /// The result ids of the variables of the block.
+    pub variables: Vec<Word>,
+    /// The new index of each original member, or `None` if the member was
+    /// removed.
+    pub new_indices: Vec<Option<u32>>,
+}
+
+impl MemberRemapping {
+    /// Returns the number of removed members.
+    pub fn removed(&self) -> usize {
+        self.new_indices.iter().filter(|index| index.is_none()).count()
+    }
+}
+
+impl fmt::Display for MemberRemapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{}:", self.block)?;
+        for (old, new) in self.new_indices.iter().enumerate() {
+            let sep = if old == 0 { " " } else { ", " };
+            match *new {
+                Some(new) => write!(f, "{}{} -> {}", sep, old, new)?,
+                None => write!(f, "{}{} removed", sep, old)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Removes the members of uniform and storage blocks which no function
+/// accesses, and returns how the members of the changed blocks were
+/// renumbered.
+///
+/// This finds the same members as
+/// [`analysis::unused_block_members`](../analysis/fn.unused_block_members.html),
+/// except that functions not called from any entry point still count, so
+/// that the module stays valid. The first member of a block is kept if all
+/// of its members are unused, since blocks cannot be empty.
+///
+/// Access chains, OpArrayLength, OpMemberName, and member decorations are
+/// renumbered. The Offset decorations of the remaining members stay as
+/// they are, so the layout the host fills is unchanged apart from the
+/// holes; run [`fix_struct_offsets`](fn.fix_struct_offsets.html) afterwards
+/// to pack the remaining members.
+///
+/// Changing the interface of a shader is only safe if the host side
+/// follows; this transformation is never run unless requested.
+pub fn remove_unused_block_members(module: &mut mr::Module) -> Vec<MemberRemapping> {
+    let remappings: Vec<MemberRemapping> = analysis::find_unused_block_members(module, true)
+        .into_iter()
+        .filter_map(|unused| {
+            let members = module.types_global_values
+                .iter()
+                .find(|inst| inst.result_id == Some(unused.block))
+                .map_or(0, |inst| inst.operands.len() as u32);
+            let mut next = 0;
+            let new_indices: Vec<Option<u32>> = (0..members)
+                .map(|member| {
+                    let removed = unused.members.contains(&member) &&
+                        (member > 0 || unused.members.len() < members as usize);
+                    if removed {
+                        return None;
+                    }
+                    next += 1;
+                    Some(next - 1)
+                })
+                .collect();
+            let remapping = MemberRemapping {
+                block: unused.block,
+                variables: unused.variables,
+                new_indices,
+            };
+            if remapping.removed() == 0 {
+                return None;
+            }
+            Some(remapping)
+        })
+        .collect();
+    if remappings.is_empty() {
+        return remappings;
+    }
+    let by_block: HashMap<Word, &MemberRemapping> =
+        remappings.iter().map(|r| (r.block, r)).collect();
+
+    for inst in &mut module.types_global_values {
+        if let Some(remapping) = inst.result_id.and_then(|id| by_block.get(&id)) {
+            let mut member = 0;
+            inst.operands.retain(|_| {
+                member += 1;
+                remapping.new_indices[member - 1].is_some()
+            });
+        }
+    }
+    // OpMemberName and member decorations share the layout of their first
+    // two operands.
+    let renumber = |inst: &mut mr::Instruction| match (inst.operands.first(),
+                                                        inst.operands.get(1)) {
+        (Some(&mr::Operand::IdRef(target)), Some(&mr::Operand::LiteralInt32(member))) => {
+            match by_block.get(&target) {
+                Some(remapping) => match remapping.new_indices.get(member as usize) {
+                    Some(&Some(new)) => {
+                        inst.operands[1] = mr::Operand::LiteralInt32(new);
+                        true
+                    }
+                    Some(&None) => false,
+                    None => true,
+                },
+                None => true,
+            }
+        }
+        _ => true,
+    };
+    module.debugs.retain_mut(|inst| {
+        inst.class.opcode != spirv::Op::MemberName || renumber(inst)
+    });
+    module.annotations.retain_mut(|inst| match inst.class.opcode {
+        spirv::Op::MemberDecorate | spirv::Op::MemberDecorateStringGOOGLE => renumber(inst),
+        _ => true,
+    });
+
+    rewrite_member_indices(module, &by_block);
+    remappings
+}
+
+/// Renumbers the member indices of OpArrayLength and of access chains
+/// into the remapped blocks.
+fn rewrite_member_indices(module: &mut mr::Module, by_block: &HashMap<Word, &MemberRemapping>) {
+    let defs: HashMap<Word, mr::Instruction> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst.clone())))
+        .collect();
+    let id_operand = |inst: &mr::Instruction, index: usize| match inst.operands.get(index) {
+        Some(&mr::Operand::IdRef(id)) => Some(id),
+        _ => None,
+    };
+    let result_types: HashMap<Word, Word> = module
+        .all_inst_iter()
+        .filter_map(|inst| Some((inst.result_id?, inst.result_type?)))
+        .collect();
+    let pointee = |pointer: Word| -> Option<Word> {
+        let def = defs.get(result_types.get(&pointer)?)?;
+        if def.class.opcode != spirv::Op::TypePointer {
+            return None;
+        }
+        id_operand(def, 1)
+    };
+
+    // The (function, block, instruction, operand) positions of member
+    // index constants, with the remapped blocks they index.
+    let mut indices = vec![];
+    for (f, function) in module.functions.iter_mut().enumerate() {
+        for (b, block) in function.basic_blocks.iter_mut().enumerate() {
+            for (i, inst) in block.instructions.iter_mut().enumerate() {
+                let base = match id_operand(inst, 0) {
+                    Some(base) => base,
+                    None => continue,
+                };
+                let first = match inst.class.opcode {
+                    spirv::Op::AccessChain | spirv::Op::InBoundsAccessChain => 1,
+                    spirv::Op::PtrAccessChain | spirv::Op::InBoundsPtrAccessChain => 2,
+                    spirv::Op::ArrayLength => {
+                        let remapping = pointee(base).and_then(|ty| by_block.get(&ty));
+                        if let (Some(remapping), Some(&mr::Operand::LiteralInt32(member))) =
+                            (remapping, inst.operands.get(1)) {
+                            if let Some(&Some(new)) = remapping.new_indices.get(member as usize) {
+                                inst.operands[1] = mr::Operand::LiteralInt32(new);
+                            }
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let mut ty = match pointee(base) {
+                    Some(ty) => ty,
+                    None => continue,
+                };
+                for operand in first..inst.operands.len() {
+                    if let Some(remapping) = by_block.get(&ty) {
+                        indices.push((f, b, i, operand, *remapping));
+                        break;
+                    }
+                    match defs.get(&ty) {
+                        Some(def) if def.class.opcode == spirv::Op::TypeArray ||
+                            def.class.opcode == spirv::Op::TypeRuntimeArray => {
+                            match id_operand(def, 0) {
+                                Some(element) => ty = element,
+                                None => break,
+                            }
+                        }
+                        _ => break,
+                    }
+                }
+            }
+        }
+    }
+
+    let values: HashMap<Word, (Word, u32)> = module.types_global_values
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::Constant)
+        .filter_map(|inst| match inst.operands[..] {
+            [mr::Operand::LiteralInt32(value)] => {
+                Some((inst.result_id?, (inst.result_type?, value)))
+            }
+            _ => None,
+        })
+        .collect();
+    let mut constants: HashMap<(Word, u32), Word> =
+        values.iter().map(|(&id, &key)| (key, id)).collect();
+    for (f, b, i, operand, remapping) in indices {
+        let index = &module.functions[f].basic_blocks[b].instructions[i].operands[operand];
+        let (ty, member) = match *index {
+            mr::Operand::IdRef(id) => match values.get(&id) {
+                Some(&key) => key,
+                None => continue,
+            },
+            _ => continue,
+        };
+        let new = match remapping.new_indices.get(member as usize) {
+            Some(&Some(new)) if new != member => new,
+            _ => continue,
+        };
+        let constant = match constants.get(&(ty, new)) {
+            Some(&id) => id,
+            None => {
+                let id = module.allocate_id();
+                module.types_global_values.push(mr::Instruction::new(
+                    spirv::Op::Constant,
+                    Some(ty),
+                    Some(id),
+                    vec![mr::Operand::LiteralInt32(new)],
+                ));
+                constants.insert((ty, new), id);
+                id
+            }
+        };
+        module.functions[f].basic_blocks[b].instructions[i].operands[operand] =
+            mr::Operand::IdRef(constant);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::remove_unused_block_members;
+
+    #[test]
+    fn test_remove_unused_block_members() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let array = b.type_runtime_array(float);
+        let block = b.type_struct(vec![float, float, float, array]);
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        for (member, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            let member = member as u32;
+            b.member_name(block, member, *name);
+            let offset = mr::Operand::LiteralInt32(member * 4);
+            b.member_decorate(block, member, spirv::Decoration::Offset, vec![offset]);
+        }
+        let ptr = b.type_pointer(None, spirv::StorageClass::StorageBuffer, block);
+        let float_ptr = b.type_pointer(None, spirv::StorageClass::StorageBuffer, float);
+        let two = b.constant_u32(uint, 2);
+        let ssbo = b.variable(ptr, None, spirv::StorageClass::StorageBuffer, None);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let c = b.access_chain(float_ptr, None, ssbo, vec![two]).unwrap();
+        b.array_length(uint, None, ssbo, 3).unwrap();
+        b.load(float, None, c, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        let remappings = remove_unused_block_members(&mut m);
+        assert_eq!(1, remappings.len());
+        assert_eq!(vec![None, None, Some(0), Some(1)], remappings[0].new_indices);
+        assert_eq!(vec![ssbo], remappings[0].variables);
+        assert_eq!(2, remappings[0].removed());
+        assert_eq!("%5: 0 removed, 1 removed, 2 -> 0, 3 -> 1", remappings[0].to_string());
+
+        let disas: Vec<String> = m.all_inst_iter().map(|inst| inst.disassemble()).collect();
+        assert!(disas.contains(&"%5 = OpTypeStruct %3 %4".to_string()));
+        assert!(disas.contains(&"OpMemberName %5 0 \"c\"".to_string()));
+        assert!(disas.contains(&"OpMemberDecorate %5 1 Offset 12".to_string()));
+        assert!(!disas.iter().any(|inst| inst.contains("\"a\"") || inst.contains("Offset 0")));
+        assert!(disas.contains(&"%16 = OpConstant  %2  0".to_string()));
+        assert!(disas.contains(&"%13 = OpAccessChain  %7  %9 %16".to_string()));
+        assert!(disas.contains(&"%14 = OpArrayLength  %2  %9 1".to_string()));
+        assert!(remove_unused_block_members(&mut m).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use super::unreachable::prune_phis;
+use spirv::Word;
+use std::collections::HashMap;
+
+/// Folds conditional branches on constant conditions.
+///
+/// OpBranchConditional instructions whose condition is OpConstantTrue or
+/// OpConstantFalse are replaced by an OpBranch to the taken target.
+/// Selection headers keep their OpSelectionMerge to preserve the structured
+/// control flow; there, the target not taken is redirected to the merge
+/// block instead, unless the taken target is the merge block itself.
+/// Loop headers are left intact. OpPhi instructions in targets no longer
+/// branched to are updated accordingly.
+///
+/// Afterwards, the targets not taken may become unreachable; use
+/// [`remove_unreachable_blocks`](fn.remove_unreachable_blocks.html) to
+/// remove them.
+///
+/// Returns the number of branches folded.
+pub fn fold_constant_branches(module: &mut mr::Module) -> usize {
+    let constants: HashMap<Word, bool> = module.types_global_values
+        .iter()
+        .filter_map(|inst| match inst.class.opcode {
+            spirv::Op::ConstantTrue => Some((inst.result_id?, true)),
+            spirv::Op::ConstantFalse => Some((inst.result_id?, false)),
+            _ => None,
+        })
+        .collect();
+    module.functions
+        .iter_mut()
+        .map(|function| fold_constant_branches_in(function, &constants))
+        .sum()
+}
+
+fn fold_constant_branches_in(function: &mut mr::Function, constants: &HashMap<Word, bool>)
+                             -> usize {
+    let mut count = 0;
+    for block in &mut function.basic_blocks {
+        let len = block.instructions.len();
+        let (taken, not_taken) = match block.instructions.last().map(|i| &i.operands[..]) {
+            Some(&[mr::Operand::IdRef(condition),
+                   mr::Operand::IdRef(true_label),
+                   mr::Operand::IdRef(false_label),
+                   ..])
+                if block.instructions[len - 1].class.opcode == spirv::Op::BranchConditional => {
+                match constants.get(&condition) {
+                    Some(&true) => (true_label, false_label),
+                    Some(&false) => (false_label, true_label),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        let merge = match len.checked_sub(2).map(|i| &block.instructions[i]) {
+            Some(inst) if inst.class.opcode == spirv::Op::LoopMerge => continue,
+            Some(inst) if inst.class.opcode == spirv::Op::SelectionMerge => {
+                match inst.operands.first() {
+                    Some(&mr::Operand::IdRef(merge)) => Some(merge),
+                    _ => continue,
+                }
+            }
+            _ => None,
+        };
+        match merge {
+            Some(merge) if merge != taken => {
+                if not_taken == merge {
+                    continue;
+                }
+                let branch = &mut block.instructions[len - 1];
+                for operand in &mut branch.operands[1..3] {
+                    if *operand == mr::Operand::IdRef(not_taken) {
+                        *operand = mr::Operand::IdRef(merge);
+                    }
+                }
+            }
+            _ => {
+                let branch = mr::Instruction::new(
+                    spirv::Op::Branch,
+                    None,
+                    None,
+                    vec![mr::Operand::IdRef(taken)],
+                );
+                block.instructions.truncate(if merge.is_some() { len - 2 } else { len - 1 });
+                block.instructions.push(branch);
+            }
+        }
+        count += 1;
+    }
+    if count != 0 {
+        prune_phis(function);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::fold_constant_branches;
+
+    #[test]
+    fn test_fold_constant_branches() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let bool_type = b.type_bool();
+        let yes = b.constant_true(bool_type);
+        let no = b.constant_false(bool_type);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let labels: Vec<spirv::Word> = (0..6).map(|_| b.id()).collect();
+        let (then1, else1, merge1, then2, merge2, end) =
+            (labels[0], labels[1], labels[2], labels[3], labels[4], labels[5]);
+        // if (true) {} else {}
+        b.begin_basic_block(None).unwrap();
+        b.selection_merge(merge1, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(yes, then1, else1, vec![]).unwrap();
+        b.begin_basic_block(Some(then1)).unwrap();
+        b.branch(merge1).unwrap();
+        b.begin_basic_block(Some(else1)).unwrap();
+        b.branch(merge1).unwrap();
+        // if (false) {}
+        b.begin_basic_block(Some(merge1)).unwrap();
+        b.selection_merge(merge2, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(no, then2, merge2, vec![]).unwrap();
+        b.begin_basic_block(Some(then2)).unwrap();
+        b.branch(merge2).unwrap();
+        // Unstructured branch.
+        b.begin_basic_block(Some(merge2)).unwrap();
+        b.branch_conditional(no, merge2, end, vec![]).unwrap();
+        b.begin_basic_block(Some(end)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(3, fold_constant_branches(&mut m));
+        let disas: Vec<String> = m.functions[0]
+            .basic_blocks
+            .iter()
+            .map(|bb| {
+                let insts: Vec<String> = bb.instructions.iter().map(|i| i.disassemble()).collect();
+                insts.join("; ")
+            })
+            .collect();
+        assert_eq!(disas,
+                   vec![format!("OpSelectionMerge %{0} None; OpBranchConditional %3 %{1} %{0}",
+                                merge1, then1),
+                        format!("OpBranch %{}", merge1),
+                        format!("OpBranch %{}", merge1),
+                        format!("OpBranch %{}", merge2),
+                        format!("OpBranch %{}", merge2),
+                        format!("OpBranch %{}", end),
+                        "OpReturn".to_string()]);
+        assert_eq!(0, fold_constant_branches(&mut m));
+    }
+}
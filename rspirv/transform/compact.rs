@@ -0,0 +1,135 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+
+use spirv::Word;
+use std::collections::HashMap;
+
+/// Renumbers all ids of the module densely, in the order of their
+/// definitions, and lowers the bound in the module header accordingly.
+///
+/// Ids referenced but never defined are numbered after all defined ones,
+/// in the order of their first reference.
+///
+/// Returns the number of ids renumbered.
+pub fn compact_ids(module: &mut mr::Module) -> usize {
+    let mut new_ids: HashMap<Word, Word> = HashMap::new();
+    for id in module.all_inst_iter().filter_map(|inst| inst.result_id) {
+        let next = new_ids.len() as Word + 1;
+        new_ids.entry(id).or_insert(next);
+    }
+    for inst in module.all_inst_iter() {
+        for id in referenced_ids(inst) {
+            let next = new_ids.len() as Word + 1;
+            new_ids.entry(id).or_insert(next);
+        }
+    }
+
+    let count = new_ids.iter().filter(|&(old, new)| old != new).count();
+    let rewrite = |inst: &mut mr::Instruction| {
+        for id in inst.result_id.iter_mut().chain(inst.result_type.iter_mut()) {
+            *id = new_ids[id];
+        }
+        for operand in &mut inst.operands {
+            match *operand {
+                mr::Operand::IdMemorySemantics(ref mut id) |
+                mr::Operand::IdScope(ref mut id) |
+                mr::Operand::IdRef(ref mut id) => *id = new_ids[id],
+                _ => {}
+            }
+        }
+    };
+    for inst in module.capabilities
+        .iter_mut()
+        .chain(&mut module.extensions)
+        .chain(&mut module.ext_inst_imports)
+        .chain(&mut module.memory_model)
+        .chain(&mut module.entry_points)
+        .chain(&mut module.execution_modes)
+        .chain(&mut module.debugs)
+        .chain(&mut module.annotations)
+        .chain(&mut module.types_global_values) {
+        rewrite(inst);
+    }
+    for function in &mut module.functions {
+        for inst in function.def
+            .iter_mut()
+            .chain(&mut function.parameters)
+            .chain(function.basic_blocks
+                .iter_mut()
+                .flat_map(|bb| bb.label.iter_mut().chain(&mut bb.instructions)))
+            .chain(&mut function.end) {
+            rewrite(inst);
+        }
+    }
+    if let Some(ref mut header) = module.header {
+        header.bound = new_ids.len() as Word + 1;
+    }
+    count
+}
+
+/// Returns the ids `inst` refers to, excluding its result id.
+fn referenced_ids(inst: &mr::Instruction) -> impl Iterator<Item = Word> + '_ {
+    inst.result_type.into_iter().chain(inst.operands.iter().filter_map(|operand| {
+        match *operand {
+            mr::Operand::IdMemorySemantics(id) |
+            mr::Operand::IdScope(id) |
+            mr::Operand::IdRef(id) => Some(id),
+            _ => None,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::compact_ids;
+
+    #[test]
+    fn test_compact_ids() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        b.type_float(32);
+        b.type_int(32, 1);
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.name(main, "main");
+        let mut m = b.module();
+        m.types_global_values.remove(1);
+        m.types_global_values.remove(1);
+
+        assert_eq!(3, compact_ids(&mut m));
+        assert_eq!(5, m.header.as_ref().unwrap().bound());
+        assert_eq!(m.disassemble(),
+                   "; SPIR-V\n\
+                    ; Version: 1.3\n\
+                    ; Generator: rspirv\n\
+                    ; Bound: 5\n\
+                    OpName %3 \"main\"\n\
+                    %1 = OpTypeVoid\n\
+                    %2 = OpTypeFunction %1\n\
+                    %3 = OpFunction  %1  None %2\n\
+                    %4 = OpLabel\n\
+                    OpReturn\n\
+                    OpFunctionEnd");
+        assert_eq!(0, compact_ids(&mut m));
+    }
+}
@@ -0,0 +1,466 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use super::{rewrite_id_uses, rewrite_operands};
+
+/// Returns a key identifying the value of the given constant-defining
+/// instruction, if it is eligible for deduplication.
+fn constant_key(inst: &mr::Instruction) -> Option<String> {
+    match inst.class.opcode {
+        spirv::Op::ConstantTrue |
+        spirv::Op::ConstantFalse |
+        spirv::Op::ConstantNull |
+        spirv::Op::Constant |
+        spirv::Op::ConstantComposite |
+        spirv::Op::ConstantSampler => {}
+        // Specialization constants are distinct even with identical
+        // default values.
+        _ => return None,
+    }
+    let operands: Vec<String> = inst.operands
+        .iter()
+        .map(|operand| match *operand {
+            // Compare floats bitwise: 0.0 and -0.0 are different constants,
+            // and so are NaNs with different payloads.
+            mr::Operand::LiteralFloat32(v) => format!("f32:{:x}", v.to_bits()),
+            mr::Operand::LiteralFloat64(v) => format!("f64:{:x}", v.to_bits()),
+            _ => format!("{:?}", operand),
+        })
+        .collect();
+    Some(format!("{:?} {:?} {}", inst.class.opcode, inst.result_type?, operands.join(" ")))
+}
+
+/// Removes constants duplicating earlier ones, i.e., OpConstantTrue,
+/// OpConstantFalse, OpConstantNull, OpConstant, OpConstantComposite, and
+/// OpConstantSampler instructions with the same result type and operands
+/// as an earlier instruction of the same opcode. All uses of the removed
+/// constants are rewritten to use the earlier ones.
+///
+/// Since uses are rewritten in order, composite constants only differing
+/// in duplicated constituents are unified too. Specialization constants
+/// and decorated constants are never touched. Debug names of removed
+/// constants are removed as well.
+///
+/// Returns the number of constants removed.
+pub fn remove_duplicate_constants(module: &mut mr::Module) -> usize {
+    let decorated = decorated_ids(module);
+    let mut seen: HashMap<String, Word> = HashMap::new();
+    let mut replacements: HashMap<Word, Word> = HashMap::new();
+    let globals = ::std::mem::take(&mut module.types_global_values);
+    for mut inst in globals {
+        rewrite_operands(&mut inst, &replacements);
+        let id = match inst.result_id {
+            Some(id) if !decorated.contains(&id) => id,
+            _ => {
+                module.types_global_values.push(inst);
+                continue;
+            }
+        };
+        if let Some(key) = constant_key(&inst) {
+            if let Some(&existing) = seen.get(&key) {
+                replacements.insert(id, existing);
+                continue;
+            }
+            seen.insert(key, id);
+        }
+        module.types_global_values.push(inst);
+    }
+    if replacements.is_empty() {
+        return 0;
+    }
+
+    module.debugs.retain(|inst| match inst.operands.first() {
+        Some(mr::Operand::IdRef(id)) => !replacements.contains_key(id),
+        _ => true,
+    });
+    rewrite_id_uses(module, &replacements);
+    replacements.len()
+}
+
+/// Returns the ids targeted by decorations.
+fn decorated_ids(module: &mr::Module) -> HashSet<Word> {
+    module.annotations
+        .iter()
+        .filter_map(|inst| match inst.operands.first() {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns true if the given instruction defines an explicit zero scalar,
+/// i.e., an OpConstantFalse or an OpConstant with all bits zero.
+///
+/// Negative zero floats are not zero here, since OpConstantNull of a
+/// float type is positive zero.
+fn is_explicit_zero(inst: &mr::Instruction) -> bool {
+    match inst.class.opcode {
+        spirv::Op::ConstantFalse => true,
+        spirv::Op::Constant => inst.operands.iter().all(|operand| match *operand {
+            mr::Operand::LiteralInt32(v) => v == 0,
+            mr::Operand::LiteralInt64(v) => v == 0,
+            mr::Operand::LiteralFloat32(v) => v.to_bits() == 0,
+            mr::Operand::LiteralFloat64(v) => v.to_bits() == 0,
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+/// Zero constants of each type, created on demand.
+struct Zeros {
+    types: HashMap<Word, mr::Instruction>,
+    lengths: HashMap<Word, u32>,
+    constants: HashMap<Word, Word>,
+}
+
+impl Zeros {
+    /// Returns the types of the constituents of the given composite type
+    /// `ty`, or `None` if `ty` is not a composite of known size.
+    fn constituent_types(&self, ty: Word) -> Option<Vec<Word>> {
+        let inst = self.types.get(&ty)?;
+        let ids: Vec<Word> = inst.operands
+            .iter()
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        let count = match (inst.class.opcode, inst.operands.get(1)) {
+            (spirv::Op::TypeVector, Some(&mr::Operand::LiteralInt32(n))) |
+            (spirv::Op::TypeMatrix, Some(&mr::Operand::LiteralInt32(n))) => n,
+            (spirv::Op::TypeArray, Some(&mr::Operand::IdRef(length))) => {
+                *self.lengths.get(&length)?
+            }
+            (spirv::Op::TypeStruct, _) => return Some(ids),
+            _ => return None,
+        };
+        // Stay within the maximum word count of an instruction.
+        if count > 0xffff - 3 {
+            return None;
+        }
+        Some(vec![*ids.first()?; count as usize])
+    }
+
+    /// Returns the id of a zero constant of the given type `ty`, appending
+    /// its definition to the module if there is none yet.
+    fn get(&mut self, module: &mut mr::Module, ty: Word) -> Word {
+        if let Some(&id) = self.constants.get(&ty) {
+            return id;
+        }
+        let (opcode, operands) = match self.constituent_types(ty) {
+            Some(constituents) => {
+                let operands = constituents
+                    .into_iter()
+                    .map(|c| mr::Operand::IdRef(self.get(module, c)))
+                    .collect();
+                (spirv::Op::ConstantComposite, operands)
+            }
+            None => {
+                let inst = &self.types[&ty];
+                match (inst.class.opcode, inst.operands.first()) {
+                    (spirv::Op::TypeBool, _) => (spirv::Op::ConstantFalse, vec![]),
+                    (spirv::Op::TypeInt, Some(&mr::Operand::LiteralInt32(64))) => {
+                        (spirv::Op::Constant, vec![mr::Operand::LiteralInt64(0)])
+                    }
+                    (spirv::Op::TypeFloat, Some(&mr::Operand::LiteralInt32(32))) => {
+                        (spirv::Op::Constant, vec![mr::Operand::LiteralFloat32(0.0)])
+                    }
+                    (spirv::Op::TypeFloat, Some(&mr::Operand::LiteralInt32(64))) => {
+                        (spirv::Op::Constant, vec![mr::Operand::LiteralFloat64(0.0)])
+                    }
+                    (spirv::Op::TypeInt, _) | (spirv::Op::TypeFloat, _) => {
+                        (spirv::Op::Constant, vec![mr::Operand::LiteralInt32(0)])
+                    }
+                    // E.g., pointers and opaque types have no explicit zero.
+                    _ => (spirv::Op::ConstantNull, vec![]),
+                }
+            }
+        };
+        let id = module.allocate_id();
+        let inst = mr::Instruction::new(opcode, Some(ty), Some(id), operands);
+        module.types_global_values.push(inst);
+        self.constants.insert(ty, id);
+        id
+    }
+}
+
+/// Expands OpConstantNull instructions of composite types, i.e., vectors,
+/// matrices, arrays of constant length, and structs, into OpConstantComposite
+/// instructions with explicit zero constituents, for consumers not
+/// supporting composite null constants.
+///
+/// The expanded constants keep their result ids. Zero constituents are
+/// booleans false, integers and floats zero, nested composites expanded
+/// the same way, and OpConstantNull for other types, e.g., pointers.
+/// Existing undecorated zero scalars are reused; missing ones are added.
+///
+/// Returns the number of constants expanded.
+pub fn expand_null_constants(module: &mut mr::Module) -> usize {
+    let decorated = decorated_ids(module);
+    let mut zeros = Zeros {
+        types: HashMap::new(),
+        lengths: HashMap::new(),
+        constants: HashMap::new(),
+    };
+    let mut count = 0;
+    let globals = ::std::mem::take(&mut module.types_global_values);
+    for mut inst in globals {
+        if grammar::reflect::is_type(inst.class.opcode) {
+            if let Some(id) = inst.result_id {
+                zeros.types.insert(id, inst.clone());
+            }
+        }
+        let (ty, id) = match (inst.result_type, inst.result_id) {
+            (Some(ty), Some(id)) => (ty, id),
+            _ => {
+                module.types_global_values.push(inst);
+                continue;
+            }
+        };
+        if inst.class.opcode == spirv::Op::Constant {
+            if let Some(&mr::Operand::LiteralInt32(length)) = inst.operands.first() {
+                zeros.lengths.insert(id, length);
+            }
+        }
+        if is_explicit_zero(&inst) && !decorated.contains(&id) {
+            zeros.constants.entry(ty).or_insert(id);
+        }
+        if inst.class.opcode == spirv::Op::ConstantNull {
+            if let Some(constituents) = zeros.constituent_types(ty) {
+                let operands = constituents
+                    .into_iter()
+                    .map(|c| mr::Operand::IdRef(zeros.get(module, c)))
+                    .collect();
+                let opcode = spirv::Op::ConstantComposite;
+                inst = mr::Instruction::new(opcode, Some(ty), Some(id), operands);
+                count += 1;
+            }
+        }
+        module.types_global_values.push(inst);
+    }
+    count
+}
+
+/// Contracts OpConstantComposite instructions whose constituents are all
+/// zero into OpConstantNull instructions, to reduce the module size.
+///
+/// Constituents are zero if they are OpConstantNull, OpConstantFalse,
+/// OpConstant with all bits zero, or composites contracted themselves.
+/// The contracted constants keep their result ids; constituents which are
+/// no longer used are left in place.
+///
+/// Returns the number of constants contracted.
+pub fn contract_null_constants(module: &mut mr::Module) -> usize {
+    let mut zero = HashSet::new();
+    let mut count = 0;
+    for inst in &mut module.types_global_values {
+        let id = match inst.result_id {
+            Some(id) => id,
+            None => continue,
+        };
+        if inst.class.opcode == spirv::Op::ConstantNull || is_explicit_zero(inst) {
+            zero.insert(id);
+        } else if inst.class.opcode == spirv::Op::ConstantComposite &&
+            inst.operands.iter().all(|operand| match *operand {
+                mr::Operand::IdRef(c) => zero.contains(&c),
+                _ => false,
+            })
+        {
+            let ty = inst.result_type;
+            *inst = mr::Instruction::new(spirv::Op::ConstantNull, ty, Some(id), vec![]);
+            zero.insert(id);
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use validate::validate;
+    use super::{contract_null_constants, expand_null_constants, remove_duplicate_constants};
+
+    #[test]
+    fn test_remove_duplicate_constants() {
+        let mut b = mr::Builder::new();
+        let boolean = b.type_bool();
+        let float = b.type_float(32);
+        let vec2 = b.type_vector(float, 2);
+        let t1 = b.constant_true(boolean);
+        let t2 = b.constant_true(boolean);
+        b.constant_false(boolean);
+        let n1 = b.constant_null(float);
+        let n2 = b.constant_null(float);
+        let one = b.constant_f32(float, 1.0);
+        let one_again = b.constant_f32(float, 1.0);
+        b.name(one_again, "one_again");
+        let zero = b.constant_f32(float, 0.0);
+        let negative_zero = b.constant_f32(float, -0.0);
+        b.constant_composite(vec2, vec![one, zero]);
+        b.constant_composite(vec2, vec![one_again, zero]);
+        b.constant_composite(vec2, vec![one, negative_zero]);
+        let s1 = b.spec_constant_f32(float, 1.0);
+        let s2 = b.spec_constant_f32(float, 1.0);
+        let decorated = b.constant_f32(float, 1.0);
+        b.decorate(decorated, spirv::Decoration::RelaxedPrecision, vec![]);
+        let floatf = b.type_function(float, vec![]);
+        b.begin_function(float, None, spirv::FunctionControl::NONE, floatf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.select(float, None, t2, n1, n2).unwrap();
+        b.fadd(float, None, one_again, s1).unwrap();
+        b.fadd(float, None, s2, decorated).unwrap();
+        b.ret_value(t1).unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(4, remove_duplicate_constants(&mut m));
+        assert!(m.debugs.is_empty());
+        let globals: Vec<String> = m.types_global_values.iter().map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "%1 = OpTypeBool",
+                "%2 = OpTypeFloat 32",
+                "%3 = OpTypeVector %2 2",
+                "%4 = OpConstantTrue  %1 ",
+                "%6 = OpConstantFalse  %1 ",
+                "%7 = OpConstantNull  %2 ",
+                "%9 = OpConstant  %2  1.0",
+                "%11 = OpConstant  %2  0.0",
+                "%12 = OpConstant  %2  -0.0",
+                "%13 = OpConstantComposite  %3  %9 %11",
+                "%15 = OpConstantComposite  %3  %9 %12",
+                "%16 = OpSpecConstant  %2  1.0",
+                "%17 = OpSpecConstant  %2  1.0",
+                "%18 = OpConstant  %2  1.0",
+                "%19 = OpTypeFunction %2",
+            ],
+            globals
+        );
+        let body: Vec<String> = m.functions[0].basic_blocks[0]
+            .instructions
+            .iter()
+            .map(|i| i.disassemble())
+            .collect();
+        assert_eq!(
+            vec![
+                "%22 = OpSelect  %2  %4 %7 %7",
+                "%23 = OpFAdd  %2  %9 %16",
+                "%24 = OpFAdd  %2  %17 %18",
+                "OpReturnValue %4",
+            ],
+            body
+        );
+    }
+
+    #[test]
+    fn test_remove_duplicate_scope_constants() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let two = b.constant_u32(uint, 2);
+        let workgroup = b.constant_u32(uint, 2);
+        let semantics = b.constant_u32(uint, 0x108);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.iadd(uint, None, two, two).unwrap();
+        b.control_barrier(workgroup, workgroup, semantics).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(1, remove_duplicate_constants(&mut m));
+        let barrier = m.functions[0].basic_blocks[0].instructions[1].disassemble();
+        assert_eq!("OpControlBarrier %3 %3 %5", barrier);
+        assert!(m.types_global_values.iter().all(|inst| inst.result_id != Some(workgroup)));
+        assert!(validate(&m).is_empty());
+    }
+
+    #[test]
+    fn test_expand_and_contract_null_constants() {
+        let mut b = mr::Builder::new();
+        let boolean = b.type_bool();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        b.constant_f32(float, 0.0);
+        let vec2 = b.type_vector(float, 2);
+        let mat2 = b.type_matrix(vec2, 2);
+        let two = b.constant_u32(uint, 2);
+        let array = b.type_array(boolean, two);
+        let pointer = b.type_pointer(None, spirv::StorageClass::Private, float);
+        let structure = b.type_struct(vec![mat2, array, pointer, uint]);
+        b.constant_null(structure);
+        b.constant_null(float);
+        let mut m = b.module();
+
+        assert_eq!(1, expand_null_constants(&mut m));
+        let globals: Vec<String> =
+            m.types_global_values.iter().skip(10).map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "%13 = OpConstantComposite  %5  %4 %4",
+                "%14 = OpConstantComposite  %6  %13 %13",
+                "%15 = OpConstantFalse  %1 ",
+                "%16 = OpConstantComposite  %8  %15 %15",
+                "%17 = OpConstantNull  %9 ",
+                "%18 = OpConstant  %2  0",
+                "%11 = OpConstantComposite  %10  %14 %16 %17 %18",
+                "%12 = OpConstantNull  %3 ",
+            ],
+            globals
+        );
+        assert_eq!(19, m.header.as_ref().unwrap().bound);
+
+        assert_eq!(4, contract_null_constants(&mut m));
+        let globals: Vec<String> =
+            m.types_global_values.iter().skip(10).map(|i| i.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "%13 = OpConstantNull  %5 ",
+                "%14 = OpConstantNull  %6 ",
+                "%15 = OpConstantFalse  %1 ",
+                "%16 = OpConstantNull  %8 ",
+                "%17 = OpConstantNull  %9 ",
+                "%18 = OpConstant  %2  0",
+                "%11 = OpConstantNull  %10 ",
+                "%12 = OpConstantNull  %3 ",
+            ],
+            globals
+        );
+    }
+
+    #[test]
+    fn test_contract_negative_zero() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let vec2 = b.type_vector(float, 2);
+        let zero = b.constant_f32(float, 0.0);
+        let negative_zero = b.constant_f32(float, -0.0);
+        b.constant_composite(vec2, vec![zero, negative_zero]);
+        b.spec_constant_composite(vec2, vec![zero, zero]);
+        let mut m = b.module();
+        assert_eq!(0, contract_null_constants(&mut m));
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Propagates copies: uses of the results of OpCopyObject instructions are
+/// replaced by their operands, and uses of the results of OpPhi
+/// instructions with a single incoming value by that value. The copies and
+/// OpPhi instructions are removed afterwards, together with their OpName
+/// and decorations.
+///
+/// This cleans up code from straightforward front ends, which tend to copy
+/// values around when building expressions.
+///
+/// Returns the number of instructions removed.
+pub fn propagate_copies(module: &mut mr::Module) -> usize {
+    let mut removed = HashSet::new();
+    for function in &mut module.functions {
+        propagate_copies_in(function, &mut removed);
+    }
+    if !removed.is_empty() {
+        let targets_removed = |inst: &mr::Instruction| match inst.operands.first() {
+            Some(&mr::Operand::IdRef(target)) => removed.contains(&target),
+            _ => false,
+        };
+        module.debugs.retain(|inst| inst.class.opcode != spirv::Op::Name || !targets_removed(inst));
+        module.annotations.retain(|inst| !targets_removed(inst));
+    }
+    removed.len()
+}
+
+fn propagate_copies_in(function: &mut mr::Function, removed: &mut HashSet<Word>) {
+    let mut copies = HashMap::new();
+    for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+        let source = match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::CopyObject, &[mr::Operand::IdRef(source)]) |
+            (spirv::Op::Phi, &[mr::Operand::IdRef(source), mr::Operand::IdRef(_)]) => source,
+            _ => continue,
+        };
+        if let Some(id) = inst.result_id {
+            if id != source {
+                copies.insert(id, source);
+            }
+        }
+    }
+    if copies.is_empty() {
+        return;
+    }
+    // Resolve chains of copies to their original values.
+    let resolve = |mut id: Word| {
+        let mut steps = 0;
+        while let Some(&source) = copies.get(&id) {
+            id = source;
+            steps += 1;
+            if steps > copies.len() {
+                break;
+            }
+        }
+        id
+    };
+    let replacements: HashMap<Word, Word> = copies.keys().map(|&id| (id, resolve(id))).collect();
+
+    for block in &mut function.basic_blocks {
+        block.instructions.retain(|inst| {
+            !inst.result_id.is_some_and(|id| replacements.contains_key(&id))
+        });
+        for inst in &mut block.instructions {
+            for operand in &mut inst.operands {
+                if let mr::Operand::IdRef(ref mut id) = *operand {
+                    if let Some(&value) = replacements.get(id) {
+                        *id = value;
+                    }
+                }
+            }
+        }
+    }
+    removed.extend(replacements.keys());
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::propagate_copies;
+
+    #[test]
+    fn test_propagate_copies() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let one = b.constant_u32(int, 1);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let next = b.id();
+        let entry = b.begin_basic_block(None).unwrap();
+        let x = b.iadd(int, None, one, one).unwrap();
+        let y = b.copy_object(int, None, x).unwrap();
+        let z = b.copy_object(int, None, y).unwrap();
+        b.name(z, "z");
+        b.branch(next).unwrap();
+        b.begin_basic_block(Some(next)).unwrap();
+        let w = b.phi(int, None, vec![(z, entry)]).unwrap();
+        b.imul(int, None, w, y).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(3, propagate_copies(&mut m));
+        let disas: Vec<String> = m.functions[0]
+            .basic_blocks
+            .iter()
+            .flat_map(|bb| &bb.instructions)
+            .map(|inst| inst.disassemble())
+            .collect();
+        assert_eq!(disas,
+                   vec![format!("%{} = OpIAdd  %2  %3 %3", x),
+                        format!("OpBranch %{}", next),
+                        format!("%12 = OpIMul  %2  %{0} %{0}", x),
+                        "OpReturn".to_string()]);
+        assert!(m.debugs.is_empty());
+        assert_eq!(0, propagate_copies(&mut m));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use mr;
+use spirv;
+
+use grammar::Opcode;
+use spirv::Word;
+use std::collections::HashSet;
+
+/// Collects the ids `inst` refers to into `used`.
+fn collect_uses(inst: &mr::Instruction, used: &mut HashSet<Word>) {
+    used.extend(inst.result_type);
+    for operand in &inst.operands {
+        match *operand {
+            mr::Operand::IdMemorySemantics(id) |
+            mr::Operand::IdScope(id) |
+            mr::Operand::IdRef(id) => {
+                used.insert(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes code which cannot affect the execution of any entry point.
+///
+/// This removes
+///
+/// * functions not called from any entry point, unless the module has no
+///   entry points or the functions are decorated with LinkageAttributes,
+/// * instructions in functions whose results are unused and which have no
+///   side effects (see [`Opcode::may_have_side_effects`]
+///   (../grammar/trait.Opcode.html#tymethod.may_have_side_effects)), and
+/// * types, constants, and global variables not used by anything but
+///   debug instructions and annotations,
+///
+/// repeatedly until nothing changes. OpName, OpMemberName, and decorations
+/// of the removed ids are removed as well.
+///
+/// Returns the number of functions and instructions removed, not counting
+/// the instructions in removed functions.
+pub fn remove_dead_code(module: &mut mr::Module) -> usize {
+    let removed = remove_uncalled_functions(module);
+    let mut count = removed.len();
+    remove_debug_annotations(module, &removed);
+    loop {
+        let mut used = HashSet::new();
+        for inst in module.entry_points.iter().chain(&module.execution_modes) {
+            collect_uses(inst, &mut used);
+        }
+        for inst in module.types_global_values
+            .iter()
+            .chain(module.functions.iter().flat_map(|f| f.all_inst_iter())) {
+            collect_uses(inst, &mut used);
+        }
+        // Annotations only count for decoration groups.
+        for inst in &module.annotations {
+            if inst.class.opcode == spirv::Op::GroupDecorate ||
+                inst.class.opcode == spirv::Op::GroupMemberDecorate {
+                collect_uses(inst, &mut used);
+            }
+        }
+
+        let mut removed = HashSet::new();
+        let mut dead = |inst: &mr::Instruction, global: bool| match inst.result_id {
+            Some(id) if !used.contains(&id) => {
+                let opcode = inst.class.opcode;
+                let removable = if global {
+                    grammar::reflect::is_type(opcode) || grammar::reflect::is_constant(opcode) ||
+                        opcode == spirv::Op::Variable
+                } else {
+                    !opcode.may_have_side_effects()
+                };
+                if removable {
+                    removed.insert(id);
+                }
+                removable
+            }
+            _ => false,
+        };
+        module.types_global_values.retain(|inst| !dead(inst, true));
+        for block in module.functions.iter_mut().flat_map(|f| &mut f.basic_blocks) {
+            block.instructions.retain(|inst| !dead(inst, false));
+        }
+        if removed.is_empty() {
+            break;
+        }
+        count += removed.len();
+        remove_debug_annotations(module, &removed);
+    }
+    count
+}
+
+/// Removes the OpName, OpMemberName, and decorations of the given ids.
+pub(super) fn remove_debug_annotations(module: &mut mr::Module, removed: &HashSet<Word>) {
+    let targets_removed = |inst: &mr::Instruction| match inst.operands.first() {
+        Some(&mr::Operand::IdRef(target)) => removed.contains(&target),
+        _ => false,
+    };
+    module.debugs.retain(|inst| match inst.class.opcode {
+        spirv::Op::Name | spirv::Op::MemberName => !targets_removed(inst),
+        _ => true,
+    });
+    module.annotations.retain(|inst| match inst.class.opcode {
+        spirv::Op::GroupDecorate | spirv::Op::GroupMemberDecorate => true,
+        _ => !targets_removed(inst),
+    });
+}
+
+/// Removes the functions not called from any entry point, if there are
+/// entry points at all, and returns the result ids of the removed
+/// functions and their parameters.
+fn remove_uncalled_functions(module: &mut mr::Module) -> HashSet<Word> {
+    let mut removed = HashSet::new();
+    if module.entry_points.is_empty() {
+        return removed;
+    }
+    let mut worklist: Vec<Word> = module.entry_points
+        .iter()
+        .filter_map(|inst| match inst.operands.get(1) {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        })
+        .collect();
+    worklist.extend(module.annotations.iter().filter_map(|inst| {
+        match inst.operands[..] {
+            [mr::Operand::IdRef(target),
+             mr::Operand::Decoration(spirv::Decoration::LinkageAttributes),
+             ..] => Some(target),
+            _ => None,
+        }
+    }));
+    let mut called: HashSet<Word> = worklist.iter().cloned().collect();
+    while let Some(id) = worklist.pop() {
+        let function = module.functions
+            .iter()
+            .find(|f| f.def.as_ref().and_then(|def| def.result_id) == Some(id));
+        for inst in function.into_iter().flat_map(|f| f.all_inst_iter()) {
+            if inst.class.opcode != spirv::Op::FunctionCall {
+                continue;
+            }
+            if let Some(&mr::Operand::IdRef(callee)) = inst.operands.first() {
+                if called.insert(callee) {
+                    worklist.push(callee);
+                }
+            }
+        }
+    }
+
+    module.functions.retain(|f| {
+        match f.def.as_ref().and_then(|def| def.result_id) {
+            Some(id) if !called.contains(&id) => {
+                removed.insert(id);
+                removed.extend(f.parameters.iter().filter_map(|param| param.result_id));
+                false
+            }
+            _ => true,
+        }
+    });
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::remove_dead_code;
+
+    #[test]
+    fn test_remove_dead_code() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let int = b.type_int(32, 1);
+        let one = b.constant_f32(float, 1.0);
+        let unused = b.constant_u32(int, 7);
+        b.name(unused, "unused");
+        let ptr = b.type_pointer(None, spirv::StorageClass::Private, float);
+        let var = b.variable(ptr, None, spirv::StorageClass::Private, None);
+        let voidf = b.type_function(void, vec![]);
+
+        let helper = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let x = b.load(float, None, var, None, vec![]).unwrap();
+        let y = b.fadd(float, None, x, one).unwrap();
+        b.fmul(float, None, y, y).unwrap();
+        b.store(var, one, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::GLCompute, main, "main", vec![]);
+        b.name(helper, "helper");
+        let mut m = b.module();
+
+        // The helper, OpFMul, OpFAdd, OpLoad, the unused constant and its type.
+        assert_eq!(6, remove_dead_code(&mut m));
+        assert_eq!(1, m.functions.len());
+        let disas: Vec<String> = m.all_inst_iter().map(|i| i.disassemble()).collect();
+        assert!(!disas.iter().any(|i| i.contains("unused") || i.contains("helper")));
+        assert!(disas.contains(&"%2 = OpTypeFloat 32".to_string()));
+        assert!(!disas.contains(&"%3 = OpTypeInt 32 1".to_string()));
+        assert!(disas.contains(&format!("OpStore %{} %4", var)));
+        assert_eq!(0, remove_dead_code(&mut m));
+    }
+}
@@ -0,0 +1,424 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Expands all decoration groups into direct decorations.
+///
+/// Each decoration applied to an OpDecorationGroup is re-applied to every
+/// target of the OpGroupDecorate and OpGroupMemberDecorate instructions
+/// using the group, via OpDecorate (or OpDecorateId) and OpMemberDecorate
+/// respectively. The groups themselves, the decorations on them, and debug
+/// names of them are removed afterwards.
+///
+/// Returns the number of decoration groups removed.
+pub fn expand_decoration_groups(module: &mut mr::Module) -> usize {
+    let groups = decoration_groups(module);
+    if groups.is_empty() {
+        return 0;
+    }
+
+    // Decorations (without the target operand) applied to each group,
+    // together with the opcode applying them.
+    let mut group_decorations: HashMap<Word, Vec<(spirv::Op, Vec<mr::Operand>)>> =
+        HashMap::new();
+    for inst in &module.annotations {
+        let opcode = inst.class.opcode;
+        if opcode != spirv::Op::Decorate && opcode != spirv::Op::DecorateId {
+            continue;
+        }
+        if let Some(&mr::Operand::IdRef(target)) = inst.operands.first() {
+            if groups.contains(&target) {
+                group_decorations
+                    .entry(target)
+                    .or_default()
+                    .push((opcode, inst.operands[1..].to_vec()));
+            }
+        }
+    }
+
+    let annotations = ::std::mem::take(&mut module.annotations);
+    for inst in annotations {
+        let group = match inst.operands.first() {
+            Some(&mr::Operand::IdRef(id)) if groups.contains(&id) => id,
+            _ => {
+                if inst.class.opcode != spirv::Op::DecorationGroup {
+                    module.annotations.push(inst);
+                }
+                continue;
+            }
+        };
+        let decorations = group_decorations.get(&group).map_or(&[][..], |d| &d[..]);
+        match inst.class.opcode {
+            spirv::Op::GroupDecorate => {
+                for target in &inst.operands[1..] {
+                    for &(opcode, ref decoration) in decorations {
+                        let mut operands = vec![target.clone()];
+                        operands.extend_from_slice(decoration);
+                        module.annotations.push(mr::Instruction::new(opcode, None, None, operands));
+                    }
+                }
+            }
+            spirv::Op::GroupMemberDecorate => {
+                for pair in inst.operands[1..].chunks(2) {
+                    // There is no member form of OpDecorateId, and none of
+                    // the decorations taking ids applies to members.
+                    let decorations = decorations
+                        .iter()
+                        .filter(|&&(opcode, _)| opcode == spirv::Op::Decorate);
+                    for (_, decoration) in decorations {
+                        let mut operands = pair.to_vec();
+                        operands.extend_from_slice(decoration);
+                        module.annotations.push(mr::Instruction::new(
+                            spirv::Op::MemberDecorate,
+                            None,
+                            None,
+                            operands,
+                        ));
+                    }
+                }
+            }
+            // Decorations on the group itself are dropped.
+            _ => {}
+        }
+    }
+
+    module.debugs.retain(|inst| match inst.operands.first() {
+        Some(&mr::Operand::IdRef(id)) => !groups.contains(&id),
+        _ => true,
+    });
+
+    groups.len()
+}
+
+/// Returns the result ids of all OpDecorationGroup instructions.
+fn decoration_groups(module: &mr::Module) -> HashSet<Word> {
+    module
+        .annotations
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::DecorationGroup)
+        .filter_map(|inst| inst.result_id)
+        .collect()
+}
+
+/// Returns whether the given instruction applies a decoration to a single
+/// target, i.e., is an OpDecorate, OpDecorateId, or OpMemberDecorate.
+fn is_direct_decoration(inst: &mr::Instruction) -> bool {
+    matches!(
+        inst.class.opcode,
+        spirv::Op::Decorate | spirv::Op::DecorateId | spirv::Op::MemberDecorate
+    )
+}
+
+/// Removes duplicated OpDecorate, OpDecorateId, and OpMemberDecorate
+/// instructions, i.e., those applying exactly the same decoration with the
+/// same parameters to the same target as an earlier instruction.
+///
+/// Returns the number of instructions removed.
+pub fn remove_duplicate_decorations(module: &mut mr::Module) -> usize {
+    let before = module.annotations.len();
+    // The decorations seen so far, by target.
+    let mut seen: HashMap<Word, Vec<(spirv::Op, Vec<mr::Operand>)>> = HashMap::new();
+    module.annotations.retain(|inst| {
+        let target = match inst.operands.first() {
+            Some(&mr::Operand::IdRef(target)) if is_direct_decoration(inst) => target,
+            _ => return true,
+        };
+        let decorations = seen.entry(target).or_default();
+        let operands = &inst.operands[1..];
+        if decorations.iter().any(|&(op, ref d)| op == inst.class.opcode && d[..] == *operands) {
+            return false;
+        }
+        decorations.push((inst.class.opcode, operands.to_vec()));
+        true
+    });
+    before - module.annotations.len()
+}
+
+/// A decoration applied by OpDecorate or OpDecorateId, with all its
+/// targets.
+struct Candidate {
+    opcode: spirv::Op,
+    /// The operands without the target.
+    decoration: Vec<mr::Operand>,
+    targets: Vec<Word>,
+}
+
+/// The distinct decorations of a module, in the order in which they first
+/// appear.
+#[derive(Default)]
+struct Candidates {
+    candidates: Vec<Candidate>,
+    /// Indices into `candidates`, by decoration.
+    index: HashMap<spirv::Decoration, Vec<usize>>,
+}
+
+impl Candidates {
+    fn position(&self, opcode: spirv::Op, decoration: &[mr::Operand]) -> Option<usize> {
+        let key = match decoration.first() {
+            Some(&mr::Operand::Decoration(d)) => d,
+            _ => return None,
+        };
+        self.index.get(&key)?.iter().cloned().find(|&i| {
+            self.candidates[i].opcode == opcode && self.candidates[i].decoration[..] == *decoration
+        })
+    }
+
+    fn add(&mut self, opcode: spirv::Op, decoration: &[mr::Operand], target: Word) {
+        if let Some(i) = self.position(opcode, decoration) {
+            self.candidates[i].targets.push(target);
+            return;
+        }
+        if let Some(&mr::Operand::Decoration(key)) = decoration.first() {
+            self.index.entry(key).or_default().push(self.candidates.len());
+            self.candidates.push(Candidate {
+                opcode,
+                decoration: decoration.to_vec(),
+                targets: vec![target],
+            });
+        }
+    }
+}
+
+/// Groups identical decorations applied to at least `min_targets` different
+/// targets into decoration groups.
+///
+/// For each such decoration, an OpDecorationGroup is created, the decoration
+/// is applied to the group, and the group is applied to all the original
+/// targets via a single OpGroupDecorate. Member decorations, and
+/// decorations of existing decoration groups, are left as is.
+///
+/// This is the reverse of [`expand_decoration_groups`](fn.expand_decoration_groups.html)
+/// and mostly useful for reducing the size of modules targeting SPIR-V
+/// versions where decoration groups are not deprecated yet.
+///
+/// Returns the number of decoration groups created.
+pub fn group_duplicate_decorations(module: &mut mr::Module, min_targets: usize) -> usize {
+    remove_duplicate_decorations(module);
+
+    // Decoration groups cannot be targets of OpGroupDecorate.
+    let groups = decoration_groups(module);
+    let target = |inst: &mr::Instruction| match (inst.class.opcode, inst.operands.first()) {
+        (spirv::Op::Decorate, Some(&mr::Operand::IdRef(target))) |
+        (spirv::Op::DecorateId, Some(&mr::Operand::IdRef(target)))
+            if !groups.contains(&target) => Some(target),
+        _ => None,
+    };
+
+    let mut candidates = Candidates::default();
+    for inst in &module.annotations {
+        if let Some(target) = target(inst) {
+            candidates.add(inst.class.opcode, &inst.operands[1..], target);
+        }
+    }
+
+    let min_targets = min_targets.max(2);
+    let mut grouped = HashSet::new();
+    let mut new_annotations = vec![];
+    for (i, candidate) in candidates.candidates.iter().enumerate() {
+        if candidate.targets.len() < min_targets {
+            continue;
+        }
+        let group = module.allocate_id();
+        new_annotations.push(mr::Instruction::new(
+            spirv::Op::DecorationGroup,
+            None,
+            Some(group),
+            vec![],
+        ));
+        let mut operands = vec![mr::Operand::IdRef(group)];
+        operands.extend_from_slice(&candidate.decoration);
+        new_annotations.push(mr::Instruction::new(candidate.opcode, None, None, operands));
+        new_annotations.push(mr::Instruction::new(
+            spirv::Op::GroupDecorate,
+            None,
+            None,
+            ::std::iter::once(group)
+                .chain(candidate.targets.iter().cloned())
+                .map(mr::Operand::IdRef)
+                .collect(),
+        ));
+        grouped.insert(i);
+    }
+
+    let count = grouped.len();
+    if count != 0 {
+        module.annotations.retain(|inst| {
+            target(inst).is_none() ||
+                candidates
+                    .position(inst.class.opcode, &inst.operands[1..])
+                    .is_none_or(|i| !grouped.contains(&i))
+        });
+        // Decoration groups must be declared before being used.
+        new_annotations.append(&mut module.annotations);
+        module.annotations = new_annotations;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use validate::check_decoration_groups;
+    use super::{expand_decoration_groups, group_duplicate_decorations,
+                remove_duplicate_decorations};
+
+    fn disas_annotations(module: &mr::Module) -> Vec<String> {
+        module.annotations.iter().map(|i| i.disassemble()).collect()
+    }
+
+    #[test]
+    fn test_expand_decoration_groups() {
+        let mut b = mr::Builder::new();
+        let group = b.decoration_group();
+        b.decorate(group, spirv::Decoration::Restrict, vec![]);
+        b.decorate(group, spirv::Decoration::Binding, vec![mr::Operand::LiteralInt32(1)]);
+        b.name(group, "group");
+        b.group_decorate(group, vec![10, 11]);
+        b.group_member_decorate(group, vec![(12, 3)]);
+        let mut m = b.module();
+
+        assert_eq!(1, expand_decoration_groups(&mut m));
+        assert_eq!(
+            vec![
+                "OpDecorate %10 Restrict",
+                "OpDecorate %10 Binding 1",
+                "OpDecorate %11 Restrict",
+                "OpDecorate %11 Binding 1",
+                "OpMemberDecorate %12 3 Restrict",
+                "OpMemberDecorate %12 3 Binding 1",
+            ],
+            disas_annotations(&m)
+        );
+        assert!(m.debugs.is_empty());
+    }
+
+    #[test]
+    fn test_expand_decoration_groups_with_ids() {
+        let mut b = mr::Builder::new();
+        let group = b.decoration_group();
+        b.decorate_id(group, spirv::Decoration::AlignmentId, vec![mr::Operand::IdRef(5)]);
+        b.decorate(group, spirv::Decoration::Restrict, vec![]);
+        b.group_decorate(group, vec![10]);
+        b.group_member_decorate(group, vec![(12, 0)]);
+        let mut m = b.module();
+
+        assert_eq!(1, expand_decoration_groups(&mut m));
+        assert_eq!(
+            vec![
+                "OpDecorateId %10 AlignmentId %5",
+                "OpDecorate %10 Restrict",
+                "OpMemberDecorate %12 0 Restrict",
+            ],
+            disas_annotations(&m)
+        );
+    }
+
+    #[test]
+    fn test_remove_duplicate_decorations() {
+        let mut b = mr::Builder::new();
+        b.decorate(1, spirv::Decoration::Flat, vec![]);
+        b.decorate(1, spirv::Decoration::Flat, vec![]);
+        b.decorate(2, spirv::Decoration::Flat, vec![]);
+        b.member_decorate(3, 0, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(0)]);
+        b.member_decorate(3, 0, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(0)]);
+        b.member_decorate(3, 1, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(0)]);
+        let mut m = b.module();
+
+        assert_eq!(2, remove_duplicate_decorations(&mut m));
+        assert_eq!(
+            vec![
+                "OpDecorate %1 Flat",
+                "OpDecorate %2 Flat",
+                "OpMemberDecorate %3 0 Offset 0",
+                "OpMemberDecorate %3 1 Offset 0",
+            ],
+            disas_annotations(&m)
+        );
+    }
+
+    #[test]
+    fn test_group_duplicate_decorations() {
+        let mut b = mr::Builder::new();
+        let ids: Vec<u32> = (0..4).map(|_| b.id()).collect();
+        b.decorate(ids[0], spirv::Decoration::Flat, vec![]);
+        b.decorate(ids[1], spirv::Decoration::Flat, vec![]);
+        b.decorate(ids[2], spirv::Decoration::Flat, vec![]);
+        b.decorate(ids[3], spirv::Decoration::Centroid, vec![]);
+        let mut m = b.module();
+
+        assert_eq!(1, group_duplicate_decorations(&mut m, 3));
+        assert_eq!(
+            vec![
+                "%5 = OpDecorationGroup",
+                "OpDecorate %5 Flat",
+                "OpGroupDecorate %5 %1 %2 %3",
+                "OpDecorate %4 Centroid",
+            ],
+            disas_annotations(&m)
+        );
+        assert_eq!(6, m.header.as_ref().unwrap().bound);
+
+        // Round trip.
+        assert_eq!(1, expand_decoration_groups(&mut m));
+        assert_eq!(
+            vec![
+                "OpDecorate %1 Flat",
+                "OpDecorate %2 Flat",
+                "OpDecorate %3 Flat",
+                "OpDecorate %4 Centroid",
+            ],
+            disas_annotations(&m)
+        );
+    }
+
+    #[test]
+    fn test_group_duplicate_decorations_skips_groups() {
+        let mut b = mr::Builder::new();
+        let group = b.decoration_group();
+        let ids: Vec<u32> = (0..3).map(|_| b.id()).collect();
+        let alignment = b.id();
+        b.decorate(group, spirv::Decoration::Flat, vec![]);
+        b.group_decorate(group, vec![ids[0]]);
+        b.decorate(ids[1], spirv::Decoration::Flat, vec![]);
+        b.decorate(ids[2], spirv::Decoration::Flat, vec![]);
+        b.decorate_id(ids[1], spirv::Decoration::AlignmentId, vec![mr::Operand::IdRef(alignment)]);
+        b.decorate_id(ids[2], spirv::Decoration::AlignmentId, vec![mr::Operand::IdRef(alignment)]);
+        let mut m = b.module();
+
+        assert_eq!(2, group_duplicate_decorations(&mut m, 2));
+        assert_eq!(
+            vec![
+                "%6 = OpDecorationGroup",
+                "OpDecorate %6 Flat",
+                "OpGroupDecorate %6 %3 %4",
+                "%7 = OpDecorationGroup",
+                "OpDecorateId %7 AlignmentId %5",
+                "OpGroupDecorate %7 %3 %4",
+                "%1 = OpDecorationGroup",
+                "OpDecorate %1 Flat",
+                "OpGroupDecorate %1 %2",
+            ],
+            disas_annotations(&m)
+        );
+        assert!(check_decoration_groups(&m).is_empty());
+    }
+}
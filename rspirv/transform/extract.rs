@@ -0,0 +1,139 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+
+/// Returns the ids among the given `operands`.
+fn id_refs(operands: &[mr::Operand]) -> impl Iterator<Item = Word> + '_ {
+    operands.iter().filter_map(|operand| match *operand {
+        mr::Operand::IdRef(id) => Some(id),
+        _ => None,
+    })
+}
+
+/// Extracts the entry point with the given `name` and `execution_model`
+/// from `module` into a standalone module.
+///
+/// The extracted module contains the OpEntryPoint instruction, its
+/// execution modes, and everything reachable from the entry point: the
+/// functions it transitively calls, and all types, constants, and global
+/// variables used by them or listed in its interface, together with their
+/// decorations and debug names. Capabilities, extensions, and the memory
+/// model are copied as they are. Ids are kept as they are in `module`.
+/// This is the inverse of merging modules, e.g., for splitting modules
+/// with multiple entry points for APIs expecting one entry point per
+/// binary.
+///
+/// See [`mr::extract_function`](../mr/fn.extract_function.html) for the
+/// details of what is considered reachable.
+///
+/// Returns `None` if there is no such entry point.
+pub fn extract_entry_point(
+    module: &mr::Module,
+    name: &str,
+    execution_model: spirv::ExecutionModel,
+) -> Option<mr::Module> {
+    let entry_point = module.entry_points.iter().find(|inst| {
+        match (inst.operands.first(), inst.operands.get(2)) {
+            (Some(&mr::Operand::ExecutionModel(model)), Some(mr::Operand::LiteralString(s))) => {
+                model == execution_model && s == name
+            }
+            _ => false,
+        }
+    })?;
+    let function = match entry_point.operands.get(1) {
+        Some(&mr::Operand::IdRef(id)) => id,
+        _ => return None,
+    };
+    let execution_modes: Vec<&mr::Instruction> = module
+        .execution_modes
+        .iter()
+        .filter(|inst| inst.operands.first() == Some(&mr::Operand::IdRef(function)))
+        .collect();
+
+    // Execution modes may refer to constants, e.g., with OpExecutionModeId.
+    let mut roots: Vec<Word> = id_refs(&entry_point.operands[3..]).collect();
+    for inst in &execution_modes {
+        roots.extend(id_refs(&inst.operands[1..]));
+    }
+    let mut extracted = mr::extract_function_with(module, function, &roots)?;
+    extracted.entry_points.push(entry_point.clone());
+    extracted.execution_modes = execution_modes.into_iter().cloned().collect();
+    Some(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::extract_entry_point;
+
+    #[test]
+    fn test_extract_entry_point() {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let input_ptr = b.type_pointer(None, spirv::StorageClass::Input, float);
+        let output_ptr = b.type_pointer(None, spirv::StorageClass::Output, float);
+        let input = b.variable(input_ptr, None, spirv::StorageClass::Input, None);
+        let output = b.variable(output_ptr, None, spirv::StorageClass::Output, None);
+        b.decorate(output, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(0)]);
+        let voidf = b.type_function(void, vec![]);
+
+        let vert = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let frag = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let value = b.load(float, None, input, None, vec![]).unwrap();
+        b.store(output, value, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+
+        b.entry_point(spirv::ExecutionModel::Vertex, vert, "main", vec![]);
+        b.entry_point(spirv::ExecutionModel::Fragment, frag, "main", vec![input, output]);
+        b.execution_mode(vert, spirv::ExecutionMode::Xfb, vec![]);
+        b.execution_mode(frag, spirv::ExecutionMode::OriginUpperLeft, vec![]);
+        let module = b.module();
+
+        let m = extract_entry_point(&module, "main", spirv::ExecutionModel::Fragment).unwrap();
+        assert_eq!(1, m.entry_points.len());
+        assert_eq!(
+            "OpEntryPoint Fragment %10 \"main\" %5 %6",
+            m.entry_points[0].disassemble()
+        );
+        assert_eq!(
+            vec!["OpExecutionMode %10 OriginUpperLeft"],
+            m.execution_modes.iter().map(|i| i.disassemble()).collect::<Vec<_>>()
+        );
+        assert_eq!(1, m.functions.len());
+        assert_eq!(1, m.annotations.len());
+        // Both types of the pointers are kept, but not the vertex shader.
+        assert_eq!(7, m.types_global_values.len());
+        assert!(m.memory_model.is_some());
+
+        let m = extract_entry_point(&module, "main", spirv::ExecutionModel::Vertex).unwrap();
+        assert_eq!(1, m.functions.len());
+        assert!(m.annotations.is_empty());
+        assert_eq!(2, m.types_global_values.len());
+        assert!(extract_entry_point(&module, "other", spirv::ExecutionModel::Vertex).is_none());
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use grammar::Opcode;
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Removes redundant OpLoad and OpStore instructions within each basic
+/// block.
+///
+/// An OpLoad is redundant if the same pointer was already loaded from or
+/// stored to earlier in the block; its uses are replaced by the value
+/// loaded or stored before. An OpStore is redundant if the same pointer is
+/// stored to again later in the block without the memory being read in
+/// between.
+///
+/// This is deliberately conservative about aliasing: any store forgets all
+/// previously known values, and any remaining load keeps all previous
+/// stores. Instructions that may have side effects, e.g., function calls,
+/// barriers, and atomics, forget everything. Accesses with the Volatile
+/// memory operand, and accesses through pointers based on variables
+/// decorated with Volatile or Coherent (or having such members) are never
+/// touched.
+///
+/// Returns the number of instructions removed.
+pub fn remove_redundant_loads_stores(module: &mut mr::Module) -> usize {
+    let excluded = excluded_variables(module);
+    let mut removed = HashSet::new();
+    let mut count = 0;
+    for function in &mut module.functions {
+        count += remove_redundant_loads_stores_in(function, &excluded, &mut removed);
+    }
+    if !removed.is_empty() {
+        let refers_to_removed = |inst: &mr::Instruction| match inst.operands.first() {
+            Some(mr::Operand::IdRef(id)) => removed.contains(id),
+            _ => false,
+        };
+        module.debugs.retain(|inst| !refers_to_removed(inst));
+        module.annotations.retain(|inst| !refers_to_removed(inst));
+    }
+    count
+}
+
+/// Returns the ids decorated with Volatile or Coherent, and the variables
+/// whose type contains members decorated so.
+fn excluded_variables(module: &mr::Module) -> HashSet<Word> {
+    let mut excluded = HashSet::new();
+    let mut excluded_types = HashSet::new();
+    for inst in &module.annotations {
+        let (target, decoration) = match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate, &[mr::Operand::IdRef(target), mr::Operand::Decoration(d), ..]) |
+            (
+                spirv::Op::MemberDecorate,
+                &[mr::Operand::IdRef(target), _, mr::Operand::Decoration(d), ..],
+            ) => (target, d),
+            _ => continue,
+        };
+        if decoration == spirv::Decoration::Volatile || decoration == spirv::Decoration::Coherent {
+            if inst.class.opcode == spirv::Op::Decorate {
+                excluded.insert(target);
+            } else {
+                excluded_types.insert(target);
+            }
+        }
+    }
+    if excluded_types.is_empty() {
+        return excluded;
+    }
+
+    // Propagate to the aggregates and pointers containing excluded types.
+    // Types are declared before use, so a single pass suffices.
+    for inst in &module.types_global_values {
+        let contains_excluded = match inst.class.opcode {
+            spirv::Op::TypeStruct |
+            spirv::Op::TypeArray |
+            spirv::Op::TypeRuntimeArray |
+            spirv::Op::TypePointer => inst.operands.iter().any(|operand| match *operand {
+                mr::Operand::IdRef(id) => excluded_types.contains(&id),
+                _ => false,
+            }),
+            spirv::Op::Variable => {
+                if inst.result_type.is_some_and(|ty| excluded_types.contains(&ty)) {
+                    excluded.extend(inst.result_id);
+                }
+                false
+            }
+            _ => false,
+        };
+        if contains_excluded {
+            excluded_types.extend(inst.result_id);
+        }
+    }
+    for inst in module.functions.iter().flat_map(|f| f.all_inst_iter()) {
+        if inst.class.opcode == spirv::Op::Variable &&
+            inst.result_type.is_some_and(|ty| excluded_types.contains(&ty))
+        {
+            excluded.extend(inst.result_id);
+        }
+    }
+    excluded
+}
+
+fn is_volatile_access(inst: &mr::Instruction, index: usize) -> bool {
+    match inst.operands.get(index) {
+        Some(&mr::Operand::MemoryAccess(access)) => {
+            access.contains(spirv::MemoryAccess::VOLATILE)
+        }
+        _ => false,
+    }
+}
+
+fn remove_redundant_loads_stores_in(
+    function: &mut mr::Function,
+    excluded: &HashSet<Word>,
+    removed: &mut HashSet<Word>,
+) -> usize {
+    // Base pointers of derived pointers.
+    let mut bases = HashMap::new();
+    for inst in function.all_inst_iter() {
+        match inst.class.opcode {
+            spirv::Op::AccessChain |
+            spirv::Op::InBoundsAccessChain |
+            spirv::Op::PtrAccessChain |
+            spirv::Op::InBoundsPtrAccessChain |
+            spirv::Op::CopyObject => {
+                if let (Some(id), Some(&mr::Operand::IdRef(base))) =
+                    (inst.result_id, inst.operands.first())
+                {
+                    bases.insert(id, base);
+                }
+            }
+            _ => {}
+        }
+    }
+    let is_excluded = |mut pointer: Word| {
+        let mut depth = 0;
+        loop {
+            if excluded.contains(&pointer) {
+                return true;
+            }
+            match bases.get(&pointer) {
+                // Guard against cycles in malformed input.
+                Some(&base) if depth < bases.len() => pointer = base,
+                _ => return false,
+            }
+            depth += 1;
+        }
+    };
+
+    let mut replacements: HashMap<Word, Word> = HashMap::new();
+    let resolve = |replacements: &HashMap<Word, Word>, mut id: Word| {
+        while let Some(&next) = replacements.get(&id) {
+            id = next;
+        }
+        id
+    };
+
+    let mut count = 0;
+    for block in &mut function.basic_blocks {
+        // Known values of pointers and the index of the last store to
+        // pointers not read since.
+        let mut values: HashMap<Word, Word> = HashMap::new();
+        let mut pending: HashMap<Word, usize> = HashMap::new();
+        let mut dead = vec![false; block.instructions.len()];
+        for (index, inst) in block.instructions.iter().enumerate() {
+            let pointer = match inst.operands.first() {
+                Some(&mr::Operand::IdRef(id)) => Some(id),
+                _ => None,
+            };
+            match (inst.class.opcode, pointer) {
+                (spirv::Op::Load, Some(pointer)) => {
+                    if is_volatile_access(inst, 1) || is_excluded(pointer) {
+                        pending.clear();
+                        continue;
+                    }
+                    let result = match inst.result_id {
+                        Some(id) => id,
+                        None => continue,
+                    };
+                    if let Some(&value) = values.get(&pointer) {
+                        replacements.insert(result, value);
+                        dead[index] = true;
+                    } else {
+                        values.insert(pointer, result);
+                        pending.clear();
+                    }
+                }
+                (spirv::Op::Store, Some(pointer)) => {
+                    values.clear();
+                    if is_volatile_access(inst, 2) || is_excluded(pointer) {
+                        pending.clear();
+                        continue;
+                    }
+                    if let Some(previous) = pending.insert(pointer, index) {
+                        dead[previous] = true;
+                    }
+                    if let Some(&mr::Operand::IdRef(value)) = inst.operands.get(1) {
+                        values.insert(pointer, resolve(&replacements, value));
+                    }
+                }
+                (opcode, _) => {
+                    if opcode.may_have_side_effects() {
+                        values.clear();
+                        pending.clear();
+                    }
+                }
+            }
+        }
+        let mut index = 0;
+        block.instructions.retain(|inst| {
+            index += 1;
+            if dead[index - 1] {
+                removed.extend(inst.result_id);
+                count += 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    if !replacements.is_empty() {
+        for block in &mut function.basic_blocks {
+            for inst in &mut block.instructions {
+                for operand in &mut inst.operands {
+                    if let mr::Operand::IdRef(ref mut id) = *operand {
+                        *id = resolve(&replacements, *id);
+                    }
+                }
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::remove_redundant_loads_stores;
+
+    fn disas_block(module: &mr::Module) -> Vec<String> {
+        module.functions[0].basic_blocks[0]
+            .instructions
+            .iter()
+            .map(|i| i.disassemble())
+            .collect()
+    }
+
+    /// Builds a function with a single block, calling `body` with the
+    /// builder, two pointers to a float, and a float value.
+    fn build<F>(volatile: bool, body: F) -> mr::Module
+    where
+        F: FnOnce(&mut mr::Builder, spirv::Word, spirv::Word, spirv::Word),
+    {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Function, float);
+        let value = b.constant_f32(float, 1.0);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let x = b.variable(ptr, None, spirv::StorageClass::Function, None);
+        let y = b.variable(ptr, None, spirv::StorageClass::Function, None);
+        if volatile {
+            b.decorate(y, spirv::Decoration::Volatile, vec![]);
+        }
+        body(&mut b, x, y, value);
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_remove_redundant_loads() {
+        let mut m = build(false, |b, x, _, _| {
+            let float = 2;
+            let a = b.load(float, None, x, None, vec![]).unwrap();
+            b.name(a, "a");
+            let c = b.load(float, None, x, None, vec![]).unwrap();
+            b.name(c, "c");
+            b.fadd(float, None, a, c).unwrap();
+        });
+        assert_eq!(1, remove_redundant_loads_stores(&mut m));
+        assert_eq!(
+            vec![
+                "%8 = OpVariable  %3  Function",
+                "%9 = OpVariable  %3  Function",
+                "%10 = OpLoad  %2  %8",
+                "%12 = OpFAdd  %2  %10 %10",
+                "OpReturn",
+            ],
+            disas_block(&m)
+        );
+        assert_eq!(1, m.debugs.len());
+    }
+
+    #[test]
+    fn test_forward_stores() {
+        let mut m = build(false, |b, x, y, value| {
+            let float = 2;
+            b.store(x, value, None, vec![]).unwrap();
+            let a = b.load(float, None, x, None, vec![]).unwrap();
+            b.store(y, a, None, vec![]).unwrap();
+            // The store to y may alias x; x needs to be loaded again.
+            b.load(float, None, x, None, vec![]).unwrap();
+        });
+        assert_eq!(1, remove_redundant_loads_stores(&mut m));
+        assert_eq!(
+            vec![
+                "%8 = OpVariable  %3  Function",
+                "%9 = OpVariable  %3  Function",
+                "OpStore %8 %4",
+                "OpStore %9 %4",
+                "%11 = OpLoad  %2  %8",
+                "OpReturn",
+            ],
+            disas_block(&m)
+        );
+    }
+
+    #[test]
+    fn test_remove_dead_stores() {
+        let mut m = build(false, |b, x, y, value| {
+            let float = 2;
+            b.store(x, value, None, vec![]).unwrap();
+            b.store(y, value, None, vec![]).unwrap();
+            b.store(x, value, None, vec![]).unwrap();
+            // The load is replaced by the stored value, so it does not read
+            // y, but the next one may.
+            let a = b.load(float, None, x, None, vec![]).unwrap();
+            b.store(y, a, None, vec![]).unwrap();
+            b.load(float, None, x, None, vec![]).unwrap();
+            b.store(y, value, None, vec![]).unwrap();
+        });
+        assert_eq!(3, remove_redundant_loads_stores(&mut m));
+        assert_eq!(
+            vec![
+                "%8 = OpVariable  %3  Function",
+                "%9 = OpVariable  %3  Function",
+                "OpStore %8 %4",
+                "OpStore %9 %4",
+                "%11 = OpLoad  %2  %8",
+                "OpStore %9 %4",
+                "OpReturn",
+            ],
+            disas_block(&m)
+        );
+    }
+
+    #[test]
+    fn test_keep_volatile_accesses() {
+        let mut m = build(true, |b, x, y, value| {
+            let float = 2;
+            b.load(float, None, y, None, vec![]).unwrap();
+            b.load(float, None, y, None, vec![]).unwrap();
+            b.store(y, value, None, vec![]).unwrap();
+            b.store(y, value, None, vec![]).unwrap();
+            let volatile = Some(spirv::MemoryAccess::VOLATILE);
+            b.load(float, None, x, volatile, vec![]).unwrap();
+            b.load(float, None, x, volatile, vec![]).unwrap();
+            b.store(x, value, volatile, vec![]).unwrap();
+            b.store(x, value, volatile, vec![]).unwrap();
+        });
+        assert_eq!(0, remove_redundant_loads_stores(&mut m));
+    }
+
+    #[test]
+    fn test_side_effects() {
+        let mut m = build(false, |b, x, _, value| {
+            let float = 2;
+            b.store(x, value, None, vec![]).unwrap();
+            b.memory_barrier(0, 0).unwrap();
+            b.store(x, value, None, vec![]).unwrap();
+            b.load(float, None, x, None, vec![]).unwrap();
+        });
+        // Only the load is removed.
+        assert_eq!(1, remove_redundant_loads_stores(&mut m));
+        assert_eq!(
+            vec![
+                "%8 = OpVariable  %3  Function",
+                "%9 = OpVariable  %3  Function",
+                "OpStore %8 %4",
+                "OpMemoryBarrier %0 %0",
+                "OpStore %8 %4",
+                "OpReturn",
+            ],
+            disas_block(&m)
+        );
+    }
+}
@@ -0,0 +1,392 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The order in which [`assign_locations`](fn.assign_locations.html)
+/// visits the variables lacking a Location.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LocationOrder {
+    /// The order of the variable declarations.
+    Declaration,
+    /// The order of the OpName names of the variables; unnamed variables
+    /// follow in the order of their declarations.
+    Name,
+}
+
+/// A Location decoration added by
+/// [`assign_locations`](fn.assign_locations.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LocationAssignment {
+    /// The result id of the variable.
+    pub variable: Word,
+    /// The storage class of the variable, `Input` or `Output`.
+    pub storage_class: spirv::StorageClass,
+    /// The assigned location.
+    pub location: u32,
+}
+
+/// A mismatch between the outputs of one stage and the inputs of the next.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InterfaceMismatch {
+    /// A user-defined variable of the interface has no Location.
+    MissingLocation {
+        variable: Word,
+        storage_class: spirv::StorageClass,
+    },
+    /// No output of the producer is at the location of an input of the
+    /// consumer.
+    MissingOutput { location: u32, input: Word },
+    /// The output and the input at a location have different types.
+    TypeMismatch { location: u32, output: Word, input: Word },
+}
+
+impl fmt::Display for InterfaceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InterfaceMismatch::MissingLocation { variable, storage_class } => {
+                write!(f, "{:?} variable %{} has no Location", storage_class, variable)
+            }
+            InterfaceMismatch::MissingOutput { location, input } => {
+                write!(f, "input %{} at location {} has no matching output", input, location)
+            }
+            InterfaceMismatch::TypeMismatch { location, output, input } => write!(
+                f,
+                "output %{} and input %{} at location {} have different types",
+                output, input, location
+            ),
+        }
+    }
+}
+
+/// The user-defined Input and Output variables of a module.
+struct Interface<'a> {
+    defs: HashMap<Word, &'a mr::Instruction>,
+    /// (variable, storage class, pointee type, Location) in declaration
+    /// order.
+    variables: Vec<(Word, spirv::StorageClass, Word, Option<u32>)>,
+}
+
+impl<'a> Interface<'a> {
+    fn new(module: &'a mr::Module) -> Interface<'a> {
+        let defs: HashMap<Word, &mr::Instruction> = module
+            .types_global_values
+            .iter()
+            .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+            .collect();
+        let decorations = module.decoration_index();
+        let mut interface = Interface { defs, variables: vec![] };
+        for inst in &module.types_global_values {
+            let (id, storage_class) = match (inst.class.opcode, inst.result_id,
+                                             inst.operands.first()) {
+                (spirv::Op::Variable, Some(id), Some(&mr::Operand::StorageClass(sc)))
+                    if sc == spirv::StorageClass::Input || sc == spirv::StorageClass::Output => {
+                    (id, sc)
+                }
+                _ => continue,
+            };
+            let pointee = match inst.result_type.and_then(|ty| interface.defs.get(&ty)) {
+                Some(pointer) => match pointer.operands.get(1) {
+                    Some(&mr::Operand::IdRef(pointee)) => pointee,
+                    _ => continue,
+                },
+                None => continue,
+            };
+            if decorations.has_decoration(id, spirv::Decoration::BuiltIn) ||
+                interface.has_builtin_members(&decorations, pointee) {
+                continue;
+            }
+            let location = decorations.get_decoration_value(id, spirv::Decoration::Location);
+            interface.variables.push((id, storage_class, pointee, location));
+        }
+        interface
+    }
+
+    /// Returns true if `ty` is a struct, or an array of structs, with
+    /// built-in members, like the `gl_PerVertex` block.
+    fn has_builtin_members(&self, decorations: &mr::DecorationIndex, ty: Word) -> bool {
+        match self.defs.get(&ty) {
+            Some(def) if def.class.opcode == spirv::Op::TypeStruct => (0..def.operands.len())
+                .any(|member| {
+                    decorations
+                        .get_member_decorations(ty, member as u32)
+                        .any(|inst| inst.operands.get(2) ==
+                             Some(&mr::Operand::Decoration(spirv::Decoration::BuiltIn)))
+                }),
+            Some(def) if def.class.opcode == spirv::Op::TypeArray => match def.operands.first() {
+                Some(&mr::Operand::IdRef(element)) => {
+                    self.has_builtin_members(decorations, element)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the number of locations consumed by a variable of type `ty`.
+    fn slots(&self, ty: Word) -> u32 {
+        let def = match self.defs.get(&ty) {
+            Some(def) => def,
+            None => return 1,
+        };
+        let id = |index: usize| match def.operands.get(index) {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        };
+        let literal = |index: usize| match def.operands.get(index) {
+            Some(&mr::Operand::LiteralInt32(value)) => value,
+            _ => 1,
+        };
+        match def.class.opcode {
+            spirv::Op::TypeVector => {
+                let wide = id(0)
+                    .and_then(|component| self.defs.get(&component))
+                    .is_some_and(|c| c.operands.first() == Some(&mr::Operand::LiteralInt32(64)));
+                if wide && literal(1) > 2 { 2 } else { 1 }
+            }
+            spirv::Op::TypeMatrix => literal(1) * id(0).map_or(1, |column| self.slots(column)),
+            spirv::Op::TypeArray => {
+                let length = match id(1).and_then(|length| self.defs.get(&length)) {
+                    Some(constant) => match constant.operands.first() {
+                        Some(&mr::Operand::LiteralInt32(length)) => length,
+                        _ => 1,
+                    },
+                    None => 1,
+                };
+                length * id(0).map_or(1, |element| self.slots(element))
+            }
+            spirv::Op::TypeStruct => def
+                .operands
+                .iter()
+                .map(|member| match *member {
+                    mr::Operand::IdRef(member) => self.slots(member),
+                    _ => 0,
+                })
+                .sum(),
+            _ => 1,
+        }
+    }
+
+    /// Returns true if type `a` of this module and type `b` of the `other`
+    /// module are structurally the same.
+    fn same_type(&self, a: Word, other: &Interface, b: Word) -> bool {
+        let (a, b) = match (self.defs.get(&a), other.defs.get(&b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        let same_id = |x: Option<Word>, y: Option<Word>| match (x, y) {
+            (Some(x), Some(y)) => self.same_type(x, other, y),
+            (x, y) => x.is_none() && y.is_none(),
+        };
+        a.class.opcode == b.class.opcode &&
+            same_id(a.result_type, b.result_type) &&
+            a.operands.len() == b.operands.len() &&
+            a.operands.iter().zip(b.operands.iter()).all(|pair| match pair {
+                (&mr::Operand::IdRef(x), &mr::Operand::IdRef(y)) => same_id(Some(x), Some(y)),
+                (x, y) => x == y,
+            })
+    }
+}
+
+/// Adds Location decorations to all user-defined Input and Output
+/// variables lacking them, visiting them in the given `order`.
+///
+/// Built-in variables and blocks with built-in members are skipped. New
+/// locations are allocated after the highest location already used in the
+/// same storage class, taking the number of locations each variable
+/// consumes into account, so that existing assignments are preserved.
+/// Arrayed interfaces of tessellation and geometry stages are counted like
+/// regular arrays, which may leave some locations unused.
+///
+/// Returns the added locations.
+pub fn assign_locations(module: &mut mr::Module, order: LocationOrder)
+                        -> Vec<LocationAssignment> {
+    let mut assignments = vec![];
+    {
+        let interface = Interface::new(module);
+        let mut next: HashMap<spirv::StorageClass, u32> = HashMap::new();
+        for &(_, storage_class, pointee, location) in &interface.variables {
+            if let Some(location) = location {
+                let end = location + interface.slots(pointee);
+                let next = next.entry(storage_class).or_insert(0);
+                *next = (*next).max(end);
+            }
+        }
+        let mut unlocated: Vec<_> =
+            interface.variables.iter().filter(|v| v.3.is_none()).collect();
+        if order == LocationOrder::Name {
+            unlocated.sort_by_key(|v| {
+                let name = module.name_of(v.0);
+                (name.is_none(), name)
+            });
+        }
+        for &&(variable, storage_class, pointee, _) in &unlocated {
+            let next = next.entry(storage_class).or_insert(0);
+            assignments.push(LocationAssignment { variable, storage_class, location: *next });
+            *next += interface.slots(pointee);
+        }
+    }
+    for assignment in &assignments {
+        module.annotations.push(mr::Instruction::new(
+            spirv::Op::Decorate,
+            None,
+            None,
+            vec![
+                mr::Operand::IdRef(assignment.variable),
+                mr::Operand::Decoration(spirv::Decoration::Location),
+                mr::Operand::LiteralInt32(assignment.location),
+            ],
+        ));
+    }
+    assignments
+}
+
+/// Checks that the Output variables of the `producer` module match the
+/// Input variables of the `consumer` module, e.g., the outputs of a vertex
+/// shader and the inputs of a fragment shader.
+///
+/// Variables are matched by their Location decorations; built-in variables
+/// are not checked. Every input needs an output of the same type at its
+/// location, while outputs without input are allowed.
+pub fn check_stage_interfaces(producer: &mr::Module, consumer: &mr::Module)
+                              -> Vec<InterfaceMismatch> {
+    let outputs = Interface::new(producer);
+    let inputs = Interface::new(consumer);
+    let mut mismatches = vec![];
+    let mut located = HashMap::new();
+    for &(variable, storage_class, pointee, location) in &outputs.variables {
+        if storage_class != spirv::StorageClass::Output {
+            continue;
+        }
+        match location {
+            Some(location) => {
+                located.insert(location, (variable, pointee));
+            }
+            None => mismatches.push(InterfaceMismatch::MissingLocation { variable, storage_class }),
+        }
+    }
+    for &(input, storage_class, pointee, location) in &inputs.variables {
+        if storage_class != spirv::StorageClass::Input {
+            continue;
+        }
+        let location = match location {
+            Some(location) => location,
+            None => {
+                mismatches.push(InterfaceMismatch::MissingLocation {
+                    variable: input,
+                    storage_class,
+                });
+                continue;
+            }
+        };
+        match located.get(&location) {
+            None => mismatches.push(InterfaceMismatch::MissingOutput { location, input }),
+            Some(&(output, ty)) if !outputs.same_type(ty, &inputs, pointee) => {
+                mismatches.push(InterfaceMismatch::TypeMismatch { location, output, input })
+            }
+            Some(_) => {}
+        }
+    }
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use spirv::StorageClass::{Input, Output};
+    use super::{assign_locations, check_stage_interfaces};
+    use super::{InterfaceMismatch, LocationAssignment, LocationOrder};
+
+    #[test]
+    fn test_assign_locations() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let mat4 = b.type_matrix(vec4, 4);
+        let ptr_mat4 = b.type_pointer(None, Output, mat4);
+        let ptr_vec4 = b.type_pointer(None, Output, vec4);
+        let ptr_input = b.type_pointer(None, Input, vec4);
+        let position = b.variable(ptr_vec4, None, Output, None);
+        let transform = b.variable(ptr_mat4, None, Output, None);
+        let color = b.variable(ptr_vec4, None, Output, None);
+        let fixed = b.variable(ptr_vec4, None, Output, None);
+        let input = b.variable(ptr_input, None, Input, None);
+        b.name(transform, "b_transform");
+        b.name(color, "a_color");
+        b.decorate(position, spirv::Decoration::BuiltIn,
+                   vec![mr::Operand::BuiltIn(spirv::BuiltIn::Position)]);
+        b.decorate(fixed, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(1)]);
+        let mut m = b.module();
+
+        assert_eq!(
+            vec![
+                LocationAssignment { variable: color, storage_class: Output, location: 2 },
+                LocationAssignment { variable: transform, storage_class: Output, location: 3 },
+                LocationAssignment { variable: input, storage_class: Input, location: 0 },
+            ],
+            assign_locations(&mut m, LocationOrder::Name)
+        );
+        let index = m.decoration_index();
+        assert_eq!(Some(3), index.get_decoration_value(transform, spirv::Decoration::Location));
+        assert_eq!(None, index.get_decoration_value(position, spirv::Decoration::Location));
+        assert!(assign_locations(&mut m, LocationOrder::Declaration).is_empty());
+    }
+
+    fn stage(storage_class: spirv::StorageClass, components: &[(u32, Option<u32>)])
+             -> (mr::Module, Vec<spirv::Word>) {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let mut variables = vec![];
+        for &(count, location) in components {
+            let ty = b.type_vector(float, count);
+            let ptr = b.type_pointer(None, storage_class, ty);
+            let var = b.variable(ptr, None, storage_class, None);
+            if let Some(location) = location {
+                b.decorate(var, spirv::Decoration::Location,
+                           vec![mr::Operand::LiteralInt32(location)]);
+            }
+            variables.push(var);
+        }
+        (b.module(), variables)
+    }
+
+    #[test]
+    fn test_check_stage_interfaces() {
+        let (vertex, outputs) = stage(Output, &[(4, Some(0)), (2, Some(1)), (3, Some(5))]);
+        let (fragment, inputs) = stage(Input, &[(4, Some(0)), (3, Some(1)), (2, Some(2)),
+                                                (4, None)]);
+        let mismatches = check_stage_interfaces(&vertex, &fragment);
+        assert_eq!(
+            vec![
+                InterfaceMismatch::TypeMismatch {
+                    location: 1,
+                    output: outputs[1],
+                    input: inputs[1],
+                },
+                InterfaceMismatch::MissingOutput { location: 2, input: inputs[2] },
+                InterfaceMismatch::MissingLocation { variable: inputs[3], storage_class: Input },
+            ],
+            mismatches
+        );
+        assert_eq!("input %10 at location 2 has no matching output", mismatches[1].to_string());
+        assert!(check_stage_interfaces(&vertex, &stage(Input, &[(4, Some(0))]).0).is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::{MemoryAccess, Word};
+use std::collections::HashMap;
+use super::robust_access::{get_or_insert_constant, get_or_insert_uint32};
+
+/// Returns the index of the MemoryAccess operand of an OpLoad or OpStore,
+/// i.e., the index right after the pointer or the object operand.
+fn memory_access_index(inst: &mr::Instruction) -> usize {
+    if inst.class.opcode == spirv::Op::Load { 1 } else { 2 }
+}
+
+/// Adds the given memory access bits to an OpLoad or OpStore, appending
+/// `scope` as the operand of the MakePointerAvailable or MakePointerVisible
+/// bit if given.
+fn add_memory_access(inst: &mut mr::Instruction, bits: MemoryAccess, scope: Option<Word>) {
+    let index = memory_access_index(inst);
+    match inst.operands.get(index).cloned() {
+        Some(mr::Operand::MemoryAccess(mask)) => {
+            inst.operands[index] = mr::Operand::MemoryAccess(mask | bits);
+        }
+        _ => {
+            inst.operands.truncate(index);
+            inst.operands.push(mr::Operand::MemoryAccess(bits));
+        }
+    }
+    // Modules using the GLSL450 memory model have no scope operands yet,
+    // so the scope follows any Aligned literal, as the grammar orders them.
+    if let Some(scope) = scope {
+        inst.operands.push(mr::Operand::IdScope(scope));
+    }
+}
+
+/// Converts a module using the GLSL450 memory model to the Vulkan memory
+/// model, as newer Vulkan validation requires.
+///
+/// The OpMemoryModel is switched to `VulkanKHR`, and the
+/// `VulkanMemoryModelKHR` capability is added, together with the
+/// SPV_KHR_vulkan_memory_model extension for modules older than SPIR-V
+/// 1.5. Coherent and Volatile decorations, which the Vulkan memory model
+/// does not allow, are translated into memory operands of the OpLoad and
+/// OpStore instructions accessing the decorated variables, directly or
+/// through access chains:
+///
+/// - loads through Coherent variables make the pointer visible, and stores
+///   make it available, at the `QueueFamilyKHR` scope, both as
+///   non-private pointer accesses;
+/// - accesses through Volatile variables become Volatile.
+///
+/// Coherent and Volatile struct members, image accesses, and barriers are
+/// not translated. Modules not using the GLSL450 memory model are left
+/// unchanged.
+///
+/// Returns the number of memory accesses rewritten.
+pub fn upgrade_to_vulkan_memory_model(module: &mut mr::Module) -> usize {
+    let glsl450 = module.memory_model.as_ref().is_some_and(|inst| {
+        inst.operands.get(1) == Some(&mr::Operand::MemoryModel(spirv::MemoryModel::GLSL450))
+    });
+    if !glsl450 {
+        return 0;
+    }
+    if let Some(ref mut inst) = module.memory_model {
+        inst.operands[1] = mr::Operand::MemoryModel(spirv::MemoryModel::VulkanKHR);
+    }
+    let capability = mr::Operand::Capability(spirv::Capability::VulkanMemoryModelKHR);
+    if !module.capabilities.iter().any(|inst| inst.operands.first() == Some(&capability)) {
+        module.capabilities.push(mr::Instruction::new(
+            spirv::Op::Capability,
+            None,
+            None,
+            vec![capability],
+        ));
+    }
+    let extension = "SPV_KHR_vulkan_memory_model";
+    let has_extension = module.extensions.iter().any(|inst| {
+        inst.operands.first() == Some(&mr::Operand::LiteralString(extension.into()))
+    });
+    if module.version_rules().version() < (1, 5) && !has_extension {
+        module.extensions.push(mr::Instruction::new(
+            spirv::Op::Extension,
+            None,
+            None,
+            vec![mr::Operand::LiteralString(extension.into())],
+        ));
+    }
+
+    // The Coherent and Volatile decorations of variables, keyed by their
+    // targets.
+    let mut decorated: HashMap<Word, (bool, bool)> = HashMap::new();
+    module.annotations.retain(|inst| match (inst.class.opcode, &inst.operands[..]) {
+        (spirv::Op::Decorate,
+         &[mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)]) => {
+            let flags = decorated.entry(target).or_insert((false, false));
+            match decoration {
+                spirv::Decoration::Coherent => flags.0 = true,
+                spirv::Decoration::Volatile => flags.1 = true,
+                _ => return true,
+            }
+            false
+        }
+        _ => true,
+    });
+    decorated.retain(|_, &mut (coherent, volatile)| coherent || volatile);
+    if decorated.is_empty() {
+        return 0;
+    }
+
+    let mut scope = None;
+    let mut count = 0;
+    let mut functions = ::std::mem::take(&mut module.functions);
+    for function in &mut functions {
+        // The decorated variable each pointer in the function is derived
+        // from.
+        let mut roots: HashMap<Word, Word> = HashMap::new();
+        for inst in function.basic_blocks.iter_mut().flat_map(|bb| &mut bb.instructions) {
+            let pointer = match inst.operands.first() {
+                Some(&mr::Operand::IdRef(pointer)) => pointer,
+                _ => continue,
+            };
+            let root = roots.get(&pointer).cloned().unwrap_or(pointer);
+            let (coherent, volatile) = match decorated.get(&root) {
+                Some(&flags) => flags,
+                None => continue,
+            };
+            let store = match inst.class.opcode {
+                spirv::Op::AccessChain |
+                spirv::Op::InBoundsAccessChain |
+                spirv::Op::PtrAccessChain |
+                spirv::Op::InBoundsPtrAccessChain |
+                spirv::Op::CopyObject => {
+                    if let Some(id) = inst.result_id {
+                        roots.insert(id, root);
+                    }
+                    continue;
+                }
+                spirv::Op::Load => false,
+                spirv::Op::Store => true,
+                _ => continue,
+            };
+            if volatile {
+                add_memory_access(inst, MemoryAccess::VOLATILE, None);
+            }
+            if coherent {
+                let scope = *scope.get_or_insert_with(|| {
+                    let uint = get_or_insert_uint32(module);
+                    get_or_insert_constant(module, uint, spirv::Scope::QueueFamilyKHR as u32)
+                });
+                let make = if store {
+                    MemoryAccess::MAKE_POINTER_AVAILABLE_KHR
+                } else {
+                    MemoryAccess::MAKE_POINTER_VISIBLE_KHR
+                };
+                add_memory_access(inst, make | MemoryAccess::NON_PRIVATE_POINTER_KHR, Some(scope));
+            }
+            count += 1;
+        }
+    }
+    module.functions = functions;
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::upgrade_to_vulkan_memory_model;
+
+    #[test]
+    fn test_upgrade_to_vulkan_memory_model() {
+        use spirv::StorageClass::StorageBuffer;
+
+        let mut b = mr::Builder::new();
+        b.set_version(1, 3);
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let block = b.type_struct(vec![uint]);
+        let ptr_block = b.type_pointer(None, StorageBuffer, block);
+        let ptr_uint = b.type_pointer(None, StorageBuffer, uint);
+        let zero = b.constant_u32(uint, 0);
+        let voidf = b.type_function(void, vec![]);
+        let coherent = b.variable(ptr_block, None, StorageBuffer, None);
+        let volatile = b.variable(ptr_block, None, StorageBuffer, None);
+        b.decorate(coherent, spirv::Decoration::Coherent, vec![]);
+        b.decorate(volatile, spirv::Decoration::Volatile, vec![]);
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let c = b.access_chain(ptr_uint, None, coherent, vec![zero]).unwrap();
+        let v = b.access_chain(ptr_uint, None, volatile, vec![zero]).unwrap();
+        let value = b.load(uint, None, c, Some(spirv::MemoryAccess::ALIGNED),
+                           vec![mr::Operand::LiteralInt32(4)]).unwrap();
+        b.store(c, value, None, vec![]).unwrap();
+        b.store(v, value, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(3, upgrade_to_vulkan_memory_model(&mut m));
+        let disas: Vec<String> = m.all_inst_iter().map(|i| i.disassemble()).collect();
+        for expected in &[
+            "OpCapability VulkanMemoryModelKHR",
+            "OpExtension \"SPV_KHR_vulkan_memory_model\"",
+            "OpMemoryModel Logical VulkanKHR",
+            "OpDecorate %3 Block",
+            "%15 = OpConstant  %2  5",
+            "%14 = OpLoad  %2  %12 Aligned|MakePointerVisibleKHR|NonPrivatePointerKHR 4 %15",
+            "OpStore %12 %14 MakePointerAvailableKHR|NonPrivatePointerKHR %15",
+            "OpStore %13 %14 Volatile",
+        ] {
+            assert!(disas.iter().any(|d| d == expected), "missing {}", expected);
+        }
+        assert!(!disas.iter().any(|d| d.contains("Coherent") || d.contains("Decorate %8")));
+        assert_eq!(0, upgrade_to_vulkan_memory_model(&mut m));
+    }
+}
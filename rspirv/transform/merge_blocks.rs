@@ -0,0 +1,173 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use analysis::ControlFlowGraph;
+use super::unreachable::merge_instruction;
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Merges straight-line basic blocks: a block ending in an OpBranch is
+/// merged with the target if the target has no other predecessor.
+///
+/// Blocks with a merge instruction are not merged with their targets, and
+/// merge blocks and continue targets are not merged into their
+/// predecessors, to preserve the structured control flow. OpPhi
+/// instructions in merged blocks are replaced by their single incoming
+/// value; OpPhi instructions in the successors of merged blocks are updated
+/// to refer to the block they were merged into. OpName instructions of the
+/// labels of merged blocks are removed.
+///
+/// Returns the number of blocks merged.
+pub fn merge_blocks(module: &mut mr::Module) -> usize {
+    let mut merged = HashSet::new();
+    for function in &mut module.functions {
+        merge_blocks_in(function, &mut merged);
+    }
+    if !merged.is_empty() {
+        module.debugs.retain(|inst| match (inst.class.opcode, inst.operands.first()) {
+            (spirv::Op::Name, Some(&mr::Operand::IdRef(target))) => !merged.contains(&target),
+            _ => true,
+        });
+    }
+    merged.len()
+}
+
+fn merge_blocks_in(function: &mut mr::Function, merged: &mut HashSet<Word>) {
+    loop {
+        let cfg = ControlFlowGraph::new(function);
+        let mut targets = HashSet::new();
+        for block in &function.basic_blocks {
+            if let Some(inst) = merge_instruction(block) {
+                let count = if inst.class.opcode == spirv::Op::LoopMerge { 2 } else { 1 };
+                let ids = inst.operands.iter().take(count).filter_map(|operand| match *operand {
+                    mr::Operand::IdRef(id) => Some(id),
+                    _ => None,
+                });
+                targets.extend(ids);
+            }
+        }
+        let candidate = (0..cfg.len()).find_map(|index| {
+            let block = &function.basic_blocks[index];
+            let target = match block.instructions.last() {
+                Some(inst) if inst.class.opcode == spirv::Op::Branch => match inst.operands[..] {
+                    [mr::Operand::IdRef(target)] => target,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let succ = cfg.index(target)?;
+            if succ == 0 || succ == index || cfg.predecessors(succ) != [index] ||
+                targets.contains(&target) || merge_instruction(block).is_some() {
+                return None;
+            }
+            Some((index, succ))
+        });
+        let (pred, succ) = match candidate {
+            Some(pair) => pair,
+            None => break,
+        };
+
+        let pred_label = cfg.label(pred);
+        let succ_label = cfg.label(succ);
+        let block = function.basic_blocks.remove(succ);
+        let pred = if succ < pred { pred - 1 } else { pred };
+        let mut replacements = HashMap::new();
+        let mut instructions = vec![];
+        for inst in block.instructions {
+            match (inst.class.opcode, inst.result_id, inst.operands.first()) {
+                (spirv::Op::Phi, Some(id), Some(&mr::Operand::IdRef(value))) => {
+                    replacements.insert(id, value);
+                }
+                _ => instructions.push(inst),
+            }
+        }
+        function.basic_blocks[pred].instructions.pop();
+        function.basic_blocks[pred].instructions.extend(instructions);
+
+        for inst in function.basic_blocks.iter_mut().flat_map(|bb| &mut bb.instructions) {
+            let is_phi = inst.class.opcode == spirv::Op::Phi;
+            for (index, operand) in inst.operands.iter_mut().enumerate() {
+                if let mr::Operand::IdRef(ref mut id) = *operand {
+                    if is_phi && index % 2 == 1 && *id == succ_label {
+                        *id = pred_label;
+                    } else if let Some(&value) = replacements.get(id) {
+                        *id = value;
+                    }
+                }
+            }
+        }
+        merged.insert(succ_label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::merge_blocks;
+
+    #[test]
+    fn test_merge_blocks() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let bool_type = b.type_bool();
+        let cond = b.constant_true(bool_type);
+        let one = b.constant_u32(int, 1);
+        let two = b.constant_u32(int, 2);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let (header, then, tail, merge) = (b.id(), b.id(), b.id(), b.id());
+        let entry = b.begin_basic_block(None).unwrap();
+        b.branch(header).unwrap();
+        b.begin_basic_block(Some(header)).unwrap();
+        b.selection_merge(merge, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, then, merge, vec![]).unwrap();
+        b.begin_basic_block(Some(then)).unwrap();
+        b.branch(tail).unwrap();
+        b.begin_basic_block(Some(tail)).unwrap();
+        let value = b.phi(int, None, vec![(two, then)]).unwrap();
+        b.branch(merge).unwrap();
+        b.begin_basic_block(Some(merge)).unwrap();
+        b.phi(int, None, vec![(one, header), (value, tail)]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.name(tail, "tail");
+        let mut m = b.module();
+
+        assert_eq!(2, merge_blocks(&mut m));
+        let disas: Vec<String> = m.functions[0]
+            .all_inst_iter()
+            .skip(1)
+            .map(|inst| inst.disassemble())
+            .collect();
+        assert_eq!(disas,
+                   vec![format!("%{} = OpLabel", entry),
+                        format!("OpSelectionMerge %{} None", merge),
+                        format!("OpBranchConditional %4 %{} %{}", then, merge),
+                        format!("%{} = OpLabel", then),
+                        format!("OpBranch %{}", merge),
+                        format!("%{} = OpLabel", merge),
+                        format!("%15 = OpPhi  %2  %5 %{} %6 %{}", entry, then),
+                        "OpReturn".to_string(),
+                        "OpFunctionEnd".to_string()]);
+        assert!(m.debugs.is_empty());
+        assert_eq!(0, merge_blocks(&mut m));
+    }
+}
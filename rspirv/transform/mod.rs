@@ -0,0 +1,133 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transformations over the data representation of SPIR-V modules.
+//!
+//! Each transformation works in place on an [`mr::Module`](../mr/struct.Module.html)
+//! and reports what it changed, so that tools can surface the repairs or
+//! optimizations to the user. Transformations can be composed into
+//! pipelines with a [`PassManager`](struct.PassManager.html), which also
+//! accepts plugin passes from other crates implementing
+//! [`ModulePass`](trait.ModulePass.html) or [`FunctionPass`](trait.FunctionPass.html).
+//! Pipelines can also be loaded from configuration files with
+//! [`Pipeline`](struct.Pipeline.html).
+
+use mr;
+
+use spirv::Word;
+use std::collections::HashMap;
+
+pub use self::bindings::{remap_bindings, BindingChange, BindingRemapping};
+pub use self::block_members::{remove_unused_block_members, MemberRemapping};
+pub use self::branches::fold_constant_branches;
+pub use self::compact::compact_ids;
+pub use self::constants::{contract_null_constants, expand_null_constants};
+pub use self::constants::remove_duplicate_constants;
+pub use self::copies::propagate_copies;
+pub use self::dead_code::remove_dead_code;
+pub use self::decoration_groups::{expand_decoration_groups, group_duplicate_decorations};
+pub use self::decoration_groups::remove_duplicate_decorations;
+pub use self::extract::extract_entry_point;
+pub use self::load_store::remove_redundant_loads_stores;
+pub use self::locations::{assign_locations, check_stage_interfaces, LocationOrder};
+pub use self::locations::{InterfaceMismatch, LocationAssignment};
+pub use self::memory_model::upgrade_to_vulkan_memory_model;
+pub use self::merge_blocks::merge_blocks;
+pub use self::permutation::{feature_toggles, generate_variant, toggle_combinations};
+pub use self::permutation::{FeatureToggle, Variant, VariantStats};
+pub use self::pass::{Analysis, FnPass, Pass, PassError, PassManager, PassResult, PassStatistics};
+pub use self::plugin::{FunctionPass, ModulePass, PassContext, PassRegistry, PluginError};
+pub use self::pipeline::{OptionValue, PassConfig, PassOptions, Pipeline, PipelineError};
+pub use self::plugin::PLUGIN_API_VERSION;
+pub use self::precision::{narrow_relaxed_precision, widen_half_precision};
+pub use self::promote::promote_extensions_to_core;
+pub use self::prune::{prune_interfaces, InterfacePruning};
+pub use self::push_constants::convert_push_constants_to_uniform;
+pub use self::robust_access::clamp_access_chain_indices;
+pub use self::samplers::{split_combined_image_samplers, SamplerSplit};
+pub use self::specialize::specialize_bool_constants;
+pub use self::strings::remove_duplicate_strings;
+pub use self::strip::{strip_debug_info, strip_non_semantic_info, strip_reflect_info};
+pub use self::struct_offsets::{check_struct_offsets, fix_struct_offsets};
+pub use self::struct_offsets::{LayoutRule, OffsetIssue};
+pub use self::unreachable::{convert_unreachable_terminators, remove_unreachable_blocks};
+
+mod bindings;
+mod block_members;
+mod branches;
+mod compact;
+mod constants;
+mod copies;
+mod dead_code;
+mod decoration_groups;
+mod extract;
+mod load_store;
+mod locations;
+mod memory_model;
+mod merge_blocks;
+mod pass;
+mod permutation;
+mod pipeline;
+mod plugin;
+mod precision;
+mod promote;
+mod prune;
+mod push_constants;
+mod robust_access;
+mod samplers;
+mod specialize;
+mod strings;
+mod strip;
+mod struct_offsets;
+mod unreachable;
+
+/// Rewrites the result type and the id operands of the given instruction
+/// found in `replacements` to the ids they map to.
+fn rewrite_operands(inst: &mut mr::Instruction, replacements: &HashMap<Word, Word>) {
+    let ids = inst.result_type.iter_mut().chain(inst.operands.iter_mut().filter_map(|operand| {
+        match *operand {
+            mr::Operand::IdMemorySemantics(ref mut id) |
+            mr::Operand::IdScope(ref mut id) |
+            mr::Operand::IdRef(ref mut id) => Some(id),
+            _ => None,
+        }
+    }));
+    for id in ids {
+        if let Some(&new) = replacements.get(id) {
+            *id = new;
+        }
+    }
+}
+
+/// Rewrites the id operands found in `replacements` in all instructions of
+/// the given module except for the debug instructions, which the callers
+/// handle themselves.
+fn rewrite_id_uses(module: &mut mr::Module, replacements: &HashMap<Word, Word>) {
+    for inst in module.entry_points
+        .iter_mut()
+        .chain(module.execution_modes.iter_mut())
+        .chain(module.annotations.iter_mut())
+        .chain(module.types_global_values.iter_mut())
+    {
+        rewrite_operands(inst, replacements);
+    }
+    for function in &mut module.functions {
+        for inst in function.def.iter_mut().chain(function.parameters.iter_mut()) {
+            rewrite_operands(inst, replacements);
+        }
+        for inst in function.basic_blocks.iter_mut().flat_map(|bb| bb.instructions.iter_mut()) {
+            rewrite_operands(inst, replacements);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use validate::Diagnostic;
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::plugin::{FunctionPass, FunctionPassAdapter, ModulePass, ModulePassAdapter};
+use super::plugin::{PassRegistry, PluginError};
+
+/// Analyses whose results a pass may keep valid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Analysis {
+    /// The def-use chains of ids.
+    DefUse,
+    /// The control flow graphs (and dominator trees) of functions.
+    Cfg,
+}
+
+const ALL_ANALYSES: &[Analysis] = &[Analysis::DefUse, Analysis::Cfg];
+
+/// The result of running a pass.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PassResult {
+    /// The pass did not change the module.
+    Unchanged,
+    /// The pass changed the module.
+    Changed,
+    /// The pass failed; the module may be left partially transformed.
+    Failed(String),
+}
+
+/// A transformation over a module, to be run by a [`PassManager`](struct.PassManager.html).
+pub trait Pass {
+    /// Returns the name of this pass, used for statistics and errors.
+    fn name(&self) -> &str;
+
+    /// Runs this pass over the given `module`.
+    fn run(&mut self, module: &mut mr::Module) -> PassResult;
+
+    /// Returns the analyses still valid after this pass changed the module.
+    ///
+    /// By default, a pass changing the module invalidates all analyses.
+    fn preserved_analyses(&self) -> &[Analysis] {
+        &[]
+    }
+
+    /// Returns the diagnostics reported by this pass since the last call.
+    ///
+    /// By default, a pass reports no diagnostics.
+    fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        vec![]
+    }
+}
+
+/// A pass wrapping a function which returns the number of changes made.
+///
+/// All transformations in this module fit, e.g.,
+/// `FnPass::new("remove-unreachable-blocks", remove_unreachable_blocks)`.
+pub struct FnPass<F> {
+    name: &'static str,
+    func: F,
+    preserved: &'static [Analysis],
+}
+
+impl<F: FnMut(&mut mr::Module) -> usize> FnPass<F> {
+    /// Creates a new pass with the given `name` running `func`.
+    pub fn new(name: &'static str, func: F) -> FnPass<F> {
+        FnPass {
+            name,
+            func,
+            preserved: &[],
+        }
+    }
+
+    /// Declares the analyses preserved by this pass.
+    pub fn preserving(mut self, analyses: &'static [Analysis]) -> FnPass<F> {
+        self.preserved = analyses;
+        self
+    }
+}
+
+impl<F: FnMut(&mut mr::Module) -> usize> Pass for FnPass<F> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn run(&mut self, module: &mut mr::Module) -> PassResult {
+        if (self.func)(module) == 0 {
+            PassResult::Unchanged
+        } else {
+            PassResult::Changed
+        }
+    }
+
+    fn preserved_analyses(&self) -> &[Analysis] {
+        self.preserved
+    }
+}
+
+/// Statistics about one run of a pass.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassStatistics {
+    /// The name of the pass.
+    pub name: String,
+    /// The result of the pass.
+    pub result: PassResult,
+    /// The time spent in the pass, if timing is enabled.
+    pub duration: Option<Duration>,
+}
+
+/// The error returned when a pass fails.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassError {
+    /// The name of the failed pass.
+    pub pass: String,
+    /// The failure message reported by the pass.
+    pub message: String,
+}
+
+impl fmt::Display for PassError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pass '{}' failed: {}", self.pass, self.message)
+    }
+}
+
+impl ::std::error::Error for PassError {}
+
+/// Runs a sequence of passes over modules, like `spirv-opt`.
+///
+/// Passes run in the order they are added. The manager keeps track of which
+/// analyses are still valid after each pass, and optionally measures the
+/// time spent in each pass.
+#[derive(Default)]
+pub struct PassManager {
+    passes: Vec<Box<dyn Pass>>,
+    timing: bool,
+    statistics: Vec<PassStatistics>,
+    diagnostics: Vec<Diagnostic>,
+    valid: Vec<Analysis>,
+}
+
+impl PassManager {
+    /// Creates a new empty pass manager.
+    pub fn new() -> PassManager {
+        Default::default()
+    }
+
+    /// Appends the given `pass` to the end of the pipeline.
+    pub fn add_pass<P: Pass + 'static>(&mut self, pass: P) -> &mut PassManager {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    /// Appends the given boxed `pass` to the end of the pipeline.
+    pub fn add_boxed_pass(&mut self, pass: Box<dyn Pass>) -> &mut PassManager {
+        self.passes.push(pass);
+        self
+    }
+
+    /// Appends the given plugin module `pass` to the end of the pipeline.
+    ///
+    /// Returns an error if the pass requires a newer plugin interface.
+    pub fn add_module_pass(
+        &mut self,
+        pass: Box<dyn ModulePass>,
+    ) -> Result<&mut PassManager, PluginError> {
+        let pass = ModulePassAdapter::new(pass)?;
+        Ok(self.add_pass(pass))
+    }
+
+    /// Appends the given plugin function `pass` to the end of the pipeline.
+    ///
+    /// Returns an error if the pass requires a newer plugin interface.
+    pub fn add_function_pass(
+        &mut self,
+        pass: Box<dyn FunctionPass>,
+    ) -> Result<&mut PassManager, PluginError> {
+        let pass = FunctionPassAdapter::new(pass)?;
+        Ok(self.add_pass(pass))
+    }
+
+    /// Appends a new instance of the pass registered under the given `name`
+    /// in `registry` to the end of the pipeline.
+    pub fn add_registered_pass(
+        &mut self,
+        registry: &PassRegistry,
+        name: &str,
+    ) -> Result<&mut PassManager, PluginError> {
+        let pass = registry.create(name)?;
+        Ok(self.add_boxed_pass(pass))
+    }
+
+    /// Returns the names of the passes in the pipeline, in order.
+    pub fn pass_names(&self) -> Vec<&str> {
+        self.passes.iter().map(|p| p.name()).collect()
+    }
+
+    /// Enables or disables measuring the time spent in each pass.
+    pub fn set_timing(&mut self, timing: bool) -> &mut PassManager {
+        self.timing = timing;
+        self
+    }
+
+    /// Runs all passes in order over the given `module`, stopping at the
+    /// first failing pass.
+    ///
+    /// Returns whether any pass changed the module. Statistics of the
+    /// passes run are available via
+    /// [`statistics`](struct.PassManager.html#method.statistics) afterwards.
+    pub fn run(&mut self, module: &mut mr::Module) -> Result<PassResult, PassError> {
+        self.statistics.clear();
+        self.diagnostics.clear();
+        self.valid = ALL_ANALYSES.to_vec();
+        let mut changed = false;
+        for pass in &mut self.passes {
+            let start = if self.timing { Some(Instant::now()) } else { None };
+            let result = pass.run(module);
+            self.diagnostics.extend(pass.take_diagnostics());
+            self.statistics.push(PassStatistics {
+                name: pass.name().to_string(),
+                result: result.clone(),
+                duration: start.map(|s| s.elapsed()),
+            });
+            match result {
+                PassResult::Unchanged => {}
+                PassResult::Changed => {
+                    changed = true;
+                    let preserved = pass.preserved_analyses();
+                    self.valid.retain(|a| preserved.contains(a));
+                }
+                PassResult::Failed(message) => {
+                    return Err(PassError {
+                        pass: pass.name().to_string(),
+                        message,
+                    });
+                }
+            }
+        }
+        Ok(if changed { PassResult::Changed } else { PassResult::Unchanged })
+    }
+
+    /// Returns the statistics of the passes run by the last
+    /// [`run`](struct.PassManager.html#method.run).
+    pub fn statistics(&self) -> &[PassStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the diagnostics reported by the passes run by the last
+    /// [`run`](struct.PassManager.html#method.run).
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns true if analyses computed before the last
+    /// [`run`](struct.PassManager.html#method.run) of the given kind are
+    /// still valid.
+    pub fn is_preserved(&self, analysis: Analysis) -> bool {
+        self.valid.contains(&analysis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{Analysis, FnPass, Pass, PassManager, PassResult};
+    use transform::remove_duplicate_decorations;
+
+    struct FailingPass;
+
+    impl Pass for FailingPass {
+        fn name(&self) -> &str {
+            "fail"
+        }
+
+        fn run(&mut self, _: &mut mr::Module) -> PassResult {
+            PassResult::Failed("always fails".to_string())
+        }
+    }
+
+    fn module() -> mr::Module {
+        let mut b = mr::Builder::new();
+        b.decorate(1, spirv::Decoration::Flat, vec![]);
+        b.decorate(1, spirv::Decoration::Flat, vec![]);
+        b.module()
+    }
+
+    #[test]
+    fn test_run_in_order() {
+        let mut m = module();
+        let mut pm = PassManager::new();
+        pm.add_pass(
+            FnPass::new("dedup", remove_duplicate_decorations).preserving(&[Analysis::Cfg]),
+        ).add_pass(FnPass::new("dedup-again", remove_duplicate_decorations))
+            .set_timing(true);
+        assert_eq!(vec!["dedup", "dedup-again"], pm.pass_names());
+
+        assert_eq!(Ok(PassResult::Changed), pm.run(&mut m));
+        assert_eq!(1, m.annotations.len());
+        let stats = pm.statistics();
+        assert_eq!(2, stats.len());
+        assert_eq!(PassResult::Changed, stats[0].result);
+        assert_eq!(PassResult::Unchanged, stats[1].result);
+        assert!(stats.iter().all(|s| s.duration.is_some()));
+        assert!(pm.is_preserved(Analysis::Cfg));
+        assert!(!pm.is_preserved(Analysis::DefUse));
+
+        assert_eq!(Ok(PassResult::Unchanged), pm.run(&mut m));
+        assert!(pm.is_preserved(Analysis::DefUse));
+    }
+
+    #[test]
+    fn test_stop_at_failure() {
+        let mut m = module();
+        let mut pm = PassManager::new();
+        pm.add_pass(FailingPass)
+            .add_pass(FnPass::new("dedup", remove_duplicate_decorations));
+        let err = pm.run(&mut m).unwrap_err();
+        assert_eq!("pass 'fail' failed: always fails", err.to_string());
+        assert_eq!(1, pm.statistics().len());
+        assert!(pm.statistics()[0].duration.is_none());
+        assert_eq!(2, m.annotations.len());
+    }
+}
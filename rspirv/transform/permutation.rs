@@ -0,0 +1,277 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use super::{compact_ids, fold_constant_branches, remove_dead_code, remove_unreachable_blocks};
+use super::specialize::spec_ids;
+use super::specialize_bool_constants;
+use spirv::Word;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A boolean specialization constant acting as a feature toggle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeatureToggle {
+    /// The SpecId of the constant.
+    pub spec_id: u32,
+    /// The result id of the OpSpecConstantTrue or OpSpecConstantFalse.
+    pub constant: Word,
+    /// The OpName of the constant, if any.
+    pub name: Option<String>,
+    /// The default value of the constant.
+    pub default: bool,
+}
+
+/// Statistics about generating a shader variant.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariantStats {
+    /// The number of specialization constants replaced by constants.
+    pub specialized: usize,
+    /// The number of conditional branches folded.
+    pub branches_folded: usize,
+    /// The number of basic blocks removed or reduced.
+    pub blocks_removed: usize,
+    /// The number of functions and instructions removed as dead code.
+    pub dead_code_removed: usize,
+    /// The number of instructions in the module before and after pruning.
+    pub instructions: (usize, usize),
+    /// The id bound of the module before and after compaction.
+    pub bound: (Word, Word),
+}
+
+impl fmt::Display for VariantStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{} constants specialized, {} branches folded, {} blocks removed, \
+                {} removed as dead code, {} -> {} instructions, bound {} -> {}",
+               self.specialized,
+               self.branches_folded,
+               self.blocks_removed,
+               self.dead_code_removed,
+               self.instructions.0,
+               self.instructions.1,
+               self.bound.0,
+               self.bound.1)
+    }
+}
+
+/// A shader variant pruned for a combination of feature toggles.
+#[derive(Clone, Debug)]
+pub struct Variant {
+    /// The values of the feature toggles, keyed by SpecId.
+    pub toggles: BTreeMap<u32, bool>,
+    /// The pruned module.
+    pub module: mr::Module,
+    /// What pruning the module did.
+    pub stats: VariantStats,
+}
+
+/// Returns the boolean specialization constants decorated with SpecId in
+/// the given module, in the order of their SpecIds.
+pub fn feature_toggles(module: &mr::Module) -> Vec<FeatureToggle> {
+    let spec_ids = spec_ids(module);
+    let names: HashMap<Word, &str> = module.debugs
+        .iter()
+        .filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Name,
+             &[mr::Operand::IdRef(target), mr::Operand::LiteralString(ref name)]) => {
+                Some((target, name.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    let mut toggles: Vec<FeatureToggle> = module.types_global_values
+        .iter()
+        .filter_map(|inst| {
+            let default = match inst.class.opcode {
+                spirv::Op::SpecConstantTrue => true,
+                spirv::Op::SpecConstantFalse => false,
+                _ => return None,
+            };
+            let constant = inst.result_id?;
+            Some(FeatureToggle {
+                spec_id: *spec_ids.get(&constant)?,
+                constant,
+                name: names.get(&constant).map(|name| name.to_string()),
+                default,
+            })
+        })
+        .collect();
+    toggles.sort_by_key(|toggle| toggle.spec_id);
+    toggles
+}
+
+/// Enumerates all combinations of values of the given feature toggles,
+/// keyed by SpecId, starting with all toggles off.
+///
+/// There are 2<sup>n</sup> combinations for n toggles; generate variants
+/// lazily from these if there are many toggles.
+pub fn toggle_combinations(toggles: &[FeatureToggle]) -> Vec<BTreeMap<u32, bool>> {
+    (0..1u64 << toggles.len())
+        .map(|bits| {
+            toggles
+                .iter()
+                .enumerate()
+                .map(|(i, toggle)| (toggle.spec_id, bits & (1 << i) != 0))
+                .collect()
+        })
+        .collect()
+}
+
+/// Generates the variant of the given module for the given values of
+/// feature toggles, keyed by SpecId.
+///
+/// The boolean specialization constants with the given SpecIds are
+/// specialized (see [`specialize_bool_constants`](fn.specialize_bool_constants.html)),
+/// branches on them are folded, and the code which became unreachable or
+/// dead is removed, before the ids are compacted. Other specialization
+/// constants are kept, so the variant may still be specialized further.
+pub fn generate_variant(module: &mr::Module, toggles: &BTreeMap<u32, bool>) -> Variant {
+    let mut variant = module.clone();
+    let before = instruction_count(&variant);
+    let bound = variant.header.as_ref().map_or_else(|| variant.max_id() + 1, |h| h.bound());
+
+    let values: HashMap<u32, bool> = toggles.iter().map(|(&k, &v)| (k, v)).collect();
+    let specialized = specialize_bool_constants(&mut variant, &values);
+    let branches_folded = fold_constant_branches(&mut variant);
+    let blocks_removed = remove_unreachable_blocks(&mut variant);
+    let dead_code_removed = remove_dead_code(&mut variant);
+    compact_ids(&mut variant);
+
+    let stats = VariantStats {
+        specialized,
+        branches_folded,
+        blocks_removed,
+        dead_code_removed,
+        instructions: (before, instruction_count(&variant)),
+        bound: (bound, variant.header.as_ref().map_or_else(|| variant.max_id() + 1, |h| h.bound())),
+    };
+    Variant { toggles: toggles.clone(), module: variant, stats }
+}
+
+fn instruction_count(module: &mr::Module) -> usize {
+    module.all_inst_iter().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use std::collections::BTreeMap;
+    use super::{feature_toggles, generate_variant, toggle_combinations, FeatureToggle};
+
+    /// Builds a shader storing 1.0 if FOG is on, and 2.0 if FOG is on and
+    /// SHADOWS is off.
+    fn build() -> mr::Module {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let bool_type = b.type_bool();
+        let float = b.type_float(32);
+        let fog = b.spec_constant_true(bool_type);
+        let shadows = b.spec_constant_false(bool_type);
+        let no_shadows = b.spec_constant_op(bool_type, spirv::Op::LogicalNot,
+                                            vec![mr::Operand::IdRef(shadows)]).unwrap();
+        let one = b.constant_f32(float, 1.0);
+        let two = b.constant_f32(float, 2.0);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Output, float);
+        let out = b.variable(ptr, None, spirv::StorageClass::Output, None);
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        let (then1, merge1, then2, merge2) = (b.id(), b.id(), b.id(), b.id());
+        b.begin_basic_block(None).unwrap();
+        b.selection_merge(merge1, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(fog, then1, merge1, vec![]).unwrap();
+        b.begin_basic_block(Some(then1)).unwrap();
+        b.store(out, one, None, vec![]).unwrap();
+        b.selection_merge(merge2, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(no_shadows, then2, merge2, vec![]).unwrap();
+        b.begin_basic_block(Some(then2)).unwrap();
+        b.store(out, two, None, vec![]).unwrap();
+        b.branch(merge2).unwrap();
+        b.begin_basic_block(Some(merge2)).unwrap();
+        b.branch(merge1).unwrap();
+        b.begin_basic_block(Some(merge1)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Fragment, main, "main", vec![out]);
+        b.name(fog, "FOG");
+        b.decorate(fog, spirv::Decoration::SpecId, vec![mr::Operand::LiteralInt32(3)]);
+        b.decorate(shadows, spirv::Decoration::SpecId, vec![mr::Operand::LiteralInt32(1)]);
+        b.module()
+    }
+
+    #[test]
+    fn test_feature_toggles() {
+        let toggles = feature_toggles(&build());
+        assert_eq!(vec![FeatureToggle { spec_id: 1, constant: 5, name: None, default: false },
+                        FeatureToggle {
+                            spec_id: 3,
+                            constant: 4,
+                            name: Some("FOG".to_string()),
+                            default: true,
+                        }],
+                   toggles);
+        let combinations = toggle_combinations(&toggles);
+        assert_eq!(4, combinations.len());
+        assert_eq!(vec![(1, true), (3, false)],
+                   combinations[1].iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_generate_variant() {
+        let module = build();
+        let toggles: BTreeMap<u32, bool> = vec![(1, true), (3, true)].into_iter().collect();
+        let variant = generate_variant(&module, &toggles);
+        assert_eq!(3, variant.stats.specialized);
+        assert_eq!(1, variant.stats.branches_folded);
+        assert_eq!(variant.module.disassemble(),
+                   "; SPIR-V\n\
+                    ; Version: 1.3\n\
+                    ; Generator: rspirv\n\
+                    ; Bound: 14\n\
+                    OpCapability Shader\n\
+                    OpMemoryModel Logical GLSL450\n\
+                    OpEntryPoint Fragment %9 \"main\" %7\n\
+                    OpName %4 \"FOG\"\n\
+                    %1 = OpTypeVoid\n\
+                    %2 = OpTypeBool\n\
+                    %3 = OpTypeFloat 32\n\
+                    %4 = OpConstantTrue  %2 \n\
+                    %5 = OpConstant  %3  1.0\n\
+                    %6 = OpTypePointer Output %3\n\
+                    %7 = OpVariable  %6  Output\n\
+                    %8 = OpTypeFunction %1\n\
+                    %9 = OpFunction  %1  None %8\n\
+                    %10 = OpLabel\n\
+                    OpSelectionMerge %13 None\n\
+                    OpBranchConditional %4 %11 %13\n\
+                    %11 = OpLabel\n\
+                    OpStore %7 %5\n\
+                    OpBranch %12\n\
+                    %12 = OpLabel\n\
+                    OpBranch %13\n\
+                    %13 = OpLabel\n\
+                    OpReturn\n\
+                    OpFunctionEnd");
+        assert_eq!("3 constants specialized, 1 branches folded, 1 blocks removed, \
+                    3 removed as dead code, 33 -> 24 instructions, bound 18 -> 14",
+                   variant.stats.to_string());
+    }
+}
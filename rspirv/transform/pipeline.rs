@@ -0,0 +1,402 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pipelines of passes described in configuration files.
+//!
+//! A pipeline is described in a subset of TOML: optional top-level
+//! settings, followed by one `[[pass]]` table per pass in pipeline order.
+//! Each table names a pass registered in a
+//! [`PassRegistry`](struct.PassRegistry.html), and all other keys in it
+//! are options handed to the pass:
+//!
+//! ```toml
+//! timing = true
+//!
+//! [[pass]]
+//! name = "remove-duplicate-decorations"
+//!
+//! [[pass]]
+//! name = "group-duplicate-decorations"
+//! min-targets = 3
+//! ```
+//!
+//! Values can be booleans, integers, or basic strings. Comments start with
+//! `#`.
+
+use std::collections::BTreeMap;
+use std::{error, fmt};
+
+use super::pass::PassManager;
+use super::plugin::{PassRegistry, PluginError};
+
+/// The value of a pass option.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionValue {
+    /// A boolean, written as `true` or `false`.
+    Bool(bool),
+    /// A decimal integer, optionally with `_` separators.
+    Int(i64),
+    /// A basic string in double quotes.
+    Str(String),
+}
+
+impl fmt::Display for OptionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OptionValue::Bool(v) => write!(f, "{}", v),
+            OptionValue::Int(v) => write!(f, "{}", v),
+            OptionValue::Str(ref v) => write!(f, "{:?}", v),
+        }
+    }
+}
+
+/// Options configuring a pass.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PassOptions {
+    values: BTreeMap<String, OptionValue>,
+}
+
+impl PassOptions {
+    /// Creates a new empty set of options.
+    pub fn new() -> PassOptions {
+        Default::default()
+    }
+
+    /// Sets the option `key` to the given `value`.
+    pub fn set<T: Into<String>>(&mut self, key: T, value: OptionValue) -> &mut PassOptions {
+        self.values.insert(key.into(), value);
+        self
+    }
+
+    /// Returns the value of the option `key`, if set.
+    pub fn get(&self, key: &str) -> Option<&OptionValue> {
+        self.values.get(key)
+    }
+
+    /// Returns true if no options are set.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an error naming the first option not among the given `keys`.
+    pub fn expect_only(&self, keys: &[&str]) -> Result<(), String> {
+        match self.values.keys().find(|k| !keys.contains(&k.as_str())) {
+            Some(key) => Err(format!("unknown option '{}'", key)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the boolean option `key`, or `default` if not set.
+    pub fn bool(&self, key: &str, default: bool) -> Result<bool, String> {
+        match self.values.get(key) {
+            None => Ok(default),
+            Some(&OptionValue::Bool(v)) => Ok(v),
+            Some(v) => Err(format!("option '{}' expects a boolean, found {}", key, v)),
+        }
+    }
+
+    /// Returns the integer option `key`, or `default` if not set.
+    pub fn int(&self, key: &str, default: i64) -> Result<i64, String> {
+        match self.values.get(key) {
+            None => Ok(default),
+            Some(&OptionValue::Int(v)) => Ok(v),
+            Some(v) => Err(format!("option '{}' expects an integer, found {}", key, v)),
+        }
+    }
+
+    /// Returns the string option `key`, or `default` if not set.
+    pub fn str<'a>(&'a self, key: &str, default: &'a str) -> Result<&'a str, String> {
+        match self.values.get(key) {
+            None => Ok(default),
+            Some(OptionValue::Str(v)) => Ok(v),
+            Some(v) => Err(format!("option '{}' expects a string, found {}", key, v)),
+        }
+    }
+}
+
+/// One pass in a pipeline description.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassConfig {
+    /// The name the pass is registered under.
+    pub name: String,
+    /// The options handed to the pass.
+    pub options: PassOptions,
+}
+
+/// Errors in loading pipeline descriptions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PipelineError {
+    /// The description is malformed at the given line (starting from 1).
+    Syntax(usize, String),
+    /// A pass in the description could not be created.
+    Plugin(PluginError),
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PipelineError::Syntax(line, ref message) => write!(f, "line {}: {}", line, message),
+            PipelineError::Plugin(ref error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl error::Error for PipelineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PipelineError::Syntax(..) => None,
+            PipelineError::Plugin(ref error) => Some(error),
+        }
+    }
+}
+
+impl From<PluginError> for PipelineError {
+    fn from(error: PluginError) -> PipelineError {
+        PipelineError::Plugin(error)
+    }
+}
+
+/// A description of a pipeline of passes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pipeline {
+    /// Whether to measure the time spent in each pass.
+    pub timing: bool,
+    /// The passes to run, in order.
+    pub passes: Vec<PassConfig>,
+}
+
+impl Pipeline {
+    /// Parses the pipeline description in the given `text`.
+    pub fn parse(text: &str) -> Result<Pipeline, PipelineError> {
+        let mut pipeline = Pipeline::default();
+        // The pass table being parsed and the line it started at.
+        let mut current: Option<(usize, Option<String>, PassOptions)> = None;
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let error = |message: &str| PipelineError::Syntax(number, message.to_string());
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                if line != "[[pass]]" {
+                    return Err(error("expected '[[pass]]' table header"));
+                }
+                if let Some(pass) = current.take() {
+                    pipeline.passes.push(finish_pass(pass)?);
+                }
+                current = Some((number, None, PassOptions::new()));
+                continue;
+            }
+            let eq = line.find('=').ok_or_else(|| error("expected 'key = value'"))?;
+            let key = line[..eq].trim();
+            let valid_key = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+            if key.is_empty() || !key.chars().all(valid_key) {
+                return Err(error("invalid key"));
+            }
+            let value = parse_value(line[eq + 1..].trim()).ok_or_else(|| error("invalid value"))?;
+            match current {
+                Some((_, ref mut name, ref mut options)) => {
+                    if key == "name" {
+                        match value {
+                            OptionValue::Str(v) => *name = Some(v),
+                            _ => return Err(error("pass name must be a string")),
+                        }
+                    } else if options.get(key).is_some() {
+                        return Err(error("duplicate key"));
+                    } else {
+                        options.set(key, value);
+                    }
+                }
+                None => match (key, value) {
+                    ("timing", OptionValue::Bool(v)) => pipeline.timing = v,
+                    ("timing", _) => return Err(error("'timing' must be a boolean")),
+                    _ => return Err(error("unknown top-level key")),
+                },
+            }
+        }
+        if let Some(pass) = current.take() {
+            pipeline.passes.push(finish_pass(pass)?);
+        }
+        Ok(pipeline)
+    }
+
+    /// Creates a pass manager running this pipeline with passes from the
+    /// given `registry`.
+    pub fn to_pass_manager(&self, registry: &PassRegistry) -> Result<PassManager, PipelineError> {
+        let mut pm = PassManager::new();
+        pm.set_timing(self.timing);
+        for pass in &self.passes {
+            pm.add_boxed_pass(registry.create_with_options(&pass.name, &pass.options)?);
+        }
+        Ok(pm)
+    }
+}
+
+fn finish_pass(pass: (usize, Option<String>, PassOptions)) -> Result<PassConfig, PipelineError> {
+    match pass {
+        (_, Some(name), options) => Ok(PassConfig { name, options }),
+        (line, None, _) => Err(PipelineError::Syntax(line, "pass without name".to_string())),
+    }
+}
+
+/// Strips a trailing comment from the given `line`, minding strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(text: &str) -> Option<OptionValue> {
+    match text {
+        "true" => return Some(OptionValue::Bool(true)),
+        "false" => return Some(OptionValue::Bool(false)),
+        _ => {}
+    }
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        let mut value = String::new();
+        let mut chars = text[1..text.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return None,
+                '\\' => value.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c @ '"' | c @ '\\' => c,
+                    _ => return None,
+                }),
+                c => value.push(c),
+            }
+        }
+        return Some(OptionValue::Str(value));
+    }
+    text.replace('_', "").parse().ok().map(OptionValue::Int)
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{OptionValue, PassOptions, Pipeline, PipelineError};
+    use transform::{PassRegistry, PassResult, PluginError};
+
+    const PIPELINE: &str = r#"
+# Deduplicate, then group.
+timing = true
+
+[[pass]]
+name = "remove-duplicate-decorations"
+
+[[pass]]  # Comments may follow tables.
+name = "group-duplicate-decorations"
+min-targets = 2
+"#;
+
+    #[test]
+    fn test_parse() {
+        let pipeline = Pipeline::parse(PIPELINE).unwrap();
+        assert!(pipeline.timing);
+        assert_eq!(2, pipeline.passes.len());
+        assert_eq!("remove-duplicate-decorations", pipeline.passes[0].name);
+        assert!(pipeline.passes[0].options.is_empty());
+        assert_eq!(Some(&OptionValue::Int(2)), pipeline.passes[1].options.get("min-targets"));
+
+        let text = "[[pass]]\nname = \"p\"\na = false\nb = -1_000\nc = \"x # \\\"y\\\"\" # z\n";
+        let options = &Pipeline::parse(text).unwrap().passes[0].options;
+        assert_eq!(Ok(false), options.bool("a", true));
+        assert_eq!(Ok(-1000), options.int("b", 0));
+        assert_eq!(Ok("x # \"y\""), options.str("c", ""));
+        assert_eq!(Ok(7), options.int("d", 7));
+        assert_eq!(
+            Err("option 'c' expects an integer, found \"x # \\\"y\\\"\"".to_string()),
+            options.int("c", 0)
+        );
+        assert_eq!(Err("unknown option 'a'".to_string()), options.expect_only(&["b", "c"]));
+
+        assert_eq!(Ok(Pipeline::default()), Pipeline::parse("# empty\n"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text: &str| Pipeline::parse(text).unwrap_err().to_string();
+        assert_eq!("line 1: unknown top-level key", error("name = \"p\""));
+        assert_eq!("line 1: 'timing' must be a boolean", error("timing = 1"));
+        assert_eq!("line 2: expected '[[pass]]' table header", error("\n[pass]"));
+        assert_eq!("line 2: expected 'key = value'", error("[[pass]]\nname"));
+        assert_eq!("line 2: invalid value", error("[[pass]]\nname = p"));
+        assert_eq!("line 2: invalid key", error("[[pass]]\na b = 1"));
+        assert_eq!("line 3: duplicate key", error("[[pass]]\na = 1\na = 2"));
+        assert_eq!("line 2: pass name must be a string", error("[[pass]]\nname = 1"));
+        assert_eq!("line 1: pass without name", error("[[pass]]\na = 1\n[[pass]]"));
+    }
+
+    #[test]
+    fn test_to_pass_manager() {
+        let mut b = mr::Builder::new();
+        for target in 1..4 {
+            b.decorate(target, spirv::Decoration::Flat, vec![]);
+            b.decorate(target, spirv::Decoration::Flat, vec![]);
+        }
+        let mut m = b.module();
+
+        let registry = PassRegistry::with_builtin_passes();
+        let mut pm = Pipeline::parse(PIPELINE).unwrap().to_pass_manager(&registry).unwrap();
+        assert_eq!(
+            vec!["remove-duplicate-decorations", "group-duplicate-decorations"],
+            pm.pass_names()
+        );
+        assert_eq!(Ok(PassResult::Changed), pm.run(&mut m));
+        assert!(pm.statistics().iter().all(|s| s.result == PassResult::Changed));
+        assert!(pm.statistics().iter().all(|s| s.duration.is_some()));
+        // One group, its decoration and the group decorate.
+        assert_eq!(3, m.annotations.len());
+
+        let mut options = PassOptions::new();
+        options.set("rule", OptionValue::Str("scalar".to_string()));
+        assert_eq!(
+            Err(PluginError::InvalidOptions(
+                "fix-struct-offsets".to_string(),
+                "unknown layout rule 'scalar'".to_string(),
+            )),
+            registry.create_with_options("fix-struct-offsets", &options).map(|_| ())
+        );
+        let text = "[[pass]]\nname = \"strip-debug-info\"\nall = true\n";
+        assert_eq!(
+            PipelineError::Plugin(PluginError::InvalidOptions(
+                "strip-debug-info".to_string(),
+                "unknown option 'all'".to_string(),
+            )),
+            Pipeline::parse(text).unwrap().to_pass_manager(&registry).err().unwrap()
+        );
+        assert_eq!(
+            "unknown pass 'inline'",
+            Pipeline::parse("[[pass]]\nname = \"inline\"")
+                .unwrap()
+                .to_pass_manager(&registry)
+                .err()
+                .unwrap()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,624 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stable interface for passes shipped by other crates.
+//!
+//! Passes implement [`ModulePass`](trait.ModulePass.html) or
+//! [`FunctionPass`](trait.FunctionPass.html) and only see the module through
+//! a [`PassContext`](struct.PassContext.html), whose capabilities are
+//! versioned by [`PLUGIN_API_VERSION`](constant.PLUGIN_API_VERSION.html).
+//! Passes can be registered by name into a
+//! [`PassRegistry`](struct.PassRegistry.html) and instantiated from there
+//! into a [`PassManager`](struct.PassManager.html).
+
+use analysis::{ControlFlowGraph, DominatorTree};
+use mr;
+use spirv;
+use validate::Diagnostic;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use super::pass::{Analysis, Pass, PassResult};
+use super::pipeline::PassOptions;
+
+/// The version of the plugin interface provided by this crate.
+///
+/// It is bumped whenever capabilities are added to
+/// [`PassContext`](struct.PassContext.html). Passes requiring a newer
+/// version than this one are rejected on registration.
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// The view of a module given to plugin passes.
+///
+/// Besides the module itself, the context provides cached analyses, a type
+/// manager, and a sink for diagnostics. Cached analyses are dropped
+/// whenever the module is accessed mutably.
+pub struct PassContext<'a> {
+    module: &'a mut mr::Module,
+    cfgs: HashMap<usize, ControlFlowGraph>,
+    dominators: HashMap<usize, DominatorTree>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> PassContext<'a> {
+    /// Creates a new context over the given `module`.
+    pub fn new(module: &'a mut mr::Module) -> PassContext<'a> {
+        PassContext {
+            module,
+            cfgs: HashMap::new(),
+            dominators: HashMap::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    /// Returns the version of the interface provided by this context.
+    pub fn api_version(&self) -> u32 {
+        PLUGIN_API_VERSION
+    }
+
+    /// Returns the module.
+    pub fn module(&self) -> &mr::Module {
+        self.module
+    }
+
+    /// Returns the module for modification, invalidating cached analyses.
+    pub fn module_mut(&mut self) -> &mut mr::Module {
+        self.cfgs.clear();
+        self.dominators.clear();
+        self.module
+    }
+
+    /// Returns the instruction defining the given `id`, if any.
+    pub fn def(&self, id: spirv::Word) -> Option<&mr::Instruction> {
+        self.module.all_inst_iter().find(|inst| inst.result_id == Some(id))
+    }
+
+    /// Returns the control flow graph of the function at the given `index`.
+    pub fn cfg(&mut self, index: usize) -> &ControlFlowGraph {
+        let function = &self.module.functions[index];
+        self.cfgs.entry(index).or_insert_with(|| ControlFlowGraph::new(function))
+    }
+
+    /// Returns the dominator tree of the function at the given `index`.
+    pub fn dominators(&mut self, index: usize) -> &DominatorTree {
+        if !self.dominators.contains_key(&index) {
+            let tree = DominatorTree::new(self.cfg(index));
+            self.dominators.insert(index, tree);
+        }
+        &self.dominators[&index]
+    }
+
+    /// Returns the id of the type declared by `opcode` with the given
+    /// `operands`, if the module declares it.
+    pub fn find_type(&self, opcode: spirv::Op, operands: &[mr::Operand]) -> Option<spirv::Word> {
+        self.module
+            .types_global_values
+            .iter()
+            .find(|inst| inst.class.opcode == opcode && inst.operands == operands)
+            .and_then(|inst| inst.result_id)
+    }
+
+    /// Returns the id of the type declared by `opcode` with the given
+    /// `operands`, declaring it first if the module does not.
+    pub fn get_or_declare_type(
+        &mut self,
+        opcode: spirv::Op,
+        operands: Vec<mr::Operand>,
+    ) -> spirv::Word {
+        if let Some(id) = self.find_type(opcode, &operands) {
+            return id;
+        }
+        let id = self.module.allocate_id();
+        let inst = mr::Instruction::new(opcode, None, Some(id), operands);
+        self.module.types_global_values.push(inst);
+        id
+    }
+
+    /// Reports the given `diagnostic`.
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Returns the diagnostics reported so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// A plugin pass over whole modules.
+pub trait ModulePass {
+    /// Returns the name of this pass, used for statistics and errors.
+    fn name(&self) -> &str;
+
+    /// Returns the version of the plugin interface this pass requires.
+    fn required_api_version(&self) -> u32 {
+        PLUGIN_API_VERSION
+    }
+
+    /// Runs this pass over the module of the given `context`.
+    fn run_on_module(&mut self, context: &mut PassContext) -> PassResult;
+
+    /// Returns the analyses still valid after this pass changed the module.
+    fn preserved_analyses(&self) -> &[Analysis] {
+        &[]
+    }
+}
+
+/// A plugin pass over individual functions.
+///
+/// The pass is run over each function of the module in order, stopping at
+/// the first failure.
+pub trait FunctionPass {
+    /// Returns the name of this pass, used for statistics and errors.
+    fn name(&self) -> &str;
+
+    /// Returns the version of the plugin interface this pass requires.
+    fn required_api_version(&self) -> u32 {
+        PLUGIN_API_VERSION
+    }
+
+    /// Runs this pass over the function at the given `index` in the module
+    /// of the given `context`.
+    fn run_on_function(&mut self, context: &mut PassContext, index: usize) -> PassResult;
+
+    /// Returns the analyses still valid after this pass changed the module.
+    fn preserved_analyses(&self) -> &[Analysis] {
+        &[]
+    }
+}
+
+/// Errors in registering plugin passes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PluginError {
+    /// The pass requires a newer plugin interface than the provided one.
+    UnsupportedVersion(String, u32),
+    /// No pass is registered under the given name.
+    UnknownPass(String),
+    /// A pass is already registered under the given name.
+    DuplicatePass(String),
+    /// The options given to the named pass are invalid.
+    InvalidOptions(String, String),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PluginError::UnsupportedVersion(ref name, version) => write!(
+                f,
+                "pass '{}' requires plugin interface version {}, but {} is provided",
+                name, version, PLUGIN_API_VERSION
+            ),
+            PluginError::UnknownPass(ref name) => write!(f, "unknown pass '{}'", name),
+            PluginError::DuplicatePass(ref name) => {
+                write!(f, "pass '{}' is already registered", name)
+            }
+            PluginError::InvalidOptions(ref name, ref message) => {
+                write!(f, "invalid options for pass '{}': {}", name, message)
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for PluginError {}
+
+fn check_version(name: &str, version: u32) -> Result<(), PluginError> {
+    if version > PLUGIN_API_VERSION {
+        Err(PluginError::UnsupportedVersion(name.to_string(), version))
+    } else {
+        Ok(())
+    }
+}
+
+/// Adapts a [`ModulePass`](trait.ModulePass.html) into a [`Pass`](trait.Pass.html).
+pub(crate) struct ModulePassAdapter {
+    pass: Box<dyn ModulePass>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ModulePassAdapter {
+    pub(crate) fn new(pass: Box<dyn ModulePass>) -> Result<ModulePassAdapter, PluginError> {
+        check_version(pass.name(), pass.required_api_version())?;
+        Ok(ModulePassAdapter {
+            pass,
+            diagnostics: vec![],
+        })
+    }
+}
+
+impl Pass for ModulePassAdapter {
+    fn name(&self) -> &str {
+        self.pass.name()
+    }
+
+    fn run(&mut self, module: &mut mr::Module) -> PassResult {
+        let mut context = PassContext::new(module);
+        let result = self.pass.run_on_module(&mut context);
+        self.diagnostics.append(&mut context.diagnostics);
+        result
+    }
+
+    fn preserved_analyses(&self) -> &[Analysis] {
+        self.pass.preserved_analyses()
+    }
+
+    fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.diagnostics.drain(..).collect()
+    }
+}
+
+/// Adapts a [`FunctionPass`](trait.FunctionPass.html) into a [`Pass`](trait.Pass.html).
+pub(crate) struct FunctionPassAdapter {
+    pass: Box<dyn FunctionPass>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FunctionPassAdapter {
+    pub(crate) fn new(pass: Box<dyn FunctionPass>) -> Result<FunctionPassAdapter, PluginError> {
+        check_version(pass.name(), pass.required_api_version())?;
+        Ok(FunctionPassAdapter {
+            pass,
+            diagnostics: vec![],
+        })
+    }
+}
+
+impl Pass for FunctionPassAdapter {
+    fn name(&self) -> &str {
+        self.pass.name()
+    }
+
+    fn run(&mut self, module: &mut mr::Module) -> PassResult {
+        let mut context = PassContext::new(module);
+        let mut result = PassResult::Unchanged;
+        for index in 0..context.module().functions.len() {
+            match self.pass.run_on_function(&mut context, index) {
+                PassResult::Unchanged => {}
+                PassResult::Changed => result = PassResult::Changed,
+                failed @ PassResult::Failed(_) => {
+                    result = failed;
+                    break;
+                }
+            }
+        }
+        self.diagnostics.append(&mut context.diagnostics);
+        result
+    }
+
+    fn preserved_analyses(&self) -> &[Analysis] {
+        self.pass.preserved_analyses()
+    }
+
+    fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.diagnostics.drain(..).collect()
+    }
+}
+
+type PassFactory = Box<dyn Fn(&PassOptions) -> Result<Box<dyn Pass>, PluginError>>;
+
+/// A registry of passes by name, for selecting passes dynamically, e.g.,
+/// from the command line.
+#[derive(Default)]
+pub struct PassRegistry {
+    factories: BTreeMap<String, PassFactory>,
+}
+
+impl PassRegistry {
+    /// Creates a new empty registry.
+    pub fn new() -> PassRegistry {
+        Default::default()
+    }
+
+    fn register(&mut self, name: &str, factory: PassFactory) -> Result<(), PluginError> {
+        if self.factories.contains_key(name) {
+            return Err(PluginError::DuplicatePass(name.to_string()));
+        }
+        self.factories.insert(name.to_string(), factory);
+        Ok(())
+    }
+
+    /// Creates a registry of the transformations provided by this crate.
+    ///
+    /// Each transformation is registered under its function name in
+    /// kebab-case, e.g., `remove-unreachable-blocks`. Besides,
+    /// `group-duplicate-decorations` takes the integer option `min-targets`
+    /// (2 by default), and `fix-struct-offsets` takes the string option
+    /// `rule` (`"std140"` by default, or `"std430"`).
+    pub fn with_builtin_passes() -> PassRegistry {
+        use super::*;
+
+        let mut registry = PassRegistry::new();
+        {
+            let mut simple = |name: &'static str, func: fn(&mut mr::Module) -> usize| {
+                registry
+                    .register_pass(name, move |options| {
+                        options.expect_only(&[])?;
+                        Ok(Box::new(FnPass::new(name, func)))
+                    })
+                    .expect("unique builtin pass name");
+            };
+            simple("compact-ids", compact_ids);
+            simple("contract-null-constants", contract_null_constants);
+            simple("convert-unreachable-terminators", convert_unreachable_terminators);
+            simple("expand-decoration-groups", expand_decoration_groups);
+            simple("expand-null-constants", expand_null_constants);
+            simple("fold-constant-branches", fold_constant_branches);
+            simple("merge-blocks", merge_blocks);
+            simple("promote-extensions-to-core", promote_extensions_to_core);
+            simple("propagate-copies", propagate_copies);
+            simple("remove-dead-code", remove_dead_code);
+            simple("remove-duplicate-constants", remove_duplicate_constants);
+            simple("remove-duplicate-decorations", remove_duplicate_decorations);
+            simple("remove-duplicate-strings", remove_duplicate_strings);
+            simple("remove-redundant-loads-stores", remove_redundant_loads_stores);
+            simple("remove-unreachable-blocks", remove_unreachable_blocks);
+            simple("strip-debug-info", strip_debug_info);
+            simple("strip-non-semantic-info", strip_non_semantic_info);
+            simple("strip-reflect-info", strip_reflect_info);
+        }
+        registry
+            .register_pass("group-duplicate-decorations", |options| {
+                options.expect_only(&["min-targets"])?;
+                let min_targets = options.int("min-targets", 2)?;
+                if min_targets < 2 {
+                    return Err("option 'min-targets' must be at least 2".to_string());
+                }
+                let func = move |m: &mut mr::Module| {
+                    group_duplicate_decorations(m, min_targets as usize)
+                };
+                Ok(Box::new(FnPass::new("group-duplicate-decorations", func)))
+            })
+            .expect("unique builtin pass name");
+        registry
+            .register_pass("fix-struct-offsets", |options| {
+                options.expect_only(&["rule"])?;
+                let rule = match options.str("rule", "std140")? {
+                    "std140" => LayoutRule::Std140,
+                    "std430" => LayoutRule::Std430,
+                    rule => return Err(format!("unknown layout rule '{}'", rule)),
+                };
+                let func = move |m: &mut mr::Module| fix_struct_offsets(m, rule).len();
+                Ok(Box::new(FnPass::new("fix-struct-offsets", func)))
+            })
+            .expect("unique builtin pass name");
+        registry
+            .register_pass("remove-unused-block-members", |options| {
+                options.expect_only(&[])?;
+                let func = |m: &mut mr::Module| {
+                    remove_unused_block_members(m).iter().map(|r| r.removed()).sum()
+                };
+                Ok(Box::new(FnPass::new("remove-unused-block-members", func)))
+            })
+            .expect("unique builtin pass name");
+        registry
+    }
+
+    /// Registers the pass created by `factory` from its options under the
+    /// given `name`.
+    ///
+    /// The factory returns an error message if the options are invalid.
+    pub fn register_pass<F>(&mut self, name: &str, factory: F) -> Result<(), PluginError>
+    where
+        F: Fn(&PassOptions) -> Result<Box<dyn Pass>, String> + 'static,
+    {
+        let owned = name.to_string();
+        self.register(
+            name,
+            Box::new(move |options| {
+                factory(options).map_err(|e| PluginError::InvalidOptions(owned.clone(), e))
+            }),
+        )
+    }
+
+    /// Registers the module pass created by `factory` under the given `name`.
+    ///
+    /// The pass accepts no options.
+    pub fn register_module_pass<F>(&mut self, name: &str, factory: F) -> Result<(), PluginError>
+    where
+        F: Fn() -> Box<dyn ModulePass> + 'static,
+    {
+        let owned = name.to_string();
+        self.register(
+            name,
+            Box::new(move |options| {
+                options
+                    .expect_only(&[])
+                    .map_err(|e| PluginError::InvalidOptions(owned.clone(), e))?;
+                Ok(Box::new(ModulePassAdapter::new(factory())?))
+            }),
+        )
+    }
+
+    /// Registers the function pass created by `factory` under the given `name`.
+    ///
+    /// The pass accepts no options.
+    pub fn register_function_pass<F>(&mut self, name: &str, factory: F) -> Result<(), PluginError>
+    where
+        F: Fn() -> Box<dyn FunctionPass> + 'static,
+    {
+        let owned = name.to_string();
+        self.register(
+            name,
+            Box::new(move |options| {
+                options
+                    .expect_only(&[])
+                    .map_err(|e| PluginError::InvalidOptions(owned.clone(), e))?;
+                Ok(Box::new(FunctionPassAdapter::new(factory())?))
+            }),
+        )
+    }
+
+    /// Returns the names of the registered passes, in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(|k| k.as_str()).collect()
+    }
+
+    /// Creates a new instance of the pass registered under the given `name`.
+    pub fn create(&self, name: &str) -> Result<Box<dyn Pass>, PluginError> {
+        self.create_with_options(name, &PassOptions::new())
+    }
+
+    /// Creates a new instance of the pass registered under the given `name`
+    /// configured with the given `options`.
+    pub fn create_with_options(
+        &self,
+        name: &str,
+        options: &PassOptions,
+    ) -> Result<Box<dyn Pass>, PluginError> {
+        match self.factories.get(name) {
+            Some(factory) => factory(options),
+            None => Err(PluginError::UnknownPass(name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use validate::Diagnostic;
+
+    use super::{FunctionPass, ModulePass, PassContext, PassRegistry, PluginError};
+    use super::PLUGIN_API_VERSION;
+    use transform::{PassManager, PassResult};
+
+    struct DeclareBool;
+
+    impl ModulePass for DeclareBool {
+        fn name(&self) -> &str {
+            "declare-bool"
+        }
+
+        fn run_on_module(&mut self, context: &mut PassContext) -> PassResult {
+            if context.find_type(spirv::Op::TypeBool, &[]).is_some() {
+                return PassResult::Unchanged;
+            }
+            let id = context.get_or_declare_type(spirv::Op::TypeBool, vec![]);
+            context.report(Diagnostic::warning(Some(id), "declared bool"));
+            PassResult::Changed
+        }
+    }
+
+    struct FutureModulePass;
+
+    impl ModulePass for FutureModulePass {
+        fn name(&self) -> &str {
+            "future"
+        }
+
+        fn required_api_version(&self) -> u32 {
+            PLUGIN_API_VERSION + 1
+        }
+
+        fn run_on_module(&mut self, _: &mut PassContext) -> PassResult {
+            PassResult::Unchanged
+        }
+    }
+
+    struct CheckSingleBlock;
+
+    impl FunctionPass for CheckSingleBlock {
+        fn name(&self) -> &str {
+            "check-single-block"
+        }
+
+        fn run_on_function(&mut self, context: &mut PassContext, index: usize) -> PassResult {
+            let blocks = context.cfg(index).len();
+            assert!(context.dominators(index).is_reachable(0));
+            if blocks != 1 {
+                let id = context.module().functions[index].def.as_ref().unwrap().result_id;
+                context.report(Diagnostic::error(id, "more than one block"));
+            }
+            PassResult::Unchanged
+        }
+    }
+
+    fn module() -> mr::Module {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let next = b.id();
+        b.branch(next).unwrap();
+        b.begin_basic_block(Some(next)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_plugin_passes() {
+        let mut m = module();
+        let mut pm = PassManager::new();
+        pm.add_module_pass(Box::new(DeclareBool)).unwrap();
+        pm.add_function_pass(Box::new(CheckSingleBlock)).unwrap();
+        assert_eq!(vec!["declare-bool", "check-single-block"], pm.pass_names());
+
+        assert_eq!(Ok(PassResult::Changed), pm.run(&mut m));
+        assert_eq!(spirv::Op::TypeBool, m.types_global_values[2].class.opcode);
+        assert_eq!(
+            vec![
+                Diagnostic::warning(Some(6), "declared bool"),
+                Diagnostic::error(Some(3), "more than one block"),
+            ],
+            pm.diagnostics()
+        );
+
+        assert_eq!(Ok(PassResult::Unchanged), pm.run(&mut m));
+        assert_eq!(1, pm.diagnostics().len());
+    }
+
+    #[test]
+    fn test_plugin_version() {
+        let mut pm = PassManager::new();
+        let err = pm.add_module_pass(Box::new(FutureModulePass)).err().unwrap();
+        assert_eq!(PluginError::UnsupportedVersion("future".to_string(), 2), err);
+        assert_eq!(
+            "pass 'future' requires plugin interface version 2, but 1 is provided",
+            err.to_string()
+        );
+        assert!(pm.pass_names().is_empty());
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = PassRegistry::new();
+        registry.register_module_pass("declare-bool", || Box::new(DeclareBool)).unwrap();
+        registry.register_function_pass("single-block", || Box::new(CheckSingleBlock)).unwrap();
+        registry.register_module_pass("future", || Box::new(FutureModulePass)).unwrap();
+        assert_eq!(
+            Err(PluginError::DuplicatePass("future".to_string())),
+            registry.register_module_pass("future", || Box::new(FutureModulePass))
+        );
+        assert_eq!(vec!["declare-bool", "future", "single-block"], registry.names());
+
+        let mut pm = PassManager::new();
+        pm.add_registered_pass(&registry, "single-block").unwrap();
+        pm.add_registered_pass(&registry, "declare-bool").unwrap();
+        assert_eq!(
+            PluginError::UnknownPass("inline".to_string()),
+            pm.add_registered_pass(&registry, "inline").err().unwrap()
+        );
+        assert_eq!(
+            PluginError::UnsupportedVersion("future".to_string(), 2),
+            pm.add_registered_pass(&registry, "future").err().unwrap()
+        );
+        assert_eq!(vec!["check-single-block", "declare-bool"], pm.pass_names());
+
+        let mut m = module();
+        assert_eq!(Ok(PassResult::Changed), pm.run(&mut m));
+        assert_eq!(2, pm.diagnostics().len());
+    }
+}
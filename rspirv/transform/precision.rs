@@ -0,0 +1,533 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use super::samplers::find_or_add_type;
+
+/// Float scalar and vector types of one width, mapped to their
+/// counterparts of another width, which are added to the module as needed.
+struct Retyping {
+    width: u32,
+    /// The types to convert, with the component type and count of vectors.
+    sources: HashMap<Word, Option<(Word, u32)>>,
+    targets: HashMap<Word, Word>,
+}
+
+impl Retyping {
+    fn is_source(&self, ty: Word) -> bool {
+        self.sources.contains_key(&ty)
+    }
+
+    /// Returns the counterpart of the source type `ty`.
+    fn target(&mut self, module: &mut mr::Module, ty: Word) -> Word {
+        if let Some(&target) = self.targets.get(&ty) {
+            return target;
+        }
+        let target = match self.sources[&ty] {
+            None => find_or_add_type(
+                module,
+                spirv::Op::TypeFloat,
+                vec![mr::Operand::LiteralInt32(self.width)],
+            ),
+            Some((component, count)) => {
+                let component = self.target(module, component);
+                find_or_add_type(
+                    module,
+                    spirv::Op::TypeVector,
+                    vec![mr::Operand::IdRef(component), mr::Operand::LiteralInt32(count)],
+                )
+            }
+        };
+        self.targets.insert(ty, target);
+        target
+    }
+}
+
+/// Returns true if the given instruction computes its result component-wise
+/// or by moving components around, so that its result and float operands
+/// can change width together.
+fn is_convertible(inst: &mr::Instruction, glsl: Option<Word>) -> bool {
+    use spirv::Op::*;
+    match inst.class.opcode {
+        FNegate | FAdd | FSub | FMul | FDiv | FRem | FMod | VectorTimesScalar | Dot |
+        CopyObject | Select | CompositeConstruct | CompositeExtract | CompositeInsert |
+        VectorShuffle | VectorExtractDynamic | VectorInsertDynamic | ConvertSToF |
+        ConvertUToF => true,
+        // The packing instructions only work on 32-bit floats.
+        ExtInst => match inst.ext_inst() {
+            Some((set, opcode)) => {
+                Some(set) == glsl &&
+                    !(spirv::GLOp::PackSnorm4x8 as u32..=spirv::GLOp::UnpackDouble2x32 as u32)
+                        .contains(&opcode)
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns an OpFConvert of `value` to `result_type`.
+fn fconvert(result_type: Word, result_id: Word, value: Word) -> mr::Instruction {
+    mr::Instruction::new(
+        spirv::Op::FConvert,
+        Some(result_type),
+        Some(result_id),
+        vec![mr::Operand::IdRef(value)],
+    )
+}
+
+/// Adds the given capability to `module` if not declared yet.
+fn add_capability(module: &mut mr::Module, capability: spirv::Capability) {
+    let operand = mr::Operand::Capability(capability);
+    if !module.capabilities.iter().any(|inst| inst.operands.first() == Some(&operand)) {
+        module.capabilities.push(mr::Instruction::new(
+            spirv::Op::Capability,
+            None,
+            None,
+            vec![operand],
+        ));
+    }
+}
+
+/// Converts the computations decorated RelaxedPrecision in the given module
+/// from 32-bit to 16-bit floats, so that the precision is lowered offline
+/// instead of being left to the driver.
+///
+/// Arithmetic, composite and GLSL.std.450 instructions of 32-bit float
+/// scalar or vector type are narrowed, along with variables of such types
+/// in the given storage classes and the loads from them. Variables are
+/// converted only if decorated RelaxedPrecision, without initializer, and
+/// only loaded from and stored to. OpFConvert instructions are inserted
+/// wherever narrowed and 32-bit values meet, e.g., at loads from and stores
+/// to interface variables left alone. The RelaxedPrecision decorations of
+/// converted results are removed.
+///
+/// The Float16 capability is added, as well as StorageInputOutput16, and
+/// the SPV_KHR_16bit_storage extension before SPIR-V 1.3, when converting
+/// `Input` or `Output` variables. Unused 32-bit types are left behind.
+///
+/// Returns the number of results converted.
+pub fn narrow_relaxed_precision(
+    module: &mut mr::Module,
+    storage_classes: &[spirv::StorageClass],
+) -> usize {
+    convert_precision(module, storage_classes, 32, 16)
+}
+
+/// Converts the 16-bit float computations in the given module to 32-bit
+/// floats decorated RelaxedPrecision, the reverse of
+/// [`narrow_relaxed_precision`](fn.narrow_relaxed_precision.html).
+///
+/// Variables of 16-bit float scalar or vector types in the given storage
+/// classes are widened under the same restrictions. Capabilities are left
+/// unchanged.
+///
+/// Returns the number of results converted.
+pub fn widen_half_precision(
+    module: &mut mr::Module,
+    storage_classes: &[spirv::StorageClass],
+) -> usize {
+    convert_precision(module, storage_classes, 16, 32)
+}
+
+fn convert_precision(
+    module: &mut mr::Module,
+    storage_classes: &[spirv::StorageClass],
+    from: u32,
+    to: u32,
+) -> usize {
+    let narrow = to < from;
+
+    let mut retyping = Retyping { width: to, sources: HashMap::new(), targets: HashMap::new() };
+    // Integer and boolean scalar and vector types, which may be operands of
+    // converted instructions as they are.
+    let mut plain = HashSet::new();
+    let mut pointers = HashMap::new();
+    for inst in &module.types_global_values {
+        let id = match inst.result_id {
+            Some(id) => id,
+            None => continue,
+        };
+        match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::TypeFloat, &[mr::Operand::LiteralInt32(width), ..]) if width == from => {
+                retyping.sources.insert(id, None);
+            }
+            (spirv::Op::TypeInt, _) | (spirv::Op::TypeBool, _) => {
+                plain.insert(id);
+            }
+            (spirv::Op::TypeVector,
+             &[mr::Operand::IdRef(component), mr::Operand::LiteralInt32(count)]) => {
+                if retyping.is_source(component) {
+                    retyping.sources.insert(id, Some((component, count)));
+                } else if plain.contains(&component) {
+                    plain.insert(id);
+                }
+            }
+            (spirv::Op::TypePointer,
+             &[mr::Operand::StorageClass(storage), mr::Operand::IdRef(pointee)]) => {
+                pointers.insert(id, (storage, pointee));
+            }
+            _ => {}
+        }
+    }
+    if retyping.sources.is_empty() {
+        return 0;
+    }
+
+    let relaxed: HashSet<Word> = module
+        .annotations
+        .iter()
+        .filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate,
+             &[mr::Operand::IdRef(target),
+               mr::Operand::Decoration(spirv::Decoration::RelaxedPrecision)]) => Some(target),
+            _ => None,
+        })
+        .collect();
+    let types: HashMap<Word, Word> = module
+        .all_inst_iter()
+        .filter_map(|inst| Some((inst.result_id?, inst.result_type?)))
+        .collect();
+
+    // Variables to convert, with the storage class and source pointee type.
+    let mut variables: HashMap<Word, (spirv::StorageClass, Word)> = module
+        .all_inst_iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::Variable && inst.operands.len() == 1)
+        .filter_map(|inst| {
+            let id = inst.result_id?;
+            let (storage, pointee) = *pointers.get(&inst.result_type?)?;
+            if storage_classes.contains(&storage) &&
+                retyping.is_source(pointee) &&
+                (!narrow || relaxed.contains(&id))
+            {
+                Some((id, (storage, pointee)))
+            } else {
+                None
+            }
+        })
+        .collect();
+    for function in &module.functions {
+        for inst in function.all_inst_iter() {
+            for (index, operand) in inst.operands.iter().enumerate() {
+                if let mr::Operand::IdRef(id) = *operand {
+                    let accessed = index == 0 &&
+                        (inst.class.opcode == spirv::Op::Load ||
+                            inst.class.opcode == spirv::Op::Store);
+                    if !accessed {
+                        variables.remove(&id);
+                    }
+                }
+            }
+        }
+    }
+
+    let glsl = module.ext_inst_set("GLSL.std.450");
+    let mut selected = HashSet::new();
+    for function in &module.functions {
+        for inst in function.all_inst_iter() {
+            let (result_type, result_id) = match (inst.result_type, inst.result_id) {
+                (Some(ty), Some(id)) => (ty, id),
+                _ => continue,
+            };
+            let loaded = inst.class.opcode == spirv::Op::Load &&
+                matches!(inst.operands.first(),
+                         Some(&mr::Operand::IdRef(pointer)) if variables.contains_key(&pointer));
+            let computed = retyping.is_source(result_type) &&
+                is_convertible(inst, glsl) &&
+                (!narrow || relaxed.contains(&result_id)) &&
+                inst.operands.iter().all(|operand| match *operand {
+                    mr::Operand::IdRef(id) => types
+                        .get(&id)
+                        .is_none_or(|&ty| retyping.is_source(ty) || plain.contains(&ty)),
+                    _ => true,
+                });
+            if loaded || computed {
+                selected.insert(result_id);
+            }
+        }
+    }
+    if selected.is_empty() && variables.is_empty() {
+        return 0;
+    }
+
+    let is_converted_store = |inst: &mr::Instruction| {
+        inst.class.opcode == spirv::Op::Store &&
+            matches!(inst.operands.first(),
+                     Some(&mr::Operand::IdRef(pointer)) if variables.contains_key(&pointer))
+    };
+    // Converted results also used by instructions left alone, which get a
+    // converted-back copy instead.
+    let mut shared = vec![];
+    let mut seen = HashSet::new();
+    for function in &module.functions {
+        for inst in function.all_inst_iter() {
+            if inst.result_id.is_some_and(|id| selected.contains(&id)) {
+                continue;
+            }
+            for (index, operand) in inst.operands.iter().enumerate() {
+                if let mr::Operand::IdRef(id) = *operand {
+                    let stored = index == 1 && is_converted_store(inst);
+                    if selected.contains(&id) && !stored && seen.insert(id) {
+                        shared.push(id);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut converted_pointers = HashMap::new();
+    let mut sorted: Vec<_> = variables.iter().collect();
+    sorted.sort_by_key(|&(&variable, _)| variable);
+    for (&variable, &(storage, pointee)) in sorted {
+        let target = retyping.target(module, pointee);
+        let pointer = find_or_add_type(
+            module,
+            spirv::Op::TypePointer,
+            vec![mr::Operand::StorageClass(storage), mr::Operand::IdRef(target)],
+        );
+        converted_pointers.insert(variable, pointer);
+    }
+    for inst in &mut module.types_global_values {
+        if let Some(&pointer) = inst.result_id.and_then(|id| converted_pointers.get(&id)) {
+            inst.result_type = Some(pointer);
+        }
+    }
+    let copies: HashMap<Word, Word> =
+        shared.into_iter().map(|id| (id, module.allocate_id())).collect();
+
+    let mut functions = ::std::mem::take(&mut module.functions);
+    for function in &mut functions {
+        for block in &mut function.basic_blocks {
+            let mut rewritten = Vec::with_capacity(block.instructions.len());
+            for mut inst in ::std::mem::take(&mut block.instructions) {
+                let result_id = inst.result_id;
+                if let Some(id) = result_id.filter(|id| selected.contains(id)) {
+                    if inst.class.opcode != spirv::Op::Load {
+                        for operand in inst.operands.iter_mut() {
+                            if let mr::Operand::IdRef(ref mut value) = *operand {
+                                if selected.contains(value) {
+                                    continue;
+                                }
+                                if let Some(&ty) = types.get(value) {
+                                    if retyping.is_source(ty) {
+                                        let target = retyping.target(module, ty);
+                                        let converted = module.allocate_id();
+                                        rewritten.push(fconvert(target, converted, *value));
+                                        *value = converted;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    let original = inst.result_type.unwrap();
+                    inst.result_type = Some(retyping.target(module, original));
+                    rewritten.push(inst);
+                    if let Some(&copy) = copies.get(&id) {
+                        rewritten.push(fconvert(original, copy, id));
+                    }
+                    continue;
+                }
+
+                if is_converted_store(&inst) {
+                    if let Some(&mr::Operand::IdRef(value)) = inst.operands.get(1) {
+                        let ty = types.get(&value).cloned();
+                        if let Some(ty) = ty.filter(|&ty| !selected.contains(&value) &&
+                                                     retyping.is_source(ty)) {
+                            let target = retyping.target(module, ty);
+                            let converted = module.allocate_id();
+                            rewritten.push(fconvert(target, converted, value));
+                            inst.operands[1] = mr::Operand::IdRef(converted);
+                        }
+                    }
+                } else {
+                    for operand in inst.operands.iter_mut() {
+                        if let mr::Operand::IdRef(ref mut value) = *operand {
+                            if let Some(&copy) = copies.get(value) {
+                                *value = copy;
+                            }
+                        }
+                    }
+                }
+                if let Some(&pointer) = result_id.and_then(|id| converted_pointers.get(&id)) {
+                    inst.result_type = Some(pointer);
+                }
+                rewritten.push(inst);
+            }
+            block.instructions = rewritten;
+        }
+    }
+    module.functions = functions;
+
+    let converted: HashSet<Word> = selected.iter().chain(variables.keys()).cloned().collect();
+    if narrow {
+        module.annotations.retain(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate,
+             &[mr::Operand::IdRef(target),
+               mr::Operand::Decoration(spirv::Decoration::RelaxedPrecision)]) => {
+                !converted.contains(&target)
+            }
+            _ => true,
+        });
+        add_capability(module, spirv::Capability::Float16);
+        let interface = variables.values().any(|&(storage, _)| {
+            storage == spirv::StorageClass::Input || storage == spirv::StorageClass::Output
+        });
+        if interface {
+            add_capability(module, spirv::Capability::StorageInputOutput16);
+            let extension = mr::Operand::LiteralString("SPV_KHR_16bit_storage".into());
+            let has_extension =
+                module.extensions.iter().any(|inst| inst.operands.first() == Some(&extension));
+            if module.version_rules().version() < (1, 3) && !has_extension {
+                module.extensions.push(mr::Instruction::new(
+                    spirv::Op::Extension,
+                    None,
+                    None,
+                    vec![extension],
+                ));
+            }
+        }
+    } else {
+        let mut ids: Vec<Word> = converted.iter().cloned().collect();
+        ids.sort();
+        for id in ids {
+            if !relaxed.contains(&id) {
+                module.annotations.push(mr::Instruction::new(
+                    spirv::Op::Decorate,
+                    None,
+                    None,
+                    vec![
+                        mr::Operand::IdRef(id),
+                        mr::Operand::Decoration(spirv::Decoration::RelaxedPrecision),
+                    ],
+                ));
+            }
+        }
+    }
+    module.sort_global_section();
+    converted.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::Disassemble;
+    use mr;
+    use spirv;
+
+    use super::{narrow_relaxed_precision, widen_half_precision};
+
+    fn disassemble_function(module: &mr::Module) -> Vec<String> {
+        module.functions[0].basic_blocks[0]
+            .instructions
+            .iter()
+            .map(|inst| inst.disassemble())
+            .collect()
+    }
+
+    #[test]
+    fn test_narrow_relaxed_precision() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let output_ptr = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let output = b.variable(output_ptr, None, spirv::StorageClass::Output, None);
+        let input_ptr = b.type_pointer(None, spirv::StorageClass::Input, vec4);
+        let input = b.variable(input_ptr, None, spirv::StorageClass::Input, None);
+        let scale = b.constant_f32(float, 0.5);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let color = b.load(vec4, None, input, None, vec![]).unwrap();
+        let scaled = b.vector_times_scalar(vec4, None, color, scale).unwrap();
+        let sum = b.fadd(vec4, None, scaled, scaled).unwrap();
+        b.store(output, sum, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        for &id in &[scaled, sum] {
+            b.decorate(id, spirv::Decoration::RelaxedPrecision, vec![]);
+        }
+        let mut module = b.module();
+
+        assert_eq!(2, narrow_relaxed_precision(&mut module, &[]));
+        assert_eq!(
+            vec![
+                "%12 = OpLoad  %3  %7",
+                "%18 = OpFConvert  %17  %12",
+                "%19 = OpFConvert  %16  %8",
+                "%13 = OpVectorTimesScalar  %17  %18 %19",
+                "%14 = OpFAdd  %17  %13 %13",
+                "%15 = OpFConvert  %3  %14",
+                "OpStore %5 %15",
+                "OpReturn",
+            ],
+            disassemble_function(&module)
+        );
+        assert!(module.annotations.is_empty());
+        assert_eq!(2, module.capabilities.len());
+        assert_eq!(
+            Some(&mr::Operand::Capability(spirv::Capability::Float16)),
+            module.capabilities[1].operands.first()
+        );
+        assert_eq!(0, narrow_relaxed_precision(&mut module, &[]));
+    }
+
+    #[test]
+    fn test_widen_half_precision() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::Float16);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let half = b.type_float(16);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Function, half);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(ptr, None, spirv::StorageClass::Function, None);
+        let x = b.load(half, None, var, None, vec![]).unwrap();
+        let y = b.fmul(half, None, x, x).unwrap();
+        b.store(var, y, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut module = b.module();
+
+        assert_eq!(3, widen_half_precision(&mut module, &[spirv::StorageClass::Function]));
+        assert_eq!(
+            vec![
+                "%7 = OpVariable  %11  Function",
+                "%8 = OpLoad  %10  %7",
+                "%9 = OpFMul  %10  %8 %8",
+                "OpStore %7 %9",
+                "OpReturn",
+            ],
+            disassemble_function(&module)
+        );
+        let decorations: Vec<String> =
+            module.annotations.iter().map(|inst| inst.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "OpDecorate %7 RelaxedPrecision",
+                "OpDecorate %8 RelaxedPrecision",
+                "OpDecorate %9 RelaxedPrecision",
+            ],
+            decorations
+        );
+    }
+}
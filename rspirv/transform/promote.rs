@@ -0,0 +1,313 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use mr;
+use spirv;
+
+use spirv::Word;
+
+/// Extensions promoted to core SPIR-V 1.3 with identical instructions and
+/// enumerants.
+const PROMOTED_1_3: &[&str] = &[
+    "SPV_KHR_16bit_storage",
+    "SPV_KHR_device_group",
+    "SPV_KHR_multiview",
+    "SPV_KHR_shader_draw_parameters",
+    "SPV_KHR_storage_buffer_storage_class",
+    "SPV_KHR_variable_pointers",
+];
+
+/// Returns the core SPIR-V 1.3 instruction equivalent to the given
+/// extension instruction when executed with subgroup scope, and the
+/// capability the core instruction requires.
+fn core_equivalent(opcode: spirv::Op) -> Option<(spirv::Op, spirv::Capability)> {
+    use spirv::Capability::{GroupNonUniformBallot, GroupNonUniformVote};
+    match opcode {
+        spirv::Op::SubgroupBallotKHR => {
+            Some((spirv::Op::GroupNonUniformBallot, GroupNonUniformBallot))
+        }
+        spirv::Op::SubgroupFirstInvocationKHR => {
+            Some((spirv::Op::GroupNonUniformBroadcastFirst, GroupNonUniformBallot))
+        }
+        spirv::Op::SubgroupReadInvocationKHR => {
+            Some((spirv::Op::GroupNonUniformBroadcast, GroupNonUniformBallot))
+        }
+        spirv::Op::SubgroupAllKHR => Some((spirv::Op::GroupNonUniformAll, GroupNonUniformVote)),
+        spirv::Op::SubgroupAnyKHR => Some((spirv::Op::GroupNonUniformAny, GroupNonUniformVote)),
+        spirv::Op::SubgroupAllEqualKHR => {
+            Some((spirv::Op::GroupNonUniformAllEqual, GroupNonUniformVote))
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites extension instructions and declarations into their core forms
+/// if the SPIR-V version in the module header already includes them.
+///
+/// For SPIR-V 1.3 and later:
+///
+/// * OpExtension instructions for extensions promoted to core 1.3 without
+///   changes (e.g., `SPV_KHR_storage_buffer_storage_class`) are removed.
+/// * The `SPV_KHR_shader_ballot` and `SPV_KHR_subgroup_vote` instructions
+///   are rewritten into the equivalent OpGroupNonUniform* instructions with
+///   subgroup scope, requiring the `GroupNonUniformBallot` and
+///   `GroupNonUniformVote` capabilities instead. OpSubgroupReadInvocationKHR
+///   is only rewritten if its invocation id is a constant, as required by
+///   OpGroupNonUniformBroadcast. The extension and its capability are
+///   removed once no instruction needs them anymore.
+///
+/// Modules without header are assumed to be of the newest version supported.
+///
+/// Returns the number of instructions rewritten or removed.
+pub fn promote_extensions_to_core(module: &mut mr::Module) -> usize {
+    let version = module.header.as_ref().map_or(
+        (spirv::MAJOR_VERSION as u8, spirv::MINOR_VERSION as u8),
+        |h| h.version(),
+    );
+    if version < (1, 3) {
+        return 0;
+    }
+
+    let mut count = remove_extensions(module, PROMOTED_1_3);
+
+    let constants: Vec<Word> = module.types_global_values
+        .iter()
+        .filter(|inst| grammar::reflect::is_constant(inst.class.opcode))
+        .filter_map(|inst| inst.result_id)
+        .collect();
+    let rewritable = |inst: &mr::Instruction| match core_equivalent(inst.class.opcode) {
+        Some((spirv::Op::GroupNonUniformBroadcast, _)) => {
+            matches!(inst.operands.get(1), Some(mr::Operand::IdRef(id)) if constants.contains(id))
+        }
+        Some(_) => true,
+        None => false,
+    };
+    let any_rewritable = module.functions
+        .iter()
+        .flat_map(|f| f.basic_blocks.iter())
+        .flat_map(|bb| bb.instructions.iter())
+        .any(&rewritable);
+    if !any_rewritable {
+        return count;
+    }
+
+    let scope = subgroup_scope_constant(module);
+    let mut capabilities = vec![];
+    for inst in module.functions
+        .iter_mut()
+        .flat_map(|f| f.basic_blocks.iter_mut())
+        .flat_map(|bb| bb.instructions.iter_mut())
+        .filter(|inst| rewritable(inst))
+    {
+        let (opcode, capability) = core_equivalent(inst.class.opcode).unwrap();
+        inst.class = grammar::CoreInstructionTable::get(opcode);
+        inst.operands.insert(0, mr::Operand::IdScope(scope));
+        if !capabilities.contains(&capability) {
+            capabilities.push(capability);
+        }
+        count += 1;
+    }
+
+    let mut declared: Vec<spirv::Capability> = module.capabilities
+        .iter()
+        .filter_map(|inst| match inst.operands.first() {
+            Some(&mr::Operand::Capability(c)) => Some(c),
+            _ => None,
+        })
+        .collect();
+    for capability in capabilities.into_iter().chain(Some(spirv::Capability::GroupNonUniform)) {
+        if !declared.contains(&capability) {
+            declared.push(capability);
+            module.capabilities.push(mr::Instruction::new(
+                spirv::Op::Capability,
+                None,
+                None,
+                vec![mr::Operand::Capability(capability)],
+            ));
+        }
+    }
+
+    // Drop the extension capabilities and extensions if no longer needed.
+    let remaining: Vec<&'static [spirv::Capability]> = module.functions
+        .iter()
+        .flat_map(|f| f.basic_blocks.iter())
+        .flat_map(|bb| bb.instructions.iter())
+        .map(|inst| inst.class.capabilities)
+        .collect();
+    let is_used = |capability: spirv::Capability| {
+        remaining.iter().any(|caps| caps.contains(&capability))
+    };
+    let ballot_used = is_used(spirv::Capability::SubgroupBallotKHR) || uses_ballot_builtins(module);
+    let vote_used = is_used(spirv::Capability::SubgroupVoteKHR);
+    let before = module.capabilities.len();
+    module.capabilities.retain(|inst| match inst.operands.first() {
+        Some(&mr::Operand::Capability(spirv::Capability::SubgroupBallotKHR)) => ballot_used,
+        Some(&mr::Operand::Capability(spirv::Capability::SubgroupVoteKHR)) => vote_used,
+        _ => true,
+    });
+    count += before - module.capabilities.len();
+    if !ballot_used {
+        count += remove_extensions(module, &["SPV_KHR_shader_ballot"]);
+    }
+    if !vote_used {
+        count += remove_extensions(module, &["SPV_KHR_subgroup_vote"]);
+    }
+    count
+}
+
+/// Removes the OpExtension instructions for the given extensions and
+/// returns the number of instructions removed.
+fn remove_extensions(module: &mut mr::Module, extensions: &[&str]) -> usize {
+    let before = module.extensions.len();
+    module.extensions.retain(|inst| match inst.operands.first() {
+        Some(mr::Operand::LiteralString(name)) => !extensions.contains(&name.as_str()),
+        _ => true,
+    });
+    before - module.extensions.len()
+}
+
+/// Returns true if the module uses any built-in variable introduced by
+/// `SPV_KHR_shader_ballot`.
+fn uses_ballot_builtins(module: &mr::Module) -> bool {
+    use spirv::BuiltIn::*;
+    module.annotations.iter().any(|inst| match inst.operands.get(2) {
+        Some(&mr::Operand::BuiltIn(builtin)) => matches!(
+            builtin,
+            SubgroupEqMask | SubgroupGeMask | SubgroupGtMask | SubgroupLeMask | SubgroupLtMask |
+                SubgroupSize | SubgroupLocalInvocationId
+        ),
+        _ => false,
+    })
+}
+
+/// Returns the id of a 32-bit integer constant with the value of
+/// `Scope::Subgroup`, creating it (and the unsigned 32-bit integer type)
+/// if it does not exist yet.
+fn subgroup_scope_constant(module: &mut mr::Module) -> Word {
+    let value = mr::Operand::LiteralInt32(spirv::Scope::Subgroup as u32);
+    let int32: Vec<Word> = module.types_global_values
+        .iter()
+        .filter(|inst| {
+            inst.class.opcode == spirv::Op::TypeInt &&
+                inst.operands.first() == Some(&mr::Operand::LiteralInt32(32))
+        })
+        .filter_map(|inst| inst.result_id)
+        .collect();
+    let existing = module.types_global_values.iter().find(|inst| {
+        inst.class.opcode == spirv::Op::Constant &&
+            inst.result_type.is_some_and(|ty| int32.contains(&ty)) &&
+            inst.operands.first() == Some(&value)
+    });
+    if let Some(id) = existing.and_then(|inst| inst.result_id) {
+        return id;
+    }
+
+    let uint32 = module.types_global_values
+        .iter()
+        .find(|inst| {
+            inst.class.opcode == spirv::Op::TypeInt &&
+                inst.operands == [mr::Operand::LiteralInt32(32), mr::Operand::LiteralInt32(0)]
+        })
+        .and_then(|inst| inst.result_id);
+    let uint32 = match uint32 {
+        Some(id) => id,
+        None => {
+            let id = module.allocate_id();
+            module.types_global_values.push(mr::Instruction::new(
+                spirv::Op::TypeInt,
+                None,
+                Some(id),
+                vec![mr::Operand::LiteralInt32(32), mr::Operand::LiteralInt32(0)],
+            ));
+            id
+        }
+    };
+    let id = module.allocate_id();
+    module.types_global_values.push(mr::Instruction::new(
+        spirv::Op::Constant,
+        Some(uint32),
+        Some(id),
+        vec![value],
+    ));
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::promote_extensions_to_core;
+
+    fn build(major: u8, minor: u8) -> mr::Module {
+        let mut b = mr::Builder::new();
+        b.set_version(major, minor);
+        b.capability(spirv::Capability::Shader);
+        b.capability(spirv::Capability::SubgroupBallotKHR);
+        b.capability(spirv::Capability::SubgroupVoteKHR);
+        b.extension("SPV_KHR_shader_ballot");
+        b.extension("SPV_KHR_subgroup_vote");
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.subgroup_any_khr(boolean, None, cond).unwrap();
+        b.subgroup_first_invocation_khr(boolean, None, cond).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_promote() {
+        let mut m = build(1, 3);
+        // 1 extension, 2 instructions, 2 capabilities, and 2 extensions.
+        assert_eq!(7, promote_extensions_to_core(&mut m));
+        let disas: Vec<String> = m.capabilities
+            .iter()
+            .chain(&m.extensions)
+            .chain(&m.types_global_values[4..])
+            .chain(&m.functions[0].basic_blocks[0].instructions)
+            .map(|i| i.disassemble())
+            .collect();
+        assert_eq!(
+            vec![
+                "OpCapability Shader",
+                "OpCapability GroupNonUniformVote",
+                "OpCapability GroupNonUniformBallot",
+                "OpCapability GroupNonUniform",
+                "%9 = OpTypeInt 32 0",
+                "%10 = OpConstant  %9  3",
+                "%7 = OpGroupNonUniformAny  %2  %10 %3",
+                "%8 = OpGroupNonUniformBroadcastFirst  %2  %10 %3",
+                "OpReturn",
+            ],
+            disas
+        );
+        assert_eq!(11, m.header.as_ref().unwrap().bound);
+        assert_eq!(0, promote_extensions_to_core(&mut m));
+    }
+
+    #[test]
+    fn test_promote_old_version() {
+        let mut m = build(1, 2);
+        assert_eq!(0, promote_extensions_to_core(&mut m));
+        assert_eq!(3, m.extensions.len());
+    }
+}
@@ -0,0 +1,262 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashSet;
+use super::block_members::{remove_unused_block_members, MemberRemapping};
+use super::dead_code::remove_debug_annotations;
+use super::locations::{assign_locations, LocationAssignment, LocationOrder};
+use super::struct_offsets::{fix_struct_offsets, LayoutRule};
+
+/// The interface changes made by
+/// [`prune_interfaces`](fn.prune_interfaces.html), for updating the
+/// reflection data of the host.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterfacePruning {
+    /// The result ids of the removed Input and Output variables.
+    pub variables: Vec<Word>,
+    /// How the members of the uniform and storage blocks were renumbered.
+    pub members: Vec<MemberRemapping>,
+    /// The new locations of the remaining Input and Output variables, if
+    /// they were packed.
+    pub locations: Vec<LocationAssignment>,
+}
+
+/// Returns the storage class of `inst` if it declares an Input or Output
+/// variable.
+fn stage_storage_class(inst: &mr::Instruction) -> Option<spirv::StorageClass> {
+    match (inst.class.opcode, inst.operands.first()) {
+        (spirv::Op::Variable, Some(&mr::Operand::StorageClass(storage_class)))
+            if storage_class == spirv::StorageClass::Input ||
+                storage_class == spirv::StorageClass::Output => Some(storage_class),
+        _ => None,
+    }
+}
+
+/// Removes the Input and Output variables no function uses and the
+/// members of uniform and storage blocks no function accesses, as
+/// permutations of generated shaders leave many of them behind.
+///
+/// Removed variables are dropped from the interfaces of the entry points,
+/// along with their names and decorations. Variables targeted by
+/// OpGroupDecorate are kept. Block members are removed by
+/// [`remove_unused_block_members`](fn.remove_unused_block_members.html).
+///
+/// Offsets and locations are preserved unless requested otherwise:
+///
+/// - with `pack_offsets`, the remaining members of the changed blocks get
+///   new Offset decorations according to the given layout rule, through
+///   [`fix_struct_offsets`](fn.fix_struct_offsets.html);
+/// - with `pack_locations`, the remaining variables of the storage classes
+///   variables were removed from get new locations, assigned in the given
+///   order by [`assign_locations`](fn.assign_locations.html), which also
+///   assigns locations to any variables lacking them. Storage classes with
+///   Component decorations or member Locations keep theirs.
+///
+/// Stages linked together must be pruned and packed alike.
+pub fn prune_interfaces(
+    module: &mut mr::Module,
+    pack_offsets: Option<LayoutRule>,
+    pack_locations: Option<LocationOrder>,
+) -> InterfacePruning {
+    let mut used = HashSet::new();
+    for inst in module.functions.iter().flat_map(|f| f.all_inst_iter()) {
+        for operand in &inst.operands {
+            if let mr::Operand::IdRef(id) = *operand {
+                used.insert(id);
+            }
+        }
+    }
+    for inst in &module.annotations {
+        if inst.class.opcode == spirv::Op::GroupDecorate {
+            for operand in &inst.operands {
+                if let mr::Operand::IdRef(id) = *operand {
+                    used.insert(id);
+                }
+            }
+        }
+    }
+
+    let mut pruning = InterfacePruning::default();
+    let mut storage_classes = HashSet::new();
+    for inst in &module.types_global_values {
+        if let (Some(storage_class), Some(id)) = (stage_storage_class(inst), inst.result_id) {
+            if !used.contains(&id) {
+                pruning.variables.push(id);
+                storage_classes.insert(storage_class);
+            }
+        }
+    }
+    let removed: HashSet<Word> = pruning.variables.iter().cloned().collect();
+    if !removed.is_empty() {
+        module.types_global_values.retain(|inst| {
+            inst.result_id.is_none_or(|id| !removed.contains(&id))
+        });
+        for inst in &mut module.entry_points {
+            inst.operands.retain(|operand| match *operand {
+                mr::Operand::IdRef(id) => !removed.contains(&id),
+                _ => true,
+            });
+        }
+        remove_debug_annotations(module, &removed);
+    }
+
+    pruning.members = remove_unused_block_members(module);
+    if let (Some(rule), false) = (pack_offsets, pruning.members.is_empty()) {
+        let blocks: HashSet<Word> = pruning.members.iter().map(|r| r.block).collect();
+        module.annotations.retain(|inst| match inst.operands[..] {
+            [mr::Operand::IdRef(target),
+             mr::Operand::LiteralInt32(_),
+             mr::Operand::Decoration(spirv::Decoration::Offset),
+             ..] => !blocks.contains(&target),
+            _ => true,
+        });
+        fix_struct_offsets(module, rule);
+    }
+
+    if let (Some(order), false) = (pack_locations, removed.is_empty()) {
+        // The storage class of each remaining Input and Output variable.
+        let variables: Vec<(Word, spirv::StorageClass)> = module
+            .types_global_values
+            .iter()
+            .filter_map(|inst| Some((inst.result_id?, stage_storage_class(inst)?)))
+            .collect();
+        let has_member_locations = module.annotations.iter().any(|inst| {
+            inst.class.opcode == spirv::Op::MemberDecorate &&
+                inst.operands.get(2) ==
+                    Some(&mr::Operand::Decoration(spirv::Decoration::Location))
+        });
+        for &(id, storage_class) in &variables {
+            let has_component = module.annotations.iter().any(|inst| {
+                matches!(inst.operands[..],
+                         [mr::Operand::IdRef(target),
+                          mr::Operand::Decoration(spirv::Decoration::Component),
+                          ..] if target == id)
+            });
+            if has_member_locations || has_component {
+                storage_classes.remove(&storage_class);
+            }
+        }
+        let repacked: HashSet<Word> = variables
+            .into_iter()
+            .filter(|&(_, storage_class)| storage_classes.contains(&storage_class))
+            .map(|(id, _)| id)
+            .collect();
+        module.annotations.retain(|inst| match inst.operands[..] {
+            [mr::Operand::IdRef(target),
+             mr::Operand::Decoration(spirv::Decoration::Location),
+             ..] => !repacked.contains(&target),
+            _ => true,
+        });
+        pruning.locations = assign_locations(module, order);
+    }
+    pruning
+}
+
+#[cfg(test)]
+mod tests {
+    use binary::Disassemble;
+    use mr;
+    use spirv;
+
+    use super::prune_interfaces;
+    use transform::{LayoutRule, LocationAssignment, LocationOrder};
+
+    #[test]
+    fn test_prune_interfaces() {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::Shader);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let input_ptr = b.type_pointer(None, spirv::StorageClass::Input, vec4);
+        let unused = b.variable(input_ptr, None, spirv::StorageClass::Input, None);
+        let input = b.variable(input_ptr, None, spirv::StorageClass::Input, None);
+        let output_ptr = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let output = b.variable(output_ptr, None, spirv::StorageClass::Output, None);
+        let block = b.type_struct(vec![vec4, vec4]);
+        let block_ptr = b.type_pointer(None, spirv::StorageClass::Uniform, block);
+        let ubo = b.variable(block_ptr, None, spirv::StorageClass::Uniform, None);
+        let uint = b.type_int(32, 0);
+        let one = b.constant_u32(uint, 1);
+        let member_ptr = b.type_pointer(None, spirv::StorageClass::Uniform, vec4);
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let x = b.load(vec4, None, input, None, vec![]).unwrap();
+        let p = b.access_chain(member_ptr, None, ubo, vec![one]).unwrap();
+        let y = b.load(vec4, None, p, None, vec![]).unwrap();
+        let z = b.fadd(vec4, None, x, y).unwrap();
+        b.store(output, z, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Fragment, main, "main", vec![unused, input, output]);
+        b.execution_mode(main, spirv::ExecutionMode::OriginUpperLeft, []);
+        b.name(unused, "unused");
+        let location = |l: u32| vec![mr::Operand::LiteralInt32(l)];
+        b.decorate(unused, spirv::Decoration::Location, location(0));
+        b.decorate(input, spirv::Decoration::Location, location(1));
+        b.decorate(output, spirv::Decoration::Location, location(0));
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.member_decorate(block, 0, spirv::Decoration::Offset, location(0));
+        b.member_decorate(block, 1, spirv::Decoration::Offset, location(16));
+        let mut module = b.module();
+
+        let pruning = prune_interfaces(
+            &mut module,
+            Some(LayoutRule::Std140),
+            Some(LocationOrder::Declaration),
+        );
+        assert_eq!(vec![unused], pruning.variables);
+        assert_eq!(1, pruning.members.len());
+        assert_eq!(vec![None, Some(0)], pruning.members[0].new_indices);
+        assert_eq!(
+            vec![LocationAssignment {
+                variable: input,
+                storage_class: spirv::StorageClass::Input,
+                location: 0,
+            }],
+            pruning.locations
+        );
+
+        assert_eq!(
+            "OpEntryPoint Fragment %16 \"main\" %6 %8",
+            module.entry_points[0].disassemble()
+        );
+        assert!(module.debugs.is_empty());
+        let annotations: Vec<String> =
+            module.annotations.iter().map(|inst| inst.disassemble()).collect();
+        assert_eq!(
+            vec![
+                "OpDecorate %8 Location 0",
+                "OpDecorate %9 Block",
+                "OpMemberDecorate %9 0 Offset 0",
+                "OpDecorate %6 Location 0",
+            ],
+            annotations
+        );
+        assert!(module.all_inst_iter().all(|inst| inst.result_id != Some(unused)));
+        assert_eq!(
+            "%19 = OpAccessChain  %14  %11 %22",
+            module.functions[0].basic_blocks[0].instructions[1].disassemble()
+        );
+
+        let pruning = prune_interfaces(&mut module, None, None);
+        assert!(pruning.variables.is_empty() && pruning.members.is_empty());
+    }
+}
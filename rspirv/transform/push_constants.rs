@@ -0,0 +1,199 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+
+/// Replaces all references to the keys of `ids` by their values in all
+/// instructions of the given module, except for the defining instructions.
+fn replace_id_uses(module: &mut mr::Module, ids: &HashMap<Word, Word>) {
+    let replace = |inst: &mut mr::Instruction| {
+        if let Some(ref mut ty) = inst.result_type {
+            *ty = ids.get(ty).cloned().unwrap_or(*ty);
+        }
+        for operand in &mut inst.operands {
+            if let mr::Operand::IdRef(ref mut id) = *operand {
+                *id = ids.get(id).cloned().unwrap_or(*id);
+            }
+        }
+    };
+    for inst in module.entry_points
+        .iter_mut()
+        .chain(&mut module.execution_modes)
+        .chain(&mut module.debugs)
+        .chain(&mut module.annotations)
+        .chain(&mut module.types_global_values) {
+        replace(inst);
+    }
+    for function in &mut module.functions {
+        for inst in function.def
+            .iter_mut()
+            .chain(&mut function.parameters)
+            .chain(function.basic_blocks.iter_mut().flat_map(|bb| &mut bb.instructions)) {
+            replace(inst);
+        }
+    }
+}
+
+/// Converts the push constant blocks of the given module into uniform
+/// buffers at the given descriptor `set` and `binding`, for targets with
+/// tiny or no push constant support.
+///
+/// The storage class of the variables and of all pointer types into push
+/// constants, and thus of all access chains into them, becomes `Uniform`;
+/// pointer types then duplicating existing ones are replaced by those.
+/// The blocks are decorated as Block if they are not yet, and the
+/// variables get the DescriptorSet and Binding decorations. Modules declare
+/// at most one push constant block per entry point, so all converted
+/// variables share the binding.
+///
+/// The Offset decorations of the blocks are kept. Since uniform buffers
+/// follow the stricter std140 layout rules unless the
+/// `UniformBufferStandardLayout` feature is enabled, check the blocks with
+/// [`check_struct_offsets`](fn.check_struct_offsets.html) if needed.
+///
+/// Returns the converted variables.
+pub fn convert_push_constants_to_uniform(module: &mut mr::Module, set: u32, binding: u32)
+                                         -> Vec<Word> {
+    let push_constant = mr::Operand::StorageClass(spirv::StorageClass::PushConstant);
+    let uniform = mr::Operand::StorageClass(spirv::StorageClass::Uniform);
+    let variables: Vec<(Word, Option<Word>)> = module
+        .types_global_values
+        .iter()
+        .filter(|inst| {
+            inst.class.opcode == spirv::Op::Variable &&
+                inst.operands.first() == Some(&push_constant)
+        })
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst.result_type)))
+        .collect();
+    if variables.is_empty() {
+        return vec![];
+    }
+
+    // Turn push constant pointer types into uniform ones in place, unless an
+    // equivalent uniform pointer type already exists.
+    let mut uniform_pointers: HashMap<Word, Word> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| match (inst.class.opcode, &inst.operands[..], inst.result_id) {
+            (spirv::Op::TypePointer, [sc, mr::Operand::IdRef(pointee)], Some(id))
+                if *sc == uniform => Some((*pointee, id)),
+            _ => None,
+        })
+        .collect();
+    let mut duplicates = HashMap::new();
+    let mut blocks = vec![];
+    for inst in &mut module.types_global_values {
+        if inst.class.opcode == spirv::Op::Variable &&
+            inst.operands.first() == Some(&push_constant) {
+            inst.operands[0] = uniform.clone();
+            continue;
+        }
+        let (pointee, id) = match (inst.class.opcode, &inst.operands[..], inst.result_id) {
+            (spirv::Op::TypePointer, [sc, mr::Operand::IdRef(pointee)], Some(id))
+                if *sc == push_constant => (*pointee, id),
+            _ => continue,
+        };
+        if variables.iter().any(|&(_, ty)| ty == Some(id)) {
+            blocks.push(pointee);
+        }
+        match uniform_pointers.get(&pointee) {
+            Some(&existing) => {
+                duplicates.insert(id, existing);
+            }
+            None => {
+                inst.operands[0] = uniform.clone();
+                uniform_pointers.insert(pointee, id);
+            }
+        }
+    }
+    if !duplicates.is_empty() {
+        module.types_global_values.retain(|inst| {
+            inst.result_id.is_none_or(|id| !duplicates.contains_key(&id))
+        });
+        replace_id_uses(module, &duplicates);
+        module.sort_global_section();
+    }
+
+    let decorate = |target: Word, decoration: spirv::Decoration, value: Option<u32>| {
+        let mut operands = vec![mr::Operand::IdRef(target), mr::Operand::Decoration(decoration)];
+        operands.extend(value.map(mr::Operand::LiteralInt32));
+        mr::Instruction::new(spirv::Op::Decorate, None, None, operands)
+    };
+    for block in blocks {
+        if !module.decoration_index().has_decoration(block, spirv::Decoration::Block) {
+            module.annotations.push(decorate(block, spirv::Decoration::Block, None));
+        }
+    }
+    for &(variable, _) in &variables {
+        module.annotations.push(decorate(variable, spirv::Decoration::DescriptorSet, Some(set)));
+        module.annotations.push(decorate(variable, spirv::Decoration::Binding, Some(binding)));
+    }
+    variables.into_iter().map(|(variable, _)| variable).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::convert_push_constants_to_uniform;
+
+    #[test]
+    fn test_convert_push_constants_to_uniform() {
+        use spirv::StorageClass::{PushConstant, Uniform};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let uint = b.type_int(32, 0);
+        let block = b.type_struct(vec![float]);
+        let ptr_block = b.type_pointer(None, PushConstant, block);
+        let ptr_float = b.type_pointer(None, PushConstant, float);
+        let ptr_uniform_float = b.type_pointer(None, Uniform, float);
+        let zero = b.constant_u32(uint, 0);
+        let voidf = b.type_function(void, vec![]);
+        let constants = b.variable(ptr_block, None, PushConstant, None);
+        b.member_decorate(block, 0, spirv::Decoration::Offset,
+                          vec![mr::Operand::LiteralInt32(0)]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let member = b.access_chain(ptr_float, None, constants, vec![zero]).unwrap();
+        b.load(float, None, member, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(vec![constants], convert_push_constants_to_uniform(&mut m, 2, 3));
+        let disas: Vec<String> = m.all_inst_iter().map(|i| i.disassemble()).collect();
+        for expected in &[
+            "OpDecorate %4 Block",
+            "OpDecorate %10 DescriptorSet 2",
+            "OpDecorate %10 Binding 3",
+            "%5 = OpTypePointer Uniform %4",
+            "%10 = OpVariable  %5  Uniform",
+        ] {
+            assert!(disas.iter().any(|d| d == expected), "missing {}", expected);
+        }
+        // The access chain reuses the existing pointer to a uniform float.
+        let chain = format!("%13 = OpAccessChain  %{}  %10 %8", ptr_uniform_float);
+        assert!(disas.contains(&chain));
+        assert!(!disas.iter().any(|d| d.contains("PushConstant") || d.starts_with("%6 =")));
+        assert!(convert_push_constants_to_uniform(&mut m, 2, 3).is_empty());
+    }
+}
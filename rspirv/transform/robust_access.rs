@@ -0,0 +1,292 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+
+/// Returns the id of the 32-bit integer constant of the given type and
+/// value in `module`, adding the constant if it does not exist yet.
+pub(super) fn get_or_insert_constant(module: &mut mr::Module, ty: Word, value: u32) -> Word {
+    let existing = module
+        .types_global_values
+        .iter()
+        .find(|inst| {
+            inst.class.opcode == spirv::Op::Constant &&
+                inst.result_type == Some(ty) &&
+                inst.operands[..] == [mr::Operand::LiteralInt32(value)]
+        })
+        .and_then(|inst| inst.result_id);
+    if let Some(id) = existing {
+        return id;
+    }
+    let id = module.allocate_id();
+    module.types_global_values.push(mr::Instruction::new(
+        spirv::Op::Constant,
+        Some(ty),
+        Some(id),
+        vec![mr::Operand::LiteralInt32(value)],
+    ));
+    id
+}
+
+/// Returns the id of the 32-bit unsigned integer type in `module`, adding
+/// the type if it does not exist yet.
+pub(super) fn get_or_insert_uint32(module: &mut mr::Module) -> Word {
+    let operands = [mr::Operand::LiteralInt32(32), mr::Operand::LiteralInt32(0)];
+    let existing = module
+        .types_global_values
+        .iter()
+        .find(|inst| inst.class.opcode == spirv::Op::TypeInt && inst.operands[..] == operands)
+        .and_then(|inst| inst.result_id);
+    if let Some(id) = existing {
+        return id;
+    }
+    let id = module.allocate_id();
+    module.types_global_values.push(mr::Instruction::new(
+        spirv::Op::TypeInt,
+        None,
+        Some(id),
+        operands.to_vec(),
+    ));
+    id
+}
+
+/// How a dynamic index into some composite is clamped.
+enum Limit {
+    /// To the given constant maximum index.
+    Fixed(u32),
+    /// To the length of the runtime array which is the given member of the
+    /// struct the base of the access chain points to.
+    RuntimeArray(u32),
+}
+
+/// Clamps the dynamic indices of all OpAccessChain and
+/// OpInBoundsAccessChain instructions to the bounds of the composites they
+/// index into, so that untrusted shaders cannot access memory out of
+/// bounds, like the graphics robust access pass of spirv-opt does.
+///
+/// Each index which is not an OpConstant is replaced by the result of a
+/// GLSL.std.450 UMin of the index and the last valid index: the length of
+/// an array minus one, the number of components of a vector or columns of
+/// a matrix minus one, or, for runtime arrays which are members of the
+/// block the base of the access chain points to, the OpArrayLength minus
+/// one. Negative signed indices are thus clamped as well. Only 32-bit
+/// indices are clamped; runtime arrays of descriptors and runtime arrays
+/// reached through nested access chains are left alone.
+///
+/// Returns the number of indices clamped.
+pub fn clamp_access_chain_indices(module: &mut mr::Module) -> usize {
+    let mut types: HashMap<Word, mr::Instruction> = HashMap::new();
+    let mut constants: HashMap<Word, u32> = HashMap::new();
+    let mut value_types: HashMap<Word, Word> = HashMap::new();
+    for inst in module.all_inst_iter() {
+        if let (Some(id), Some(ty)) = (inst.result_id, inst.result_type) {
+            value_types.insert(id, ty);
+        }
+        match (inst.class.opcode, inst.result_id, inst.operands.first()) {
+            (spirv::Op::Constant, Some(id), Some(&mr::Operand::LiteralInt32(value))) => {
+                constants.insert(id, value);
+            }
+            (opcode, Some(id), _) if inst.result_type.is_none() &&
+                opcode != spirv::Op::Label => {
+                types.insert(id, inst.clone());
+            }
+            _ => {}
+        }
+    }
+    let is_int32 = |ty: Word| {
+        types.get(&ty).is_some_and(|def| {
+            def.class.opcode == spirv::Op::TypeInt &&
+                def.operands.first() == Some(&mr::Operand::LiteralInt32(32))
+        })
+    };
+    let id_operand = |inst: &mr::Instruction, index: usize| match inst.operands.get(index) {
+        Some(&mr::Operand::IdRef(id)) => Some(id),
+        _ => None,
+    };
+
+    let mut functions = ::std::mem::take(&mut module.functions);
+    let mut glsl = None;
+    let mut uint = None;
+    let mut count = 0;
+    for block in functions.iter_mut().flat_map(|f| &mut f.basic_blocks) {
+        let mut instructions = Vec::with_capacity(block.instructions.len());
+        for mut inst in block.instructions.drain(..) {
+            if inst.class.opcode != spirv::Op::AccessChain &&
+                inst.class.opcode != spirv::Op::InBoundsAccessChain {
+                instructions.push(inst);
+                continue;
+            }
+            let base = id_operand(&inst, 0).unwrap_or(0);
+            let mut ty = value_types
+                .get(&base)
+                .and_then(|pointer| types.get(pointer))
+                .filter(|pointer| pointer.class.opcode == spirv::Op::TypePointer)
+                .and_then(|pointer| id_operand(pointer, 1));
+            let mut member = None;
+            for position in 1..inst.operands.len() {
+                let def = match ty.and_then(|ty| types.get(&ty)) {
+                    Some(def) => def,
+                    None => break,
+                };
+                let index = match id_operand(&inst, position) {
+                    Some(index) => index,
+                    None => break,
+                };
+                let limit = match def.class.opcode {
+                    spirv::Op::TypeStruct => {
+                        member = constants.get(&index).cloned();
+                        ty = member.and_then(|m| id_operand(def, m as usize));
+                        continue;
+                    }
+                    spirv::Op::TypeArray => {
+                        id_operand(def, 1).and_then(|length| constants.get(&length)).cloned()
+                    }
+                    spirv::Op::TypeVector | spirv::Op::TypeMatrix => match def.operands.get(1) {
+                        Some(&mr::Operand::LiteralInt32(count)) => Some(count),
+                        _ => None,
+                    },
+                    spirv::Op::TypeRuntimeArray => None,
+                    _ => break,
+                };
+                let limit = match (limit, def.class.opcode) {
+                    (Some(length), _) if length > 0 => Some(Limit::Fixed(length - 1)),
+                    (None, spirv::Op::TypeRuntimeArray) if position == 2 => {
+                        member.map(Limit::RuntimeArray)
+                    }
+                    _ => None,
+                };
+                ty = id_operand(def, 0);
+                let index_type = match value_types.get(&index) {
+                    Some(&index_type) if is_int32(index_type) => index_type,
+                    _ => continue,
+                };
+                let limit = match limit {
+                    Some(limit) if !constants.contains_key(&index) => limit,
+                    _ => continue,
+                };
+
+                let max = match limit {
+                    Limit::Fixed(max) => get_or_insert_constant(module, index_type, max),
+                    Limit::RuntimeArray(member) => {
+                        let uint = *uint.get_or_insert_with(|| get_or_insert_uint32(module));
+                        let one = get_or_insert_constant(module, index_type, 1);
+                        let length = module.allocate_id();
+                        instructions.push(mr::Instruction::new(
+                            spirv::Op::ArrayLength,
+                            Some(uint),
+                            Some(length),
+                            vec![mr::Operand::IdRef(base), mr::Operand::LiteralInt32(member)],
+                        ));
+                        let max = module.allocate_id();
+                        instructions.push(mr::Instruction::new(
+                            spirv::Op::ISub,
+                            Some(index_type),
+                            Some(max),
+                            vec![mr::Operand::IdRef(length), mr::Operand::IdRef(one)],
+                        ));
+                        max
+                    }
+                };
+                let set = *glsl
+                    .get_or_insert_with(|| module.get_or_insert_ext_inst_set("GLSL.std.450"));
+                let clamped = module.allocate_id();
+                instructions.push(mr::Instruction::new(
+                    spirv::Op::ExtInst,
+                    Some(index_type),
+                    Some(clamped),
+                    vec![
+                        mr::Operand::IdRef(set),
+                        mr::Operand::LiteralExtInstInteger(spirv::GLOp::UMin as u32),
+                        mr::Operand::IdRef(index),
+                        mr::Operand::IdRef(max),
+                    ],
+                ));
+                inst.operands[position] = mr::Operand::IdRef(clamped);
+                count += 1;
+            }
+            instructions.push(inst);
+        }
+        block.instructions = instructions;
+    }
+    module.functions = functions;
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::clamp_access_chain_indices;
+
+    #[test]
+    fn test_clamp_access_chain_indices() {
+        use spirv::StorageClass::{Function, StorageBuffer};
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let int = b.type_int(32, 1);
+        let four = b.constant_u32(int, 4);
+        let array = b.type_array(float, four);
+        let runtime = b.type_runtime_array(float);
+        let block = b.type_struct(vec![array, runtime]);
+        let ptr_block = b.type_pointer(None, StorageBuffer, block);
+        let ptr_float = b.type_pointer(None, StorageBuffer, float);
+        let ptr_int = b.type_pointer(None, Function, int);
+        let voidf = b.type_function(void, vec![]);
+        let buffer = b.variable(ptr_block, None, StorageBuffer, None);
+        let zero = b.constant_u32(int, 0);
+        let one = b.constant_u32(int, 1);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let var = b.variable(ptr_int, None, Function, None);
+        let i = b.load(int, None, var, None, vec![]).unwrap();
+        b.access_chain(ptr_float, None, buffer, vec![zero, i]).unwrap();
+        b.access_chain(ptr_float, None, buffer, vec![zero, one]).unwrap();
+        b.access_chain(ptr_float, None, buffer, vec![one, i]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(2, clamp_access_chain_indices(&mut m));
+        let disas: Vec<String> = m.functions[0]
+            .basic_blocks[0]
+            .instructions
+            .iter()
+            .map(|i| i.disassemble())
+            .collect();
+        assert_eq!(
+            disas,
+            vec![
+                "%17 = OpVariable  %10  Function",
+                "%18 = OpLoad  %3  %17",
+                "%24 = OpExtInst  %3  %23 38 %18 %22",
+                "%19 = OpAccessChain  %9  %12 %13 %24",
+                "%20 = OpAccessChain  %9  %12 %13 %14",
+                "%26 = OpArrayLength  %25  %12 1",
+                "%27 = OpISub  %3  %26 %14",
+                "%28 = OpExtInst  %3  %23 38 %18 %27",
+                "%21 = OpAccessChain  %9  %12 %14 %28",
+                "OpReturn",
+            ]
+        );
+        assert_eq!(Some(23), m.ext_inst_set("GLSL.std.450"));
+    }
+}
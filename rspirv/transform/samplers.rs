@@ -0,0 +1,307 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A combined image sampler variable split by
+/// [`split_combined_image_samplers`](fn.split_combined_image_samplers.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SamplerSplit {
+    /// The result id of the original variable, which now holds the image.
+    pub image: Word,
+    /// The result id of the new sampler variable.
+    pub sampler: Word,
+}
+
+impl fmt::Display for SamplerSplit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%{} -> image %{}, sampler %{}", self.image, self.image, self.sampler)
+    }
+}
+
+/// A combined image sampler variable to split.
+struct Candidate {
+    variable: Word,
+    sampled_image_type: Word,
+    image_type: Word,
+}
+
+/// Returns the id of the type in `module` defined by the given opcode and
+/// operands, adding the type if it does not exist yet.
+pub(super) fn find_or_add_type(module: &mut mr::Module,
+                               opcode: spirv::Op,
+                               operands: Vec<mr::Operand>)
+                               -> Word {
+    let existing = module
+        .types_global_values
+        .iter()
+        .find(|inst| inst.class.opcode == opcode && inst.operands[..] == operands[..])
+        .and_then(|inst| inst.result_id);
+    if let Some(id) = existing {
+        return id;
+    }
+    let id = module.allocate_id();
+    module.types_global_values.push(mr::Instruction::new(opcode, None, Some(id), operands));
+    id
+}
+
+/// Splits the combined image sampler variables of the given module into
+/// separate image and sampler variables, as HLSL-born SPIR-V expects them.
+///
+/// Each `UniformConstant` variable of OpTypeSampledImage type keeps its id
+/// but now holds the image; a new sampler variable is added next to it.
+/// Every OpLoad of the combined variable is replaced by loads of both
+/// variables and an OpSampledImage, keeping the result id, so that the
+/// sampling instructions are left untouched. The sampler variable gets
+/// the DescriptorSet of the original variable and its Binding increased by
+/// `sampler_binding_offset`, is named after it with a `_sampler` suffix,
+/// and is added to the interfaces of the entry points listing the original
+/// variable.
+///
+/// Arrays of combined image samplers and variables used other than by
+/// OpLoad, e.g., passed to functions, are left unchanged.
+///
+/// Returns the split variables.
+pub fn split_combined_image_samplers(module: &mut mr::Module, sampler_binding_offset: u32)
+                                     -> Vec<SamplerSplit> {
+    let defs: HashMap<Word, &mr::Instruction> = module
+        .types_global_values
+        .iter()
+        .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+        .collect();
+    let mut candidates: Vec<Candidate> = module
+        .types_global_values
+        .iter()
+        .filter(|inst| {
+            inst.class.opcode == spirv::Op::Variable &&
+                inst.operands.first() ==
+                    Some(&mr::Operand::StorageClass(spirv::StorageClass::UniformConstant))
+        })
+        .filter_map(|inst| {
+            let pointer = defs.get(&inst.result_type?)?;
+            let sampled_image_type = match pointer.operands.get(1) {
+                Some(&mr::Operand::IdRef(pointee)) => pointee,
+                _ => return None,
+            };
+            let sampled_image = defs.get(&sampled_image_type)?;
+            match (sampled_image.class.opcode, sampled_image.operands.first()) {
+                (spirv::Op::TypeSampledImage, Some(&mr::Operand::IdRef(image_type))) => {
+                    Some(Candidate { variable: inst.result_id?, sampled_image_type, image_type })
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    // Only variables merely loaded from can be split.
+    for inst in module.functions.iter().flat_map(|f| f.all_inst_iter()) {
+        let loaded = if inst.class.opcode == spirv::Op::Load { 1 } else { 0 };
+        for operand in inst.operands.iter().skip(loaded) {
+            if let mr::Operand::IdRef(id) = *operand {
+                candidates.retain(|c| c.variable != id);
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return vec![];
+    }
+
+    let sampler_type = find_or_add_type(module, spirv::Op::TypeSampler, vec![]);
+    let uniform_constant = mr::Operand::StorageClass(spirv::StorageClass::UniformConstant);
+    let sampler_pointer = find_or_add_type(
+        module,
+        spirv::Op::TypePointer,
+        vec![uniform_constant.clone(), mr::Operand::IdRef(sampler_type)],
+    );
+    let mut splits = vec![];
+    let mut images = HashMap::new();
+    for candidate in &candidates {
+        let image_pointer = find_or_add_type(
+            module,
+            spirv::Op::TypePointer,
+            vec![uniform_constant.clone(), mr::Operand::IdRef(candidate.image_type)],
+        );
+        let sampler = module.allocate_id();
+        for inst in &mut module.types_global_values {
+            if inst.result_id == Some(candidate.variable) {
+                inst.result_type = Some(image_pointer);
+            }
+        }
+        module.types_global_values.push(mr::Instruction::new(
+            spirv::Op::Variable,
+            Some(sampler_pointer),
+            Some(sampler),
+            vec![uniform_constant.clone()],
+        ));
+        images.insert(candidate.variable, (candidate, sampler));
+        splits.push(SamplerSplit { image: candidate.variable, sampler });
+    }
+
+    let mut annotations = vec![];
+    for inst in &module.annotations {
+        let (target, decoration, value) = match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate,
+             &[mr::Operand::IdRef(target),
+               mr::Operand::Decoration(decoration),
+               mr::Operand::LiteralInt32(value)]) => (target, decoration, value),
+            _ => continue,
+        };
+        let value = match (images.get(&target), decoration) {
+            (Some(_), spirv::Decoration::DescriptorSet) => value,
+            (Some(_), spirv::Decoration::Binding) => value + sampler_binding_offset,
+            _ => continue,
+        };
+        let mut decorate = inst.clone();
+        decorate.operands[0] = mr::Operand::IdRef(images[&target].1);
+        decorate.operands[2] = mr::Operand::LiteralInt32(value);
+        annotations.push(decorate);
+    }
+    module.annotations.extend(annotations);
+    let mut names = vec![];
+    for inst in &module.debugs {
+        if let (spirv::Op::Name,
+                &[mr::Operand::IdRef(target), mr::Operand::LiteralString(ref name)]) =
+            (inst.class.opcode, &inst.operands[..]) {
+            if let Some(&(_, sampler)) = images.get(&target) {
+                names.push(mr::Instruction::new(
+                    spirv::Op::Name,
+                    None,
+                    None,
+                    vec![
+                        mr::Operand::IdRef(sampler),
+                        mr::Operand::LiteralString(format!("{}_sampler", name).into()),
+                    ],
+                ));
+            }
+        }
+    }
+    module.debugs.extend(names);
+    for entry in &mut module.entry_points {
+        let added: Vec<mr::Operand> = entry
+            .operands
+            .iter()
+            .skip(3)
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(id) => images.get(&id).map(|&(_, s)| mr::Operand::IdRef(s)),
+                _ => None,
+            })
+            .collect();
+        entry.operands.extend(added);
+    }
+
+    let mut loads = vec![];
+    for (f, function) in module.functions.iter().enumerate() {
+        for (b, block) in function.basic_blocks.iter().enumerate() {
+            for (i, inst) in block.instructions.iter().enumerate() {
+                if let (spirv::Op::Load, Some(&mr::Operand::IdRef(pointer))) =
+                    (inst.class.opcode, inst.operands.first()) {
+                    if images.contains_key(&pointer) {
+                        loads.push((f, b, i, pointer));
+                    }
+                }
+            }
+        }
+    }
+    let ids: Vec<(Word, Word)> =
+        loads.iter().map(|_| (module.allocate_id(), module.allocate_id())).collect();
+    // Replace from the back so that the indices of earlier loads stay valid.
+    for (&(f, b, i, pointer), &(image, sampler)) in loads.iter().zip(&ids).rev() {
+        let (candidate, sampler_variable) = images[&pointer];
+        let instructions = &mut module.functions[f].basic_blocks[b].instructions;
+        let load = instructions[i].clone();
+        let mut load_image = load.clone();
+        load_image.result_type = Some(candidate.image_type);
+        load_image.result_id = Some(image);
+        let load_sampler = mr::Instruction::new(
+            spirv::Op::Load,
+            Some(sampler_type),
+            Some(sampler),
+            vec![mr::Operand::IdRef(sampler_variable)],
+        );
+        let combine = mr::Instruction::new(
+            spirv::Op::SampledImage,
+            Some(candidate.sampled_image_type),
+            load.result_id,
+            vec![mr::Operand::IdRef(image), mr::Operand::IdRef(sampler)],
+        );
+        instructions.splice(i..i + 1, vec![load_image, load_sampler, combine]);
+    }
+    module.sort_global_section();
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::{split_combined_image_samplers, SamplerSplit};
+
+    #[test]
+    fn test_split_combined_image_samplers() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let vec2 = b.type_vector(float, 2);
+        let image = b.type_image(float, spirv::Dim::Dim2D, 0, 0, 0, 1,
+                                 spirv::ImageFormat::Unknown, None);
+        let sampled_image = b.type_sampled_image(image);
+        let ptr = b.type_pointer(None, spirv::StorageClass::UniformConstant, sampled_image);
+        let voidf = b.type_function(void, vec![]);
+        let texture = b.variable(ptr, None, spirv::StorageClass::UniformConstant, None);
+        let coord = b.constant_null(vec2);
+        b.name(texture, "albedo");
+        b.decorate(texture, spirv::Decoration::DescriptorSet,
+                   vec![mr::Operand::LiteralInt32(1)]);
+        b.decorate(texture, spirv::Decoration::Binding, vec![mr::Operand::LiteralInt32(2)]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let combined = b.load(sampled_image, None, texture, None, vec![]).unwrap();
+        b.image_sample_implicit_lod(vec4, None, combined, coord, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Fragment, main, "main", vec![texture]);
+        let mut m = b.module();
+
+        let splits = split_combined_image_samplers(&mut m, 16);
+        assert_eq!(vec![SamplerSplit { image: texture, sampler: 18 }], splits);
+        assert_eq!("%9 -> image %9, sampler %18", splits[0].to_string());
+        let disas: Vec<String> = m.all_inst_iter().map(|i| i.disassemble()).collect();
+        for expected in &[
+            "OpEntryPoint Fragment %11 \"main\" %9 %18",
+            "OpName %18 \"albedo_sampler\"",
+            "OpDecorate %18 DescriptorSet 1",
+            "OpDecorate %18 Binding 18",
+            "%15 = OpTypeSampler",
+            "%16 = OpTypePointer UniformConstant %15",
+            "%17 = OpTypePointer UniformConstant %5",
+            "%9 = OpVariable  %17  UniformConstant",
+            "%19 = OpLoad  %5  %9",
+            "%20 = OpLoad  %15  %18",
+            "%13 = OpSampledImage  %6  %19 %20",
+        ] {
+            assert!(disas.iter().any(|d| d == expected), "missing {}", expected);
+        }
+        let sorted = m.types_global_values.iter().position(|i| i.result_id == Some(17)) <
+            m.types_global_values.iter().position(|i| i.result_id == Some(texture));
+        assert!(sorted);
+        assert!(split_combined_image_samplers(&mut m, 16).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Returns the SpecId decorations of the given module, keyed by their
+/// targets.
+pub(super) fn spec_ids(module: &mr::Module) -> HashMap<Word, u32> {
+    module.annotations
+        .iter()
+        .filter_map(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate,
+             &[mr::Operand::IdRef(target),
+               mr::Operand::Decoration(spirv::Decoration::SpecId),
+               mr::Operand::LiteralInt32(spec_id)]) => Some((target, spec_id)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replaces boolean specialization constants by regular constants with the
+/// values given for their SpecIds, as if the module were specialized by
+/// the consumer.
+///
+/// Afterwards, OpSpecConstantOp instructions computing OpLogicalNot,
+/// OpLogicalAnd, OpLogicalOr, OpLogicalEqual, OpLogicalNotEqual, or
+/// OpSelect from constant booleans only are folded into constants as well.
+/// Specialization constants whose SpecIds are not given are kept, so are
+/// their SpecId decorations.
+///
+/// Returns the number of specialization constants replaced.
+pub fn specialize_bool_constants(module: &mut mr::Module, values: &HashMap<u32, bool>) -> usize {
+    let spec_ids = spec_ids(module);
+    let mut constants: HashMap<Word, bool> = HashMap::new();
+    let mut count = 0;
+    for inst in &mut module.types_global_values {
+        let id = match inst.result_id {
+            Some(id) => id,
+            None => continue,
+        };
+        let value = match inst.class.opcode {
+            spirv::Op::ConstantTrue => Some(true),
+            spirv::Op::ConstantFalse => Some(false),
+            spirv::Op::SpecConstantTrue | spirv::Op::SpecConstantFalse => {
+                spec_ids.get(&id).and_then(|spec_id| values.get(spec_id)).cloned()
+            }
+            spirv::Op::SpecConstantOp => fold_bool_op(&inst.operands, &constants),
+            _ => None,
+        };
+        let value = match value {
+            Some(value) => value,
+            None => continue,
+        };
+        constants.insert(id, value);
+        let opcode = if value { spirv::Op::ConstantTrue } else { spirv::Op::ConstantFalse };
+        if inst.class.opcode != opcode &&
+            inst.class.opcode != spirv::Op::ConstantTrue &&
+            inst.class.opcode != spirv::Op::ConstantFalse {
+            *inst = mr::Instruction::new(opcode, inst.result_type, Some(id), vec![]);
+            count += 1;
+        }
+    }
+    if count != 0 {
+        // Regular constants cannot be decorated with SpecId.
+        let replaced: HashSet<Word> = spec_ids
+            .iter()
+            .filter(|&(id, spec_id)| values.contains_key(spec_id) && constants.contains_key(id))
+            .map(|(&id, _)| id)
+            .collect();
+        module.annotations.retain(|inst| match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::Decorate,
+             &[mr::Operand::IdRef(target),
+               mr::Operand::Decoration(spirv::Decoration::SpecId),
+               ..]) => !replaced.contains(&target),
+            _ => true,
+        });
+    }
+    count
+}
+
+/// Evaluates the boolean operation of the OpSpecConstantOp with the given
+/// operands if all its inputs are known.
+fn fold_bool_op(operands: &[mr::Operand], constants: &HashMap<Word, bool>) -> Option<bool> {
+    let (opcode, inputs) = match operands.split_first() {
+        Some((&mr::Operand::LiteralSpecConstantOpInteger(opcode), inputs)) => (opcode, inputs),
+        _ => return None,
+    };
+    let value = |index: usize| match inputs.get(index) {
+        Some(mr::Operand::IdRef(id)) => constants.get(id).cloned(),
+        _ => None,
+    };
+    match opcode {
+        spirv::Op::LogicalNot => value(0).map(|a| !a),
+        spirv::Op::LogicalAnd => Some(value(0)? && value(1)?),
+        spirv::Op::LogicalOr => Some(value(0)? || value(1)?),
+        spirv::Op::LogicalEqual => Some(value(0)? == value(1)?),
+        spirv::Op::LogicalNotEqual => Some(value(0)? != value(1)?),
+        spirv::Op::Select => {
+            if value(0)? { value(1) } else { value(2) }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use std::collections::HashMap;
+    use super::specialize_bool_constants;
+
+    #[test]
+    fn test_specialize_bool_constants() {
+        let mut b = mr::Builder::new();
+        let bool_type = b.type_bool();
+        let fast = b.spec_constant_true(bool_type);
+        let shadows = b.spec_constant_false(bool_type);
+        let other = b.spec_constant_true(bool_type);
+        let slow = b.spec_constant_op(bool_type, spirv::Op::LogicalNot,
+                                      vec![mr::Operand::IdRef(fast)]).unwrap();
+        let both = b.spec_constant_op(bool_type, spirv::Op::LogicalAnd,
+                                      vec![mr::Operand::IdRef(slow), mr::Operand::IdRef(other)])
+            .unwrap();
+        for (&id, spec_id) in [fast, shadows, other].iter().zip(0..) {
+            b.decorate(id, spirv::Decoration::SpecId, vec![mr::Operand::LiteralInt32(spec_id)]);
+        }
+        let mut m = b.module();
+
+        let values: HashMap<u32, bool> = vec![(0, false), (1, true)].into_iter().collect();
+        assert_eq!(3, specialize_bool_constants(&mut m, &values));
+        let disas: Vec<String> = m.global_inst_iter().map(|i| i.disassemble()).collect();
+        assert_eq!(disas,
+                   vec!["OpDecorate %4 SpecId 2",
+                        "%1 = OpTypeBool",
+                        "%2 = OpConstantFalse  %1 ",
+                        "%3 = OpConstantTrue  %1 ",
+                        "%4 = OpSpecConstantTrue  %1 ",
+                        "%5 = OpConstantTrue  %1 ",
+                        format!("%{} = OpSpecConstantOp  %1  LogicalAnd %5 %4", both).as_str()]);
+        assert_eq!(0, specialize_bool_constants(&mut m, &values));
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use super::{rewrite_id_uses, rewrite_operands};
+
+/// Removes OpString instructions duplicating earlier ones, i.e., with the
+/// same string, and rewrites all uses of the removed strings, e.g., the
+/// file names of OpSource and OpLine instructions, to use the earlier ones.
+/// Debug names of removed strings are removed as well.
+///
+/// Afterwards, OpSource instructions identical to an earlier one, including
+/// the source text in their OpSourceContinued instructions, are removed
+/// together with those continuations.
+///
+/// Returns the number of instructions removed, not counting debug names.
+pub fn remove_duplicate_strings(module: &mut mr::Module) -> usize {
+    let mut seen: HashMap<mr::Symbol, Word> = HashMap::new();
+    let mut replacements: HashMap<Word, Word> = HashMap::new();
+    for inst in &module.debugs {
+        if let (spirv::Op::String, Some(id), Some(mr::Operand::LiteralString(s))) =
+            (inst.class.opcode, inst.result_id, inst.operands.first())
+        {
+            match seen.get(s) {
+                Some(&existing) => {
+                    replacements.insert(id, existing);
+                }
+                None => {
+                    seen.insert(s.clone(), id);
+                }
+            }
+        }
+    }
+
+    let mut removed = replacements.len();
+    let mut sources: HashSet<String> = HashSet::new();
+    let mut debugs = Vec::with_capacity(module.debugs.len());
+    let mut insts = ::std::mem::take(&mut module.debugs).into_iter().peekable();
+    while let Some(mut inst) = insts.next() {
+        let removed_id = match (inst.result_id, inst.operands.first()) {
+            (Some(id), _) | (None, Some(&mr::Operand::IdRef(id))) => {
+                replacements.contains_key(&id)
+            }
+            _ => false,
+        };
+        if removed_id {
+            continue;
+        }
+        rewrite_operands(&mut inst, &replacements);
+        if inst.class.opcode != spirv::Op::Source {
+            debugs.push(inst);
+            continue;
+        }
+        let mut source = vec![inst];
+        while insts.peek().is_some_and(|inst| inst.class.opcode == spirv::Op::SourceContinued) {
+            source.push(insts.next().unwrap());
+        }
+        let key: Vec<String> = source.iter().map(|inst| format!("{:?}", inst.operands)).collect();
+        if sources.insert(key.join("\n")) {
+            debugs.extend(source);
+        } else {
+            removed += source.len();
+        }
+    }
+    module.debugs = debugs;
+    if replacements.is_empty() {
+        return removed;
+    }
+
+    rewrite_id_uses(module, &replacements);
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::remove_duplicate_strings;
+
+    #[test]
+    fn test_remove_duplicate_strings() {
+        let mut b = mr::Builder::new();
+        let path = b.string("src/shader.hlsl");
+        let other = b.string("src/common.hlsl");
+        let dup = b.string("src/shader.hlsl");
+        b.name(dup, "dup");
+        b.source(spirv::SourceLanguage::HLSL, 500, Some(path), Some("float4 main();"));
+        b.source(spirv::SourceLanguage::HLSL, 500, Some(dup), Some("float4 main();"));
+        b.source(spirv::SourceLanguage::HLSL, 500, Some(other), None::<String>);
+        let float = b.type_float(32);
+        b.line(dup, 3, 1);
+        b.constant_f32(float, 1.0);
+        let mut m = b.module();
+
+        assert_eq!(2, remove_duplicate_strings(&mut m));
+        assert_eq!(
+            vec![
+                "%1 = OpString \"src/shader.hlsl\"",
+                "%2 = OpString \"src/common.hlsl\"",
+                "OpSource HLSL 500 %1 \"float4 main();\"",
+                "OpSource HLSL 500 %2",
+            ],
+            m.debugs.iter().map(|inst| inst.disassemble()).collect::<Vec<_>>()
+        );
+        assert_eq!("OpLine %1 3 1", m.types_global_values[1].disassemble());
+        assert_eq!(0, remove_duplicate_strings(&mut m));
+    }
+}
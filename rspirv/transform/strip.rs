@@ -0,0 +1,217 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use grammar;
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashSet;
+
+/// Removes all debug information: OpSource, OpSourceContinued,
+/// OpSourceExtension, OpString, OpName, OpMemberName, OpModuleProcessed,
+/// OpLine, and OpNoLine.
+///
+/// Non-semantic extended instruction sets are kept; see
+/// [`strip_non_semantic_info`](fn.strip_non_semantic_info.html).
+///
+/// Returns the number of instructions removed.
+pub fn strip_debug_info(module: &mut mr::Module) -> usize {
+    let mut count = module.debugs.len();
+    module.debugs.clear();
+    count += retain_instructions(module, |inst| {
+        !grammar::reflect::is_location_debug(inst.class.opcode)
+    });
+    count
+}
+
+/// Removes all reflection information for HLSL: OpDecorateStringGOOGLE,
+/// OpMemberDecorateStringGOOGLE, OpDecorateId with HlslCounterBufferGOOGLE,
+/// and the OpExtension instructions for `SPV_GOOGLE_decorate_string` and
+/// `SPV_GOOGLE_hlsl_functionality1`.
+///
+/// Returns the number of instructions removed.
+pub fn strip_reflect_info(module: &mut mr::Module) -> usize {
+    let before = module.annotations.len() + module.extensions.len();
+    module.annotations.retain(|inst| match inst.class.opcode {
+        spirv::Op::DecorateStringGOOGLE | spirv::Op::MemberDecorateStringGOOGLE => false,
+        spirv::Op::DecorateId => {
+            inst.operands.get(1) !=
+                Some(&mr::Operand::Decoration(spirv::Decoration::HlslCounterBufferGOOGLE))
+        }
+        _ => true,
+    });
+    module.extensions.retain(|inst| match inst.operands.first() {
+        Some(mr::Operand::LiteralString(name)) => {
+            name != "SPV_GOOGLE_decorate_string" && name != "SPV_GOOGLE_hlsl_functionality1"
+        }
+        _ => true,
+    });
+    before - module.annotations.len() - module.extensions.len()
+}
+
+/// Removes all non-semantic information: OpExtInst instructions using
+/// `NonSemantic.*` extended instruction sets, the OpExtInstImport
+/// instructions for these sets, and the OpExtension instruction for
+/// `SPV_KHR_non_semantic_info`.
+///
+/// Returns the number of instructions removed.
+pub fn strip_non_semantic_info(module: &mut mr::Module) -> usize {
+    let mut sets: HashSet<Word> = HashSet::new();
+    let mut count = 0;
+    module.ext_inst_imports.retain(|inst| match (inst.result_id, inst.operands.first()) {
+        (Some(id), Some(mr::Operand::LiteralString(name))) if name.starts_with("NonSemantic.") => {
+            sets.insert(id);
+            count += 1;
+            false
+        }
+        _ => true,
+    });
+    if sets.is_empty() {
+        return 0;
+    }
+    count += retain_instructions(module, |inst| {
+        inst.class.opcode != spirv::Op::ExtInst ||
+            !matches!(inst.operands.first(), Some(mr::Operand::IdRef(set)) if sets.contains(set))
+    });
+    let before = module.extensions.len();
+    module.extensions.retain(|inst| match inst.operands.first() {
+        Some(mr::Operand::LiteralString(name)) => name != "SPV_KHR_non_semantic_info",
+        _ => true,
+    });
+    count + before - module.extensions.len()
+}
+
+/// Retains only global values and instructions in basic blocks for which
+/// `keep` returns true, and returns the number of instructions removed.
+fn retain_instructions<F: Fn(&mr::Instruction) -> bool>(module: &mut mr::Module, keep: F) -> usize {
+    let mut count = 0;
+    let before = module.types_global_values.len();
+    module.types_global_values.retain(&keep);
+    count += before - module.types_global_values.len();
+    for block in module.functions.iter_mut().flat_map(|f| f.basic_blocks.iter_mut()) {
+        let before = block.instructions.len();
+        block.instructions.retain(&keep);
+        count += before - block.instructions.len();
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::{strip_debug_info, strip_non_semantic_info, strip_reflect_info};
+    use transform::{Analysis, FnPass, PassManager, PassResult};
+
+    fn module_with_debug_info() -> mr::Module {
+        let mut b = mr::Builder::new();
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let file = b.string("shader.hlsl");
+        b.source(spirv::SourceLanguage::HLSL, 500, Some(file), None::<String>);
+        b.module_processed("entry-point main");
+        let void = b.type_void();
+        b.name(void, "void");
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.line(file, 1, 1);
+        b.no_line();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    #[test]
+    fn test_strip_debug_info() {
+        let mut m = module_with_debug_info();
+        assert_eq!(6, strip_debug_info(&mut m));
+        assert!(m.debugs.is_empty());
+        assert_eq!(
+            "; SPIR-V\n\
+             ; Version: 1.3\n\
+             ; Generator: rspirv\n\
+             ; Bound: 6\n\
+             OpMemoryModel Logical GLSL450\n\
+             %2 = OpTypeVoid\n\
+             %3 = OpTypeFunction %2\n\
+             %4 = OpFunction  %2  None %3\n\
+             %5 = OpLabel\n\
+             OpReturn\n\
+             OpFunctionEnd",
+            m.disassemble()
+        );
+        assert_eq!(0, strip_debug_info(&mut m));
+    }
+
+    #[test]
+    fn test_module_strip_debug() {
+        let mut m = module_with_debug_info();
+        assert_eq!(6, m.strip_debug());
+        assert!(m.debugs.is_empty());
+    }
+
+    #[test]
+    fn test_strip_debug_info_pass() {
+        let mut m = module_with_debug_info();
+        let mut pm = PassManager::new();
+        pm.add_pass(FnPass::new("strip-debug", strip_debug_info).preserving(&[Analysis::Cfg]));
+        assert_eq!(Ok(PassResult::Changed), pm.run(&mut m));
+        assert!(pm.is_preserved(Analysis::Cfg));
+        assert_eq!(Ok(PassResult::Unchanged), pm.run(&mut m));
+    }
+
+    #[test]
+    fn test_strip_reflect_info() {
+        let mut b = mr::Builder::new();
+        b.extension("SPV_GOOGLE_decorate_string");
+        b.extension("SPV_GOOGLE_hlsl_functionality1");
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        b.decorate_string_google(1, spirv::Decoration::HlslSemanticGOOGLE, vec![]);
+        b.decorate_id(1, spirv::Decoration::HlslCounterBufferGOOGLE, vec![mr::Operand::IdRef(2)]);
+        b.decorate(1, spirv::Decoration::Binding, vec![mr::Operand::LiteralInt32(0)]);
+        let mut m = b.module();
+
+        assert_eq!(4, strip_reflect_info(&mut m));
+        assert_eq!(1, m.extensions.len());
+        assert_eq!(1, m.annotations.len());
+        assert_eq!(spirv::Op::Decorate, m.annotations[0].class.opcode);
+    }
+
+    #[test]
+    fn test_strip_non_semantic_info() {
+        let mut b = mr::Builder::new();
+        b.extension("SPV_KHR_non_semantic_info");
+        let glsl = b.ext_inst_import("GLSL.std.450");
+        let info = b.ext_inst_import("NonSemantic.DebugPrintf");
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ext_inst(void, None, info, 1, vec![]).unwrap();
+        b.ext_inst(float, None, glsl, 1, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(3, strip_non_semantic_info(&mut m));
+        assert!(m.extensions.is_empty());
+        assert_eq!(1, m.ext_inst_imports.len());
+        assert_eq!(2, m.functions[0].basic_blocks[0].instructions.len());
+        assert_eq!(0, strip_non_semantic_info(&mut m));
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Standard layout rules for explicitly laid out blocks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutRule {
+    /// The std140 layout, required for uniform blocks.
+    Std140,
+    /// The std430 layout, commonly used for storage blocks.
+    Std430,
+}
+
+/// A struct member whose Offset decoration is missing or inconsistent.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OffsetIssue {
+    /// The member has no Offset decoration.
+    Missing {
+        struct_id: Word,
+        member: u32,
+        member_name: Option<String>,
+        /// The offset computed from the layout rule.
+        expected: u32,
+    },
+    /// The member's Offset is misaligned or overlaps the previous member.
+    Inconsistent {
+        struct_id: Word,
+        member: u32,
+        member_name: Option<String>,
+        /// The offset found in the module.
+        offset: u32,
+        /// The offset computed from the layout rule.
+        expected: u32,
+    },
+}
+
+impl fmt::Display for OffsetIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (struct_id, member, name) = match *self {
+            OffsetIssue::Missing { struct_id, member, ref member_name, .. } |
+            OffsetIssue::Inconsistent { struct_id, member, ref member_name, .. } => {
+                (struct_id, member, member_name)
+            }
+        };
+        let name = name.as_ref().map_or(String::new(), |n| format!(" ({})", n));
+        match *self {
+            OffsetIssue::Missing { expected, .. } => write!(
+                f,
+                "member {}{} of struct %{} has no Offset; expected {}",
+                member, name, struct_id, expected
+            ),
+            OffsetIssue::Inconsistent { offset, expected, .. } => write!(
+                f,
+                "member {}{} of struct %{} has inconsistent Offset {}; expected {}",
+                member, name, struct_id, offset, expected
+            ),
+        }
+    }
+}
+
+/// Size and alignment of a type under some layout rule.
+#[derive(Copy, Clone, Debug)]
+struct Layout {
+    size: u32,
+    align: u32,
+}
+
+fn round_up(value: u32, align: u32) -> u32 {
+    if align <= 1 {
+        value
+    } else {
+        value.next_multiple_of(align)
+    }
+}
+
+/// Cached information about the module needed for computing layouts.
+struct LayoutContext<'a> {
+    rule: LayoutRule,
+    types: HashMap<Word, &'a mr::Instruction>,
+    constants: HashMap<Word, u32>,
+    array_strides: HashMap<Word, u32>,
+    /// (struct id, member index) -> decorations on that member.
+    member_decorations: HashMap<(Word, u32), Vec<&'a mr::Instruction>>,
+}
+
+impl<'a> LayoutContext<'a> {
+    fn new(module: &'a mr::Module, rule: LayoutRule) -> LayoutContext<'a> {
+        let mut types = HashMap::new();
+        let mut constants = HashMap::new();
+        for inst in &module.types_global_values {
+            if let Some(id) = inst.result_id {
+                match inst.class.opcode {
+                    spirv::Op::Constant | spirv::Op::SpecConstant => {
+                        if let Some(&mr::Operand::LiteralInt32(v)) = inst.operands.first() {
+                            constants.insert(id, v);
+                        }
+                    }
+                    _ => {
+                        types.insert(id, inst);
+                    }
+                }
+            }
+        }
+
+        let mut array_strides = HashMap::new();
+        let mut member_decorations = HashMap::new();
+        for inst in &module.annotations {
+            match (inst.class.opcode, &inst.operands[..]) {
+                (
+                    spirv::Op::Decorate,
+                    &[mr::Operand::IdRef(target),
+                      mr::Operand::Decoration(spirv::Decoration::ArrayStride),
+                      mr::Operand::LiteralInt32(stride)],
+                ) => {
+                    array_strides.insert(target, stride);
+                }
+                (
+                    spirv::Op::MemberDecorate,
+                    &[mr::Operand::IdRef(target), mr::Operand::LiteralInt32(member), ..],
+                ) => {
+                    member_decorations
+                        .entry((target, member))
+                        .or_insert_with(Vec::new)
+                        .push(inst);
+                }
+                _ => {}
+            }
+        }
+
+        LayoutContext {
+            rule,
+            types,
+            constants,
+            array_strides,
+            member_decorations,
+        }
+    }
+
+    /// Returns the instruction applying `decoration` to the given struct member.
+    fn member_decoration(
+        &self,
+        struct_id: Word,
+        member: u32,
+        decoration: spirv::Decoration,
+    ) -> Option<&'a mr::Instruction> {
+        self.member_decorations
+            .get(&(struct_id, member))
+            .and_then(|decs| {
+                decs.iter()
+                    .find(|inst| inst.operands.get(2) == Some(&mr::Operand::Decoration(decoration)))
+                    .cloned()
+            })
+    }
+
+    fn member_literal(&self, struct_id: Word, member: u32, decoration: spirv::Decoration) -> Option<u32> {
+        match self.member_decoration(struct_id, member, decoration)
+            .and_then(|inst| inst.operands.get(3))
+        {
+            Some(&mr::Operand::LiteralInt32(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the member type ids of the given struct type.
+    fn struct_members(&self, struct_id: Word) -> Vec<Word> {
+        match self.types.get(&struct_id) {
+            Some(inst) if inst.class.opcode == spirv::Op::TypeStruct => inst.operands
+                .iter()
+                .filter_map(|op| match *op {
+                    mr::Operand::IdRef(id) => Some(id),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the alignment of an array whose elements have the given layout.
+    fn array_alignment(&self, element: Layout) -> u32 {
+        match self.rule {
+            LayoutRule::Std140 => round_up(element.align, 16),
+            LayoutRule::Std430 => element.align,
+        }
+    }
+
+    fn default_stride(&self, element: Layout) -> u32 {
+        round_up(element.size, self.array_alignment(element))
+    }
+
+    /// Computes the layout of a vector of `count` components of `scalar`.
+    fn vector_layout(&self, scalar: Layout, count: u32) -> Layout {
+        let align = match count {
+            1 => scalar.align,
+            2 => scalar.align * 2,
+            _ => scalar.align * 4,
+        };
+        Layout {
+            size: scalar.size * count,
+            align,
+        }
+    }
+
+    /// Computes the layout of the given type.
+    ///
+    /// `row_major` and `matrix_stride` only affect matrix types; they
+    /// come from the decorations of the enclosing struct member.
+    fn layout(&self, id: Word, row_major: bool, matrix_stride: Option<u32>) -> Option<Layout> {
+        let inst = *self.types.get(&id)?;
+        let operand_id = |index: usize| match inst.operands.get(index) {
+            Some(&mr::Operand::IdRef(v)) => Some(v),
+            _ => None,
+        };
+        let operand_literal = |index: usize| match inst.operands.get(index) {
+            Some(&mr::Operand::LiteralInt32(v)) => Some(v),
+            _ => None,
+        };
+        match inst.class.opcode {
+            spirv::Op::TypeBool => Some(Layout { size: 4, align: 4 }),
+            spirv::Op::TypeInt | spirv::Op::TypeFloat => {
+                let bytes = operand_literal(0)? / 8;
+                Some(Layout { size: bytes, align: bytes })
+            }
+            spirv::Op::TypePointer => Some(Layout { size: 8, align: 8 }),
+            spirv::Op::TypeVector => {
+                let scalar = self.layout(operand_id(0)?, false, None)?;
+                Some(self.vector_layout(scalar, operand_literal(1)?))
+            }
+            spirv::Op::TypeMatrix => {
+                let column = self.types.get(&operand_id(0)?)?;
+                let column_count = operand_literal(1)?;
+                let scalar_id = match column.operands.first() {
+                    Some(&mr::Operand::IdRef(v)) => v,
+                    _ => return None,
+                };
+                let row_count = match column.operands.get(1) {
+                    Some(&mr::Operand::LiteralInt32(v)) => v,
+                    _ => return None,
+                };
+                let scalar = self.layout(scalar_id, false, None)?;
+                // A row-major matrix is laid out as an array of rows.
+                let (vector, count) = if row_major {
+                    (self.vector_layout(scalar, column_count), row_count)
+                } else {
+                    (self.vector_layout(scalar, row_count), column_count)
+                };
+                let stride = matrix_stride.unwrap_or_else(|| self.default_stride(vector));
+                Some(Layout {
+                    size: stride * count,
+                    align: self.array_alignment(vector),
+                })
+            }
+            spirv::Op::TypeArray => {
+                let element = self.layout(operand_id(0)?, row_major, matrix_stride)?;
+                let length = *self.constants.get(&operand_id(1)?)?;
+                let stride = self.array_strides
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_else(|| self.default_stride(element));
+                Some(Layout {
+                    size: stride * length,
+                    align: self.array_alignment(element),
+                })
+            }
+            spirv::Op::TypeRuntimeArray => {
+                let element = self.layout(operand_id(0)?, row_major, matrix_stride)?;
+                Some(Layout {
+                    size: 0,
+                    align: self.array_alignment(element),
+                })
+            }
+            spirv::Op::TypeStruct => {
+                let offsets = self.standard_offsets(id)?;
+                let mut layout = Layout { size: 0, align: 1 };
+                for (member, ty) in self.struct_members(id).into_iter().enumerate() {
+                    let l = self.member_layout(id, member as u32, ty)?;
+                    layout.align = layout.align.max(l.align);
+                    layout.size = layout.size.max(offsets[member] + l.size);
+                }
+                if self.rule == LayoutRule::Std140 {
+                    layout.align = round_up(layout.align, 16);
+                }
+                layout.size = round_up(layout.size, layout.align);
+                Some(layout)
+            }
+            _ => None,
+        }
+    }
+
+    fn member_layout(&self, struct_id: Word, member: u32, ty: Word) -> Option<Layout> {
+        let row_major = self.member_decoration(struct_id, member, spirv::Decoration::RowMajor)
+            .is_some();
+        let matrix_stride = self.member_literal(struct_id, member, spirv::Decoration::MatrixStride);
+        self.layout(ty, row_major, matrix_stride)
+    }
+
+    /// Computes the offsets of all members of the given struct, keeping
+    /// existing offsets as long as they are consistent.
+    fn standard_offsets(&self, struct_id: Word) -> Option<Vec<u32>> {
+        let mut offsets = vec![];
+        let mut cursor = 0;
+        for (member, ty) in self.struct_members(struct_id).into_iter().enumerate() {
+            let layout = self.member_layout(struct_id, member as u32, ty)?;
+            let expected = round_up(cursor, layout.align);
+            let offset = match self.member_literal(struct_id, member as u32, spirv::Decoration::Offset) {
+                Some(v) if v >= cursor && v % layout.align == 0 => v,
+                _ => expected,
+            };
+            offsets.push(offset);
+            cursor = offset + layout.size;
+        }
+        Some(offsets)
+    }
+
+    /// Returns all struct ids that need explicit layout: those decorated
+    /// as Block or BufferBlock, and structs nested within them.
+    fn explicit_layout_structs(&self, module: &mr::Module) -> Vec<Word> {
+        let mut worklist = vec![];
+        for inst in &module.annotations {
+            if let (spirv::Op::Decorate,
+                    &[mr::Operand::IdRef(target), mr::Operand::Decoration(d), ..]) =
+                (inst.class.opcode, &inst.operands[..])
+            {
+                if d == spirv::Decoration::Block || d == spirv::Decoration::BufferBlock {
+                    worklist.push(target);
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut result = vec![];
+        while let Some(id) = worklist.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let inst = match self.types.get(&id) {
+                Some(inst) => inst,
+                None => continue,
+            };
+            match inst.class.opcode {
+                spirv::Op::TypeStruct => {
+                    result.push(id);
+                    worklist.extend(self.struct_members(id));
+                }
+                spirv::Op::TypeArray | spirv::Op::TypeRuntimeArray => {
+                    if let Some(&mr::Operand::IdRef(element)) = inst.operands.first() {
+                        worklist.push(element);
+                    }
+                }
+                _ => {}
+            }
+        }
+        result.sort();
+        result
+    }
+}
+
+fn member_names(module: &mr::Module) -> HashMap<(Word, u32), String> {
+    let mut names = HashMap::new();
+    for inst in &module.debugs {
+        if let (spirv::Op::MemberName,
+                &[mr::Operand::IdRef(target), mr::Operand::LiteralInt32(member),
+                  mr::Operand::LiteralString(ref name)]) = (inst.class.opcode, &inst.operands[..])
+        {
+            names.insert((target, member), name.to_string());
+        }
+    }
+    names
+}
+
+/// Checks the Offset decorations of all members of explicitly laid out
+/// structs (those decorated as Block or BufferBlock, and structs nested
+/// within them) against the given layout `rule`.
+///
+/// A member's Offset is reported as inconsistent if it is not aligned to
+/// the member type's alignment or if it overlaps the previous member.
+/// Structs containing types whose layout cannot be computed are skipped.
+pub fn check_struct_offsets(module: &mr::Module, rule: LayoutRule) -> Vec<OffsetIssue> {
+    let context = LayoutContext::new(module, rule);
+    let names = member_names(module);
+    let mut issues = vec![];
+    for struct_id in context.explicit_layout_structs(module) {
+        let offsets = match context.standard_offsets(struct_id) {
+            Some(offsets) => offsets,
+            None => continue,
+        };
+        for (member, expected) in offsets.into_iter().enumerate() {
+            let member = member as u32;
+            let member_name = names.get(&(struct_id, member)).cloned();
+            match context.member_literal(struct_id, member, spirv::Decoration::Offset) {
+                None => issues.push(OffsetIssue::Missing {
+                    struct_id,
+                    member,
+                    member_name,
+                    expected,
+                }),
+                Some(offset) if offset != expected => issues.push(OffsetIssue::Inconsistent {
+                    struct_id,
+                    member,
+                    member_name,
+                    offset,
+                    expected,
+                }),
+                Some(_) => {}
+            }
+        }
+    }
+    issues
+}
+
+/// Fixes the Offset decorations of all members of explicitly laid out
+/// structs according to the given layout `rule`.
+///
+/// Missing Offset decorations are added and inconsistent ones are
+/// rewritten; consistent ones are left untouched, even if they differ
+/// from what the layout rule would have chosen. Returns the issues that
+/// were repaired.
+pub fn fix_struct_offsets(module: &mut mr::Module, rule: LayoutRule) -> Vec<OffsetIssue> {
+    let issues = check_struct_offsets(module, rule);
+    for issue in &issues {
+        match *issue {
+            OffsetIssue::Missing { struct_id, member, expected, .. } => {
+                module.annotations.push(mr::Instruction::new(
+                    spirv::Op::MemberDecorate,
+                    None,
+                    None,
+                    vec![
+                        mr::Operand::IdRef(struct_id),
+                        mr::Operand::LiteralInt32(member),
+                        mr::Operand::Decoration(spirv::Decoration::Offset),
+                        mr::Operand::LiteralInt32(expected),
+                    ],
+                ));
+            }
+            OffsetIssue::Inconsistent { struct_id, member, expected, .. } => {
+                for inst in &mut module.annotations {
+                    if inst.class.opcode == spirv::Op::MemberDecorate &&
+                        inst.operands.len() == 4 &&
+                        inst.operands[0] == mr::Operand::IdRef(struct_id) &&
+                        inst.operands[1] == mr::Operand::LiteralInt32(member) &&
+                        inst.operands[2] == mr::Operand::Decoration(spirv::Decoration::Offset)
+                    {
+                        inst.operands[3] = mr::Operand::LiteralInt32(expected);
+                    }
+                }
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use super::{check_struct_offsets, fix_struct_offsets, LayoutRule, OffsetIssue};
+
+    fn offset(module: &mr::Module, struct_id: u32, member: u32) -> Option<u32> {
+        module.annotations.iter().filter_map(|inst| match &inst.operands[..] {
+            &[mr::Operand::IdRef(s),
+              mr::Operand::LiteralInt32(m),
+              mr::Operand::Decoration(spirv::Decoration::Offset),
+              mr::Operand::LiteralInt32(o)] if s == struct_id && m == member => Some(o),
+            _ => None,
+        }).next()
+    }
+
+    /// Builds a uniform block `{ float a; vec3 b; float c; float d[2]; }`.
+    fn build_block(b: &mut mr::Builder) -> u32 {
+        let float = b.type_float(32);
+        let vec3 = b.type_vector(float, 3);
+        let uint = b.type_int(32, 0);
+        let two = b.constant_u32(uint, 2);
+        let array = b.type_array(float, two);
+        let block = b.type_struct(vec![float, vec3, float, array]);
+        b.decorate(block, spirv::Decoration::Block, vec![]);
+        b.member_name(block, 1, "b");
+        block
+    }
+
+    #[test]
+    fn test_fill_missing_std140() {
+        let mut b = mr::Builder::new();
+        let block = build_block(&mut b);
+        let mut m = b.module();
+
+        let issues = fix_struct_offsets(&mut m, LayoutRule::Std140);
+        assert_eq!(4, issues.len());
+        assert_eq!(
+            OffsetIssue::Missing {
+                struct_id: block,
+                member: 1,
+                member_name: Some("b".to_string()),
+                expected: 16,
+            },
+            issues[1]
+        );
+        assert_eq!(Some(0), offset(&m, block, 0));
+        assert_eq!(Some(16), offset(&m, block, 1));
+        assert_eq!(Some(28), offset(&m, block, 2));
+        assert_eq!(Some(32), offset(&m, block, 3));
+        assert!(check_struct_offsets(&m, LayoutRule::Std140).is_empty());
+    }
+
+    #[test]
+    fn test_fill_missing_std430() {
+        let mut b = mr::Builder::new();
+        let block = build_block(&mut b);
+        let mut m = b.module();
+
+        fix_struct_offsets(&mut m, LayoutRule::Std430);
+        assert_eq!(Some(0), offset(&m, block, 0));
+        assert_eq!(Some(16), offset(&m, block, 1));
+        assert_eq!(Some(28), offset(&m, block, 2));
+        assert_eq!(Some(32), offset(&m, block, 3));
+    }
+
+    #[test]
+    fn test_fix_inconsistent() {
+        let mut b = mr::Builder::new();
+        let block = build_block(&mut b);
+        b.member_decorate(block, 0, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(0)]);
+        // Misaligned vec3.
+        b.member_decorate(block, 1, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(4)]);
+        // Valid but not what std140 would choose; must be kept.
+        b.member_decorate(block, 2, spirv::Decoration::Offset, vec![mr::Operand::LiteralInt32(32)]);
+        let mut m = b.module();
+
+        let issues = fix_struct_offsets(&mut m, LayoutRule::Std140);
+        assert_eq!(2, issues.len());
+        assert_eq!(
+            OffsetIssue::Inconsistent {
+                struct_id: block,
+                member: 1,
+                member_name: Some("b".to_string()),
+                offset: 4,
+                expected: 16,
+            },
+            issues[0]
+        );
+        assert_eq!(Some(16), offset(&m, block, 1));
+        assert_eq!(Some(32), offset(&m, block, 2));
+        assert_eq!(Some(48), offset(&m, block, 3));
+    }
+
+    #[test]
+    fn test_ignore_non_block_structs() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        b.type_struct(vec![float, float]);
+        let m = b.module();
+        assert!(check_struct_offsets(&m, LayoutRule::Std140).is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use analysis::ControlFlowGraph;
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+
+/// Removes basic blocks unreachable from the entry block of each function.
+///
+/// Unreachable blocks still declared as the merge block of a reachable
+/// header are kept but reduced to a single OpUnreachable; unreachable blocks
+/// still declared as the continue target of a reachable loop header are
+/// reduced to a branch back to the header. OpPhi instructions are updated
+/// to no longer refer to removed predecessors.
+///
+/// Returns the number of blocks removed or reduced.
+pub fn remove_unreachable_blocks(module: &mut mr::Module) -> usize {
+    module.functions.iter_mut().map(remove_unreachable_blocks_in).sum()
+}
+
+fn remove_unreachable_blocks_in(function: &mut mr::Function) -> usize {
+    let cfg = ControlFlowGraph::new(function);
+    let reachable = cfg.reachable_blocks();
+    if reachable.iter().all(|&r| r) {
+        return 0;
+    }
+
+    // Merge blocks and continue targets (mapped to their loop headers)
+    // declared by reachable blocks.
+    let mut merges = HashSet::new();
+    let mut continues = HashMap::new();
+    for (index, block) in function.basic_blocks.iter().enumerate() {
+        if !reachable[index] {
+            continue;
+        }
+        match merge_instruction(block).map(|inst| (inst.class.opcode, &inst.operands[..])) {
+            Some((spirv::Op::SelectionMerge, &[mr::Operand::IdRef(merge), ..])) => {
+                merges.insert(merge);
+            }
+            Some((spirv::Op::LoopMerge, &[mr::Operand::IdRef(merge), mr::Operand::IdRef(cont), ..])) => {
+                merges.insert(merge);
+                continues.insert(cont, cfg.label(index));
+            }
+            _ => {}
+        }
+    }
+
+    let mut count = 0;
+    let blocks = ::std::mem::take(&mut function.basic_blocks);
+    for (index, mut block) in blocks.into_iter().enumerate() {
+        if reachable[index] {
+            function.basic_blocks.push(block);
+            continue;
+        }
+        count += 1;
+        let label = cfg.label(index);
+        if let Some(&header) = continues.get(&label) {
+            block.instructions = vec![mr::Instruction::new(
+                spirv::Op::Branch,
+                None,
+                None,
+                vec![mr::Operand::IdRef(header)],
+            )];
+            function.basic_blocks.push(block);
+        } else if merges.contains(&label) {
+            block.instructions = vec![unreachable()];
+            function.basic_blocks.push(block);
+        }
+    }
+    prune_phis(function);
+    count
+}
+
+/// Replaces the terminators of blocks that are provably unreachable at run
+/// time with OpUnreachable.
+///
+/// A block is provably unreachable if all of its successors are terminated
+/// by OpUnreachable: executing it inevitably leads to undefined behavior.
+/// This is applied iteratively until no more block changes. Blocks with a
+/// merge instruction are left intact to preserve the structured control
+/// flow. OpPhi instructions in former successors are updated accordingly.
+///
+/// Afterwards, the former successors may become unreachable from the entry
+/// block; use [`remove_unreachable_blocks`](fn.remove_unreachable_blocks.html)
+/// to remove them.
+///
+/// Returns the number of terminators replaced.
+pub fn convert_unreachable_terminators(module: &mut mr::Module) -> usize {
+    module.functions.iter_mut().map(convert_unreachable_terminators_in).sum()
+}
+
+fn convert_unreachable_terminators_in(function: &mut mr::Function) -> usize {
+    let mut count = 0;
+    loop {
+        let cfg = ControlFlowGraph::new(function);
+        let candidates: Vec<usize> = (0..cfg.len())
+            .filter(|&index| {
+                !cfg.ends_in_unreachable(index) && !cfg.successors(index).is_empty() &&
+                    cfg.successors(index).iter().all(|&s| cfg.ends_in_unreachable(s)) &&
+                    merge_instruction(&function.basic_blocks[index]).is_none()
+            })
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+        for index in candidates {
+            let block = &mut function.basic_blocks[index];
+            block.instructions.pop();
+            block.instructions.push(unreachable());
+            count += 1;
+        }
+    }
+    if count != 0 {
+        prune_phis(function);
+    }
+    count
+}
+
+fn unreachable() -> mr::Instruction {
+    mr::Instruction::new(spirv::Op::Unreachable, None, None, vec![])
+}
+
+/// Returns the OpSelectionMerge or OpLoopMerge instruction of the given block.
+pub(super) fn merge_instruction(block: &mr::BasicBlock) -> Option<&mr::Instruction> {
+    let len = block.instructions.len();
+    if len < 2 {
+        return None;
+    }
+    let inst = &block.instructions[len - 2];
+    match inst.class.opcode {
+        spirv::Op::SelectionMerge | spirv::Op::LoopMerge => Some(inst),
+        _ => None,
+    }
+}
+
+/// Removes incoming (value, parent) pairs from OpPhi instructions whose
+/// parent is no longer a predecessor.
+pub(super) fn prune_phis(function: &mut mr::Function) {
+    let cfg = ControlFlowGraph::new(function);
+    for (index, block) in function.basic_blocks.iter_mut().enumerate() {
+        let preds: HashSet<Word> = cfg.predecessors(index).iter().map(|&p| cfg.label(p)).collect();
+        for inst in &mut block.instructions {
+            if inst.class.opcode != spirv::Op::Phi {
+                continue;
+            }
+            let operands = ::std::mem::take(&mut inst.operands);
+            for pair in operands.chunks(2) {
+                if let [_, mr::Operand::IdRef(parent)] = *pair {
+                    if !preds.contains(&parent) {
+                        continue;
+                    }
+                }
+                inst.operands.extend_from_slice(pair);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use binary::Disassemble;
+    use super::{convert_unreachable_terminators, remove_unreachable_blocks};
+
+    fn disas_function(module: &mr::Module) -> Vec<String> {
+        module.functions[0]
+            .basic_blocks
+            .iter()
+            .flat_map(|bb| bb.label.iter().chain(&bb.instructions))
+            .map(|i| i.disassemble())
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_unreachable_blocks() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let int = b.type_int(32, 1);
+        let one = b.constant_u32(int, 1);
+        let two = b.constant_u32(int, 2);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.branch(11).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.branch(11).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.phi(int, Some(20), vec![(one, 10), (two, 12)]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(1, remove_unreachable_blocks(&mut m));
+        assert_eq!(
+            vec!["%10 = OpLabel", "OpBranch %11", "%11 = OpLabel", "%20 = OpPhi  %2  %3 %10", "OpReturn"],
+            disas_function(&m)
+        );
+        assert_eq!(0, remove_unreachable_blocks(&mut m));
+    }
+
+    #[test]
+    fn test_keep_unreachable_merge_and_continue() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.branch(11).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.loop_merge(12, 13, spirv::LoopControl::NONE, vec![]).unwrap();
+        b.branch(14).unwrap();
+        b.begin_basic_block(Some(14)).unwrap();
+        b.ret().unwrap();
+        b.begin_basic_block(Some(13)).unwrap();
+        b.ret().unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(2, remove_unreachable_blocks(&mut m));
+        assert_eq!(
+            vec![
+                "%10 = OpLabel",
+                "OpBranch %11",
+                "%11 = OpLabel",
+                "OpLoopMerge %12 %13 None",
+                "OpBranch %14",
+                "%14 = OpLabel",
+                "OpReturn",
+                "%13 = OpLabel",
+                "OpBranch %11",
+                "%12 = OpLabel",
+                "OpUnreachable",
+            ],
+            disas_function(&m)
+        );
+    }
+
+    #[test]
+    fn test_convert_unreachable_terminators() {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let boolean = b.type_bool();
+        let cond = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(Some(10)).unwrap();
+        b.selection_merge(13, spirv::SelectionControl::NONE).unwrap();
+        b.branch_conditional(cond, 11, 13, vec![]).unwrap();
+        b.begin_basic_block(Some(11)).unwrap();
+        b.branch(12).unwrap();
+        b.begin_basic_block(Some(12)).unwrap();
+        b.unreachable().unwrap();
+        b.begin_basic_block(Some(13)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let mut m = b.module();
+
+        assert_eq!(1, convert_unreachable_terminators(&mut m));
+        assert_eq!(
+            vec![
+                "%10 = OpLabel",
+                "OpSelectionMerge %13 None",
+                "OpBranchConditional %3 %11 %13",
+                "%11 = OpLabel",
+                "OpUnreachable",
+                "%12 = OpLabel",
+                "OpUnreachable",
+                "%13 = OpLabel",
+                "OpReturn",
+            ],
+            disas_function(&m)
+        );
+        // Block 12 is not reachable anymore.
+        assert_eq!(1, remove_unreachable_blocks(&mut m));
+        assert_eq!(0, convert_unreachable_terminators(&mut m));
+    }
+}
@@ -15,6 +15,8 @@
 #![allow(dead_code)]
 
 use std::mem;
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
 /// Splits the given u32 `value` into a vector of bytes in little-endian format.
 pub fn u32_to_bytes(val: u32) -> Vec<u8> {
@@ -68,6 +70,11 @@ pub fn f32_to_u32(value: f32) -> u32 {
     unsafe { mem::transmute::<f32, u32>(value) }
 }
 
+/// Gets the bit pattern of the given f64 `value` as an u64.
+pub fn f64_to_u64(value: f64) -> u64 {
+    value.to_bits()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,330 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use num::FromPrimitive;
+use spirv::Word;
+use std::collections::HashMap;
+use super::Diagnostic;
+
+/// Checks the operands of SPV_KHR_cooperative_matrix types and instructions.
+///
+/// * The Scope of OpTypeCooperativeMatrixKHR must be a constant naming a
+///   valid scope, its Use a constant naming a valid CooperativeMatrixUse,
+///   and its Rows and Columns integer constants.
+/// * The MemoryLayout of OpCooperativeMatrixLoadKHR and
+///   OpCooperativeMatrixStoreKHR must be a constant naming a valid
+///   CooperativeMatrixLayout.
+/// * The A, B, and C operands and the result of OpCooperativeMatrixMulAddKHR
+///   must be MatrixAKHR (MxK), MatrixBKHR (KxN), and MatrixAccumulatorKHR
+///   (MxN) matrices of the same scope.
+pub fn check_cooperative_matrices(module: &mr::Module) -> Vec<Diagnostic> {
+    let mut checker = Checker::new(module);
+    for inst in &module.types_global_values {
+        if inst.class.opcode == spirv::Op::TypeCooperativeMatrixKHR {
+            checker.check_type(inst);
+        }
+    }
+    let insts = module
+        .functions
+        .iter()
+        .flat_map(|f| &f.basic_blocks)
+        .flat_map(|bb| &bb.instructions);
+    for inst in insts {
+        match inst.class.opcode {
+            spirv::Op::CooperativeMatrixLoadKHR => checker.check_layout(inst, 1),
+            spirv::Op::CooperativeMatrixStoreKHR => checker.check_layout(inst, 2),
+            spirv::Op::CooperativeMatrixMulAddKHR => checker.check_mul_add(inst),
+            _ => {}
+        }
+    }
+    checker.diagnostics
+}
+
+/// The operands of an OpTypeCooperativeMatrixKHR.
+struct MatrixType {
+    scope: Word,
+    rows: Word,
+    columns: Word,
+    usage: Option<spirv::CooperativeMatrixUse>,
+}
+
+struct Checker<'a> {
+    defs: HashMap<Word, &'a mr::Instruction>,
+    /// The result type of every instruction in function bodies.
+    value_types: HashMap<Word, Word>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(module: &'a mr::Module) -> Checker<'a> {
+        let defs = module
+            .types_global_values
+            .iter()
+            .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+            .collect();
+        let value_types = module
+            .functions
+            .iter()
+            .flat_map(|f| {
+                let body = f.basic_blocks.iter().flat_map(|bb| &bb.instructions);
+                f.parameters.iter().chain(body)
+            })
+            .filter_map(|inst| match (inst.result_id, inst.result_type) {
+                (Some(id), Some(ty)) => Some((id, ty)),
+                _ => None,
+            })
+            .collect();
+        Checker { defs, value_types, diagnostics: vec![] }
+    }
+
+    fn error<T: Into<String>>(&mut self, id: Option<Word>, message: T) {
+        self.diagnostics.push(Diagnostic::error(id, message));
+    }
+
+    fn id_operand(inst: &mr::Instruction, index: usize) -> Option<Word> {
+        match inst.operands.get(index) {
+            Some(&mr::Operand::IdRef(id)) | Some(&mr::Operand::IdScope(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of `id` if it is an OpConstant of a 32-bit integer
+    /// type.
+    fn constant_value(&self, id: Word) -> Option<u32> {
+        let inst = self.defs.get(&id)?;
+        if inst.class.opcode != spirv::Op::Constant || !self.is_int_type(inst.result_type?) {
+            return None;
+        }
+        match inst.operands.first() {
+            Some(&mr::Operand::LiteralInt32(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns true if `id` is a (specialization) constant of an integer type.
+    fn is_int_constant(&self, id: Word) -> bool {
+        match self.defs.get(&id) {
+            Some(inst) => {
+                matches!(
+                    inst.class.opcode,
+                    spirv::Op::Constant | spirv::Op::SpecConstant | spirv::Op::SpecConstantOp
+                ) && inst.result_type.is_some_and(|ty| self.is_int_type(ty))
+            }
+            None => false,
+        }
+    }
+
+    fn is_int_type(&self, id: Word) -> bool {
+        self.defs.get(&id).is_some_and(|inst| inst.class.opcode == spirv::Op::TypeInt)
+    }
+
+    fn matrix_type(&self, id: Word) -> Option<MatrixType> {
+        let inst = self.defs.get(&id)?;
+        if inst.class.opcode != spirv::Op::TypeCooperativeMatrixKHR {
+            return None;
+        }
+        Some(MatrixType {
+            scope: Self::id_operand(inst, 1)?,
+            rows: Self::id_operand(inst, 2)?,
+            columns: Self::id_operand(inst, 3)?,
+            usage: Self::id_operand(inst, 4)
+                .and_then(|u| self.constant_value(u))
+                .and_then(spirv::CooperativeMatrixUse::from_u32),
+        })
+    }
+
+    /// Returns false if the two ids are known to hold different values.
+    fn same_value(&self, a: Word, b: Word) -> bool {
+        match (self.constant_value(a), self.constant_value(b)) {
+            (Some(x), Some(y)) => x == y,
+            // Specialization constants cannot be compared statically.
+            _ => true,
+        }
+    }
+
+    /// Returns the result type of the value used as operand `index`.
+    fn operand_type(&self, inst: &mr::Instruction, index: usize) -> Option<Word> {
+        Self::id_operand(inst, index).and_then(|v| self.value_types.get(&v).cloned())
+    }
+
+    fn check_type(&mut self, inst: &mr::Instruction) {
+        let id = inst.result_id;
+        match Self::id_operand(inst, 1).and_then(|s| self.constant_value(s)) {
+            Some(v) if spirv::Scope::from_u32(v).is_some() => {}
+            Some(v) => self.error(id, format!("cooperative matrix has invalid scope {}", v)),
+            None => self.error(id, "cooperative matrix scope must be an integer constant"),
+        }
+        for &(index, name) in &[(2, "rows"), (3, "columns")] {
+            if !Self::id_operand(inst, index).is_some_and(|c| self.is_int_constant(c)) {
+                self.error(id, format!("cooperative matrix {} must be an integer constant", name));
+            }
+        }
+        match Self::id_operand(inst, 4).and_then(|u| self.constant_value(u)) {
+            Some(v) if spirv::CooperativeMatrixUse::from_u32(v).is_some() => {}
+            Some(v) => self.error(id, format!("cooperative matrix has invalid use {}", v)),
+            None => self.error(id, "cooperative matrix use must be an integer constant"),
+        }
+    }
+
+    fn check_layout(&mut self, inst: &mr::Instruction, index: usize) {
+        let id = inst.result_id;
+        let opname = inst.class.opname;
+        match Self::id_operand(inst, index).and_then(|l| self.constant_value(l)) {
+            Some(v) if spirv::CooperativeMatrixLayout::from_u32(v).is_some() => {}
+            Some(v) => self.error(id, format!("Op{} has invalid memory layout {}", opname, v)),
+            None => self.error(
+                id,
+                format!("Op{} memory layout must be an integer constant", opname),
+            ),
+        }
+    }
+
+    fn check_mul_add(&mut self, inst: &mr::Instruction) {
+        use spirv::CooperativeMatrixUse::*;
+        let id = inst.result_id;
+        let operands = [
+            ("Result Type", inst.result_type, MatrixAccumulatorKHR),
+            ("A", self.operand_type(inst, 0), MatrixAKHR),
+            ("B", self.operand_type(inst, 1), MatrixBKHR),
+            ("C", self.operand_type(inst, 2), MatrixAccumulatorKHR),
+        ];
+        let mut matrices = vec![];
+        for &(name, ty, usage) in &operands {
+            match ty.and_then(|t| self.matrix_type(t)) {
+                Some(ref m) if m.usage != Some(usage) => self.error(
+                    id,
+                    format!("OpCooperativeMatrixMulAddKHR {} must have use {}", name, usage),
+                ),
+                Some(m) => matrices.push(m),
+                None => self.error(
+                    id,
+                    format!("OpCooperativeMatrixMulAddKHR {} must be a cooperative matrix", name),
+                ),
+            }
+        }
+        if let [ref r, ref a, ref b, ref c] = matrices[..] {
+            let same_scope = self.same_value(a.scope, b.scope) &&
+                self.same_value(a.scope, c.scope) &&
+                self.same_value(a.scope, r.scope);
+            if !same_scope {
+                self.error(id, "OpCooperativeMatrixMulAddKHR operands must have the same scope");
+            }
+            // A is MxK, B is KxN, and both C and the result are MxN.
+            let same_dims = self.same_value(a.rows, c.rows) &&
+                self.same_value(a.columns, b.rows) &&
+                self.same_value(b.columns, c.columns) &&
+                self.same_value(r.rows, c.rows) &&
+                self.same_value(r.columns, c.columns);
+            if !same_dims {
+                self.error(id, "OpCooperativeMatrixMulAddKHR operands have mismatched dimensions");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use validate::check_cooperative_matrices;
+
+    use spirv::CooperativeMatrixUse::*;
+
+    /// Builds a module multiplying matrices of the given uses and rows, with
+    /// the given scope and memory layout values.
+    fn build(uses: [spirv::CooperativeMatrixUse; 3], rows: [u32; 3], scope: u32, layout: u32)
+        -> mr::Module
+    {
+        let mut b = mr::Builder::new();
+        b.capability(spirv::Capability::CooperativeMatrixKHR);
+        let void = b.type_void();
+        let uint = b.type_int(32, 0);
+        let float = b.type_float(32);
+        let scope = b.constant_u32(uint, scope);
+        let layout = b.constant_u32(uint, layout);
+        let eight = b.constant_u32(uint, 8);
+        let types: Vec<_> = uses
+            .iter()
+            .zip(rows.iter())
+            .map(|(&u, &r)| {
+                let u = b.constant_u32(uint, u as u32);
+                let r = b.constant_u32(uint, r);
+                b.type_cooperative_matrix_khr(float, scope, r, eight, u)
+            })
+            .collect();
+        let array = b.type_array(float, eight);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Workgroup, array);
+        let voidf = b.type_function(void, vec![]);
+        let var = b.variable(ptr, None, spirv::StorageClass::Workgroup, None);
+        b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let m: Vec<_> = types
+            .iter()
+            .map(|&ty| b.cooperative_matrix_load_khr(ty, None, var, layout, None, None, vec![]))
+            .map(Result::unwrap)
+            .collect();
+        let d = b.cooperative_matrix_mul_add_khr(types[2], None, m[0], m[1], m[2], None).unwrap();
+        b.cooperative_matrix_store_khr(var, d, layout, None, None, vec![]).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.module()
+    }
+
+    fn messages(module: &mr::Module) -> Vec<String> {
+        check_cooperative_matrices(module).iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let uses = [MatrixAKHR, MatrixBKHR, MatrixAccumulatorKHR];
+        assert!(messages(&build(uses, [8, 8, 8], 3, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_scope_and_layout() {
+        let uses = [MatrixAKHR, MatrixBKHR, MatrixAccumulatorKHR];
+        assert_eq!(
+            vec![
+                "error: %9: cooperative matrix has invalid scope 42",
+                "error: %12: cooperative matrix has invalid scope 42",
+                "error: %15: cooperative matrix has invalid scope 42",
+                "error: %22: OpCooperativeMatrixLoadKHR has invalid memory layout 7",
+                "error: %23: OpCooperativeMatrixLoadKHR has invalid memory layout 7",
+                "error: %24: OpCooperativeMatrixLoadKHR has invalid memory layout 7",
+                "error: OpCooperativeMatrixStoreKHR has invalid memory layout 7",
+            ],
+            messages(&build(uses, [8, 8, 8], 42, 7))
+        );
+    }
+
+    #[test]
+    fn test_mul_add_operands() {
+        let uses = [MatrixBKHR, MatrixAKHR, MatrixAccumulatorKHR];
+        assert_eq!(
+            vec![
+                "error: %25: OpCooperativeMatrixMulAddKHR A must have use MatrixAKHR",
+                "error: %25: OpCooperativeMatrixMulAddKHR B must have use MatrixBKHR",
+            ],
+            messages(&build(uses, [8, 8, 8], 3, 0))
+        );
+        let uses = [MatrixAKHR, MatrixBKHR, MatrixAccumulatorKHR];
+        assert_eq!(
+            vec!["error: %25: OpCooperativeMatrixMulAddKHR operands have mismatched dimensions"],
+            messages(&build(uses, [16, 8, 8], 3, 0))
+        );
+    }
+}
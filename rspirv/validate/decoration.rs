@@ -0,0 +1,123 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use std::collections::HashSet;
+use super::Diagnostic;
+
+/// The first SPIR-V version deprecating decoration groups.
+const DECORATION_GROUP_DEPRECATED_VERSION: (u8, u8) = (1, 6);
+
+/// Checks the usage of OpDecorationGroup, OpGroupDecorate, and
+/// OpGroupMemberDecorate.
+///
+/// Using a non-group id as a decoration group, or a decoration group as
+/// the target of OpGroupDecorate or OpGroupMemberDecorate, is an error.
+/// Using decoration groups at all is a warning for SPIR-V versions where
+/// they are deprecated.
+pub fn check_decoration_groups(module: &mr::Module) -> Vec<Diagnostic> {
+    let groups: HashSet<spirv::Word> = module.annotations
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::DecorationGroup)
+        .filter_map(|inst| inst.result_id)
+        .collect();
+
+    let mut diagnostics = vec![];
+    let (major, minor) = module.version_rules().version();
+    if !groups.is_empty() && (major, minor) >= DECORATION_GROUP_DEPRECATED_VERSION {
+        for &group in &groups {
+            diagnostics.push(Diagnostic::warning(
+                Some(group),
+                format!("decoration groups are deprecated in SPIR-V {}.{}", major, minor),
+            ));
+        }
+    }
+
+    for inst in &module.annotations {
+        let opcode = inst.class.opcode;
+        if opcode != spirv::Op::GroupDecorate && opcode != spirv::Op::GroupMemberDecorate {
+            continue;
+        }
+        match inst.operands.first() {
+            Some(&mr::Operand::IdRef(id)) if groups.contains(&id) => {}
+            Some(&mr::Operand::IdRef(id)) => diagnostics.push(Diagnostic::error(
+                Some(id),
+                format!("Op{} uses an id that is not an OpDecorationGroup", inst.class.opname),
+            )),
+            _ => diagnostics.push(Diagnostic::error(
+                None,
+                format!("Op{} is missing its decoration group", inst.class.opname),
+            )),
+        }
+        // For OpGroupMemberDecorate, every other operand is a member index.
+        let step = if opcode == spirv::Op::GroupDecorate { 1 } else { 2 };
+        for target in inst.operands.iter().skip(1).step_by(step) {
+            if let mr::Operand::IdRef(id) = *target {
+                if groups.contains(&id) {
+                    diagnostics.push(Diagnostic::error(
+                        Some(id),
+                        format!("Op{} targets a decoration group", inst.class.opname),
+                    ));
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use validate::{check_decoration_groups, Severity};
+
+    #[test]
+    fn test_valid_groups() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 3);
+        let group = b.decoration_group();
+        b.decorate(group, spirv::Decoration::Flat, vec![]);
+        b.group_decorate(group, vec![10, 11]);
+        b.group_member_decorate(group, vec![(12, 0)]);
+        assert!(check_decoration_groups(&b.module()).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 3);
+        let group = b.decoration_group();
+        b.group_decorate(42, vec![10]);
+        b.group_decorate(group, vec![group]);
+        let diagnostics = check_decoration_groups(&b.module());
+        assert_eq!(2, diagnostics.len());
+        assert_eq!(Severity::Error, diagnostics[0].severity);
+        assert_eq!(Some(42), diagnostics[0].id);
+        assert_eq!(Some(group), diagnostics[1].id);
+    }
+
+    #[test]
+    fn test_deprecated_groups() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 6);
+        let group = b.decoration_group();
+        b.group_decorate(group, vec![10]);
+        let diagnostics = check_decoration_groups(&b.module());
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(Severity::Warning, diagnostics[0].severity);
+        assert_eq!(Some(group), diagnostics[0].id);
+    }
+}
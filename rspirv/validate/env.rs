@@ -0,0 +1,410 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::{HashMap, HashSet};
+use super::Diagnostic;
+
+/// Checks the module against the rules specific to the target environment
+/// `env`, beyond the version, capability, and memory model restrictions
+/// checked by [`check_target_env()`](fn.check_target_env.html).
+///
+/// * Vulkan only allows OpKill in functions called by Fragment entry points.
+/// * Vulkan and OpenGL require variables in the Uniform, StorageBuffer, and
+///   PushConstant storage classes to be blocks decorated with Block or
+///   BufferBlock, with an explicit layout: an Offset on every member, a
+///   MatrixStride on every matrix member, and an ArrayStride on every array.
+/// * OpenCL only allows Kernel entry points, and the OpenCL memory model with
+///   physical addressing.
+pub fn check_env_rules(module: &mr::Module, env: mr::TargetEnv) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    if env.is_vulkan() {
+        check_kill(module, env, &mut diagnostics);
+    }
+    if env.is_shader_only() {
+        ExplicitLayout::new(module, env, &mut diagnostics).check();
+    }
+    if env.is_opencl() {
+        check_kernels(module, env, &mut diagnostics);
+    }
+    diagnostics
+}
+
+/// Reports functions containing OpKill which are called by non-fragment
+/// entry points.
+fn check_kill(module: &mr::Module, env: mr::TargetEnv, diagnostics: &mut Vec<Diagnostic>) {
+    let functions: HashMap<Word, &mr::Function> = module
+        .functions
+        .iter()
+        .filter_map(|f| f.def.as_ref().and_then(|d| d.result_id).map(|id| (id, f)))
+        .collect();
+    let mut reported = HashSet::new();
+    for inst in &module.entry_points {
+        let (model, entry) = match (inst.operands.first(), inst.operands.get(1)) {
+            (Some(&mr::Operand::ExecutionModel(m)), Some(&mr::Operand::IdRef(f))) => (m, f),
+            _ => continue,
+        };
+        if model == spirv::ExecutionModel::Fragment {
+            continue;
+        }
+        let mut visited = HashSet::new();
+        let mut stack = vec![entry];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let function = match functions.get(&id) {
+                Some(f) => f,
+                None => continue,
+            };
+            for inst in function.basic_blocks.iter().flat_map(|bb| &bb.instructions) {
+                match inst.class.opcode {
+                    spirv::Op::Kill if reported.insert(id) => {
+                        diagnostics.push(Diagnostic::error(
+                            Some(id),
+                            format!("{}: OpKill is not allowed in {} entry points", env, model),
+                        ));
+                    }
+                    spirv::Op::FunctionCall => {
+                        if let Some(&mr::Operand::IdRef(callee)) = inst.operands.first() {
+                            stack.push(callee);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Checker for the explicit layout of blocks.
+struct ExplicitLayout<'a> {
+    module: &'a mr::Module,
+    env: mr::TargetEnv,
+    defs: HashMap<Word, &'a mr::Instruction>,
+    decorations: HashSet<(Word, spirv::Decoration)>,
+    member_decorations: HashSet<(Word, u32, spirv::Decoration)>,
+    checked: HashSet<Word>,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl<'a> ExplicitLayout<'a> {
+    fn new(
+        module: &'a mr::Module,
+        env: mr::TargetEnv,
+        diagnostics: &'a mut Vec<Diagnostic>,
+    ) -> ExplicitLayout<'a> {
+        let defs = module
+            .types_global_values
+            .iter()
+            .filter_map(|inst| inst.result_id.map(|id| (id, inst)))
+            .collect();
+        let mut decorations = HashSet::new();
+        let mut member_decorations = HashSet::new();
+        for inst in &module.annotations {
+            let ops = &inst.operands;
+            match (inst.class.opcode, ops.first(), ops.get(1), ops.get(2)) {
+                (
+                    spirv::Op::Decorate,
+                    Some(&mr::Operand::IdRef(target)),
+                    Some(&mr::Operand::Decoration(d)),
+                    _,
+                ) => {
+                    decorations.insert((target, d));
+                }
+                (
+                    spirv::Op::MemberDecorate,
+                    Some(&mr::Operand::IdRef(target)),
+                    Some(&mr::Operand::LiteralInt32(member)),
+                    Some(&mr::Operand::Decoration(d)),
+                ) => {
+                    member_decorations.insert((target, member, d));
+                }
+                _ => {}
+            }
+        }
+        ExplicitLayout {
+            module,
+            env,
+            defs,
+            decorations,
+            member_decorations,
+            checked: HashSet::new(),
+            diagnostics,
+        }
+    }
+
+    fn check(&mut self) {
+        let module = self.module;
+        for inst in &module.types_global_values {
+            if inst.class.opcode != spirv::Op::Variable {
+                continue;
+            }
+            let sc = match inst.operands.first() {
+                Some(&mr::Operand::StorageClass(sc)) => sc,
+                _ => continue,
+            };
+            if !matches!(
+                sc,
+                spirv::StorageClass::Uniform |
+                    spirv::StorageClass::StorageBuffer |
+                    spirv::StorageClass::PushConstant
+            ) {
+                continue;
+            }
+            let pointee = match inst.result_type.and_then(|t| self.operand_id(t, 1)) {
+                Some(id) => id,
+                None => continue,
+            };
+            // Arrays of blocks are arrays of descriptors, which have no
+            // layout themselves.
+            let block = self.strip_arrays(pointee);
+            let is_block = self.opcode(block) == Some(spirv::Op::TypeStruct) &&
+                (self.decorations.contains(&(block, spirv::Decoration::Block)) ||
+                    self.decorations.contains(&(block, spirv::Decoration::BufferBlock)));
+            if is_block {
+                self.check_type(block);
+            } else {
+                self.error(
+                    inst.result_id,
+                    format!("variable of storage class {} must be a Block or BufferBlock", sc),
+                );
+            }
+        }
+    }
+
+    fn check_type(&mut self, id: Word) {
+        match self.opcode(id) {
+            Some(spirv::Op::TypeStruct) => self.check_struct(id),
+            Some(spirv::Op::TypeArray) | Some(spirv::Op::TypeRuntimeArray) => {
+                if self.checked.insert(id) &&
+                    !self.decorations.contains(&(id, spirv::Decoration::ArrayStride))
+                {
+                    self.error(Some(id), "array has no ArrayStride decoration".to_string());
+                }
+                if let Some(element) = self.operand_id(id, 0) {
+                    self.check_type(element);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_struct(&mut self, id: Word) {
+        if !self.checked.insert(id) {
+            return;
+        }
+        let members: Vec<Word> = self.defs[&id]
+            .operands
+            .iter()
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(member) => Some(member),
+                _ => None,
+            })
+            .collect();
+        for (index, &member) in members.iter().enumerate() {
+            let index = index as u32;
+            if !self.member_decorations.contains(&(id, index, spirv::Decoration::Offset)) {
+                self.error(Some(id), format!("member {} has no Offset decoration", index));
+            }
+            let is_matrix = self.opcode(self.strip_arrays(member)) == Some(spirv::Op::TypeMatrix);
+            if is_matrix &&
+                !self.member_decorations.contains(&(id, index, spirv::Decoration::MatrixStride))
+            {
+                self.error(Some(id), format!("member {} has no MatrixStride decoration", index));
+            }
+            self.check_type(member);
+        }
+    }
+
+    fn error(&mut self, id: Option<Word>, message: String) {
+        let message = format!("{}: {}", self.env, message);
+        self.diagnostics.push(Diagnostic::error(id, message));
+    }
+
+    fn opcode(&self, id: Word) -> Option<spirv::Op> {
+        self.defs.get(&id).map(|inst| inst.class.opcode)
+    }
+
+    /// Returns the id operand at `index` of the instruction defining `id`.
+    fn operand_id(&self, id: Word, index: usize) -> Option<Word> {
+        match self.defs.get(&id).and_then(|inst| inst.operands.get(index)) {
+            Some(&mr::Operand::IdRef(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the innermost element type if `id` is an array type, and
+    /// `id` itself otherwise.
+    fn strip_arrays(&self, mut id: Word) -> Word {
+        while let Some(spirv::Op::TypeArray) | Some(spirv::Op::TypeRuntimeArray) = self.opcode(id) {
+            match self.operand_id(id, 0) {
+                Some(element) => id = element,
+                None => break,
+            }
+        }
+        id
+    }
+}
+
+/// Reports non-kernel entry points and memory models other than OpenCL.
+fn check_kernels(module: &mr::Module, env: mr::TargetEnv, diagnostics: &mut Vec<Diagnostic>) {
+    for inst in &module.entry_points {
+        let ops = &inst.operands;
+        if let (Some(&mr::Operand::ExecutionModel(model)), Some(&mr::Operand::IdRef(f))) =
+            (ops.first(), ops.get(1))
+        {
+            if model != spirv::ExecutionModel::Kernel {
+                diagnostics.push(Diagnostic::error(
+                    Some(f),
+                    format!("{}: execution model {} is not allowed", env, model),
+                ));
+            }
+        }
+    }
+    if let Some(inst) = module.memory_model.as_ref() {
+        if let (Some(&mr::Operand::AddressingModel(a)), Some(&mr::Operand::MemoryModel(m))) =
+            (inst.operands.first(), inst.operands.get(1))
+        {
+            let physical = matches!(
+                a,
+                spirv::AddressingModel::Physical32 | spirv::AddressingModel::Physical64
+            );
+            if !physical || m != spirv::MemoryModel::OpenCL {
+                diagnostics.push(Diagnostic::error(
+                    None,
+                    format!("{}: memory model {} {} is not allowed", env, a, m),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use validate::{check_env_rules, validate_for_env};
+
+    fn messages(module: &mr::Module, env: mr::TargetEnv) -> Vec<String> {
+        check_env_rules(module, env).iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_kill() {
+        let build = |model: spirv::ExecutionModel| {
+            let mut b = mr::Builder::for_vulkan_1_0();
+            let void = b.type_void();
+            let voidf = b.type_function(void, vec![]);
+            let discard = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf)
+                .unwrap();
+            b.begin_basic_block(None).unwrap();
+            b.kill().unwrap();
+            b.end_function().unwrap();
+            let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf)
+                .unwrap();
+            b.begin_basic_block(None).unwrap();
+            b.function_call(void, None, discard, vec![]).unwrap();
+            b.ret().unwrap();
+            b.end_function().unwrap();
+            b.entry_point(model, main, "main", vec![]);
+            b.module()
+        };
+        let env = mr::TargetEnv::Vulkan1_0;
+        assert!(messages(&build(spirv::ExecutionModel::Fragment), env).is_empty());
+        let m = build(spirv::ExecutionModel::Vertex);
+        assert_eq!(
+            vec!["error: %3: Vulkan 1.0: OpKill is not allowed in Vertex entry points"],
+            messages(&m, env)
+        );
+        // The rule is specific to Vulkan.
+        assert!(messages(&m, mr::TargetEnv::Universal1_0).is_empty());
+    }
+
+    #[test]
+    fn test_explicit_layout() {
+        let build = |decorate: bool| {
+            let mut b = mr::Builder::for_opengl_4_5();
+            let float = b.type_float(32);
+            let vec4 = b.type_vector(float, 4);
+            let mat4 = b.type_matrix(vec4, 4);
+            let uint = b.type_int(32, 0);
+            let four = b.constant_u32(uint, 4);
+            let array = b.type_array(vec4, four);
+            let block = b.type_struct(vec![mat4, array]);
+            let ptr = b.type_pointer(None, spirv::StorageClass::Uniform, block);
+            b.variable(ptr, None, spirv::StorageClass::Uniform, None);
+            let ptr = b.type_pointer(None, spirv::StorageClass::PushConstant, float);
+            b.variable(ptr, None, spirv::StorageClass::PushConstant, None);
+            b.decorate(block, spirv::Decoration::Block, vec![]);
+            let offset = vec![mr::Operand::LiteralInt32(0)];
+            b.member_decorate(block, 0, spirv::Decoration::Offset, offset);
+            if decorate {
+                let offset = vec![mr::Operand::LiteralInt32(64)];
+                b.member_decorate(block, 1, spirv::Decoration::Offset, offset);
+                let stride = vec![mr::Operand::LiteralInt32(16)];
+                b.member_decorate(block, 0, spirv::Decoration::MatrixStride, stride.clone());
+                b.decorate(array, spirv::Decoration::ArrayStride, stride);
+            }
+            b.module()
+        };
+        let env = mr::TargetEnv::OpenGL4_5;
+        assert_eq!(
+            vec![
+                "error: %7: OpenGL 4.5: member 0 has no MatrixStride decoration",
+                "error: %7: OpenGL 4.5: member 1 has no Offset decoration",
+                "error: %6: OpenGL 4.5: array has no ArrayStride decoration",
+                "error: %11: OpenGL 4.5: variable of storage class PushConstant must be a \
+                 Block or BufferBlock",
+            ],
+            messages(&build(false), env)
+        );
+        assert_eq!(1, messages(&build(true), env).len());
+        assert!(messages(&build(false), mr::TargetEnv::Universal1_0).is_empty());
+    }
+
+    #[test]
+    fn test_opencl() {
+        let mut b = mr::Builder::for_target_env(mr::TargetEnv::OpenCL2_1);
+        let void = b.type_void();
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Kernel, main, "main", vec![]);
+        let m = b.module();
+        assert!(validate_for_env(&m, mr::TargetEnv::OpenCL2_1).is_empty());
+
+        let mut b = mr::Builder::from_module(m);
+        b.capability(spirv::Capability::Shader);
+        b.entry_point(spirv::ExecutionModel::GLCompute, main, "compute", vec![]);
+        b.memory_model(spirv::AddressingModel::Logical, spirv::MemoryModel::GLSL450);
+        let messages: Vec<String> = validate_for_env(&b.module(), mr::TargetEnv::OpenCL2_1)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "error: OpenCL 2.1: capability Shader is not allowed",
+                "error: %3: OpenCL 2.1: execution model GLCompute is not allowed",
+                "error: OpenCL 2.1: memory model Logical GLSL450 is not allowed",
+            ],
+            messages
+        );
+    }
+}
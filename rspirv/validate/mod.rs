@@ -0,0 +1,113 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation of SPIR-V modules in data representation.
+//!
+//! The validator is far from complete; it currently only performs a few
+//! checks that are cheap to do on the data representation. Each check
+//! produces [`Diagnostic`](struct.Diagnostic.html)s instead of stopping at
+//! the first problem, so that tools can report all of them at once.
+
+use mr;
+use spirv;
+
+use std::fmt;
+
+pub use self::cooperative_matrix::check_cooperative_matrices;
+pub use self::decoration::check_decoration_groups;
+pub use self::env::check_env_rules;
+pub use self::stage_link::{check_stage_link, StageLinkMismatch};
+pub use self::version::{check_target_env, check_version_rules};
+
+mod cooperative_matrix;
+mod decoration;
+mod env;
+mod stage_link;
+mod version;
+
+/// The severity of a validation diagnostic.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The module is valid, but uses deprecated or discouraged features.
+    Warning,
+    /// The module is invalid.
+    Error,
+}
+
+/// A problem found by the validator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The severity of this diagnostic.
+    pub severity: Severity,
+    /// The id this diagnostic is about, if any.
+    pub id: Option<spirv::Word>,
+    /// The human readable description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Creates a new error diagnostic.
+    pub fn error<T: Into<String>>(id: Option<spirv::Word>, message: T) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            id,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a new warning diagnostic.
+    pub fn warning<T: Into<String>>(id: Option<spirv::Word>, message: T) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            id,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self.id {
+            Some(id) => write!(f, "{}: %{}: {}", severity, id, self.message),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// Validates the given `module` and returns all problems found.
+pub fn validate(module: &mr::Module) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    diagnostics.extend(check_decoration_groups(module));
+    diagnostics.extend(check_version_rules(module));
+    diagnostics.extend(check_cooperative_matrices(module));
+    diagnostics
+}
+
+/// Validates the given `module` for the target environment `env` and
+/// returns all problems found.
+///
+/// Besides the checks of [`validate()`](fn.validate.html), the module is
+/// checked to fit `env` with [`check_target_env()`](fn.check_target_env.html)
+/// and against the rules specific to `env` with
+/// [`check_env_rules()`](fn.check_env_rules.html).
+pub fn validate_for_env(module: &mr::Module, env: mr::TargetEnv) -> Vec<Diagnostic> {
+    let mut diagnostics = validate(module);
+    diagnostics.extend(check_target_env(module, env));
+    diagnostics.extend(check_env_rules(module, env));
+    diagnostics
+}
@@ -0,0 +1,502 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use spirv::Word;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Decorations which must agree between the output of one stage and the
+/// input of the next.
+const INTERPOLATION: &[spirv::Decoration] = &[
+    spirv::Decoration::Flat,
+    spirv::Decoration::NoPerspective,
+    spirv::Decoration::Centroid,
+    spirv::Decoration::Sample,
+    spirv::Decoration::Patch,
+];
+
+/// A mismatch between the outputs of a stage and the inputs of the next.
+///
+/// Interface variables are identified by their (location, component).
+#[derive(Clone, Debug, PartialEq)]
+pub enum StageLinkMismatch {
+    /// The consumer reads an input the producer does not write.
+    MissingOutput { location: u32, component: u32 },
+    /// The output and input have different types.
+    Type {
+        location: u32,
+        component: u32,
+        output: String,
+        input: String,
+    },
+    /// The output has fewer components than the input.
+    ComponentCount {
+        location: u32,
+        component: u32,
+        output: u32,
+        input: u32,
+    },
+    /// The output and input have different interpolation decorations.
+    Interpolation {
+        location: u32,
+        component: u32,
+        output: Vec<spirv::Decoration>,
+        input: Vec<spirv::Decoration>,
+    },
+}
+
+impl fmt::Display for StageLinkMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StageLinkMismatch::MissingOutput { location, component } => write!(
+                f,
+                "input at location {} component {} has no matching output",
+                location, component
+            ),
+            StageLinkMismatch::Type { location, component, ref output, ref input } => write!(
+                f,
+                "location {} component {}: output type {} does not match input type {}",
+                location, component, output, input
+            ),
+            StageLinkMismatch::ComponentCount { location, component, output, input } => write!(
+                f,
+                "location {} component {}: output has {} components but input has {}",
+                location, component, output, input
+            ),
+            StageLinkMismatch::Interpolation { location, component, ref output, ref input } => {
+                write!(
+                    f,
+                    "location {} component {}: output decorations {:?} do not match input \
+                     decorations {:?}",
+                    location, component, output, input
+                )
+            }
+        }
+    }
+}
+
+/// A user-defined interface variable.
+struct Interface {
+    location: u32,
+    component: u32,
+    /// The type of the variable, with the per-vertex array level stripped.
+    ty: Word,
+    interpolation: Vec<spirv::Decoration>,
+}
+
+/// Cached information about one of the linked modules.
+struct Stage<'a> {
+    types: HashMap<Word, &'a mr::Instruction>,
+    constants: HashMap<Word, u32>,
+}
+
+impl<'a> Stage<'a> {
+    fn new(module: &'a mr::Module) -> Stage<'a> {
+        let mut types = HashMap::new();
+        let mut constants = HashMap::new();
+        for inst in &module.types_global_values {
+            if let Some(id) = inst.result_id {
+                match (inst.class.opcode, inst.operands.first()) {
+                    (spirv::Op::Constant, Some(&mr::Operand::LiteralInt32(v))) => {
+                        constants.insert(id, v);
+                    }
+                    _ => {
+                        types.insert(id, inst);
+                    }
+                }
+            }
+        }
+        Stage { types, constants }
+    }
+
+    /// Returns a description of the given type independent of ids.
+    fn describe(&self, ty: Word) -> String {
+        let inst = match self.types.get(&ty) {
+            Some(inst) => inst,
+            None => return format!("%{}", ty),
+        };
+        let id = |index: usize| match inst.operands.get(index) {
+            Some(&mr::Operand::IdRef(id)) => id,
+            _ => 0,
+        };
+        let literal = |index: usize| match inst.operands.get(index) {
+            Some(&mr::Operand::LiteralInt32(v)) => v,
+            _ => 0,
+        };
+        match inst.class.opcode {
+            spirv::Op::TypeBool => "bool".to_string(),
+            spirv::Op::TypeInt if literal(1) == 1 => format!("i{}", literal(0)),
+            spirv::Op::TypeInt => format!("u{}", literal(0)),
+            spirv::Op::TypeFloat => format!("f{}", literal(0)),
+            spirv::Op::TypeVector => format!("vec{}<{}>", literal(1), self.describe(id(0))),
+            spirv::Op::TypeMatrix => format!("mat{}<{}>", literal(1), self.describe(id(0))),
+            spirv::Op::TypeArray => {
+                let length = self.constants.get(&id(1)).map_or("?".to_string(), |l| l.to_string());
+                format!("[{}; {}]", self.describe(id(0)), length)
+            }
+            spirv::Op::TypeStruct => {
+                let members: Vec<String> = inst.operands
+                    .iter()
+                    .filter_map(|operand| match *operand {
+                        mr::Operand::IdRef(id) => Some(self.describe(id)),
+                        _ => None,
+                    })
+                    .collect();
+                format!("struct {{ {} }}", members.join(", "))
+            }
+            _ => format!("Op{}", inst.class.opname),
+        }
+    }
+
+    /// Returns the component type and count of the given scalar or vector
+    /// type.
+    fn components(&self, ty: Word) -> Option<(String, u32)> {
+        let inst = self.types.get(&ty)?;
+        match (inst.class.opcode, &inst.operands[..]) {
+            (spirv::Op::TypeVector,
+             &[mr::Operand::IdRef(component), mr::Operand::LiteralInt32(count)]) => {
+                Some((self.describe(component), count))
+            }
+            (spirv::Op::TypeBool, _) | (spirv::Op::TypeInt, _) | (spirv::Op::TypeFloat, _) => {
+                Some((self.describe(ty), 1))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the element type if the given type is an array.
+    fn element(&self, ty: Word) -> Option<Word> {
+        match self.types.get(&ty) {
+            Some(inst) if inst.class.opcode == spirv::Op::TypeArray => {
+                match inst.operands.first() {
+                    Some(&mr::Operand::IdRef(element)) => Some(element),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the user-defined interface variables of the given storage class,
+/// keyed by (location, component).
+fn interfaces(
+    module: &mr::Module,
+    stage: &Stage,
+    storage_class: spirv::StorageClass,
+    arrayed: bool,
+) -> HashMap<(u32, u32), Interface> {
+    let mut decorations: HashMap<Word, Vec<(spirv::Decoration, Option<u32>)>> = HashMap::new();
+    for inst in &module.annotations {
+        if inst.class.opcode != spirv::Op::Decorate {
+            continue;
+        }
+        if let [mr::Operand::IdRef(target), mr::Operand::Decoration(decoration), ref rest @ ..] =
+            inst.operands[..]
+        {
+            let value = match rest.first() {
+                Some(&mr::Operand::LiteralInt32(v)) => Some(v),
+                _ => None,
+            };
+            decorations.entry(target).or_default().push((decoration, value));
+        }
+    }
+
+    let mut result = HashMap::new();
+    for inst in &module.types_global_values {
+        if inst.class.opcode != spirv::Op::Variable ||
+            inst.operands.first() != Some(&mr::Operand::StorageClass(storage_class))
+        {
+            continue;
+        }
+        let (var, pointer) = match (inst.result_id, inst.result_type) {
+            (Some(var), Some(pointer)) => (var, pointer),
+            _ => continue,
+        };
+        let decorations = match decorations.get(&var) {
+            Some(decorations) => decorations,
+            None => continue,
+        };
+        let find = |wanted: spirv::Decoration| {
+            decorations.iter().find(|&&(d, _)| d == wanted).and_then(|&(_, v)| v)
+        };
+        // Built-ins are matched by the built-in itself, not by location.
+        let location = match find(spirv::Decoration::Location) {
+            Some(location) => location,
+            None => continue,
+        };
+        let component = find(spirv::Decoration::Component).unwrap_or(0);
+        let interpolation: Vec<spirv::Decoration> = INTERPOLATION
+            .iter()
+            .cloned()
+            .filter(|&i| decorations.iter().any(|&(d, _)| d == i))
+            .collect();
+
+        let mut ty = match stage.types.get(&pointer).map(|p| &p.operands[..]) {
+            Some(&[_, mr::Operand::IdRef(pointee)]) => pointee,
+            _ => continue,
+        };
+        if arrayed && !interpolation.contains(&spirv::Decoration::Patch) {
+            ty = stage.element(ty).unwrap_or(ty);
+        }
+        result.insert(
+            (location, component),
+            Interface {
+                location,
+                component,
+                ty,
+                interpolation,
+            },
+        );
+    }
+    result
+}
+
+/// Returns true if any entry point of the given `module` uses one of the
+/// given execution models.
+fn has_execution_model(module: &mr::Module, models: &[spirv::ExecutionModel]) -> bool {
+    module.entry_points.iter().any(|inst| match inst.operands.first() {
+        Some(&mr::Operand::ExecutionModel(model)) => models.contains(&model),
+        _ => false,
+    })
+}
+
+/// Checks that the outputs of the `producer` stage match the inputs of the
+/// `consumer` stage.
+///
+/// Each input of the consumer decorated with a Location must have an
+/// output in the producer with the same Location and Component, the same
+/// type (an output vector may have more components than the input), and
+/// the same interpolation decorations. Per-vertex arrays of tessellation
+/// and geometry stages are compared by their element types. Built-in
+/// variables and outputs without matching input are ignored.
+///
+/// Returns all mismatches found, ordered by location and component.
+pub fn check_stage_link(producer: &mr::Module, consumer: &mr::Module) -> Vec<StageLinkMismatch> {
+    use spirv::ExecutionModel::{Geometry, TessellationControl, TessellationEvaluation};
+
+    let producer_stage = Stage::new(producer);
+    let consumer_stage = Stage::new(consumer);
+    let outputs = interfaces(
+        producer,
+        &producer_stage,
+        spirv::StorageClass::Output,
+        has_execution_model(producer, &[TessellationControl]),
+    );
+    let inputs = interfaces(
+        consumer,
+        &consumer_stage,
+        spirv::StorageClass::Input,
+        has_execution_model(consumer, &[TessellationControl, TessellationEvaluation, Geometry]),
+    );
+
+    let mut inputs: Vec<&Interface> = inputs.values().collect();
+    inputs.sort_by_key(|input| (input.location, input.component));
+    let mut mismatches = vec![];
+    for input in inputs {
+        let (location, component) = (input.location, input.component);
+        let output = match outputs.get(&(location, component)) {
+            Some(output) => output,
+            None => {
+                mismatches.push(StageLinkMismatch::MissingOutput { location, component });
+                continue;
+            }
+        };
+        let output_type = producer_stage.describe(output.ty);
+        let input_type = consumer_stage.describe(input.ty);
+        if output_type != input_type {
+            match (producer_stage.components(output.ty), consumer_stage.components(input.ty)) {
+                (Some((o, output)), Some((i, input))) if o == i => {
+                    if output < input {
+                        mismatches.push(StageLinkMismatch::ComponentCount {
+                            location,
+                            component,
+                            output,
+                            input,
+                        });
+                    }
+                }
+                _ => mismatches.push(StageLinkMismatch::Type {
+                    location,
+                    component,
+                    output: output_type,
+                    input: input_type,
+                }),
+            }
+        }
+        if output.interpolation != input.interpolation {
+            mismatches.push(StageLinkMismatch::Interpolation {
+                location,
+                component,
+                output: output.interpolation.clone(),
+                input: input.interpolation.clone(),
+            });
+        }
+    }
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+
+    use super::{check_stage_link, StageLinkMismatch};
+
+    fn location(b: &mut mr::Builder, var: spirv::Word, location: u32) {
+        b.decorate(var, spirv::Decoration::Location, vec![mr::Operand::LiteralInt32(location)]);
+    }
+
+    /// Builds a module with interface variables of the given storage class
+    /// at locations 0, 1, 2, and 3.
+    fn build(
+        model: spirv::ExecutionModel,
+        storage_class: spirv::StorageClass,
+        vec3_at_1: bool,
+        flat_at_2: bool,
+    ) -> mr::Module {
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let int = b.type_int(32, 1);
+        let vec4 = b.type_vector(float, 4);
+        let vec3 = b.type_vector(float, 3);
+        let ty = if vec3_at_1 { vec3 } else { vec4 };
+        let vec_ptr = b.type_pointer(None, storage_class, ty);
+        let int_ptr = b.type_pointer(None, storage_class, int);
+        let float_ptr = b.type_pointer(None, storage_class, float);
+        let vec4_ptr = b.type_pointer(None, storage_class, vec4);
+        let var0 = b.variable(vec4_ptr, None, storage_class, None);
+        let var1 = b.variable(vec_ptr, None, storage_class, None);
+        let var2 = b.variable(int_ptr, None, storage_class, None);
+        let var3 = b.variable(
+            if storage_class == spirv::StorageClass::Input { float_ptr } else { int_ptr },
+            None,
+            storage_class,
+            None,
+        );
+        location(&mut b, var0, 0);
+        location(&mut b, var1, 1);
+        location(&mut b, var2, 2);
+        location(&mut b, var3, 3);
+        if flat_at_2 {
+            b.decorate(var2, spirv::Decoration::Flat, vec![]);
+        }
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(model, main, "main", vec![var0, var1, var2, var3]);
+        b.module()
+    }
+
+    #[test]
+    fn test_stage_link() {
+        let vertex = build(spirv::ExecutionModel::Vertex, spirv::StorageClass::Output, true, false);
+        let fragment =
+            build(spirv::ExecutionModel::Fragment, spirv::StorageClass::Input, false, true);
+        let mismatches = check_stage_link(&vertex, &fragment);
+        assert_eq!(
+            vec![
+                StageLinkMismatch::ComponentCount {
+                    location: 1,
+                    component: 0,
+                    output: 3,
+                    input: 4,
+                },
+                StageLinkMismatch::Interpolation {
+                    location: 2,
+                    component: 0,
+                    output: vec![],
+                    input: vec![spirv::Decoration::Flat],
+                },
+                StageLinkMismatch::Type {
+                    location: 3,
+                    component: 0,
+                    output: "i32".to_string(),
+                    input: "f32".to_string(),
+                },
+            ],
+            mismatches
+        );
+        assert_eq!(
+            "location 3 component 0: output type i32 does not match input type f32",
+            mismatches[2].to_string()
+        );
+
+        // Outputs may have more components than inputs.
+        let mismatches = check_stage_link(&vec4_output(), &vec3_input());
+        assert!(mismatches.is_empty());
+    }
+
+    fn vec4_output() -> mr::Module {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let var = b.variable(ptr, None, spirv::StorageClass::Output, None);
+        location(&mut b, var, 0);
+        b.module()
+    }
+
+    fn vec3_input() -> mr::Module {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let vec3 = b.type_vector(float, 3);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Input, vec3);
+        let var = b.variable(ptr, None, spirv::StorageClass::Input, None);
+        location(&mut b, var, 0);
+        b.module()
+    }
+
+    #[test]
+    fn test_stage_link_missing_and_arrayed() {
+        let mut b = mr::Builder::new();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Output, vec4);
+        let var = b.variable(ptr, None, spirv::StorageClass::Output, None);
+        location(&mut b, var, 0);
+        let vertex = b.module();
+
+        let mut b = mr::Builder::new();
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let vec4 = b.type_vector(float, 4);
+        let uint = b.type_int(32, 0);
+        let three = b.constant_u32(uint, 3);
+        let array = b.type_array(vec4, three);
+        let ptr = b.type_pointer(None, spirv::StorageClass::Input, array);
+        let var0 = b.variable(ptr, None, spirv::StorageClass::Input, None);
+        let var1 = b.variable(ptr, None, spirv::StorageClass::Input, None);
+        location(&mut b, var0, 0);
+        location(&mut b, var1, 1);
+        let voidf = b.type_function(void, vec![]);
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        b.entry_point(spirv::ExecutionModel::Geometry, main, "main", vec![var0, var1]);
+        let geometry = b.module();
+
+        assert_eq!(
+            vec![StageLinkMismatch::MissingOutput { location: 1, component: 0 }],
+            check_stage_link(&vertex, &geometry)
+        );
+    }
+}
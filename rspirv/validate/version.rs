@@ -0,0 +1,243 @@
+// Copyright 2018 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mr;
+use spirv;
+
+use std::collections::HashMap;
+use super::Diagnostic;
+
+/// Checks the module against the rules depending on its SPIR-V version.
+///
+/// Entry point interfaces must list exactly the global variables required
+/// by the version: only Input and Output variables before SPIR-V 1.4, and
+/// all statically used global variables since. Storage classes must be
+/// available in the version or via a declared extension, and since SPIR-V
+/// 1.6, OpBranchConditional must branch to two distinct labels.
+pub fn check_version_rules(module: &mr::Module) -> Vec<Diagnostic> {
+    let rules = module.version_rules();
+    let (major, minor) = rules.version();
+    let mut diagnostics = vec![];
+
+    let storage_classes: HashMap<spirv::Word, spirv::StorageClass> = module
+        .types_global_values
+        .iter()
+        .filter(|inst| inst.class.opcode == spirv::Op::Variable)
+        .filter_map(|inst| match (inst.result_id, inst.operands.first()) {
+            (Some(id), Some(&mr::Operand::StorageClass(sc))) => Some((id, sc)),
+            _ => None,
+        })
+        .collect();
+
+    for inst in &module.entry_points {
+        let (function, name) = match (inst.operands.get(1), inst.operands.get(2)) {
+            (Some(&mr::Operand::IdRef(f)), Some(mr::Operand::LiteralString(n))) => (f, n),
+            _ => continue,
+        };
+        let listed: Vec<spirv::Word> = inst.operands[3..]
+            .iter()
+            .filter_map(|operand| match *operand {
+                mr::Operand::IdRef(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        for &id in &listed {
+            match storage_classes.get(&id) {
+                Some(&sc) if !rules.is_interface_storage_class(sc) => {
+                    diagnostics.push(Diagnostic::error(
+                        Some(id),
+                        format!(
+                            "entry point '{}' lists a variable of storage class {:?}, \
+                             which is not allowed in SPIR-V {}.{}",
+                            name, sc, major, minor
+                        ),
+                    ))
+                }
+                Some(_) => {}
+                None => diagnostics.push(Diagnostic::error(
+                    Some(id),
+                    format!("entry point '{}' lists an id that is not a global variable", name),
+                )),
+            }
+        }
+        if let Ok(required) = rules.entry_point_interface(module, function) {
+            for id in required.into_iter().filter(|id| !listed.contains(id)) {
+                diagnostics.push(Diagnostic::error(
+                    Some(id),
+                    format!("entry point '{}' does not list a used variable", name),
+                ));
+            }
+        }
+    }
+
+    let extensions: Vec<&str> = module
+        .extensions
+        .iter()
+        .filter_map(|inst| match inst.operands.first() {
+            Some(mr::Operand::LiteralString(name)) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    for inst in module.all_inst_iter() {
+        let opcode = inst.class.opcode;
+        if opcode != spirv::Op::Variable && opcode != spirv::Op::TypePointer {
+            continue;
+        }
+        if let Some(&mr::Operand::StorageClass(sc)) = inst.operands.first() {
+            if !rules.allows_storage_class(sc, &extensions) {
+                diagnostics.push(Diagnostic::error(
+                    inst.result_id,
+                    format!(
+                        "storage class {:?} is not available in SPIR-V {}.{}",
+                        sc, major, minor
+                    ),
+                ));
+            }
+        }
+    }
+
+    if rules.requires_distinct_branch_targets() {
+        let branches = module
+            .functions
+            .iter()
+            .flat_map(|f| &f.basic_blocks)
+            .filter_map(|bb| bb.instructions.last().map(|inst| (bb, inst)));
+        for (bb, inst) in branches {
+            if inst.class.opcode != spirv::Op::BranchConditional {
+                continue;
+            }
+            if let (Some(t), Some(f)) = (inst.operands.get(1), inst.operands.get(2)) {
+                if t == f {
+                    diagnostics.push(Diagnostic::error(
+                        bb.label.as_ref().and_then(|l| l.result_id),
+                        format!(
+                            "OpBranchConditional must branch to distinct labels in SPIR-V {}.{}",
+                            major, minor
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Checks whether the module can be consumed by the given target
+/// environment `env`; see
+/// [`Module::check_target_env()`](../mr/struct.Module.html#method.check_target_env).
+pub fn check_target_env(module: &mr::Module, env: mr::TargetEnv) -> Vec<Diagnostic> {
+    module
+        .check_target_env(env)
+        .into_iter()
+        .map(|issue| Diagnostic::error(None, format!("{}: {}", env, issue)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mr;
+    use spirv;
+    use validate::{check_target_env, check_version_rules};
+
+    fn build(version: (u8, u8), interface: &[usize]) -> mr::Module {
+        let mut b = mr::Builder::new();
+        b.set_version(version.0, version.1);
+        let void = b.type_void();
+        let float = b.type_float(32);
+        let boolean = b.type_bool();
+        let t = b.constant_true(boolean);
+        let voidf = b.type_function(void, vec![]);
+        let ptr_in = b.type_pointer(None, spirv::StorageClass::Input, float);
+        let ptr_priv = b.type_pointer(None, spirv::StorageClass::Private, float);
+        let vars = [
+            b.variable(ptr_in, None, spirv::StorageClass::Input, None),
+            b.variable(ptr_priv, None, spirv::StorageClass::Private, None),
+        ];
+        let main = b.begin_function(void, None, spirv::FunctionControl::NONE, voidf).unwrap();
+        b.begin_basic_block(None).unwrap();
+        let value = b.load(float, None, vars[0], None, vec![]).unwrap();
+        b.store(vars[1], value, None, vec![]).unwrap();
+        let next = b.id();
+        b.branch_conditional(t, next, next, vec![]).unwrap();
+        b.begin_basic_block(Some(next)).unwrap();
+        b.ret().unwrap();
+        b.end_function().unwrap();
+        let listed: Vec<spirv::Word> = interface.iter().map(|&i| vars[i]).collect();
+        b.entry_point(spirv::ExecutionModel::Fragment, main, "main", listed);
+        b.module()
+    }
+
+    fn messages(module: &mr::Module) -> Vec<String> {
+        check_version_rules(module).iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_interface_before_1_4() {
+        assert!(messages(&build((1, 3), &[0])).is_empty());
+        assert_eq!(
+            vec![
+                "error: %9: entry point 'main' lists a variable of storage class Private, \
+                 which is not allowed in SPIR-V 1.3",
+            ],
+            messages(&build((1, 3), &[0, 1]))
+        );
+        assert_eq!(
+            vec!["error: %8: entry point 'main' does not list a used variable"],
+            messages(&build((1, 3), &[]))
+        );
+    }
+
+    #[test]
+    fn test_interface_since_1_4() {
+        assert!(messages(&build((1, 4), &[0, 1])).is_empty());
+        assert_eq!(
+            vec!["error: %9: entry point 'main' does not list a used variable"],
+            messages(&build((1, 4), &[0]))
+        );
+        assert_eq!(
+            vec![
+                "error: %11: OpBranchConditional must branch to distinct labels in SPIR-V 1.6",
+            ],
+            messages(&build((1, 6), &[0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_storage_class_version() {
+        let mut b = mr::Builder::new();
+        b.set_version(1, 2);
+        let float = b.type_float(32);
+        b.type_pointer(None, spirv::StorageClass::StorageBuffer, float);
+        let m = b.module();
+        assert_eq!(
+            vec!["error: %2: storage class StorageBuffer is not available in SPIR-V 1.2"],
+            messages(&m)
+        );
+
+        let mut b = mr::Builder::from_module(m);
+        b.extension("SPV_KHR_storage_buffer_storage_class");
+        assert!(messages(&b.module()).is_empty());
+    }
+
+    #[test]
+    fn test_target_env() {
+        let mut b = mr::Builder::for_vulkan_1_0();
+        b.capability(spirv::Capability::Kernel);
+        let messages: Vec<String> = check_target_env(&b.module(), mr::TargetEnv::Vulkan1_0)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(vec!["error: Vulkan 1.0: capability Kernel is not allowed"], messages);
+    }
+}
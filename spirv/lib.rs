@@ -21,6 +21,7 @@
 //!
 //! The version of this crate is the version of SPIR-V it contains.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_camel_case_types)]
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
@@ -31,4 +32,81 @@ extern crate num_traits;
 #[macro_use]
 extern crate num_derive;
 
+#[cfg(feature = "std")]
+use std::{error, fmt, str};
+#[cfg(not(feature = "std"))]
+use core::{fmt, str};
+
+/// The error returned when parsing an enumerant of an operand kind from its
+/// name fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseEnumError;
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown enumerant name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParseEnumError {}
+
+/// Writes the names of the set flags in `flags` separated by `|`, or
+/// `None` if there are no such flags.
+fn write_flags(f: &mut fmt::Formatter, flags: &[(&str, bool)]) -> fmt::Result {
+    let mut names = flags.iter().filter(|&&(_, set)| set).map(|&(name, _)| name);
+    match names.next() {
+        Some(first) => {
+            f.write_str(first)?;
+            for name in names {
+                write!(f, "|{}", name)?;
+            }
+            Ok(())
+        }
+        None => f.write_str("None"),
+    }
+}
+
 include!("spirv.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_enum_names() {
+        assert_eq!("UniformConstant", StorageClass::UniformConstant.to_string());
+        assert_eq!(Ok(StorageClass::Function), "Function".parse());
+        assert_eq!("1D", Dim::Dim1D.to_string());
+        assert_eq!(Ok(Dim::DimSubpassData), "SubpassData".parse());
+        // Aliases are parsed but displayed with their canonical names.
+        assert_eq!(Ok(BuiltIn::SubgroupEqMask), "SubgroupEqMaskKHR".parse());
+        assert_eq!("SubgroupEqMask", BuiltIn::SubgroupEqMaskKHR.to_string());
+        assert_eq!(Err::<Decoration, _>(ParseEnumError), "Unknown".parse());
+    }
+
+    #[test]
+    fn test_bit_enum_names() {
+        assert_eq!("None", FunctionControl::NONE.to_string());
+        assert_eq!("DontInline|Const",
+                   (FunctionControl::DONT_INLINE | FunctionControl::CONST).to_string());
+        assert_eq!(Ok(FunctionControl::INLINE | FunctionControl::PURE), "Inline | Pure".parse());
+        assert_eq!(Ok(FPFastMathMode::NOT_NAN), "NotNaN".parse());
+        assert_eq!(Ok(MemorySemantics::empty()), "Relaxed".parse());
+        assert_eq!(Err::<LoopControl, _>(ParseEnumError), "Unroll|Roll".parse());
+    }
+
+    #[test]
+    fn test_requirements() {
+        assert_eq!(&[Capability::Matrix], Op::TypeMatrix.required_capabilities());
+        assert!(Op::IAdd.required_capabilities().is_empty());
+        assert_eq!(Some((1, 0)), Op::IAdd.required_version());
+        assert_eq!(Some((1, 2)), Op::DecorateId.required_version());
+        assert_eq!(None, Op::SubgroupBallotKHR.required_version());
+        assert_eq!(&["SPV_KHR_shader_ballot"], Op::SubgroupBallotKHR.required_extensions());
+        assert_eq!(Some((1, 3)), StorageClass::StorageBuffer.required_version());
+        assert_eq!(&["SPV_KHR_storage_buffer_storage_class", "SPV_KHR_variable_pointers"],
+                   StorageClass::StorageBuffer.required_extensions());
+        assert_eq!(&[Capability::Sampled1D, Capability::Image1D], Dim::Dim1D.required_capabilities());
+    }
+}
@@ -41,6 +41,52 @@ bitflags!{
     }
 }
 
+impl fmt::Display for ImageOperands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Bias", self.contains(ImageOperands::BIAS)),
+            ("Lod", self.contains(ImageOperands::LOD)),
+            ("Grad", self.contains(ImageOperands::GRAD)),
+            ("ConstOffset", self.contains(ImageOperands::CONST_OFFSET)),
+            ("Offset", self.contains(ImageOperands::OFFSET)),
+            ("ConstOffsets", self.contains(ImageOperands::CONST_OFFSETS)),
+            ("Sample", self.contains(ImageOperands::SAMPLE)),
+            ("MinLod", self.contains(ImageOperands::MIN_LOD)),
+            ("MakeTexelAvailableKHR", self.contains(ImageOperands::MAKE_TEXEL_AVAILABLE_KHR)),
+            ("MakeTexelVisibleKHR", self.contains(ImageOperands::MAKE_TEXEL_VISIBLE_KHR)),
+            ("NonPrivateTexelKHR", self.contains(ImageOperands::NON_PRIVATE_TEXEL_KHR)),
+            ("VolatileTexelKHR", self.contains(ImageOperands::VOLATILE_TEXEL_KHR)),
+        ])
+    }
+}
+
+impl str::FromStr for ImageOperands {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = ImageOperands::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => ImageOperands::NONE,
+                "Bias" => ImageOperands::BIAS,
+                "Lod" => ImageOperands::LOD,
+                "Grad" => ImageOperands::GRAD,
+                "ConstOffset" => ImageOperands::CONST_OFFSET,
+                "Offset" => ImageOperands::OFFSET,
+                "ConstOffsets" => ImageOperands::CONST_OFFSETS,
+                "Sample" => ImageOperands::SAMPLE,
+                "MinLod" => ImageOperands::MIN_LOD,
+                "MakeTexelAvailableKHR" => ImageOperands::MAKE_TEXEL_AVAILABLE_KHR,
+                "MakeTexelVisibleKHR" => ImageOperands::MAKE_TEXEL_VISIBLE_KHR,
+                "NonPrivateTexelKHR" => ImageOperands::NON_PRIVATE_TEXEL_KHR,
+                "VolatileTexelKHR" => ImageOperands::VOLATILE_TEXEL_KHR,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [FPFastMathMode](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_fp_fast_math_mode_a_fp_fast_math_mode)
     pub struct FPFastMathMode : u32 {
@@ -53,6 +99,38 @@ bitflags!{
     }
 }
 
+impl fmt::Display for FPFastMathMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("NotNaN", self.contains(FPFastMathMode::NOT_NAN)),
+            ("NotInf", self.contains(FPFastMathMode::NOT_INF)),
+            ("NSZ", self.contains(FPFastMathMode::NSZ)),
+            ("AllowRecip", self.contains(FPFastMathMode::ALLOW_RECIP)),
+            ("Fast", self.contains(FPFastMathMode::FAST)),
+        ])
+    }
+}
+
+impl str::FromStr for FPFastMathMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = FPFastMathMode::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => FPFastMathMode::NONE,
+                "NotNaN" => FPFastMathMode::NOT_NAN,
+                "NotInf" => FPFastMathMode::NOT_INF,
+                "NSZ" => FPFastMathMode::NSZ,
+                "AllowRecip" => FPFastMathMode::ALLOW_RECIP,
+                "Fast" => FPFastMathMode::FAST,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [SelectionControl](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_selection_control_a_selection_control)
     pub struct SelectionControl : u32 {
@@ -62,6 +140,32 @@ bitflags!{
     }
 }
 
+impl fmt::Display for SelectionControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Flatten", self.contains(SelectionControl::FLATTEN)),
+            ("DontFlatten", self.contains(SelectionControl::DONT_FLATTEN)),
+        ])
+    }
+}
+
+impl str::FromStr for SelectionControl {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = SelectionControl::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => SelectionControl::NONE,
+                "Flatten" => SelectionControl::FLATTEN,
+                "DontFlatten" => SelectionControl::DONT_FLATTEN,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [LoopControl](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_loop_control_a_loop_control)
     pub struct LoopControl : u32 {
@@ -73,6 +177,36 @@ bitflags!{
     }
 }
 
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Unroll", self.contains(LoopControl::UNROLL)),
+            ("DontUnroll", self.contains(LoopControl::DONT_UNROLL)),
+            ("DependencyInfinite", self.contains(LoopControl::DEPENDENCY_INFINITE)),
+            ("DependencyLength", self.contains(LoopControl::DEPENDENCY_LENGTH)),
+        ])
+    }
+}
+
+impl str::FromStr for LoopControl {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = LoopControl::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => LoopControl::NONE,
+                "Unroll" => LoopControl::UNROLL,
+                "DontUnroll" => LoopControl::DONT_UNROLL,
+                "DependencyInfinite" => LoopControl::DEPENDENCY_INFINITE,
+                "DependencyLength" => LoopControl::DEPENDENCY_LENGTH,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [FunctionControl](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_function_control_a_function_control)
     pub struct FunctionControl : u32 {
@@ -84,6 +218,36 @@ bitflags!{
     }
 }
 
+impl fmt::Display for FunctionControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Inline", self.contains(FunctionControl::INLINE)),
+            ("DontInline", self.contains(FunctionControl::DONT_INLINE)),
+            ("Pure", self.contains(FunctionControl::PURE)),
+            ("Const", self.contains(FunctionControl::CONST)),
+        ])
+    }
+}
+
+impl str::FromStr for FunctionControl {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = FunctionControl::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => FunctionControl::NONE,
+                "Inline" => FunctionControl::INLINE,
+                "DontInline" => FunctionControl::DONT_INLINE,
+                "Pure" => FunctionControl::PURE,
+                "Const" => FunctionControl::CONST,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [MemorySemantics](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_memory_semantics_a_memory_semantics)
     pub struct MemorySemantics : u32 {
@@ -105,6 +269,55 @@ bitflags!{
     }
 }
 
+impl fmt::Display for MemorySemantics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Acquire", self.contains(MemorySemantics::ACQUIRE)),
+            ("Release", self.contains(MemorySemantics::RELEASE)),
+            ("AcquireRelease", self.contains(MemorySemantics::ACQUIRE_RELEASE)),
+            ("SequentiallyConsistent", self.contains(MemorySemantics::SEQUENTIALLY_CONSISTENT)),
+            ("UniformMemory", self.contains(MemorySemantics::UNIFORM_MEMORY)),
+            ("SubgroupMemory", self.contains(MemorySemantics::SUBGROUP_MEMORY)),
+            ("WorkgroupMemory", self.contains(MemorySemantics::WORKGROUP_MEMORY)),
+            ("CrossWorkgroupMemory", self.contains(MemorySemantics::CROSS_WORKGROUP_MEMORY)),
+            ("AtomicCounterMemory", self.contains(MemorySemantics::ATOMIC_COUNTER_MEMORY)),
+            ("ImageMemory", self.contains(MemorySemantics::IMAGE_MEMORY)),
+            ("OutputMemoryKHR", self.contains(MemorySemantics::OUTPUT_MEMORY_KHR)),
+            ("MakeAvailableKHR", self.contains(MemorySemantics::MAKE_AVAILABLE_KHR)),
+            ("MakeVisibleKHR", self.contains(MemorySemantics::MAKE_VISIBLE_KHR)),
+        ])
+    }
+}
+
+impl str::FromStr for MemorySemantics {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = MemorySemantics::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "Relaxed" => MemorySemantics::RELAXED,
+                "None" => MemorySemantics::NONE,
+                "Acquire" => MemorySemantics::ACQUIRE,
+                "Release" => MemorySemantics::RELEASE,
+                "AcquireRelease" => MemorySemantics::ACQUIRE_RELEASE,
+                "SequentiallyConsistent" => MemorySemantics::SEQUENTIALLY_CONSISTENT,
+                "UniformMemory" => MemorySemantics::UNIFORM_MEMORY,
+                "SubgroupMemory" => MemorySemantics::SUBGROUP_MEMORY,
+                "WorkgroupMemory" => MemorySemantics::WORKGROUP_MEMORY,
+                "CrossWorkgroupMemory" => MemorySemantics::CROSS_WORKGROUP_MEMORY,
+                "AtomicCounterMemory" => MemorySemantics::ATOMIC_COUNTER_MEMORY,
+                "ImageMemory" => MemorySemantics::IMAGE_MEMORY,
+                "OutputMemoryKHR" => MemorySemantics::OUTPUT_MEMORY_KHR,
+                "MakeAvailableKHR" => MemorySemantics::MAKE_AVAILABLE_KHR,
+                "MakeVisibleKHR" => MemorySemantics::MAKE_VISIBLE_KHR,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [MemoryAccess](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_memory_access_a_memory_access)
     pub struct MemoryAccess : u32 {
@@ -118,6 +331,40 @@ bitflags!{
     }
 }
 
+impl fmt::Display for MemoryAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("Volatile", self.contains(MemoryAccess::VOLATILE)),
+            ("Aligned", self.contains(MemoryAccess::ALIGNED)),
+            ("Nontemporal", self.contains(MemoryAccess::NONTEMPORAL)),
+            ("MakePointerAvailableKHR", self.contains(MemoryAccess::MAKE_POINTER_AVAILABLE_KHR)),
+            ("MakePointerVisibleKHR", self.contains(MemoryAccess::MAKE_POINTER_VISIBLE_KHR)),
+            ("NonPrivatePointerKHR", self.contains(MemoryAccess::NON_PRIVATE_POINTER_KHR)),
+        ])
+    }
+}
+
+impl str::FromStr for MemoryAccess {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = MemoryAccess::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => MemoryAccess::NONE,
+                "Volatile" => MemoryAccess::VOLATILE,
+                "Aligned" => MemoryAccess::ALIGNED,
+                "Nontemporal" => MemoryAccess::NONTEMPORAL,
+                "MakePointerAvailableKHR" => MemoryAccess::MAKE_POINTER_AVAILABLE_KHR,
+                "MakePointerVisibleKHR" => MemoryAccess::MAKE_POINTER_VISIBLE_KHR,
+                "NonPrivatePointerKHR" => MemoryAccess::NON_PRIVATE_POINTER_KHR,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 bitflags!{
     /// SPIR-V operand kind: [KernelProfilingInfo](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_kernel_profiling_info_a_kernel_profiling_info)
     pub struct KernelProfilingInfo : u32 {
@@ -126,6 +373,133 @@ bitflags!{
     }
 }
 
+impl fmt::Display for KernelProfilingInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("CmdExecTime", self.contains(KernelProfilingInfo::CMD_EXEC_TIME)),
+        ])
+    }
+}
+
+impl str::FromStr for KernelProfilingInfo {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = KernelProfilingInfo::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => KernelProfilingInfo::NONE,
+                "CmdExecTime" => KernelProfilingInfo::CMD_EXEC_TIME,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
+bitflags!{
+    /// SPIR-V operand kind: [RayFlags](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_ray_flags_a_ray_flags)
+    pub struct RayFlags : u32 {
+        const NONE_KHR = 0x0000;
+        const OPAQUE_KHR = 0x0001;
+        const NO_OPAQUE_KHR = 0x0002;
+        const TERMINATE_ON_FIRST_HIT_KHR = 0x0004;
+        const SKIP_CLOSEST_HIT_SHADER_KHR = 0x0008;
+        const CULL_BACK_FACING_TRIANGLES_KHR = 0x0010;
+        const CULL_FRONT_FACING_TRIANGLES_KHR = 0x0020;
+        const CULL_OPAQUE_KHR = 0x0040;
+        const CULL_NO_OPAQUE_KHR = 0x0080;
+        const SKIP_TRIANGLES_KHR = 0x0100;
+        const SKIP_AABBS_KHR = 0x0200;
+    }
+}
+
+impl fmt::Display for RayFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("OpaqueKHR", self.contains(RayFlags::OPAQUE_KHR)),
+            ("NoOpaqueKHR", self.contains(RayFlags::NO_OPAQUE_KHR)),
+            ("TerminateOnFirstHitKHR", self.contains(RayFlags::TERMINATE_ON_FIRST_HIT_KHR)),
+            ("SkipClosestHitShaderKHR", self.contains(RayFlags::SKIP_CLOSEST_HIT_SHADER_KHR)),
+            ("CullBackFacingTrianglesKHR", self.contains(RayFlags::CULL_BACK_FACING_TRIANGLES_KHR)),
+            ("CullFrontFacingTrianglesKHR", self.contains(RayFlags::CULL_FRONT_FACING_TRIANGLES_KHR)),
+            ("CullOpaqueKHR", self.contains(RayFlags::CULL_OPAQUE_KHR)),
+            ("CullNoOpaqueKHR", self.contains(RayFlags::CULL_NO_OPAQUE_KHR)),
+            ("SkipTrianglesKHR", self.contains(RayFlags::SKIP_TRIANGLES_KHR)),
+            ("SkipAABBsKHR", self.contains(RayFlags::SKIP_AABBS_KHR)),
+        ])
+    }
+}
+
+impl str::FromStr for RayFlags {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = RayFlags::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "NoneKHR" => RayFlags::NONE_KHR,
+                "OpaqueKHR" => RayFlags::OPAQUE_KHR,
+                "NoOpaqueKHR" => RayFlags::NO_OPAQUE_KHR,
+                "TerminateOnFirstHitKHR" => RayFlags::TERMINATE_ON_FIRST_HIT_KHR,
+                "SkipClosestHitShaderKHR" => RayFlags::SKIP_CLOSEST_HIT_SHADER_KHR,
+                "CullBackFacingTrianglesKHR" => RayFlags::CULL_BACK_FACING_TRIANGLES_KHR,
+                "CullFrontFacingTrianglesKHR" => RayFlags::CULL_FRONT_FACING_TRIANGLES_KHR,
+                "CullOpaqueKHR" => RayFlags::CULL_OPAQUE_KHR,
+                "CullNoOpaqueKHR" => RayFlags::CULL_NO_OPAQUE_KHR,
+                "SkipTrianglesKHR" => RayFlags::SKIP_TRIANGLES_KHR,
+                "SkipAABBsKHR" => RayFlags::SKIP_AABBS_KHR,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
+bitflags!{
+    /// SPIR-V operand kind: [CooperativeMatrixOperands](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_cooperative_matrix_operands_a_cooperative_matrix_operands)
+    pub struct CooperativeMatrixOperands : u32 {
+        const NONE_KHR = 0x0000;
+        const MATRIX_ASIGNED_COMPONENTS_KHR = 0x0001;
+        const MATRIX_BSIGNED_COMPONENTS_KHR = 0x0002;
+        const MATRIX_CSIGNED_COMPONENTS_KHR = 0x0004;
+        const MATRIX_RESULT_SIGNED_COMPONENTS_KHR = 0x0008;
+        const SATURATING_ACCUMULATION_KHR = 0x0010;
+    }
+}
+
+impl fmt::Display for CooperativeMatrixOperands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("MatrixASignedComponentsKHR", self.contains(CooperativeMatrixOperands::MATRIX_ASIGNED_COMPONENTS_KHR)),
+            ("MatrixBSignedComponentsKHR", self.contains(CooperativeMatrixOperands::MATRIX_BSIGNED_COMPONENTS_KHR)),
+            ("MatrixCSignedComponentsKHR", self.contains(CooperativeMatrixOperands::MATRIX_CSIGNED_COMPONENTS_KHR)),
+            ("MatrixResultSignedComponentsKHR", self.contains(CooperativeMatrixOperands::MATRIX_RESULT_SIGNED_COMPONENTS_KHR)),
+            ("SaturatingAccumulationKHR", self.contains(CooperativeMatrixOperands::SATURATING_ACCUMULATION_KHR)),
+        ])
+    }
+}
+
+impl str::FromStr for CooperativeMatrixOperands {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = CooperativeMatrixOperands::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "NoneKHR" => CooperativeMatrixOperands::NONE_KHR,
+                "MatrixASignedComponentsKHR" => CooperativeMatrixOperands::MATRIX_ASIGNED_COMPONENTS_KHR,
+                "MatrixBSignedComponentsKHR" => CooperativeMatrixOperands::MATRIX_BSIGNED_COMPONENTS_KHR,
+                "MatrixCSignedComponentsKHR" => CooperativeMatrixOperands::MATRIX_CSIGNED_COMPONENTS_KHR,
+                "MatrixResultSignedComponentsKHR" => CooperativeMatrixOperands::MATRIX_RESULT_SIGNED_COMPONENTS_KHR,
+                "SaturatingAccumulationKHR" => CooperativeMatrixOperands::SATURATING_ACCUMULATION_KHR,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
 /// SPIR-V operand kind: [SourceLanguage](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_source_language_a_source_language)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -138,6 +512,56 @@ pub enum SourceLanguage {
     HLSL = 5,
 }
 
+impl fmt::Display for SourceLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            SourceLanguage::Unknown => "Unknown",
+            SourceLanguage::ESSL => "ESSL",
+            SourceLanguage::GLSL => "GLSL",
+            SourceLanguage::OpenCL_C => "OpenCL_C",
+            SourceLanguage::OpenCL_CPP => "OpenCL_CPP",
+            SourceLanguage::HLSL => "HLSL",
+        })
+    }
+}
+
+impl str::FromStr for SourceLanguage {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Unknown" => Ok(SourceLanguage::Unknown),
+            "ESSL" => Ok(SourceLanguage::ESSL),
+            "GLSL" => Ok(SourceLanguage::GLSL),
+            "OpenCL_C" => Ok(SourceLanguage::OpenCL_C),
+            "OpenCL_CPP" => Ok(SourceLanguage::OpenCL_CPP),
+            "HLSL" => Ok(SourceLanguage::HLSL),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl SourceLanguage {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        &[]
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [ExecutionModel](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_execution_model_a_execution_model)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -149,6 +573,118 @@ pub enum ExecutionModel {
     Fragment = 4,
     GLCompute = 5,
     Kernel = 6,
+    RayGenerationKHR = 5313,
+    IntersectionKHR = 5314,
+    AnyHitKHR = 5315,
+    ClosestHitKHR = 5316,
+    MissKHR = 5317,
+    CallableKHR = 5318,
+    TaskEXT = 5364,
+    MeshEXT = 5365,
+}
+
+impl fmt::Display for ExecutionModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ExecutionModel::Vertex => "Vertex",
+            ExecutionModel::TessellationControl => "TessellationControl",
+            ExecutionModel::TessellationEvaluation => "TessellationEvaluation",
+            ExecutionModel::Geometry => "Geometry",
+            ExecutionModel::Fragment => "Fragment",
+            ExecutionModel::GLCompute => "GLCompute",
+            ExecutionModel::Kernel => "Kernel",
+            ExecutionModel::RayGenerationKHR => "RayGenerationKHR",
+            ExecutionModel::IntersectionKHR => "IntersectionKHR",
+            ExecutionModel::AnyHitKHR => "AnyHitKHR",
+            ExecutionModel::ClosestHitKHR => "ClosestHitKHR",
+            ExecutionModel::MissKHR => "MissKHR",
+            ExecutionModel::CallableKHR => "CallableKHR",
+            ExecutionModel::TaskEXT => "TaskEXT",
+            ExecutionModel::MeshEXT => "MeshEXT",
+        })
+    }
+}
+
+impl str::FromStr for ExecutionModel {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Vertex" => Ok(ExecutionModel::Vertex),
+            "TessellationControl" => Ok(ExecutionModel::TessellationControl),
+            "TessellationEvaluation" => Ok(ExecutionModel::TessellationEvaluation),
+            "Geometry" => Ok(ExecutionModel::Geometry),
+            "Fragment" => Ok(ExecutionModel::Fragment),
+            "GLCompute" => Ok(ExecutionModel::GLCompute),
+            "Kernel" => Ok(ExecutionModel::Kernel),
+            "RayGenerationKHR" => Ok(ExecutionModel::RayGenerationKHR),
+            "IntersectionKHR" => Ok(ExecutionModel::IntersectionKHR),
+            "AnyHitKHR" => Ok(ExecutionModel::AnyHitKHR),
+            "ClosestHitKHR" => Ok(ExecutionModel::ClosestHitKHR),
+            "MissKHR" => Ok(ExecutionModel::MissKHR),
+            "CallableKHR" => Ok(ExecutionModel::CallableKHR),
+            "TaskEXT" => Ok(ExecutionModel::TaskEXT),
+            "MeshEXT" => Ok(ExecutionModel::MeshEXT),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl ExecutionModel {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            ExecutionModel::Vertex => &[Capability::Shader],
+            ExecutionModel::TessellationControl => &[Capability::Tessellation],
+            ExecutionModel::TessellationEvaluation => &[Capability::Tessellation],
+            ExecutionModel::Geometry => &[Capability::Geometry],
+            ExecutionModel::Fragment => &[Capability::Shader],
+            ExecutionModel::GLCompute => &[Capability::Shader],
+            ExecutionModel::Kernel => &[Capability::Kernel],
+            ExecutionModel::RayGenerationKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::IntersectionKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::AnyHitKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::ClosestHitKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::MissKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::CallableKHR => &[Capability::RayTracingKHR],
+            ExecutionModel::TaskEXT => &[Capability::MeshShadingEXT],
+            ExecutionModel::MeshEXT => &[Capability::MeshShadingEXT],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            ExecutionModel::RayGenerationKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::IntersectionKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::AnyHitKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::ClosestHitKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::MissKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::CallableKHR => &["SPV_KHR_ray_tracing"],
+            ExecutionModel::TaskEXT => &["SPV_EXT_mesh_shader"],
+            ExecutionModel::MeshEXT => &["SPV_EXT_mesh_shader"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            ExecutionModel::RayGenerationKHR => None,
+            ExecutionModel::IntersectionKHR => None,
+            ExecutionModel::AnyHitKHR => None,
+            ExecutionModel::ClosestHitKHR => None,
+            ExecutionModel::MissKHR => None,
+            ExecutionModel::CallableKHR => None,
+            ExecutionModel::TaskEXT => None,
+            ExecutionModel::MeshEXT => None,
+            _ => Some((1, 0)),
+        }
+    }
 }
 
 /// SPIR-V operand kind: [AddressingModel](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_addressing_model_a_addressing_model)
@@ -160,6 +696,54 @@ pub enum AddressingModel {
     Physical64 = 2,
 }
 
+impl fmt::Display for AddressingModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            AddressingModel::Logical => "Logical",
+            AddressingModel::Physical32 => "Physical32",
+            AddressingModel::Physical64 => "Physical64",
+        })
+    }
+}
+
+impl str::FromStr for AddressingModel {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Logical" => Ok(AddressingModel::Logical),
+            "Physical32" => Ok(AddressingModel::Physical32),
+            "Physical64" => Ok(AddressingModel::Physical64),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl AddressingModel {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            AddressingModel::Physical32 => &[Capability::Addresses],
+            AddressingModel::Physical64 => &[Capability::Addresses],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [MemoryModel](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_memory_model_a_memory_model)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -170,6 +754,57 @@ pub enum MemoryModel {
     VulkanKHR = 3,
 }
 
+impl fmt::Display for MemoryModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            MemoryModel::Simple => "Simple",
+            MemoryModel::GLSL450 => "GLSL450",
+            MemoryModel::OpenCL => "OpenCL",
+            MemoryModel::VulkanKHR => "VulkanKHR",
+        })
+    }
+}
+
+impl str::FromStr for MemoryModel {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Simple" => Ok(MemoryModel::Simple),
+            "GLSL450" => Ok(MemoryModel::GLSL450),
+            "OpenCL" => Ok(MemoryModel::OpenCL),
+            "VulkanKHR" => Ok(MemoryModel::VulkanKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl MemoryModel {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            MemoryModel::Simple => &[Capability::Shader],
+            MemoryModel::GLSL450 => &[Capability::Shader],
+            MemoryModel::OpenCL => &[Capability::Kernel],
+            MemoryModel::VulkanKHR => &[Capability::VulkanMemoryModelKHR],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [ExecutionMode](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_execution_mode_a_execution_mode)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -213,7 +848,228 @@ pub enum ExecutionMode {
     LocalSizeId = 38,
     LocalSizeHintId = 39,
     PostDepthCoverage = 4446,
+    DenormPreserve = 4459,
+    DenormFlushToZero = 4460,
+    SignedZeroInfNanPreserve = 4461,
+    RoundingModeRTE = 4462,
+    RoundingModeRTZ = 4463,
     StencilRefReplacingEXT = 5027,
+    OutputLinesEXT = 5269,
+    OutputPrimitivesEXT = 5270,
+    OutputTrianglesEXT = 5298,
+}
+
+impl fmt::Display for ExecutionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ExecutionMode::Invocations => "Invocations",
+            ExecutionMode::SpacingEqual => "SpacingEqual",
+            ExecutionMode::SpacingFractionalEven => "SpacingFractionalEven",
+            ExecutionMode::SpacingFractionalOdd => "SpacingFractionalOdd",
+            ExecutionMode::VertexOrderCw => "VertexOrderCw",
+            ExecutionMode::VertexOrderCcw => "VertexOrderCcw",
+            ExecutionMode::PixelCenterInteger => "PixelCenterInteger",
+            ExecutionMode::OriginUpperLeft => "OriginUpperLeft",
+            ExecutionMode::OriginLowerLeft => "OriginLowerLeft",
+            ExecutionMode::EarlyFragmentTests => "EarlyFragmentTests",
+            ExecutionMode::PointMode => "PointMode",
+            ExecutionMode::Xfb => "Xfb",
+            ExecutionMode::DepthReplacing => "DepthReplacing",
+            ExecutionMode::DepthGreater => "DepthGreater",
+            ExecutionMode::DepthLess => "DepthLess",
+            ExecutionMode::DepthUnchanged => "DepthUnchanged",
+            ExecutionMode::LocalSize => "LocalSize",
+            ExecutionMode::LocalSizeHint => "LocalSizeHint",
+            ExecutionMode::InputPoints => "InputPoints",
+            ExecutionMode::InputLines => "InputLines",
+            ExecutionMode::InputLinesAdjacency => "InputLinesAdjacency",
+            ExecutionMode::Triangles => "Triangles",
+            ExecutionMode::InputTrianglesAdjacency => "InputTrianglesAdjacency",
+            ExecutionMode::Quads => "Quads",
+            ExecutionMode::Isolines => "Isolines",
+            ExecutionMode::OutputVertices => "OutputVertices",
+            ExecutionMode::OutputPoints => "OutputPoints",
+            ExecutionMode::OutputLineStrip => "OutputLineStrip",
+            ExecutionMode::OutputTriangleStrip => "OutputTriangleStrip",
+            ExecutionMode::VecTypeHint => "VecTypeHint",
+            ExecutionMode::ContractionOff => "ContractionOff",
+            ExecutionMode::Initializer => "Initializer",
+            ExecutionMode::Finalizer => "Finalizer",
+            ExecutionMode::SubgroupSize => "SubgroupSize",
+            ExecutionMode::SubgroupsPerWorkgroup => "SubgroupsPerWorkgroup",
+            ExecutionMode::SubgroupsPerWorkgroupId => "SubgroupsPerWorkgroupId",
+            ExecutionMode::LocalSizeId => "LocalSizeId",
+            ExecutionMode::LocalSizeHintId => "LocalSizeHintId",
+            ExecutionMode::PostDepthCoverage => "PostDepthCoverage",
+            ExecutionMode::DenormPreserve => "DenormPreserve",
+            ExecutionMode::DenormFlushToZero => "DenormFlushToZero",
+            ExecutionMode::SignedZeroInfNanPreserve => "SignedZeroInfNanPreserve",
+            ExecutionMode::RoundingModeRTE => "RoundingModeRTE",
+            ExecutionMode::RoundingModeRTZ => "RoundingModeRTZ",
+            ExecutionMode::StencilRefReplacingEXT => "StencilRefReplacingEXT",
+            ExecutionMode::OutputLinesEXT => "OutputLinesEXT",
+            ExecutionMode::OutputPrimitivesEXT => "OutputPrimitivesEXT",
+            ExecutionMode::OutputTrianglesEXT => "OutputTrianglesEXT",
+        })
+    }
+}
+
+impl str::FromStr for ExecutionMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Invocations" => Ok(ExecutionMode::Invocations),
+            "SpacingEqual" => Ok(ExecutionMode::SpacingEqual),
+            "SpacingFractionalEven" => Ok(ExecutionMode::SpacingFractionalEven),
+            "SpacingFractionalOdd" => Ok(ExecutionMode::SpacingFractionalOdd),
+            "VertexOrderCw" => Ok(ExecutionMode::VertexOrderCw),
+            "VertexOrderCcw" => Ok(ExecutionMode::VertexOrderCcw),
+            "PixelCenterInteger" => Ok(ExecutionMode::PixelCenterInteger),
+            "OriginUpperLeft" => Ok(ExecutionMode::OriginUpperLeft),
+            "OriginLowerLeft" => Ok(ExecutionMode::OriginLowerLeft),
+            "EarlyFragmentTests" => Ok(ExecutionMode::EarlyFragmentTests),
+            "PointMode" => Ok(ExecutionMode::PointMode),
+            "Xfb" => Ok(ExecutionMode::Xfb),
+            "DepthReplacing" => Ok(ExecutionMode::DepthReplacing),
+            "DepthGreater" => Ok(ExecutionMode::DepthGreater),
+            "DepthLess" => Ok(ExecutionMode::DepthLess),
+            "DepthUnchanged" => Ok(ExecutionMode::DepthUnchanged),
+            "LocalSize" => Ok(ExecutionMode::LocalSize),
+            "LocalSizeHint" => Ok(ExecutionMode::LocalSizeHint),
+            "InputPoints" => Ok(ExecutionMode::InputPoints),
+            "InputLines" => Ok(ExecutionMode::InputLines),
+            "InputLinesAdjacency" => Ok(ExecutionMode::InputLinesAdjacency),
+            "Triangles" => Ok(ExecutionMode::Triangles),
+            "InputTrianglesAdjacency" => Ok(ExecutionMode::InputTrianglesAdjacency),
+            "Quads" => Ok(ExecutionMode::Quads),
+            "Isolines" => Ok(ExecutionMode::Isolines),
+            "OutputVertices" => Ok(ExecutionMode::OutputVertices),
+            "OutputPoints" => Ok(ExecutionMode::OutputPoints),
+            "OutputLineStrip" => Ok(ExecutionMode::OutputLineStrip),
+            "OutputTriangleStrip" => Ok(ExecutionMode::OutputTriangleStrip),
+            "VecTypeHint" => Ok(ExecutionMode::VecTypeHint),
+            "ContractionOff" => Ok(ExecutionMode::ContractionOff),
+            "Initializer" => Ok(ExecutionMode::Initializer),
+            "Finalizer" => Ok(ExecutionMode::Finalizer),
+            "SubgroupSize" => Ok(ExecutionMode::SubgroupSize),
+            "SubgroupsPerWorkgroup" => Ok(ExecutionMode::SubgroupsPerWorkgroup),
+            "SubgroupsPerWorkgroupId" => Ok(ExecutionMode::SubgroupsPerWorkgroupId),
+            "LocalSizeId" => Ok(ExecutionMode::LocalSizeId),
+            "LocalSizeHintId" => Ok(ExecutionMode::LocalSizeHintId),
+            "PostDepthCoverage" => Ok(ExecutionMode::PostDepthCoverage),
+            "DenormPreserve" => Ok(ExecutionMode::DenormPreserve),
+            "DenormFlushToZero" => Ok(ExecutionMode::DenormFlushToZero),
+            "SignedZeroInfNanPreserve" => Ok(ExecutionMode::SignedZeroInfNanPreserve),
+            "RoundingModeRTE" => Ok(ExecutionMode::RoundingModeRTE),
+            "RoundingModeRTZ" => Ok(ExecutionMode::RoundingModeRTZ),
+            "StencilRefReplacingEXT" => Ok(ExecutionMode::StencilRefReplacingEXT),
+            "OutputLinesEXT" => Ok(ExecutionMode::OutputLinesEXT),
+            "OutputPrimitivesEXT" => Ok(ExecutionMode::OutputPrimitivesEXT),
+            "OutputTrianglesEXT" => Ok(ExecutionMode::OutputTrianglesEXT),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl ExecutionMode {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            ExecutionMode::Invocations => &[Capability::Geometry],
+            ExecutionMode::SpacingEqual => &[Capability::Tessellation],
+            ExecutionMode::SpacingFractionalEven => &[Capability::Tessellation],
+            ExecutionMode::SpacingFractionalOdd => &[Capability::Tessellation],
+            ExecutionMode::VertexOrderCw => &[Capability::Tessellation],
+            ExecutionMode::VertexOrderCcw => &[Capability::Tessellation],
+            ExecutionMode::PixelCenterInteger => &[Capability::Shader],
+            ExecutionMode::OriginUpperLeft => &[Capability::Shader],
+            ExecutionMode::OriginLowerLeft => &[Capability::Shader],
+            ExecutionMode::EarlyFragmentTests => &[Capability::Shader],
+            ExecutionMode::PointMode => &[Capability::Tessellation],
+            ExecutionMode::Xfb => &[Capability::TransformFeedback],
+            ExecutionMode::DepthReplacing => &[Capability::Shader],
+            ExecutionMode::DepthGreater => &[Capability::Shader],
+            ExecutionMode::DepthLess => &[Capability::Shader],
+            ExecutionMode::DepthUnchanged => &[Capability::Shader],
+            ExecutionMode::LocalSizeHint => &[Capability::Kernel],
+            ExecutionMode::InputPoints => &[Capability::Geometry],
+            ExecutionMode::InputLines => &[Capability::Geometry],
+            ExecutionMode::InputLinesAdjacency => &[Capability::Geometry],
+            ExecutionMode::Triangles => &[Capability::Geometry, Capability::Tessellation],
+            ExecutionMode::InputTrianglesAdjacency => &[Capability::Geometry],
+            ExecutionMode::Quads => &[Capability::Tessellation],
+            ExecutionMode::Isolines => &[Capability::Tessellation],
+            ExecutionMode::OutputVertices => &[Capability::Geometry, Capability::Tessellation, Capability::MeshShadingEXT],
+            ExecutionMode::OutputPoints => &[Capability::Geometry, Capability::MeshShadingEXT],
+            ExecutionMode::OutputLineStrip => &[Capability::Geometry],
+            ExecutionMode::OutputTriangleStrip => &[Capability::Geometry],
+            ExecutionMode::VecTypeHint => &[Capability::Kernel],
+            ExecutionMode::ContractionOff => &[Capability::Kernel],
+            ExecutionMode::Initializer => &[Capability::Kernel],
+            ExecutionMode::Finalizer => &[Capability::Kernel],
+            ExecutionMode::SubgroupSize => &[Capability::SubgroupDispatch],
+            ExecutionMode::SubgroupsPerWorkgroup => &[Capability::SubgroupDispatch],
+            ExecutionMode::SubgroupsPerWorkgroupId => &[Capability::SubgroupDispatch],
+            ExecutionMode::LocalSizeHintId => &[Capability::Kernel],
+            ExecutionMode::PostDepthCoverage => &[Capability::SampleMaskPostDepthCoverage],
+            ExecutionMode::DenormPreserve => &[Capability::DenormPreserve],
+            ExecutionMode::DenormFlushToZero => &[Capability::DenormFlushToZero],
+            ExecutionMode::SignedZeroInfNanPreserve => &[Capability::SignedZeroInfNanPreserve],
+            ExecutionMode::RoundingModeRTE => &[Capability::RoundingModeRTE],
+            ExecutionMode::RoundingModeRTZ => &[Capability::RoundingModeRTZ],
+            ExecutionMode::StencilRefReplacingEXT => &[Capability::StencilExportEXT],
+            ExecutionMode::OutputLinesEXT => &[Capability::MeshShadingEXT],
+            ExecutionMode::OutputPrimitivesEXT => &[Capability::MeshShadingEXT],
+            ExecutionMode::OutputTrianglesEXT => &[Capability::MeshShadingEXT],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            ExecutionMode::PostDepthCoverage => &["SPV_KHR_post_depth_coverage"],
+            ExecutionMode::DenormPreserve => &["SPV_KHR_float_controls"],
+            ExecutionMode::DenormFlushToZero => &["SPV_KHR_float_controls"],
+            ExecutionMode::SignedZeroInfNanPreserve => &["SPV_KHR_float_controls"],
+            ExecutionMode::RoundingModeRTE => &["SPV_KHR_float_controls"],
+            ExecutionMode::RoundingModeRTZ => &["SPV_KHR_float_controls"],
+            ExecutionMode::StencilRefReplacingEXT => &["SPV_EXT_shader_stencil_export"],
+            ExecutionMode::OutputLinesEXT => &["SPV_EXT_mesh_shader"],
+            ExecutionMode::OutputPrimitivesEXT => &["SPV_EXT_mesh_shader"],
+            ExecutionMode::OutputTrianglesEXT => &["SPV_EXT_mesh_shader"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            ExecutionMode::Initializer => Some((1, 1)),
+            ExecutionMode::Finalizer => Some((1, 1)),
+            ExecutionMode::SubgroupSize => Some((1, 1)),
+            ExecutionMode::SubgroupsPerWorkgroup => Some((1, 1)),
+            ExecutionMode::SubgroupsPerWorkgroupId => Some((1, 2)),
+            ExecutionMode::LocalSizeId => Some((1, 2)),
+            ExecutionMode::LocalSizeHintId => Some((1, 2)),
+            ExecutionMode::PostDepthCoverage => None,
+            ExecutionMode::DenormPreserve => Some((1, 4)),
+            ExecutionMode::DenormFlushToZero => Some((1, 4)),
+            ExecutionMode::SignedZeroInfNanPreserve => Some((1, 4)),
+            ExecutionMode::RoundingModeRTE => Some((1, 4)),
+            ExecutionMode::RoundingModeRTZ => Some((1, 4)),
+            ExecutionMode::StencilRefReplacingEXT => None,
+            ExecutionMode::OutputLinesEXT => None,
+            ExecutionMode::OutputPrimitivesEXT => None,
+            ExecutionMode::OutputTrianglesEXT => None,
+            _ => Some((1, 0)),
+        }
+    }
 }
 
 /// SPIR-V operand kind: [StorageClass](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_storage_class_a_storage_class)
@@ -233,6 +1089,127 @@ pub enum StorageClass {
     AtomicCounter = 10,
     Image = 11,
     StorageBuffer = 12,
+    CallableDataKHR = 5328,
+    IncomingCallableDataKHR = 5329,
+    RayPayloadKHR = 5338,
+    HitAttributeKHR = 5339,
+    IncomingRayPayloadKHR = 5342,
+    ShaderRecordBufferKHR = 5343,
+    TaskPayloadWorkgroupEXT = 5402,
+}
+
+impl fmt::Display for StorageClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            StorageClass::UniformConstant => "UniformConstant",
+            StorageClass::Input => "Input",
+            StorageClass::Uniform => "Uniform",
+            StorageClass::Output => "Output",
+            StorageClass::Workgroup => "Workgroup",
+            StorageClass::CrossWorkgroup => "CrossWorkgroup",
+            StorageClass::Private => "Private",
+            StorageClass::Function => "Function",
+            StorageClass::Generic => "Generic",
+            StorageClass::PushConstant => "PushConstant",
+            StorageClass::AtomicCounter => "AtomicCounter",
+            StorageClass::Image => "Image",
+            StorageClass::StorageBuffer => "StorageBuffer",
+            StorageClass::CallableDataKHR => "CallableDataKHR",
+            StorageClass::IncomingCallableDataKHR => "IncomingCallableDataKHR",
+            StorageClass::RayPayloadKHR => "RayPayloadKHR",
+            StorageClass::HitAttributeKHR => "HitAttributeKHR",
+            StorageClass::IncomingRayPayloadKHR => "IncomingRayPayloadKHR",
+            StorageClass::ShaderRecordBufferKHR => "ShaderRecordBufferKHR",
+            StorageClass::TaskPayloadWorkgroupEXT => "TaskPayloadWorkgroupEXT",
+        })
+    }
+}
+
+impl str::FromStr for StorageClass {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "UniformConstant" => Ok(StorageClass::UniformConstant),
+            "Input" => Ok(StorageClass::Input),
+            "Uniform" => Ok(StorageClass::Uniform),
+            "Output" => Ok(StorageClass::Output),
+            "Workgroup" => Ok(StorageClass::Workgroup),
+            "CrossWorkgroup" => Ok(StorageClass::CrossWorkgroup),
+            "Private" => Ok(StorageClass::Private),
+            "Function" => Ok(StorageClass::Function),
+            "Generic" => Ok(StorageClass::Generic),
+            "PushConstant" => Ok(StorageClass::PushConstant),
+            "AtomicCounter" => Ok(StorageClass::AtomicCounter),
+            "Image" => Ok(StorageClass::Image),
+            "StorageBuffer" => Ok(StorageClass::StorageBuffer),
+            "CallableDataKHR" => Ok(StorageClass::CallableDataKHR),
+            "IncomingCallableDataKHR" => Ok(StorageClass::IncomingCallableDataKHR),
+            "RayPayloadKHR" => Ok(StorageClass::RayPayloadKHR),
+            "HitAttributeKHR" => Ok(StorageClass::HitAttributeKHR),
+            "IncomingRayPayloadKHR" => Ok(StorageClass::IncomingRayPayloadKHR),
+            "ShaderRecordBufferKHR" => Ok(StorageClass::ShaderRecordBufferKHR),
+            "TaskPayloadWorkgroupEXT" => Ok(StorageClass::TaskPayloadWorkgroupEXT),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl StorageClass {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            StorageClass::Uniform => &[Capability::Shader],
+            StorageClass::Output => &[Capability::Shader],
+            StorageClass::Private => &[Capability::Shader],
+            StorageClass::Generic => &[Capability::GenericPointer],
+            StorageClass::PushConstant => &[Capability::Shader],
+            StorageClass::AtomicCounter => &[Capability::AtomicStorage],
+            StorageClass::StorageBuffer => &[Capability::Shader],
+            StorageClass::CallableDataKHR => &[Capability::RayTracingKHR],
+            StorageClass::IncomingCallableDataKHR => &[Capability::RayTracingKHR],
+            StorageClass::RayPayloadKHR => &[Capability::RayTracingKHR],
+            StorageClass::HitAttributeKHR => &[Capability::RayTracingKHR],
+            StorageClass::IncomingRayPayloadKHR => &[Capability::RayTracingKHR],
+            StorageClass::ShaderRecordBufferKHR => &[Capability::RayTracingKHR],
+            StorageClass::TaskPayloadWorkgroupEXT => &[Capability::MeshShadingEXT],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            StorageClass::StorageBuffer => &["SPV_KHR_storage_buffer_storage_class", "SPV_KHR_variable_pointers"],
+            StorageClass::CallableDataKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::IncomingCallableDataKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::RayPayloadKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::HitAttributeKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::IncomingRayPayloadKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::ShaderRecordBufferKHR => &["SPV_KHR_ray_tracing"],
+            StorageClass::TaskPayloadWorkgroupEXT => &["SPV_EXT_mesh_shader"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            StorageClass::StorageBuffer => Some((1, 3)),
+            StorageClass::CallableDataKHR => None,
+            StorageClass::IncomingCallableDataKHR => None,
+            StorageClass::RayPayloadKHR => None,
+            StorageClass::HitAttributeKHR => None,
+            StorageClass::IncomingRayPayloadKHR => None,
+            StorageClass::ShaderRecordBufferKHR => None,
+            StorageClass::TaskPayloadWorkgroupEXT => None,
+            _ => Some((1, 0)),
+        }
+    }
 }
 
 /// SPIR-V operand kind: [Dim](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_dim_a_dim)
@@ -248,6 +1225,66 @@ pub enum Dim {
     DimSubpassData = 6,
 }
 
+impl fmt::Display for Dim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Dim::Dim1D => "1D",
+            Dim::Dim2D => "2D",
+            Dim::Dim3D => "3D",
+            Dim::DimCube => "Cube",
+            Dim::DimRect => "Rect",
+            Dim::DimBuffer => "Buffer",
+            Dim::DimSubpassData => "SubpassData",
+        })
+    }
+}
+
+impl str::FromStr for Dim {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1D" => Ok(Dim::Dim1D),
+            "2D" => Ok(Dim::Dim2D),
+            "3D" => Ok(Dim::Dim3D),
+            "Cube" => Ok(Dim::DimCube),
+            "Rect" => Ok(Dim::DimRect),
+            "Buffer" => Ok(Dim::DimBuffer),
+            "SubpassData" => Ok(Dim::DimSubpassData),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl Dim {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            Dim::Dim1D => &[Capability::Sampled1D, Capability::Image1D],
+            Dim::Dim2D => &[Capability::Shader, Capability::Kernel, Capability::ImageMSArray],
+            Dim::DimCube => &[Capability::Shader, Capability::ImageCubeArray],
+            Dim::DimRect => &[Capability::SampledRect, Capability::ImageRect],
+            Dim::DimBuffer => &[Capability::SampledBuffer, Capability::ImageBuffer],
+            Dim::DimSubpassData => &[Capability::InputAttachment],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [SamplerAddressingMode](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_sampler_addressing_mode_a_sampler_addressing_mode)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -259,6 +1296,60 @@ pub enum SamplerAddressingMode {
     RepeatMirrored = 4,
 }
 
+impl fmt::Display for SamplerAddressingMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            SamplerAddressingMode::None => "None",
+            SamplerAddressingMode::ClampToEdge => "ClampToEdge",
+            SamplerAddressingMode::Clamp => "Clamp",
+            SamplerAddressingMode::Repeat => "Repeat",
+            SamplerAddressingMode::RepeatMirrored => "RepeatMirrored",
+        })
+    }
+}
+
+impl str::FromStr for SamplerAddressingMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" => Ok(SamplerAddressingMode::None),
+            "ClampToEdge" => Ok(SamplerAddressingMode::ClampToEdge),
+            "Clamp" => Ok(SamplerAddressingMode::Clamp),
+            "Repeat" => Ok(SamplerAddressingMode::Repeat),
+            "RepeatMirrored" => Ok(SamplerAddressingMode::RepeatMirrored),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl SamplerAddressingMode {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            SamplerAddressingMode::None => &[Capability::Kernel],
+            SamplerAddressingMode::ClampToEdge => &[Capability::Kernel],
+            SamplerAddressingMode::Clamp => &[Capability::Kernel],
+            SamplerAddressingMode::Repeat => &[Capability::Kernel],
+            SamplerAddressingMode::RepeatMirrored => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [SamplerFilterMode](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_sampler_filter_mode_a_sampler_filter_mode)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -267,6 +1358,51 @@ pub enum SamplerFilterMode {
     Linear = 1,
 }
 
+impl fmt::Display for SamplerFilterMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            SamplerFilterMode::Nearest => "Nearest",
+            SamplerFilterMode::Linear => "Linear",
+        })
+    }
+}
+
+impl str::FromStr for SamplerFilterMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Nearest" => Ok(SamplerFilterMode::Nearest),
+            "Linear" => Ok(SamplerFilterMode::Linear),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl SamplerFilterMode {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            SamplerFilterMode::Nearest => &[Capability::Kernel],
+            SamplerFilterMode::Linear => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [ImageFormat](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_image_format_a_image_format)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -313,6 +1449,165 @@ pub enum ImageFormat {
     R8ui = 39,
 }
 
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ImageFormat::Unknown => "Unknown",
+            ImageFormat::Rgba32f => "Rgba32f",
+            ImageFormat::Rgba16f => "Rgba16f",
+            ImageFormat::R32f => "R32f",
+            ImageFormat::Rgba8 => "Rgba8",
+            ImageFormat::Rgba8Snorm => "Rgba8Snorm",
+            ImageFormat::Rg32f => "Rg32f",
+            ImageFormat::Rg16f => "Rg16f",
+            ImageFormat::R11fG11fB10f => "R11fG11fB10f",
+            ImageFormat::R16f => "R16f",
+            ImageFormat::Rgba16 => "Rgba16",
+            ImageFormat::Rgb10A2 => "Rgb10A2",
+            ImageFormat::Rg16 => "Rg16",
+            ImageFormat::Rg8 => "Rg8",
+            ImageFormat::R16 => "R16",
+            ImageFormat::R8 => "R8",
+            ImageFormat::Rgba16Snorm => "Rgba16Snorm",
+            ImageFormat::Rg16Snorm => "Rg16Snorm",
+            ImageFormat::Rg8Snorm => "Rg8Snorm",
+            ImageFormat::R16Snorm => "R16Snorm",
+            ImageFormat::R8Snorm => "R8Snorm",
+            ImageFormat::Rgba32i => "Rgba32i",
+            ImageFormat::Rgba16i => "Rgba16i",
+            ImageFormat::Rgba8i => "Rgba8i",
+            ImageFormat::R32i => "R32i",
+            ImageFormat::Rg32i => "Rg32i",
+            ImageFormat::Rg16i => "Rg16i",
+            ImageFormat::Rg8i => "Rg8i",
+            ImageFormat::R16i => "R16i",
+            ImageFormat::R8i => "R8i",
+            ImageFormat::Rgba32ui => "Rgba32ui",
+            ImageFormat::Rgba16ui => "Rgba16ui",
+            ImageFormat::Rgba8ui => "Rgba8ui",
+            ImageFormat::R32ui => "R32ui",
+            ImageFormat::Rgb10a2ui => "Rgb10a2ui",
+            ImageFormat::Rg32ui => "Rg32ui",
+            ImageFormat::Rg16ui => "Rg16ui",
+            ImageFormat::Rg8ui => "Rg8ui",
+            ImageFormat::R16ui => "R16ui",
+            ImageFormat::R8ui => "R8ui",
+        })
+    }
+}
+
+impl str::FromStr for ImageFormat {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Unknown" => Ok(ImageFormat::Unknown),
+            "Rgba32f" => Ok(ImageFormat::Rgba32f),
+            "Rgba16f" => Ok(ImageFormat::Rgba16f),
+            "R32f" => Ok(ImageFormat::R32f),
+            "Rgba8" => Ok(ImageFormat::Rgba8),
+            "Rgba8Snorm" => Ok(ImageFormat::Rgba8Snorm),
+            "Rg32f" => Ok(ImageFormat::Rg32f),
+            "Rg16f" => Ok(ImageFormat::Rg16f),
+            "R11fG11fB10f" => Ok(ImageFormat::R11fG11fB10f),
+            "R16f" => Ok(ImageFormat::R16f),
+            "Rgba16" => Ok(ImageFormat::Rgba16),
+            "Rgb10A2" => Ok(ImageFormat::Rgb10A2),
+            "Rg16" => Ok(ImageFormat::Rg16),
+            "Rg8" => Ok(ImageFormat::Rg8),
+            "R16" => Ok(ImageFormat::R16),
+            "R8" => Ok(ImageFormat::R8),
+            "Rgba16Snorm" => Ok(ImageFormat::Rgba16Snorm),
+            "Rg16Snorm" => Ok(ImageFormat::Rg16Snorm),
+            "Rg8Snorm" => Ok(ImageFormat::Rg8Snorm),
+            "R16Snorm" => Ok(ImageFormat::R16Snorm),
+            "R8Snorm" => Ok(ImageFormat::R8Snorm),
+            "Rgba32i" => Ok(ImageFormat::Rgba32i),
+            "Rgba16i" => Ok(ImageFormat::Rgba16i),
+            "Rgba8i" => Ok(ImageFormat::Rgba8i),
+            "R32i" => Ok(ImageFormat::R32i),
+            "Rg32i" => Ok(ImageFormat::Rg32i),
+            "Rg16i" => Ok(ImageFormat::Rg16i),
+            "Rg8i" => Ok(ImageFormat::Rg8i),
+            "R16i" => Ok(ImageFormat::R16i),
+            "R8i" => Ok(ImageFormat::R8i),
+            "Rgba32ui" => Ok(ImageFormat::Rgba32ui),
+            "Rgba16ui" => Ok(ImageFormat::Rgba16ui),
+            "Rgba8ui" => Ok(ImageFormat::Rgba8ui),
+            "R32ui" => Ok(ImageFormat::R32ui),
+            "Rgb10a2ui" => Ok(ImageFormat::Rgb10a2ui),
+            "Rg32ui" => Ok(ImageFormat::Rg32ui),
+            "Rg16ui" => Ok(ImageFormat::Rg16ui),
+            "Rg8ui" => Ok(ImageFormat::Rg8ui),
+            "R16ui" => Ok(ImageFormat::R16ui),
+            "R8ui" => Ok(ImageFormat::R8ui),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl ImageFormat {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            ImageFormat::Rgba32f => &[Capability::Shader],
+            ImageFormat::Rgba16f => &[Capability::Shader],
+            ImageFormat::R32f => &[Capability::Shader],
+            ImageFormat::Rgba8 => &[Capability::Shader],
+            ImageFormat::Rgba8Snorm => &[Capability::Shader],
+            ImageFormat::Rg32f => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg16f => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R11fG11fB10f => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R16f => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rgba16 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rgb10A2 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg16 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg8 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R16 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R8 => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rgba16Snorm => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg16Snorm => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg8Snorm => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R16Snorm => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R8Snorm => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rgba32i => &[Capability::Shader],
+            ImageFormat::Rgba16i => &[Capability::Shader],
+            ImageFormat::Rgba8i => &[Capability::Shader],
+            ImageFormat::R32i => &[Capability::Shader],
+            ImageFormat::Rg32i => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg16i => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg8i => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R16i => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R8i => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rgba32ui => &[Capability::Shader],
+            ImageFormat::Rgba16ui => &[Capability::Shader],
+            ImageFormat::Rgba8ui => &[Capability::Shader],
+            ImageFormat::R32ui => &[Capability::Shader],
+            ImageFormat::Rgb10a2ui => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg32ui => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg16ui => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::Rg8ui => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R16ui => &[Capability::StorageImageExtendedFormats],
+            ImageFormat::R8ui => &[Capability::StorageImageExtendedFormats],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [ImageChannelOrder](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_image_channel_order_a_image_channel_order)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -339,6 +1634,105 @@ pub enum ImageChannelOrder {
     ABGR = 19,
 }
 
+impl fmt::Display for ImageChannelOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ImageChannelOrder::R => "R",
+            ImageChannelOrder::A => "A",
+            ImageChannelOrder::RG => "RG",
+            ImageChannelOrder::RA => "RA",
+            ImageChannelOrder::RGB => "RGB",
+            ImageChannelOrder::RGBA => "RGBA",
+            ImageChannelOrder::BGRA => "BGRA",
+            ImageChannelOrder::ARGB => "ARGB",
+            ImageChannelOrder::Intensity => "Intensity",
+            ImageChannelOrder::Luminance => "Luminance",
+            ImageChannelOrder::Rx => "Rx",
+            ImageChannelOrder::RGx => "RGx",
+            ImageChannelOrder::RGBx => "RGBx",
+            ImageChannelOrder::Depth => "Depth",
+            ImageChannelOrder::DepthStencil => "DepthStencil",
+            ImageChannelOrder::sRGB => "sRGB",
+            ImageChannelOrder::sRGBx => "sRGBx",
+            ImageChannelOrder::sRGBA => "sRGBA",
+            ImageChannelOrder::sBGRA => "sBGRA",
+            ImageChannelOrder::ABGR => "ABGR",
+        })
+    }
+}
+
+impl str::FromStr for ImageChannelOrder {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "R" => Ok(ImageChannelOrder::R),
+            "A" => Ok(ImageChannelOrder::A),
+            "RG" => Ok(ImageChannelOrder::RG),
+            "RA" => Ok(ImageChannelOrder::RA),
+            "RGB" => Ok(ImageChannelOrder::RGB),
+            "RGBA" => Ok(ImageChannelOrder::RGBA),
+            "BGRA" => Ok(ImageChannelOrder::BGRA),
+            "ARGB" => Ok(ImageChannelOrder::ARGB),
+            "Intensity" => Ok(ImageChannelOrder::Intensity),
+            "Luminance" => Ok(ImageChannelOrder::Luminance),
+            "Rx" => Ok(ImageChannelOrder::Rx),
+            "RGx" => Ok(ImageChannelOrder::RGx),
+            "RGBx" => Ok(ImageChannelOrder::RGBx),
+            "Depth" => Ok(ImageChannelOrder::Depth),
+            "DepthStencil" => Ok(ImageChannelOrder::DepthStencil),
+            "sRGB" => Ok(ImageChannelOrder::sRGB),
+            "sRGBx" => Ok(ImageChannelOrder::sRGBx),
+            "sRGBA" => Ok(ImageChannelOrder::sRGBA),
+            "sBGRA" => Ok(ImageChannelOrder::sBGRA),
+            "ABGR" => Ok(ImageChannelOrder::ABGR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl ImageChannelOrder {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            ImageChannelOrder::R => &[Capability::Kernel],
+            ImageChannelOrder::A => &[Capability::Kernel],
+            ImageChannelOrder::RG => &[Capability::Kernel],
+            ImageChannelOrder::RA => &[Capability::Kernel],
+            ImageChannelOrder::RGB => &[Capability::Kernel],
+            ImageChannelOrder::RGBA => &[Capability::Kernel],
+            ImageChannelOrder::BGRA => &[Capability::Kernel],
+            ImageChannelOrder::ARGB => &[Capability::Kernel],
+            ImageChannelOrder::Intensity => &[Capability::Kernel],
+            ImageChannelOrder::Luminance => &[Capability::Kernel],
+            ImageChannelOrder::Rx => &[Capability::Kernel],
+            ImageChannelOrder::RGx => &[Capability::Kernel],
+            ImageChannelOrder::RGBx => &[Capability::Kernel],
+            ImageChannelOrder::Depth => &[Capability::Kernel],
+            ImageChannelOrder::DepthStencil => &[Capability::Kernel],
+            ImageChannelOrder::sRGB => &[Capability::Kernel],
+            ImageChannelOrder::sRGBx => &[Capability::Kernel],
+            ImageChannelOrder::sRGBA => &[Capability::Kernel],
+            ImageChannelOrder::sBGRA => &[Capability::Kernel],
+            ImageChannelOrder::ABGR => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [ImageChannelDataType](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_image_channel_data_type_a_image_channel_data_type)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -362,6 +1756,96 @@ pub enum ImageChannelDataType {
     UnormInt101010_2 = 16,
 }
 
+impl fmt::Display for ImageChannelDataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ImageChannelDataType::SnormInt8 => "SnormInt8",
+            ImageChannelDataType::SnormInt16 => "SnormInt16",
+            ImageChannelDataType::UnormInt8 => "UnormInt8",
+            ImageChannelDataType::UnormInt16 => "UnormInt16",
+            ImageChannelDataType::UnormShort565 => "UnormShort565",
+            ImageChannelDataType::UnormShort555 => "UnormShort555",
+            ImageChannelDataType::UnormInt101010 => "UnormInt101010",
+            ImageChannelDataType::SignedInt8 => "SignedInt8",
+            ImageChannelDataType::SignedInt16 => "SignedInt16",
+            ImageChannelDataType::SignedInt32 => "SignedInt32",
+            ImageChannelDataType::UnsignedInt8 => "UnsignedInt8",
+            ImageChannelDataType::UnsignedInt16 => "UnsignedInt16",
+            ImageChannelDataType::UnsignedInt32 => "UnsignedInt32",
+            ImageChannelDataType::HalfFloat => "HalfFloat",
+            ImageChannelDataType::Float => "Float",
+            ImageChannelDataType::UnormInt24 => "UnormInt24",
+            ImageChannelDataType::UnormInt101010_2 => "UnormInt101010_2",
+        })
+    }
+}
+
+impl str::FromStr for ImageChannelDataType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SnormInt8" => Ok(ImageChannelDataType::SnormInt8),
+            "SnormInt16" => Ok(ImageChannelDataType::SnormInt16),
+            "UnormInt8" => Ok(ImageChannelDataType::UnormInt8),
+            "UnormInt16" => Ok(ImageChannelDataType::UnormInt16),
+            "UnormShort565" => Ok(ImageChannelDataType::UnormShort565),
+            "UnormShort555" => Ok(ImageChannelDataType::UnormShort555),
+            "UnormInt101010" => Ok(ImageChannelDataType::UnormInt101010),
+            "SignedInt8" => Ok(ImageChannelDataType::SignedInt8),
+            "SignedInt16" => Ok(ImageChannelDataType::SignedInt16),
+            "SignedInt32" => Ok(ImageChannelDataType::SignedInt32),
+            "UnsignedInt8" => Ok(ImageChannelDataType::UnsignedInt8),
+            "UnsignedInt16" => Ok(ImageChannelDataType::UnsignedInt16),
+            "UnsignedInt32" => Ok(ImageChannelDataType::UnsignedInt32),
+            "HalfFloat" => Ok(ImageChannelDataType::HalfFloat),
+            "Float" => Ok(ImageChannelDataType::Float),
+            "UnormInt24" => Ok(ImageChannelDataType::UnormInt24),
+            "UnormInt101010_2" => Ok(ImageChannelDataType::UnormInt101010_2),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl ImageChannelDataType {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            ImageChannelDataType::SnormInt8 => &[Capability::Kernel],
+            ImageChannelDataType::SnormInt16 => &[Capability::Kernel],
+            ImageChannelDataType::UnormInt8 => &[Capability::Kernel],
+            ImageChannelDataType::UnormInt16 => &[Capability::Kernel],
+            ImageChannelDataType::UnormShort565 => &[Capability::Kernel],
+            ImageChannelDataType::UnormShort555 => &[Capability::Kernel],
+            ImageChannelDataType::UnormInt101010 => &[Capability::Kernel],
+            ImageChannelDataType::SignedInt8 => &[Capability::Kernel],
+            ImageChannelDataType::SignedInt16 => &[Capability::Kernel],
+            ImageChannelDataType::SignedInt32 => &[Capability::Kernel],
+            ImageChannelDataType::UnsignedInt8 => &[Capability::Kernel],
+            ImageChannelDataType::UnsignedInt16 => &[Capability::Kernel],
+            ImageChannelDataType::UnsignedInt32 => &[Capability::Kernel],
+            ImageChannelDataType::HalfFloat => &[Capability::Kernel],
+            ImageChannelDataType::Float => &[Capability::Kernel],
+            ImageChannelDataType::UnormInt24 => &[Capability::Kernel],
+            ImageChannelDataType::UnormInt101010_2 => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [FPRoundingMode](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_fp_rounding_mode_a_fp_rounding_mode)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -372,6 +1856,52 @@ pub enum FPRoundingMode {
     RTN = 3,
 }
 
+impl fmt::Display for FPRoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            FPRoundingMode::RTE => "RTE",
+            FPRoundingMode::RTZ => "RTZ",
+            FPRoundingMode::RTP => "RTP",
+            FPRoundingMode::RTN => "RTN",
+        })
+    }
+}
+
+impl str::FromStr for FPRoundingMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RTE" => Ok(FPRoundingMode::RTE),
+            "RTZ" => Ok(FPRoundingMode::RTZ),
+            "RTP" => Ok(FPRoundingMode::RTP),
+            "RTN" => Ok(FPRoundingMode::RTN),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl FPRoundingMode {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        &[]
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [LinkageType](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_linkage_type_a_linkage_type)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -380,6 +1910,51 @@ pub enum LinkageType {
     Import = 1,
 }
 
+impl fmt::Display for LinkageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            LinkageType::Export => "Export",
+            LinkageType::Import => "Import",
+        })
+    }
+}
+
+impl str::FromStr for LinkageType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Export" => Ok(LinkageType::Export),
+            "Import" => Ok(LinkageType::Import),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl LinkageType {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            LinkageType::Export => &[Capability::Linkage],
+            LinkageType::Import => &[Capability::Linkage],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [AccessQualifier](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_access_qualifier_a_access_qualifier)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -389,6 +1964,54 @@ pub enum AccessQualifier {
     ReadWrite = 2,
 }
 
+impl fmt::Display for AccessQualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            AccessQualifier::ReadOnly => "ReadOnly",
+            AccessQualifier::WriteOnly => "WriteOnly",
+            AccessQualifier::ReadWrite => "ReadWrite",
+        })
+    }
+}
+
+impl str::FromStr for AccessQualifier {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ReadOnly" => Ok(AccessQualifier::ReadOnly),
+            "WriteOnly" => Ok(AccessQualifier::WriteOnly),
+            "ReadWrite" => Ok(AccessQualifier::ReadWrite),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl AccessQualifier {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            AccessQualifier::ReadOnly => &[Capability::Kernel],
+            AccessQualifier::WriteOnly => &[Capability::Kernel],
+            AccessQualifier::ReadWrite => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [FunctionParameterAttribute](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_function_parameter_attribute_a_function_parameter_attribute)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -403,6 +2026,69 @@ pub enum FunctionParameterAttribute {
     NoReadWrite = 7,
 }
 
+impl fmt::Display for FunctionParameterAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            FunctionParameterAttribute::Zext => "Zext",
+            FunctionParameterAttribute::Sext => "Sext",
+            FunctionParameterAttribute::ByVal => "ByVal",
+            FunctionParameterAttribute::Sret => "Sret",
+            FunctionParameterAttribute::NoAlias => "NoAlias",
+            FunctionParameterAttribute::NoCapture => "NoCapture",
+            FunctionParameterAttribute::NoWrite => "NoWrite",
+            FunctionParameterAttribute::NoReadWrite => "NoReadWrite",
+        })
+    }
+}
+
+impl str::FromStr for FunctionParameterAttribute {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Zext" => Ok(FunctionParameterAttribute::Zext),
+            "Sext" => Ok(FunctionParameterAttribute::Sext),
+            "ByVal" => Ok(FunctionParameterAttribute::ByVal),
+            "Sret" => Ok(FunctionParameterAttribute::Sret),
+            "NoAlias" => Ok(FunctionParameterAttribute::NoAlias),
+            "NoCapture" => Ok(FunctionParameterAttribute::NoCapture),
+            "NoWrite" => Ok(FunctionParameterAttribute::NoWrite),
+            "NoReadWrite" => Ok(FunctionParameterAttribute::NoReadWrite),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl FunctionParameterAttribute {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            FunctionParameterAttribute::Zext => &[Capability::Kernel],
+            FunctionParameterAttribute::Sext => &[Capability::Kernel],
+            FunctionParameterAttribute::ByVal => &[Capability::Kernel],
+            FunctionParameterAttribute::Sret => &[Capability::Kernel],
+            FunctionParameterAttribute::NoAlias => &[Capability::Kernel],
+            FunctionParameterAttribute::NoCapture => &[Capability::Kernel],
+            FunctionParameterAttribute::NoWrite => &[Capability::Kernel],
+            FunctionParameterAttribute::NoReadWrite => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [Decoration](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_decoration_a_decoration)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -461,6 +2147,223 @@ pub enum Decoration {
     NonUniformEXT = 5300,
     HlslCounterBufferGOOGLE = 5634,
     HlslSemanticGOOGLE = 5635,
+    PerPrimitiveEXT = 5271,
+}
+
+impl fmt::Display for Decoration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Decoration::RelaxedPrecision => "RelaxedPrecision",
+            Decoration::SpecId => "SpecId",
+            Decoration::Block => "Block",
+            Decoration::BufferBlock => "BufferBlock",
+            Decoration::RowMajor => "RowMajor",
+            Decoration::ColMajor => "ColMajor",
+            Decoration::ArrayStride => "ArrayStride",
+            Decoration::MatrixStride => "MatrixStride",
+            Decoration::GLSLShared => "GLSLShared",
+            Decoration::GLSLPacked => "GLSLPacked",
+            Decoration::CPacked => "CPacked",
+            Decoration::BuiltIn => "BuiltIn",
+            Decoration::NoPerspective => "NoPerspective",
+            Decoration::Flat => "Flat",
+            Decoration::Patch => "Patch",
+            Decoration::Centroid => "Centroid",
+            Decoration::Sample => "Sample",
+            Decoration::Invariant => "Invariant",
+            Decoration::Restrict => "Restrict",
+            Decoration::Aliased => "Aliased",
+            Decoration::Volatile => "Volatile",
+            Decoration::Constant => "Constant",
+            Decoration::Coherent => "Coherent",
+            Decoration::NonWritable => "NonWritable",
+            Decoration::NonReadable => "NonReadable",
+            Decoration::Uniform => "Uniform",
+            Decoration::SaturatedConversion => "SaturatedConversion",
+            Decoration::Stream => "Stream",
+            Decoration::Location => "Location",
+            Decoration::Component => "Component",
+            Decoration::Index => "Index",
+            Decoration::Binding => "Binding",
+            Decoration::DescriptorSet => "DescriptorSet",
+            Decoration::Offset => "Offset",
+            Decoration::XfbBuffer => "XfbBuffer",
+            Decoration::XfbStride => "XfbStride",
+            Decoration::FuncParamAttr => "FuncParamAttr",
+            Decoration::FPRoundingMode => "FPRoundingMode",
+            Decoration::FPFastMathMode => "FPFastMathMode",
+            Decoration::LinkageAttributes => "LinkageAttributes",
+            Decoration::NoContraction => "NoContraction",
+            Decoration::InputAttachmentIndex => "InputAttachmentIndex",
+            Decoration::Alignment => "Alignment",
+            Decoration::MaxByteOffset => "MaxByteOffset",
+            Decoration::AlignmentId => "AlignmentId",
+            Decoration::MaxByteOffsetId => "MaxByteOffsetId",
+            Decoration::ExplicitInterpAMD => "ExplicitInterpAMD",
+            Decoration::OverrideCoverageNV => "OverrideCoverageNV",
+            Decoration::PassthroughNV => "PassthroughNV",
+            Decoration::ViewportRelativeNV => "ViewportRelativeNV",
+            Decoration::SecondaryViewportRelativeNV => "SecondaryViewportRelativeNV",
+            Decoration::NonUniformEXT => "NonUniformEXT",
+            Decoration::HlslCounterBufferGOOGLE => "HlslCounterBufferGOOGLE",
+            Decoration::HlslSemanticGOOGLE => "HlslSemanticGOOGLE",
+            Decoration::PerPrimitiveEXT => "PerPrimitiveEXT",
+        })
+    }
+}
+
+impl str::FromStr for Decoration {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RelaxedPrecision" => Ok(Decoration::RelaxedPrecision),
+            "SpecId" => Ok(Decoration::SpecId),
+            "Block" => Ok(Decoration::Block),
+            "BufferBlock" => Ok(Decoration::BufferBlock),
+            "RowMajor" => Ok(Decoration::RowMajor),
+            "ColMajor" => Ok(Decoration::ColMajor),
+            "ArrayStride" => Ok(Decoration::ArrayStride),
+            "MatrixStride" => Ok(Decoration::MatrixStride),
+            "GLSLShared" => Ok(Decoration::GLSLShared),
+            "GLSLPacked" => Ok(Decoration::GLSLPacked),
+            "CPacked" => Ok(Decoration::CPacked),
+            "BuiltIn" => Ok(Decoration::BuiltIn),
+            "NoPerspective" => Ok(Decoration::NoPerspective),
+            "Flat" => Ok(Decoration::Flat),
+            "Patch" => Ok(Decoration::Patch),
+            "Centroid" => Ok(Decoration::Centroid),
+            "Sample" => Ok(Decoration::Sample),
+            "Invariant" => Ok(Decoration::Invariant),
+            "Restrict" => Ok(Decoration::Restrict),
+            "Aliased" => Ok(Decoration::Aliased),
+            "Volatile" => Ok(Decoration::Volatile),
+            "Constant" => Ok(Decoration::Constant),
+            "Coherent" => Ok(Decoration::Coherent),
+            "NonWritable" => Ok(Decoration::NonWritable),
+            "NonReadable" => Ok(Decoration::NonReadable),
+            "Uniform" => Ok(Decoration::Uniform),
+            "SaturatedConversion" => Ok(Decoration::SaturatedConversion),
+            "Stream" => Ok(Decoration::Stream),
+            "Location" => Ok(Decoration::Location),
+            "Component" => Ok(Decoration::Component),
+            "Index" => Ok(Decoration::Index),
+            "Binding" => Ok(Decoration::Binding),
+            "DescriptorSet" => Ok(Decoration::DescriptorSet),
+            "Offset" => Ok(Decoration::Offset),
+            "XfbBuffer" => Ok(Decoration::XfbBuffer),
+            "XfbStride" => Ok(Decoration::XfbStride),
+            "FuncParamAttr" => Ok(Decoration::FuncParamAttr),
+            "FPRoundingMode" => Ok(Decoration::FPRoundingMode),
+            "FPFastMathMode" => Ok(Decoration::FPFastMathMode),
+            "LinkageAttributes" => Ok(Decoration::LinkageAttributes),
+            "NoContraction" => Ok(Decoration::NoContraction),
+            "InputAttachmentIndex" => Ok(Decoration::InputAttachmentIndex),
+            "Alignment" => Ok(Decoration::Alignment),
+            "MaxByteOffset" => Ok(Decoration::MaxByteOffset),
+            "AlignmentId" => Ok(Decoration::AlignmentId),
+            "MaxByteOffsetId" => Ok(Decoration::MaxByteOffsetId),
+            "ExplicitInterpAMD" => Ok(Decoration::ExplicitInterpAMD),
+            "OverrideCoverageNV" => Ok(Decoration::OverrideCoverageNV),
+            "PassthroughNV" => Ok(Decoration::PassthroughNV),
+            "ViewportRelativeNV" => Ok(Decoration::ViewportRelativeNV),
+            "SecondaryViewportRelativeNV" => Ok(Decoration::SecondaryViewportRelativeNV),
+            "NonUniformEXT" => Ok(Decoration::NonUniformEXT),
+            "HlslCounterBufferGOOGLE" => Ok(Decoration::HlslCounterBufferGOOGLE),
+            "HlslSemanticGOOGLE" => Ok(Decoration::HlslSemanticGOOGLE),
+            "PerPrimitiveEXT" => Ok(Decoration::PerPrimitiveEXT),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl Decoration {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            Decoration::RelaxedPrecision => &[Capability::Shader],
+            Decoration::SpecId => &[Capability::Shader, Capability::Kernel],
+            Decoration::Block => &[Capability::Shader],
+            Decoration::BufferBlock => &[Capability::Shader],
+            Decoration::RowMajor => &[Capability::Matrix],
+            Decoration::ColMajor => &[Capability::Matrix],
+            Decoration::ArrayStride => &[Capability::Shader],
+            Decoration::MatrixStride => &[Capability::Matrix],
+            Decoration::GLSLShared => &[Capability::Shader],
+            Decoration::GLSLPacked => &[Capability::Shader],
+            Decoration::CPacked => &[Capability::Kernel],
+            Decoration::NoPerspective => &[Capability::Shader],
+            Decoration::Flat => &[Capability::Shader],
+            Decoration::Patch => &[Capability::Tessellation],
+            Decoration::Centroid => &[Capability::Shader],
+            Decoration::Sample => &[Capability::SampleRateShading],
+            Decoration::Invariant => &[Capability::Shader],
+            Decoration::Constant => &[Capability::Kernel],
+            Decoration::Uniform => &[Capability::Shader],
+            Decoration::SaturatedConversion => &[Capability::Kernel],
+            Decoration::Stream => &[Capability::GeometryStreams],
+            Decoration::Location => &[Capability::Shader],
+            Decoration::Component => &[Capability::Shader],
+            Decoration::Index => &[Capability::Shader],
+            Decoration::Binding => &[Capability::Shader],
+            Decoration::DescriptorSet => &[Capability::Shader],
+            Decoration::Offset => &[Capability::Shader],
+            Decoration::XfbBuffer => &[Capability::TransformFeedback],
+            Decoration::XfbStride => &[Capability::TransformFeedback],
+            Decoration::FuncParamAttr => &[Capability::Kernel],
+            Decoration::FPFastMathMode => &[Capability::Kernel],
+            Decoration::LinkageAttributes => &[Capability::Linkage],
+            Decoration::NoContraction => &[Capability::Shader],
+            Decoration::InputAttachmentIndex => &[Capability::InputAttachment],
+            Decoration::Alignment => &[Capability::Kernel],
+            Decoration::MaxByteOffset => &[Capability::Addresses],
+            Decoration::AlignmentId => &[Capability::Kernel],
+            Decoration::MaxByteOffsetId => &[Capability::Addresses],
+            Decoration::OverrideCoverageNV => &[Capability::SampleMaskOverrideCoverageNV],
+            Decoration::PassthroughNV => &[Capability::GeometryShaderPassthroughNV],
+            Decoration::ViewportRelativeNV => &[Capability::ShaderViewportMaskNV],
+            Decoration::SecondaryViewportRelativeNV => &[Capability::ShaderStereoViewNV],
+            Decoration::NonUniformEXT => &[Capability::ShaderNonUniformEXT],
+            Decoration::PerPrimitiveEXT => &[Capability::MeshShadingEXT],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            Decoration::ExplicitInterpAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            Decoration::OverrideCoverageNV => &["SPV_NV_sample_mask_override_coverage"],
+            Decoration::PassthroughNV => &["SPV_NV_geometry_shader_passthrough"],
+            Decoration::SecondaryViewportRelativeNV => &["SPV_NV_stereo_view_rendering"],
+            Decoration::HlslCounterBufferGOOGLE => &["SPV_GOOGLE_hlsl_functionality1"],
+            Decoration::HlslSemanticGOOGLE => &["SPV_GOOGLE_hlsl_functionality1"],
+            Decoration::PerPrimitiveEXT => &["SPV_EXT_mesh_shader"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            Decoration::MaxByteOffset => Some((1, 1)),
+            Decoration::AlignmentId => Some((1, 2)),
+            Decoration::MaxByteOffsetId => Some((1, 2)),
+            Decoration::ExplicitInterpAMD => None,
+            Decoration::OverrideCoverageNV => None,
+            Decoration::PassthroughNV => None,
+            Decoration::ViewportRelativeNV => None,
+            Decoration::SecondaryViewportRelativeNV => None,
+            Decoration::HlslCounterBufferGOOGLE => None,
+            Decoration::HlslSemanticGOOGLE => None,
+            Decoration::PerPrimitiveEXT => None,
+            _ => Some((1, 0)),
+        }
+    }
 }
 
 /// SPIR-V operand kind: [BuiltIn](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_built_in_a_built_in)
@@ -532,6 +2435,24 @@ pub enum BuiltIn {
     PositionPerViewNV = 5261,
     ViewportMaskPerViewNV = 5262,
     FullyCoveredEXT = 5264,
+    LaunchIdKHR = 5319,
+    LaunchSizeKHR = 5320,
+    WorldRayOriginKHR = 5321,
+    WorldRayDirectionKHR = 5322,
+    ObjectRayOriginKHR = 5323,
+    ObjectRayDirectionKHR = 5324,
+    RayTminKHR = 5325,
+    RayTmaxKHR = 5326,
+    InstanceCustomIndexKHR = 5327,
+    ObjectToWorldKHR = 5330,
+    WorldToObjectKHR = 5331,
+    HitKindKHR = 5333,
+    IncomingRayFlagsKHR = 5351,
+    RayGeometryIndexKHR = 5352,
+    PrimitivePointIndicesEXT = 5294,
+    PrimitiveLineIndicesEXT = 5295,
+    PrimitiveTriangleIndicesEXT = 5296,
+    CullPrimitiveEXT = 5299,
 }
 
 #[allow(non_upper_case_globals)]
@@ -542,6 +2463,368 @@ impl BuiltIn {
     pub const SubgroupLeMaskKHR: BuiltIn = BuiltIn::SubgroupLeMask;
     pub const SubgroupLtMaskKHR: BuiltIn = BuiltIn::SubgroupLtMask;
 }
+
+impl fmt::Display for BuiltIn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            BuiltIn::Position => "Position",
+            BuiltIn::PointSize => "PointSize",
+            BuiltIn::ClipDistance => "ClipDistance",
+            BuiltIn::CullDistance => "CullDistance",
+            BuiltIn::VertexId => "VertexId",
+            BuiltIn::InstanceId => "InstanceId",
+            BuiltIn::PrimitiveId => "PrimitiveId",
+            BuiltIn::InvocationId => "InvocationId",
+            BuiltIn::Layer => "Layer",
+            BuiltIn::ViewportIndex => "ViewportIndex",
+            BuiltIn::TessLevelOuter => "TessLevelOuter",
+            BuiltIn::TessLevelInner => "TessLevelInner",
+            BuiltIn::TessCoord => "TessCoord",
+            BuiltIn::PatchVertices => "PatchVertices",
+            BuiltIn::FragCoord => "FragCoord",
+            BuiltIn::PointCoord => "PointCoord",
+            BuiltIn::FrontFacing => "FrontFacing",
+            BuiltIn::SampleId => "SampleId",
+            BuiltIn::SamplePosition => "SamplePosition",
+            BuiltIn::SampleMask => "SampleMask",
+            BuiltIn::FragDepth => "FragDepth",
+            BuiltIn::HelperInvocation => "HelperInvocation",
+            BuiltIn::NumWorkgroups => "NumWorkgroups",
+            BuiltIn::WorkgroupSize => "WorkgroupSize",
+            BuiltIn::WorkgroupId => "WorkgroupId",
+            BuiltIn::LocalInvocationId => "LocalInvocationId",
+            BuiltIn::GlobalInvocationId => "GlobalInvocationId",
+            BuiltIn::LocalInvocationIndex => "LocalInvocationIndex",
+            BuiltIn::WorkDim => "WorkDim",
+            BuiltIn::GlobalSize => "GlobalSize",
+            BuiltIn::EnqueuedWorkgroupSize => "EnqueuedWorkgroupSize",
+            BuiltIn::GlobalOffset => "GlobalOffset",
+            BuiltIn::GlobalLinearId => "GlobalLinearId",
+            BuiltIn::SubgroupSize => "SubgroupSize",
+            BuiltIn::SubgroupMaxSize => "SubgroupMaxSize",
+            BuiltIn::NumSubgroups => "NumSubgroups",
+            BuiltIn::NumEnqueuedSubgroups => "NumEnqueuedSubgroups",
+            BuiltIn::SubgroupId => "SubgroupId",
+            BuiltIn::SubgroupLocalInvocationId => "SubgroupLocalInvocationId",
+            BuiltIn::VertexIndex => "VertexIndex",
+            BuiltIn::InstanceIndex => "InstanceIndex",
+            BuiltIn::SubgroupEqMask => "SubgroupEqMask",
+            BuiltIn::SubgroupGeMask => "SubgroupGeMask",
+            BuiltIn::SubgroupGtMask => "SubgroupGtMask",
+            BuiltIn::SubgroupLeMask => "SubgroupLeMask",
+            BuiltIn::SubgroupLtMask => "SubgroupLtMask",
+            BuiltIn::BaseVertex => "BaseVertex",
+            BuiltIn::BaseInstance => "BaseInstance",
+            BuiltIn::DrawIndex => "DrawIndex",
+            BuiltIn::DeviceIndex => "DeviceIndex",
+            BuiltIn::ViewIndex => "ViewIndex",
+            BuiltIn::BaryCoordNoPerspAMD => "BaryCoordNoPerspAMD",
+            BuiltIn::BaryCoordNoPerspCentroidAMD => "BaryCoordNoPerspCentroidAMD",
+            BuiltIn::BaryCoordNoPerspSampleAMD => "BaryCoordNoPerspSampleAMD",
+            BuiltIn::BaryCoordSmoothAMD => "BaryCoordSmoothAMD",
+            BuiltIn::BaryCoordSmoothCentroidAMD => "BaryCoordSmoothCentroidAMD",
+            BuiltIn::BaryCoordSmoothSampleAMD => "BaryCoordSmoothSampleAMD",
+            BuiltIn::BaryCoordPullModelAMD => "BaryCoordPullModelAMD",
+            BuiltIn::FragStencilRefEXT => "FragStencilRefEXT",
+            BuiltIn::ViewportMaskNV => "ViewportMaskNV",
+            BuiltIn::SecondaryPositionNV => "SecondaryPositionNV",
+            BuiltIn::SecondaryViewportMaskNV => "SecondaryViewportMaskNV",
+            BuiltIn::PositionPerViewNV => "PositionPerViewNV",
+            BuiltIn::ViewportMaskPerViewNV => "ViewportMaskPerViewNV",
+            BuiltIn::FullyCoveredEXT => "FullyCoveredEXT",
+            BuiltIn::LaunchIdKHR => "LaunchIdKHR",
+            BuiltIn::LaunchSizeKHR => "LaunchSizeKHR",
+            BuiltIn::WorldRayOriginKHR => "WorldRayOriginKHR",
+            BuiltIn::WorldRayDirectionKHR => "WorldRayDirectionKHR",
+            BuiltIn::ObjectRayOriginKHR => "ObjectRayOriginKHR",
+            BuiltIn::ObjectRayDirectionKHR => "ObjectRayDirectionKHR",
+            BuiltIn::RayTminKHR => "RayTminKHR",
+            BuiltIn::RayTmaxKHR => "RayTmaxKHR",
+            BuiltIn::InstanceCustomIndexKHR => "InstanceCustomIndexKHR",
+            BuiltIn::ObjectToWorldKHR => "ObjectToWorldKHR",
+            BuiltIn::WorldToObjectKHR => "WorldToObjectKHR",
+            BuiltIn::HitKindKHR => "HitKindKHR",
+            BuiltIn::IncomingRayFlagsKHR => "IncomingRayFlagsKHR",
+            BuiltIn::RayGeometryIndexKHR => "RayGeometryIndexKHR",
+            BuiltIn::PrimitivePointIndicesEXT => "PrimitivePointIndicesEXT",
+            BuiltIn::PrimitiveLineIndicesEXT => "PrimitiveLineIndicesEXT",
+            BuiltIn::PrimitiveTriangleIndicesEXT => "PrimitiveTriangleIndicesEXT",
+            BuiltIn::CullPrimitiveEXT => "CullPrimitiveEXT",
+        })
+    }
+}
+
+impl str::FromStr for BuiltIn {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Position" => Ok(BuiltIn::Position),
+            "PointSize" => Ok(BuiltIn::PointSize),
+            "ClipDistance" => Ok(BuiltIn::ClipDistance),
+            "CullDistance" => Ok(BuiltIn::CullDistance),
+            "VertexId" => Ok(BuiltIn::VertexId),
+            "InstanceId" => Ok(BuiltIn::InstanceId),
+            "PrimitiveId" => Ok(BuiltIn::PrimitiveId),
+            "InvocationId" => Ok(BuiltIn::InvocationId),
+            "Layer" => Ok(BuiltIn::Layer),
+            "ViewportIndex" => Ok(BuiltIn::ViewportIndex),
+            "TessLevelOuter" => Ok(BuiltIn::TessLevelOuter),
+            "TessLevelInner" => Ok(BuiltIn::TessLevelInner),
+            "TessCoord" => Ok(BuiltIn::TessCoord),
+            "PatchVertices" => Ok(BuiltIn::PatchVertices),
+            "FragCoord" => Ok(BuiltIn::FragCoord),
+            "PointCoord" => Ok(BuiltIn::PointCoord),
+            "FrontFacing" => Ok(BuiltIn::FrontFacing),
+            "SampleId" => Ok(BuiltIn::SampleId),
+            "SamplePosition" => Ok(BuiltIn::SamplePosition),
+            "SampleMask" => Ok(BuiltIn::SampleMask),
+            "FragDepth" => Ok(BuiltIn::FragDepth),
+            "HelperInvocation" => Ok(BuiltIn::HelperInvocation),
+            "NumWorkgroups" => Ok(BuiltIn::NumWorkgroups),
+            "WorkgroupSize" => Ok(BuiltIn::WorkgroupSize),
+            "WorkgroupId" => Ok(BuiltIn::WorkgroupId),
+            "LocalInvocationId" => Ok(BuiltIn::LocalInvocationId),
+            "GlobalInvocationId" => Ok(BuiltIn::GlobalInvocationId),
+            "LocalInvocationIndex" => Ok(BuiltIn::LocalInvocationIndex),
+            "WorkDim" => Ok(BuiltIn::WorkDim),
+            "GlobalSize" => Ok(BuiltIn::GlobalSize),
+            "EnqueuedWorkgroupSize" => Ok(BuiltIn::EnqueuedWorkgroupSize),
+            "GlobalOffset" => Ok(BuiltIn::GlobalOffset),
+            "GlobalLinearId" => Ok(BuiltIn::GlobalLinearId),
+            "SubgroupSize" => Ok(BuiltIn::SubgroupSize),
+            "SubgroupMaxSize" => Ok(BuiltIn::SubgroupMaxSize),
+            "NumSubgroups" => Ok(BuiltIn::NumSubgroups),
+            "NumEnqueuedSubgroups" => Ok(BuiltIn::NumEnqueuedSubgroups),
+            "SubgroupId" => Ok(BuiltIn::SubgroupId),
+            "SubgroupLocalInvocationId" => Ok(BuiltIn::SubgroupLocalInvocationId),
+            "VertexIndex" => Ok(BuiltIn::VertexIndex),
+            "InstanceIndex" => Ok(BuiltIn::InstanceIndex),
+            "SubgroupEqMask" => Ok(BuiltIn::SubgroupEqMask),
+            "SubgroupGeMask" => Ok(BuiltIn::SubgroupGeMask),
+            "SubgroupGtMask" => Ok(BuiltIn::SubgroupGtMask),
+            "SubgroupLeMask" => Ok(BuiltIn::SubgroupLeMask),
+            "SubgroupLtMask" => Ok(BuiltIn::SubgroupLtMask),
+            "SubgroupEqMaskKHR" => Ok(BuiltIn::SubgroupEqMaskKHR),
+            "SubgroupGeMaskKHR" => Ok(BuiltIn::SubgroupGeMaskKHR),
+            "SubgroupGtMaskKHR" => Ok(BuiltIn::SubgroupGtMaskKHR),
+            "SubgroupLeMaskKHR" => Ok(BuiltIn::SubgroupLeMaskKHR),
+            "SubgroupLtMaskKHR" => Ok(BuiltIn::SubgroupLtMaskKHR),
+            "BaseVertex" => Ok(BuiltIn::BaseVertex),
+            "BaseInstance" => Ok(BuiltIn::BaseInstance),
+            "DrawIndex" => Ok(BuiltIn::DrawIndex),
+            "DeviceIndex" => Ok(BuiltIn::DeviceIndex),
+            "ViewIndex" => Ok(BuiltIn::ViewIndex),
+            "BaryCoordNoPerspAMD" => Ok(BuiltIn::BaryCoordNoPerspAMD),
+            "BaryCoordNoPerspCentroidAMD" => Ok(BuiltIn::BaryCoordNoPerspCentroidAMD),
+            "BaryCoordNoPerspSampleAMD" => Ok(BuiltIn::BaryCoordNoPerspSampleAMD),
+            "BaryCoordSmoothAMD" => Ok(BuiltIn::BaryCoordSmoothAMD),
+            "BaryCoordSmoothCentroidAMD" => Ok(BuiltIn::BaryCoordSmoothCentroidAMD),
+            "BaryCoordSmoothSampleAMD" => Ok(BuiltIn::BaryCoordSmoothSampleAMD),
+            "BaryCoordPullModelAMD" => Ok(BuiltIn::BaryCoordPullModelAMD),
+            "FragStencilRefEXT" => Ok(BuiltIn::FragStencilRefEXT),
+            "ViewportMaskNV" => Ok(BuiltIn::ViewportMaskNV),
+            "SecondaryPositionNV" => Ok(BuiltIn::SecondaryPositionNV),
+            "SecondaryViewportMaskNV" => Ok(BuiltIn::SecondaryViewportMaskNV),
+            "PositionPerViewNV" => Ok(BuiltIn::PositionPerViewNV),
+            "ViewportMaskPerViewNV" => Ok(BuiltIn::ViewportMaskPerViewNV),
+            "FullyCoveredEXT" => Ok(BuiltIn::FullyCoveredEXT),
+            "LaunchIdKHR" => Ok(BuiltIn::LaunchIdKHR),
+            "LaunchSizeKHR" => Ok(BuiltIn::LaunchSizeKHR),
+            "WorldRayOriginKHR" => Ok(BuiltIn::WorldRayOriginKHR),
+            "WorldRayDirectionKHR" => Ok(BuiltIn::WorldRayDirectionKHR),
+            "ObjectRayOriginKHR" => Ok(BuiltIn::ObjectRayOriginKHR),
+            "ObjectRayDirectionKHR" => Ok(BuiltIn::ObjectRayDirectionKHR),
+            "RayTminKHR" => Ok(BuiltIn::RayTminKHR),
+            "RayTmaxKHR" => Ok(BuiltIn::RayTmaxKHR),
+            "InstanceCustomIndexKHR" => Ok(BuiltIn::InstanceCustomIndexKHR),
+            "ObjectToWorldKHR" => Ok(BuiltIn::ObjectToWorldKHR),
+            "WorldToObjectKHR" => Ok(BuiltIn::WorldToObjectKHR),
+            "HitKindKHR" => Ok(BuiltIn::HitKindKHR),
+            "IncomingRayFlagsKHR" => Ok(BuiltIn::IncomingRayFlagsKHR),
+            "RayGeometryIndexKHR" => Ok(BuiltIn::RayGeometryIndexKHR),
+            "PrimitivePointIndicesEXT" => Ok(BuiltIn::PrimitivePointIndicesEXT),
+            "PrimitiveLineIndicesEXT" => Ok(BuiltIn::PrimitiveLineIndicesEXT),
+            "PrimitiveTriangleIndicesEXT" => Ok(BuiltIn::PrimitiveTriangleIndicesEXT),
+            "CullPrimitiveEXT" => Ok(BuiltIn::CullPrimitiveEXT),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl BuiltIn {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            BuiltIn::Position => &[Capability::Shader],
+            BuiltIn::PointSize => &[Capability::Shader],
+            BuiltIn::ClipDistance => &[Capability::ClipDistance],
+            BuiltIn::CullDistance => &[Capability::CullDistance],
+            BuiltIn::VertexId => &[Capability::Shader],
+            BuiltIn::InstanceId => &[Capability::Shader],
+            BuiltIn::PrimitiveId => &[Capability::Geometry, Capability::Tessellation, Capability::MeshShadingEXT],
+            BuiltIn::InvocationId => &[Capability::Geometry, Capability::Tessellation],
+            BuiltIn::Layer => &[Capability::Geometry, Capability::MeshShadingEXT],
+            BuiltIn::ViewportIndex => &[Capability::MultiViewport, Capability::MeshShadingEXT],
+            BuiltIn::TessLevelOuter => &[Capability::Tessellation],
+            BuiltIn::TessLevelInner => &[Capability::Tessellation],
+            BuiltIn::TessCoord => &[Capability::Tessellation],
+            BuiltIn::PatchVertices => &[Capability::Tessellation],
+            BuiltIn::FragCoord => &[Capability::Shader],
+            BuiltIn::PointCoord => &[Capability::Shader],
+            BuiltIn::FrontFacing => &[Capability::Shader],
+            BuiltIn::SampleId => &[Capability::SampleRateShading],
+            BuiltIn::SamplePosition => &[Capability::SampleRateShading],
+            BuiltIn::SampleMask => &[Capability::Shader],
+            BuiltIn::FragDepth => &[Capability::Shader],
+            BuiltIn::HelperInvocation => &[Capability::Shader],
+            BuiltIn::WorkDim => &[Capability::Kernel],
+            BuiltIn::GlobalSize => &[Capability::Kernel],
+            BuiltIn::EnqueuedWorkgroupSize => &[Capability::Kernel],
+            BuiltIn::GlobalOffset => &[Capability::Kernel],
+            BuiltIn::GlobalLinearId => &[Capability::Kernel],
+            BuiltIn::SubgroupSize => &[Capability::Kernel, Capability::GroupNonUniform, Capability::SubgroupBallotKHR],
+            BuiltIn::SubgroupMaxSize => &[Capability::Kernel],
+            BuiltIn::NumSubgroups => &[Capability::Kernel, Capability::GroupNonUniform],
+            BuiltIn::NumEnqueuedSubgroups => &[Capability::Kernel],
+            BuiltIn::SubgroupId => &[Capability::Kernel, Capability::GroupNonUniform],
+            BuiltIn::SubgroupLocalInvocationId => &[Capability::Kernel, Capability::GroupNonUniform, Capability::SubgroupBallotKHR],
+            BuiltIn::VertexIndex => &[Capability::Shader],
+            BuiltIn::InstanceIndex => &[Capability::Shader],
+            BuiltIn::SubgroupEqMask => &[Capability::SubgroupBallotKHR, Capability::GroupNonUniformBallot],
+            BuiltIn::SubgroupGeMask => &[Capability::SubgroupBallotKHR, Capability::GroupNonUniformBallot],
+            BuiltIn::SubgroupGtMask => &[Capability::SubgroupBallotKHR, Capability::GroupNonUniformBallot],
+            BuiltIn::SubgroupLeMask => &[Capability::SubgroupBallotKHR, Capability::GroupNonUniformBallot],
+            BuiltIn::SubgroupLtMask => &[Capability::SubgroupBallotKHR, Capability::GroupNonUniformBallot],
+            BuiltIn::BaseVertex => &[Capability::DrawParameters],
+            BuiltIn::BaseInstance => &[Capability::DrawParameters],
+            BuiltIn::DrawIndex => &[Capability::DrawParameters],
+            BuiltIn::DeviceIndex => &[Capability::DeviceGroup],
+            BuiltIn::ViewIndex => &[Capability::MultiView],
+            BuiltIn::FragStencilRefEXT => &[Capability::StencilExportEXT],
+            BuiltIn::ViewportMaskNV => &[Capability::ShaderViewportMaskNV],
+            BuiltIn::SecondaryPositionNV => &[Capability::ShaderStereoViewNV],
+            BuiltIn::SecondaryViewportMaskNV => &[Capability::ShaderStereoViewNV],
+            BuiltIn::PositionPerViewNV => &[Capability::PerViewAttributesNV],
+            BuiltIn::ViewportMaskPerViewNV => &[Capability::PerViewAttributesNV],
+            BuiltIn::FullyCoveredEXT => &[Capability::FragmentFullyCoveredEXT],
+            BuiltIn::LaunchIdKHR => &[Capability::RayTracingKHR],
+            BuiltIn::LaunchSizeKHR => &[Capability::RayTracingKHR],
+            BuiltIn::WorldRayOriginKHR => &[Capability::RayTracingKHR],
+            BuiltIn::WorldRayDirectionKHR => &[Capability::RayTracingKHR],
+            BuiltIn::ObjectRayOriginKHR => &[Capability::RayTracingKHR],
+            BuiltIn::ObjectRayDirectionKHR => &[Capability::RayTracingKHR],
+            BuiltIn::RayTminKHR => &[Capability::RayTracingKHR],
+            BuiltIn::RayTmaxKHR => &[Capability::RayTracingKHR],
+            BuiltIn::InstanceCustomIndexKHR => &[Capability::RayTracingKHR],
+            BuiltIn::ObjectToWorldKHR => &[Capability::RayTracingKHR],
+            BuiltIn::WorldToObjectKHR => &[Capability::RayTracingKHR],
+            BuiltIn::HitKindKHR => &[Capability::RayTracingKHR],
+            BuiltIn::IncomingRayFlagsKHR => &[Capability::RayTracingKHR],
+            BuiltIn::RayGeometryIndexKHR => &[Capability::RayTracingKHR],
+            BuiltIn::PrimitivePointIndicesEXT => &[Capability::MeshShadingEXT],
+            BuiltIn::PrimitiveLineIndicesEXT => &[Capability::MeshShadingEXT],
+            BuiltIn::PrimitiveTriangleIndicesEXT => &[Capability::MeshShadingEXT],
+            BuiltIn::CullPrimitiveEXT => &[Capability::MeshShadingEXT],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            BuiltIn::BaseVertex => &["SPV_KHR_shader_draw_parameters"],
+            BuiltIn::BaseInstance => &["SPV_KHR_shader_draw_parameters"],
+            BuiltIn::DrawIndex => &["SPV_KHR_shader_draw_parameters"],
+            BuiltIn::DeviceIndex => &["SPV_KHR_device_group"],
+            BuiltIn::ViewIndex => &["SPV_KHR_multiview"],
+            BuiltIn::BaryCoordNoPerspAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordNoPerspCentroidAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordNoPerspSampleAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordSmoothAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordSmoothCentroidAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordSmoothSampleAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::BaryCoordPullModelAMD => &["SPV_AMD_shader_explicit_vertex_parameter"],
+            BuiltIn::FragStencilRefEXT => &["SPV_EXT_shader_stencil_export"],
+            BuiltIn::SecondaryPositionNV => &["SPV_NV_stereo_view_rendering"],
+            BuiltIn::SecondaryViewportMaskNV => &["SPV_NV_stereo_view_rendering"],
+            BuiltIn::FullyCoveredEXT => &["SPV_EXT_fragment_fully_covered"],
+            BuiltIn::LaunchIdKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::LaunchSizeKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::WorldRayOriginKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::WorldRayDirectionKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::ObjectRayOriginKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::ObjectRayDirectionKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::RayTminKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::RayTmaxKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::InstanceCustomIndexKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::ObjectToWorldKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::WorldToObjectKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::HitKindKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::IncomingRayFlagsKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::RayGeometryIndexKHR => &["SPV_KHR_ray_tracing"],
+            BuiltIn::PrimitivePointIndicesEXT => &["SPV_EXT_mesh_shader"],
+            BuiltIn::PrimitiveLineIndicesEXT => &["SPV_EXT_mesh_shader"],
+            BuiltIn::PrimitiveTriangleIndicesEXT => &["SPV_EXT_mesh_shader"],
+            BuiltIn::CullPrimitiveEXT => &["SPV_EXT_mesh_shader"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            BuiltIn::SubgroupEqMask => Some((1, 3)),
+            BuiltIn::SubgroupGeMask => Some((1, 3)),
+            BuiltIn::SubgroupGtMask => Some((1, 3)),
+            BuiltIn::SubgroupLeMask => Some((1, 3)),
+            BuiltIn::SubgroupLtMask => Some((1, 3)),
+            BuiltIn::BaseVertex => Some((1, 3)),
+            BuiltIn::BaseInstance => Some((1, 3)),
+            BuiltIn::DrawIndex => Some((1, 3)),
+            BuiltIn::DeviceIndex => Some((1, 3)),
+            BuiltIn::ViewIndex => Some((1, 3)),
+            BuiltIn::BaryCoordNoPerspAMD => None,
+            BuiltIn::BaryCoordNoPerspCentroidAMD => None,
+            BuiltIn::BaryCoordNoPerspSampleAMD => None,
+            BuiltIn::BaryCoordSmoothAMD => None,
+            BuiltIn::BaryCoordSmoothCentroidAMD => None,
+            BuiltIn::BaryCoordSmoothSampleAMD => None,
+            BuiltIn::BaryCoordPullModelAMD => None,
+            BuiltIn::FragStencilRefEXT => None,
+            BuiltIn::ViewportMaskNV => None,
+            BuiltIn::SecondaryPositionNV => None,
+            BuiltIn::SecondaryViewportMaskNV => None,
+            BuiltIn::PositionPerViewNV => None,
+            BuiltIn::ViewportMaskPerViewNV => None,
+            BuiltIn::FullyCoveredEXT => None,
+            BuiltIn::LaunchIdKHR => None,
+            BuiltIn::LaunchSizeKHR => None,
+            BuiltIn::WorldRayOriginKHR => None,
+            BuiltIn::WorldRayDirectionKHR => None,
+            BuiltIn::ObjectRayOriginKHR => None,
+            BuiltIn::ObjectRayDirectionKHR => None,
+            BuiltIn::RayTminKHR => None,
+            BuiltIn::RayTmaxKHR => None,
+            BuiltIn::InstanceCustomIndexKHR => None,
+            BuiltIn::ObjectToWorldKHR => None,
+            BuiltIn::WorldToObjectKHR => None,
+            BuiltIn::HitKindKHR => None,
+            BuiltIn::IncomingRayFlagsKHR => None,
+            BuiltIn::RayGeometryIndexKHR => None,
+            BuiltIn::PrimitivePointIndicesEXT => None,
+            BuiltIn::PrimitiveLineIndicesEXT => None,
+            BuiltIn::PrimitiveTriangleIndicesEXT => None,
+            BuiltIn::CullPrimitiveEXT => None,
+            _ => Some((1, 0)),
+        }
+    }
+}
+
 /// SPIR-V operand kind: [Scope](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_scope_a_scope)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -554,6 +2837,59 @@ pub enum Scope {
     QueueFamilyKHR = 5,
 }
 
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Scope::CrossDevice => "CrossDevice",
+            Scope::Device => "Device",
+            Scope::Workgroup => "Workgroup",
+            Scope::Subgroup => "Subgroup",
+            Scope::Invocation => "Invocation",
+            Scope::QueueFamilyKHR => "QueueFamilyKHR",
+        })
+    }
+}
+
+impl str::FromStr for Scope {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "CrossDevice" => Ok(Scope::CrossDevice),
+            "Device" => Ok(Scope::Device),
+            "Workgroup" => Ok(Scope::Workgroup),
+            "Subgroup" => Ok(Scope::Subgroup),
+            "Invocation" => Ok(Scope::Invocation),
+            "QueueFamilyKHR" => Ok(Scope::QueueFamilyKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl Scope {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            Scope::QueueFamilyKHR => &[Capability::VulkanMemoryModelKHR],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [GroupOperation](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_group_operation_a_group_operation)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -567,6 +2903,77 @@ pub enum GroupOperation {
     PartitionedExclusiveScanNV = 8,
 }
 
+impl fmt::Display for GroupOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            GroupOperation::Reduce => "Reduce",
+            GroupOperation::InclusiveScan => "InclusiveScan",
+            GroupOperation::ExclusiveScan => "ExclusiveScan",
+            GroupOperation::ClusteredReduce => "ClusteredReduce",
+            GroupOperation::PartitionedReduceNV => "PartitionedReduceNV",
+            GroupOperation::PartitionedInclusiveScanNV => "PartitionedInclusiveScanNV",
+            GroupOperation::PartitionedExclusiveScanNV => "PartitionedExclusiveScanNV",
+        })
+    }
+}
+
+impl str::FromStr for GroupOperation {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Reduce" => Ok(GroupOperation::Reduce),
+            "InclusiveScan" => Ok(GroupOperation::InclusiveScan),
+            "ExclusiveScan" => Ok(GroupOperation::ExclusiveScan),
+            "ClusteredReduce" => Ok(GroupOperation::ClusteredReduce),
+            "PartitionedReduceNV" => Ok(GroupOperation::PartitionedReduceNV),
+            "PartitionedInclusiveScanNV" => Ok(GroupOperation::PartitionedInclusiveScanNV),
+            "PartitionedExclusiveScanNV" => Ok(GroupOperation::PartitionedExclusiveScanNV),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl GroupOperation {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            GroupOperation::Reduce => &[Capability::Kernel, Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformBallot],
+            GroupOperation::InclusiveScan => &[Capability::Kernel, Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformBallot],
+            GroupOperation::ExclusiveScan => &[Capability::Kernel, Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformBallot],
+            GroupOperation::ClusteredReduce => &[Capability::GroupNonUniformClustered],
+            GroupOperation::PartitionedReduceNV => &[Capability::GroupNonUniformPartitionedNV],
+            GroupOperation::PartitionedInclusiveScanNV => &[Capability::GroupNonUniformPartitionedNV],
+            GroupOperation::PartitionedExclusiveScanNV => &[Capability::GroupNonUniformPartitionedNV],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            GroupOperation::PartitionedReduceNV => &["SPV_NV_shader_subgroup_partitioned"],
+            GroupOperation::PartitionedInclusiveScanNV => &["SPV_NV_shader_subgroup_partitioned"],
+            GroupOperation::PartitionedExclusiveScanNV => &["SPV_NV_shader_subgroup_partitioned"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            GroupOperation::ClusteredReduce => Some((1, 3)),
+            GroupOperation::PartitionedReduceNV => None,
+            GroupOperation::PartitionedInclusiveScanNV => None,
+            GroupOperation::PartitionedExclusiveScanNV => None,
+            _ => Some((1, 0)),
+        }
+    }
+}
+
 /// SPIR-V operand kind: [KernelEnqueueFlags](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_kernel_enqueue_flags_a_kernel_enqueue_flags)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -576,6 +2983,54 @@ pub enum KernelEnqueueFlags {
     WaitWorkGroup = 2,
 }
 
+impl fmt::Display for KernelEnqueueFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            KernelEnqueueFlags::NoWait => "NoWait",
+            KernelEnqueueFlags::WaitKernel => "WaitKernel",
+            KernelEnqueueFlags::WaitWorkGroup => "WaitWorkGroup",
+        })
+    }
+}
+
+impl str::FromStr for KernelEnqueueFlags {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NoWait" => Ok(KernelEnqueueFlags::NoWait),
+            "WaitKernel" => Ok(KernelEnqueueFlags::WaitKernel),
+            "WaitWorkGroup" => Ok(KernelEnqueueFlags::WaitWorkGroup),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl KernelEnqueueFlags {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            KernelEnqueueFlags::NoWait => &[Capability::Kernel],
+            KernelEnqueueFlags::WaitKernel => &[Capability::Kernel],
+            KernelEnqueueFlags::WaitWorkGroup => &[Capability::Kernel],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V operand kind: [Capability](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_capability_a_capability)
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -663,6 +3118,15 @@ pub enum Capability {
     StorageBuffer8BitAccess = 4448,
     UniformAndStorageBuffer8BitAccess = 4449,
     StoragePushConstant8 = 4450,
+    DenormPreserve = 4464,
+    DenormFlushToZero = 4465,
+    SignedZeroInfNanPreserve = 4466,
+    RoundingModeRTE = 4467,
+    RoundingModeRTZ = 4468,
+    MeshShadingEXT = 5283,
+    RayQueryKHR = 4472,
+    RayTraversalPrimitiveCullingKHR = 4478,
+    RayTracingKHR = 4479,
     Float16ImageAMD = 5008,
     ImageGatherBiasLodAMD = 5009,
     FragmentMaskAMD = 5010,
@@ -693,6 +3157,7 @@ pub enum Capability {
     GroupNonUniformPartitionedNV = 5297,
     VulkanMemoryModelKHR = 5345,
     VulkanMemoryModelDeviceScopeKHR = 5346,
+    CooperativeMatrixKHR = 6022,
 }
 
 #[allow(non_upper_case_globals)]
@@ -701,6 +3166,777 @@ impl Capability {
     pub const StorageUniform16: Capability = Capability::UniformAndStorageBuffer16BitAccess;
     pub const ShaderViewportIndexLayerNV: Capability = Capability::ShaderViewportIndexLayerEXT;
 }
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Capability::Matrix => "Matrix",
+            Capability::Shader => "Shader",
+            Capability::Geometry => "Geometry",
+            Capability::Tessellation => "Tessellation",
+            Capability::Addresses => "Addresses",
+            Capability::Linkage => "Linkage",
+            Capability::Kernel => "Kernel",
+            Capability::Vector16 => "Vector16",
+            Capability::Float16Buffer => "Float16Buffer",
+            Capability::Float16 => "Float16",
+            Capability::Float64 => "Float64",
+            Capability::Int64 => "Int64",
+            Capability::Int64Atomics => "Int64Atomics",
+            Capability::ImageBasic => "ImageBasic",
+            Capability::ImageReadWrite => "ImageReadWrite",
+            Capability::ImageMipmap => "ImageMipmap",
+            Capability::Pipes => "Pipes",
+            Capability::Groups => "Groups",
+            Capability::DeviceEnqueue => "DeviceEnqueue",
+            Capability::LiteralSampler => "LiteralSampler",
+            Capability::AtomicStorage => "AtomicStorage",
+            Capability::Int16 => "Int16",
+            Capability::TessellationPointSize => "TessellationPointSize",
+            Capability::GeometryPointSize => "GeometryPointSize",
+            Capability::ImageGatherExtended => "ImageGatherExtended",
+            Capability::StorageImageMultisample => "StorageImageMultisample",
+            Capability::UniformBufferArrayDynamicIndexing => "UniformBufferArrayDynamicIndexing",
+            Capability::SampledImageArrayDynamicIndexing => "SampledImageArrayDynamicIndexing",
+            Capability::StorageBufferArrayDynamicIndexing => "StorageBufferArrayDynamicIndexing",
+            Capability::StorageImageArrayDynamicIndexing => "StorageImageArrayDynamicIndexing",
+            Capability::ClipDistance => "ClipDistance",
+            Capability::CullDistance => "CullDistance",
+            Capability::ImageCubeArray => "ImageCubeArray",
+            Capability::SampleRateShading => "SampleRateShading",
+            Capability::ImageRect => "ImageRect",
+            Capability::SampledRect => "SampledRect",
+            Capability::GenericPointer => "GenericPointer",
+            Capability::Int8 => "Int8",
+            Capability::InputAttachment => "InputAttachment",
+            Capability::SparseResidency => "SparseResidency",
+            Capability::MinLod => "MinLod",
+            Capability::Sampled1D => "Sampled1D",
+            Capability::Image1D => "Image1D",
+            Capability::SampledCubeArray => "SampledCubeArray",
+            Capability::SampledBuffer => "SampledBuffer",
+            Capability::ImageBuffer => "ImageBuffer",
+            Capability::ImageMSArray => "ImageMSArray",
+            Capability::StorageImageExtendedFormats => "StorageImageExtendedFormats",
+            Capability::ImageQuery => "ImageQuery",
+            Capability::DerivativeControl => "DerivativeControl",
+            Capability::InterpolationFunction => "InterpolationFunction",
+            Capability::TransformFeedback => "TransformFeedback",
+            Capability::GeometryStreams => "GeometryStreams",
+            Capability::StorageImageReadWithoutFormat => "StorageImageReadWithoutFormat",
+            Capability::StorageImageWriteWithoutFormat => "StorageImageWriteWithoutFormat",
+            Capability::MultiViewport => "MultiViewport",
+            Capability::SubgroupDispatch => "SubgroupDispatch",
+            Capability::NamedBarrier => "NamedBarrier",
+            Capability::PipeStorage => "PipeStorage",
+            Capability::GroupNonUniform => "GroupNonUniform",
+            Capability::GroupNonUniformVote => "GroupNonUniformVote",
+            Capability::GroupNonUniformArithmetic => "GroupNonUniformArithmetic",
+            Capability::GroupNonUniformBallot => "GroupNonUniformBallot",
+            Capability::GroupNonUniformShuffle => "GroupNonUniformShuffle",
+            Capability::GroupNonUniformShuffleRelative => "GroupNonUniformShuffleRelative",
+            Capability::GroupNonUniformClustered => "GroupNonUniformClustered",
+            Capability::GroupNonUniformQuad => "GroupNonUniformQuad",
+            Capability::SubgroupBallotKHR => "SubgroupBallotKHR",
+            Capability::DrawParameters => "DrawParameters",
+            Capability::SubgroupVoteKHR => "SubgroupVoteKHR",
+            Capability::StorageBuffer16BitAccess => "StorageBuffer16BitAccess",
+            Capability::UniformAndStorageBuffer16BitAccess => "UniformAndStorageBuffer16BitAccess",
+            Capability::StoragePushConstant16 => "StoragePushConstant16",
+            Capability::StorageInputOutput16 => "StorageInputOutput16",
+            Capability::DeviceGroup => "DeviceGroup",
+            Capability::MultiView => "MultiView",
+            Capability::VariablePointersStorageBuffer => "VariablePointersStorageBuffer",
+            Capability::VariablePointers => "VariablePointers",
+            Capability::AtomicStorageOps => "AtomicStorageOps",
+            Capability::SampleMaskPostDepthCoverage => "SampleMaskPostDepthCoverage",
+            Capability::StorageBuffer8BitAccess => "StorageBuffer8BitAccess",
+            Capability::UniformAndStorageBuffer8BitAccess => "UniformAndStorageBuffer8BitAccess",
+            Capability::StoragePushConstant8 => "StoragePushConstant8",
+            Capability::DenormPreserve => "DenormPreserve",
+            Capability::DenormFlushToZero => "DenormFlushToZero",
+            Capability::SignedZeroInfNanPreserve => "SignedZeroInfNanPreserve",
+            Capability::RoundingModeRTE => "RoundingModeRTE",
+            Capability::RoundingModeRTZ => "RoundingModeRTZ",
+            Capability::MeshShadingEXT => "MeshShadingEXT",
+            Capability::RayQueryKHR => "RayQueryKHR",
+            Capability::RayTraversalPrimitiveCullingKHR => "RayTraversalPrimitiveCullingKHR",
+            Capability::RayTracingKHR => "RayTracingKHR",
+            Capability::Float16ImageAMD => "Float16ImageAMD",
+            Capability::ImageGatherBiasLodAMD => "ImageGatherBiasLodAMD",
+            Capability::FragmentMaskAMD => "FragmentMaskAMD",
+            Capability::StencilExportEXT => "StencilExportEXT",
+            Capability::ImageReadWriteLodAMD => "ImageReadWriteLodAMD",
+            Capability::SampleMaskOverrideCoverageNV => "SampleMaskOverrideCoverageNV",
+            Capability::GeometryShaderPassthroughNV => "GeometryShaderPassthroughNV",
+            Capability::ShaderViewportIndexLayerEXT => "ShaderViewportIndexLayerEXT",
+            Capability::ShaderViewportMaskNV => "ShaderViewportMaskNV",
+            Capability::ShaderStereoViewNV => "ShaderStereoViewNV",
+            Capability::PerViewAttributesNV => "PerViewAttributesNV",
+            Capability::FragmentFullyCoveredEXT => "FragmentFullyCoveredEXT",
+            Capability::ShaderNonUniformEXT => "ShaderNonUniformEXT",
+            Capability::RuntimeDescriptorArrayEXT => "RuntimeDescriptorArrayEXT",
+            Capability::InputAttachmentArrayDynamicIndexingEXT => "InputAttachmentArrayDynamicIndexingEXT",
+            Capability::UniformTexelBufferArrayDynamicIndexingEXT => "UniformTexelBufferArrayDynamicIndexingEXT",
+            Capability::StorageTexelBufferArrayDynamicIndexingEXT => "StorageTexelBufferArrayDynamicIndexingEXT",
+            Capability::UniformBufferArrayNonUniformIndexingEXT => "UniformBufferArrayNonUniformIndexingEXT",
+            Capability::SampledImageArrayNonUniformIndexingEXT => "SampledImageArrayNonUniformIndexingEXT",
+            Capability::StorageBufferArrayNonUniformIndexingEXT => "StorageBufferArrayNonUniformIndexingEXT",
+            Capability::StorageImageArrayNonUniformIndexingEXT => "StorageImageArrayNonUniformIndexingEXT",
+            Capability::InputAttachmentArrayNonUniformIndexingEXT => "InputAttachmentArrayNonUniformIndexingEXT",
+            Capability::UniformTexelBufferArrayNonUniformIndexingEXT => "UniformTexelBufferArrayNonUniformIndexingEXT",
+            Capability::StorageTexelBufferArrayNonUniformIndexingEXT => "StorageTexelBufferArrayNonUniformIndexingEXT",
+            Capability::SubgroupShuffleINTEL => "SubgroupShuffleINTEL",
+            Capability::SubgroupBufferBlockIOINTEL => "SubgroupBufferBlockIOINTEL",
+            Capability::SubgroupImageBlockIOINTEL => "SubgroupImageBlockIOINTEL",
+            Capability::GroupNonUniformPartitionedNV => "GroupNonUniformPartitionedNV",
+            Capability::VulkanMemoryModelKHR => "VulkanMemoryModelKHR",
+            Capability::VulkanMemoryModelDeviceScopeKHR => "VulkanMemoryModelDeviceScopeKHR",
+            Capability::CooperativeMatrixKHR => "CooperativeMatrixKHR",
+        })
+    }
+}
+
+impl str::FromStr for Capability {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Matrix" => Ok(Capability::Matrix),
+            "Shader" => Ok(Capability::Shader),
+            "Geometry" => Ok(Capability::Geometry),
+            "Tessellation" => Ok(Capability::Tessellation),
+            "Addresses" => Ok(Capability::Addresses),
+            "Linkage" => Ok(Capability::Linkage),
+            "Kernel" => Ok(Capability::Kernel),
+            "Vector16" => Ok(Capability::Vector16),
+            "Float16Buffer" => Ok(Capability::Float16Buffer),
+            "Float16" => Ok(Capability::Float16),
+            "Float64" => Ok(Capability::Float64),
+            "Int64" => Ok(Capability::Int64),
+            "Int64Atomics" => Ok(Capability::Int64Atomics),
+            "ImageBasic" => Ok(Capability::ImageBasic),
+            "ImageReadWrite" => Ok(Capability::ImageReadWrite),
+            "ImageMipmap" => Ok(Capability::ImageMipmap),
+            "Pipes" => Ok(Capability::Pipes),
+            "Groups" => Ok(Capability::Groups),
+            "DeviceEnqueue" => Ok(Capability::DeviceEnqueue),
+            "LiteralSampler" => Ok(Capability::LiteralSampler),
+            "AtomicStorage" => Ok(Capability::AtomicStorage),
+            "Int16" => Ok(Capability::Int16),
+            "TessellationPointSize" => Ok(Capability::TessellationPointSize),
+            "GeometryPointSize" => Ok(Capability::GeometryPointSize),
+            "ImageGatherExtended" => Ok(Capability::ImageGatherExtended),
+            "StorageImageMultisample" => Ok(Capability::StorageImageMultisample),
+            "UniformBufferArrayDynamicIndexing" => Ok(Capability::UniformBufferArrayDynamicIndexing),
+            "SampledImageArrayDynamicIndexing" => Ok(Capability::SampledImageArrayDynamicIndexing),
+            "StorageBufferArrayDynamicIndexing" => Ok(Capability::StorageBufferArrayDynamicIndexing),
+            "StorageImageArrayDynamicIndexing" => Ok(Capability::StorageImageArrayDynamicIndexing),
+            "ClipDistance" => Ok(Capability::ClipDistance),
+            "CullDistance" => Ok(Capability::CullDistance),
+            "ImageCubeArray" => Ok(Capability::ImageCubeArray),
+            "SampleRateShading" => Ok(Capability::SampleRateShading),
+            "ImageRect" => Ok(Capability::ImageRect),
+            "SampledRect" => Ok(Capability::SampledRect),
+            "GenericPointer" => Ok(Capability::GenericPointer),
+            "Int8" => Ok(Capability::Int8),
+            "InputAttachment" => Ok(Capability::InputAttachment),
+            "SparseResidency" => Ok(Capability::SparseResidency),
+            "MinLod" => Ok(Capability::MinLod),
+            "Sampled1D" => Ok(Capability::Sampled1D),
+            "Image1D" => Ok(Capability::Image1D),
+            "SampledCubeArray" => Ok(Capability::SampledCubeArray),
+            "SampledBuffer" => Ok(Capability::SampledBuffer),
+            "ImageBuffer" => Ok(Capability::ImageBuffer),
+            "ImageMSArray" => Ok(Capability::ImageMSArray),
+            "StorageImageExtendedFormats" => Ok(Capability::StorageImageExtendedFormats),
+            "ImageQuery" => Ok(Capability::ImageQuery),
+            "DerivativeControl" => Ok(Capability::DerivativeControl),
+            "InterpolationFunction" => Ok(Capability::InterpolationFunction),
+            "TransformFeedback" => Ok(Capability::TransformFeedback),
+            "GeometryStreams" => Ok(Capability::GeometryStreams),
+            "StorageImageReadWithoutFormat" => Ok(Capability::StorageImageReadWithoutFormat),
+            "StorageImageWriteWithoutFormat" => Ok(Capability::StorageImageWriteWithoutFormat),
+            "MultiViewport" => Ok(Capability::MultiViewport),
+            "SubgroupDispatch" => Ok(Capability::SubgroupDispatch),
+            "NamedBarrier" => Ok(Capability::NamedBarrier),
+            "PipeStorage" => Ok(Capability::PipeStorage),
+            "GroupNonUniform" => Ok(Capability::GroupNonUniform),
+            "GroupNonUniformVote" => Ok(Capability::GroupNonUniformVote),
+            "GroupNonUniformArithmetic" => Ok(Capability::GroupNonUniformArithmetic),
+            "GroupNonUniformBallot" => Ok(Capability::GroupNonUniformBallot),
+            "GroupNonUniformShuffle" => Ok(Capability::GroupNonUniformShuffle),
+            "GroupNonUniformShuffleRelative" => Ok(Capability::GroupNonUniformShuffleRelative),
+            "GroupNonUniformClustered" => Ok(Capability::GroupNonUniformClustered),
+            "GroupNonUniformQuad" => Ok(Capability::GroupNonUniformQuad),
+            "SubgroupBallotKHR" => Ok(Capability::SubgroupBallotKHR),
+            "DrawParameters" => Ok(Capability::DrawParameters),
+            "SubgroupVoteKHR" => Ok(Capability::SubgroupVoteKHR),
+            "StorageBuffer16BitAccess" => Ok(Capability::StorageBuffer16BitAccess),
+            "StorageUniformBufferBlock16" => Ok(Capability::StorageUniformBufferBlock16),
+            "UniformAndStorageBuffer16BitAccess" => Ok(Capability::UniformAndStorageBuffer16BitAccess),
+            "StorageUniform16" => Ok(Capability::StorageUniform16),
+            "StoragePushConstant16" => Ok(Capability::StoragePushConstant16),
+            "StorageInputOutput16" => Ok(Capability::StorageInputOutput16),
+            "DeviceGroup" => Ok(Capability::DeviceGroup),
+            "MultiView" => Ok(Capability::MultiView),
+            "VariablePointersStorageBuffer" => Ok(Capability::VariablePointersStorageBuffer),
+            "VariablePointers" => Ok(Capability::VariablePointers),
+            "AtomicStorageOps" => Ok(Capability::AtomicStorageOps),
+            "SampleMaskPostDepthCoverage" => Ok(Capability::SampleMaskPostDepthCoverage),
+            "StorageBuffer8BitAccess" => Ok(Capability::StorageBuffer8BitAccess),
+            "UniformAndStorageBuffer8BitAccess" => Ok(Capability::UniformAndStorageBuffer8BitAccess),
+            "StoragePushConstant8" => Ok(Capability::StoragePushConstant8),
+            "DenormPreserve" => Ok(Capability::DenormPreserve),
+            "DenormFlushToZero" => Ok(Capability::DenormFlushToZero),
+            "SignedZeroInfNanPreserve" => Ok(Capability::SignedZeroInfNanPreserve),
+            "RoundingModeRTE" => Ok(Capability::RoundingModeRTE),
+            "RoundingModeRTZ" => Ok(Capability::RoundingModeRTZ),
+            "MeshShadingEXT" => Ok(Capability::MeshShadingEXT),
+            "RayQueryKHR" => Ok(Capability::RayQueryKHR),
+            "RayTraversalPrimitiveCullingKHR" => Ok(Capability::RayTraversalPrimitiveCullingKHR),
+            "RayTracingKHR" => Ok(Capability::RayTracingKHR),
+            "Float16ImageAMD" => Ok(Capability::Float16ImageAMD),
+            "ImageGatherBiasLodAMD" => Ok(Capability::ImageGatherBiasLodAMD),
+            "FragmentMaskAMD" => Ok(Capability::FragmentMaskAMD),
+            "StencilExportEXT" => Ok(Capability::StencilExportEXT),
+            "ImageReadWriteLodAMD" => Ok(Capability::ImageReadWriteLodAMD),
+            "SampleMaskOverrideCoverageNV" => Ok(Capability::SampleMaskOverrideCoverageNV),
+            "GeometryShaderPassthroughNV" => Ok(Capability::GeometryShaderPassthroughNV),
+            "ShaderViewportIndexLayerEXT" => Ok(Capability::ShaderViewportIndexLayerEXT),
+            "ShaderViewportIndexLayerNV" => Ok(Capability::ShaderViewportIndexLayerNV),
+            "ShaderViewportMaskNV" => Ok(Capability::ShaderViewportMaskNV),
+            "ShaderStereoViewNV" => Ok(Capability::ShaderStereoViewNV),
+            "PerViewAttributesNV" => Ok(Capability::PerViewAttributesNV),
+            "FragmentFullyCoveredEXT" => Ok(Capability::FragmentFullyCoveredEXT),
+            "ShaderNonUniformEXT" => Ok(Capability::ShaderNonUniformEXT),
+            "RuntimeDescriptorArrayEXT" => Ok(Capability::RuntimeDescriptorArrayEXT),
+            "InputAttachmentArrayDynamicIndexingEXT" => Ok(Capability::InputAttachmentArrayDynamicIndexingEXT),
+            "UniformTexelBufferArrayDynamicIndexingEXT" => Ok(Capability::UniformTexelBufferArrayDynamicIndexingEXT),
+            "StorageTexelBufferArrayDynamicIndexingEXT" => Ok(Capability::StorageTexelBufferArrayDynamicIndexingEXT),
+            "UniformBufferArrayNonUniformIndexingEXT" => Ok(Capability::UniformBufferArrayNonUniformIndexingEXT),
+            "SampledImageArrayNonUniformIndexingEXT" => Ok(Capability::SampledImageArrayNonUniformIndexingEXT),
+            "StorageBufferArrayNonUniformIndexingEXT" => Ok(Capability::StorageBufferArrayNonUniformIndexingEXT),
+            "StorageImageArrayNonUniformIndexingEXT" => Ok(Capability::StorageImageArrayNonUniformIndexingEXT),
+            "InputAttachmentArrayNonUniformIndexingEXT" => Ok(Capability::InputAttachmentArrayNonUniformIndexingEXT),
+            "UniformTexelBufferArrayNonUniformIndexingEXT" => Ok(Capability::UniformTexelBufferArrayNonUniformIndexingEXT),
+            "StorageTexelBufferArrayNonUniformIndexingEXT" => Ok(Capability::StorageTexelBufferArrayNonUniformIndexingEXT),
+            "SubgroupShuffleINTEL" => Ok(Capability::SubgroupShuffleINTEL),
+            "SubgroupBufferBlockIOINTEL" => Ok(Capability::SubgroupBufferBlockIOINTEL),
+            "SubgroupImageBlockIOINTEL" => Ok(Capability::SubgroupImageBlockIOINTEL),
+            "GroupNonUniformPartitionedNV" => Ok(Capability::GroupNonUniformPartitionedNV),
+            "VulkanMemoryModelKHR" => Ok(Capability::VulkanMemoryModelKHR),
+            "VulkanMemoryModelDeviceScopeKHR" => Ok(Capability::VulkanMemoryModelDeviceScopeKHR),
+            "CooperativeMatrixKHR" => Ok(Capability::CooperativeMatrixKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl Capability {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            Capability::Shader => &[Capability::Matrix],
+            Capability::Geometry => &[Capability::Shader],
+            Capability::Tessellation => &[Capability::Shader],
+            Capability::Vector16 => &[Capability::Kernel],
+            Capability::Float16Buffer => &[Capability::Kernel],
+            Capability::Int64Atomics => &[Capability::Int64],
+            Capability::ImageBasic => &[Capability::Kernel],
+            Capability::ImageReadWrite => &[Capability::ImageBasic],
+            Capability::ImageMipmap => &[Capability::ImageBasic],
+            Capability::Pipes => &[Capability::Kernel],
+            Capability::DeviceEnqueue => &[Capability::Kernel],
+            Capability::LiteralSampler => &[Capability::Kernel],
+            Capability::AtomicStorage => &[Capability::Shader],
+            Capability::TessellationPointSize => &[Capability::Tessellation],
+            Capability::GeometryPointSize => &[Capability::Geometry],
+            Capability::ImageGatherExtended => &[Capability::Shader],
+            Capability::StorageImageMultisample => &[Capability::Shader],
+            Capability::UniformBufferArrayDynamicIndexing => &[Capability::Shader],
+            Capability::SampledImageArrayDynamicIndexing => &[Capability::Shader],
+            Capability::StorageBufferArrayDynamicIndexing => &[Capability::Shader],
+            Capability::StorageImageArrayDynamicIndexing => &[Capability::Shader],
+            Capability::ClipDistance => &[Capability::Shader],
+            Capability::CullDistance => &[Capability::Shader],
+            Capability::ImageCubeArray => &[Capability::SampledCubeArray],
+            Capability::SampleRateShading => &[Capability::Shader],
+            Capability::ImageRect => &[Capability::SampledRect],
+            Capability::SampledRect => &[Capability::Shader],
+            Capability::GenericPointer => &[Capability::Addresses],
+            Capability::InputAttachment => &[Capability::Shader],
+            Capability::SparseResidency => &[Capability::Shader],
+            Capability::MinLod => &[Capability::Shader],
+            Capability::Image1D => &[Capability::Sampled1D],
+            Capability::SampledCubeArray => &[Capability::Shader],
+            Capability::ImageBuffer => &[Capability::SampledBuffer],
+            Capability::ImageMSArray => &[Capability::Shader],
+            Capability::StorageImageExtendedFormats => &[Capability::Shader],
+            Capability::ImageQuery => &[Capability::Shader],
+            Capability::DerivativeControl => &[Capability::Shader],
+            Capability::InterpolationFunction => &[Capability::Shader],
+            Capability::TransformFeedback => &[Capability::Shader],
+            Capability::GeometryStreams => &[Capability::Geometry],
+            Capability::StorageImageReadWithoutFormat => &[Capability::Shader],
+            Capability::StorageImageWriteWithoutFormat => &[Capability::Shader],
+            Capability::MultiViewport => &[Capability::Geometry],
+            Capability::SubgroupDispatch => &[Capability::DeviceEnqueue],
+            Capability::NamedBarrier => &[Capability::Kernel],
+            Capability::PipeStorage => &[Capability::Pipes],
+            Capability::GroupNonUniformVote => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformArithmetic => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformBallot => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformShuffle => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformShuffleRelative => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformClustered => &[Capability::GroupNonUniform],
+            Capability::GroupNonUniformQuad => &[Capability::GroupNonUniform],
+            Capability::DrawParameters => &[Capability::Shader],
+            Capability::UniformAndStorageBuffer16BitAccess => &[Capability::StorageBuffer16BitAccess, Capability::StorageUniformBufferBlock16],
+            Capability::MultiView => &[Capability::Shader],
+            Capability::VariablePointersStorageBuffer => &[Capability::Shader],
+            Capability::VariablePointers => &[Capability::VariablePointersStorageBuffer],
+            Capability::UniformAndStorageBuffer8BitAccess => &[Capability::StorageBuffer8BitAccess],
+            Capability::MeshShadingEXT => &[Capability::Shader],
+            Capability::RayQueryKHR => &[Capability::Shader],
+            Capability::RayTraversalPrimitiveCullingKHR => &[Capability::RayQueryKHR, Capability::RayTracingKHR],
+            Capability::RayTracingKHR => &[Capability::Shader],
+            Capability::Float16ImageAMD => &[Capability::Shader],
+            Capability::ImageGatherBiasLodAMD => &[Capability::Shader],
+            Capability::FragmentMaskAMD => &[Capability::Shader],
+            Capability::StencilExportEXT => &[Capability::Shader],
+            Capability::ImageReadWriteLodAMD => &[Capability::Shader],
+            Capability::SampleMaskOverrideCoverageNV => &[Capability::SampleRateShading],
+            Capability::GeometryShaderPassthroughNV => &[Capability::Geometry],
+            Capability::ShaderViewportIndexLayerEXT => &[Capability::MultiViewport],
+            Capability::ShaderViewportMaskNV => &[Capability::ShaderViewportIndexLayerNV],
+            Capability::ShaderStereoViewNV => &[Capability::ShaderViewportMaskNV],
+            Capability::PerViewAttributesNV => &[Capability::MultiView],
+            Capability::FragmentFullyCoveredEXT => &[Capability::Shader],
+            Capability::ShaderNonUniformEXT => &[Capability::Shader],
+            Capability::RuntimeDescriptorArrayEXT => &[Capability::Shader],
+            Capability::InputAttachmentArrayDynamicIndexingEXT => &[Capability::InputAttachment],
+            Capability::UniformTexelBufferArrayDynamicIndexingEXT => &[Capability::SampledBuffer],
+            Capability::StorageTexelBufferArrayDynamicIndexingEXT => &[Capability::ImageBuffer],
+            Capability::UniformBufferArrayNonUniformIndexingEXT => &[Capability::ShaderNonUniformEXT],
+            Capability::SampledImageArrayNonUniformIndexingEXT => &[Capability::ShaderNonUniformEXT],
+            Capability::StorageBufferArrayNonUniformIndexingEXT => &[Capability::ShaderNonUniformEXT],
+            Capability::StorageImageArrayNonUniformIndexingEXT => &[Capability::ShaderNonUniformEXT],
+            Capability::InputAttachmentArrayNonUniformIndexingEXT => &[Capability::InputAttachment, Capability::ShaderNonUniformEXT],
+            Capability::UniformTexelBufferArrayNonUniformIndexingEXT => &[Capability::SampledBuffer, Capability::ShaderNonUniformEXT],
+            Capability::StorageTexelBufferArrayNonUniformIndexingEXT => &[Capability::ImageBuffer, Capability::ShaderNonUniformEXT],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            Capability::SubgroupBallotKHR => &["SPV_KHR_shader_ballot"],
+            Capability::DrawParameters => &["SPV_KHR_shader_draw_parameters"],
+            Capability::SubgroupVoteKHR => &["SPV_KHR_subgroup_vote"],
+            Capability::StorageBuffer16BitAccess => &["SPV_KHR_16bit_storage"],
+            Capability::UniformAndStorageBuffer16BitAccess => &["SPV_KHR_16bit_storage"],
+            Capability::StoragePushConstant16 => &["SPV_KHR_16bit_storage"],
+            Capability::StorageInputOutput16 => &["SPV_KHR_16bit_storage"],
+            Capability::DeviceGroup => &["SPV_KHR_device_group"],
+            Capability::MultiView => &["SPV_KHR_multiview"],
+            Capability::VariablePointersStorageBuffer => &["SPV_KHR_variable_pointers"],
+            Capability::VariablePointers => &["SPV_KHR_variable_pointers"],
+            Capability::AtomicStorageOps => &["SPV_KHR_shader_atomic_counter_ops"],
+            Capability::SampleMaskPostDepthCoverage => &["SPV_KHR_post_depth_coverage"],
+            Capability::StorageBuffer8BitAccess => &["SPV_KHR_8bit_storage"],
+            Capability::UniformAndStorageBuffer8BitAccess => &["SPV_KHR_8bit_storage"],
+            Capability::StoragePushConstant8 => &["SPV_KHR_8bit_storage"],
+            Capability::DenormPreserve => &["SPV_KHR_float_controls"],
+            Capability::DenormFlushToZero => &["SPV_KHR_float_controls"],
+            Capability::SignedZeroInfNanPreserve => &["SPV_KHR_float_controls"],
+            Capability::RoundingModeRTE => &["SPV_KHR_float_controls"],
+            Capability::RoundingModeRTZ => &["SPV_KHR_float_controls"],
+            Capability::MeshShadingEXT => &["SPV_EXT_mesh_shader"],
+            Capability::RayQueryKHR => &["SPV_KHR_ray_query"],
+            Capability::RayTraversalPrimitiveCullingKHR => &["SPV_KHR_ray_query", "SPV_KHR_ray_tracing"],
+            Capability::RayTracingKHR => &["SPV_KHR_ray_tracing"],
+            Capability::Float16ImageAMD => &["SPV_AMD_gpu_shader_half_float_fetch"],
+            Capability::ImageGatherBiasLodAMD => &["SPV_AMD_texture_gather_bias_lod"],
+            Capability::FragmentMaskAMD => &["SPV_AMD_shader_fragment_mask"],
+            Capability::StencilExportEXT => &["SPV_EXT_shader_stencil_export"],
+            Capability::ImageReadWriteLodAMD => &["SPV_AMD_shader_image_load_store_lod"],
+            Capability::SampleMaskOverrideCoverageNV => &["SPV_NV_sample_mask_override_coverage"],
+            Capability::GeometryShaderPassthroughNV => &["SPV_NV_geometry_shader_passthrough"],
+            Capability::ShaderViewportIndexLayerEXT => &["SPV_EXT_shader_viewport_index_layer"],
+            Capability::ShaderViewportMaskNV => &["SPV_NV_viewport_array2"],
+            Capability::ShaderStereoViewNV => &["SPV_NV_stereo_view_rendering"],
+            Capability::PerViewAttributesNV => &["SPV_NVX_multiview_per_view_attributes"],
+            Capability::FragmentFullyCoveredEXT => &["SPV_EXT_fragment_fully_covered"],
+            Capability::ShaderNonUniformEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::RuntimeDescriptorArrayEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::InputAttachmentArrayDynamicIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::UniformTexelBufferArrayDynamicIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::StorageTexelBufferArrayDynamicIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::UniformBufferArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::SampledImageArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::StorageBufferArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::StorageImageArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::InputAttachmentArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::UniformTexelBufferArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::StorageTexelBufferArrayNonUniformIndexingEXT => &["SPV_EXT_descriptor_indexing"],
+            Capability::SubgroupShuffleINTEL => &["SPV_INTEL_subgroups"],
+            Capability::SubgroupBufferBlockIOINTEL => &["SPV_INTEL_subgroups"],
+            Capability::SubgroupImageBlockIOINTEL => &["SPV_INTEL_subgroups"],
+            Capability::GroupNonUniformPartitionedNV => &["SPV_NV_shader_subgroup_partitioned"],
+            Capability::VulkanMemoryModelKHR => &["SPV_KHR_vulkan_memory_model"],
+            Capability::VulkanMemoryModelDeviceScopeKHR => &["SPV_KHR_vulkan_memory_model"],
+            Capability::CooperativeMatrixKHR => &["SPV_KHR_cooperative_matrix"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            Capability::SubgroupDispatch => Some((1, 1)),
+            Capability::NamedBarrier => Some((1, 1)),
+            Capability::PipeStorage => Some((1, 1)),
+            Capability::GroupNonUniform => Some((1, 3)),
+            Capability::GroupNonUniformVote => Some((1, 3)),
+            Capability::GroupNonUniformArithmetic => Some((1, 3)),
+            Capability::GroupNonUniformBallot => Some((1, 3)),
+            Capability::GroupNonUniformShuffle => Some((1, 3)),
+            Capability::GroupNonUniformShuffleRelative => Some((1, 3)),
+            Capability::GroupNonUniformClustered => Some((1, 3)),
+            Capability::GroupNonUniformQuad => Some((1, 3)),
+            Capability::SubgroupBallotKHR => None,
+            Capability::DrawParameters => Some((1, 3)),
+            Capability::SubgroupVoteKHR => None,
+            Capability::StorageBuffer16BitAccess => Some((1, 3)),
+            Capability::UniformAndStorageBuffer16BitAccess => Some((1, 3)),
+            Capability::StoragePushConstant16 => Some((1, 3)),
+            Capability::StorageInputOutput16 => Some((1, 3)),
+            Capability::DeviceGroup => Some((1, 3)),
+            Capability::MultiView => Some((1, 3)),
+            Capability::VariablePointersStorageBuffer => Some((1, 3)),
+            Capability::VariablePointers => Some((1, 3)),
+            Capability::AtomicStorageOps => None,
+            Capability::SampleMaskPostDepthCoverage => None,
+            Capability::StorageBuffer8BitAccess => None,
+            Capability::UniformAndStorageBuffer8BitAccess => None,
+            Capability::StoragePushConstant8 => None,
+            Capability::DenormPreserve => Some((1, 4)),
+            Capability::DenormFlushToZero => Some((1, 4)),
+            Capability::SignedZeroInfNanPreserve => Some((1, 4)),
+            Capability::RoundingModeRTE => Some((1, 4)),
+            Capability::RoundingModeRTZ => Some((1, 4)),
+            Capability::MeshShadingEXT => None,
+            Capability::RayQueryKHR => None,
+            Capability::RayTraversalPrimitiveCullingKHR => None,
+            Capability::RayTracingKHR => None,
+            Capability::Float16ImageAMD => None,
+            Capability::ImageGatherBiasLodAMD => None,
+            Capability::FragmentMaskAMD => None,
+            Capability::StencilExportEXT => None,
+            Capability::ImageReadWriteLodAMD => None,
+            Capability::SampleMaskOverrideCoverageNV => None,
+            Capability::GeometryShaderPassthroughNV => None,
+            Capability::ShaderViewportIndexLayerEXT => None,
+            Capability::ShaderViewportMaskNV => None,
+            Capability::ShaderStereoViewNV => None,
+            Capability::PerViewAttributesNV => None,
+            Capability::FragmentFullyCoveredEXT => None,
+            Capability::ShaderNonUniformEXT => None,
+            Capability::RuntimeDescriptorArrayEXT => None,
+            Capability::InputAttachmentArrayDynamicIndexingEXT => None,
+            Capability::UniformTexelBufferArrayDynamicIndexingEXT => None,
+            Capability::StorageTexelBufferArrayDynamicIndexingEXT => None,
+            Capability::UniformBufferArrayNonUniformIndexingEXT => None,
+            Capability::SampledImageArrayNonUniformIndexingEXT => None,
+            Capability::StorageBufferArrayNonUniformIndexingEXT => None,
+            Capability::StorageImageArrayNonUniformIndexingEXT => None,
+            Capability::InputAttachmentArrayNonUniformIndexingEXT => None,
+            Capability::UniformTexelBufferArrayNonUniformIndexingEXT => None,
+            Capability::StorageTexelBufferArrayNonUniformIndexingEXT => None,
+            Capability::SubgroupShuffleINTEL => None,
+            Capability::SubgroupBufferBlockIOINTEL => None,
+            Capability::SubgroupImageBlockIOINTEL => None,
+            Capability::GroupNonUniformPartitionedNV => None,
+            Capability::VulkanMemoryModelKHR => None,
+            Capability::VulkanMemoryModelDeviceScopeKHR => None,
+            Capability::CooperativeMatrixKHR => None,
+            _ => Some((1, 0)),
+        }
+    }
+}
+
+/// SPIR-V operand kind: [RayQueryIntersection](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_ray_query_intersection_a_ray_query_intersection)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum RayQueryIntersection {
+    RayQueryCandidateIntersectionKHR = 0,
+    RayQueryCommittedIntersectionKHR = 1,
+}
+
+impl fmt::Display for RayQueryIntersection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            RayQueryIntersection::RayQueryCandidateIntersectionKHR => "RayQueryCandidateIntersectionKHR",
+            RayQueryIntersection::RayQueryCommittedIntersectionKHR => "RayQueryCommittedIntersectionKHR",
+        })
+    }
+}
+
+impl str::FromStr for RayQueryIntersection {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RayQueryCandidateIntersectionKHR" => Ok(RayQueryIntersection::RayQueryCandidateIntersectionKHR),
+            "RayQueryCommittedIntersectionKHR" => Ok(RayQueryIntersection::RayQueryCommittedIntersectionKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl RayQueryIntersection {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            RayQueryIntersection::RayQueryCandidateIntersectionKHR => &[Capability::RayQueryKHR],
+            RayQueryIntersection::RayQueryCommittedIntersectionKHR => &[Capability::RayQueryKHR],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
+/// SPIR-V operand kind: [RayQueryCommittedIntersectionType](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_ray_query_committed_intersection_type_a_ray_query_committed_intersection_type)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum RayQueryCommittedIntersectionType {
+    RayQueryCommittedIntersectionNoneKHR = 0,
+    RayQueryCommittedIntersectionTriangleKHR = 1,
+    RayQueryCommittedIntersectionGeneratedKHR = 2,
+}
+
+impl fmt::Display for RayQueryCommittedIntersectionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionNoneKHR => "RayQueryCommittedIntersectionNoneKHR",
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionTriangleKHR => "RayQueryCommittedIntersectionTriangleKHR",
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionGeneratedKHR => "RayQueryCommittedIntersectionGeneratedKHR",
+        })
+    }
+}
+
+impl str::FromStr for RayQueryCommittedIntersectionType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RayQueryCommittedIntersectionNoneKHR" => Ok(RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionNoneKHR),
+            "RayQueryCommittedIntersectionTriangleKHR" => Ok(RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionTriangleKHR),
+            "RayQueryCommittedIntersectionGeneratedKHR" => Ok(RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionGeneratedKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl RayQueryCommittedIntersectionType {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionNoneKHR => &[Capability::RayQueryKHR],
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionTriangleKHR => &[Capability::RayQueryKHR],
+            RayQueryCommittedIntersectionType::RayQueryCommittedIntersectionGeneratedKHR => &[Capability::RayQueryKHR],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
+/// SPIR-V operand kind: [RayQueryCandidateIntersectionType](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_ray_query_candidate_intersection_type_a_ray_query_candidate_intersection_type)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum RayQueryCandidateIntersectionType {
+    RayQueryCandidateIntersectionTriangleKHR = 0,
+    RayQueryCandidateIntersectionAABBKHR = 1,
+}
+
+impl fmt::Display for RayQueryCandidateIntersectionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionTriangleKHR => "RayQueryCandidateIntersectionTriangleKHR",
+            RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionAABBKHR => "RayQueryCandidateIntersectionAABBKHR",
+        })
+    }
+}
+
+impl str::FromStr for RayQueryCandidateIntersectionType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RayQueryCandidateIntersectionTriangleKHR" => Ok(RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionTriangleKHR),
+            "RayQueryCandidateIntersectionAABBKHR" => Ok(RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionAABBKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl RayQueryCandidateIntersectionType {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionTriangleKHR => &[Capability::RayQueryKHR],
+            RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionAABBKHR => &[Capability::RayQueryKHR],
+        }
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
+/// SPIR-V operand kind: [CooperativeMatrixLayout](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_cooperative_matrix_layout_a_cooperative_matrix_layout)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum CooperativeMatrixLayout {
+    RowMajorKHR = 0,
+    ColumnMajorKHR = 1,
+}
+
+impl fmt::Display for CooperativeMatrixLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            CooperativeMatrixLayout::RowMajorKHR => "RowMajorKHR",
+            CooperativeMatrixLayout::ColumnMajorKHR => "ColumnMajorKHR",
+        })
+    }
+}
+
+impl str::FromStr for CooperativeMatrixLayout {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RowMajorKHR" => Ok(CooperativeMatrixLayout::RowMajorKHR),
+            "ColumnMajorKHR" => Ok(CooperativeMatrixLayout::ColumnMajorKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl CooperativeMatrixLayout {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        &[]
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
+/// SPIR-V operand kind: [CooperativeMatrixUse](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_cooperative_matrix_use_a_cooperative_matrix_use)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum CooperativeMatrixUse {
+    MatrixAKHR = 0,
+    MatrixBKHR = 1,
+    MatrixAccumulatorKHR = 2,
+}
+
+impl fmt::Display for CooperativeMatrixUse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            CooperativeMatrixUse::MatrixAKHR => "MatrixAKHR",
+            CooperativeMatrixUse::MatrixBKHR => "MatrixBKHR",
+            CooperativeMatrixUse::MatrixAccumulatorKHR => "MatrixAccumulatorKHR",
+        })
+    }
+}
+
+impl str::FromStr for CooperativeMatrixUse {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "MatrixAKHR" => Ok(CooperativeMatrixUse::MatrixAKHR),
+            "MatrixBKHR" => Ok(CooperativeMatrixUse::MatrixBKHR),
+            "MatrixAccumulatorKHR" => Ok(CooperativeMatrixUse::MatrixAccumulatorKHR),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+impl CooperativeMatrixUse {
+    /// Returns the capabilities enabling this enumerant; any one of them is
+    /// sufficient. The enumerant is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        &[]
+    }
+
+    /// Returns the extensions enabling this enumerant in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this enumerant
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        Some((1, 0))
+    }
+}
+
 /// SPIR-V [instructions](https://www.khronos.org/registry/spir-v/specs/unified1/SPIRV.html#_a_id_instructions_a_instructions) opcodes
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
@@ -1051,6 +4287,23 @@ pub enum Op {
     SubgroupAnyKHR = 4429,
     SubgroupAllEqualKHR = 4430,
     SubgroupReadInvocationKHR = 4432,
+    TraceRayKHR = 4445,
+    ExecuteCallableKHR = 4446,
+    ConvertUToAccelerationStructureKHR = 4447,
+    IgnoreIntersectionKHR = 4448,
+    TerminateRayKHR = 4449,
+    TypeCooperativeMatrixKHR = 4456,
+    CooperativeMatrixLoadKHR = 4457,
+    CooperativeMatrixStoreKHR = 4458,
+    CooperativeMatrixMulAddKHR = 4459,
+    CooperativeMatrixLengthKHR = 4460,
+    TypeRayQueryKHR = 4472,
+    RayQueryInitializeKHR = 4473,
+    RayQueryTerminateKHR = 4474,
+    RayQueryGenerateIntersectionKHR = 4475,
+    RayQueryConfirmIntersectionKHR = 4476,
+    RayQueryProceedKHR = 4477,
+    RayQueryGetIntersectionTypeKHR = 4479,
     GroupIAddNonUniformAMD = 5000,
     GroupFAddNonUniformAMD = 5001,
     GroupFMinNonUniformAMD = 5002,
@@ -1061,6 +4314,9 @@ pub enum Op {
     GroupSMaxNonUniformAMD = 5007,
     FragmentMaskFetchAMD = 5011,
     FragmentFetchAMD = 5012,
+    EmitMeshTasksEXT = 5294,
+    SetMeshOutputsEXT = 5295,
+    TypeAccelerationStructureKHR = 5341,
     SubgroupShuffleINTEL = 5571,
     SubgroupShuffleDownINTEL = 5572,
     SubgroupShuffleUpINTEL = 5573,
@@ -1072,6 +4328,454 @@ pub enum Op {
     DecorateStringGOOGLE = 5632,
     MemberDecorateStringGOOGLE = 5633,
     GroupNonUniformPartitionNV = 5296,
+    RayQueryGetRayTMinKHR = 6016,
+    RayQueryGetRayFlagsKHR = 6017,
+    RayQueryGetIntersectionTKHR = 6018,
+    RayQueryGetIntersectionInstanceCustomIndexKHR = 6019,
+    RayQueryGetIntersectionInstanceIdKHR = 6020,
+    RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR = 6021,
+    RayQueryGetIntersectionGeometryIndexKHR = 6022,
+    RayQueryGetIntersectionPrimitiveIndexKHR = 6023,
+    RayQueryGetIntersectionBarycentricsKHR = 6024,
+    RayQueryGetIntersectionFrontFaceKHR = 6025,
+    RayQueryGetIntersectionCandidateAABBOpaqueKHR = 6026,
+    RayQueryGetIntersectionObjectRayDirectionKHR = 6027,
+    RayQueryGetIntersectionObjectRayOriginKHR = 6028,
+    RayQueryGetWorldRayDirectionKHR = 6029,
+    RayQueryGetWorldRayOriginKHR = 6030,
+    RayQueryGetIntersectionObjectToWorldKHR = 6031,
+    RayQueryGetIntersectionWorldToObjectKHR = 6032,
+}
+
+impl Op {
+    /// Returns the capabilities enabling this instruction; any one of them is
+    /// sufficient. The instruction is always available if there are none.
+    pub fn required_capabilities(self) -> &'static [Capability] {
+        match self {
+            Op::TypeMatrix => &[Capability::Matrix],
+            Op::TypeRuntimeArray => &[Capability::Shader],
+            Op::TypeOpaque => &[Capability::Kernel],
+            Op::TypeEvent => &[Capability::Kernel],
+            Op::TypeDeviceEvent => &[Capability::DeviceEnqueue],
+            Op::TypeReserveId => &[Capability::Pipes],
+            Op::TypeQueue => &[Capability::DeviceEnqueue],
+            Op::TypePipe => &[Capability::Pipes],
+            Op::TypeForwardPointer => &[Capability::Addresses],
+            Op::ConstantSampler => &[Capability::LiteralSampler],
+            Op::CopyMemorySized => &[Capability::Addresses],
+            Op::PtrAccessChain => &[Capability::Addresses, Capability::VariablePointers, Capability::VariablePointersStorageBuffer],
+            Op::ArrayLength => &[Capability::Shader],
+            Op::GenericPtrMemSemantics => &[Capability::Kernel],
+            Op::InBoundsPtrAccessChain => &[Capability::Addresses],
+            Op::Transpose => &[Capability::Matrix],
+            Op::ImageSampleImplicitLod => &[Capability::Shader],
+            Op::ImageSampleDrefImplicitLod => &[Capability::Shader],
+            Op::ImageSampleDrefExplicitLod => &[Capability::Shader],
+            Op::ImageSampleProjImplicitLod => &[Capability::Shader],
+            Op::ImageSampleProjExplicitLod => &[Capability::Shader],
+            Op::ImageSampleProjDrefImplicitLod => &[Capability::Shader],
+            Op::ImageSampleProjDrefExplicitLod => &[Capability::Shader],
+            Op::ImageGather => &[Capability::Shader],
+            Op::ImageDrefGather => &[Capability::Shader],
+            Op::ImageQueryFormat => &[Capability::Kernel],
+            Op::ImageQueryOrder => &[Capability::Kernel],
+            Op::ImageQuerySizeLod => &[Capability::Kernel, Capability::ImageQuery],
+            Op::ImageQuerySize => &[Capability::Kernel, Capability::ImageQuery],
+            Op::ImageQueryLod => &[Capability::ImageQuery],
+            Op::ImageQueryLevels => &[Capability::Kernel, Capability::ImageQuery],
+            Op::ImageQuerySamples => &[Capability::Kernel, Capability::ImageQuery],
+            Op::ConvertPtrToU => &[Capability::Addresses],
+            Op::SatConvertSToU => &[Capability::Kernel],
+            Op::SatConvertUToS => &[Capability::Kernel],
+            Op::ConvertUToPtr => &[Capability::Addresses],
+            Op::PtrCastToGeneric => &[Capability::Kernel],
+            Op::GenericCastToPtr => &[Capability::Kernel],
+            Op::GenericCastToPtrExplicit => &[Capability::Kernel],
+            Op::MatrixTimesScalar => &[Capability::Matrix],
+            Op::VectorTimesMatrix => &[Capability::Matrix],
+            Op::MatrixTimesVector => &[Capability::Matrix],
+            Op::MatrixTimesMatrix => &[Capability::Matrix],
+            Op::OuterProduct => &[Capability::Matrix],
+            Op::IsFinite => &[Capability::Kernel],
+            Op::IsNormal => &[Capability::Kernel],
+            Op::SignBitSet => &[Capability::Kernel],
+            Op::LessOrGreater => &[Capability::Kernel],
+            Op::Ordered => &[Capability::Kernel],
+            Op::Unordered => &[Capability::Kernel],
+            Op::BitFieldInsert => &[Capability::Shader],
+            Op::BitFieldSExtract => &[Capability::Shader],
+            Op::BitFieldUExtract => &[Capability::Shader],
+            Op::BitReverse => &[Capability::Shader],
+            Op::DPdx => &[Capability::Shader],
+            Op::DPdy => &[Capability::Shader],
+            Op::Fwidth => &[Capability::Shader],
+            Op::DPdxFine => &[Capability::DerivativeControl],
+            Op::DPdyFine => &[Capability::DerivativeControl],
+            Op::FwidthFine => &[Capability::DerivativeControl],
+            Op::DPdxCoarse => &[Capability::DerivativeControl],
+            Op::DPdyCoarse => &[Capability::DerivativeControl],
+            Op::FwidthCoarse => &[Capability::DerivativeControl],
+            Op::EmitVertex => &[Capability::Geometry],
+            Op::EndPrimitive => &[Capability::Geometry],
+            Op::EmitStreamVertex => &[Capability::GeometryStreams],
+            Op::EndStreamPrimitive => &[Capability::GeometryStreams],
+            Op::AtomicCompareExchangeWeak => &[Capability::Kernel],
+            Op::Kill => &[Capability::Shader],
+            Op::LifetimeStart => &[Capability::Kernel],
+            Op::LifetimeStop => &[Capability::Kernel],
+            Op::GroupAsyncCopy => &[Capability::Kernel],
+            Op::GroupWaitEvents => &[Capability::Kernel],
+            Op::GroupAll => &[Capability::Groups],
+            Op::GroupAny => &[Capability::Groups],
+            Op::GroupBroadcast => &[Capability::Groups],
+            Op::GroupIAdd => &[Capability::Groups],
+            Op::GroupFAdd => &[Capability::Groups],
+            Op::GroupFMin => &[Capability::Groups],
+            Op::GroupUMin => &[Capability::Groups],
+            Op::GroupSMin => &[Capability::Groups],
+            Op::GroupFMax => &[Capability::Groups],
+            Op::GroupUMax => &[Capability::Groups],
+            Op::GroupSMax => &[Capability::Groups],
+            Op::ReadPipe => &[Capability::Pipes],
+            Op::WritePipe => &[Capability::Pipes],
+            Op::ReservedReadPipe => &[Capability::Pipes],
+            Op::ReservedWritePipe => &[Capability::Pipes],
+            Op::ReserveReadPipePackets => &[Capability::Pipes],
+            Op::ReserveWritePipePackets => &[Capability::Pipes],
+            Op::CommitReadPipe => &[Capability::Pipes],
+            Op::CommitWritePipe => &[Capability::Pipes],
+            Op::IsValidReserveId => &[Capability::Pipes],
+            Op::GetNumPipePackets => &[Capability::Pipes],
+            Op::GetMaxPipePackets => &[Capability::Pipes],
+            Op::GroupReserveReadPipePackets => &[Capability::Pipes],
+            Op::GroupReserveWritePipePackets => &[Capability::Pipes],
+            Op::GroupCommitReadPipe => &[Capability::Pipes],
+            Op::GroupCommitWritePipe => &[Capability::Pipes],
+            Op::EnqueueMarker => &[Capability::DeviceEnqueue],
+            Op::EnqueueKernel => &[Capability::DeviceEnqueue],
+            Op::GetKernelNDrangeSubGroupCount => &[Capability::DeviceEnqueue],
+            Op::GetKernelNDrangeMaxSubGroupSize => &[Capability::DeviceEnqueue],
+            Op::GetKernelWorkGroupSize => &[Capability::DeviceEnqueue],
+            Op::GetKernelPreferredWorkGroupSizeMultiple => &[Capability::DeviceEnqueue],
+            Op::RetainEvent => &[Capability::DeviceEnqueue],
+            Op::ReleaseEvent => &[Capability::DeviceEnqueue],
+            Op::CreateUserEvent => &[Capability::DeviceEnqueue],
+            Op::IsValidEvent => &[Capability::DeviceEnqueue],
+            Op::SetUserEventStatus => &[Capability::DeviceEnqueue],
+            Op::CaptureEventProfilingInfo => &[Capability::DeviceEnqueue],
+            Op::GetDefaultQueue => &[Capability::DeviceEnqueue],
+            Op::BuildNDRange => &[Capability::DeviceEnqueue],
+            Op::ImageSparseSampleImplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleExplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleDrefImplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleDrefExplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleProjImplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleProjExplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleProjDrefImplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseSampleProjDrefExplicitLod => &[Capability::SparseResidency],
+            Op::ImageSparseFetch => &[Capability::SparseResidency],
+            Op::ImageSparseGather => &[Capability::SparseResidency],
+            Op::ImageSparseDrefGather => &[Capability::SparseResidency],
+            Op::ImageSparseTexelsResident => &[Capability::SparseResidency],
+            Op::AtomicFlagTestAndSet => &[Capability::Kernel],
+            Op::AtomicFlagClear => &[Capability::Kernel],
+            Op::ImageSparseRead => &[Capability::SparseResidency],
+            Op::SizeOf => &[Capability::Addresses],
+            Op::TypePipeStorage => &[Capability::PipeStorage],
+            Op::ConstantPipeStorage => &[Capability::PipeStorage],
+            Op::CreatePipeFromPipeStorage => &[Capability::PipeStorage],
+            Op::GetKernelLocalSizeForSubgroupCount => &[Capability::SubgroupDispatch],
+            Op::GetKernelMaxNumSubgroups => &[Capability::SubgroupDispatch],
+            Op::TypeNamedBarrier => &[Capability::NamedBarrier],
+            Op::NamedBarrierInitialize => &[Capability::NamedBarrier],
+            Op::MemoryNamedBarrier => &[Capability::NamedBarrier],
+            Op::GroupNonUniformElect => &[Capability::GroupNonUniform],
+            Op::GroupNonUniformAll => &[Capability::GroupNonUniformVote],
+            Op::GroupNonUniformAny => &[Capability::GroupNonUniformVote],
+            Op::GroupNonUniformAllEqual => &[Capability::GroupNonUniformVote],
+            Op::GroupNonUniformBroadcast => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBroadcastFirst => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBallot => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformInverseBallot => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBallotBitExtract => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBallotBitCount => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBallotFindLSB => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformBallotFindMSB => &[Capability::GroupNonUniformBallot],
+            Op::GroupNonUniformShuffle => &[Capability::GroupNonUniformShuffle],
+            Op::GroupNonUniformShuffleXor => &[Capability::GroupNonUniformShuffle],
+            Op::GroupNonUniformShuffleUp => &[Capability::GroupNonUniformShuffleRelative],
+            Op::GroupNonUniformShuffleDown => &[Capability::GroupNonUniformShuffleRelative],
+            Op::GroupNonUniformIAdd => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformFAdd => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformIMul => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformFMul => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformSMin => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformUMin => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformFMin => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformSMax => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformUMax => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformFMax => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformBitwiseAnd => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformBitwiseOr => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformBitwiseXor => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformLogicalAnd => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformLogicalOr => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformLogicalXor => &[Capability::GroupNonUniformArithmetic, Capability::GroupNonUniformClustered],
+            Op::GroupNonUniformQuadBroadcast => &[Capability::GroupNonUniformQuad],
+            Op::GroupNonUniformQuadSwap => &[Capability::GroupNonUniformQuad],
+            Op::SubgroupBallotKHR => &[Capability::SubgroupBallotKHR],
+            Op::SubgroupFirstInvocationKHR => &[Capability::SubgroupBallotKHR],
+            Op::SubgroupAllKHR => &[Capability::SubgroupVoteKHR],
+            Op::SubgroupAnyKHR => &[Capability::SubgroupVoteKHR],
+            Op::SubgroupAllEqualKHR => &[Capability::SubgroupVoteKHR],
+            Op::SubgroupReadInvocationKHR => &[Capability::SubgroupBallotKHR],
+            Op::TraceRayKHR => &[Capability::RayTracingKHR],
+            Op::ExecuteCallableKHR => &[Capability::RayTracingKHR],
+            Op::ConvertUToAccelerationStructureKHR => &[Capability::RayQueryKHR, Capability::RayTracingKHR],
+            Op::IgnoreIntersectionKHR => &[Capability::RayTracingKHR],
+            Op::TerminateRayKHR => &[Capability::RayTracingKHR],
+            Op::TypeCooperativeMatrixKHR => &[Capability::CooperativeMatrixKHR],
+            Op::CooperativeMatrixLoadKHR => &[Capability::CooperativeMatrixKHR],
+            Op::CooperativeMatrixStoreKHR => &[Capability::CooperativeMatrixKHR],
+            Op::CooperativeMatrixMulAddKHR => &[Capability::CooperativeMatrixKHR],
+            Op::CooperativeMatrixLengthKHR => &[Capability::CooperativeMatrixKHR],
+            Op::TypeRayQueryKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryInitializeKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryTerminateKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGenerateIntersectionKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryConfirmIntersectionKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryProceedKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionTypeKHR => &[Capability::RayQueryKHR],
+            Op::GroupIAddNonUniformAMD => &[Capability::Groups],
+            Op::GroupFAddNonUniformAMD => &[Capability::Groups],
+            Op::GroupFMinNonUniformAMD => &[Capability::Groups],
+            Op::GroupUMinNonUniformAMD => &[Capability::Groups],
+            Op::GroupSMinNonUniformAMD => &[Capability::Groups],
+            Op::GroupFMaxNonUniformAMD => &[Capability::Groups],
+            Op::GroupUMaxNonUniformAMD => &[Capability::Groups],
+            Op::GroupSMaxNonUniformAMD => &[Capability::Groups],
+            Op::FragmentMaskFetchAMD => &[Capability::FragmentMaskAMD],
+            Op::FragmentFetchAMD => &[Capability::FragmentMaskAMD],
+            Op::EmitMeshTasksEXT => &[Capability::MeshShadingEXT],
+            Op::SetMeshOutputsEXT => &[Capability::MeshShadingEXT],
+            Op::TypeAccelerationStructureKHR => &[Capability::RayQueryKHR, Capability::RayTracingKHR],
+            Op::SubgroupShuffleINTEL => &[Capability::SubgroupShuffleINTEL],
+            Op::SubgroupShuffleDownINTEL => &[Capability::SubgroupShuffleINTEL],
+            Op::SubgroupShuffleUpINTEL => &[Capability::SubgroupShuffleINTEL],
+            Op::SubgroupShuffleXorINTEL => &[Capability::SubgroupShuffleINTEL],
+            Op::SubgroupBlockReadINTEL => &[Capability::SubgroupBufferBlockIOINTEL],
+            Op::SubgroupBlockWriteINTEL => &[Capability::SubgroupBufferBlockIOINTEL],
+            Op::SubgroupImageBlockReadINTEL => &[Capability::SubgroupImageBlockIOINTEL],
+            Op::SubgroupImageBlockWriteINTEL => &[Capability::SubgroupImageBlockIOINTEL],
+            Op::GroupNonUniformPartitionNV => &[Capability::GroupNonUniformPartitionedNV],
+            Op::RayQueryGetRayTMinKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetRayFlagsKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionTKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionInstanceCustomIndexKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionInstanceIdKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionGeometryIndexKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionPrimitiveIndexKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionBarycentricsKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionFrontFaceKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionCandidateAABBOpaqueKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionObjectRayDirectionKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionObjectRayOriginKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetWorldRayDirectionKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetWorldRayOriginKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionObjectToWorldKHR => &[Capability::RayQueryKHR],
+            Op::RayQueryGetIntersectionWorldToObjectKHR => &[Capability::RayQueryKHR],
+            _ => &[],
+        }
+    }
+
+    /// Returns the extensions enabling this instruction in SPIR-V versions before
+    /// `required_version`; any one of them is sufficient.
+    pub fn required_extensions(self) -> &'static [&'static str] {
+        match self {
+            Op::DecorateId => &["SPV_GOOGLE_hlsl_functionality1"],
+            Op::SubgroupBallotKHR => &["SPV_KHR_shader_ballot"],
+            Op::SubgroupFirstInvocationKHR => &["SPV_KHR_shader_ballot"],
+            Op::SubgroupAllKHR => &["SPV_KHR_subgroup_vote"],
+            Op::SubgroupAnyKHR => &["SPV_KHR_subgroup_vote"],
+            Op::SubgroupAllEqualKHR => &["SPV_KHR_subgroup_vote"],
+            Op::SubgroupReadInvocationKHR => &["SPV_KHR_shader_ballot"],
+            Op::TraceRayKHR => &["SPV_KHR_ray_tracing"],
+            Op::ExecuteCallableKHR => &["SPV_KHR_ray_tracing"],
+            Op::ConvertUToAccelerationStructureKHR => &["SPV_KHR_ray_query", "SPV_KHR_ray_tracing"],
+            Op::IgnoreIntersectionKHR => &["SPV_KHR_ray_tracing"],
+            Op::TerminateRayKHR => &["SPV_KHR_ray_tracing"],
+            Op::TypeCooperativeMatrixKHR => &["SPV_KHR_cooperative_matrix"],
+            Op::CooperativeMatrixLoadKHR => &["SPV_KHR_cooperative_matrix"],
+            Op::CooperativeMatrixStoreKHR => &["SPV_KHR_cooperative_matrix"],
+            Op::CooperativeMatrixMulAddKHR => &["SPV_KHR_cooperative_matrix"],
+            Op::CooperativeMatrixLengthKHR => &["SPV_KHR_cooperative_matrix"],
+            Op::TypeRayQueryKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryInitializeKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryTerminateKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGenerateIntersectionKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryConfirmIntersectionKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryProceedKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionTypeKHR => &["SPV_KHR_ray_query"],
+            Op::GroupIAddNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupFAddNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupFMinNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupUMinNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupSMinNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupFMaxNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupUMaxNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::GroupSMaxNonUniformAMD => &["SPV_AMD_shader_ballot"],
+            Op::FragmentMaskFetchAMD => &["SPV_AMD_shader_fragment_mask"],
+            Op::FragmentFetchAMD => &["SPV_AMD_shader_fragment_mask"],
+            Op::EmitMeshTasksEXT => &["SPV_EXT_mesh_shader"],
+            Op::SetMeshOutputsEXT => &["SPV_EXT_mesh_shader"],
+            Op::TypeAccelerationStructureKHR => &["SPV_KHR_ray_query", "SPV_KHR_ray_tracing"],
+            Op::DecorateStringGOOGLE => &["SPV_GOOGLE_decorate_string", "SPV_GOOGLE_hlsl_functionality1"],
+            Op::MemberDecorateStringGOOGLE => &["SPV_GOOGLE_decorate_string", "SPV_GOOGLE_hlsl_functionality1"],
+            Op::GroupNonUniformPartitionNV => &["SPV_NV_shader_subgroup_partitioned"],
+            Op::RayQueryGetRayTMinKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetRayFlagsKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionTKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionInstanceCustomIndexKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionInstanceIdKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionGeometryIndexKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionPrimitiveIndexKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionBarycentricsKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionFrontFaceKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionCandidateAABBOpaqueKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionObjectRayDirectionKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionObjectRayOriginKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetWorldRayDirectionKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetWorldRayOriginKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionObjectToWorldKHR => &["SPV_KHR_ray_query"],
+            Op::RayQueryGetIntersectionWorldToObjectKHR => &["SPV_KHR_ray_query"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the SPIR-V version, as (major, minor), since which this instruction
+    /// is available without extensions, or `None` if it is only available
+    /// through extensions.
+    pub fn required_version(self) -> Option<(u8, u8)> {
+        match self {
+            Op::ImageSparseSampleProjImplicitLod => None,
+            Op::ImageSparseSampleProjExplicitLod => None,
+            Op::ImageSparseSampleProjDrefImplicitLod => None,
+            Op::ImageSparseSampleProjDrefExplicitLod => None,
+            Op::SizeOf => Some((1, 1)),
+            Op::TypePipeStorage => Some((1, 1)),
+            Op::ConstantPipeStorage => Some((1, 1)),
+            Op::CreatePipeFromPipeStorage => Some((1, 1)),
+            Op::GetKernelLocalSizeForSubgroupCount => Some((1, 1)),
+            Op::GetKernelMaxNumSubgroups => Some((1, 1)),
+            Op::TypeNamedBarrier => Some((1, 1)),
+            Op::NamedBarrierInitialize => Some((1, 1)),
+            Op::MemoryNamedBarrier => Some((1, 1)),
+            Op::ModuleProcessed => Some((1, 1)),
+            Op::ExecutionModeId => Some((1, 2)),
+            Op::DecorateId => Some((1, 2)),
+            Op::GroupNonUniformElect => Some((1, 3)),
+            Op::GroupNonUniformAll => Some((1, 3)),
+            Op::GroupNonUniformAny => Some((1, 3)),
+            Op::GroupNonUniformAllEqual => Some((1, 3)),
+            Op::GroupNonUniformBroadcast => Some((1, 3)),
+            Op::GroupNonUniformBroadcastFirst => Some((1, 3)),
+            Op::GroupNonUniformBallot => Some((1, 3)),
+            Op::GroupNonUniformInverseBallot => Some((1, 3)),
+            Op::GroupNonUniformBallotBitExtract => Some((1, 3)),
+            Op::GroupNonUniformBallotBitCount => Some((1, 3)),
+            Op::GroupNonUniformBallotFindLSB => Some((1, 3)),
+            Op::GroupNonUniformBallotFindMSB => Some((1, 3)),
+            Op::GroupNonUniformShuffle => Some((1, 3)),
+            Op::GroupNonUniformShuffleXor => Some((1, 3)),
+            Op::GroupNonUniformShuffleUp => Some((1, 3)),
+            Op::GroupNonUniformShuffleDown => Some((1, 3)),
+            Op::GroupNonUniformIAdd => Some((1, 3)),
+            Op::GroupNonUniformFAdd => Some((1, 3)),
+            Op::GroupNonUniformIMul => Some((1, 3)),
+            Op::GroupNonUniformFMul => Some((1, 3)),
+            Op::GroupNonUniformSMin => Some((1, 3)),
+            Op::GroupNonUniformUMin => Some((1, 3)),
+            Op::GroupNonUniformFMin => Some((1, 3)),
+            Op::GroupNonUniformSMax => Some((1, 3)),
+            Op::GroupNonUniformUMax => Some((1, 3)),
+            Op::GroupNonUniformFMax => Some((1, 3)),
+            Op::GroupNonUniformBitwiseAnd => Some((1, 3)),
+            Op::GroupNonUniformBitwiseOr => Some((1, 3)),
+            Op::GroupNonUniformBitwiseXor => Some((1, 3)),
+            Op::GroupNonUniformLogicalAnd => Some((1, 3)),
+            Op::GroupNonUniformLogicalOr => Some((1, 3)),
+            Op::GroupNonUniformLogicalXor => Some((1, 3)),
+            Op::GroupNonUniformQuadBroadcast => Some((1, 3)),
+            Op::GroupNonUniformQuadSwap => Some((1, 3)),
+            Op::SubgroupBallotKHR => None,
+            Op::SubgroupFirstInvocationKHR => None,
+            Op::SubgroupAllKHR => None,
+            Op::SubgroupAnyKHR => None,
+            Op::SubgroupAllEqualKHR => None,
+            Op::SubgroupReadInvocationKHR => None,
+            Op::TraceRayKHR => None,
+            Op::ExecuteCallableKHR => None,
+            Op::ConvertUToAccelerationStructureKHR => None,
+            Op::IgnoreIntersectionKHR => None,
+            Op::TerminateRayKHR => None,
+            Op::TypeCooperativeMatrixKHR => None,
+            Op::CooperativeMatrixLoadKHR => None,
+            Op::CooperativeMatrixStoreKHR => None,
+            Op::CooperativeMatrixMulAddKHR => None,
+            Op::CooperativeMatrixLengthKHR => None,
+            Op::TypeRayQueryKHR => None,
+            Op::RayQueryInitializeKHR => None,
+            Op::RayQueryTerminateKHR => None,
+            Op::RayQueryGenerateIntersectionKHR => None,
+            Op::RayQueryConfirmIntersectionKHR => None,
+            Op::RayQueryProceedKHR => None,
+            Op::RayQueryGetIntersectionTypeKHR => None,
+            Op::GroupIAddNonUniformAMD => None,
+            Op::GroupFAddNonUniformAMD => None,
+            Op::GroupFMinNonUniformAMD => None,
+            Op::GroupUMinNonUniformAMD => None,
+            Op::GroupSMinNonUniformAMD => None,
+            Op::GroupFMaxNonUniformAMD => None,
+            Op::GroupUMaxNonUniformAMD => None,
+            Op::GroupSMaxNonUniformAMD => None,
+            Op::FragmentMaskFetchAMD => None,
+            Op::FragmentFetchAMD => None,
+            Op::EmitMeshTasksEXT => None,
+            Op::SetMeshOutputsEXT => None,
+            Op::TypeAccelerationStructureKHR => None,
+            Op::SubgroupShuffleINTEL => None,
+            Op::SubgroupShuffleDownINTEL => None,
+            Op::SubgroupShuffleUpINTEL => None,
+            Op::SubgroupShuffleXorINTEL => None,
+            Op::SubgroupBlockReadINTEL => None,
+            Op::SubgroupBlockWriteINTEL => None,
+            Op::SubgroupImageBlockReadINTEL => None,
+            Op::SubgroupImageBlockWriteINTEL => None,
+            Op::DecorateStringGOOGLE => None,
+            Op::MemberDecorateStringGOOGLE => None,
+            Op::GroupNonUniformPartitionNV => None,
+            Op::RayQueryGetRayTMinKHR => None,
+            Op::RayQueryGetRayFlagsKHR => None,
+            Op::RayQueryGetIntersectionTKHR => None,
+            Op::RayQueryGetIntersectionInstanceCustomIndexKHR => None,
+            Op::RayQueryGetIntersectionInstanceIdKHR => None,
+            Op::RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR => None,
+            Op::RayQueryGetIntersectionGeometryIndexKHR => None,
+            Op::RayQueryGetIntersectionPrimitiveIndexKHR => None,
+            Op::RayQueryGetIntersectionBarycentricsKHR => None,
+            Op::RayQueryGetIntersectionFrontFaceKHR => None,
+            Op::RayQueryGetIntersectionCandidateAABBOpaqueKHR => None,
+            Op::RayQueryGetIntersectionObjectRayDirectionKHR => None,
+            Op::RayQueryGetIntersectionObjectRayOriginKHR => None,
+            Op::RayQueryGetWorldRayDirectionKHR => None,
+            Op::RayQueryGetWorldRayOriginKHR => None,
+            Op::RayQueryGetIntersectionObjectToWorldKHR => None,
+            Op::RayQueryGetIntersectionWorldToObjectKHR => None,
+            _ => Some((1, 0)),
+        }
+    }
 }
 
 /// [GLSL.std.450](https://www.khronos.org/registry/spir-v/specs/unified1/GLSL.std.450.html) extended instruction opcode
@@ -1328,3 +5032,365 @@ pub enum CLOp {
     printf = 184,
     prefetch = 185,
 }
+
+/// [NonSemantic.Shader.DebugInfo.100](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html) extended instruction opcode
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugInfoOp {
+    DebugInfoNone = 0,
+    DebugCompilationUnit = 1,
+    DebugTypeBasic = 2,
+    DebugTypePointer = 3,
+    DebugTypeQualifier = 4,
+    DebugTypeArray = 5,
+    DebugTypeVector = 6,
+    DebugTypedef = 7,
+    DebugTypeFunction = 8,
+    DebugTypeEnum = 9,
+    DebugTypeComposite = 10,
+    DebugTypeMember = 11,
+    DebugTypeInheritance = 12,
+    DebugTypePtrToMember = 13,
+    DebugTypeTemplate = 14,
+    DebugTypeTemplateParameter = 15,
+    DebugTypeTemplateTemplateParameter = 16,
+    DebugTypeTemplateParameterPack = 17,
+    DebugGlobalVariable = 18,
+    DebugFunctionDeclaration = 19,
+    DebugFunction = 20,
+    DebugLexicalBlock = 21,
+    DebugLexicalBlockDiscriminator = 22,
+    DebugScope = 23,
+    DebugNoScope = 24,
+    DebugInlinedAt = 25,
+    DebugLocalVariable = 26,
+    DebugInlinedVariable = 27,
+    DebugDeclare = 28,
+    DebugValue = 29,
+    DebugOperation = 30,
+    DebugExpression = 31,
+    DebugMacroDef = 32,
+    DebugMacroUndef = 33,
+    DebugImportedEntity = 34,
+    DebugSource = 35,
+    DebugFunctionDefinition = 101,
+    DebugSourceContinued = 102,
+    DebugLine = 103,
+    DebugNoLine = 104,
+    DebugBuildIdentifier = 105,
+    DebugStoragePath = 106,
+    DebugEntryPoint = 107,
+    DebugTypeMatrix = 108,
+}
+
+bitflags!{
+    /// SPIR-V operand kind: [DebugInfoFlags](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+    pub struct DebugInfoFlags : u32 {
+        const NONE = 0x0000;
+        const FLAG_IS_PROTECTED = 0x0001;
+        const FLAG_IS_PRIVATE = 0x0002;
+        const FLAG_IS_PUBLIC = 0x0003;
+        const FLAG_IS_LOCAL = 0x0004;
+        const FLAG_IS_DEFINITION = 0x0008;
+        const FLAG_FWD_DECL = 0x0010;
+        const FLAG_ARTIFICIAL = 0x0020;
+        const FLAG_EXPLICIT = 0x0040;
+        const FLAG_PROTOTYPED = 0x0080;
+        const FLAG_OBJECT_POINTER = 0x0100;
+        const FLAG_STATIC_MEMBER = 0x0200;
+        const FLAG_INDIRECT_VARIABLE = 0x0400;
+        const FLAG_LVALUE_REFERENCE = 0x0800;
+        const FLAG_RVALUE_REFERENCE = 0x1000;
+        const FLAG_IS_OPTIMIZED = 0x2000;
+        const FLAG_IS_ENUM_CLASS = 0x4000;
+        const FLAG_TYPE_PASS_BY_VALUE = 0x8000;
+        const FLAG_TYPE_PASS_BY_REFERENCE = 0x10000;
+        const FLAG_UNKNOWN_PHYSICAL_LAYOUT = 0x20000;
+    }
+}
+
+impl fmt::Display for DebugInfoFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("FlagIsProtected", self.contains(DebugInfoFlags::FLAG_IS_PROTECTED)),
+            ("FlagIsPrivate", self.contains(DebugInfoFlags::FLAG_IS_PRIVATE)),
+            ("FlagIsPublic", self.contains(DebugInfoFlags::FLAG_IS_PUBLIC)),
+            ("FlagIsLocal", self.contains(DebugInfoFlags::FLAG_IS_LOCAL)),
+            ("FlagIsDefinition", self.contains(DebugInfoFlags::FLAG_IS_DEFINITION)),
+            ("FlagFwdDecl", self.contains(DebugInfoFlags::FLAG_FWD_DECL)),
+            ("FlagArtificial", self.contains(DebugInfoFlags::FLAG_ARTIFICIAL)),
+            ("FlagExplicit", self.contains(DebugInfoFlags::FLAG_EXPLICIT)),
+            ("FlagPrototyped", self.contains(DebugInfoFlags::FLAG_PROTOTYPED)),
+            ("FlagObjectPointer", self.contains(DebugInfoFlags::FLAG_OBJECT_POINTER)),
+            ("FlagStaticMember", self.contains(DebugInfoFlags::FLAG_STATIC_MEMBER)),
+            ("FlagIndirectVariable", self.contains(DebugInfoFlags::FLAG_INDIRECT_VARIABLE)),
+            ("FlagLValueReference", self.contains(DebugInfoFlags::FLAG_LVALUE_REFERENCE)),
+            ("FlagRValueReference", self.contains(DebugInfoFlags::FLAG_RVALUE_REFERENCE)),
+            ("FlagIsOptimized", self.contains(DebugInfoFlags::FLAG_IS_OPTIMIZED)),
+            ("FlagIsEnumClass", self.contains(DebugInfoFlags::FLAG_IS_ENUM_CLASS)),
+            ("FlagTypePassByValue", self.contains(DebugInfoFlags::FLAG_TYPE_PASS_BY_VALUE)),
+            ("FlagTypePassByReference", self.contains(DebugInfoFlags::FLAG_TYPE_PASS_BY_REFERENCE)),
+            ("FlagUnknownPhysicalLayout", self.contains(DebugInfoFlags::FLAG_UNKNOWN_PHYSICAL_LAYOUT)),
+        ])
+    }
+}
+
+impl str::FromStr for DebugInfoFlags {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = DebugInfoFlags::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "None" => DebugInfoFlags::NONE,
+                "FlagIsProtected" => DebugInfoFlags::FLAG_IS_PROTECTED,
+                "FlagIsPrivate" => DebugInfoFlags::FLAG_IS_PRIVATE,
+                "FlagIsPublic" => DebugInfoFlags::FLAG_IS_PUBLIC,
+                "FlagIsLocal" => DebugInfoFlags::FLAG_IS_LOCAL,
+                "FlagIsDefinition" => DebugInfoFlags::FLAG_IS_DEFINITION,
+                "FlagFwdDecl" => DebugInfoFlags::FLAG_FWD_DECL,
+                "FlagArtificial" => DebugInfoFlags::FLAG_ARTIFICIAL,
+                "FlagExplicit" => DebugInfoFlags::FLAG_EXPLICIT,
+                "FlagPrototyped" => DebugInfoFlags::FLAG_PROTOTYPED,
+                "FlagObjectPointer" => DebugInfoFlags::FLAG_OBJECT_POINTER,
+                "FlagStaticMember" => DebugInfoFlags::FLAG_STATIC_MEMBER,
+                "FlagIndirectVariable" => DebugInfoFlags::FLAG_INDIRECT_VARIABLE,
+                "FlagLValueReference" => DebugInfoFlags::FLAG_LVALUE_REFERENCE,
+                "FlagRValueReference" => DebugInfoFlags::FLAG_RVALUE_REFERENCE,
+                "FlagIsOptimized" => DebugInfoFlags::FLAG_IS_OPTIMIZED,
+                "FlagIsEnumClass" => DebugInfoFlags::FLAG_IS_ENUM_CLASS,
+                "FlagTypePassByValue" => DebugInfoFlags::FLAG_TYPE_PASS_BY_VALUE,
+                "FlagTypePassByReference" => DebugInfoFlags::FLAG_TYPE_PASS_BY_REFERENCE,
+                "FlagUnknownPhysicalLayout" => DebugInfoFlags::FLAG_UNKNOWN_PHYSICAL_LAYOUT,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
+bitflags!{
+    /// SPIR-V operand kind: [BuildIdentifierFlags](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+    pub struct BuildIdentifierFlags : u32 {
+        const IDENTIFIER_POSSIBLE_DUPLICATES = 0x01;
+    }
+}
+
+impl fmt::Display for BuildIdentifierFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, &[
+            ("IdentifierPossibleDuplicates", self.contains(BuildIdentifierFlags::IDENTIFIER_POSSIBLE_DUPLICATES)),
+        ])
+    }
+}
+
+impl str::FromStr for BuildIdentifierFlags {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = BuildIdentifierFlags::empty();
+        for name in s.split('|') {
+            flags |= match name.trim() {
+                "IdentifierPossibleDuplicates" => BuildIdentifierFlags::IDENTIFIER_POSSIBLE_DUPLICATES,
+                _ => return Err(ParseEnumError),
+            };
+        }
+        Ok(flags)
+    }
+}
+
+/// SPIR-V operand kind: [DebugBaseTypeAttributeEncoding](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugBaseTypeAttributeEncoding {
+    Unspecified = 0,
+    Address = 1,
+    Boolean = 2,
+    Float = 3,
+    Signed = 4,
+    SignedChar = 5,
+    Unsigned = 6,
+    UnsignedChar = 7,
+}
+
+impl fmt::Display for DebugBaseTypeAttributeEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DebugBaseTypeAttributeEncoding::Unspecified => "Unspecified",
+            DebugBaseTypeAttributeEncoding::Address => "Address",
+            DebugBaseTypeAttributeEncoding::Boolean => "Boolean",
+            DebugBaseTypeAttributeEncoding::Float => "Float",
+            DebugBaseTypeAttributeEncoding::Signed => "Signed",
+            DebugBaseTypeAttributeEncoding::SignedChar => "SignedChar",
+            DebugBaseTypeAttributeEncoding::Unsigned => "Unsigned",
+            DebugBaseTypeAttributeEncoding::UnsignedChar => "UnsignedChar",
+        })
+    }
+}
+
+impl str::FromStr for DebugBaseTypeAttributeEncoding {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Unspecified" => Ok(DebugBaseTypeAttributeEncoding::Unspecified),
+            "Address" => Ok(DebugBaseTypeAttributeEncoding::Address),
+            "Boolean" => Ok(DebugBaseTypeAttributeEncoding::Boolean),
+            "Float" => Ok(DebugBaseTypeAttributeEncoding::Float),
+            "Signed" => Ok(DebugBaseTypeAttributeEncoding::Signed),
+            "SignedChar" => Ok(DebugBaseTypeAttributeEncoding::SignedChar),
+            "Unsigned" => Ok(DebugBaseTypeAttributeEncoding::Unsigned),
+            "UnsignedChar" => Ok(DebugBaseTypeAttributeEncoding::UnsignedChar),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+/// SPIR-V operand kind: [DebugCompositeType](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugCompositeType {
+    Class = 0,
+    Structure = 1,
+    Union = 2,
+}
+
+impl fmt::Display for DebugCompositeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DebugCompositeType::Class => "Class",
+            DebugCompositeType::Structure => "Structure",
+            DebugCompositeType::Union => "Union",
+        })
+    }
+}
+
+impl str::FromStr for DebugCompositeType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Class" => Ok(DebugCompositeType::Class),
+            "Structure" => Ok(DebugCompositeType::Structure),
+            "Union" => Ok(DebugCompositeType::Union),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+/// SPIR-V operand kind: [DebugTypeQualifier](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugTypeQualifier {
+    ConstType = 0,
+    VolatileType = 1,
+    RestrictType = 2,
+    AtomicType = 3,
+}
+
+impl fmt::Display for DebugTypeQualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DebugTypeQualifier::ConstType => "ConstType",
+            DebugTypeQualifier::VolatileType => "VolatileType",
+            DebugTypeQualifier::RestrictType => "RestrictType",
+            DebugTypeQualifier::AtomicType => "AtomicType",
+        })
+    }
+}
+
+impl str::FromStr for DebugTypeQualifier {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ConstType" => Ok(DebugTypeQualifier::ConstType),
+            "VolatileType" => Ok(DebugTypeQualifier::VolatileType),
+            "RestrictType" => Ok(DebugTypeQualifier::RestrictType),
+            "AtomicType" => Ok(DebugTypeQualifier::AtomicType),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+/// SPIR-V operand kind: [DebugOperation](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugOperation {
+    Deref = 0,
+    Plus = 1,
+    Minus = 2,
+    PlusUconst = 3,
+    BitPiece = 4,
+    Swap = 5,
+    Xderef = 6,
+    StackValue = 7,
+    Constu = 8,
+    Fragment = 9,
+}
+
+impl fmt::Display for DebugOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DebugOperation::Deref => "Deref",
+            DebugOperation::Plus => "Plus",
+            DebugOperation::Minus => "Minus",
+            DebugOperation::PlusUconst => "PlusUconst",
+            DebugOperation::BitPiece => "BitPiece",
+            DebugOperation::Swap => "Swap",
+            DebugOperation::Xderef => "Xderef",
+            DebugOperation::StackValue => "StackValue",
+            DebugOperation::Constu => "Constu",
+            DebugOperation::Fragment => "Fragment",
+        })
+    }
+}
+
+impl str::FromStr for DebugOperation {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Deref" => Ok(DebugOperation::Deref),
+            "Plus" => Ok(DebugOperation::Plus),
+            "Minus" => Ok(DebugOperation::Minus),
+            "PlusUconst" => Ok(DebugOperation::PlusUconst),
+            "BitPiece" => Ok(DebugOperation::BitPiece),
+            "Swap" => Ok(DebugOperation::Swap),
+            "Xderef" => Ok(DebugOperation::Xderef),
+            "StackValue" => Ok(DebugOperation::StackValue),
+            "Constu" => Ok(DebugOperation::Constu),
+            "Fragment" => Ok(DebugOperation::Fragment),
+            _ => Err(ParseEnumError),
+        }
+    }
+}
+
+/// SPIR-V operand kind: [DebugImportedEntity](https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum DebugImportedEntity {
+    ImportedModule = 0,
+    ImportedDeclaration = 1,
+}
+
+impl fmt::Display for DebugImportedEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DebugImportedEntity::ImportedModule => "ImportedModule",
+            DebugImportedEntity::ImportedDeclaration => "ImportedDeclaration",
+        })
+    }
+}
+
+impl str::FromStr for DebugImportedEntity {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ImportedModule" => Ok(DebugImportedEntity::ImportedModule),
+            "ImportedDeclaration" => Ok(DebugImportedEntity::ImportedDeclaration),
+            _ => Err(ParseEnumError),
+        }
+    }
+}